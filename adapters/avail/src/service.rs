@@ -10,7 +10,7 @@ use avail_subxt::{api, AvailConfig};
 use pin_project::pin_project;
 use reqwest::StatusCode;
 use sov_rollup_interface::da::DaSpec;
-use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::services::da::{DaService, Fee};
 use sp_core::crypto::Pair as PairTrait;
 use sp_keyring::sr25519::sr25519::Pair;
 use subxt::tx::PairSigner;
@@ -313,4 +313,22 @@ impl DaService for DaProvider {
     async fn get_aggregated_proofs_at(&self, _height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
         unimplemented!()
     }
+
+    async fn estimate_fee(&self, blob_size: usize) -> Result<Fee, Self::Error> {
+        let data_transfer = api::tx()
+            .data_availability()
+            .submit_data(BoundedVec(vec![0; blob_size]));
+
+        let extrinsic_params = AvailExtrinsicParams::new_with_app_id(self.app_id.into());
+
+        let fee = self
+            .node_client
+            .tx()
+            .create_signed(&data_transfer, &self.signer, extrinsic_params)
+            .await?
+            .partial_fee_estimate()
+            .await?;
+
+        Ok(Fee(fee))
+    }
 }
@@ -0,0 +1,163 @@
+//! Builders of the transactions writing envelopes to Bitcoin. An envelope is written in two transactions:
+//! the commit transaction pays to a taproot output committing to the envelope script, and the reveal
+//! transaction spends it through the script, revealing the envelope in its witness.
+
+use bitcoin::absolute::LockTime;
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::{All, KeyPair, Message, Secp256k1};
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use bitcoin::taproot::{LeafVersion, TapLeafHash, TaprootBuilder, TaprootSpendInfo};
+use bitcoin::{OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness};
+
+use super::envelope::build_envelope_script;
+
+/// The value of the output of the reveal transactions, in satoshis, which is above the dust limit.
+pub const REVEAL_OUTPUT_AMOUNT: u64 = 546;
+
+/// Returns the fee of the reveal transaction of an envelope with `marker` and `body`, paying to
+/// `recipient`, at `fee_rate` satoshis per virtual byte.
+pub fn estimate_reveal_fee(
+    secp: &Secp256k1<All>,
+    key_pair: &KeyPair,
+    marker: &[u8],
+    body: &[u8],
+    recipient: ScriptBuf,
+    fee_rate: u64,
+) -> anyhow::Result<u64> {
+    let script = build_envelope_script(&key_pair.x_only_public_key().0, marker, 0, body)?;
+    let commit_output = TxOut {
+        value: REVEAL_OUTPUT_AMOUNT,
+        script_pubkey: commit_script_pubkey(secp, key_pair, &script)?,
+    };
+    let reveal_tx = build_reveal_tx(
+        secp,
+        key_pair,
+        &script,
+        OutPoint::null(),
+        &commit_output,
+        recipient,
+    )?;
+    Ok(reveal_tx.vsize() as u64 * fee_rate)
+}
+
+/// Grinds the nonce of the envelope with `marker` and `body` until the `wtxid` of its reveal transaction
+/// starts with `reveal_wtxid_prefix`, and returns the commit and reveal transactions.
+///
+/// The first output of `commit_tx` must be the commit output, its script is replaced for each nonce.
+/// The inputs of `commit_tx` must spend segwit outputs, so that signing it doesn't change its `txid`,
+/// which is spent by the reveal transaction.
+pub fn build_commit_reveal_txs(
+    secp: &Secp256k1<All>,
+    key_pair: &KeyPair,
+    marker: &[u8],
+    body: &[u8],
+    mut commit_tx: Transaction,
+    recipient: ScriptBuf,
+    reveal_wtxid_prefix: &[u8],
+) -> anyhow::Result<(Transaction, Transaction)> {
+    anyhow::ensure!(
+        !commit_tx.output.is_empty(),
+        "The commit transaction has no output"
+    );
+    let sender = key_pair.x_only_public_key().0;
+
+    for nonce in 0..=u32::MAX {
+        let script = build_envelope_script(&sender, marker, nonce, body)?;
+        commit_tx.output[0].script_pubkey = commit_script_pubkey(secp, key_pair, &script)?;
+
+        let reveal_tx = build_reveal_tx(
+            secp,
+            key_pair,
+            &script,
+            OutPoint::new(commit_tx.txid(), 0),
+            &commit_tx.output[0],
+            recipient.clone(),
+        )?;
+        if reveal_tx
+            .wtxid()
+            .as_byte_array()
+            .starts_with(reveal_wtxid_prefix)
+        {
+            return Ok((commit_tx, reveal_tx));
+        }
+    }
+    anyhow::bail!(
+        "No nonce gives a reveal transaction with the wtxid prefix {}",
+        hex::encode(reveal_wtxid_prefix)
+    )
+}
+
+/// Builds the reveal transaction spending `commit_output` at `commit_outpoint` through `script`,
+/// and paying [`REVEAL_OUTPUT_AMOUNT`] to `recipient`.
+pub fn build_reveal_tx(
+    secp: &Secp256k1<All>,
+    key_pair: &KeyPair,
+    script: &ScriptBuf,
+    commit_outpoint: OutPoint,
+    commit_output: &TxOut,
+    recipient: ScriptBuf,
+) -> anyhow::Result<Transaction> {
+    let mut reveal_tx = Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: commit_outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: REVEAL_OUTPUT_AMOUNT,
+            script_pubkey: recipient,
+        }],
+    };
+
+    let leaf_hash = TapLeafHash::from_script(script, LeafVersion::TapScript);
+    let sighash = SighashCache::new(&reveal_tx).taproot_script_spend_signature_hash(
+        0,
+        &Prevouts::All(&[commit_output]),
+        leaf_hash,
+        TapSighashType::Default,
+    )?;
+    let message = Message::from_slice(sighash.as_byte_array())?;
+    let signature = bitcoin::taproot::Signature {
+        sig: secp.sign_schnorr_no_aux_rand(&message, key_pair),
+        hash_ty: TapSighashType::Default,
+    };
+    let control_block = spend_info(secp, key_pair, script)?
+        .control_block(&(script.clone(), LeafVersion::TapScript))
+        .expect("The script is a leaf of the tree");
+
+    let mut witness = Witness::new();
+    witness.push(signature.to_vec());
+    witness.push(script.as_bytes());
+    witness.push(control_block.serialize());
+    reveal_tx.input[0].witness = witness;
+    Ok(reveal_tx)
+}
+
+/// Returns the script of the commit output for `script`. Its internal key is the key of the sender,
+/// so that the sender can also spend it without revealing the envelope.
+fn commit_script_pubkey(
+    secp: &Secp256k1<All>,
+    key_pair: &KeyPair,
+    script: &ScriptBuf,
+) -> anyhow::Result<ScriptBuf> {
+    let spend_info = spend_info(secp, key_pair, script)?;
+    Ok(ScriptBuf::new_v1_p2tr(
+        secp,
+        spend_info.internal_key(),
+        spend_info.merkle_root(),
+    ))
+}
+
+fn spend_info(
+    secp: &Secp256k1<All>,
+    key_pair: &KeyPair,
+    script: &ScriptBuf,
+) -> anyhow::Result<TaprootSpendInfo> {
+    TaprootBuilder::new()
+        .add_leaf(0, script.clone())?
+        .finalize(secp, key_pair.x_only_public_key().0)
+        .map_err(|_| anyhow::anyhow!("The taproot tree of the envelope can't be finalized"))
+}
@@ -0,0 +1,185 @@
+//! Rollup data is written to Bitcoin in an envelope: a branch of a tapscript which is never executed,
+//! revealed when the script is spent. The envelope of a blob looks like:
+//!
+//! ```text
+//! <sender x-only public key> OP_CHECKSIG
+//! OP_FALSE
+//! OP_IF
+//!   <marker>
+//!   <nonce: 4 bytes, little-endian>
+//!   <first chunk of the body> ... <last chunk of the body>
+//! OP_ENDIF
+//! ```
+//!
+//! Spending the script requires a signature of the sender, so the sender of a blob is authenticated
+//! by Bitcoin consensus. The marker tells rollups apart, and the kinds of data of a rollup, e.g.
+//! batches and proofs. The nonce is ground by the sender so that the `wtxid` of the transaction
+//! revealing the envelope starts with a prefix, which keeps the completeness proofs small.
+
+use bitcoin::blockdata::opcodes::all::{OP_CHECKSIG, OP_ENDIF, OP_IF};
+use bitcoin::blockdata::opcodes::OP_FALSE;
+use bitcoin::blockdata::script::{Builder, Instruction, PushBytes, Script, ScriptBuf};
+use bitcoin::secp256k1::XOnlyPublicKey;
+use bitcoin::Transaction;
+
+/// The maximum size of a push in a tapscript, in bytes. Bodies are split in chunks of this size.
+pub const MAX_PUSH_SIZE: usize = 520;
+
+/// The length of the nonce of an envelope, in bytes.
+pub const NONCE_LENGTH: usize = 4;
+
+/// The contents of an envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// The x-only public key of the sender, which signed the transaction revealing the envelope.
+    pub sender: [u8; 32],
+    /// The nonce ground by the sender.
+    pub nonce: u32,
+    /// The data of the envelope.
+    pub body: Vec<u8>,
+}
+
+/// Builds the tapscript holding an envelope with `marker`, `nonce` and `body`, which can only be spent
+/// by `sender`.
+pub fn build_envelope_script(
+    sender: &XOnlyPublicKey,
+    marker: &[u8],
+    nonce: u32,
+    body: &[u8],
+) -> anyhow::Result<ScriptBuf> {
+    anyhow::ensure!(
+        !marker.is_empty() && marker.len() <= MAX_PUSH_SIZE,
+        "The marker must have between 1 and {} bytes, it has {}",
+        MAX_PUSH_SIZE,
+        marker.len()
+    );
+
+    let mut builder = Builder::new()
+        .push_x_only_key(sender)
+        .push_opcode(OP_CHECKSIG)
+        .push_opcode(OP_FALSE)
+        .push_opcode(OP_IF)
+        .push_slice(push_bytes(marker))
+        .push_slice(nonce.to_le_bytes());
+    for chunk in body.chunks(MAX_PUSH_SIZE) {
+        builder = builder.push_slice(push_bytes(chunk));
+    }
+    Ok(builder.push_opcode(OP_ENDIF).into_script())
+}
+
+/// Parses the envelope held by `script`. Returns `None` if the script isn't an envelope with `marker`.
+pub fn parse_envelope(script: &Script, marker: &[u8]) -> Option<Envelope> {
+    let mut instructions = script.instructions();
+    let mut next = move || instructions.next()?.ok();
+
+    let sender = match next()? {
+        Instruction::PushBytes(bytes) => bytes.as_bytes().try_into().ok()?,
+        Instruction::Op(_) => return None,
+    };
+    if next()? != Instruction::Op(OP_CHECKSIG) {
+        return None;
+    }
+    match next()? {
+        Instruction::PushBytes(bytes) if bytes.is_empty() => {}
+        _ => return None,
+    }
+    if next()? != Instruction::Op(OP_IF) {
+        return None;
+    }
+    match next()? {
+        Instruction::PushBytes(bytes) if bytes.as_bytes() == marker => {}
+        _ => return None,
+    }
+    let nonce = match next()? {
+        Instruction::PushBytes(bytes) => u32::from_le_bytes(bytes.as_bytes().try_into().ok()?),
+        Instruction::Op(_) => return None,
+    };
+
+    let mut body = Vec::new();
+    loop {
+        match next()? {
+            Instruction::PushBytes(chunk) => body.extend_from_slice(chunk.as_bytes()),
+            Instruction::Op(OP_ENDIF) => break,
+            Instruction::Op(_) => return None,
+        }
+    }
+    // Nothing can follow the envelope
+    if next().is_some() {
+        return None;
+    }
+
+    Some(Envelope {
+        sender,
+        nonce,
+        body,
+    })
+}
+
+/// Returns the envelope with `marker` revealed by the first input of `tx`, if there is one.
+/// Envelopes revealed by the other inputs are ignored, so that a transaction holds at most one blob.
+pub fn extract_envelope(tx: &Transaction, marker: &[u8]) -> Option<Envelope> {
+    let script = tx.input.first()?.witness.tapscript()?;
+    parse_envelope(script, marker)
+}
+
+fn push_bytes(bytes: &[u8]) -> &PushBytes {
+    bytes
+        .try_into()
+        .expect("The pushes are shorter than the maximum push size")
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::secp256k1::{KeyPair, Secp256k1, SecretKey};
+
+    use super::*;
+
+    fn sender() -> XOnlyPublicKey {
+        let secp = Secp256k1::new();
+        let secret_key = SecretKey::from_slice(&[7; 32]).unwrap();
+        KeyPair::from_secret_key(&secp, &secret_key)
+            .x_only_public_key()
+            .0
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let sender = sender();
+        for body_len in [
+            0,
+            1,
+            MAX_PUSH_SIZE,
+            MAX_PUSH_SIZE + 1,
+            3 * MAX_PUSH_SIZE + 7,
+        ] {
+            let body: Vec<u8> = (0..body_len).map(|i| i as u8).collect();
+            let script = build_envelope_script(&sender, b"sov-btc", 42, &body).unwrap();
+
+            let envelope = parse_envelope(&script, b"sov-btc").unwrap();
+            assert_eq!(envelope.sender, sender.serialize());
+            assert_eq!(envelope.nonce, 42);
+            assert_eq!(envelope.body, body);
+        }
+    }
+
+    #[test]
+    fn test_envelope_with_other_marker() {
+        let script = build_envelope_script(&sender(), b"sov-btc", 0, &[1, 2, 3]).unwrap();
+        assert!(parse_envelope(&script, b"sov-btc-proof").is_none());
+        assert!(parse_envelope(&script, b"sov").is_none());
+    }
+
+    #[test]
+    fn test_invalid_marker() {
+        assert!(build_envelope_script(&sender(), &[], 0, &[1]).is_err());
+        assert!(build_envelope_script(&sender(), &[0; MAX_PUSH_SIZE + 1], 0, &[1]).is_err());
+    }
+
+    #[test]
+    fn test_trailing_instructions_are_rejected() {
+        let script = build_envelope_script(&sender(), b"sov-btc", 0, &[1, 2, 3]).unwrap();
+        let mut bytes = script.into_bytes();
+        bytes.push(OP_CHECKSIG.to_u8());
+        assert!(parse_envelope(Script::from_bytes(&bytes), b"sov-btc").is_none());
+    }
+}
@@ -0,0 +1,120 @@
+//! Merkle trees of transaction ids, computed the same way as the `merkle_root` of Bitcoin block headers:
+//! the nodes are hashed with double SHA-256, and the last node of a level is paired with itself if the
+//! level has an odd number of nodes.
+
+use bitcoin::hashes::{sha256d, Hash};
+
+/// Returns the double SHA-256 hash of the concatenation of `left` and `right`.
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(left);
+    preimage[32..].copy_from_slice(right);
+    sha256d::Hash::hash(&preimage).to_byte_array()
+}
+
+/// Returns the root of the tree whose leaves are `leaves`, or `None` if there are no leaves.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let mut level = leaves.to_vec();
+    if level.is_empty() {
+        return None;
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    Some(level[0])
+}
+
+/// Returns the number of levels above the leaves of a tree with `leaf_count` leaves, which is
+/// also the length of the proofs of its leaves.
+pub fn merkle_depth(leaf_count: usize) -> usize {
+    let mut depth = 0;
+    let mut width = leaf_count;
+    while width > 1 {
+        width = (width + 1) / 2;
+        depth += 1;
+    }
+    depth
+}
+
+/// Returns the siblings of the path from the leaf at `index` to the root, starting from the leaf.
+///
+/// # Panics
+/// Panics if `index` is out of bounds.
+pub fn merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    assert!(index < leaves.len(), "The leaf index is out of bounds");
+    let mut proof = Vec::with_capacity(merkle_depth(leaves.len()));
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let sibling = level.get(index ^ 1).unwrap_or(&level[index]);
+        proof.push(*sibling);
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+/// Returns the root computed from the leaf `leaf` at `index`, and the siblings of its path.
+pub fn merkle_root_from_proof(leaf: [u8; 32], mut index: usize, proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut node = leaf;
+    for sibling in proof {
+        node = if index % 2 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        };
+        index /= 2;
+    }
+    node
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hash_types::TxMerkleNode;
+    use bitcoin::hashes::Hash;
+
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn test_root_matches_bitcoin() {
+        for count in 1..10 {
+            let leaves = leaves(count);
+            let expected = bitcoin::merkle_tree::calculate_root(
+                leaves
+                    .iter()
+                    .map(|leaf| TxMerkleNode::from_byte_array(*leaf)),
+            )
+            .unwrap();
+            assert_eq!(
+                merkle_root(&leaves).unwrap(),
+                expected.to_byte_array(),
+                "Wrong root for {count} leaves"
+            );
+        }
+        assert!(merkle_root(&[]).is_none());
+    }
+
+    #[test]
+    fn test_proofs() {
+        for count in 1..10 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves).unwrap();
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, index);
+                assert_eq!(proof.len(), merkle_depth(leaves.len()));
+                assert_eq!(merkle_root_from_proof(*leaf, index, &proof), root);
+                assert_ne!(merkle_root_from_proof([u8::MAX; 32], index, &proof), root);
+            }
+        }
+    }
+}
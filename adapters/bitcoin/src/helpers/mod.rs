@@ -0,0 +1,6 @@
+//! Helpers to write rollup data to Bitcoin transactions, and to prove its inclusion in blocks.
+
+#[cfg(feature = "native")]
+pub mod builders;
+pub mod envelope;
+pub mod merkle;
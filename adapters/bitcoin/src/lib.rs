@@ -0,0 +1,15 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+pub mod helpers;
+#[cfg(feature = "native")]
+mod rpc;
+#[cfg(feature = "native")]
+mod service;
+pub mod spec;
+pub mod verifier;
+
+#[cfg(feature = "native")]
+pub use service::{BitcoinService, BitcoinServiceConfig};
+pub use spec::*;
+pub use verifier::{BitcoinVerifier, ChainValidityCondition, ValidationError};
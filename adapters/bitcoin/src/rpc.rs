@@ -0,0 +1,169 @@
+//! A minimal client of the JSON-RPC interface of Bitcoin Core.
+
+use anyhow::{bail, Context};
+use bitcoin::block::Header;
+use bitcoin::consensus::encode::{deserialize, serialize_hex};
+use bitcoin::{Block, BlockHash, ScriptBuf, Transaction, Txid};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct HexResponse {
+    hex: String,
+}
+
+#[derive(Deserialize)]
+struct AddressInfo {
+    #[serde(rename = "scriptPubKey")]
+    script_pubkey: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct BitcoinNode {
+    client: reqwest::Client,
+    url: String,
+    username: String,
+    password: String,
+}
+
+impl BitcoinNode {
+    pub(crate) fn new(url: String, username: String, password: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            username,
+            password,
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> anyhow::Result<T> {
+        let response: RpcResponse<T> = self
+            .client
+            .post(&self.url)
+            .basic_auth(&self.username, Some(&self.password))
+            .json(&json!({
+                "jsonrpc": "1.0",
+                "id": method,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Invalid response to {}", method))?;
+
+        if let Some(error) = response.error {
+            bail!(
+                "{} failed with code {}: {}",
+                method,
+                error.code,
+                error.message
+            );
+        }
+        response
+            .result
+            .with_context(|| format!("{} returned no result", method))
+    }
+
+    /// Returns the height of the tip of the best chain.
+    pub(crate) async fn get_block_count(&self) -> anyhow::Result<u64> {
+        self.call("getblockcount", vec![]).await
+    }
+
+    /// Returns the hash of the block at `height` on the best chain.
+    pub(crate) async fn get_block_hash(&self, height: u64) -> anyhow::Result<BlockHash> {
+        let hash: String = self.call("getblockhash", vec![json!(height)]).await?;
+        Ok(hash.parse()?)
+    }
+
+    /// Returns the header of the block with the hash `hash`.
+    pub(crate) async fn get_block_header(&self, hash: &BlockHash) -> anyhow::Result<Header> {
+        let header: String = self
+            .call(
+                "getblockheader",
+                vec![json!(hash.to_string()), json!(false)],
+            )
+            .await?;
+        Ok(deserialize(&hex::decode(header)?)?)
+    }
+
+    /// Returns the block with the hash `hash`.
+    pub(crate) async fn get_block(&self, hash: &BlockHash) -> anyhow::Result<Block> {
+        let block: String = self
+            .call("getblock", vec![json!(hash.to_string()), json!(0)])
+            .await?;
+        Ok(deserialize(&hex::decode(block)?)?)
+    }
+
+    /// Adds inputs of the wallet of the node to `tx` to pay for its outputs, and a change output after them.
+    /// The inputs are locked until the transaction is sent, so that they aren't spent twice.
+    pub(crate) async fn fund_raw_transaction(
+        &self,
+        tx: &Transaction,
+        fee_rate: u64,
+    ) -> anyhow::Result<Transaction> {
+        let funded: HexResponse = self
+            .call(
+                "fundrawtransaction",
+                vec![
+                    json!(serialize_hex(tx)),
+                    json!({
+                        "changePosition": tx.output.len(),
+                        "lockUnspents": true,
+                        "fee_rate": fee_rate,
+                    }),
+                ],
+            )
+            .await?;
+        Ok(deserialize(&hex::decode(funded.hex)?)?)
+    }
+
+    /// Signs the inputs of `tx` with the wallet of the node.
+    pub(crate) async fn sign_raw_transaction_with_wallet(
+        &self,
+        tx: &Transaction,
+    ) -> anyhow::Result<Transaction> {
+        let signed: HexResponse = self
+            .call(
+                "signrawtransactionwithwallet",
+                vec![json!(serialize_hex(tx))],
+            )
+            .await?;
+        Ok(deserialize(&hex::decode(signed.hex)?)?)
+    }
+
+    /// Broadcasts `tx`.
+    pub(crate) async fn send_raw_transaction(&self, tx: &Transaction) -> anyhow::Result<Txid> {
+        let txid: String = self
+            .call("sendrawtransaction", vec![json!(serialize_hex(tx))])
+            .await?;
+        Ok(txid.parse()?)
+    }
+
+    /// Returns the script of a new taproot address of the wallet of the node.
+    pub(crate) async fn get_new_script_pubkey(&self) -> anyhow::Result<ScriptBuf> {
+        let address: String = self
+            .call("getnewaddress", vec![json!(""), json!("bech32m")])
+            .await?;
+        let info: AddressInfo = self.call("getaddressinfo", vec![json!(address)]).await?;
+        Ok(ScriptBuf::from_hex(&info.script_pubkey)?)
+    }
+}
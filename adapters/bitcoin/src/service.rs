@@ -0,0 +1,315 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bitcoin::absolute::LockTime;
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::{All, KeyPair, Secp256k1};
+use bitcoin::{ScriptBuf, Transaction, TxOut, Txid};
+use futures::Stream;
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::services::da::{DaService, Fee};
+use tracing::{debug, info, instrument};
+
+use crate::helpers::builders::{
+    build_commit_reveal_txs, estimate_reveal_fee, REVEAL_OUTPUT_AMOUNT,
+};
+use crate::helpers::envelope::extract_envelope;
+use crate::rpc::BitcoinNode;
+use crate::spec::proof::completeness_proof;
+use crate::spec::{
+    BitcoinBlock, BitcoinSpec, BlobWithSender, HeaderWrapper, InclusionMultiProof, RollupParams,
+};
+use crate::verifier::BitcoinVerifier;
+
+/// Runtime configuration for the [`DaService`] implementation.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct BitcoinServiceConfig {
+    /// The address of the JSON-RPC server of the Bitcoin Core node. The blobs are paid by the wallet of the node.
+    pub node_url: String,
+    /// The user of the JSON-RPC server.
+    pub node_username: String,
+    /// The password of the JSON-RPC server.
+    pub node_password: String,
+    /// The secret key signing the blobs, in hex. Its x-only public key is the sender of the blobs.
+    pub sender_private_key: String,
+    /// The number of confirmations after which a block is considered final.
+    #[serde(default = "default_finality_depth")]
+    pub finality_depth: u64,
+    /// The fee rate of the commit and reveal transactions, in satoshis per virtual byte.
+    #[serde(default = "default_fee_rate")]
+    pub fee_rate: u64,
+    /// The interval between two polls of the node while waiting for a block, in milliseconds.
+    #[serde(default = "default_polling_interval_ms")]
+    pub polling_interval_ms: u64,
+}
+
+const fn default_finality_depth() -> u64 {
+    6
+}
+
+const fn default_fee_rate() -> u64 {
+    2
+}
+
+const fn default_polling_interval_ms() -> u64 {
+    10_000
+}
+
+/// The estimated virtual size of a commit transaction funded by a single segwit input, with a change output.
+const COMMIT_TX_VSIZE_ESTIMATE: u64 = 154;
+
+/// A [`DaService`] writing blobs to Bitcoin through a Bitcoin Core node.
+#[derive(Debug, Clone)]
+pub struct BitcoinService {
+    node: BitcoinNode,
+    params: RollupParams,
+    secp: Secp256k1<All>,
+    key_pair: KeyPair,
+    finality_depth: u64,
+    fee_rate: u64,
+    polling_interval: Duration,
+}
+
+impl BitcoinService {
+    /// Creates a new service talking to the node configured in `config`.
+    pub async fn new(config: BitcoinServiceConfig, chain_params: RollupParams) -> Self {
+        let secp = Secp256k1::new();
+        let key_pair = KeyPair::from_seckey_str(&secp, &config.sender_private_key)
+            .expect("The sender private key must be a valid secret key in hex");
+        assert!(
+            config.finality_depth > 0,
+            "The finality depth must be at least one block"
+        );
+
+        Self {
+            node: BitcoinNode::new(config.node_url, config.node_username, config.node_password),
+            params: chain_params,
+            secp,
+            key_pair,
+            finality_depth: config.finality_depth,
+            fee_rate: config.fee_rate,
+            polling_interval: Duration::from_millis(config.polling_interval_ms),
+        }
+    }
+
+    async fn get_header_at(&self, height: u64) -> anyhow::Result<HeaderWrapper> {
+        let hash = self.node.get_block_hash(height).await?;
+        let header = self.node.get_block_header(&hash).await?;
+        Ok(HeaderWrapper::new(header, height))
+    }
+
+    /// Returns the height of the last finalized block, if any.
+    async fn get_last_finalized_height(&self) -> anyhow::Result<Option<u64>> {
+        let block_count = self.node.get_block_count().await?;
+        Ok((block_count + 1).checked_sub(self.finality_depth))
+    }
+
+    /// Writes an envelope with `marker` and `body`, and returns the `txid` of its reveal transaction.
+    #[instrument(skip(self, body), fields(body_len = body.len()), err)]
+    async fn send_envelope(&self, marker: &[u8], body: &[u8]) -> anyhow::Result<Txid> {
+        let recipient = self.node.get_new_script_pubkey().await?;
+        let reveal_fee = estimate_reveal_fee(
+            &self.secp,
+            &self.key_pair,
+            marker,
+            body,
+            recipient.clone(),
+            self.fee_rate,
+        )?;
+
+        // The commit output is a taproot output, like the recipient, so the size of the commit
+        // transaction doesn't change when its script is replaced
+        let commit_tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: reveal_fee + REVEAL_OUTPUT_AMOUNT,
+                script_pubkey: recipient.clone(),
+            }],
+        };
+        let commit_tx = self
+            .node
+            .fund_raw_transaction(&commit_tx, self.fee_rate)
+            .await?;
+
+        debug!("Grinding the nonce of the envelope...");
+        let secp = self.secp.clone();
+        let key_pair = self.key_pair;
+        let marker = marker.to_vec();
+        let body = body.to_vec();
+        let prefix = self.params.reveal_wtxid_prefix.clone();
+        let (commit_tx, reveal_tx) = tokio::task::spawn_blocking(move || {
+            build_commit_reveal_txs(
+                &secp, &key_pair, &marker, &body, commit_tx, recipient, &prefix,
+            )
+        })
+        .await??;
+
+        let signed_commit_tx = self
+            .node
+            .sign_raw_transaction_with_wallet(&commit_tx)
+            .await?;
+        anyhow::ensure!(
+            signed_commit_tx.txid() == commit_tx.txid(),
+            "The wallet of the node must only spend segwit outputs to fund the commit transactions"
+        );
+        self.node.send_raw_transaction(&signed_commit_tx).await?;
+        let txid = self.node.send_raw_transaction(&reveal_tx).await?;
+        info!(
+            "Envelope has been sent to Bitcoin. commit-txid={}, reveal-txid={}",
+            commit_tx.txid(),
+            txid
+        );
+        Ok(txid)
+    }
+}
+
+#[async_trait]
+impl DaService for BitcoinService {
+    type Spec = BitcoinSpec;
+
+    type Verifier = BitcoinVerifier;
+
+    type FilteredBlock = BitcoinBlock;
+    type HeaderStream = Pin<Box<dyn Stream<Item = Result<HeaderWrapper, Self::Error>> + Send>>;
+    type TransactionId = Txid;
+    type Error = anyhow::Error;
+
+    #[instrument(skip(self), err)]
+    async fn get_block_at(&self, height: u64) -> Result<Self::FilteredBlock, Self::Error> {
+        while self.node.get_block_count().await? < height {
+            debug!("Waiting for block at height {}...", height);
+            tokio::time::sleep(self.polling_interval).await;
+        }
+
+        let hash = self.node.get_block_hash(height).await?;
+        let block = self.node.get_block(&hash).await?;
+        Ok(BitcoinBlock {
+            header: HeaderWrapper::new(block.header, height),
+            txdata: block.txdata,
+        })
+    }
+
+    async fn get_last_finalized_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let Some(height) = self.get_last_finalized_height().await? else {
+            anyhow::bail!("No block has {} confirmations yet", self.finality_depth);
+        };
+        self.get_header_at(height).await
+    }
+
+    async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
+        let next_height = self
+            .get_last_finalized_height()
+            .await?
+            .map_or(0, |height| height + 1);
+        let stream = futures::stream::unfold(
+            (self.clone(), next_height),
+            |(service, next_height)| async move {
+                loop {
+                    match service.get_last_finalized_height().await {
+                        Ok(Some(height)) if height >= next_height => break,
+                        Ok(_) => tokio::time::sleep(service.polling_interval).await,
+                        Err(e) => return Some((Err(e), (service, next_height))),
+                    }
+                }
+                let header = service.get_header_at(next_height).await;
+                let next_height = if header.is_ok() {
+                    next_height + 1
+                } else {
+                    next_height
+                };
+                Some((header, (service, next_height)))
+            },
+        );
+        Ok(Box::pin(stream))
+    }
+
+    async fn get_head_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let height = self.node.get_block_count().await?;
+        self.get_header_at(height).await
+    }
+
+    fn extract_relevant_blobs(
+        &self,
+        block: &Self::FilteredBlock,
+    ) -> Vec<<Self::Spec as DaSpec>::BlobTransaction> {
+        block
+            .txdata
+            .iter()
+            .skip(1)
+            .filter(|tx| {
+                tx.wtxid()
+                    .as_byte_array()
+                    .starts_with(&self.params.reveal_wtxid_prefix)
+            })
+            .filter_map(|tx| {
+                let envelope = extract_envelope(tx, &self.params.batch_marker)?;
+                Some(BlobWithSender::new(
+                    envelope.body,
+                    envelope.sender,
+                    tx.txid().to_byte_array(),
+                ))
+            })
+            .collect()
+    }
+
+    async fn get_extraction_proof(
+        &self,
+        block: &Self::FilteredBlock,
+        _blobs: &[<Self::Spec as DaSpec>::BlobTransaction],
+    ) -> (
+        <Self::Spec as DaSpec>::InclusionMultiProof,
+        <Self::Spec as DaSpec>::CompletenessProof,
+    ) {
+        (
+            InclusionMultiProof::from_block(block),
+            completeness_proof(block, &self.params.reveal_wtxid_prefix),
+        )
+    }
+
+    async fn send_transaction(&self, blob: &[u8]) -> Result<Self::TransactionId, Self::Error> {
+        debug!("Sending {} bytes of raw data to Bitcoin.", blob.len());
+        self.send_envelope(&self.params.batch_marker, blob).await
+    }
+
+    /// Sends the proof, and returns the height of the next block, in which it is expected to be included.
+    async fn send_aggregated_zk_proof(&self, aggregated_proof: &[u8]) -> Result<u64, Self::Error> {
+        self.send_envelope(&self.params.proof_marker, aggregated_proof)
+            .await?;
+        Ok(self.node.get_block_count().await? + 1)
+    }
+
+    async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let block = self.get_block_at(height).await?;
+        Ok(block
+            .txdata
+            .iter()
+            .skip(1)
+            .filter_map(|tx| extract_envelope(tx, &self.params.proof_marker))
+            .map(|envelope| envelope.body)
+            .collect())
+    }
+
+    /// Estimates the fees of the commit and reveal transactions of a batch of `blob_size` bytes.
+    async fn estimate_fee(&self, blob_size: usize) -> Result<Fee, Self::Error> {
+        // The recipient is a taproot output, like the outputs of the wallet of the node
+        let recipient =
+            ScriptBuf::new_v1_p2tr(&self.secp, self.key_pair.x_only_public_key().0, None);
+        let reveal_fee = estimate_reveal_fee(
+            &self.secp,
+            &self.key_pair,
+            &self.params.batch_marker,
+            &vec![0; blob_size],
+            recipient,
+            self.fee_rate,
+        )?;
+        let commit_fee = COMMIT_TX_VSIZE_ESTIMATE * self.fee_rate;
+        Ok(Fee((commit_fee + reveal_fee) as u128))
+    }
+}
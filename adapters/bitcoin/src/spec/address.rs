@@ -0,0 +1,47 @@
+//! The address of the sender of a blob.
+
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// The x-only public key which signed the transaction revealing a blob.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct AddressWrapper(pub [u8; 32]);
+
+impl sov_rollup_interface::BasicAddress for AddressWrapper {}
+
+impl Display for AddressWrapper {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl AsRef<[u8]> for AddressWrapper {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<[u8; 32]> for AddressWrapper {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl FromStr for AddressWrapper {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(anyhow::Error::msg)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for AddressWrapper {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(Self(<[u8; 32]>::try_from(value)?))
+    }
+}
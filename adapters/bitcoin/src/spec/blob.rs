@@ -0,0 +1,55 @@
+//! A blob revealed by a Bitcoin transaction.
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlobReaderTrait, CountedBufReader};
+
+use super::address::AddressWrapper;
+
+/// A blob, along with the public key of its sender.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BlobWithSender {
+    /// The data of the blob.
+    pub blob: CountedBufReader<Bytes>,
+    /// The x-only public key of the sender.
+    pub sender: AddressWrapper,
+    /// The `txid` of the transaction revealing the blob, in internal byte order.
+    pub hash: [u8; 32],
+}
+
+impl BlobWithSender {
+    /// Creates a new blob.
+    pub fn new(blob: Vec<u8>, sender: [u8; 32], hash: [u8; 32]) -> Self {
+        Self {
+            blob: CountedBufReader::new(Bytes::from(blob)),
+            sender: AddressWrapper(sender),
+            hash,
+        }
+    }
+}
+
+impl BlobReaderTrait for BlobWithSender {
+    type Address = AddressWrapper;
+
+    fn sender(&self) -> AddressWrapper {
+        self.sender
+    }
+
+    fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    fn verified_data(&self) -> &[u8] {
+        self.blob.accumulator()
+    }
+
+    fn total_len(&self) -> usize {
+        self.blob.total_len()
+    }
+
+    #[cfg(feature = "native")]
+    fn advance(&mut self, num_bytes: usize) -> &[u8] {
+        self.blob.advance(num_bytes);
+        self.verified_data()
+    }
+}
@@ -0,0 +1,39 @@
+//! A Bitcoin block.
+
+use bitcoin::Transaction;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::services::da::SlotData;
+
+use super::header::HeaderWrapper;
+use crate::verifier::ChainValidityCondition;
+
+/// A Bitcoin block, with all its transactions. The witnesses of the transactions are needed to
+/// extract the blobs, so no transaction can be filtered out before the proofs are generated.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct BitcoinBlock {
+    /// The header of the block.
+    pub header: HeaderWrapper,
+    /// The transactions of the block, starting with the coinbase transaction.
+    pub txdata: Vec<Transaction>,
+}
+
+impl SlotData for BitcoinBlock {
+    type BlockHeader = HeaderWrapper;
+    type Cond = ChainValidityCondition;
+
+    fn hash(&self) -> [u8; 32] {
+        self.header.hash().to_byte_array()
+    }
+
+    fn header(&self) -> &Self::BlockHeader {
+        &self.header
+    }
+
+    fn validity_condition(&self) -> ChainValidityCondition {
+        ChainValidityCondition {
+            prev_hash: self.header.prev_hash().to_byte_array(),
+            block_hash: <Self as SlotData>::hash(self),
+        }
+    }
+}
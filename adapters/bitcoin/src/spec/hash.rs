@@ -0,0 +1,44 @@
+//! The hash of a Bitcoin block.
+
+use bitcoin::hashes::Hash;
+use bitcoin::BlockHash;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::BlockHashTrait;
+
+/// The double SHA-256 hash of a Bitcoin block header.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockHashWrapper(pub BlockHash);
+
+impl BlockHashTrait for BlockHashWrapper {}
+
+impl BlockHashWrapper {
+    /// Returns the bytes of the hash, in internal byte order.
+    pub fn to_byte_array(&self) -> [u8; 32] {
+        self.0.to_byte_array()
+    }
+}
+
+impl core::fmt::Display for BlockHashWrapper {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Bitcoin displays hashes in reversed byte order
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<[u8]> for BlockHashWrapper {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<BlockHashWrapper> for [u8; 32] {
+    fn from(value: BlockHashWrapper) -> Self {
+        value.to_byte_array()
+    }
+}
+
+impl From<BlockHash> for BlockHashWrapper {
+    fn from(value: BlockHash) -> Self {
+        Self(value)
+    }
+}
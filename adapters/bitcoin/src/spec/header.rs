@@ -0,0 +1,50 @@
+//! The header of a Bitcoin block.
+
+use bitcoin::block::Header;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlockHeaderTrait, Time};
+
+use super::hash::BlockHashWrapper;
+
+/// The header of a Bitcoin block, along with its height. The height isn't part of Bitcoin headers,
+/// it is checked against the coinbase transaction of the block when verifying its transactions.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct HeaderWrapper {
+    /// The Bitcoin header.
+    pub header: Header,
+    /// The height of the block.
+    pub height: u64,
+}
+
+impl HeaderWrapper {
+    /// Creates a new header.
+    pub fn new(header: Header, height: u64) -> Self {
+        Self { header, height }
+    }
+
+    /// Returns the root of the tree of the `txid`s of the block.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        use bitcoin::hashes::Hash;
+        self.header.merkle_root.to_byte_array()
+    }
+}
+
+impl BlockHeaderTrait for HeaderWrapper {
+    type Hash = BlockHashWrapper;
+
+    fn prev_hash(&self) -> Self::Hash {
+        BlockHashWrapper(self.header.prev_blockhash)
+    }
+
+    fn hash(&self) -> Self::Hash {
+        BlockHashWrapper(self.header.block_hash())
+    }
+
+    fn height(&self) -> u64 {
+        self.height
+    }
+
+    fn time(&self) -> Time {
+        Time::from_secs(self.header.time as i64)
+    }
+}
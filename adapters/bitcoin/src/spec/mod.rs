@@ -0,0 +1,55 @@
+//! The types used by the rollup to represent Bitcoin data.
+
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::DaSpec;
+
+use crate::verifier::ChainValidityCondition;
+
+pub mod address;
+pub mod blob;
+pub mod block;
+pub mod hash;
+pub mod header;
+pub mod proof;
+
+pub use address::AddressWrapper;
+pub use blob::BlobWithSender;
+pub use block::BitcoinBlock;
+pub use hash::BlockHashWrapper;
+pub use header::HeaderWrapper;
+pub use proof::{completeness_proof, CompletenessProof, InclusionMultiProof};
+
+/// The [`DaSpec`] of Bitcoin.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct BitcoinSpec;
+
+impl DaSpec for BitcoinSpec {
+    type SlotHash = BlockHashWrapper;
+
+    type BlockHeader = HeaderWrapper;
+
+    type BlobTransaction = BlobWithSender;
+
+    type Address = AddressWrapper;
+
+    type ValidityCondition = ChainValidityCondition;
+
+    type InclusionMultiProof = InclusionMultiProof;
+
+    type CompletenessProof = CompletenessProof;
+
+    type ChainParams = RollupParams;
+}
+
+/// The parameters of a rollup on Bitcoin, which are baked into its state-transition function.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RollupParams {
+    /// The marker of the envelopes holding the batches of the rollup.
+    pub batch_marker: Vec<u8>,
+    /// The marker of the envelopes holding the aggregated proofs of the rollup.
+    pub proof_marker: Vec<u8>,
+    /// The prefix of the `wtxid`s, in internal byte order, of the transactions revealing envelopes.
+    /// The transactions with other `wtxid`s are ignored. Each byte of prefix divides the size of
+    /// the completeness proofs by 256, and multiplies the work to submit a blob by 256.
+    pub reveal_wtxid_prefix: Vec<u8>,
+}
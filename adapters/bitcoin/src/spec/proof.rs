@@ -0,0 +1,76 @@
+//! The proofs that the blobs of a rollup were extracted correctly from a Bitcoin block.
+
+use bitcoin::hashes::Hash;
+use bitcoin::Transaction;
+use serde::{Deserialize, Serialize};
+
+use super::block::BitcoinBlock;
+use crate::helpers::merkle::merkle_proof;
+
+/// A proof of the `wtxid`s of all the transactions of a block, against its header:
+/// - the coinbase transaction is proven against the `merkle_root` of the header,
+/// - the root of the tree of the `wtxid`s is proven against the witness commitment of the coinbase
+///   transaction, as defined by BIP-141.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InclusionMultiProof {
+    /// The `wtxid`s of the transactions of the block, in internal byte order.
+    /// The `wtxid` of the coinbase transaction is all zeros.
+    pub wtxids: Vec<[u8; 32]>,
+    /// The coinbase transaction of the block.
+    pub coinbase_tx: Transaction,
+    /// The siblings of the path from the `txid` of the coinbase transaction to the `merkle_root` of the header.
+    pub coinbase_merkle_proof: Vec<[u8; 32]>,
+}
+
+impl InclusionMultiProof {
+    /// Builds the proof of the `wtxid`s of the transactions of `block`.
+    ///
+    /// # Panics
+    /// Panics if the block has no transactions.
+    pub fn from_block(block: &BitcoinBlock) -> Self {
+        let coinbase_tx = block
+            .txdata
+            .first()
+            .expect("A block always has a coinbase transaction")
+            .clone();
+        let wtxids = block
+            .txdata
+            .iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                if index == 0 {
+                    [0; 32]
+                } else {
+                    tx.wtxid().to_byte_array()
+                }
+            })
+            .collect();
+        let txids: Vec<[u8; 32]> = block
+            .txdata
+            .iter()
+            .map(|tx| tx.txid().to_byte_array())
+            .collect();
+
+        Self {
+            wtxids,
+            coinbase_tx,
+            coinbase_merkle_proof: merkle_proof(&txids, 0),
+        }
+    }
+}
+
+/// The transactions of a block whose `wtxid` starts with the prefix of the rollup, in the order of the block.
+/// Since the `wtxid`s of all the transactions are proven, they prove that no blob was left out.
+pub type CompletenessProof = Vec<Transaction>;
+
+/// Returns the completeness proof of `block`, for the rollup whose reveal transactions have `wtxid`s
+/// starting with `reveal_wtxid_prefix`.
+pub fn completeness_proof(block: &BitcoinBlock, reveal_wtxid_prefix: &[u8]) -> CompletenessProof {
+    block
+        .txdata
+        .iter()
+        .skip(1)
+        .filter(|tx| tx.wtxid().as_byte_array().starts_with(reveal_wtxid_prefix))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,470 @@
+//! Verification of the blobs extracted from Bitcoin blocks, run by the rollup in the zkVM.
+
+use bitcoin::blockdata::opcodes::all::{OP_PUSHNUM_1, OP_PUSHNUM_16};
+use bitcoin::blockdata::script::{read_scriptint, Instruction};
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::Transaction;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec, DaVerifier};
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::zk::ValidityCondition;
+use thiserror::Error;
+
+use crate::helpers::envelope::extract_envelope;
+use crate::helpers::merkle::{merkle_depth, merkle_root, merkle_root_from_proof};
+use crate::spec::{BitcoinSpec, RollupParams};
+
+/// The prefix of the output of a coinbase transaction holding the witness commitment, as defined by BIP-141:
+/// `OP_RETURN`, a push of 36 bytes, and the commitment header.
+const WITNESS_COMMITMENT_PREFIX: [u8; 6] = [0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed];
+
+/// Verifies the blobs of a rollup extracted from Bitcoin blocks.
+pub struct BitcoinVerifier {
+    params: RollupParams,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Hash,
+    BorshDeserialize,
+    BorshSerialize,
+)]
+/// A validity condition expressing that a chain of DA layer blocks is contiguous and canonical
+pub struct ChainValidityCondition {
+    /// The hash of the previous block, in internal byte order.
+    pub prev_hash: [u8; 32],
+    /// The hash of the block, in internal byte order.
+    pub block_hash: [u8; 32],
+}
+
+/// The error returned when two [`ChainValidityCondition`]s can't be combined.
+#[derive(Error, Debug)]
+pub enum ValidityConditionError {
+    /// The blocks aren't consecutive.
+    #[error("conditions for validity can only be combined if the blocks are consecutive")]
+    BlocksNotConsecutive,
+}
+
+impl ValidityCondition for ChainValidityCondition {
+    type Error = ValidityConditionError;
+    fn combine<H: Digest>(&self, rhs: Self) -> Result<Self, Self::Error> {
+        if self.block_hash != rhs.prev_hash {
+            return Err(ValidityConditionError::BlocksNotConsecutive);
+        }
+        Ok(rhs)
+    }
+}
+
+/// The error returned when the blobs of a block can't be verified.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The hash of the header is above its target.
+    #[error("the header doesn't have enough proof of work")]
+    InvalidProofOfWork,
+    /// The transaction proven as the first transaction of the block isn't a coinbase transaction.
+    #[error("the first transaction of the block isn't a coinbase transaction")]
+    InvalidCoinbase,
+    /// The coinbase transaction isn't included in the block.
+    #[error("the coinbase transaction isn't included in the block")]
+    InvalidCoinbaseProof,
+    /// The height committed by the coinbase transaction, as defined by BIP-34, isn't the height of the header.
+    #[error("the block is at height {committed:?}, not {claimed}")]
+    WrongHeight {
+        /// The height of the header.
+        claimed: u64,
+        /// The height committed by the coinbase transaction, if any.
+        committed: Option<u64>,
+    },
+    /// The `wtxid`s don't match the witness commitment of the coinbase transaction.
+    #[error("the wtxids don't match the witness commitment of the block")]
+    InvalidWitnessCommitment,
+    /// A transaction of the completeness proof is missing, or isn't in the block.
+    #[error("the completeness proof doesn't match the wtxids of the block")]
+    InvalidCompletenessProof,
+    /// A blob of the block is missing.
+    #[error("a blob of the block is missing")]
+    MissingBlob,
+    /// A blob isn't in the block.
+    #[error("a blob isn't in the block")]
+    ExtraBlob,
+    /// A blob doesn't match the envelope revealed in the block.
+    #[error("the blob {} doesn't match its envelope", hex::encode(.0))]
+    BlobMismatch([u8; 32]),
+}
+
+impl DaVerifier for BitcoinVerifier {
+    type Spec = BitcoinSpec;
+
+    type Error = ValidationError;
+
+    fn new(params: <Self::Spec as DaSpec>::ChainParams) -> Self {
+        Self { params }
+    }
+
+    fn verify_relevant_tx_list(
+        &self,
+        block_header: &<Self::Spec as DaSpec>::BlockHeader,
+        txs: &[<Self::Spec as DaSpec>::BlobTransaction],
+        inclusion_proof: <Self::Spec as DaSpec>::InclusionMultiProof,
+        completeness_proof: <Self::Spec as DaSpec>::CompletenessProof,
+    ) -> Result<<Self::Spec as DaSpec>::ValidityCondition, Self::Error> {
+        let header = &block_header.header;
+        header
+            .validate_pow(header.target())
+            .map_err(|_| ValidationError::InvalidProofOfWork)?;
+        let validity_condition = ChainValidityCondition {
+            prev_hash: block_header.prev_hash().to_byte_array(),
+            block_hash: block_header.hash().to_byte_array(),
+        };
+
+        // Check that the coinbase transaction is the first transaction of the block. The length of the proof
+        // must match the number of wtxids, so that they are proven to be all the transactions of the block.
+        let coinbase_tx = &inclusion_proof.coinbase_tx;
+        if !is_coinbase(coinbase_tx) || inclusion_proof.wtxids.first() != Some(&[0; 32]) {
+            return Err(ValidationError::InvalidCoinbase);
+        }
+        if inclusion_proof.coinbase_merkle_proof.len() != merkle_depth(inclusion_proof.wtxids.len())
+            || merkle_root_from_proof(
+                coinbase_tx.txid().to_byte_array(),
+                0,
+                &inclusion_proof.coinbase_merkle_proof,
+            ) != block_header.merkle_root()
+        {
+            return Err(ValidationError::InvalidCoinbaseProof);
+        }
+        let committed_height = coinbase_height(coinbase_tx);
+        if committed_height != Some(block_header.height) {
+            return Err(ValidationError::WrongHeight {
+                claimed: block_header.height,
+                committed: committed_height,
+            });
+        }
+
+        // Without a witness commitment, the block has no witness data, so it can't reveal any envelope
+        let Some(commitment) = witness_commitment(coinbase_tx) else {
+            if txs.is_empty() {
+                return Ok(validity_condition);
+            }
+            return Err(ValidationError::ExtraBlob);
+        };
+        let reserved_value = coinbase_tx.input[0]
+            .witness
+            .nth(0)
+            .filter(|value| value.len() == 32)
+            .ok_or(ValidationError::InvalidWitnessCommitment)?;
+        let witness_root =
+            merkle_root(&inclusion_proof.wtxids).expect("The coinbase wtxid is in the tree");
+        let mut preimage = witness_root.to_vec();
+        preimage.extend_from_slice(reserved_value);
+        if sha256d::Hash::hash(&preimage).to_byte_array() != commitment {
+            return Err(ValidationError::InvalidWitnessCommitment);
+        }
+
+        // Check that all the transactions with the prefix were provided, and that their envelopes
+        // are the blobs, in order
+        let mut proven_txs = completeness_proof.iter();
+        let mut blobs = txs.iter();
+        let prefixed_wtxids = inclusion_proof
+            .wtxids
+            .iter()
+            .skip(1)
+            .filter(|wtxid| wtxid.starts_with(&self.params.reveal_wtxid_prefix));
+        for wtxid in prefixed_wtxids {
+            let tx = proven_txs
+                .next()
+                .ok_or(ValidationError::InvalidCompletenessProof)?;
+            if tx.wtxid().as_byte_array() != wtxid {
+                return Err(ValidationError::InvalidCompletenessProof);
+            }
+
+            let Some(envelope) = extract_envelope(tx, &self.params.batch_marker) else {
+                continue;
+            };
+            let blob = blobs.next().ok_or(ValidationError::MissingBlob)?;
+            let hash = tx.txid().to_byte_array();
+            // The rollup may have read only the beginning of the blob
+            if blob.hash() != hash
+                || blob.sender().0 != envelope.sender
+                || blob.total_len() != envelope.body.len()
+                || !envelope.body.starts_with(blob.verified_data())
+            {
+                return Err(ValidationError::BlobMismatch(blob.hash()));
+            }
+        }
+        if proven_txs.next().is_some() {
+            return Err(ValidationError::InvalidCompletenessProof);
+        }
+        if blobs.next().is_some() {
+            return Err(ValidationError::ExtraBlob);
+        }
+
+        Ok(validity_condition)
+    }
+}
+
+fn is_coinbase(tx: &Transaction) -> bool {
+    tx.input.len() == 1 && tx.input[0].previous_output.is_null()
+}
+
+/// Returns the height committed by the coinbase transaction `tx`, as defined by BIP-34:
+/// it is the number pushed first by the script of its input.
+fn coinbase_height(tx: &Transaction) -> Option<u64> {
+    let instruction = tx.input.first()?.script_sig.instructions().next()?.ok()?;
+    let height = match instruction {
+        Instruction::PushBytes(bytes) => read_scriptint(bytes.as_bytes()).ok()?,
+        Instruction::Op(op)
+            if (OP_PUSHNUM_1.to_u8()..=OP_PUSHNUM_16.to_u8()).contains(&op.to_u8()) =>
+        {
+            (op.to_u8() - OP_PUSHNUM_1.to_u8() + 1) as i64
+        }
+        Instruction::Op(_) => return None,
+    };
+    u64::try_from(height).ok()
+}
+
+/// Returns the witness commitment of the coinbase transaction `tx`, which is in its last output
+/// starting with [`WITNESS_COMMITMENT_PREFIX`].
+fn witness_commitment(tx: &Transaction) -> Option<[u8; 32]> {
+    tx.output.iter().rev().find_map(|output| {
+        let script = output.script_pubkey.as_bytes();
+        if script.len() < 38 || !script.starts_with(&WITNESS_COMMITMENT_PREFIX) {
+            return None;
+        }
+        script[6..38].try_into().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::block::{Header, Version};
+    use bitcoin::blockdata::script::Builder;
+    use bitcoin::hash_types::TxMerkleNode;
+    use bitcoin::secp256k1::{KeyPair, Secp256k1, SecretKey};
+    use bitcoin::{
+        BlockHash, CompactTarget, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Txid, Witness,
+    };
+    use sov_rollup_interface::services::da::SlotData;
+
+    use super::*;
+    use crate::helpers::builders::build_commit_reveal_txs;
+    use crate::helpers::merkle::{hash_pair, merkle_root};
+    use crate::spec::{
+        completeness_proof, BitcoinBlock, BlobWithSender, HeaderWrapper, InclusionMultiProof,
+    };
+
+    const HEIGHT: u64 = 1234;
+
+    fn params() -> RollupParams {
+        RollupParams {
+            batch_marker: b"sov-btc".to_vec(),
+            proof_marker: b"sov-btc-proof".to_vec(),
+            reveal_wtxid_prefix: vec![1],
+        }
+    }
+
+    fn funded_tx(vout: u32) -> Transaction {
+        Transaction {
+            version: 2,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::all_zeros(), vout),
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: 10_000,
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    /// Returns the reveal transaction of an envelope with `body`, whose wtxid starts with `prefix`.
+    fn reveal_tx(marker: &[u8], body: &[u8], prefix: &[u8], vout: u32) -> Transaction {
+        let secp = Secp256k1::new();
+        let key_pair = KeyPair::from_secret_key(&secp, &SecretKey::from_slice(&[7; 32]).unwrap());
+        build_commit_reveal_txs(
+            &secp,
+            &key_pair,
+            marker,
+            body,
+            funded_tx(vout),
+            ScriptBuf::new(),
+            prefix,
+        )
+        .unwrap()
+        .1
+    }
+
+    fn coinbase_tx(height: u64, witness_root: [u8; 32]) -> Transaction {
+        let reserved_value = [0u8; 32];
+        let mut commitment_script = WITNESS_COMMITMENT_PREFIX.to_vec();
+        commitment_script.extend_from_slice(&hash_pair(&witness_root, &reserved_value));
+
+        Transaction {
+            version: 2,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig: Builder::new().push_int(height as i64).into_script(),
+                sequence: Sequence::MAX,
+                witness: Witness::from_slice(&[reserved_value]),
+            }],
+            output: vec![
+                TxOut {
+                    value: 5_000_000_000,
+                    script_pubkey: ScriptBuf::new(),
+                },
+                TxOut {
+                    value: 0,
+                    script_pubkey: ScriptBuf::from_bytes(commitment_script),
+                },
+            ],
+        }
+    }
+
+    /// Builds a mined block with a coinbase committing to `height`, followed by `txs`.
+    fn block(height: u64, txs: Vec<Transaction>) -> BitcoinBlock {
+        let mut wtxids = vec![[0; 32]];
+        wtxids.extend(txs.iter().map(|tx| tx.wtxid().to_byte_array()));
+        let mut txdata = vec![coinbase_tx(height, merkle_root(&wtxids).unwrap())];
+        txdata.extend(txs);
+
+        let txids: Vec<[u8; 32]> = txdata.iter().map(|tx| tx.txid().to_byte_array()).collect();
+        let mut header = Header {
+            version: Version::from_consensus(2),
+            prev_blockhash: BlockHash::all_zeros(),
+            merkle_root: TxMerkleNode::from_byte_array(merkle_root(&txids).unwrap()),
+            time: 1_700_000_000,
+            // The target of regtest
+            bits: CompactTarget::from_consensus(0x207fffff),
+            nonce: 0,
+        };
+        while header.validate_pow(header.target()).is_err() {
+            header.nonce += 1;
+        }
+
+        BitcoinBlock {
+            header: HeaderWrapper::new(header, height),
+            txdata,
+        }
+    }
+
+    fn blob(tx: &Transaction, body: &[u8]) -> BlobWithSender {
+        let envelope = extract_envelope(tx, b"sov-btc").unwrap();
+        BlobWithSender::new(body.to_vec(), envelope.sender, tx.txid().to_byte_array())
+    }
+
+    fn verify(
+        block: &BitcoinBlock,
+        blobs: &[BlobWithSender],
+    ) -> Result<ChainValidityCondition, ValidationError> {
+        BitcoinVerifier::new(params()).verify_relevant_tx_list(
+            block.header(),
+            blobs,
+            InclusionMultiProof::from_block(block),
+            completeness_proof(block, &params().reveal_wtxid_prefix),
+        )
+    }
+
+    #[test]
+    fn test_verify_blobs() {
+        let batch_tx = reveal_tx(b"sov-btc", &[1; 2000], &[1], 0);
+        let proof_tx = reveal_tx(b"sov-btc-proof", &[2; 100], &[1], 1);
+        // Ignored, since its wtxid doesn't have the prefix
+        let unprefixed_tx = reveal_tx(b"sov-btc", &[3; 100], &[2], 2);
+        let block = block(
+            HEIGHT,
+            vec![funded_tx(3), batch_tx.clone(), proof_tx, unprefixed_tx],
+        );
+
+        let blobs = vec![blob(&batch_tx, &[1; 2000])];
+        let validity_condition = verify(&block, &blobs).unwrap();
+        assert_eq!(validity_condition, block.validity_condition());
+
+        assert_eq!(verify(&block, &[]), Err(ValidationError::MissingBlob));
+        assert_eq!(
+            verify(&block, &[blob(&batch_tx, &[1; 1999])]),
+            Err(ValidationError::BlobMismatch(
+                batch_tx.txid().to_byte_array()
+            ))
+        );
+        assert_eq!(
+            verify(&block, &[blobs[0].clone(), blobs[0].clone()]),
+            Err(ValidationError::ExtraBlob)
+        );
+    }
+
+    #[test]
+    fn test_verify_empty_block() {
+        let block = block(HEIGHT, vec![]);
+        assert!(verify(&block, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_reject_invalid_proofs() {
+        let batch_tx = reveal_tx(b"sov-btc", &[1; 100], &[1], 0);
+        let block = block(HEIGHT, vec![funded_tx(1), batch_tx.clone()]);
+        let blobs = vec![blob(&batch_tx, &[1; 100])];
+        let verifier = BitcoinVerifier::new(params());
+        let verify_with = |inclusion_proof, completeness_proof| {
+            verifier.verify_relevant_tx_list(
+                block.header(),
+                &blobs,
+                inclusion_proof,
+                completeness_proof,
+            )
+        };
+
+        // The blob is left out of the completeness proof
+        assert_eq!(
+            verify_with(InclusionMultiProof::from_block(&block), vec![]),
+            Err(ValidationError::InvalidCompletenessProof)
+        );
+
+        // The blob is left out of the wtxids
+        let mut inclusion_proof = InclusionMultiProof::from_block(&block);
+        inclusion_proof.wtxids.pop();
+        assert_eq!(
+            verify_with(inclusion_proof, vec![batch_tx]),
+            Err(ValidationError::InvalidCoinbaseProof)
+        );
+
+        let mut inclusion_proof = InclusionMultiProof::from_block(&block);
+        inclusion_proof.wtxids[1] = [1; 32];
+        assert_eq!(
+            verify_with(inclusion_proof, vec![]),
+            Err(ValidationError::InvalidWitnessCommitment)
+        );
+    }
+
+    #[test]
+    fn test_reject_wrong_height() {
+        let mut block = block(HEIGHT, vec![]);
+        block.header.height += 1;
+        assert_eq!(
+            verify(&block, &[]),
+            Err(ValidationError::WrongHeight {
+                claimed: HEIGHT + 1,
+                committed: Some(HEIGHT),
+            })
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_proof_of_work() {
+        let mut block = block(HEIGHT, vec![]);
+        block.header.header.bits = CompactTarget::from_consensus(0x1d00ffff);
+        assert_eq!(
+            verify(&block, &[]),
+            Err(ValidationError::InvalidProofOfWork)
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
 use celestia_rpc::prelude::*;
 use celestia_types::blob::{Blob as JsonBlob, Commitment, SubmitOptions};
@@ -13,9 +16,14 @@ use jsonrpsee::core::client::Subscription;
 use jsonrpsee::http_client::{HeaderMap, HttpClient};
 use pin_project::pin_project;
 use sov_rollup_interface::da::CountedBufReader;
-use sov_rollup_interface::services::da::DaService;
-use tracing::{debug, info, instrument, trace};
+use sov_rollup_interface::services::da::{DaService, Fee};
+use tracing::{debug, info, instrument, trace, warn};
 
+use crate::light_node::{LightNode, LightNodeConfig};
+use crate::metrics::{
+    CELESTIA_BLOB_FAILED_SUBMISSIONS, CELESTIA_BLOB_FEE_BUMPS,
+    CELESTIA_BLOB_INCLUSION_LATENCY_SECONDS, CELESTIA_BLOB_SUBMISSIONS,
+};
 use crate::shares::Blob;
 use crate::types::FilteredCelestiaBlock;
 use crate::utils::BoxError;
@@ -25,13 +33,69 @@ use crate::{BlobWithSender, CelestiaHeader};
 
 // Approximate value, just to make it work.
 const GAS_PER_BYTE: usize = 20;
-const GAS_PRICE: usize = 1;
+
+// About half of the Celestia block time
+const INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(6);
 
 #[derive(Debug, Clone)]
 pub struct CelestiaService {
     client: HttpClient,
     rollup_batch_namespace: Namespace,
     rollup_proof_namespace: Namespace,
+    fees: FeeSettings,
+    max_header_sync_lag: Option<u64>,
+    // Kept alive as long as the service, and its clones, are
+    _light_node: Option<Arc<LightNode>>,
+}
+
+/// How the fees of the blob submissions are estimated, and bumped when blobs aren't included in time.
+#[derive(Debug, Clone)]
+struct FeeSettings {
+    gas_price: f64,
+    gas_limit_multiplier: f64,
+    fee_bump_multiplier: f64,
+    inclusion_timeout_blocks: u64,
+    max_resubmissions: u32,
+    inclusion_poll_interval: Duration,
+}
+
+impl Default for FeeSettings {
+    fn default() -> Self {
+        Self {
+            gas_price: default_gas_price(),
+            gas_limit_multiplier: default_gas_limit_multiplier(),
+            fee_bump_multiplier: default_fee_bump_multiplier(),
+            inclusion_timeout_blocks: default_inclusion_timeout_blocks(),
+            max_resubmissions: default_max_resubmissions(),
+            inclusion_poll_interval: INCLUSION_POLL_INTERVAL,
+        }
+    }
+}
+
+impl FeeSettings {
+    fn gas_limit(&self, blob_size: usize) -> u64 {
+        (get_gas_limit_for_bytes(blob_size) as f64 * self.gas_limit_multiplier).ceil() as u64
+    }
+
+    fn fee(gas_limit: u64, gas_price: f64) -> u64 {
+        (gas_limit as f64 * gas_price).ceil() as u64
+    }
+}
+
+/// The kinds of blobs the rollup submits, each to its own namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlobKind {
+    Batch,
+    Proof,
+}
+
+impl BlobKind {
+    fn label(&self) -> &'static str {
+        match self {
+            BlobKind::Batch => "batch",
+            BlobKind::Proof => "proof",
+        }
+    }
 }
 
 impl CelestiaService {
@@ -40,10 +104,17 @@ impl CelestiaService {
         rollup_batch_namespace: Namespace,
         rollup_proof_namespace: Namespace,
     ) -> Self {
+        assert_ne!(
+            rollup_batch_namespace, rollup_proof_namespace,
+            "The proofs must not share the namespace of the batches"
+        );
         Self {
             client,
             rollup_batch_namespace,
             rollup_proof_namespace,
+            fees: FeeSettings::default(),
+            max_header_sync_lag: None,
+            _light_node: None,
         }
     }
 }
@@ -51,7 +122,9 @@ impl CelestiaService {
 /// Runtime configuration for the [`DaService`] implementation.
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct CelestiaConfig {
-    /// The JWT used to authenticate with the Celestia RPC server
+    /// The JWT used to authenticate with the Celestia RPC server.
+    /// Ignored with a managed light node, which issues its own token
+    #[serde(default)]
     pub celestia_rpc_auth_token: String,
     /// The address of the Celestia RPC server
     #[serde(default = "default_rpc_addr")]
@@ -62,6 +135,29 @@ pub struct CelestiaConfig {
     /// The timeout for a Celestia RPC request, in seconds
     #[serde(default = "default_request_timeout_seconds")]
     pub celestia_rpc_timeout_seconds: u64,
+    /// The price of gas of the blob submissions, in utia
+    #[serde(default = "default_gas_price")]
+    pub gas_price: f64,
+    /// The multiplier applied to the gas limit estimated from the size of a blob
+    #[serde(default = "default_gas_limit_multiplier")]
+    pub gas_limit_multiplier: f64,
+    /// The multiplier applied to the gas price each time a blob is resubmitted
+    #[serde(default = "default_fee_bump_multiplier")]
+    pub fee_bump_multiplier: f64,
+    /// The number of blocks after which a blob which wasn't included is resubmitted at a higher fee
+    #[serde(default = "default_inclusion_timeout_blocks")]
+    pub inclusion_timeout_blocks: u64,
+    /// The maximum number of resubmissions of a blob, after which its submission fails
+    #[serde(default = "default_max_resubmissions")]
+    pub max_resubmissions: u32,
+    /// The maximum number of blocks the headers of the Celestia node can lag behind the network.
+    /// If set, the service refuses to read blocks while the node is syncing further behind
+    #[serde(default)]
+    pub max_header_sync_lag: Option<u64>,
+    /// A light node started and stopped with the service, instead of a separately operated node.
+    /// The RPC address and auth token of the light node replace the configured ones
+    #[serde(default)]
+    pub light_node: Option<LightNodeConfig>,
 }
 
 fn default_rpc_addr() -> String {
@@ -76,8 +172,40 @@ const fn default_request_timeout_seconds() -> u64 {
     60
 }
 
+const fn default_gas_price() -> f64 {
+    1.0
+}
+
+const fn default_gas_limit_multiplier() -> f64 {
+    1.0
+}
+
+const fn default_fee_bump_multiplier() -> f64 {
+    1.5
+}
+
+const fn default_inclusion_timeout_blocks() -> u64 {
+    5
+}
+
+const fn default_max_resubmissions() -> u32 {
+    3
+}
+
 impl CelestiaService {
-    pub async fn new(config: CelestiaConfig, chain_params: RollupParams) -> Self {
+    pub async fn new(mut config: CelestiaConfig, chain_params: RollupParams) -> Self {
+        let light_node = match &config.light_node {
+            Some(light_node_config) => {
+                let (light_node, auth_token) = LightNode::start(light_node_config)
+                    .await
+                    .expect("Failed to start the Celestia light node");
+                config.celestia_rpc_address = light_node_config.rpc_address();
+                config.celestia_rpc_auth_token = auth_token;
+                Some(Arc::new(light_node))
+            }
+            None => None,
+        };
+
         let client = {
             let mut headers = HeaderMap::new();
             headers.insert(
@@ -97,11 +225,136 @@ impl CelestiaService {
         }
         .expect("Client initialization is valid");
 
-        Self::with_client(
+        let mut service = Self::with_client(
             client,
             chain_params.rollup_batch_namespace,
             chain_params.rollup_proof_namespace,
-        )
+        );
+        service.fees = FeeSettings {
+            gas_price: config.gas_price,
+            gas_limit_multiplier: config.gas_limit_multiplier,
+            fee_bump_multiplier: config.fee_bump_multiplier,
+            inclusion_timeout_blocks: config.inclusion_timeout_blocks,
+            max_resubmissions: config.max_resubmissions,
+            inclusion_poll_interval: INCLUSION_POLL_INTERVAL,
+        };
+        service.max_header_sync_lag = config.max_header_sync_lag;
+        service._light_node = light_node;
+
+        if let Some(light_node_config) = &config.light_node {
+            service
+                .wait_for_rpc(Duration::from_secs(
+                    light_node_config.startup_timeout_seconds,
+                ))
+                .await
+                .expect("The Celestia light node didn't serve its RPC in time");
+        }
+        service
+    }
+
+    /// Resolves once the node serves its RPC, or fails after `timeout`.
+    async fn wait_for_rpc(&self, timeout: Duration) -> Result<(), BoxError> {
+        let started = Instant::now();
+        loop {
+            match self.client.header_local_head().await {
+                Ok(_) => return Ok(()),
+                Err(e) if started.elapsed() < timeout => {
+                    debug!("Waiting for the Celestia node RPC: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Fails if the headers of the node lag more than `max_header_sync_lag` blocks behind the network,
+    /// as the data it serves for the latest blocks can't be trusted yet.
+    async fn ensure_headers_synced(&self) -> Result<(), BoxError> {
+        let Some(max_lag) = self.max_header_sync_lag else {
+            return Ok(());
+        };
+        let state = self.client.header_sync_state().await?;
+        check_sync_lag(state.height, state.to_height, max_lag)
+    }
+
+    fn namespace_of(&self, kind: BlobKind) -> Namespace {
+        match kind {
+            BlobKind::Batch => self.rollup_batch_namespace,
+            BlobKind::Proof => self.rollup_proof_namespace,
+        }
+    }
+
+    /// Submits `data` in the namespace of its `kind`, and returns the height of the block including it.
+    /// If the blob isn't included within `inclusion_timeout_blocks` blocks, it is resubmitted
+    /// with its gas price multiplied by `fee_bump_multiplier`, up to `max_resubmissions` times.
+    ///
+    /// Celestia doesn't replace transactions in its mempool, so a blob which is included late
+    /// can be included again by its resubmission.
+    async fn submit_blob(&self, kind: BlobKind, data: &[u8]) -> Result<u64, BoxError> {
+        let blob = JsonBlob::new(self.namespace_of(kind), data.to_vec())?;
+        let kind = kind.label();
+        info!("Submitting: {:?}", blob.commitment);
+
+        let gas_limit = self.fees.gas_limit(data.len());
+        let mut gas_price = self.fees.gas_price;
+        let started = Instant::now();
+        for submission in 1..=self.fees.max_resubmissions + 1 {
+            let fee = FeeSettings::fee(gas_limit, gas_price);
+            debug!(submission, gas_limit, fee, "Submitting blob");
+            let blobs = [blob.clone()];
+            let options = SubmitOptions {
+                fee: Some(fee),
+                gas_limit: Some(gas_limit),
+            };
+
+            tokio::select! {
+                biased;
+                height = self.client.blob_submit(&blobs, options) => {
+                    let height = height?;
+                    CELESTIA_BLOB_INCLUSION_LATENCY_SECONDS
+                        .with_label_values(&[kind])
+                        .observe(started.elapsed().as_secs_f64());
+                    CELESTIA_BLOB_SUBMISSIONS
+                        .with_label_values(&[kind])
+                        .observe(submission as f64);
+                    return Ok(height);
+                }
+                _ = self.wait_for_blocks(self.fees.inclusion_timeout_blocks) => {
+                    CELESTIA_BLOB_FEE_BUMPS.with_label_values(&[kind]).inc();
+                    gas_price *= self.fees.fee_bump_multiplier;
+                    warn!(
+                        "Blob wasn't included within {} blocks, resubmitting it with a gas price of {}",
+                        self.fees.inclusion_timeout_blocks, gas_price
+                    );
+                }
+            }
+        }
+
+        CELESTIA_BLOB_FAILED_SUBMISSIONS
+            .with_label_values(&[kind])
+            .inc();
+        Err(anyhow!(
+            "Blob wasn't included after {} submissions",
+            self.fees.max_resubmissions + 1
+        ))
+    }
+
+    /// Resolves once `blocks` blocks were produced after the first time the head of the network was fetched.
+    async fn wait_for_blocks(&self, blocks: u64) {
+        let mut start_height = None;
+        loop {
+            tokio::time::sleep(self.fees.inclusion_poll_interval).await;
+            match self.client.header_network_head().await {
+                Ok(head) => {
+                    let height = head.header.height.value();
+                    let start_height = *start_height.get_or_insert(height);
+                    if height >= start_height + blocks {
+                        return;
+                    }
+                }
+                Err(e) => warn!("Failed to fetch the head of the network: {}", e),
+            }
+        }
     }
 }
 
@@ -150,6 +403,7 @@ impl DaService for CelestiaService {
 
     #[instrument(skip(self), err)]
     async fn get_block_at(&self, height: u64) -> Result<Self::FilteredBlock, Self::Error> {
+        self.ensure_headers_synced().await?;
         let client = self.client.clone();
         let rollup_namespace = self.rollup_batch_namespace;
 
@@ -245,22 +499,7 @@ impl DaService for CelestiaService {
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
         debug!("Sending {} bytes of raw data to Celestia.", blob.len());
 
-        let gas_limit = get_gas_limit_for_bytes(blob.len()) as u64;
-        let fee = gas_limit * GAS_PRICE as u64;
-
-        let blob = JsonBlob::new(self.rollup_batch_namespace, blob.to_vec())?;
-        info!("Submitting: {:?}", blob.commitment);
-
-        let height = self
-            .client
-            .blob_submit(
-                &[blob],
-                SubmitOptions {
-                    fee: Some(fee),
-                    gas_limit: Some(gas_limit),
-                },
-            )
-            .await?;
+        let height = self.submit_blob(BlobKind::Batch, blob).await?;
         info!(
             "Blob has been submitted to Celestia. block-height={}",
             height,
@@ -269,32 +508,42 @@ impl DaService for CelestiaService {
     }
 
     async fn send_aggregated_zk_proof(&self, aggregated_proof: &[u8]) -> Result<u64, Self::Error> {
-        let gas_limit = get_gas_limit_for_bytes(aggregated_proof.len()) as u64;
-        let fee = gas_limit * GAS_PRICE as u64;
-        let blob = JsonBlob::new(self.rollup_proof_namespace, aggregated_proof.to_vec())?;
+        self.submit_blob(BlobKind::Proof, aggregated_proof).await
+    }
 
-        let height = self
+    async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
+        self.ensure_headers_synced().await?;
+        let header = self.client.header_get_by_height(height).await?;
+        let proof_rows = self
             .client
-            .blob_submit(
-                &[blob],
-                SubmitOptions {
-                    fee: Some(fee),
-                    gas_limit: Some(gas_limit),
-                },
-            )
+            .share_get_shares_by_namespace(&header, self.rollup_proof_namespace)
             .await?;
 
-        Ok(height)
+        // Check that no proof was left out, like the verifier does for the batches
+        let verifier = CelestiaVerifier {
+            rollup_namespace: self.rollup_batch_namespace,
+            rollup_proof_namespace: self.rollup_proof_namespace,
+        };
+        Ok(verifier.verify_proof_blobs(&CelestiaHeader::from(header), proof_rows)?)
     }
 
-    async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
-        let blobs = self
-            .client
-            .blob_get_all(height, &[self.rollup_proof_namespace])
-            .await?;
+    async fn estimate_fee(&self, blob_size: usize) -> Result<Fee, Self::Error> {
+        // The fee of the first submission, before any bump
+        let gas_limit = self.fees.gas_limit(blob_size);
+        Ok(Fee(FeeSettings::fee(gas_limit, self.fees.gas_price) as u128))
+    }
+}
 
-        Ok(blobs.into_iter().map(|blob| blob.data).collect())
+fn check_sync_lag(height: u64, network_height: u64, max_lag: u64) -> Result<(), BoxError> {
+    let lag = network_height.saturating_sub(height);
+    if lag > max_lag {
+        bail!(
+            "The Celestia node is syncing headers: at height {}, {} blocks behind the network",
+            height,
+            lag
+        );
     }
+    Ok(())
 }
 
 // https://docs.celestia.org/learn/submit-data/#fees-and-gas-limits
@@ -310,6 +559,7 @@ fn get_gas_limit_for_bytes(n: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::time::Duration;
 
     use celestia_types::nmt::Namespace;
@@ -317,14 +567,17 @@ mod tests {
     use serde_json::json;
     use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier};
     use sov_rollup_interface::services::da::DaService;
-    use wiremock::matchers::{bearer_token, body_json, method, path};
+    use wiremock::matchers::{bearer_token, body_json, body_partial_json, method, path};
     use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
     use super::default_request_timeout_seconds;
-    use crate::da_service::{get_gas_limit_for_bytes, CelestiaConfig, CelestiaService, GAS_PRICE};
+    use crate::da_service::{
+        check_sync_lag, get_gas_limit_for_bytes, CelestiaConfig, CelestiaService,
+    };
     use crate::parse_pfb_namespace;
     use crate::shares::NamespaceGroup;
-    use crate::types::tests::{with_rollup_data, without_rollup_data};
+    use crate::types::tests::{with_rollup_data, without_rollup_data, ROLLUP_NAMESPACE};
+    use crate::types::ValidationError;
     use crate::verifier::{CelestiaVerifier, RollupParams};
 
     const ROLLUP_ROWS_JSON: &str = with_rollup_data::ROLLUP_ROWS_JSON;
@@ -359,6 +612,31 @@ mod tests {
         assert!(blobs.next().is_none());
     }
 
+    #[test]
+    fn test_check_sync_lag() {
+        assert!(check_sync_lag(100, 100, 0).is_ok());
+        assert!(check_sync_lag(98, 100, 2).is_ok());
+        // the node can see a block before its network head is updated
+        assert!(check_sync_lag(101, 100, 0).is_ok());
+
+        let error = check_sync_lag(90, 100, 2).unwrap_err().to_string();
+        assert!(error.contains("10 blocks behind"));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee() {
+        let (_mock_server, _config, mut da_service, _params) = setup_service(None).await;
+        da_service.fees.gas_price = 0.5;
+
+        let small = da_service.estimate_fee(10).await.unwrap();
+        let large = da_service.estimate_fee(100_000).await.unwrap();
+        assert_eq!(
+            small.0,
+            (get_gas_limit_for_bytes(10) as f64 * 0.5).ceil() as u128
+        );
+        assert!(large > small);
+    }
+
     // Last return value is namespace
     async fn setup_service(
         timeout_sec: Option<u64>,
@@ -372,6 +650,13 @@ mod tests {
             celestia_rpc_address: mock_server.uri(),
             max_celestia_response_body_size: 120_000,
             celestia_rpc_timeout_seconds: timeout_sec,
+            gas_price: 1.0,
+            gas_limit_multiplier: 1.0,
+            fee_bump_multiplier: 1.5,
+            inclusion_timeout_blocks: 5,
+            max_resubmissions: 3,
+            max_header_sync_lag: None,
+            light_node: None,
         };
         let rollup_batch_namespace = Namespace::new_v0(b"sov-test").unwrap();
         let rollup_proof_namespace = Namespace::new_v0(b"sov-proof").unwrap();
@@ -409,7 +694,7 @@ mod tests {
                 [JsonBlob::new(rollup_params.rollup_batch_namespace, blob.to_vec()).unwrap()],
                 {
                     "GasLimit": gas_limit,
-                    "Fee": gas_limit * GAS_PRICE,
+                    "Fee": gas_limit,
                 },
             ]
         });
@@ -668,6 +953,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn proof_blobs_are_verified_in_their_namespace() {
+        let block = with_rollup_data::filtered_block();
+        let proof_rows: NamespacedShares = serde_json::from_str(ROLLUP_ROWS_JSON).unwrap();
+
+        // the test block has a single blob, which we treat as a proof
+        let verifier = CelestiaVerifier::new(RollupParams {
+            rollup_batch_namespace: Namespace::new_v0(b"abc").unwrap(),
+            rollup_proof_namespace: ROLLUP_NAMESPACE,
+        });
+
+        let proofs = verifier
+            .verify_proof_blobs(&block.header, proof_rows)
+            .unwrap();
+
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].len(), 252);
+    }
+
+    #[test]
+    fn proof_blobs_verification_fails_if_rows_missing() {
+        let block = with_rollup_data::filtered_block();
+        let mut proof_rows: NamespacedShares = serde_json::from_str(ROLLUP_ROWS_JSON).unwrap();
+        proof_rows.rows.clear();
+
+        let verifier = CelestiaVerifier::new(RollupParams {
+            rollup_batch_namespace: Namespace::new_v0(b"abc").unwrap(),
+            rollup_proof_namespace: ROLLUP_NAMESPACE,
+        });
+
+        let error = verifier
+            .verify_proof_blobs(&block.header, proof_rows)
+            .unwrap_err();
+
+        assert!(matches!(error, ValidationError::InvalidRowProof));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not share the namespace of the batches")]
+    fn verifier_rejects_shared_namespace() {
+        CelestiaVerifier::new(RollupParams {
+            rollup_batch_namespace: ROLLUP_NAMESPACE,
+            rollup_proof_namespace: ROLLUP_NAMESPACE,
+        });
+    }
+
     #[tokio::test]
     async fn test_submit_proof() -> anyhow::Result<()> {
         let (mock_server, config, da_service, rollup_params) = setup_service(None).await;
@@ -683,7 +1014,7 @@ mod tests {
                 [JsonBlob::new(rollup_params.rollup_proof_namespace, zk_proof.to_vec()).unwrap()],
                 {
                     "GasLimit": gas_limit,
-                    "Fee": gas_limit * GAS_PRICE,
+                    "Fee": gas_limit,
                 },
             ]
         });
@@ -712,4 +1043,115 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_submit_blob_bumps_fee_when_not_included() -> anyhow::Result<()> {
+        let (mock_server, _config, mut da_service, rollup_params) = setup_service(None).await;
+        da_service.fees.inclusion_timeout_blocks = 1;
+        da_service.fees.inclusion_poll_interval = Duration::from_millis(50);
+
+        let blob: Vec<u8> = vec![1, 2, 3, 4, 5, 11, 12, 13, 14, 15];
+        let json_blob = JsonBlob::new(rollup_params.rollup_batch_namespace, blob.clone()).unwrap();
+        let gas_limit = get_gas_limit_for_bytes(blob.len());
+        let bumped_fee = (gas_limit as f64 * 1.5).ceil() as usize;
+
+        // A new block is produced each time the head of the network is fetched
+        let height = AtomicU64::new(100);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "header.NetworkHead" })))
+            .respond_with(move |req: &Request| {
+                let request: BasicJsonRpcRequest = serde_json::from_slice(&req.body).unwrap();
+                let mut header: serde_json::Value =
+                    serde_json::from_str(with_rollup_data::HEADER_JSON).unwrap();
+                header["header"]["height"] =
+                    json!(height.fetch_add(1, Ordering::Relaxed).to_string());
+                ResponseTemplate::new(200)
+                    .append_header("Content-Type", "application/json")
+                    .set_body_json(json!({
+                        "jsonrpc": "2.0",
+                        "id": request.id,
+                        "result": header,
+                    }))
+            })
+            .mount(&mock_server)
+            .await;
+
+        // The first submission is never included
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "blob.Submit",
+                "params": [[json_blob], { "GasLimit": gas_limit, "Fee": gas_limit }],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "blob.Submit",
+                "params": [[json_blob], { "GasLimit": gas_limit, "Fee": bumped_fee }],
+            })))
+            .respond_with(|req: &Request| {
+                let request: BasicJsonRpcRequest = serde_json::from_slice(&req.body).unwrap();
+                ResponseTemplate::new(200)
+                    .append_header("Content-Type", "application/json")
+                    .set_body_json(json!({
+                        "jsonrpc": "2.0",
+                        "id": request.id,
+                        "result": 14, // just some block-height
+                    }))
+            })
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        da_service.send_transaction(&blob).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_blob_fails_after_max_resubmissions() -> anyhow::Result<()> {
+        let (mock_server, _config, mut da_service, _rollup_params) = setup_service(None).await;
+        da_service.fees.inclusion_timeout_blocks = 1;
+        da_service.fees.inclusion_poll_interval = Duration::from_millis(50);
+        da_service.fees.max_resubmissions = 1;
+
+        let height = AtomicU64::new(100);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "header.NetworkHead" })))
+            .respond_with(move |req: &Request| {
+                let request: BasicJsonRpcRequest = serde_json::from_slice(&req.body).unwrap();
+                let mut header: serde_json::Value =
+                    serde_json::from_str(with_rollup_data::HEADER_JSON).unwrap();
+                header["header"]["height"] =
+                    json!(height.fetch_add(1, Ordering::Relaxed).to_string());
+                ResponseTemplate::new(200)
+                    .append_header("Content-Type", "application/json")
+                    .set_body_json(json!({
+                        "jsonrpc": "2.0",
+                        "id": request.id,
+                        "result": header,
+                    }))
+            })
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "blob.Submit" })))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let error = da_service
+            .send_transaction(&[1, 2, 3])
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("wasn't included after 2 submissions"));
+        Ok(())
+    }
 }
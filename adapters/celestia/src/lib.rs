@@ -1,6 +1,10 @@
 pub mod celestia;
 #[cfg(feature = "native")]
 mod da_service;
+#[cfg(feature = "native")]
+mod light_node;
+#[cfg(feature = "native")]
+mod metrics;
 pub mod shares;
 pub mod types;
 mod utils;
@@ -8,5 +12,7 @@ pub mod verifier;
 
 #[cfg(feature = "native")]
 pub use da_service::{CelestiaConfig, CelestiaService};
+#[cfg(feature = "native")]
+pub use light_node::LightNodeConfig;
 
 pub use crate::celestia::*;
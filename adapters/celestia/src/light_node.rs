@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::{bail, Context};
+use tokio::process::{Child, Command};
+use tracing::info;
+
+use crate::utils::BoxError;
+
+/// Configuration of a Celestia light node managed by the [`CelestiaService`](crate::CelestiaService),
+/// for rollups deployed without a separately operated Celestia node.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct LightNodeConfig {
+    /// The path of the `celestia` binary
+    #[serde(default = "default_binary_path")]
+    pub binary_path: PathBuf,
+    /// The Celestia network joined by the light node, e.g. `celestia`, `mocha` or `arabica`
+    pub network: String,
+    /// The address of the consensus node the light node submits the blobs to
+    pub core_ip: String,
+    /// The directory of the node store. The default store of the network is used if unset
+    #[serde(default)]
+    pub store_path: Option<PathBuf>,
+    /// The port of the RPC server of the light node, which only listens on localhost
+    #[serde(default = "default_rpc_port")]
+    pub rpc_port: u16,
+    /// Additional arguments passed to `celestia light start`
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// The time given to the light node to serve its RPC after being started, in seconds
+    #[serde(default = "default_startup_timeout_seconds")]
+    pub startup_timeout_seconds: u64,
+}
+
+fn default_binary_path() -> PathBuf {
+    "celestia".into()
+}
+
+const fn default_rpc_port() -> u16 {
+    26658
+}
+
+const fn default_startup_timeout_seconds() -> u64 {
+    120
+}
+
+impl LightNodeConfig {
+    /// The address of the RPC server of the light node.
+    pub fn rpc_address(&self) -> String {
+        format!("http://127.0.0.1:{}", self.rpc_port)
+    }
+
+    fn args(&self, command: &[&str]) -> Vec<String> {
+        let mut args: Vec<String> = ["light"]
+            .iter()
+            .chain(command)
+            .map(|arg| arg.to_string())
+            .collect();
+        args.extend(["--p2p.network".to_string(), self.network.clone()]);
+        if let Some(store_path) = &self.store_path {
+            args.extend(["--node.store".to_string(), store_path.display().to_string()]);
+        }
+        args
+    }
+
+    fn init_args(&self) -> Vec<String> {
+        self.args(&["init"])
+    }
+
+    fn auth_args(&self) -> Vec<String> {
+        self.args(&["auth", "admin"])
+    }
+
+    fn start_args(&self) -> Vec<String> {
+        let mut args = self.args(&["start"]);
+        args.extend([
+            "--core.ip".to_string(),
+            self.core_ip.clone(),
+            "--rpc.addr".to_string(),
+            "127.0.0.1".to_string(),
+            "--rpc.port".to_string(),
+            self.rpc_port.to_string(),
+        ]);
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+}
+
+/// A light node process, killed when dropped.
+#[derive(Debug)]
+pub(crate) struct LightNode {
+    _process: Child,
+}
+
+impl LightNode {
+    /// Initializes the store of the light node if needed, and starts it.
+    /// Returns the node with the JWT authenticating to its RPC server.
+    pub(crate) async fn start(config: &LightNodeConfig) -> Result<(Self, String), BoxError> {
+        run(config, config.init_args()).await?;
+        let auth_token = run(config, config.auth_args())
+            .await?
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .context("The light node didn't issue an auth token")?
+            .to_string();
+
+        info!(
+            "Starting a Celestia light node on the {} network, serving RPC at {}",
+            config.network,
+            config.rpc_address()
+        );
+        let process = Command::new(&config.binary_path)
+            .args(config.start_args())
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        Ok((Self { _process: process }, auth_token))
+    }
+}
+
+/// Runs `celestia` with `args` to completion, and returns its standard output.
+async fn run(config: &LightNodeConfig, args: Vec<String>) -> Result<String, BoxError> {
+    let output = Command::new(&config.binary_path)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        bail!(
+            "`{} {}` failed with {}: {}",
+            config.binary_path.display(),
+            args.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> LightNodeConfig {
+        LightNodeConfig {
+            binary_path: default_binary_path(),
+            network: "mocha".to_string(),
+            core_ip: "rpc-mocha.pops.one".to_string(),
+            store_path: Some("/tmp/celestia-light".into()),
+            rpc_port: 26000,
+            extra_args: vec!["--log.level".to_string(), "warn".to_string()],
+            startup_timeout_seconds: default_startup_timeout_seconds(),
+        }
+    }
+
+    #[test]
+    fn test_light_node_args() {
+        let config = config();
+
+        assert_eq!(
+            config.auth_args(),
+            [
+                "light",
+                "auth",
+                "admin",
+                "--p2p.network",
+                "mocha",
+                "--node.store",
+                "/tmp/celestia-light"
+            ]
+        );
+        assert_eq!(
+            config.start_args(),
+            [
+                "light",
+                "start",
+                "--p2p.network",
+                "mocha",
+                "--node.store",
+                "/tmp/celestia-light",
+                "--core.ip",
+                "rpc-mocha.pops.one",
+                "--rpc.addr",
+                "127.0.0.1",
+                "--rpc.port",
+                "26000",
+                "--log.level",
+                "warn"
+            ]
+        );
+        assert_eq!(config.rpc_address(), "http://127.0.0.1:26000");
+    }
+
+    #[test]
+    fn test_light_node_args_without_store() {
+        let config = LightNodeConfig {
+            store_path: None,
+            ..config()
+        };
+
+        assert_eq!(
+            config.init_args(),
+            ["light", "init", "--p2p.network", "mocha"]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, HistogramVec,
+    IntCounterVec,
+};
+
+pub static CELESTIA_BLOB_INCLUSION_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "celestia_blob_inclusion_latency_seconds",
+        // metric description
+        "Time from the first submission of a blob to its inclusion in a block, in seconds",
+        // metric labels (dimensions)
+        &["kind"],
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 12).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static CELESTIA_BLOB_SUBMISSIONS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "celestia_blob_submissions",
+        // metric description
+        "Number of submissions needed to include a blob in a block",
+        // metric labels (dimensions)
+        &["kind"],
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 8.0, 13.0],
+    )
+    .unwrap()
+});
+
+pub static CELESTIA_BLOB_FEE_BUMPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "celestia_blob_fee_bumps",
+        "Resubmissions of blobs at a higher fee, after they weren't included in time",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+pub static CELESTIA_BLOB_FAILED_SUBMISSIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "celestia_blob_failed_submissions",
+        "Blobs which weren't included after the maximum number of resubmissions",
+        &["kind"]
+    )
+    .unwrap()
+});
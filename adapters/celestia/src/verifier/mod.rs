@@ -24,7 +24,11 @@ use crate::utils::read_varint;
 use crate::{pfb_from_iter, BlobWithSender, CelestiaHeader};
 
 pub struct CelestiaVerifier {
+    /// The namespace of the rollup batches.
     pub rollup_namespace: Namespace,
+    /// The namespace of the aggregated proofs and attestations, which must differ from
+    /// the batch namespace.
+    pub rollup_proof_namespace: Namespace,
 }
 
 pub const PFB_NAMESPACE: Namespace = Namespace::const_v0([0, 0, 0, 0, 0, 0, 0, 0, 0, 4]);
@@ -169,8 +173,13 @@ impl da::DaVerifier for CelestiaVerifier {
     type Error = ValidationError;
 
     fn new(params: <Self::Spec as DaSpec>::ChainParams) -> Self {
+        assert_ne!(
+            params.rollup_batch_namespace, params.rollup_proof_namespace,
+            "The proofs must not share the namespace of the batches"
+        );
         Self {
             rollup_namespace: params.rollup_batch_namespace,
+            rollup_proof_namespace: params.rollup_proof_namespace,
         }
     }
 
@@ -312,27 +321,53 @@ impl CelestiaVerifier {
         row_proofs: NamespacedShares,
         dah: &DataAvailabilityHeader,
     ) -> Result<NamespaceGroup, ValidationError> {
-        let mut row_proofs = row_proofs.rows.into_iter();
-        // Check the validity and completeness of the rollup share proofs
-        let mut verified_shares = Vec::new();
-        for row_root in dah.row_roots.iter() {
-            // TODO: short circuit this loop at the first row after the rollup namespace
-            if row_root.contains(self.rollup_namespace.into()) {
-                let row_proof = row_proofs.next().ok_or(ValidationError::InvalidRowProof)?;
-                row_proof
-                    .proof
-                    .verify_complete_namespace(
-                        row_root,
-                        &row_proof.shares,
-                        self.rollup_namespace.into(),
-                    )
-                    .expect("Proofs must be valid");
-
-                for leaf in row_proof.shares {
-                    verified_shares.push(leaf)
-                }
+        verify_namespace_rows(self.rollup_namespace, row_proofs, dah)
+    }
+
+    /// Verifies that `proof_rows` contain all the shares of the proof namespace in the block
+    /// with the header `block_header`, and returns the data of the blobs they contain.
+    pub fn verify_proof_blobs(
+        &self,
+        block_header: &CelestiaHeader,
+        proof_rows: NamespacedShares,
+    ) -> Result<Vec<Vec<u8>>, ValidationError> {
+        block_header.validate_dah()?;
+        let verified_shares =
+            verify_namespace_rows(self.rollup_proof_namespace, proof_rows, &block_header.dah)?;
+
+        Ok(verified_shares
+            .blobs()
+            .map(|blob| {
+                let mut blob_iter = blob.data();
+                let mut blob_data = vec![0; blob_iter.remaining()];
+                blob_iter.copy_to_slice(blob_data.as_mut_slice());
+                blob_data
+            })
+            .collect())
+    }
+}
+
+/// Checks the validity and completeness of the share proofs of `namespace` against the DAH.
+fn verify_namespace_rows(
+    namespace: Namespace,
+    row_proofs: NamespacedShares,
+    dah: &DataAvailabilityHeader,
+) -> Result<NamespaceGroup, ValidationError> {
+    let mut row_proofs = row_proofs.rows.into_iter();
+    let mut verified_shares = Vec::new();
+    for row_root in dah.row_roots.iter() {
+        // TODO: short circuit this loop at the first row after the namespace
+        if row_root.contains(namespace.into()) {
+            let row_proof = row_proofs.next().ok_or(ValidationError::InvalidRowProof)?;
+            row_proof
+                .proof
+                .verify_complete_namespace(row_root, &row_proof.shares, namespace.into())
+                .expect("Proofs must be valid");
+
+            for leaf in row_proof.shares {
+                verified_shares.push(leaf)
             }
         }
-        Ok(NamespaceGroup::from_shares(verified_shares))
     }
+    Ok(NamespaceGroup::from_shares(verified_shares))
 }
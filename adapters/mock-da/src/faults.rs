@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+/// Faults injected by the [`MockDaService`](crate::MockDaService), to test how the rollup handles
+/// a misbehaving DA layer. The faults are drawn from a generator seeded with `seed`,
+/// so a test sees the same faults on every run.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MockDaFaults {
+    /// The seed of the generator the faults are drawn from.
+    pub seed: u64,
+    /// The delay of each read of a block, simulating a slow DA node.
+    pub read_latency: Duration,
+    /// The probability that a submitted blob is dropped: its block is produced without it.
+    pub drop_probability: f64,
+    /// The probability that a submitted blob is delivered twice in its block.
+    pub duplicate_probability: f64,
+    /// The probability that the chain is reorganized after a block is produced.
+    pub reorg_probability: f64,
+    /// The maximum number of blocks replaced by a reorg. The depth of each reorg is drawn
+    /// between 1 and this, and is capped by the number of blocks which aren't finalized.
+    pub max_reorg_depth: u64,
+}
+
+/// The faults of a [`MockDaService`](crate::MockDaService), with the generator they are drawn from.
+pub(crate) struct FaultInjector {
+    pub(crate) faults: MockDaFaults,
+    rng: SplitMix64,
+    /// The number of reorgs so far, which makes the hashes of the replaced blocks unique.
+    pub(crate) reorg_count: u64,
+}
+
+impl FaultInjector {
+    pub(crate) fn new(faults: MockDaFaults) -> Self {
+        Self {
+            rng: SplitMix64(faults.seed),
+            faults,
+            reorg_count: 0,
+        }
+    }
+
+    /// Returns how many times a submitted blob is delivered: 0 if dropped, 2 if duplicated.
+    pub(crate) fn deliveries(&mut self) -> usize {
+        if self.rng.chance(self.faults.drop_probability) {
+            0
+        } else if self.rng.chance(self.faults.duplicate_probability) {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Returns the depth of the reorg following the production of a block, if any,
+    /// when `reorgable` blocks aren't finalized.
+    pub(crate) fn reorg_depth(&mut self, reorgable: u64) -> Option<u64> {
+        let max_depth = self.faults.max_reorg_depth.min(reorgable);
+        if max_depth == 0 || !self.rng.chance(self.faults.reorg_probability) {
+            return None;
+        }
+        Some(1 + self.rng.next_u64() % max_depth)
+    }
+}
+
+/// A small deterministic generator, see <https://prng.di.unimi.it/splitmix64.c>.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns true with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        // The 53 high bits, uniformly distributed in [0, 1)
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faults_are_deterministic() {
+        let faults = MockDaFaults {
+            seed: 42,
+            drop_probability: 0.3,
+            duplicate_probability: 0.3,
+            reorg_probability: 0.5,
+            max_reorg_depth: 3,
+            ..Default::default()
+        };
+        let draw = |faults: &MockDaFaults| {
+            let mut injector = FaultInjector::new(faults.clone());
+            (0..100)
+                .map(|_| (injector.deliveries(), injector.reorg_depth(5)))
+                .collect::<Vec<_>>()
+        };
+
+        let draws = draw(&faults);
+        assert_eq!(draws, draw(&faults));
+        assert_ne!(
+            draws,
+            draw(&MockDaFaults {
+                seed: 43,
+                ..faults.clone()
+            })
+        );
+
+        assert!(draws.iter().any(|(deliveries, _)| *deliveries == 0));
+        assert!(draws.iter().any(|(deliveries, _)| *deliveries == 2));
+        assert!(draws
+            .iter()
+            .filter_map(|(_, depth)| *depth)
+            .all(|depth| (1..=3).contains(&depth)));
+    }
+
+    #[test]
+    fn test_no_faults_by_default() {
+        let mut injector = FaultInjector::new(MockDaFaults::default());
+        for _ in 0..100 {
+            assert_eq!(injector.deliveries(), 1);
+            assert_eq!(injector.reorg_depth(10), None);
+        }
+    }
+
+    #[test]
+    fn test_reorg_depth_is_capped() {
+        let mut injector = FaultInjector::new(MockDaFaults {
+            reorg_probability: 1.0,
+            max_reorg_depth: 10,
+            ..Default::default()
+        });
+        for _ in 0..100 {
+            assert!(injector.reorg_depth(2).unwrap() <= 2);
+        }
+        assert_eq!(injector.reorg_depth(0), None);
+    }
+}
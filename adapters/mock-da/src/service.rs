@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -9,10 +10,11 @@ use pin_project::pin_project;
 use sha2::Digest;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, Time};
 use sov_rollup_interface::maybestd::sync::Arc;
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, Fee, SlotData};
 use tokio::sync::{broadcast, RwLock, RwLockWriteGuard};
 use tokio::time;
 
+use crate::faults::{FaultInjector, MockDaFaults};
 use crate::types::{MockAddress, MockBlob, MockBlock, MockDaVerifier};
 use crate::verifier::MockDaSpec;
 use crate::{MockBlockHeader, MockHash};
@@ -56,9 +58,22 @@ impl PlannedFork {
     }
 }
 
+/// Creates the channel of the finalized headers.
+fn finalized_header_channel() -> broadcast::Sender<MockBlockHeader> {
+    let (tx, rx1) = broadcast::channel(16);
+    // Spawn a task, so channel is never closed
+    tokio::spawn(async move {
+        let mut rx = rx1;
+        while let Ok(header) = rx.recv().await {
+            tracing::debug!("Finalized MockHeader: {}", header);
+        }
+    });
+    tx
+}
+
 #[derive(Clone)]
 /// DaService used in tests.
-/// Currently only supports single blob per block.
+/// Each submitted blob is delivered in its own block, unless faults are injected with [`MockDaFaults`].
 /// Height of the first submitted block is 1.
 /// Submitted blocks are kept indefinitely in memory.
 pub struct MockDaService {
@@ -67,9 +82,14 @@ pub struct MockDaService {
     /// How many blocks should be submitted, before block is finalized. 0 means instant finality.
     blocks_to_finality: u32,
     /// Used for calculating correct finality from state of `blocks`
-    finalized_header_sender: broadcast::Sender<MockBlockHeader>,
+    finalized_header_sender: Arc<Mutex<broadcast::Sender<MockBlockHeader>>>,
     wait_attempts: usize,
     planned_fork: Arc<Mutex<Option<PlannedFork>>>,
+    faults: Arc<Mutex<FaultInjector>>,
+    /// The last finalized height, while finality is paused.
+    finality_paused_at: Arc<Mutex<Option<u64>>>,
+    /// The fee of each byte of a submitted blob, returned by the fee estimates.
+    fee_per_byte: Arc<AtomicU64>,
 }
 
 impl MockDaService {
@@ -80,24 +100,41 @@ impl MockDaService {
 
     /// Create a new [`MockDaService`] with given finality.
     pub fn with_finality(sequencer_da_address: MockAddress, blocks_to_finality: u32) -> Self {
-        let (tx, rx1) = broadcast::channel(16);
-        // Spawn a task, so channel is never closed
-        tokio::spawn(async move {
-            let mut rx = rx1;
-            while let Ok(header) = rx.recv().await {
-                tracing::debug!("Finalized MockHeader: {}", header);
-            }
-        });
         Self {
             sequencer_da_address,
             blocks: Arc::new(Default::default()),
             blocks_to_finality,
-            finalized_header_sender: tx,
+            finalized_header_sender: Arc::new(Mutex::new(finalized_header_channel())),
             wait_attempts: 100_0000,
             planned_fork: Arc::new(Mutex::new(None)),
+            faults: Arc::new(Mutex::new(FaultInjector::new(Default::default()))),
+            finality_paused_at: Arc::new(Mutex::new(None)),
+            fee_per_byte: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Create a new [`MockDaService`] with given finality, which injects `faults`.
+    pub fn with_faults(
+        sequencer_da_address: MockAddress,
+        blocks_to_finality: u32,
+        faults: MockDaFaults,
+    ) -> Self {
+        let da_service = Self::with_finality(sequencer_da_address, blocks_to_finality);
+        da_service.set_faults(faults);
+        da_service
+    }
+
+    /// Replaces the injected faults, and reseeds the generator they are drawn from.
+    pub fn set_faults(&self, faults: MockDaFaults) {
+        *self.faults.lock().unwrap() = FaultInjector::new(faults);
+    }
+
+    /// Sets the fee of each byte of a submitted blob, to simulate the DA prices going up or down.
+    /// The fees are only estimated: submitting a blob is free.
+    pub fn set_fee_per_byte(&self, fee_per_byte: u64) {
+        self.fee_per_byte.store(fee_per_byte, Ordering::Relaxed);
+    }
+
     /// Get sequencer address
     pub fn get_sequencer_address(&self) -> MockAddress {
         self.sequencer_da_address
@@ -146,13 +183,88 @@ impl MockDaService {
         blocks.retain(|b| b.header().height <= height);
         for blob in blobs {
             let _ = self
-                .add_blob(&blob, Default::default(), &mut blocks)
+                .add_blob(&blob, Default::default(), 1, &mut blocks)
                 .await?;
         }
 
         Ok(())
     }
 
+    /// Replaces the last `depth` blocks with blocks holding the same blobs but with different hashes,
+    /// as if the chain was reorganized. Fails if any of the replaced blocks is finalized.
+    pub async fn reorg(&self, depth: u64) -> anyhow::Result<()> {
+        let mut blocks = self.blocks.write().await;
+        self.reorg_blocks(depth, &mut blocks).await
+    }
+
+    async fn reorg_blocks(
+        &self,
+        depth: u64,
+        blocks: &mut VecDeque<MockBlock>,
+    ) -> anyhow::Result<()> {
+        let last_finalized_height = self.get_last_finalized_height(blocks).await;
+        let head_height = blocks.back().map_or(0, |b| b.header().height);
+        if head_height.saturating_sub(last_finalized_height) < depth {
+            anyhow::bail!(
+                "Cannot reorg {} blocks, last finalized height is {} and head height is {}",
+                depth,
+                last_finalized_height,
+                head_height
+            );
+        }
+        tracing::debug!("Reorganizing the last {} blocks", depth);
+
+        let salt = {
+            let mut faults = self.faults.lock().unwrap();
+            faults.reorg_count += 1;
+            hash_to_array(&faults.reorg_count.to_be_bytes())
+        };
+        for index in blocks.len() - depth as usize..blocks.len() {
+            let prev_hash = match index {
+                0 => GENESIS_HEADER.hash(),
+                _ => blocks[index - 1].header().hash(),
+            };
+            let header = &mut blocks[index].header;
+            header.hash = block_hash(header.height, header.hash.into(), salt, prev_hash.into());
+            header.prev_hash = prev_hash;
+            header.time = Time::now();
+        }
+        Ok(())
+    }
+
+    /// Stops finalizing blocks until [`MockDaService::resume_finality`] is called, simulating a delayed finality.
+    pub async fn pause_finality(&self) {
+        let blocks = self.blocks.read().await;
+        let last_finalized_height = self.get_last_finalized_height(&blocks).await;
+        self.finality_paused_at
+            .lock()
+            .unwrap()
+            .get_or_insert(last_finalized_height);
+    }
+
+    /// Finalizes the blocks which would have been finalized while finality was paused.
+    pub async fn resume_finality(&self) {
+        let blocks = self.blocks.read().await;
+        let paused_at = self.finality_paused_at.lock().unwrap().take();
+        let Some(paused_at) = paused_at else {
+            return;
+        };
+        let last_finalized_height = self.get_last_finalized_height(&blocks).await;
+        for height in paused_at + 1..=last_finalized_height {
+            self.finalized_header_sender
+                .lock()
+                .unwrap()
+                .send(blocks[height as usize - 1].header().clone())
+                .unwrap();
+        }
+    }
+
+    /// Closes the streams of the finalized headers returned by [`DaService::subscribe_finalized_header`] so far,
+    /// as if the connection to the DA node was lost. Later subscriptions stream the next finalized headers.
+    pub fn close_finalized_header_subscriptions(&self) {
+        *self.finalized_header_sender.lock().unwrap() = finalized_header_channel();
+    }
+
     /// Set planned fork, that will be executed at specified height
     pub async fn set_planned_fork(&self, planned_fork: PlannedFork) -> anyhow::Result<()> {
         let last_finalized_height = {
@@ -173,20 +285,39 @@ impl MockDaService {
         Ok(())
     }
 
-    async fn get_last_finalized_height(
-        &self,
-        blocks: &RwLockWriteGuard<'_, VecDeque<MockBlock>>,
-    ) -> u64 {
-        blocks
+    async fn get_last_finalized_height(&self, blocks: &VecDeque<MockBlock>) -> u64 {
+        let last_finalized_height = blocks
             .len()
             .checked_sub(self.blocks_to_finality as usize)
-            .unwrap_or_default() as u64
+            .unwrap_or_default() as u64;
+        match *self.finality_paused_at.lock().unwrap() {
+            Some(paused_at) => last_finalized_height.min(paused_at),
+            None => last_finalized_height,
+        }
+    }
+
+    /// Adds a block for a submitted blob, injecting the faults.
+    async fn submit_blob(&self, blob: &[u8], zkp_proof: Vec<u8>) -> anyhow::Result<u64> {
+        let mut blocks = self.blocks.write().await;
+        let deliveries = self.faults.lock().unwrap().deliveries();
+        let height = self
+            .add_blob(blob, zkp_proof, deliveries, &mut blocks)
+            .await?;
+
+        let reorgable = height - self.get_last_finalized_height(&blocks).await;
+        let reorg_depth = self.faults.lock().unwrap().reorg_depth(reorgable);
+        if let Some(depth) = reorg_depth {
+            self.reorg_blocks(depth, &mut blocks).await?;
+        }
+        Ok(height)
     }
 
+    /// Adds a block holding `deliveries` copies of the blob.
     async fn add_blob(
         &self,
         blob: &[u8],
         zkp_proof: Vec<u8>,
+        deliveries: usize,
         blocks: &mut RwLockWriteGuard<'_, VecDeque<MockBlock>>,
     ) -> anyhow::Result<u64> {
         let (previous_block_hash, height) = match blocks.iter().last().map(|b| b.header().clone()) {
@@ -214,15 +345,18 @@ impl MockDaService {
         let block = MockBlock {
             header,
             validity_cond: Default::default(),
-            blobs: vec![blob],
+            blobs: vec![blob; deliveries],
         };
         blocks.push_back(block);
 
         // Enough blocks to finalize block
-        if blocks.len() > self.blocks_to_finality as usize {
+        let finality_paused = self.finality_paused_at.lock().unwrap().is_some();
+        if blocks.len() > self.blocks_to_finality as usize && !finality_paused {
             let next_index_to_finalize = blocks.len() - self.blocks_to_finality as usize - 1;
             let next_finalized_header = blocks[next_index_to_finalize].header().clone();
             self.finalized_header_sender
+                .lock()
+                .unwrap()
                 .send(next_finalized_header)
                 .unwrap();
         }
@@ -295,6 +429,10 @@ impl DaService for MockDaService {
         if height == 0 {
             anyhow::bail!("The lowest queryable block should be > 0");
         }
+        let read_latency = self.faults.lock().unwrap().faults.read_latency;
+        if !read_latency.is_zero() {
+            time::sleep(read_latency).await;
+        }
         // Fork logic
         self.planned_fork_handler(height).await?;
         // Block until there's something
@@ -315,18 +453,17 @@ impl DaService for MockDaService {
     async fn get_last_finalized_block_header(
         &self,
     ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
-        let blocks_len = { self.blocks.read().await.len() };
-        if blocks_len < self.blocks_to_finality as usize + 1 {
+        let blocks = self.blocks.read().await;
+        let last_finalized_height = self.get_last_finalized_height(&blocks).await;
+        if last_finalized_height == 0 {
             return Ok(GENESIS_HEADER);
         }
 
-        let blocks = self.blocks.read().await;
-        let index = blocks_len - self.blocks_to_finality as usize - 1;
-        Ok(blocks[index].header().clone())
+        Ok(blocks[last_finalized_height as usize - 1].header().clone())
     }
 
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
-        let receiver = self.finalized_header_sender.subscribe();
+        let receiver = self.finalized_header_sender.lock().unwrap().subscribe();
         Ok(MockDaBlockHeaderStream::new(receiver))
     }
 
@@ -361,20 +498,27 @@ impl DaService for MockDaService {
     }
 
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
-        let mut blocks = self.blocks.write().await;
-        let _ = self.add_blob(blob, Default::default(), &mut blocks).await?;
+        let _ = self.submit_blob(blob, Default::default()).await?;
         Ok(())
     }
 
     async fn send_aggregated_zk_proof(&self, proof: &[u8]) -> Result<u64, Self::Error> {
-        let mut blocks = self.blocks.write().await;
-        self.add_blob(Default::default(), proof.to_vec(), &mut blocks)
-            .await
+        self.submit_blob(Default::default(), proof.to_vec()).await
     }
 
     async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
         let blobs = self.get_block_at(height).await?.blobs;
-        Ok(blobs.into_iter().map(|b| b.zk_proofs_data).collect())
+        // Only the blobs submitted with `send_aggregated_zk_proof` carry proof data
+        Ok(blobs
+            .into_iter()
+            .map(|b| b.zk_proofs_data)
+            .filter(|proof| !proof.is_empty())
+            .collect())
+    }
+
+    async fn estimate_fee(&self, blob_size: usize) -> Result<Fee, Self::Error> {
+        let fee_per_byte = self.fee_per_byte.load(Ordering::Relaxed);
+        Ok(Fee(blob_size as u128 * fee_per_byte as u128))
     }
 }
 
@@ -627,6 +771,9 @@ mod tests {
         let proofs = da.get_aggregated_proofs_at(height).await?;
 
         assert_eq!(vec![aggregated_proof_data], proofs);
+
+        let height = da.send_transaction(&[4, 5, 6]).await?;
+        assert!(da.get_aggregated_proofs_at(height).await?.is_empty());
         Ok(())
     }
 
@@ -794,6 +941,152 @@ mod tests {
         }
     }
 
+    mod fault_injection {
+        use super::*;
+        use crate::MockDaFaults;
+
+        async fn chain_hashes(da: &MockDaService, num_blocks: u64) -> Vec<MockHash> {
+            let mut hashes = Vec::new();
+            let mut prev_hash = GENESIS_HEADER.hash();
+            for height in 1..=num_blocks {
+                let block = da.get_block_at(height).await.unwrap();
+                assert_eq!(prev_hash, block.header().prev_hash());
+                prev_hash = block.header().hash();
+                hashes.push(prev_hash);
+            }
+            hashes
+        }
+
+        #[tokio::test]
+        async fn test_dropped_and_duplicated_blobs() {
+            let mut da = MockDaService::with_faults(
+                MockAddress::new([1; 32]),
+                0,
+                MockDaFaults {
+                    drop_probability: 1.0,
+                    ..Default::default()
+                },
+            );
+            da.wait_attempts = 2;
+
+            da.send_transaction(&[1, 2, 3, 4]).await.unwrap();
+            let block_1 = da.get_block_at(1).await.unwrap();
+            assert!(block_1.blobs.is_empty());
+
+            da.set_faults(MockDaFaults {
+                duplicate_probability: 1.0,
+                ..Default::default()
+            });
+            da.send_transaction(&[5, 6, 7, 8]).await.unwrap();
+            let mut block_2 = da.get_block_at(2).await.unwrap();
+            assert_eq!(2, block_2.blobs.len());
+            assert_eq!(block_2.blobs[0], block_2.blobs[1]);
+            assert_eq!(&[5u8, 6, 7, 8], block_2.blobs[0].full_data());
+            assert_consecutive_blocks(&block_1, &block_2);
+        }
+
+        #[tokio::test]
+        async fn test_random_reorgs_are_deterministic() {
+            let faults = MockDaFaults {
+                seed: 7,
+                reorg_probability: 0.5,
+                max_reorg_depth: 3,
+                ..Default::default()
+            };
+            let da = MockDaService::with_faults(MockAddress::new([1; 32]), 3, faults.clone());
+            let same_seed_da = MockDaService::with_faults(MockAddress::new([1; 32]), 3, faults);
+            let honest_da = MockDaService::with_finality(MockAddress::new([1; 32]), 3);
+
+            for i in 0..10u8 {
+                da.send_transaction(&[i; 4]).await.unwrap();
+                same_seed_da.send_transaction(&[i; 4]).await.unwrap();
+                honest_da.send_transaction(&[i; 4]).await.unwrap();
+            }
+
+            let hashes = chain_hashes(&da, 10).await;
+            assert_eq!(hashes, chain_hashes(&same_seed_da, 10).await);
+            assert_ne!(hashes, chain_hashes(&honest_da, 10).await);
+
+            // Reorgs keep the blobs of the replaced blocks
+            for i in 0..10u8 {
+                let mut block = da.get_block_at(i as u64 + 1).await.unwrap();
+                assert_eq!(&[i; 4], block.blobs[0].full_data());
+            }
+        }
+
+        #[tokio::test]
+        async fn test_reorg() {
+            let da = MockDaService::with_finality(MockAddress::new([1; 32]), 2);
+
+            for i in 0..4u8 {
+                da.send_transaction(&[i; 4]).await.unwrap();
+            }
+            let hashes_before = chain_hashes(&da, 4).await;
+
+            da.reorg(2).await.unwrap();
+            let hashes_after = chain_hashes(&da, 4).await;
+            assert_eq!(hashes_before[..2], hashes_after[..2]);
+            assert_ne!(hashes_before[2], hashes_after[2]);
+            assert_ne!(hashes_before[3], hashes_after[3]);
+
+            let result = da.reorg(3).await;
+            assert_eq!(
+                "Cannot reorg 3 blocks, last finalized height is 2 and head height is 4",
+                result.unwrap_err().to_string()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_paused_finality() {
+            let mut da = MockDaService::with_finality(MockAddress::new([1; 32]), 1);
+            let collector_handle = get_finalized_headers_collector(&mut da, 4).await;
+
+            da.send_transaction(&[1; 4]).await.unwrap();
+            da.send_transaction(&[2; 4]).await.unwrap();
+            assert_eq!(
+                1,
+                da.get_last_finalized_block_header().await.unwrap().height()
+            );
+
+            da.pause_finality().await;
+            da.send_transaction(&[3; 4]).await.unwrap();
+            da.send_transaction(&[4; 4]).await.unwrap();
+            assert_eq!(
+                1,
+                da.get_last_finalized_block_header().await.unwrap().height()
+            );
+            // Blocks which would have been finalized can be reorganized
+            da.reorg(3).await.unwrap();
+
+            da.resume_finality().await;
+            assert_eq!(
+                3,
+                da.get_last_finalized_block_header().await.unwrap().height()
+            );
+            da.send_transaction(&[5; 4]).await.unwrap();
+
+            let received = collector_handle.await.unwrap();
+            let heights: Vec<u64> = received.iter().map(|h| h.height()).collect();
+            assert_eq!(vec![1, 2, 3, 4], heights);
+        }
+
+        #[tokio::test]
+        async fn test_closed_finalized_header_subscriptions() {
+            let da = MockDaService::new(MockAddress::new([1; 32]));
+            let mut closed = da.subscribe_finalized_header().await.unwrap();
+            da.send_transaction(&[1; 4]).await.unwrap();
+
+            da.close_finalized_header_subscriptions();
+            let mut resubscribed = da.subscribe_finalized_header().await.unwrap();
+            da.send_transaction(&[2; 4]).await.unwrap();
+
+            // The closed stream only yields the headers finalized before it was closed
+            assert_eq!(1, closed.next().await.unwrap().unwrap().height());
+            assert!(closed.next().await.is_none());
+            assert_eq!(2, resubscribed.next().await.unwrap().unwrap().height());
+        }
+    }
+
     fn assert_consecutive_blocks(block1: &MockBlock, block2: &MockBlock) {
         assert_eq!(block2.header().prev_hash(), block1.header().hash())
     }
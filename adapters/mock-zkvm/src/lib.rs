@@ -9,7 +9,9 @@ use anyhow::ensure;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use sov_rollup_interface::da::BlockHeaderTrait;
-use sov_rollup_interface::zk::{Matches, StateTransitionData, ValidityCondition};
+use sov_rollup_interface::zk::{
+    Matches, Proof, RecursiveZkvmGuest, RecursiveZkvmHost, StateTransitionData, ValidityCondition,
+};
 
 /// A mock commitment to a particular zkVM program.
 #[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -97,6 +99,7 @@ impl Notifier {
 pub struct MockZkvm<ValidityCond> {
     worker_thread_notifier: Notifier,
     committed_data: VecDeque<Vec<u8>>,
+    proof_hints: VecDeque<Vec<u8>>,
     validity_condition: ValidityCond,
 }
 
@@ -106,6 +109,7 @@ impl<ValidityCond> MockZkvm<ValidityCond> {
         Self {
             worker_thread_notifier: Default::default(),
             committed_data: Default::default(),
+            proof_hints: Default::default(),
             validity_condition,
         }
     }
@@ -145,6 +149,17 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::Zkvm for MockZkv
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(bincode::deserialize(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
 }
 
 impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
@@ -164,7 +179,9 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
     }
 
     fn simulate_with_hints(&mut self) -> Self::Guest {
-        MockZkGuest {}
+        MockZkGuest {
+            proof_hints: Mutex::new(std::mem::take(&mut self.proof_hints)),
+        }
     }
 
     fn run(&mut self, _with_proof: bool) -> Result<sov_rollup_interface::zk::Proof, anyhow::Error> {
@@ -198,8 +215,26 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
     }
 }
 
+impl<ValidityCond: ValidityCondition> RecursiveZkvmHost for MockZkvm<ValidityCond> {
+    fn add_proof_hint(&mut self, proof: &Proof) -> Result<(), anyhow::Error> {
+        match proof {
+            // The full proofs given to the mock are expected to be encoded `MockProof`s.
+            Proof::Full(data) => {
+                self.proof_hints.push_back(data.clone());
+                Ok(())
+            }
+            Proof::PublicInput(_) => {
+                anyhow::bail!("Only full proofs can be verified recursively")
+            }
+        }
+    }
+}
+
 /// A mock implementing the Guest.
-pub struct MockZkGuest {}
+#[derive(Default)]
+pub struct MockZkGuest {
+    proof_hints: Mutex<VecDeque<Vec<u8>>>,
+}
 
 impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     type CodeCommitment = MockCodeCommitment;
@@ -222,6 +257,16 @@ impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
         unimplemented!()
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        unimplemented!()
+    }
 }
 
 impl sov_rollup_interface::zk::ZkvmGuest for MockZkGuest {
@@ -234,6 +279,38 @@ impl sov_rollup_interface::zk::ZkvmGuest for MockZkGuest {
     }
 }
 
+impl RecursiveZkvmGuest for MockZkGuest {
+    fn verify_previous_proof(
+        &self,
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let serialized_proof = self
+            .proof_hints
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| anyhow::anyhow!("The host provided no proof to verify"))?;
+        let proof = MockProof::decode(&serialized_proof)?;
+        anyhow::ensure!(
+            proof.program_id.matches(code_commitment),
+            "Proof failed to verify against requested code commitment"
+        );
+        anyhow::ensure!(proof.is_valid, "Proof is not valid");
+        Ok(proof.log.to_vec())
+    }
+
+    fn verify_previous_transition<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        &self,
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        let output = self.verify_previous_proof(code_commitment)?;
+        Ok(bincode::deserialize(&output)?)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ProofInfo<ValidityCond> {
     hint: Vec<u8>,
@@ -254,3 +331,35 @@ fn test_mock_proof_round_trip() {
     let decoded = MockProof::decode(&encoded).unwrap();
     assert_eq!(proof, decoded);
 }
+
+#[test]
+fn test_verify_previous_proof() {
+    let program_id = MockCodeCommitment([1; 32]);
+    let valid_proof = MockProof {
+        program_id: program_id.clone(),
+        is_valid: true,
+        log: &[2; 50],
+    };
+    let invalid_proof = MockProof {
+        is_valid: false,
+        ..valid_proof.clone()
+    };
+
+    let guest = MockZkGuest {
+        proof_hints: Mutex::new(VecDeque::from([
+            valid_proof.encode_to_vec(),
+            valid_proof.encode_to_vec(),
+            invalid_proof.encode_to_vec(),
+        ])),
+    };
+    assert_eq!(
+        vec![2; 50],
+        guest.verify_previous_proof(&program_id).unwrap()
+    );
+    assert!(guest
+        .verify_previous_proof(&MockCodeCommitment([3; 32]))
+        .is_err());
+    assert!(guest.verify_previous_proof(&program_id).is_err());
+    // All the proofs were consumed
+    assert!(guest.verify_previous_proof(&program_id).is_err());
+}
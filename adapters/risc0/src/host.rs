@@ -46,6 +46,16 @@ impl<'a> Risc0Host<'a> {
         }
     }
 
+    /// Returns the binary proven by this host.
+    pub(crate) fn elf(&self) -> &'a [u8] {
+        self.elf
+    }
+
+    /// Returns the hints accumulated so far, serialized as the input of the binary.
+    pub(crate) fn serialized_hints(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.env)
+    }
+
     /// Run a computation in the zkVM without generating a receipt.
     /// This creates the "Session" trace without invoking the heavy cryptographic machinery.
     pub fn run_without_proving(&mut self) -> anyhow::Result<Session> {
@@ -135,6 +145,17 @@ impl<'host> Zkvm for Risc0Host<'host> {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 /// A verifier for Risc0 proofs.
@@ -162,6 +183,17 @@ impl Zkvm for Risc0Verifier {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 fn verify_from_slice<'a>(
@@ -13,6 +13,8 @@ pub mod host;
 
 #[cfg(feature = "bench")]
 pub mod metrics;
+#[cfg(feature = "bonsai")]
+pub mod remote;
 
 /// Uniquely identifies a Risc0 binary. Roughly equivalent to
 /// the hash of the ELF file.
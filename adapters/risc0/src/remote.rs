@@ -0,0 +1,356 @@
+//! This module implements a [`ZkvmHost`] which generates the proofs on a remote proving service,
+//! the Bonsai API, instead of the local hardware.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use bonsai_sdk::alpha as bonsai;
+use risc0_zkvm::{compute_image_id, Receipt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::zk::{Proof, Zkvm, ZkvmHost};
+
+use crate::guest::Risc0Guest;
+use crate::host::Risc0Host;
+use crate::Risc0MethodId;
+
+/// The configuration of the remote proving service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteProverConfig {
+    /// The URL of the Bonsai API.
+    pub url: String,
+    /// The API key of the operator.
+    pub api_key: String,
+    /// The number of milliseconds between two polls of the status of a proving session.
+    pub poll_interval_ms: u64,
+    /// The number of seconds after which a proving session is abandoned.
+    pub timeout_secs: u64,
+}
+
+/// The status of a proving session on a remote proving service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStatus {
+    /// The status of the session, e.g. `RUNNING` or `SUCCEEDED`.
+    pub status: String,
+    /// The URL of the receipt, once the session succeeded.
+    pub receipt_url: Option<String>,
+    /// The error of the session, if it failed.
+    pub error_msg: Option<String>,
+}
+
+/// The operations of a remote proving service used by a [`RemoteProver`].
+pub trait ProvingService: Clone + Send + Sync {
+    /// Uploads the binary `elf` and its serialized hints `input`, and starts a proving session.
+    /// Returns the id of the session.
+    fn create_session(&self, elf: &[u8], input: &[u8]) -> anyhow::Result<String>;
+
+    /// Returns the status of the proving session `session_id`.
+    fn session_status(&self, session_id: &str) -> anyhow::Result<SessionStatus>;
+
+    /// Downloads the receipt at `receipt_url`.
+    fn download(&self, receipt_url: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// A [`ProvingService`] backed by the Bonsai API.
+#[derive(Clone)]
+pub struct BonsaiService {
+    client: Arc<bonsai::Client>,
+}
+
+impl BonsaiService {
+    /// Creates a client of the Bonsai API at `url`, authenticated by `api_key`.
+    pub fn new(url: String, api_key: String) -> anyhow::Result<Self> {
+        let client = bonsai::Client::from_parts(url, api_key, risc0_zkvm::VERSION)?;
+        Ok(Self {
+            client: Arc::new(client),
+        })
+    }
+}
+
+impl ProvingService for BonsaiService {
+    fn create_session(&self, elf: &[u8], input: &[u8]) -> anyhow::Result<String> {
+        let image_id = hex::encode(compute_image_id(elf)?);
+        self.client.upload_img(&image_id, elf.to_vec())?;
+        let input_id = self.client.upload_input(input.to_vec())?;
+        let session = self.client.create_session(image_id, input_id, vec![])?;
+        Ok(session.uuid)
+    }
+
+    fn session_status(&self, session_id: &str) -> anyhow::Result<SessionStatus> {
+        let status = bonsai::SessionId::new(session_id.to_owned()).status(&self.client)?;
+        Ok(SessionStatus {
+            status: status.status,
+            receipt_url: status.receipt_url,
+            error_msg: status.error_msg,
+        })
+    }
+
+    fn download(&self, receipt_url: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self.client.download(receipt_url)?)
+    }
+}
+
+/// A [`RemoteProver`] submits the binary of a [`Risc0Host`] and its hints to a remote proving service,
+/// polls the service until the proving session completes, and verifies the returned receipt locally.
+/// If the remote proving fails, the proof is generated locally by the [`Risc0Host`] instead.
+///
+/// Executing the binary without a proof and simulating it are always done locally.
+#[derive(Clone)]
+pub struct RemoteProver<'a, S = BonsaiService> {
+    host: Risc0Host<'a>,
+    config: RemoteProverConfig,
+    service: S,
+}
+
+impl<'a> RemoteProver<'a> {
+    /// Creates a new [`RemoteProver`] to prove the given binary on the Bonsai API described by `config`.
+    pub fn new(elf: &'a [u8], config: RemoteProverConfig) -> anyhow::Result<Self> {
+        let service = BonsaiService::new(config.url.clone(), config.api_key.clone())?;
+        Ok(Self::with_service(elf, config, service))
+    }
+}
+
+impl<'a, S: ProvingService> RemoteProver<'a, S> {
+    /// Creates a new [`RemoteProver`] to prove the given binary on `service`, polled as described by `config`.
+    pub fn with_service(elf: &'a [u8], config: RemoteProverConfig, service: S) -> Self {
+        Self {
+            host: Risc0Host::new(elf),
+            config,
+            service,
+        }
+    }
+
+    /// Proves the execution of the binary on the remote proving service, and verifies the receipt.
+    pub fn prove_remotely(&self) -> anyhow::Result<Receipt> {
+        let receipt = self.fetch_receipt()?;
+        let receipt: Receipt = bincode::deserialize(&receipt)
+            .context("The receipt returned by the remote prover is invalid")?;
+        let image_id = compute_image_id(self.host.elf())?;
+        receipt
+            .verify(image_id)
+            .context("The receipt returned by the remote prover is invalid")?;
+        Ok(receipt)
+    }
+
+    /// Submits a proving session and polls it until it succeeds, fails or times out.
+    /// Returns the serialized receipt of the session.
+    fn fetch_receipt(&self) -> anyhow::Result<Vec<u8>> {
+        let session = self
+            .service
+            .create_session(self.host.elf(), self.host.serialized_hints())?;
+        tracing::info!(%session, "Submitted a proving session to the remote prover");
+
+        let deadline = Instant::now() + Duration::from_secs(self.config.timeout_secs);
+        let receipt_url = loop {
+            let status = self.service.session_status(&session)?;
+            match status.status.as_str() {
+                "SUCCEEDED" => {
+                    break status
+                        .receipt_url
+                        .with_context(|| format!("Proving session {} has no receipt", session))?
+                }
+                "FAILED" | "TIMED_OUT" | "ABORTED" => bail!(
+                    "Proving session {} failed with status {}: {}",
+                    session,
+                    status.status,
+                    status.error_msg.unwrap_or_default()
+                ),
+                "RUNNING" => {}
+                other => tracing::warn!(
+                    %session,
+                    status = other,
+                    "Unexpected status of the proving session, polling it again"
+                ),
+            }
+
+            if Instant::now() >= deadline {
+                bail!(
+                    "Proving session {} didn't complete within {} seconds",
+                    session,
+                    self.config.timeout_secs
+                );
+            }
+            std::thread::sleep(Duration::from_millis(self.config.poll_interval_ms));
+        };
+
+        self.service.download(&receipt_url)
+    }
+}
+
+impl<'a, S: ProvingService> ZkvmHost for RemoteProver<'a, S> {
+    type Guest = Risc0Guest;
+
+    fn add_hint<T: serde::Serialize>(&mut self, item: T) {
+        self.host.add_hint(item)
+    }
+
+    fn simulate_with_hints(&mut self) -> Self::Guest {
+        self.host.simulate_with_hints()
+    }
+
+    fn run(&mut self, with_proof: bool) -> Result<Proof, anyhow::Error> {
+        if !with_proof {
+            return ZkvmHost::run(&mut self.host, false);
+        }
+
+        match self.prove_remotely() {
+            Ok(receipt) => Ok(Proof::Full(bincode::serialize(&receipt)?)),
+            Err(e) => {
+                tracing::warn!(
+                    error = ?e,
+                    "Remote proving failed, falling back to local proving"
+                );
+                ZkvmHost::run(&mut self.host, true)
+            }
+        }
+    }
+
+    fn extract_output<Da: sov_rollup_interface::da::DaSpec, Root: Serialize + DeserializeOwned>(
+        proof: &Proof,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        Risc0Host::extract_output(proof)
+    }
+}
+
+impl<'host, S: ProvingService> Zkvm for RemoteProver<'host, S> {
+    type CodeCommitment = Risc0MethodId;
+
+    type Error = anyhow::Error;
+
+    fn verify<'a>(
+        serialized_proof: &'a [u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<&'a [u8], Self::Error> {
+        Risc0Host::verify(serialized_proof, code_commitment)
+    }
+
+    fn verify_and_extract_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        Risc0Host::verify_and_extract_output(serialized_proof, code_commitment)
+    }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        Risc0Host::verify_and_extract_aggregated_output(serialized_proof, code_commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    const RECEIPT_URL: &str = "https://bonsai.test/receipt";
+
+    /// A [`ProvingService`] returning the given statuses to the successive polls, the last one repeating.
+    #[derive(Clone)]
+    struct MockService {
+        statuses: Arc<Mutex<VecDeque<&'static str>>>,
+        receipt: Vec<u8>,
+    }
+
+    impl MockService {
+        fn new(statuses: &[&'static str], receipt: Vec<u8>) -> Self {
+            Self {
+                statuses: Arc::new(Mutex::new(statuses.iter().copied().collect())),
+                receipt,
+            }
+        }
+
+        fn remaining_statuses(&self) -> usize {
+            self.statuses.lock().unwrap().len()
+        }
+    }
+
+    impl ProvingService for MockService {
+        fn create_session(&self, _elf: &[u8], _input: &[u8]) -> anyhow::Result<String> {
+            Ok("session".to_owned())
+        }
+
+        fn session_status(&self, _session_id: &str) -> anyhow::Result<SessionStatus> {
+            let mut statuses = self.statuses.lock().unwrap();
+            let status = match statuses.len() {
+                1 => statuses[0],
+                _ => statuses.pop_front().expect("The mock has statuses"),
+            };
+            Ok(SessionStatus {
+                status: status.to_owned(),
+                receipt_url: (status == "SUCCEEDED").then(|| RECEIPT_URL.to_owned()),
+                error_msg: None,
+            })
+        }
+
+        fn download(&self, receipt_url: &str) -> anyhow::Result<Vec<u8>> {
+            assert_eq!(receipt_url, RECEIPT_URL);
+            Ok(self.receipt.clone())
+        }
+    }
+
+    fn prover(service: MockService, timeout_secs: u64) -> RemoteProver<'static, MockService> {
+        let config = RemoteProverConfig {
+            url: "https://bonsai.test".to_owned(),
+            api_key: "key".to_owned(),
+            poll_interval_ms: 0,
+            timeout_secs,
+        };
+        RemoteProver::with_service(&[], config, service)
+    }
+
+    #[test]
+    fn test_receipt_is_downloaded_once_the_session_succeeds() {
+        let service = MockService::new(&["RUNNING", "QUEUED", "SUCCEEDED"], vec![1, 2, 3]);
+        let prover = prover(service.clone(), 60);
+
+        assert_eq!(prover.fetch_receipt().unwrap(), vec![1, 2, 3]);
+        assert_eq!(service.remaining_statuses(), 1);
+    }
+
+    #[test]
+    fn test_failed_session_falls_back_to_local_proving() {
+        let mut prover = prover(MockService::new(&["RUNNING", "FAILED"], vec![]), 60);
+
+        let error = prover.prove_remotely().unwrap_err();
+        assert!(error.to_string().contains("failed with status FAILED"));
+
+        // The binary is empty, so the local proving fails too, with its own error.
+        let remote = ZkvmHost::run(&mut prover, true).unwrap_err();
+        let local = ZkvmHost::run(&mut Risc0Host::new(&[]), true).unwrap_err();
+        assert_eq!(format!("{:#}", remote), format!("{:#}", local));
+    }
+
+    #[test]
+    fn test_session_times_out_whatever_its_status() {
+        for status in ["RUNNING", "QUEUED"] {
+            let prover = prover(MockService::new(&[status], vec![]), 0);
+
+            let error = prover.fetch_receipt().unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("didn't complete within 0 seconds"));
+        }
+    }
+
+    #[test]
+    fn test_invalid_receipt_is_rejected() {
+        let prover = prover(MockService::new(&["SUCCEEDED"], vec![0xff; 16]), 60);
+
+        let error = prover.prove_remotely().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The receipt returned by the remote prover is invalid"
+        );
+    }
+}
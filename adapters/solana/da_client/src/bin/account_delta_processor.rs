@@ -6,12 +6,15 @@
 // ----------------------------------------------------------------------------
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use backoff::future::retry;
 use backoff::ExponentialBackoff;
 use clap::Parser;
+use da_client::chain_tracker::ChainTracker;
 use da_client::hash_solana_account;
+use da_client::slot_tracker::{LogAlertHook, MetricAlertHook, SlotTracker, WebhookAlertHook};
 use futures::future::TryFutureExt;
 use futures::sink::SinkExt;
 use futures::stream::StreamExt;
@@ -32,6 +35,7 @@ type BlocksFilterMap = HashMap<String, SubscribeRequestFilterBlocks>;
 type BlocksMetaFilterMap = HashMap<String, SubscribeRequestFilterBlocksMeta>;
 
 const DEFAULT_GRPC_URL: &str = "http://127.0.0.1:10000";
+const DEFAULT_SLOT_GAP_TOLERANCE: u64 = 0;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -39,15 +43,23 @@ struct Cli {
     #[arg(short, long, default_value_t=DEFAULT_GRPC_URL.to_string())]
     /// URL for solana RPC
     grpc_url: String,
+
+    #[arg(long, default_value_t = DEFAULT_SLOT_GAP_TOLERANCE)]
+    /// Number of consecutive missing slots tolerated before alerting
+    slot_gap_tolerance: u64,
+
+    #[arg(long)]
+    /// URL of a webhook to which missed slots are posted
+    alert_webhook_url: Option<String>,
 }
 
 fn get_subscribe_request() -> SubscribeRequest {
     let mut accounts: AccountFilterMap = HashMap::new();
-    let slots: SlotsFilterMap = HashMap::new();
+    let mut slots: SlotsFilterMap = HashMap::new();
     let transactions: TransactionsFilterMap = HashMap::new();
     let entry: EntryFilterMap = HashMap::new();
     let blocks: BlocksFilterMap = HashMap::new();
-    let blocks_meta: BlocksMetaFilterMap = HashMap::new();
+    let mut blocks_meta: BlocksMetaFilterMap = HashMap::new();
     let accounts_data_slice = Vec::new();
 
     accounts.insert(
@@ -58,6 +70,9 @@ fn get_subscribe_request() -> SubscribeRequest {
             filters: vec![],
         },
     );
+    // Every slot is streamed, so that missed slots can be detected
+    slots.insert("client".to_owned(), SubscribeRequestFilterSlots::default());
+    blocks_meta.insert("client".to_owned(), SubscribeRequestFilterBlocksMeta {});
     SubscribeRequest {
         slots,
         accounts,
@@ -99,8 +114,20 @@ async fn main() -> anyhow::Result<()> {
     // optional overrides
     let grpc_url = &cli.grpc_url;
     let mut maybe_first_attempt = Some(());
+    let gap_metrics = Arc::new(MetricAlertHook::default());
+    let mut slot_tracker = SlotTracker::new(cli.slot_gap_tolerance)
+        .with_hook(LogAlertHook)
+        .with_hook(gap_metrics.clone());
+    if let Some(url) = cli.alert_webhook_url {
+        slot_tracker = slot_tracker.with_hook(WebhookAlertHook::new(url));
+    }
+    let slot_tracker = Arc::new(Mutex::new(slot_tracker));
+    let chain_tracker = Arc::new(Mutex::new(ChainTracker::new()));
 
     retry(ExponentialBackoff::default(), move || {
+        let slot_tracker = slot_tracker.clone();
+        let gap_metrics = gap_metrics.clone();
+        let chain_tracker = chain_tracker.clone();
         async move {
             if maybe_first_attempt.take().is_none() {
                 info!("Retry to connect to the server");
@@ -132,23 +159,49 @@ async fn main() -> anyhow::Result<()> {
 
             while let Some(message) = stream.next().await {
                 match message {
-                    Ok(msg) =>
-                    {
-                        #[allow(clippy::single_match)]
-                        match msg.update_oneof {
-                            Some(UpdateOneof::Account(account)) => {
-                                print_account(account);
-                                continue;
+                    Ok(msg) => match msg.update_oneof {
+                        Some(UpdateOneof::Account(account)) => {
+                            print_account(account);
+                            continue;
+                        }
+                        Some(UpdateOneof::Slot(slot)) => {
+                            let mut slot_tracker =
+                                slot_tracker.lock().expect("slot tracker lock poisoned");
+                            // The slots skipped by their leaders are never streamed
+                            match slot.parent {
+                                Some(parent) => slot_tracker.observe_with_parent(slot.slot, parent),
+                                None => slot_tracker.observe(slot.slot),
+                            };
+                            continue;
+                        }
+                        Some(UpdateOneof::BlockMeta(block)) => {
+                            if let Err(discontinuity) = chain_tracker
+                                .lock()
+                                .expect("chain tracker lock poisoned")
+                                .observe(
+                                    block.slot,
+                                    &block.blockhash,
+                                    block.parent_slot,
+                                    &block.parent_blockhash,
+                                )
+                            {
+                                error!("chain discontinuity: {discontinuity}");
                             }
-                            _ => {}
+                            continue;
                         }
-                    }
+                        _ => {}
+                    },
                     Err(error) => {
                         error!("error: {error:?}");
                         break;
                     }
                 }
             }
+            info!(
+                "stream closed: {} slot gap(s) detected, {} slot(s) missed in total",
+                gap_metrics.gaps(),
+                gap_metrics.missed_slots()
+            );
             Ok::<(), backoff::Error<anyhow::Error>>(())
         }
         .inspect_err(|error| error!("failed to connect: {error}"))
@@ -0,0 +1,120 @@
+//! Rolling continuity check over the chain of blocks streamed from the geyser plugin.
+//!
+//! Every block must name the previously observed block as its parent. A block whose parent
+//! hash does not match is flagged, since it could be a consistent-looking slot spliced into
+//! the stream that is not connected to the chain the client has already verified.
+//!
+//! The yellowstone plugin only exposes blockhashes in its block metadata, so the chain is
+//! tracked over `parent_blockhash`, which plays the role of the parent bankhash here.
+
+use log::debug;
+
+/// A block that does not extend the last observed block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discontinuity {
+    /// Slot of the last observed block
+    pub last_slot: u64,
+    /// Hash of the last observed block
+    pub last_hash: String,
+    /// Slot of the offending block
+    pub slot: u64,
+    /// Parent slot claimed by the offending block
+    pub parent_slot: u64,
+    /// Parent hash claimed by the offending block
+    pub parent_hash: String,
+}
+
+impl std::fmt::Display for Discontinuity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "block at slot {} claims parent {} at slot {}, but the last block is {} at slot {}",
+            self.slot, self.parent_hash, self.parent_slot, self.last_hash, self.last_slot
+        )
+    }
+}
+
+impl std::error::Error for Discontinuity {}
+
+/// Tracks the tip of the chain of observed blocks.
+#[derive(Debug, Default)]
+pub struct ChainTracker {
+    tip: Option<(u64, String)>,
+}
+
+impl ChainTracker {
+    /// Creates a tracker which trusts the first block it observes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The slot and hash of the last block accepted by the tracker
+    pub fn tip(&self) -> Option<(u64, &str)> {
+        self.tip.as_ref().map(|(slot, hash)| (*slot, hash.as_str()))
+    }
+
+    /// Checks that the block extends the current tip and makes it the new tip.
+    ///
+    /// Blocks at or below the current tip are ignored. A discontinuous block is rejected and
+    /// the tip is left unchanged, so the following blocks are still checked against the last
+    /// block known to be connected.
+    pub fn observe(
+        &mut self,
+        slot: u64,
+        hash: &str,
+        parent_slot: u64,
+        parent_hash: &str,
+    ) -> Result<(), Discontinuity> {
+        if let Some((last_slot, last_hash)) = &self.tip {
+            if slot <= *last_slot {
+                debug!(
+                    "ignoring block at slot {} (tip is at slot {})",
+                    slot, last_slot
+                );
+                return Ok(());
+            }
+            if parent_slot != *last_slot || parent_hash != last_hash {
+                return Err(Discontinuity {
+                    last_slot: *last_slot,
+                    last_hash: last_hash.clone(),
+                    slot,
+                    parent_slot,
+                    parent_hash: parent_hash.to_string(),
+                });
+            }
+        }
+
+        self.tip = Some((slot, hash.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connected_blocks_are_accepted() {
+        let mut tracker = ChainTracker::new();
+        tracker.observe(10, "a", 9, "genesis").unwrap();
+        tracker.observe(11, "b", 10, "a").unwrap();
+        // Skipped slots are fine as long as the parent matches
+        tracker.observe(13, "c", 11, "b").unwrap();
+        assert_eq!(tracker.tip(), Some((13, "c")));
+    }
+
+    #[test]
+    fn test_spliced_block_is_rejected() {
+        let mut tracker = ChainTracker::new();
+        tracker.observe(10, "a", 9, "genesis").unwrap();
+
+        let err = tracker.observe(11, "x", 10, "forged").unwrap_err();
+        assert_eq!(err.parent_hash, "forged");
+        assert_eq!(err.last_hash, "a");
+        assert!(tracker.observe(12, "y", 9, "a").is_err());
+
+        // The tip doesn't move, so the honest continuation is still accepted
+        assert_eq!(tracker.tip(), Some((10, "a")));
+        tracker.observe(11, "b", 10, "a").unwrap();
+    }
+}
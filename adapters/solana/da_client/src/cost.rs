@@ -0,0 +1,171 @@
+//! Accounting of the lamports spent on publishing blobs to Solana.
+//!
+//! Every transaction sent by the client is recorded as a [`TransactionCost`], and all
+//! transactions belonging to a single blob are aggregated into a [`CostReport`], so that
+//! rollup operators can reconcile DA expenses and tune their batch sizes.
+
+use std::fmt;
+
+use solana_sdk::signature::Signature;
+
+/// Lamports spent by a single transaction sent by the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionCost {
+    /// Signature of the transaction
+    pub signature: Signature,
+    /// Base fee, charged per signature
+    pub base_fee: u64,
+    /// Prioritization fee paid on top of the base fee
+    pub priority_fee: u64,
+    /// Lamports deposited for rent exemption of newly created accounts
+    pub rent: u64,
+}
+
+impl TransactionCost {
+    /// Builds the cost of a transaction from the total fee reported by the cluster,
+    /// splitting it into its base and prioritization parts.
+    pub fn from_fee(
+        signature: Signature,
+        total_fee: u64,
+        num_signatures: u64,
+        lamports_per_signature: u64,
+    ) -> Self {
+        let base_fee = total_fee.min(num_signatures.saturating_mul(lamports_per_signature));
+        Self {
+            signature,
+            base_fee,
+            priority_fee: total_fee - base_fee,
+            rent: 0,
+        }
+    }
+
+    /// Attaches the rent deposited by the transaction
+    pub fn with_rent(mut self, rent: u64) -> Self {
+        self.rent = rent;
+        self
+    }
+
+    /// Total lamports spent by the transaction
+    pub fn total(&self) -> u64 {
+        self.base_fee
+            .saturating_add(self.priority_fee)
+            .saturating_add(self.rent)
+    }
+}
+
+/// Aggregated cost of publishing one blob (a batch of chunk transactions).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostReport {
+    /// Digest of the published blob
+    pub digest: [u8; 32],
+    /// Size of the published blob in bytes
+    pub blob_size: u64,
+    /// Costs of every transaction which landed on chain
+    pub transactions: Vec<TransactionCost>,
+    /// Number of transactions that failed to be sent or whose cost could not be fetched
+    pub failed_transactions: u64,
+}
+
+impl CostReport {
+    /// Creates an empty report for the blob with the given `digest`
+    pub fn new(digest: [u8; 32], blob_size: u64) -> Self {
+        Self {
+            digest,
+            blob_size,
+            ..Default::default()
+        }
+    }
+
+    /// Records the cost of a transaction belonging to the blob
+    pub fn record(&mut self, cost: TransactionCost) {
+        self.transactions.push(cost);
+    }
+
+    /// Records a transaction whose cost is unknown
+    pub fn record_failure(&mut self) {
+        self.failed_transactions += 1;
+    }
+
+    /// Sum of the base fees
+    pub fn base_fees(&self) -> u64 {
+        self.transactions.iter().map(|c| c.base_fee).sum()
+    }
+
+    /// Sum of the prioritization fees
+    pub fn priority_fees(&self) -> u64 {
+        self.transactions.iter().map(|c| c.priority_fee).sum()
+    }
+
+    /// Sum of the rent deposits
+    pub fn rent(&self) -> u64 {
+        self.transactions.iter().map(|c| c.rent).sum()
+    }
+
+    /// Total lamports spent on the blob
+    pub fn total(&self) -> u64 {
+        self.transactions.iter().map(TransactionCost::total).sum()
+    }
+
+    /// Average lamports spent per byte of blob data, `None` for an empty blob
+    pub fn lamports_per_byte(&self) -> Option<f64> {
+        (self.blob_size > 0).then(|| self.total() as f64 / self.blob_size as f64)
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "cost report for blob {}", hex::encode(self.digest))?;
+        writeln!(f, "  blob size: {} bytes", self.blob_size)?;
+        writeln!(
+            f,
+            "  transactions: {} landed, {} failed",
+            self.transactions.len(),
+            self.failed_transactions
+        )?;
+        writeln!(f, "  base fees: {} lamports", self.base_fees())?;
+        writeln!(f, "  priority fees: {} lamports", self.priority_fees())?;
+        writeln!(f, "  rent: {} lamports", self.rent())?;
+        write!(f, "  total: {} lamports", self.total())?;
+        if let Some(per_byte) = self.lamports_per_byte() {
+            write!(f, " ({:.4} lamports/byte)", per_byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_split() {
+        let cost = TransactionCost::from_fee(Signature::default(), 12_000, 2, 5_000);
+        assert_eq!(cost.base_fee, 10_000);
+        assert_eq!(cost.priority_fee, 2_000);
+        assert_eq!(cost.with_rent(500).total(), 12_500);
+    }
+
+    #[test]
+    fn test_report_aggregation() {
+        let mut report = CostReport::new([1; 32], 100);
+        report.record(TransactionCost::from_fee(
+            Signature::default(),
+            10_000,
+            2,
+            5_000,
+        ));
+        report.record(TransactionCost::from_fee(
+            Signature::default(),
+            11_000,
+            2,
+            5_000,
+        ));
+        report.record_failure();
+
+        assert_eq!(report.base_fees(), 20_000);
+        assert_eq!(report.priority_fees(), 1_000);
+        assert_eq!(report.total(), 21_000);
+        assert_eq!(report.failed_transactions, 1);
+        assert_eq!(report.lamports_per_byte(), Some(210.0));
+    }
+}
@@ -7,6 +7,10 @@ use solana_runtime::accounts_hash::AccountsHasher;
 use solana_sdk::hash::Hash;
 use solana_sdk::pubkey::Pubkey;
 
+pub mod chain_tracker;
+pub mod cost;
+pub mod slot_tracker;
+
 /// Util helper function to write `size` number of random bytes to file at path `P`
 pub fn write_random_bytes<P: AsRef<Path>>(path: P, size: u64) -> std::io::Result<()> {
     let mut file = File::create(path)?;
@@ -10,19 +10,32 @@ use blockroot::{accounts as blockroot_accounts, instruction as blockroot_instruc
 use clap::{Parser, Subcommand};
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::message::Message;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{EncodableKey, Keypair, Signature, Signer};
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::sysvar::SysvarId;
 use solana_sdk::transaction::Transaction;
 use solana_sdk::{system_instruction, system_program};
+use solana_transaction_status::UiTransactionEncoding;
 extern crate alloc;
 
+use da_client::cost::{CostReport, TransactionCost};
 use da_client::{read_file_to_vec, write_random_bytes};
 
 const DEFAULT_RPC_URL: &str = "http://localhost:8899";
 const DEFAULT_WS_URL: &str = "ws://localhost:8900";
 
+/// The chunk transactions of a blob sent by [`DaClient::submit_blob`]
+pub struct SubmittedBlob {
+    /// Digest of the blob
+    pub digest: [u8; 32],
+    /// Size of the blob in bytes
+    pub size: u64,
+    /// Signatures of the chunk transactions, `None` for the ones which failed to be sent
+    pub signatures: Vec<Option<Signature>>,
+}
+
 pub struct DaClient {
     pub rpc_url: String,
     pub ws_url: String,
@@ -50,7 +63,12 @@ impl DaClient {
         }
     }
 
-    pub fn create_account(&self, chunks_account: &str, size: u64) -> anyhow::Result<Keypair> {
+    /// Creates the chunks account, and returns its keypair with the cost of its creation, rent included
+    pub fn create_account(
+        &self,
+        chunks_account: &str,
+        size: u64,
+    ) -> anyhow::Result<(Keypair, TransactionCost)> {
         let client = RpcClient::new(&self.rpc_url);
         let new_account = Keypair::new();
         new_account.write_to_file(chunks_account).unwrap();
@@ -71,8 +89,11 @@ impl DaClient {
             &[&self.signer, &new_account],
             blockhash,
         );
-        client.send_and_confirm_transaction(&tx)?;
-        Ok(new_account)
+        let signature = client.send_and_confirm_transaction(&tx)?;
+        let cost = self
+            .fetch_transaction_cost(&signature, self.lamports_per_signature()?)?
+            .with_rent(rent);
+        Ok((new_account, cost))
     }
 
     pub fn initialize_account(&self, chunks_keypair: &Keypair) -> anyhow::Result<Signature> {
@@ -95,11 +116,15 @@ impl DaClient {
         Ok(signature)
     }
 
-    pub fn create_large_account(&self, chunks_account: &str, size: u64) -> anyhow::Result<Keypair> {
-        let chunks_keypair = self.create_account(chunks_account, size)?;
+    pub fn create_large_account(
+        &self,
+        chunks_account: &str,
+        size: u64,
+    ) -> anyhow::Result<(Keypair, TransactionCost)> {
+        let (chunks_keypair, cost) = self.create_account(chunks_account, size)?;
         let signature = self.initialize_account(&chunks_keypair);
         println!("{:?}", signature);
-        Ok(chunks_keypair)
+        Ok((chunks_keypair, cost))
     }
 
     pub fn send_chunk_transaction(
@@ -135,6 +160,93 @@ impl DaClient {
         Ok(signature)
     }
 
+    /// Fee charged for every signature of a transaction on the cluster
+    pub fn lamports_per_signature(&self) -> anyhow::Result<u64> {
+        let client = RpcClient::new(&self.rpc_url);
+        // A message without instructions is only charged the fee for its single signature
+        let message = Message::new(&[], Some(&self.signer.pubkey()));
+        Ok(client.get_fee_for_message(&message)?)
+    }
+
+    /// Waits for the transaction to be confirmed and fetches the fees it paid.
+    /// `lamports_per_signature` is fetched once by the caller, see [`DaClient::lamports_per_signature`].
+    pub fn fetch_transaction_cost(
+        &self,
+        signature: &Signature,
+        lamports_per_signature: u64,
+    ) -> anyhow::Result<TransactionCost> {
+        let client = RpcClient::new(&self.rpc_url);
+        client.poll_for_signature_with_commitment(signature, CommitmentConfig::confirmed())?;
+
+        let tx = client.get_transaction(signature, UiTransactionEncoding::Base64)?;
+        let fee = tx
+            .transaction
+            .meta
+            .ok_or_else(|| anyhow::anyhow!("transaction {} has no status meta", signature))?
+            .fee;
+        let num_signatures = tx
+            .transaction
+            .transaction
+            .decode()
+            .ok_or_else(|| anyhow::anyhow!("failed to decode transaction {}", signature))?
+            .signatures
+            .len() as u64;
+
+        Ok(TransactionCost::from_fee(
+            *signature,
+            fee,
+            num_signatures,
+            lamports_per_signature,
+        ))
+    }
+
+    /// Sends every chunk of `blob` to the chunks account. Fails if the blob is empty
+    pub fn submit_blob(
+        &self,
+        chunks_keypair: &Keypair,
+        blob: &[u8],
+    ) -> anyhow::Result<SubmittedBlob> {
+        anyhow::ensure!(!blob.is_empty(), "cannot submit an empty blob");
+        let chunk_list = get_chunks(blob, CHUNK_SIZE);
+        let digest = chunk_list
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("blob of {} bytes has no chunks", blob.len()))?
+            .digest;
+
+        let mut signatures = Vec::with_capacity(chunk_list.len());
+        for c in chunk_list {
+            let sig = self.send_chunk_transaction(chunks_keypair, c);
+            println!("{:?}", sig);
+            signatures.push(sig.ok());
+        }
+        Ok(SubmittedBlob {
+            digest,
+            size: blob.len() as u64,
+            signatures,
+        })
+    }
+
+    /// Reports the lamports spent on the chunk transactions of a submitted blob.
+    /// Every transaction is waited for and fetched, which costs two RPC calls per chunk.
+    pub fn blob_cost_report(&self, blob: &SubmittedBlob) -> anyhow::Result<CostReport> {
+        let lamports_per_signature = self.lamports_per_signature()?;
+        let mut report = CostReport::new(blob.digest, blob.size);
+        for signature in &blob.signatures {
+            let cost = match signature {
+                Some(signature) => self.fetch_transaction_cost(signature, lamports_per_signature),
+                None => Err(anyhow::anyhow!("chunk transaction failed to be sent")),
+            };
+            match cost {
+                Ok(cost) => report.record(cost),
+                Err(e) => {
+                    println!("failed to account for chunk transaction: {}", e);
+                    report.record_failure();
+                }
+            }
+        }
+        Ok(report)
+    }
+
     pub fn wipe_account(&self, chunks_keypair: &Keypair) -> anyhow::Result<Signature> {
         let creator_pubkey = self.signer.pubkey();
         let c = Client::new(
@@ -204,6 +316,9 @@ enum Commands {
     Submit {
         chunk_account_path: String,
         blob_path: String,
+        #[arg(long, default_value_t = false)]
+        /// Don't wait for the chunk transactions to report the lamports spent on them
+        no_cost_report: bool,
     },
     Verify,
 }
@@ -227,6 +342,14 @@ enum ChunkAccountArgs {
     },
 }
 
+fn print_account_creation_cost(cost: &TransactionCost) {
+    println!(
+        "chunk account created, spent {} lamports ({} rent)",
+        cost.total(),
+        cost.rent
+    );
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -259,7 +382,8 @@ fn main() {
                 if Path::new(path).exists() {
                     if *force {
                         println!("Over-writing existing keypair at {} ", path);
-                        da_client.create_large_account(&path, *size).unwrap();
+                        let (_, cost) = da_client.create_large_account(&path, *size).unwrap();
+                        print_account_creation_cost(&cost);
                     } else {
                         if *use_existing {
                             println!("Attempting to re-use existing keypair at {} ", path);
@@ -273,7 +397,8 @@ fn main() {
                         process::exit(1);
                     }
                 } else {
-                    da_client.create_large_account(&path, *size).unwrap();
+                    let (_, cost) = da_client.create_large_account(&path, *size).unwrap();
+                    print_account_creation_cost(&cost);
                 }
             }
             ChunkAccountArgs::Clear { path } => {
@@ -293,21 +418,27 @@ fn main() {
         Commands::Submit {
             chunk_account_path,
             blob_path,
+            no_cost_report,
         } => {
             let chunks_keypair = read_keypair_file(chunk_account_path).unwrap();
             let contents = read_file_to_vec(blob_path).expect("Failed to read from the file");
             let chunk_list = get_chunks(&contents, CHUNK_SIZE);
+            let Some(first_chunk) = chunk_list.first() else {
+                println!("Blob file at {} is empty, nothing to submit", blob_path);
+                process::exit(1);
+            };
             println!("raw data file: {}", blob_path);
-            println!("digest: {}", hex::encode(chunk_list[0].digest));
-            println!("number of chunk transactions: {}", chunk_list[0].num_chunks);
+            println!("digest: {}", hex::encode(first_chunk.digest));
+            println!("number of chunk transactions: {}", first_chunk.num_chunks);
             println!(
                 "chunks digest for blob file at {} is {} ",
                 blob_path,
-                hex::encode(chunk_list[0].digest)
+                hex::encode(first_chunk.digest)
             );
-            for c in chunk_list {
-                let sig = da_client.send_chunk_transaction(&chunks_keypair, c);
-                println!("{:?}", sig);
+            let submitted = da_client.submit_blob(&chunks_keypair, &contents).unwrap();
+            if !*no_cost_report {
+                let report = da_client.blob_cost_report(&submitted).unwrap();
+                println!("{}", report);
             }
         }
     }
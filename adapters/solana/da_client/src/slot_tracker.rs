@@ -0,0 +1,290 @@
+//! Detection of gaps in the sequence of slots streamed from the geyser plugin.
+//!
+//! The rollup must not skip any DA data, so a slot that silently never arrives is a
+//! critical condition. Solana leaders do occasionally skip slots, which are recognized from
+//! the parents of the streamed slots. Gaps are only reported once they exceed a configurable
+//! tolerance.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use log::{debug, error, warn};
+
+/// A run of slots that were never received by the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotGap {
+    /// The last slot received before the gap
+    pub last_seen: u64,
+    /// The first slot after the gap, which was either received or skipped by its leader
+    pub received: u64,
+}
+
+impl SlotGap {
+    /// The number of slots between `last_seen` and `received` that were never received
+    pub fn missed(&self) -> u64 {
+        self.received - self.last_seen - 1
+    }
+}
+
+/// A pluggable callback invoked by the [`SlotTracker`] whenever it detects a gap
+/// exceeding its tolerance. Implementations can log, update metrics or call out
+/// to an external alerting system (e.g. a webhook).
+pub trait AlertHook: Send + Sync {
+    /// Called once for every detected gap
+    fn on_gap(&self, gap: &SlotGap);
+}
+
+impl<T: AlertHook + ?Sized> AlertHook for Arc<T> {
+    fn on_gap(&self, gap: &SlotGap) {
+        (**self).on_gap(gap)
+    }
+}
+
+/// Logs every detected gap at the `warn` level
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogAlertHook;
+
+impl AlertHook for LogAlertHook {
+    fn on_gap(&self, gap: &SlotGap) {
+        warn!(
+            "missed {} slot(s) between slot {} and slot {}",
+            gap.missed(),
+            gap.last_seen,
+            gap.received
+        );
+    }
+}
+
+/// Counts detected gaps and missed slots, so they can be exported as metrics
+#[derive(Debug, Default)]
+pub struct MetricAlertHook {
+    gaps: AtomicU64,
+    missed_slots: AtomicU64,
+}
+
+impl MetricAlertHook {
+    /// Total number of gaps detected so far
+    pub fn gaps(&self) -> u64 {
+        self.gaps.load(Ordering::Relaxed)
+    }
+
+    /// Total number of slots missed across all detected gaps
+    pub fn missed_slots(&self) -> u64 {
+        self.missed_slots.load(Ordering::Relaxed)
+    }
+}
+
+impl AlertHook for MetricAlertHook {
+    fn on_gap(&self, gap: &SlotGap) {
+        self.gaps.fetch_add(1, Ordering::Relaxed);
+        self.missed_slots.fetch_add(gap.missed(), Ordering::Relaxed);
+    }
+}
+
+/// Posts every detected gap as JSON to a webhook, e.g. of an incident management service.
+/// The requests are sent in the background, so a slow webhook doesn't hold up the stream.
+/// Must be created inside a tokio runtime.
+#[derive(Debug, Clone)]
+pub struct WebhookAlertHook {
+    url: String,
+    client: reqwest::Client,
+    runtime: tokio::runtime::Handle,
+}
+
+impl WebhookAlertHook {
+    /// Creates a hook posting the gaps to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+            runtime: tokio::runtime::Handle::current(),
+        }
+    }
+
+    /// The JSON body posted for `gap`
+    pub fn payload(gap: &SlotGap) -> String {
+        format!(
+            r#"{{"last_seen":{},"received":{},"missed":{}}}"#,
+            gap.last_seen,
+            gap.received,
+            gap.missed()
+        )
+    }
+}
+
+impl AlertHook for WebhookAlertHook {
+    fn on_gap(&self, gap: &SlotGap) {
+        let request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(Self::payload(gap));
+        let url = self.url.clone();
+        self.runtime.spawn(async move {
+            match request.send().await.and_then(|r| r.error_for_status()) {
+                Ok(_) => debug!("posted slot gap alert to {}", url),
+                Err(e) => error!("failed to post slot gap alert to {}: {}", url, e),
+            }
+        });
+    }
+}
+
+/// Tracks the sequence of received slots and reports gaps to the registered [`AlertHook`]s.
+pub struct SlotTracker {
+    tolerance: u64,
+    last_slot: Option<u64>,
+    hooks: Vec<Box<dyn AlertHook>>,
+}
+
+impl SlotTracker {
+    /// Creates a tracker that tolerates up to `tolerance` consecutive missing slots
+    /// before alerting.
+    pub fn new(tolerance: u64) -> Self {
+        Self {
+            tolerance,
+            last_slot: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Registers an additional alert hook
+    pub fn with_hook(mut self, hook: impl AlertHook + 'static) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// The highest slot observed so far
+    pub fn last_slot(&self) -> Option<u64> {
+        self.last_slot
+    }
+
+    /// Records that `slot` has been received. Repeated or out of order slots are ignored.
+    /// Returns the detected gap if it exceeds the tolerance, after invoking all hooks.
+    pub fn observe(&mut self, slot: u64) -> Option<SlotGap> {
+        self.record(slot, slot)
+    }
+
+    /// Records that `slot`, built on top of the slot `parent`, has been received. The slots between
+    /// `parent` and `slot` were skipped by their leaders, so only the slots up to `parent` can be missed.
+    /// See [`SlotTracker::observe`].
+    pub fn observe_with_parent(&mut self, slot: u64, parent: u64) -> Option<SlotGap> {
+        self.record(slot, parent.saturating_add(1).min(slot))
+    }
+
+    // Records `slot`, where the slots from `gap_end` to `slot` are known not to be missed.
+    fn record(&mut self, slot: u64, gap_end: u64) -> Option<SlotGap> {
+        let last_seen = match self.last_slot {
+            None => {
+                self.last_slot = Some(slot);
+                return None;
+            }
+            Some(last_seen) if slot <= last_seen => {
+                if slot < last_seen {
+                    debug!(
+                        "ignoring out of order slot {} (last seen {})",
+                        slot, last_seen
+                    );
+                }
+                return None;
+            }
+            Some(last_seen) => last_seen,
+        };
+
+        self.last_slot = Some(slot);
+        if gap_end <= last_seen {
+            return None;
+        }
+        let gap = SlotGap {
+            last_seen,
+            received: gap_end,
+        };
+        if gap.missed() <= self.tolerance {
+            return None;
+        }
+
+        for hook in &self.hooks {
+            hook.on_gap(&gap);
+        }
+        Some(gap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_within_tolerance_is_ignored() {
+        let metrics = Arc::new(MetricAlertHook::default());
+        let mut tracker = SlotTracker::new(2).with_hook(metrics.clone());
+
+        assert_eq!(tracker.observe(10), None);
+        assert_eq!(tracker.observe(10), None);
+        assert_eq!(tracker.observe(11), None);
+        assert_eq!(tracker.observe(14), None);
+        assert_eq!(metrics.gaps(), 0);
+        assert_eq!(tracker.last_slot(), Some(14));
+    }
+
+    #[test]
+    fn test_gap_beyond_tolerance_alerts() {
+        let metrics = Arc::new(MetricAlertHook::default());
+        let mut tracker = SlotTracker::new(1).with_hook(metrics.clone());
+
+        tracker.observe(5);
+        let gap = tracker.observe(9).expect("gap must be detected");
+        assert_eq!(
+            gap,
+            SlotGap {
+                last_seen: 5,
+                received: 9
+            }
+        );
+        assert_eq!(gap.missed(), 3);
+        // Slots from the past don't move the tracker backwards
+        assert_eq!(tracker.observe(7), None);
+        assert_eq!(
+            tracker.observe(12),
+            Some(SlotGap {
+                last_seen: 9,
+                received: 12
+            })
+        );
+        assert_eq!(metrics.gaps(), 2);
+        assert_eq!(metrics.missed_slots(), 5);
+    }
+
+    #[test]
+    fn test_skipped_slots_are_not_missed() {
+        let metrics = Arc::new(MetricAlertHook::default());
+        let mut tracker = SlotTracker::new(0).with_hook(metrics.clone());
+
+        tracker.observe(5);
+        // Slots 6 and 7 were skipped by their leaders
+        assert_eq!(tracker.observe_with_parent(8, 5), None);
+        assert_eq!(tracker.observe_with_parent(9, 8), None);
+        // Slot 10 was missed, and slot 11 skipped
+        assert_eq!(
+            tracker.observe_with_parent(12, 10),
+            Some(SlotGap {
+                last_seen: 9,
+                received: 11
+            })
+        );
+        assert_eq!(tracker.last_slot(), Some(12));
+        assert_eq!(metrics.gaps(), 1);
+        assert_eq!(metrics.missed_slots(), 1);
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let gap = SlotGap {
+            last_seen: 5,
+            received: 9,
+        };
+        assert_eq!(
+            WebhookAlertHook::payload(&gap),
+            r#"{"last_seen":5,"received":9,"missed":3}"#
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Helpers to build SP1 guest programs from the `build.rs` of the crate embedding them.
+
+use std::path::Path;
+
+/// The path of the ELF of a guest program built by [`build_guest`], relative to the guest crate.
+pub const ELF_PATH: &str = "elf/riscv32im-succinct-zkvm-elf";
+
+/// Builds the SP1 guest program of the crate at `guest_path`, relative to the crate calling this
+/// function from its `build.rs`. The ELF is written to [`ELF_PATH`] in the guest crate, and can be
+/// embedded with `include_bytes!`.
+///
+/// If the `SKIP_GUEST_BUILD` environment variable is set, e.g. for CI runs, the guest is not built,
+/// and an empty ELF is written instead if there is none yet.
+pub fn build_guest(guest_path: &str) {
+    if std::env::var("SKIP_GUEST_BUILD").is_ok() {
+        println!("Skipping guest build for CI run");
+        let elf_path = Path::new(guest_path).join(ELF_PATH);
+        if !elf_path.exists() {
+            let elf_dir = elf_path.parent().expect("The ELF path has a parent");
+            std::fs::create_dir_all(elf_dir).expect("Failed to create the ELF directory");
+            std::fs::write(elf_path, []).expect("Failed to write mock guest elf");
+        }
+        return;
+    }
+    sp1_helper::build_program(guest_path);
+}
@@ -0,0 +1,102 @@
+//! This module implements the `ZkvmGuest` trait for the SP1 VM.
+#[cfg(not(target_os = "zkvm"))]
+use std::collections::VecDeque;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::zk::{Zkvm, ZkvmGuest};
+
+use crate::Sp1MethodId;
+
+#[cfg(target_os = "zkvm")]
+impl ZkvmGuest for Sp1Guest {
+    fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
+        sp1_zkvm::io::read()
+    }
+
+    fn commit<T: serde::Serialize>(&self, item: &T) {
+        sp1_zkvm::io::commit(item);
+    }
+}
+
+/// A guest for the SP1 VM. When running in the SP1 environment, this struct
+/// implements the `ZkvmGuest` trait in terms of SP1's io::read and io::commit functions.
+/// When running in any other environment, the struct uses interior mutability to emulate
+/// the same functionality.
+#[derive(Default)]
+pub struct Sp1Guest {
+    #[cfg(not(target_os = "zkvm"))]
+    hints: std::sync::Mutex<VecDeque<Vec<u8>>>,
+    #[cfg(not(target_os = "zkvm"))]
+    commits: std::sync::Mutex<Vec<u8>>,
+}
+
+impl Sp1Guest {
+    /// Constructs a new SP1 Guest
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new SP1 Guest with the provided hints, each serialized with `bincode`.
+    ///
+    /// This function is only available outside of SP1's environment.
+    #[cfg(not(target_os = "zkvm"))]
+    pub fn with_hints(hints: Vec<Vec<u8>>) -> Self {
+        Self {
+            hints: std::sync::Mutex::new(hints.into()),
+            commits: Default::default(),
+        }
+    }
+}
+
+#[cfg(not(target_os = "zkvm"))]
+impl ZkvmGuest for Sp1Guest {
+    fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
+        let hint = self
+            .hints
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("The host provided no more hints");
+        bincode::deserialize(&hint).unwrap()
+    }
+
+    fn commit<T: serde::Serialize>(&self, item: &T) {
+        let mut commits = self.commits.lock().unwrap();
+        bincode::serialize_into(&mut *commits, item).expect("Serialization to vec is infallible");
+    }
+}
+
+impl Zkvm for Sp1Guest {
+    type CodeCommitment = Sp1MethodId;
+
+    type Error = anyhow::Error;
+
+    fn verify<'a>(
+        _serialized_proof: &'a [u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<&'a [u8], Self::Error> {
+        // Implement this method with SP1's in-VM proof verification: issue #633
+        todo!("Implement with SP1 recursion: https://github.com/Sovereign-Labs/sovereign-sdk/issues/633")
+    }
+
+    fn verify_and_extract_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        todo!()
+    }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        todo!()
+    }
+}
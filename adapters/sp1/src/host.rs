@@ -0,0 +1,201 @@
+//! This module implements the [`ZkvmHost`] trait for the SP1 VM.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::zk::{Proof, Zkvm, ZkvmHost};
+use sp1_sdk::{
+    ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1PublicValues, SP1Stdin, SP1VerifyingKey,
+};
+
+use crate::guest::Sp1Guest;
+use crate::Sp1MethodId;
+
+/// A [`Sp1Host`] stores a binary to execute in the SP1 VM, and accumulates hints to be
+/// provided to its execution.
+#[derive(Clone)]
+pub struct Sp1Host<'a> {
+    stdin: SP1Stdin,
+    elf: &'a [u8],
+}
+
+impl<'a> Sp1Host<'a> {
+    /// Create a new Sp1Host to prove the given binary.
+    pub fn new(elf: &'a [u8]) -> Self {
+        Self {
+            stdin: SP1Stdin::new(),
+            elf,
+        }
+    }
+
+    /// Returns the method id of the binary, which the verifiers of its proofs must use.
+    pub fn method_id(&self) -> anyhow::Result<Sp1MethodId> {
+        let (_, verifying_key) = ProverClient::new().setup(self.elf);
+        Ok(Sp1MethodId::new(bincode::serialize(&verifying_key)?))
+    }
+
+    /// Run a computation in the zkVM without generating a proof, and return its public values.
+    pub fn run_without_proving(&mut self) -> anyhow::Result<SP1PublicValues> {
+        let (public_values, _report) = ProverClient::new()
+            .execute(self.elf, self.stdin.clone())
+            .run()?;
+        Ok(public_values)
+    }
+
+    /// Run a computation in the zkvm and generate a proof.
+    pub fn run(&mut self) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let client = ProverClient::new();
+        let (proving_key, _) = client.setup(self.elf);
+        client.prove(&proving_key, self.stdin.clone()).run()
+    }
+}
+
+impl<'a> ZkvmHost for Sp1Host<'a> {
+    type Guest = Sp1Guest;
+
+    fn add_hint<T: serde::Serialize>(&mut self, item: T) {
+        self.stdin.write(&item);
+    }
+
+    fn simulate_with_hints(&mut self) -> Self::Guest {
+        Sp1Guest::with_hints(std::mem::take(&mut self.stdin.buffer))
+    }
+
+    fn run(&mut self, with_proof: bool) -> Result<Proof, anyhow::Error> {
+        if with_proof {
+            let SP1ProofWithPublicValues {
+                proof,
+                public_values,
+                sp1_version,
+                ..
+            } = self.run()?;
+            let data = bincode::serialize(&Sp1Proof {
+                proof,
+                public_values: public_values.as_slice(),
+                sp1_version,
+            })?;
+            Ok(Proof::Full(data))
+        } else {
+            let public_values = self.run_without_proving()?;
+            Ok(Proof::PublicInput(public_values.to_vec()))
+        }
+    }
+
+    fn extract_output<Da: sov_rollup_interface::da::DaSpec, Root: Serialize + DeserializeOwned>(
+        proof: &Proof,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        match proof {
+            Proof::PublicInput(public_values) => Ok(bincode::deserialize(public_values)?),
+            Proof::Full(data) => {
+                let proof: Sp1Proof = bincode::deserialize(data)?;
+                Ok(bincode::deserialize(proof.public_values)?)
+            }
+        }
+    }
+}
+
+impl<'host> Zkvm for Sp1Host<'host> {
+    type CodeCommitment = Sp1MethodId;
+
+    type Error = anyhow::Error;
+
+    fn verify<'a>(
+        serialized_proof: &'a [u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<&'a [u8], Self::Error> {
+        verify_from_slice(serialized_proof, code_commitment)
+    }
+
+    fn verify_and_extract_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+}
+
+/// A verifier for SP1 proofs.
+pub struct Sp1Verifier;
+
+impl Zkvm for Sp1Verifier {
+    type CodeCommitment = Sp1MethodId;
+
+    type Error = anyhow::Error;
+
+    fn verify<'a>(
+        serialized_proof: &'a [u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<&'a [u8], Self::Error> {
+        verify_from_slice(serialized_proof, code_commitment)
+    }
+
+    fn verify_and_extract_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::AggregatedStateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+}
+
+fn verify_from_slice<'a>(
+    serialized_proof: &'a [u8],
+    code_commitment: &Sp1MethodId,
+) -> Result<&'a [u8], anyhow::Error> {
+    let Sp1Proof::<'a> {
+        proof,
+        public_values,
+        sp1_version,
+    } = bincode::deserialize(serialized_proof)?;
+    let verifying_key: SP1VerifyingKey = bincode::deserialize(code_commitment.as_bytes())?;
+
+    let proof = SP1ProofWithPublicValues {
+        proof,
+        stdin: SP1Stdin::new(),
+        public_values: SP1PublicValues::from(public_values),
+        sp1_version,
+    };
+    ProverClient::new().verify(&proof, &verifying_key)?;
+    Ok(public_values)
+}
+
+/// A convenience type which contains the same data as an SP1 [`SP1ProofWithPublicValues`], except
+/// for the inputs of the program, and borrows the public values. This allows us to avoid one
+/// unnecessary copy during proof verification.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Sp1Proof<'a> {
+    /// The cryptographic data certifying the execution of the program.
+    pub proof: SP1Proof,
+    /// The public values committed by the program execution.
+    pub public_values: &'a [u8],
+    /// The version of SP1 which generated the proof.
+    pub sp1_version: String,
+}
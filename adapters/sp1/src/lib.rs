@@ -0,0 +1,36 @@
+#![deny(missing_docs)]
+//! # SP1 Adapter
+//!
+//! This crate contains an adapter allowing SP1 to be used as a proof system for
+//! Sovereign SDK rollups.
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::zk::Matches;
+
+#[cfg(feature = "build")]
+pub mod build;
+pub mod guest;
+#[cfg(feature = "native")]
+pub mod host;
+
+/// Uniquely identifies an SP1 binary: the serialized verifying key of the ELF file,
+/// which SP1 needs to verify the proofs of its execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sp1MethodId(Vec<u8>);
+
+impl Sp1MethodId {
+    /// Creates a method id from a verifying key serialized with `bincode`.
+    pub fn new(serialized_verifying_key: Vec<u8>) -> Self {
+        Self(serialized_verifying_key)
+    }
+
+    /// Returns the verifying key, serialized with `bincode`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Matches<Self> for Sp1MethodId {
+    fn matches(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
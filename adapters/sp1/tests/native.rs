@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::zk::{ZkvmGuest, ZkvmHost};
+use sov_sp1_adapter::host::Sp1Host;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct TestStruct {
+    ints: Vec<i32>,
+    string: String,
+}
+
+#[test]
+fn test_hints_roundtrip() {
+    let mut host = Sp1Host::new(&[]);
+
+    let hint_a = TestStruct {
+        ints: vec![1, 2, 3, 4, 5],
+        string: "hello".to_string(),
+    };
+    let hint_b = TestStruct {
+        ints: vec![6, 7, 8],
+        string: "world".to_string(),
+    };
+
+    host.add_hint(&hint_a);
+    host.add_hint(&hint_b);
+
+    let guest = host.simulate_with_hints();
+
+    let mut received;
+    received = guest.read_from_host();
+    assert_eq!(hint_a, received);
+    received = guest.read_from_host();
+    assert_eq!(hint_b, received);
+}
@@ -47,6 +47,7 @@ fn rollup_bench(_bench: &mut Criterion) {
 
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path,
+        pruning: rollup_config.storage.pruning,
     };
     let storage = new_orphan_storage::<DefaultStorageSpec>(&storage_config.path)
         .expect("Failed to initialize orphan ProverStorage");
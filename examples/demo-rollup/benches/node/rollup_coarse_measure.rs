@@ -105,6 +105,7 @@ async fn main() -> Result<(), anyhow::Error> {
 
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path.clone(),
+        pruning: rollup_config.storage.pruning,
     };
     let mut storage_manager =
         ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(storage_config)
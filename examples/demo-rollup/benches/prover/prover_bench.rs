@@ -176,6 +176,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let da_service = MockDaService::new(MockAddress::default());
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path,
+        pruning: rollup_config.storage.pruning,
     };
 
     let mut storage_manager =
@@ -63,6 +63,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
         #[allow(unused_mut)]
         let mut rpc_methods = sov_modules_rollup_blueprint::register_rpc::<
             Self::NativeRuntime,
+            Self::NativeKernel,
             Self::NativeContext,
             Self::DaService,
         >(storage, ledger_db, da_service, sequencer)?;
@@ -103,6 +104,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
 
         let da_verifier = CelestiaVerifier {
             rollup_namespace: ROLLUP_BATCH_NAMESPACE,
+            rollup_proof_namespace: ROLLUP_PROOF_NAMESPACE,
         };
 
         ParallelProverService::new_with_default_workers(
@@ -121,6 +123,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
     ) -> Result<Self::StorageManager, anyhow::Error> {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
+            pruning: rollup_config.storage.pruning,
         };
         ProverStorageManager::new(storage_config)
     }
@@ -1,14 +1,23 @@
+use std::hash::Hash;
+use std::path::PathBuf;
+
 use anyhow::Context as _;
 use clap::Parser;
-use demo_stf::genesis_config::GenesisPaths;
+use demo_stf::genesis_config::{GenesisPaths, StorageConfig};
+use serde::de::DeserializeOwned;
+use sov_celestia_adapter::verifier::CelestiaSpec;
 use sov_demo_rollup::{initialize_logging, CelestiaDemoRollup, MockDemoRollup};
-use sov_mock_da::MockDaConfig;
-use sov_modules_rollup_blueprint::{Rollup, RollupBlueprint};
+use sov_mock_da::{MockDaConfig, MockDaSpec};
+use sov_modules_api::DaSpec;
+use sov_modules_rollup_blueprint::{
+    export_snapshot, import_snapshot, Rollup, RollupBlueprint, RollupSnapshot,
+};
 use sov_modules_stf_blueprint::kernels::basic::{
     BasicKernelGenesisConfig, BasicKernelGenesisPaths,
 };
+use sov_state::DefaultStorageSpec;
 use sov_stf_runner::{from_toml_path, RollupConfig, RollupProverConfig};
-use tracing::log::debug;
+use tracing::log::{debug, info};
 
 #[cfg(test)]
 mod test_rpc;
@@ -27,6 +36,18 @@ struct Args {
     /// The path to the rollup config.
     #[arg(long, default_value = "mock_rollup_config.toml")]
     rollup_config_path: String,
+
+    /// Write a snapshot of the state at the latest slot to this path, and exit.
+    #[arg(long)]
+    export_snapshot: Option<PathBuf>,
+
+    /// Start from the snapshot at this path instead of genesis. The storage must be empty.
+    #[arg(long, conflicts_with = "export_snapshot")]
+    import_snapshot: Option<PathBuf>,
+
+    /// The hex encoded state root the imported snapshot must have, obtained from a trusted source.
+    #[arg(long, requires = "import_snapshot")]
+    trusted_state_root: Option<String>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -44,6 +65,9 @@ async fn main() -> Result<(), anyhow::Error> {
 
     match args.da_layer {
         SupportedDaLayer::Mock => {
+            if process_snapshot_args::<MockDaSpec, MockDaConfig>(&args)? {
+                return Ok(());
+            }
             let rollup = new_rollup_with_mock_da(
                 &GenesisPaths::from_dir("../test-data/genesis/demo-tests/mock"),
                 &BasicKernelGenesisPaths {
@@ -56,6 +80,9 @@ async fn main() -> Result<(), anyhow::Error> {
             rollup.run().await
         }
         SupportedDaLayer::Celestia => {
+            if process_snapshot_args::<CelestiaSpec, sov_celestia_adapter::CelestiaConfig>(&args)? {
+                return Ok(());
+            }
             let rollup = new_rollup_with_celestia_da(
                 &GenesisPaths::from_dir("../test-data/genesis/demo-tests/celestia"),
                 &BasicKernelGenesisPaths {
@@ -70,6 +97,60 @@ async fn main() -> Result<(), anyhow::Error> {
     }
 }
 
+/// Exports or imports a snapshot of the state if requested by `args`. Returns true if the node should exit.
+fn process_snapshot_args<Da: DaSpec, DaConfig: DeserializeOwned>(
+    args: &Args,
+) -> Result<bool, anyhow::Error>
+where
+    Da::SlotHash: Hash,
+{
+    if args.export_snapshot.is_none() && args.import_snapshot.is_none() {
+        return Ok(false);
+    }
+
+    let rollup_config: RollupConfig<DaConfig> =
+        from_toml_path(&args.rollup_config_path).context("Failed to read rollup configuration")?;
+    let storage_config = StorageConfig {
+        path: rollup_config.storage.path,
+        pruning: rollup_config.storage.pruning,
+    };
+
+    if let Some(path) = &args.export_snapshot {
+        let snapshot = export_snapshot::<Da, DefaultStorageSpec>(storage_config)?;
+        snapshot.write_to_file(path)?;
+        info!(
+            "Exported the state at slot {} with root 0x{} to {}",
+            snapshot.slot_number,
+            hex::encode(snapshot.state.root),
+            path.display()
+        );
+        return Ok(true);
+    }
+
+    if let Some(path) = &args.import_snapshot {
+        let trusted_root = args
+            .trusted_state_root
+            .as_ref()
+            .map(|root| -> Result<[u8; 32], anyhow::Error> {
+                let mut bytes = [0; 32];
+                hex::decode_to_slice(root.trim_start_matches("0x"), &mut bytes)
+                    .context("Invalid trusted state root")?;
+                Ok(bytes)
+            })
+            .transpose()?;
+        let snapshot = RollupSnapshot::read_from_file(path)?;
+        let slot_number = snapshot.slot_number;
+        import_snapshot::<Da, DefaultStorageSpec>(storage_config, snapshot, trusted_root)?;
+        info!(
+            "Imported the state at slot {} from {}",
+            slot_number,
+            path.display()
+        );
+    }
+
+    Ok(false)
+}
+
 async fn new_rollup_with_celestia_da(
     rt_genesis_paths: &GenesisPaths,
     kernel_genesis_paths: &BasicKernelGenesisPaths,
@@ -83,6 +164,9 @@ async fn new_rollup_with_celestia_da(
 
     let rollup_config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
         from_toml_path(rollup_config_path).context("Failed to read rollup configuration")?;
+    // The kernel genesis holds addresses, which are parsed with the prefix of the chain.
+    sov_modules_api::set_address_prefix(&rollup_config.chain.address_prefix)
+        .context("Invalid address prefix")?;
 
     let kernel_genesis = BasicKernelGenesisConfig {
         chain_state: serde_json::from_str(
@@ -112,6 +196,9 @@ async fn new_rollup_with_mock_da(
 
     let rollup_config: RollupConfig<MockDaConfig> =
         from_toml_path(rollup_config_path).context("Failed to read rollup configuration")?;
+    // The kernel genesis holds addresses, which are parsed with the prefix of the chain.
+    sov_modules_api::set_address_prefix(&rollup_config.chain.address_prefix)
+        .context("Invalid address prefix")?;
 
     let kernel_genesis = BasicKernelGenesisConfig {
         chain_state: serde_json::from_str(
@@ -61,6 +61,7 @@ impl RollupBlueprint for MockDemoRollup {
         #[allow(unused_mut)]
         let mut rpc_methods = sov_modules_rollup_blueprint::register_rpc::<
             Self::NativeRuntime,
+            Self::NativeKernel,
             Self::NativeContext,
             Self::DaService,
         >(storage, ledger_db, da_service, sequencer)?;
@@ -109,6 +110,7 @@ impl RollupBlueprint for MockDemoRollup {
     ) -> anyhow::Result<Self::StorageManager> {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
+            pruning: rollup_config.storage.pruning,
         };
         ProverStorageManager::new(storage_config)
     }
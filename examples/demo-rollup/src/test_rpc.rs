@@ -75,6 +75,8 @@ fn test_helper(test_queries: Vec<TestExpect>, slots: Vec<SlotCommit<MockBlock, u
         let rpc_config = RpcConfig {
             bind_host: "127.0.0.1".to_string(),
             bind_port: addr.port(),
+            rest_bind_port: None,
+            metrics_bind_port: None,
         };
 
         queries_test_runner(test_queries, rpc_config).await;
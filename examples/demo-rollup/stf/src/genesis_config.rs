@@ -5,9 +5,10 @@
 use std::convert::AsRef;
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Context as _};
+use anyhow::Context as _;
 use sov_accounts::AccountConfig;
 use sov_bank::BankConfig;
+use sov_cli::genesis;
 #[cfg(feature = "experimental")]
 use sov_evm::EvmConfig;
 pub use sov_modules_api::default_context::DefaultContext;
@@ -72,23 +73,9 @@ pub fn get_genesis_config<C: Context, Da: DaSpec>(
 pub(crate) fn validate_config<C: Context, Da: DaSpec>(
     genesis_config: <Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig,
 ) -> Result<<Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig, anyhow::Error> {
-    let token_address = &sov_bank::get_genesis_token_address::<C>(
-        &genesis_config.bank.tokens[0].token_name,
-        genesis_config.bank.tokens[0].salt,
-    );
-
-    let coins_token_addr = &genesis_config
-        .sequencer_registry
-        .coins_to_lock
-        .token_address;
-
-    if coins_token_addr != token_address {
-        bail!(
-            "Wrong token address in `sequencer_registry_config` expected {} but found {}",
-            token_address,
-            coins_token_addr
-        )
-    }
+    genesis::validate_bank(&genesis_config.bank)?;
+    genesis::validate_sequencer(&genesis_config.sequencer_registry, &genesis_config.bank)?;
+    genesis::validate_accounts(&genesis_config.accounts)?;
 
     Ok(genesis_config)
 }
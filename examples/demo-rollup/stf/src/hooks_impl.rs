@@ -1,7 +1,6 @@
-use sov_accounts::AccountsTxHook;
-use sov_bank::BankTxHook;
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
 use sov_modules_api::transaction::Transaction;
+use sov_modules_api::tx_middleware::TxMiddlewares;
 use sov_modules_api::{AccessoryWorkingSet, Context, Spec, WorkingSet};
 use sov_modules_stf_blueprint::{RuntimeTxHook, SequencerOutcome};
 #[cfg(feature = "experimental")]
@@ -13,6 +12,13 @@ use tracing::info;
 
 use crate::runtime::Runtime;
 
+impl<C: Context, Da: DaSpec> Runtime<C, Da> {
+    /// The policies applied to every transaction, in order: authentication and nonces, then fees.
+    fn tx_middlewares(&self) -> TxMiddlewares<'_, C> {
+        TxMiddlewares::new().with(&self.accounts).with(&self.bank)
+    }
+}
+
 impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
     type Context = C;
     type PreArg = RuntimeTxHook<C>;
@@ -25,14 +31,8 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
         arg: &RuntimeTxHook<C>,
     ) -> anyhow::Result<C> {
         let RuntimeTxHook { height, sequencer } = arg;
-        let AccountsTxHook { sender, sequencer } =
-            self.accounts
-                .pre_dispatch_tx_hook(tx, working_set, sequencer)?;
-
-        let hook = BankTxHook { sender, sequencer };
-        self.bank.pre_dispatch_tx_hook(tx, working_set, &hook)?;
-
-        Ok(C::new(hook.sender, hook.sequencer, *height))
+        self.tx_middlewares()
+            .pre_dispatch(tx, sequencer, *height, working_set)
     }
 
     fn post_dispatch_tx_hook(
@@ -41,9 +41,7 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
         ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        self.accounts.post_dispatch_tx_hook(tx, ctx, working_set)?;
-        self.bank.post_dispatch_tx_hook(tx, ctx, working_set)?;
-        Ok(())
+        self.tx_middlewares().post_dispatch(tx, ctx, working_set)
     }
 }
 
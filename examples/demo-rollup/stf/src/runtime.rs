@@ -42,10 +42,12 @@ use sov_evm::{EvmRpcImpl, EvmRpcServer};
 pub use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::macros::DefaultRuntime;
 #[cfg(feature = "native")]
-use sov_modules_api::macros::{expose_rpc, CliWallet};
+use sov_modules_api::macros::{expose_rpc, CliWallet, RuntimeCallSchema};
+#[cfg(feature = "native")]
+use sov_modules_api::schema::RuntimeSchema;
 #[cfg(feature = "native")]
 use sov_modules_api::Spec;
-use sov_modules_api::{Context, DispatchCall, Genesis, MessageCodec};
+use sov_modules_api::{Context, DispatchCall, GasUnit, Genesis, MessageCodec, StateAccessGasCosts};
 #[cfg(feature = "native")]
 use sov_nft_module::{NonFungibleTokenRpcImpl, NonFungibleTokenRpcServer};
 use sov_rollup_interface::da::DaSpec;
@@ -58,7 +60,7 @@ use sov_value_setter::{ValueSetterRpcImpl, ValueSetterRpcServer};
 use crate::genesis_config::GenesisPaths;
 
 /// The `demo-stf runtime`.
-#[cfg_attr(feature = "native", derive(CliWallet), expose_rpc)]
+#[cfg_attr(feature = "native", derive(CliWallet, RuntimeCallSchema), expose_rpc)]
 #[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
 #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
 #[cfg_attr(feature = "serde", serialization(serde::Serialize, serde::Deserialize))]
@@ -74,7 +76,7 @@ pub struct Runtime<C: Context, Da: DaSpec> {
     /// The NFT module.
     pub nft: sov_nft_module::NonFungibleToken<C>,
     #[cfg(feature = "experimental")]
-    #[cfg_attr(feature = "native", cli_skip)]
+    #[cfg_attr(feature = "native", cli_skip, schema_skip)]
     /// The EVM module.
     pub evm: sov_evm::Evm<C>,
 }
@@ -100,4 +102,17 @@ where
     ) -> Result<Self::GenesisConfig, anyhow::Error> {
         crate::genesis_config::get_genesis_config(genesis_paths)
     }
+
+    #[cfg(feature = "native")]
+    fn schema() -> Result<RuntimeSchema, anyhow::Error> {
+        RuntimeSchema::of::<Self>()
+    }
+
+    fn state_access_gas_costs() -> StateAccessGasCosts<C::GasUnit> {
+        StateAccessGasCosts {
+            read: C::GasUnit::from_arbitrary_dimensions(&[1, 1]),
+            write: C::GasUnit::from_arbitrary_dimensions(&[2, 2]),
+            delete: C::GasUnit::from_arbitrary_dimensions(&[1, 1]),
+        }
+    }
 }
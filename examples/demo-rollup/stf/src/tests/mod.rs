@@ -13,6 +13,9 @@ use crate::genesis_config::{get_genesis_config, GenesisPaths};
 use crate::runtime::{GenesisConfig, Runtime};
 
 mod da_simulation;
+mod parallel_tests;
+mod schema_tests;
+mod simulation_tests;
 mod stf_tests;
 mod tx_revert_tests;
 pub(crate) type C = DefaultContext;
@@ -32,6 +35,7 @@ pub(crate) fn create_storage_manager_for_tests(
 ) -> ProverStorageManager<MockDaSpec, DefaultStorageSpec> {
     let config = sov_state::config::Config {
         path: path.as_ref().to_path_buf(),
+        pruning: Default::default(),
     };
     ProverStorageManager::new(config).unwrap()
 }
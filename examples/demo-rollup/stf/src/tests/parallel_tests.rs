@@ -0,0 +1,431 @@
+use borsh::BorshSerialize;
+use sov_cli::wallet_state::PrivateKeyAndAddress;
+use sov_mock_da::{MockBlock, MOCK_SEQUENCER_DA_ADDRESS};
+use sov_modules_api::transaction::{AccessList, Transaction};
+use sov_modules_api::{Address, EncodeCall, PrivateKey, WorkingSet};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisConfig;
+use sov_modules_stf_blueprint::{
+    Batch, GenesisParams, RawTx, SequencerOutcome, StfBlueprint, TxEffect,
+};
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::{StateTransitionFunction, TransactionReceipt};
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::storage::StorageKey;
+
+use crate::runtime::GenesisConfig;
+use crate::tests::stf_tests::read_private_key;
+use crate::tests::{
+    create_storage_manager_for_tests, get_genesis_config_for_tests, Da, RuntimeTest,
+    StfBlueprintTest, C,
+};
+
+const RECEIVER: [u8; 32] = [7; 32];
+const TRANSFERRED: u64 = 10;
+/// The gas limit of the transactions paying a fee.
+const GAS_LIMIT: u64 = 100;
+
+type GenesisParamsTest = GenesisParams<GenesisConfig<C, Da>, BasicKernelGenesisConfig<C, Da>>;
+
+/// What a slot produced, serialized so that the executions can be compared.
+#[derive(Debug, PartialEq)]
+struct SlotOutput {
+    state_root: Vec<u8>,
+    receipts: String,
+    witness: String,
+    value: Option<u32>,
+    receiver_balance: Option<u64>,
+    admin_nonce: u64,
+    /// The borsh-encoded address of the token in which the fee of each transaction was settled, if it paid one.
+    settled_fee_tokens: Vec<Option<Vec<u8>>>,
+}
+
+fn token_address() -> Address {
+    sov_bank::get_genesis_token_address::<C>("sov-demo-token", 0)
+}
+
+fn gas_token_address() -> Address {
+    sov_bank::get_genesis_token_address::<C>("sov-gas-token", 0)
+}
+
+/// The signer of the transactions besides the admin, which holds no tokens at genesis.
+fn read_signer_key() -> PrivateKeyAndAddress<C> {
+    let signer_data = std::fs::read_to_string("../../test-data/keys/tx_signer_private_key.json")
+        .expect("Unable to read file to string");
+    serde_json::from_str(&signer_data).expect("Unable to convert data to PrivateKeyAndAddress")
+}
+
+fn key(key: StorageKey) -> Vec<u8> {
+    key.key().to_vec()
+}
+
+fn set_value_call(value: u32) -> Vec<u8> {
+    <RuntimeTest as EncodeCall<sov_value_setter::ValueSetter<C>>>::encode_call(
+        sov_value_setter::CallMessage::SetValue(value),
+    )
+}
+
+fn transfer_call(to: Address, amount: u64) -> Vec<u8> {
+    <RuntimeTest as EncodeCall<sov_bank::Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+        to,
+        coins: sov_bank::Coins {
+            amount,
+            token_address: token_address(),
+        },
+    })
+}
+
+/// The keys accessed by the call setting the value of the value setter.
+fn set_value_list() -> AccessList {
+    let runtime = RuntimeTest::default();
+    AccessList {
+        reads: vec![key(StorageKey::singleton(
+            runtime.value_setter.admin.prefix(),
+        ))],
+        writes: vec![key(StorageKey::singleton(
+            runtime.value_setter.value.prefix(),
+        ))],
+    }
+}
+
+/// The keys accessed by the call transferring tokens from `from` to `to`.
+fn transfer_list(from: Address, to: Address) -> AccessList {
+    let runtime = RuntimeTest::default();
+    AccessList {
+        reads: vec![key(runtime.bank.token_storage_key(token_address()))],
+        writes: vec![
+            key(runtime.bank.balance_storage_key(from, token_address())),
+            key(runtime.bank.balance_storage_key(to, token_address())),
+        ],
+    }
+}
+
+/// Adds the keys accessed by the hooks of a transaction signed by `signer` without a fee to `access_list`.
+fn with_hooks_keys(mut access_list: AccessList, signer: &PrivateKeyAndAddress<C>) -> AccessList {
+    let runtime = RuntimeTest::default();
+    let (reads, writes) = runtime
+        .accounts
+        .tx_hooks_storage_keys(&signer.private_key.pub_key(), &signer.address);
+    access_list.reads.extend(reads.into_iter().map(key));
+    access_list.writes.extend(writes.into_iter().map(key));
+    access_list
+}
+
+fn sign(
+    signer: &PrivateKeyAndAddress<C>,
+    nonce: u64,
+    msg: Vec<u8>,
+    gas_limit: u64,
+    access_list: Option<AccessList>,
+) -> RawTx {
+    let tx = Transaction::<C>::new_signed_tx_with_access_list(
+        &signer.private_key,
+        msg,
+        0,
+        0,
+        gas_limit,
+        0,
+        nonce,
+        access_list,
+    );
+    RawTx {
+        data: tx.try_to_vec().unwrap(),
+    }
+}
+
+/// The admin funds the signer, then sets the value of the value setter while the signer transfers the tokens to
+/// the receiver. The access lists declare the keys accessed by the hooks too, so the last two transactions form
+/// a wave applied in parallel. If `undeclared_value` is set, the admin then sets it with an empty access list.
+fn create_txs(undeclared_value: Option<u32>) -> Vec<RawTx> {
+    let admin = read_private_key::<C>();
+    let signer = read_signer_key();
+    let receiver = Address::from(RECEIVER);
+
+    let mut txs = vec![
+        sign(
+            &admin,
+            0,
+            transfer_call(signer.address, TRANSFERRED),
+            0,
+            Some(with_hooks_keys(
+                transfer_list(admin.address, signer.address),
+                &admin,
+            )),
+        ),
+        sign(
+            &admin,
+            1,
+            set_value_call(99),
+            0,
+            Some(with_hooks_keys(set_value_list(), &admin)),
+        ),
+        sign(
+            &signer,
+            0,
+            transfer_call(receiver, TRANSFERRED),
+            0,
+            Some(with_hooks_keys(
+                transfer_list(signer.address, receiver),
+                &signer,
+            )),
+        ),
+    ];
+    if let Some(value) = undeclared_value {
+        txs.push(sign(
+            &admin,
+            2,
+            set_value_call(value),
+            0,
+            Some(AccessList::default()),
+        ));
+    }
+    txs
+}
+
+/// Two transactions of the admin, with consecutive nonces, whose access lists only declare the keys accessed by
+/// their calls, so they form a single wave.
+fn create_same_sender_txs() -> Vec<RawTx> {
+    let admin = read_private_key::<C>();
+    let receiver = Address::from(RECEIVER);
+    vec![
+        sign(&admin, 0, set_value_call(99), 0, Some(set_value_list())),
+        sign(
+            &admin,
+            1,
+            transfer_call(receiver, TRANSFERRED),
+            0,
+            Some(transfer_list(admin.address, receiver)),
+        ),
+    ]
+}
+
+/// The admin accepts the demo token for fee payment and funds the signer with it. Then, in a single wave, the admin
+/// sets the value of the value setter, paying the fee in gas tokens, while the signer, who holds no gas tokens,
+/// transfers demo tokens to the receiver, paying the fee in demo tokens.
+fn create_mixed_fee_txs() -> Vec<RawTx> {
+    let admin = read_private_key::<C>();
+    let signer = read_signer_key();
+    let receiver = Address::from(RECEIVER);
+    let set_fee_token = <RuntimeTest as EncodeCall<sov_bank::Bank<C>>>::encode_call(
+        sov_bank::CallMessage::SetFeeToken {
+            token_address: token_address(),
+            rate: Some(sov_bank::ConversionRate {
+                numerator: 3,
+                denominator: 2,
+            }),
+        },
+    );
+
+    vec![
+        sign(&admin, 0, set_fee_token, 0, None),
+        sign(&admin, 1, transfer_call(signer.address, 1000), 0, None),
+        sign(
+            &admin,
+            2,
+            set_value_call(99),
+            GAS_LIMIT,
+            Some(set_value_list()),
+        ),
+        sign(
+            &signer,
+            0,
+            transfer_call(receiver, TRANSFERRED),
+            GAS_LIMIT,
+            Some(transfer_list(signer.address, receiver)),
+        ),
+    ]
+}
+
+/// The genesis of the tests, with a gas token held by the admin, who can accept other tokens for fee payment.
+fn genesis_with_gas_token() -> GenesisParamsTest {
+    let admin = read_private_key::<C>();
+    let mut config = get_genesis_config_for_tests();
+    config.runtime.bank.tokens.push(sov_bank::TokenConfig {
+        token_name: "sov-gas-token".to_owned(),
+        address_and_balances: vec![(admin.address, 1000)],
+        authorized_minters: vec![],
+        admins: vec![admin.address],
+        max_supply: None,
+        metadata: None,
+        salt: 0,
+    });
+    config
+}
+
+/// Returns the borsh-encoded address of the token in which the fee of a transaction was settled, if it paid one.
+fn settled_fee_token(receipt: &TransactionReceipt<TxEffect>) -> Option<Vec<u8>> {
+    receipt
+        .events
+        .iter()
+        .filter(|event| event.key().inner() == b"settle_fee")
+        .flat_map(|event| event.indexed_attributes())
+        .find(|attribute| attribute.name().inner() == b"token_address")
+        .map(|attribute| attribute.value().inner().clone())
+}
+
+fn apply_slot(stf: StfBlueprintTest, genesis: GenesisParamsTest, txs: Vec<RawTx>) -> SlotOutput {
+    let tempdir = tempfile::tempdir().unwrap();
+    let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, genesis);
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    let blob = sov_data_generators::new_test_blob_from_batch(
+        Batch { txs },
+        &MOCK_SEQUENCER_DA_ADDRESS,
+        [0; 32],
+    );
+    let mut blobs = [blob];
+    let block_1 = genesis_block.next_mock();
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, result.batch_receipts.len());
+    assert_eq!(
+        SequencerOutcome::Rewarded(0),
+        result.batch_receipts[0].inner
+    );
+    assert!(result.batch_receipts[0]
+        .tx_receipts
+        .iter()
+        .all(|receipt| receipt.receipt == TxEffect::Successful));
+
+    let runtime = RuntimeTest::default();
+    let mut working_set = WorkingSet::new(result.change_set);
+    let value = runtime
+        .value_setter
+        .query_value(&mut working_set)
+        .unwrap()
+        .value;
+    let receiver_balance = runtime
+        .bank
+        .balance_of(
+            None,
+            Address::from(RECEIVER),
+            token_address(),
+            &mut working_set,
+        )
+        .unwrap()
+        .amount;
+    let admin_nonce = runtime
+        .accounts
+        .get_nonce(
+            read_private_key::<C>().private_key.pub_key(),
+            0,
+            &mut working_set,
+        )
+        .unwrap();
+
+    SlotOutput {
+        state_root: result.state_root.as_ref().to_vec(),
+        receipts: serde_json::to_string(&result.batch_receipts).unwrap(),
+        witness: serde_json::to_string(&result.witness).unwrap(),
+        value,
+        receiver_balance,
+        admin_nonce,
+        settled_fee_tokens: result.batch_receipts[0]
+            .tx_receipts
+            .iter()
+            .map(settled_fee_token)
+            .collect(),
+    }
+}
+
+#[test]
+fn test_parallel_wave_matches_sequential_execution() {
+    let parallel = apply_slot(
+        StfBlueprint::new().with_max_threads(4),
+        get_genesis_config_for_tests(),
+        create_txs(None),
+    );
+    let sequential = apply_slot(
+        StfBlueprint::new().with_max_threads(1),
+        get_genesis_config_for_tests(),
+        create_txs(None),
+    );
+
+    assert_eq!(parallel.value, Some(99));
+    assert_eq!(parallel.receiver_balance, Some(TRANSFERRED));
+    assert_eq!(parallel.admin_nonce, 2);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn test_wave_with_undeclared_accesses_matches_sequential_execution() {
+    let parallel = apply_slot(
+        StfBlueprint::new().with_max_threads(4),
+        get_genesis_config_for_tests(),
+        create_txs(Some(33)),
+    );
+    let sequential = apply_slot(
+        StfBlueprint::new().with_max_threads(1),
+        get_genesis_config_for_tests(),
+        create_txs(Some(33)),
+    );
+
+    // The last transaction doesn't declare the keys it accesses, so the wave is applied again sequentially.
+    assert_eq!(parallel.value, Some(33));
+    assert_eq!(parallel.receiver_balance, Some(TRANSFERRED));
+    assert_eq!(parallel.admin_nonce, 3);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn test_wave_of_same_sender_matches_sequential_execution() {
+    let parallel = apply_slot(
+        StfBlueprint::new().with_max_threads(4),
+        get_genesis_config_for_tests(),
+        create_same_sender_txs(),
+    );
+    let sequential = apply_slot(
+        StfBlueprint::new().with_max_threads(1),
+        get_genesis_config_for_tests(),
+        create_same_sender_txs(),
+    );
+
+    // The hooks of the second transaction see the nonce incremented by the first one.
+    assert_eq!(parallel.value, Some(99));
+    assert_eq!(parallel.receiver_balance, Some(TRANSFERRED));
+    assert_eq!(parallel.admin_nonce, 2);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn test_wave_with_mixed_fee_tokens_matches_sequential_execution() {
+    let parallel = apply_slot(
+        StfBlueprint::new().with_max_threads(4),
+        genesis_with_gas_token(),
+        create_mixed_fee_txs(),
+    );
+    let sequential = apply_slot(
+        StfBlueprint::new().with_max_threads(1),
+        genesis_with_gas_token(),
+        create_mixed_fee_txs(),
+    );
+
+    // Each transaction of the wave settles its fee in the token it paid it with.
+    assert_eq!(
+        parallel.settled_fee_tokens,
+        vec![
+            None,
+            None,
+            Some(gas_token_address().try_to_vec().unwrap()),
+            Some(token_address().try_to_vec().unwrap()),
+        ]
+    );
+    assert_eq!(parallel.value, Some(99));
+    assert_eq!(parallel.receiver_balance, Some(TRANSFERRED));
+    assert_eq!(parallel, sequential);
+}
@@ -0,0 +1,58 @@
+use borsh::BorshSerialize;
+use sov_data_generators::bank_data::get_default_private_key;
+use sov_modules_api::schema::{BorshLayout, RuntimeSchema};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{EncodeCall, PrivateKey, Spec};
+use sov_modules_stf_blueprint::Runtime as _;
+
+use super::{RuntimeTest, C};
+
+#[test]
+fn test_runtime_schema_describes_encoding() {
+    let schema = RuntimeTest::schema().unwrap();
+
+    let modules = schema
+        .modules
+        .iter()
+        .map(|module| (module.module.as_str(), module.index))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("bank", 0),
+            ("sequencer_registry", 1),
+            ("value_setter", 2),
+            ("accounts", 3),
+            ("nft", 4),
+        ],
+        modules
+    );
+    let BorshLayout::Enum { variants } = &schema.call.root else {
+        panic!("The runtime call must be an enum");
+    };
+    assert_eq!(modules.len(), variants.len());
+
+    // The layouts match the actual encoding of a call message and of a signed transaction
+    let private_key = get_default_private_key();
+    let token_address = private_key.pub_key().to_address::<<C as Spec>::Address>();
+    let msg = <RuntimeTest as EncodeCall<sov_bank::Bank<C>>>::encode_call(
+        sov_bank::CallMessage::Transfer {
+            to: token_address,
+            coins: sov_bank::Coins {
+                amount: 15,
+                token_address,
+            },
+        },
+    );
+    schema.call.validate(&msg).unwrap();
+    assert!(schema.call.validate(&msg[..msg.len() - 1]).is_err());
+
+    let tx = Transaction::<C>::new_signed_tx(&private_key, msg, 0, 0, 1_000, 1);
+    schema
+        .transaction
+        .validate(&tx.try_to_vec().unwrap())
+        .unwrap();
+
+    // The schema is deterministic, and so is its version
+    assert_eq!(schema, RuntimeSchema::of::<RuntimeTest>().unwrap());
+    assert_eq!(64, schema.version.len());
+}
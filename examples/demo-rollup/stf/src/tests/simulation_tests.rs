@@ -0,0 +1,102 @@
+use borsh::BorshDeserialize;
+use sov_accounts::Response;
+use sov_data_generators::bank_data::get_default_private_key;
+use sov_mock_da::MockBlock;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{PrivateKey, WorkingSet};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
+use sov_modules_stf_blueprint::simulation::{SimulatedTx, Simulator};
+use sov_modules_stf_blueprint::{StfBlueprint, TxEffect};
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+
+use super::{create_storage_manager_for_tests, get_genesis_config_for_tests, RuntimeTest, C};
+use crate::tests::da_simulation::simulate_da_with_revert_msg;
+use crate::tests::{Da, StfBlueprintTest};
+
+#[test]
+fn test_simulate_without_committing() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let config = get_genesis_config_for_tests();
+    let sequencer_rollup_address = config.runtime.sequencer_registry.seq_rollup_address;
+
+    let genesis_block = MockBlock::default();
+    let block_1 = genesis_block.next_mock();
+
+    let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+    let stf: StfBlueprintTest = StfBlueprint::new();
+    let (_, storage) = stf.init_chain(
+        storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap(),
+        config,
+    );
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+
+    let simulator = Simulator::<C, Da, RuntimeTest, BasicKernel<C, Da>>::new(
+        storage.clone(),
+        sequencer_rollup_address,
+    );
+    // create 1000 tokens
+    // transfer 15 tokens
+    // transfer 5000 tokens
+    let txs = simulate_da_with_revert_msg();
+
+    let result = simulator
+        .simulate(SimulatedTx::Signed {
+            body: txs[0].data.clone(),
+        })
+        .unwrap();
+    assert_eq!(TxEffect::Successful, result.effect);
+    assert_eq!(None, result.revert_reason);
+    assert!(!result.state_diff.is_empty());
+
+    // The first transaction wasn't committed, so the nonce of the second one is too high
+    let result = simulator
+        .simulate(SimulatedTx::Signed {
+            body: txs[1].data.clone(),
+        })
+        .unwrap();
+    assert_eq!(TxEffect::Reverted, result.effect);
+    assert!(result
+        .revert_reason
+        .unwrap()
+        .starts_with("Stateful verification error"));
+
+    // An unsigned transaction skips the nonce check, and is reverted by the bank
+    let transfer = Transaction::<C>::try_from_slice(&txs[2].data).unwrap();
+    let result = simulator
+        .simulate(SimulatedTx::Unsigned {
+            sender: get_default_private_key().default_address(),
+            runtime_msg: transfer.runtime_msg().to_vec(),
+            gas_limit: transfer.gas_limit(),
+        })
+        .unwrap();
+    assert_eq!(TxEffect::Reverted, result.effect);
+    assert!(result.revert_reason.is_some());
+    assert!(result.events.is_empty());
+    assert!(result.state_diff.is_empty());
+
+    // A transaction which isn't correctly signed can't be simulated
+    let mut body = txs[0].data.clone();
+    let last = body.len() - 1;
+    body[last] ^= 1;
+    assert!(simulator.simulate(SimulatedTx::Signed { body }).is_err());
+
+    let runtime = RuntimeTest::default();
+    let mut working_set = WorkingSet::new(storage);
+    let nonce = match runtime
+        .accounts
+        .get_account(get_default_private_key().pub_key(), &mut working_set)
+        .unwrap()
+    {
+        Response::AccountExists { nonce, .. } => nonce,
+        Response::AccountEmpty => 0,
+    };
+    assert_eq!(0, nonce);
+}
@@ -1,11 +1,17 @@
+use std::rc::Rc;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_chain_state::ChainState;
 use sov_cli::wallet_state::PrivateKeyAndAddress;
 use sov_data_generators::bank_data::get_default_token_address;
-use sov_data_generators::{has_tx_events, new_test_blob_from_batch};
-use sov_mock_da::{MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
+use sov_data_generators::value_setter_data::{ValueSetterMessage, ValueSetterMessages};
+use sov_data_generators::{has_tx_events, new_test_blob_from_batch, MessageGenerator};
+use sov_mock_da::{MockAddress, MockBlob, MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::{Context, PrivateKey, WorkingSet};
-use sov_modules_stf_blueprint::{Batch, SequencerOutcome, StfBlueprint};
+use sov_modules_api::transaction::{AccessList, Transaction};
+use sov_modules_api::{Address, Context, PrivateKey, WorkingSet};
+use sov_modules_stf_blueprint::{Batch, RawTx, SequencerOutcome, StfBlueprint, TxEffect};
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
@@ -274,7 +280,157 @@ fn test_sequencer_unknown_sequencer() {
     assert!(!has_tx_events(&apply_blob_outcome));
 }
 
-fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
+#[test]
+fn test_demo_values_with_undeclared_accesses() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let config = get_genesis_config_for_tests();
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, config);
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // Both transactions declare empty access lists, so they're scheduled in the same wave. They access
+    // the same state, so the wave must be applied sequentially.
+    let admin = read_private_key::<DefaultContext>().private_key;
+    let value_setter = ValueSetterMessages::new(vec![ValueSetterMessage {
+        admin: Rc::new(admin.clone()),
+        messages: vec![99, 33],
+    }]);
+    let txs = value_setter
+        .create_raw_txs::<Runtime<DefaultContext, MockDaSpec>>()
+        .into_iter()
+        .map(|raw_tx| {
+            let tx = Transaction::<DefaultContext>::try_from_slice(&raw_tx.data).unwrap();
+            let tx = Transaction::<DefaultContext>::new_signed_tx_with_access_list(
+                &admin,
+                tx.runtime_msg().to_vec(),
+                tx.chain_id(),
+                tx.gas_tip(),
+                tx.gas_limit(),
+                tx.nonce_lane(),
+                tx.nonce(),
+                Some(AccessList::default()),
+            );
+            RawTx {
+                data: tx.try_to_vec().unwrap(),
+            }
+        })
+        .collect();
+
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+    let mut blobs = [blob];
+    let block_1 = genesis_block.next_mock();
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+
+    let apply_block_result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, apply_block_result.batch_receipts.len());
+    let apply_blob_outcome = apply_block_result.batch_receipts[0].clone();
+    assert_eq!(SequencerOutcome::Rewarded(0), apply_blob_outcome.inner);
+    assert!(apply_blob_outcome
+        .tx_receipts
+        .iter()
+        .all(|receipt| receipt.receipt == TxEffect::Successful));
+
+    let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(apply_block_result.change_set);
+    let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
+    assert_eq!(resp, sov_value_setter::Response { value: Some(33) });
+}
+
+#[test]
+fn test_demo_values_with_expiry() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let config = get_genesis_config_for_tests();
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, config);
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // The first transaction is valid until the slot it's included in, the second one has already expired.
+    let admin = read_private_key::<DefaultContext>().private_key;
+    let value_setter = ValueSetterMessages::new(vec![ValueSetterMessage {
+        admin: Rc::new(admin.clone()),
+        messages: vec![99, 33],
+    }]);
+    let txs = value_setter
+        .create_raw_txs::<Runtime<DefaultContext, MockDaSpec>>()
+        .into_iter()
+        .zip([1, 0])
+        .map(|(raw_tx, valid_until_slot)| {
+            let tx = Transaction::<DefaultContext>::try_from_slice(&raw_tx.data).unwrap();
+            let tx = Transaction::<DefaultContext>::new_signed_tx_with_expiry(
+                &admin,
+                tx.runtime_msg().to_vec(),
+                tx.chain_id(),
+                tx.gas_tip(),
+                tx.gas_limit(),
+                tx.nonce_lane(),
+                tx.nonce(),
+                valid_until_slot,
+            );
+            RawTx {
+                data: tx.try_to_vec().unwrap(),
+            }
+        })
+        .collect();
+
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+    let mut blobs = [blob];
+    let block_1 = genesis_block.next_mock();
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+
+    let apply_block_result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, apply_block_result.batch_receipts.len());
+    let receipts: Vec<_> = apply_block_result.batch_receipts[0]
+        .tx_receipts
+        .iter()
+        .map(|receipt| receipt.receipt)
+        .collect();
+    assert_eq!(receipts, vec![TxEffect::Successful, TxEffect::Reverted]);
+
+    let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(apply_block_result.change_set);
+    let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
+    assert_eq!(resp, sov_value_setter::Response { value: Some(99) });
+}
+
+pub(crate) fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
     let token_deployer_data =
         std::fs::read_to_string("../../test-data/keys/token_deployer_private_key.json")
             .expect("Unable to read file to string");
@@ -294,3 +450,144 @@ fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
 
     token_deployer
 }
+
+#[test]
+fn test_chain_state_is_updated_by_the_kernel() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, get_genesis_config_for_tests());
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // The slots contain no transaction, so nothing but the kernel touches the chain state
+    let mut blocks = vec![genesis_block.next_mock()];
+    let mut roots = vec![genesis_root];
+    for _ in 0..3 {
+        let block = blocks.last().unwrap();
+        let storage = storage_manager.create_storage_on(block.header()).unwrap();
+        let result = stf.apply_slot(
+            roots.last().unwrap(),
+            storage,
+            Default::default(),
+            &block.header,
+            &block.validity_cond,
+            &mut Vec::<MockBlob>::new(),
+        );
+        storage_manager
+            .save_change_set(block.header(), result.change_set)
+            .unwrap();
+        let next_block = block.next_mock();
+        roots.push(result.state_root);
+        blocks.push(next_block);
+    }
+
+    let storage = storage_manager
+        .create_storage_on(blocks[3].header())
+        .unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+    assert_eq!(chain_state.true_slot_height(&mut working_set), 3);
+    assert_eq!(
+        chain_state.get_genesis_hash(&mut working_set),
+        Some(genesis_root)
+    );
+
+    // The transition of each slot is stored by the next one, so the transition of the last slot isn't stored yet
+    for height in 1..=3 {
+        let block = &blocks[height as usize - 1];
+        let transition = chain_state.get_historical_transitions(height, &mut working_set);
+        assert_eq!(
+            transition.map(|transition| {
+                transition.compare_hashes(&block.header.hash, &roots[height as usize])
+            }),
+            (height < 3).then_some(true)
+        );
+
+        let address = Address::from([0; 32]);
+        let mut versioned_working_set =
+            working_set.versioned_state(&DefaultContext::new(address, address, 3));
+        assert_eq!(
+            chain_state.time_at(height, &mut versioned_working_set),
+            Some(block.header.time.clone())
+        );
+    }
+}
+
+#[test]
+fn test_slot_sequencers_are_recorded() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, get_genesis_config_for_tests());
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // The first slot has two batches of the preferred sequencer and one of an unknown sequencer,
+    // which is deferred until it expires two slots later
+    let unknown_sequencer = [121; 32];
+    let empty_blob = |sequencer: &[u8], hash: u8| {
+        new_test_blob_from_batch(Batch { txs: vec![] }, sequencer, [hash; 32])
+    };
+    let mut slots = vec![
+        vec![
+            empty_blob(&unknown_sequencer, 0),
+            empty_blob(&MOCK_SEQUENCER_DA_ADDRESS, 1),
+            empty_blob(&MOCK_SEQUENCER_DA_ADDRESS, 2),
+        ],
+        vec![],
+        vec![],
+    ];
+
+    let mut block = genesis_block;
+    let mut root = genesis_root;
+    let mut batches = vec![];
+    for blobs in slots.iter_mut() {
+        block = block.next_mock();
+        let storage = storage_manager.create_storage_on(block.header()).unwrap();
+        let result = stf.apply_slot(
+            &root,
+            storage,
+            Default::default(),
+            &block.header,
+            &block.validity_cond,
+            blobs,
+        );
+        batches.push(result.batch_receipts.len());
+        storage_manager
+            .save_change_set(block.header(), result.change_set)
+            .unwrap();
+        root = result.state_root;
+    }
+    assert_eq!(batches, vec![2, 0, 1]);
+
+    // Each sequencer is recorded once per slot, in the slot its batches are executed
+    let storage = storage_manager
+        .create_storage_on(block.next_mock().header())
+        .unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+    assert_eq!(
+        chain_state.get_slot_sequencers(1, &mut working_set),
+        vec![MockAddress::from(MOCK_SEQUENCER_DA_ADDRESS)]
+    );
+    assert!(chain_state
+        .get_slot_sequencers(2, &mut working_set)
+        .is_empty());
+    assert_eq!(
+        chain_state.get_slot_sequencers(3, &mut working_set),
+        vec![MockAddress::from(unknown_sequencer)]
+    );
+}
@@ -1,10 +1,14 @@
+use borsh::BorshSerialize;
 use sov_accounts::Response;
 use sov_data_generators::bank_data::{get_default_private_key, get_default_token_address};
 use sov_data_generators::{has_tx_events, new_test_blob_from_batch};
 use sov_mock_da::{MockAddress, MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
 use sov_modules_api::default_context::DefaultContext;
-use sov_modules_api::{PrivateKey, WorkingSet};
-use sov_modules_stf_blueprint::{Batch, SequencerOutcome, SlashingReason, StfBlueprint, TxEffect};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{EncodeCall, PrivateKey, WorkingSet};
+use sov_modules_stf_blueprint::{
+    Batch, RawTx, Runtime as _, SequencerOutcome, SlashingReason, StfBlueprint, TxEffect,
+};
 use sov_rollup_interface::da::BlobReaderTrait;
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::StateTransitionFunction;
@@ -16,6 +20,7 @@ use crate::tests::da_simulation::{
     simulate_da_with_bad_nonce, simulate_da_with_bad_serialization, simulate_da_with_bad_sig,
     simulate_da_with_revert_msg,
 };
+use crate::tests::stf_tests::read_private_key;
 use crate::tests::StfBlueprintTest;
 
 // Assume there was proper address and we converted it to bytes already.
@@ -359,3 +364,228 @@ fn test_tx_bad_serialization() {
         assert_eq!(sequencer_balance_before, sequencer_balance_after);
     }
 }
+
+#[test]
+fn test_batch_exceeding_resource_limits() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+
+    let config = get_genesis_config_for_tests();
+    let genesis_block = MockBlock::default();
+    let block_1 = genesis_block.next_mock();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let (genesis_root, storage) = stf.init_chain(
+        storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap(),
+        config,
+    );
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    let limits = RuntimeTest::resource_limits();
+    let tx = Transaction::<DefaultContext>::new_signed_tx(
+        &get_default_private_key(),
+        vec![0; limits.max_call_message_size + 1],
+        0,
+        0,
+        0,
+        0,
+    );
+    let txs = vec![RawTx {
+        data: tx.try_to_vec().unwrap(),
+    }];
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+    let blob_sender = blob.sender();
+    let mut blobs = [blob];
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let apply_block_result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, apply_block_result.batch_receipts.len());
+    assert_eq!(
+        SequencerOutcome::Slashed {
+            reason: SlashingReason::CallMessageTooLarge,
+            sequencer_da_address: blob_sender,
+        },
+        apply_block_result.batch_receipts[0].inner,
+    );
+}
+
+#[test]
+fn test_tx_signed_for_another_chain() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+
+    let config = get_genesis_config_for_tests();
+    let chain_id = config.kernel.chain_state.chain_id;
+    let genesis_block = MockBlock::default();
+    let block_1 = genesis_block.next_mock();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let (genesis_root, storage) = stf.init_chain(
+        storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap(),
+        config,
+    );
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // The same admin sets the value twice with the same nonce, first for another chain
+    let admin = read_private_key::<DefaultContext>();
+    let set_value = |value, chain_id| {
+        let msg =
+            <RuntimeTest as EncodeCall<sov_value_setter::ValueSetter<DefaultContext>>>::encode_call(
+                sov_value_setter::CallMessage::SetValue(value),
+            );
+        let tx = Transaction::<DefaultContext>::new_signed_tx(
+            &admin.private_key,
+            msg,
+            chain_id,
+            0,
+            0,
+            0,
+        );
+        RawTx {
+            data: tx.try_to_vec().unwrap(),
+        }
+    };
+    let txs = vec![set_value(5, chain_id + 1), set_value(7, chain_id)];
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+    let mut blobs = [blob];
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let apply_block_result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, apply_block_result.batch_receipts.len());
+    let batch_receipt = &apply_block_result.batch_receipts[0];
+    assert_eq!(SequencerOutcome::Rewarded(0), batch_receipt.inner);
+
+    // The transaction signed for another chain is rejected without any effect
+    let rejected = &batch_receipt.tx_receipts[0];
+    assert_eq!(rejected.receipt, TxEffect::Reverted);
+    assert!(rejected.events.is_empty());
+    assert!(rejected.body_to_save.is_none());
+    assert_eq!(batch_receipt.tx_receipts[1].receipt, TxEffect::Successful);
+
+    // In particular, it didn't consume the nonce of the admin
+    let runtime = Runtime::<DefaultContext, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(apply_block_result.change_set);
+    assert_eq!(
+        runtime
+            .value_setter
+            .query_value(&mut working_set)
+            .unwrap()
+            .value,
+        Some(7)
+    );
+    let nonce = match runtime
+        .accounts
+        .get_account(admin.private_key.pub_key(), &mut working_set)
+        .unwrap()
+    {
+        Response::AccountExists { nonce, .. } => nonce,
+        Response::AccountEmpty => 0,
+    };
+    assert_eq!(1, nonce);
+}
+
+#[test]
+fn test_tx_out_of_gas_on_state_access() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+
+    // The runtime charges for state accesses, which are only paid for once gas is priced
+    let mut config = get_genesis_config_for_tests();
+    config
+        .kernel
+        .chain_state
+        .fee_market
+        .initial_base_fee_per_gas = vec![1, 1];
+    let chain_id = config.kernel.chain_state.chain_id;
+    let genesis_block = MockBlock::default();
+    let block_1 = genesis_block.next_mock();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+    let stf: StfBlueprintTest = StfBlueprint::new();
+
+    let (genesis_root, storage) = stf.init_chain(
+        storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap(),
+        config,
+    );
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    // The hooks access the state for free, but the call can't pay for its accesses without a gas limit
+    let admin = read_private_key::<DefaultContext>();
+    let msg =
+        <RuntimeTest as EncodeCall<sov_value_setter::ValueSetter<DefaultContext>>>::encode_call(
+            sov_value_setter::CallMessage::SetValue(5),
+        );
+    let tx =
+        Transaction::<DefaultContext>::new_signed_tx(&admin.private_key, msg, chain_id, 0, 0, 0);
+    let txs = vec![RawTx {
+        data: tx.try_to_vec().unwrap(),
+    }];
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+    let mut blobs = [blob];
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let apply_block_result = stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut blobs,
+    );
+
+    assert_eq!(1, apply_block_result.batch_receipts.len());
+    let batch_receipt = &apply_block_result.batch_receipts[0];
+    assert_eq!(SequencerOutcome::Rewarded(0), batch_receipt.inner);
+    assert_eq!(1, batch_receipt.tx_receipts.len());
+    assert_eq!(batch_receipt.tx_receipts[0].receipt, TxEffect::Reverted);
+
+    // The call was reverted, but the nonce consumed by the pre-dispatch hook is kept
+    let runtime = Runtime::<DefaultContext, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(apply_block_result.change_set);
+    assert_eq!(
+        runtime
+            .value_setter
+            .query_value(&mut working_set)
+            .unwrap()
+            .value,
+        None
+    );
+    let nonce = match runtime
+        .accounts
+        .get_account(admin.private_key.pub_key(), &mut working_set)
+        .unwrap()
+    {
+        Response::AccountExists { nonce, .. } => nonce,
+        Response::AccountEmpty => 0,
+    };
+    assert_eq!(1, nonce);
+}
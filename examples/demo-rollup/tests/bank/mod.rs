@@ -56,6 +56,8 @@ async fn build_create_token_tx(key: &DefaultPrivateKey, nonce: u64) -> Transacti
         initial_balance: 1000,
         minter_address: user_address,
         authorized_minters: vec![],
+        max_supply: None,
+        metadata: None,
     });
     let chain_id = 0;
     let gas_tip = 0;
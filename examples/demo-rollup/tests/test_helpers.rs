@@ -24,20 +24,27 @@ pub async fn start_rollup(
     let rollup_config = RollupConfig {
         storage: StorageConfig {
             path: temp_path.to_path_buf(),
+            pruning: Default::default(),
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".into(),
                 bind_port: 0,
+                rest_bind_port: None,
+                metrics_bind_port: None,
             },
+            max_proof_attempts: 3,
+            max_proving_lag: 16,
         },
         da: MockDaConfig {
             sender_address: MockAddress::from([0; 32]),
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            num_workers: None,
         },
+        chain: Default::default(),
     };
 
     let mock_demo_rollup = MockDemoRollup {};
@@ -1,22 +1,27 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::Serialize;
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::{BatchReceipt, Event};
 use sov_schema_db::{Schema, SchemaBatch, SeekKeyEncoder, DB};
 
+use crate::pruning::PruningTask;
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber, TxByHash,
-    TxByNumber, LEDGER_TABLES,
+    BatchByHash, BatchByNumber, EventByKey, EventByNumber, OldestRetainedItems, PendingSlotCommit,
+    SlotByHash, SlotByNumber, TxByHash, TxByNumber, LEDGER_TABLES,
 };
 use crate::schema::types::{
     split_tx_for_storage, BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot,
     StoredTransaction, TxNumber,
 };
 
+mod proof_jobs;
 mod rpc;
+mod verified_proofs;
 
 const LEDGER_DB_PATH_SUFFIX: &str = "ledger";
 
@@ -30,11 +35,13 @@ pub struct LedgerDB {
     db: Arc<DB>,
     next_item_numbers: Arc<Mutex<ItemNumbers>>,
     slot_subscriptions: tokio::sync::broadcast::Sender<u64>,
+    /// Stops when the last clone of the [`LedgerDB`] is dropped.
+    pruning_task: Option<Arc<PruningTask>>,
 }
 
 /// A SlotNumber, BatchNumber, TxNumber, and EventNumber which are grouped together, typically representing
 /// the respective heights at the start or end of slot processing.
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct ItemNumbers {
     /// The slot number
@@ -97,22 +104,51 @@ impl LedgerDB {
             &gen_rocksdb_options(&Default::default(), false),
         )?;
 
+        // Pruning may have deleted all the items of a kind, numbering then resumes from the oldest retained one
+        let oldest = inner.get::<OldestRetainedItems>(&())?.unwrap_or_default();
         let next_item_numbers = ItemNumbers {
-            slot_number: Self::last_version_written(&inner, SlotByNumber)?.unwrap_or_default() + 1,
-            batch_number: Self::last_version_written(&inner, BatchByNumber)?.unwrap_or_default()
-                + 1,
-            tx_number: Self::last_version_written(&inner, TxByNumber)?.unwrap_or_default() + 1,
-            event_number: Self::last_version_written(&inner, EventByNumber)?.unwrap_or_default()
-                + 1,
+            slot_number: (Self::last_version_written(&inner, SlotByNumber)?.unwrap_or_default()
+                + 1)
+            .max(oldest.slot_number),
+            batch_number: (Self::last_version_written(&inner, BatchByNumber)?.unwrap_or_default()
+                + 1)
+            .max(oldest.batch_number),
+            tx_number: (Self::last_version_written(&inner, TxByNumber)?.unwrap_or_default() + 1)
+                .max(oldest.tx_number),
+            event_number: (Self::last_version_written(&inner, EventByNumber)?.unwrap_or_default()
+                + 1)
+            .max(oldest.event_number),
         };
 
         Ok(Self {
             db: Arc::new(inner),
             next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
             slot_subscriptions: tokio::sync::broadcast::channel(10).0,
+            pruning_task: None,
         })
     }
 
+    /// Starts a background task deleting the slots older than the latest `retained_slots` ones every `interval`,
+    /// as done by [`LedgerDB::prune`], and compacting the database afterwards.
+    /// The task stops when the last clone of this [`LedgerDB`] is dropped.
+    pub fn start_pruning(
+        &mut self,
+        retained_slots: u64,
+        interval: Duration,
+    ) -> Result<(), anyhow::Error> {
+        // The task holds a clone without the task itself, which would otherwise never be dropped
+        let mut ledger_db = self.clone();
+        ledger_db.pruning_task = None;
+        let task = PruningTask::spawn("ledger-pruning", interval, move || {
+            if ledger_db.prune(retained_slots)? {
+                ledger_db.compact()?;
+            }
+            Ok(())
+        })?;
+        self.pruning_task = Some(Arc::new(task));
+        Ok(())
+    }
+
     /// Get the next slot, block, transaction, and event numbers
     pub fn get_next_items_numbers(&self) -> ItemNumbers {
         self.next_item_numbers.lock().unwrap().clone()
@@ -288,6 +324,8 @@ impl LedgerDB {
             &SlotNumber(current_item_numbers.slot_number),
             &mut schema_batch,
         )?;
+        // The slot is committed along with the deletion of its pending commit, so a commit is never partially applied
+        schema_batch.delete::<PendingSlotCommit>(&())?;
 
         self.db.write_schemas(schema_batch)?;
 
@@ -299,6 +337,25 @@ impl LedgerDB {
         Ok(())
     }
 
+    /// Records that the storage of the next slot is about to be finalized, while the finalized storage is at
+    /// `storage_version`. The record is deleted by the [`LedgerDB::commit_slot`] of the slot, or by
+    /// [`LedgerDB::clear_pending_slot_commit`] when nothing is committed to the ledger, as for the genesis.
+    pub fn put_pending_slot_commit(&self, storage_version: u64) -> Result<(), anyhow::Error> {
+        self.db.put::<PendingSlotCommit>(&(), &storage_version)
+    }
+
+    /// Returns the version recorded by [`LedgerDB::put_pending_slot_commit`], if the slot was never committed.
+    /// The finalized storage may then contain the data of a slot missing from the ledger, and should be
+    /// rolled back to this version.
+    pub fn get_pending_slot_commit(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.db.get::<PendingSlotCommit>(&())
+    }
+
+    /// Deletes the record written by [`LedgerDB::put_pending_slot_commit`].
+    pub fn clear_pending_slot_commit(&self) -> Result<(), anyhow::Error> {
+        self.db.delete::<PendingSlotCommit>(&())
+    }
+
     fn last_version_written<T: Schema<Key = U>, U: Into<u64>>(
         db: &DB,
         _schema: T,
@@ -313,6 +370,163 @@ impl LedgerDB {
         }
     }
 
+    /// Get the numbers of the oldest slot, batch, transaction and event which were not pruned
+    pub fn get_oldest_retained_items(&self) -> Result<ItemNumbers, anyhow::Error> {
+        Ok(self.db.get::<OldestRetainedItems>(&())?.unwrap_or_default())
+    }
+
+    /// Deletes the slots older than the latest `retained_slots` ones, with their batches, transactions and events,
+    /// and records the oldest items which can still be read. Returns false if there was nothing to prune.
+    pub fn prune(&self, retained_slots: u64) -> Result<bool, anyhow::Error> {
+        let Some((head_slot, _)) = self.get_head_slot()? else {
+            return Ok(false);
+        };
+        let oldest_retained_slot = (head_slot.0 + 1).saturating_sub(retained_slots.max(1));
+        let pruned = self.get_oldest_retained_items()?;
+        if oldest_retained_slot <= pruned.slot_number {
+            return Ok(false);
+        }
+        let Some(oldest_slot) = self
+            .db
+            .get::<SlotByNumber>(&SlotNumber(oldest_retained_slot))?
+        else {
+            return Ok(false);
+        };
+
+        // The oldest retained items are the first ones after the pruned slots. If there are none yet,
+        // they are the ones following the last pruned items.
+        let batch_number = oldest_slot.batches.start;
+        let mut batches = self.db.iter::<BatchByNumber>()?;
+        batches.seek(&batch_number)?;
+        let tx_number = match batches.next().transpose()? {
+            Some(batch) => batch.value.txs.start,
+            None => match self
+                .db
+                .get::<BatchByNumber>(&BatchNumber(batch_number.0.saturating_sub(1)))?
+            {
+                Some(batch) => batch.txs.end,
+                None => TxNumber(pruned.tx_number),
+            },
+        };
+        let mut txs = self.db.iter::<TxByNumber>()?;
+        txs.seek(&tx_number)?;
+        let event_number = match txs.next().transpose()? {
+            Some(tx) => tx.value.events.start,
+            None => match self
+                .db
+                .get::<TxByNumber>(&TxNumber(tx_number.0.saturating_sub(1)))?
+            {
+                Some(tx) => tx.events.end,
+                None => EventNumber(pruned.event_number),
+            },
+        };
+
+        let mut schema_batch = SchemaBatch::new();
+        let mut slots = self.db.iter::<SlotByNumber>()?;
+        slots.seek_to_first();
+        for slot in slots {
+            let (number, slot) = slot?.into_tuple();
+            if number.0 >= oldest_retained_slot {
+                break;
+            }
+            schema_batch.delete::<SlotByNumber>(&number)?;
+            schema_batch.delete::<SlotByHash>(&slot.hash)?;
+        }
+
+        let mut batches = self.db.iter::<BatchByNumber>()?;
+        batches.seek_to_first();
+        for batch in batches {
+            let (number, batch) = batch?.into_tuple();
+            if number >= batch_number {
+                break;
+            }
+            schema_batch.delete::<BatchByNumber>(&number)?;
+            schema_batch.delete::<BatchByHash>(&batch.hash)?;
+        }
+
+        let mut txs = self.db.iter::<TxByNumber>()?;
+        txs.seek_to_first();
+        for tx in txs {
+            let (number, tx) = tx?.into_tuple();
+            if number >= tx_number {
+                break;
+            }
+            for event_number in tx.events.start.0..tx.events.end.0 {
+                let event_number = EventNumber(event_number);
+                if let Some(event) = self.db.get::<EventByNumber>(&event_number)? {
+                    schema_batch.delete::<EventByNumber>(&event_number)?;
+                    schema_batch.delete::<EventByKey>(&(
+                        event.key().clone(),
+                        number,
+                        event_number,
+                    ))?;
+                }
+            }
+            schema_batch.delete::<TxByNumber>(&number)?;
+            schema_batch.delete::<TxByHash>(&tx.hash)?;
+        }
+
+        let oldest_retained_items = ItemNumbers {
+            slot_number: oldest_retained_slot,
+            batch_number: batch_number.0,
+            tx_number: tx_number.0,
+            event_number: event_number.0,
+        };
+        schema_batch.put::<OldestRetainedItems>(&(), &oldest_retained_items)?;
+        self.db.write_schemas(schema_batch)?;
+        Ok(true)
+    }
+
+    /// Compacts all the tables of the ledger, reclaiming the space of pruned items.
+    pub fn compact(&self) -> Result<(), anyhow::Error> {
+        for table in LEDGER_TABLES {
+            self.db.compact_cf(table)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the files of the tables of the ledger, in bytes.
+    pub fn size_on_disk(&self) -> Result<u64, anyhow::Error> {
+        let mut size = 0;
+        for table in LEDGER_TABLES {
+            size += self
+                .db
+                .get_property(table, "rocksdb.total-sst-files-size")?;
+        }
+        Ok(size)
+    }
+
+    /// Records the slot `slot_number` with hash `slot_hash` as the head of an empty ledger, so that a node whose
+    /// state was imported from a snapshot taken at this slot resumes processing from the next one.
+    /// The slots before the head, and their batches, transactions and events, are reported as pruned.
+    pub fn import_snapshot_head(
+        &self,
+        slot_number: u64,
+        slot_hash: [u8; 32],
+    ) -> Result<(), anyhow::Error> {
+        if self.get_head_slot()?.is_some() {
+            anyhow::bail!("Cannot import a snapshot into a non-empty ledger");
+        }
+
+        let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
+        let slot = StoredSlot {
+            hash: slot_hash,
+            extra_data: vec![].into(),
+            batches: BatchNumber(next_item_numbers.batch_number)
+                ..BatchNumber(next_item_numbers.batch_number),
+        };
+        let oldest_retained_items = ItemNumbers {
+            slot_number,
+            ..next_item_numbers.clone()
+        };
+        let mut schema_batch = SchemaBatch::new();
+        self.put_slot(&slot, &SlotNumber(slot_number), &mut schema_batch)?;
+        schema_batch.put::<OldestRetainedItems>(&(), &oldest_retained_items)?;
+        self.db.write_schemas(schema_batch)?;
+        next_item_numbers.slot_number = slot_number + 1;
+        Ok(())
+    }
+
     /// Get the most recent committed slot, if any
     pub fn get_head_slot(&self) -> anyhow::Result<Option<(SlotNumber, StoredSlot)>> {
         let mut iter = self.db.iter::<SlotByNumber>()?;
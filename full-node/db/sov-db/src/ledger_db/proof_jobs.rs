@@ -0,0 +1,161 @@
+use sov_rollup_interface::rpc::ProofJobStatus;
+use sov_schema_db::SchemaBatch;
+
+use super::LedgerDB;
+use crate::schema::tables::{LastExecutedHeight, LastProvenHeight, ProofJobs, UnfinishedProofJobs};
+use crate::schema::types::StoredProofJob;
+
+impl LedgerDB {
+    /// Queues a job proving the slot at DA height `height`, with its serialized `StateTransitionData`.
+    /// A previous job for the same height is replaced.
+    pub fn enqueue_proof_job(
+        &self,
+        height: u64,
+        state_transition_data: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let job = StoredProofJob {
+            status: ProofJobStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            state_transition_data: Some(state_transition_data),
+        };
+        self.put_proof_job(height, &job)
+    }
+
+    /// Get the proof job of the slot at DA height `height`, if any
+    pub fn get_proof_job(&self, height: u64) -> Result<Option<StoredProofJob>, anyhow::Error> {
+        self.db.get::<ProofJobs>(&height)
+    }
+
+    /// Get the proof jobs from DA height `start`, by increasing height, up to `limit` jobs
+    pub fn get_proof_jobs(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, StoredProofJob)>, anyhow::Error> {
+        let mut iter = self.db.iter::<ProofJobs>()?;
+        iter.seek(&start)?;
+        iter.take(limit)
+            .map(|item| Ok(item?.into_tuple()))
+            .collect()
+    }
+
+    /// Get the pending and running proof jobs, by increasing height. When the node starts,
+    /// the running jobs are the ones which were interrupted.
+    pub fn get_unfinished_proof_jobs(&self) -> Result<Vec<(u64, StoredProofJob)>, anyhow::Error> {
+        let mut iter = self.db.iter::<UnfinishedProofJobs>()?;
+        iter.seek_to_first();
+
+        let mut jobs = Vec::new();
+        for item in iter {
+            let height = item?.key;
+            if let Some(job) = self.get_proof_job(height)? {
+                jobs.push((height, job));
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// Records that the proof generation of the job at DA height `height` started.
+    pub fn mark_proof_job_running(&self, height: u64) -> Result<(), anyhow::Error> {
+        let mut job = self.get_existing_proof_job(height)?;
+        job.status = ProofJobStatus::Running;
+        job.attempts += 1;
+        self.put_proof_job(height, &job)
+    }
+
+    /// Records that the proof of the job at DA height `height` was generated and sent to the DA,
+    /// and drops the state transition data of the job. The slot becomes the last proven one,
+    /// unless a later slot was already proven.
+    pub fn mark_proof_job_complete(&self, height: u64) -> Result<(), anyhow::Error> {
+        let mut job = self.get_existing_proof_job(height)?;
+        job.status = ProofJobStatus::Complete;
+        job.last_error = None;
+        job.state_transition_data = None;
+
+        let mut schema_batch = Self::proof_job_batch(height, &job)?;
+        // The proofs are sent in slot order, but a requeued job of an older slot can complete later
+        if self
+            .get_last_proven_height()?
+            .map_or(true, |last_proven_height| last_proven_height < height)
+        {
+            schema_batch.put::<LastProvenHeight>(&(), &height)?;
+        }
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Records that the proof generation of the job at DA height `height` failed with `error`.
+    /// The job is queued again if it was started less than `max_attempts` times, and fails otherwise.
+    /// Returns the new status of the job.
+    pub fn mark_proof_job_failed(
+        &self,
+        height: u64,
+        error: String,
+        max_attempts: u32,
+    ) -> Result<ProofJobStatus, anyhow::Error> {
+        let mut job = self.get_existing_proof_job(height)?;
+        job.status = if job.attempts < max_attempts {
+            ProofJobStatus::Pending
+        } else {
+            ProofJobStatus::Failed
+        };
+        job.last_error = Some(error);
+        self.put_proof_job(height, &job)?;
+        Ok(job.status)
+    }
+
+    /// Queues the job at DA height `height` again, resetting its attempts. Only the jobs which are
+    /// not complete can be requeued, e.g. the failed ones, or the running ones which are stuck.
+    pub fn requeue_proof_job(&self, height: u64) -> Result<StoredProofJob, anyhow::Error> {
+        let mut job = self.get_existing_proof_job(height)?;
+        anyhow::ensure!(
+            job.status != ProofJobStatus::Complete,
+            "The proof job of the slot at height {} is already complete",
+            height
+        );
+        job.status = ProofJobStatus::Pending;
+        job.attempts = 0;
+        self.put_proof_job(height, &job)?;
+        Ok(job)
+    }
+
+    fn get_existing_proof_job(&self, height: u64) -> Result<StoredProofJob, anyhow::Error> {
+        self.get_proof_job(height)?
+            .ok_or_else(|| anyhow::anyhow!("No proof job for the slot at height {}", height))
+    }
+
+    /// Records that the slot at DA height `height` was executed. Its proof may be generated later,
+    /// as execution runs ahead of proving.
+    pub fn set_last_executed_height(&self, height: u64) -> Result<(), anyhow::Error> {
+        self.db.put::<LastExecutedHeight>(&(), &height)
+    }
+
+    /// Get the DA height of the last slot executed by the runner, if any
+    pub fn get_last_executed_height(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.db.get::<LastExecutedHeight>(&())
+    }
+
+    /// Get the DA height of the last slot whose proof was generated and sent to the DA, if any
+    pub fn get_last_proven_height(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.db.get::<LastProvenHeight>(&())
+    }
+
+    fn put_proof_job(&self, height: u64, job: &StoredProofJob) -> Result<(), anyhow::Error> {
+        let schema_batch = Self::proof_job_batch(height, job)?;
+        self.db.write_schemas(schema_batch)
+    }
+
+    fn proof_job_batch(height: u64, job: &StoredProofJob) -> Result<SchemaBatch, anyhow::Error> {
+        let mut schema_batch = SchemaBatch::new();
+        schema_batch.put::<ProofJobs>(&height, job)?;
+        match job.status {
+            ProofJobStatus::Pending | ProofJobStatus::Running => {
+                schema_batch.put::<UnfinishedProofJobs>(&height, &())?
+            }
+            ProofJobStatus::Failed | ProofJobStatus::Complete => {
+                schema_batch.delete::<UnfinishedProofJobs>(&height)?
+            }
+        }
+        Ok(schema_batch)
+    }
+}
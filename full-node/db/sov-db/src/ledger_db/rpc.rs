@@ -1,8 +1,8 @@
 use serde::de::DeserializeOwned;
 use sov_rollup_interface::rpc::{
-    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
-    LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset,
-    TxIdentifier, TxResponse,
+    BatchIdAndOffset, BatchIdentifier, BatchResponse, CheckpointsResponse, EventIdentifier,
+    ItemOrHash, LedgerRpcProvider, ProofJobResponse, PrunedError, QueryMode, SlotIdAndOffset,
+    SlotIdentifier, SlotResponse, TxIdAndOffset, TxIdentifier, TxResponse, VerifiedProofResponse,
 };
 use sov_rollup_interface::stf::Event;
 use tokio::sync::broadcast::Receiver;
@@ -11,7 +11,7 @@ use crate::schema::tables::{
     BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, TxByHash, TxByNumber,
 };
 use crate::schema::types::{
-    BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
+    BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredProofJob, StoredSlot, TxNumber,
 };
 
 /// The maximum number of slots that can be requested in a single RPC range query
@@ -22,6 +22,10 @@ const MAX_BATCHES_PER_REQUEST: u64 = 20;
 const MAX_TRANSACTIONS_PER_REQUEST: u64 = 100;
 /// The maximum number of events that can be requested in a single RPC range query
 const MAX_EVENTS_PER_REQUEST: u64 = 500;
+/// The maximum number of proof jobs that can be requested in a single RPC query
+const MAX_PROOF_JOBS_PER_REQUEST: u64 = 100;
+/// The maximum number of verified proofs that can be requested in a single RPC query
+const MAX_VERIFIED_PROOFS_PER_REQUEST: u64 = 20;
 
 use super::LedgerDB;
 
@@ -39,11 +43,13 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let oldest_retained = self.get_oldest_retained_items()?;
         let mut out = Vec::with_capacity(slot_ids.len());
         for slot_id in slot_ids {
             let slot_num = self.resolve_slot_identifier(slot_id)?;
             out.push(match slot_num {
                 Some(num) => {
+                    ensure_retained("slot", num.0, oldest_retained.slot_number)?;
                     if let Some(stored_slot) = self.db.get::<SlotByNumber>(&num)? {
                         Some(self.populate_slot_response(num.into(), stored_slot, query_mode)?)
                     } else {
@@ -69,11 +75,13 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let oldest_retained = self.get_oldest_retained_items()?;
         let mut out = Vec::with_capacity(batch_ids.len());
         for batch_id in batch_ids {
             let batch_num = self.resolve_batch_identifier(batch_id)?;
             out.push(match batch_num {
                 Some(num) => {
+                    ensure_retained("batch", num.0, oldest_retained.batch_number)?;
                     if let Some(stored_batch) = self.db.get::<BatchByNumber>(&num)? {
                         Some(self.populate_batch_response(stored_batch, query_mode)?)
                     } else {
@@ -99,11 +107,13 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let oldest_retained = self.get_oldest_retained_items()?;
         let mut out: Vec<Option<TxResponse<T>>> = Vec::with_capacity(tx_ids.len());
         for id in tx_ids {
             let num = self.resolve_tx_identifier(id)?;
             out.push(match num {
                 Some(num) => {
+                    ensure_retained("transaction", num.0, oldest_retained.tx_number)?;
                     if let Some(tx) = self.db.get::<TxByNumber>(&num)? {
                         Some(tx.try_into()?)
                     } else {
@@ -128,11 +138,15 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: Sort the input and use an iterator instead of querying for each slot individually
         // https://github.com/Sovereign-Labs/sovereign-sdk/issues/191
+        let oldest_retained = self.get_oldest_retained_items()?;
         let mut out = Vec::with_capacity(event_ids.len());
         for id in event_ids {
             let num = self.resolve_event_identifier(id)?;
             out.push(match num {
-                Some(num) => self.db.get::<EventByNumber>(&num)?,
+                Some(num) => {
+                    ensure_retained("event", num.0, oldest_retained.event_number)?;
+                    self.db.get::<EventByNumber>(&num)?
+                }
                 None => None,
             })
         }
@@ -270,11 +284,89 @@ impl LedgerRpcProvider for LedgerDB {
         self.get_transactions(&ids, query_mode)
     }
 
+    fn get_proof_jobs(
+        &self,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<ProofJobResponse>, anyhow::Error> {
+        anyhow::ensure!(
+            limit <= MAX_PROOF_JOBS_PER_REQUEST,
+            "requested too many proof jobs. Requested: {}. Max: {}",
+            limit,
+            MAX_PROOF_JOBS_PER_REQUEST
+        );
+        Ok(self
+            .get_proof_jobs(start, limit as usize)?
+            .into_iter()
+            .map(|(height, job)| proof_job_response(height, job))
+            .collect())
+    }
+
+    fn requeue_proof_job(&self, height: u64) -> Result<ProofJobResponse, anyhow::Error> {
+        let job = LedgerDB::requeue_proof_job(self, height)?;
+        Ok(proof_job_response(height, job))
+    }
+
+    fn get_checkpoints(&self) -> Result<CheckpointsResponse, anyhow::Error> {
+        Ok(CheckpointsResponse {
+            last_executed_height: self.get_last_executed_height()?,
+            last_proven_height: self.get_last_proven_height()?,
+        })
+    }
+
+    fn get_verified_proofs(
+        &self,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<VerifiedProofResponse>, anyhow::Error> {
+        anyhow::ensure!(
+            limit <= MAX_VERIFIED_PROOFS_PER_REQUEST,
+            "requested too many verified proofs. Requested: {}. Max: {}",
+            limit,
+            MAX_VERIFIED_PROOFS_PER_REQUEST
+        );
+        Ok(self
+            .get_verified_proofs(start, limit as usize)?
+            .into_iter()
+            .map(|(height, proof)| VerifiedProofResponse {
+                height,
+                proof: proof.proof,
+                public_output: proof.public_output,
+            })
+            .collect())
+    }
+
     fn subscribe_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
         Ok(self.slot_subscriptions.subscribe())
     }
 }
 
+fn proof_job_response(height: u64, job: StoredProofJob) -> ProofJobResponse {
+    ProofJobResponse {
+        height,
+        status: job.status,
+        attempts: job.attempts,
+        last_error: job.last_error,
+    }
+}
+
+/// Fails with a [`PrunedError`] if the item `number` is older than the oldest retained one.
+fn ensure_retained(
+    kind: &'static str,
+    number: u64,
+    oldest_retained: u64,
+) -> Result<(), anyhow::Error> {
+    if number < oldest_retained {
+        return Err(PrunedError {
+            kind,
+            number,
+            oldest_retained,
+        }
+        .into());
+    }
+    Ok(())
+}
+
 impl LedgerDB {
     fn resolve_slot_identifier(
         &self,
@@ -428,10 +520,132 @@ impl LedgerDB {
 
 #[cfg(test)]
 mod tests {
-    use sov_mock_da::{MockBlob, MockBlock};
-    use sov_rollup_interface::rpc::LedgerRpcProvider;
+    use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader};
+    use sov_rollup_interface::rpc::{LedgerRpcProvider, ProofJobStatus, PrunedError, QueryMode};
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+
+    use crate::ledger_db::{ItemNumbers, LedgerDB, SlotCommit};
+    use crate::schema::types::StoredVerifiedProof;
+
+    fn commit_slot_with_one_tx(db: &LedgerDB, height: u64) {
+        let mut slot = SlotCommit::new(MockBlock {
+            header: MockBlockHeader::from_height(height),
+            ..Default::default()
+        });
+        slot.add_batch(BatchReceipt {
+            batch_hash: [height as u8; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [height as u8; 32],
+                body_to_save: None,
+                events: vec![Event::new("height", &height.to_string())],
+                receipt: 0u32,
+                gas_used: vec![],
+            }],
+            inner: 0u32,
+        });
+        db.commit_slot(slot).unwrap();
+    }
+
+    fn assert_pruned<T: std::fmt::Debug>(result: anyhow::Result<T>, kind: &str) {
+        let err = result.unwrap_err();
+        assert_eq!(kind, err.downcast_ref::<PrunedError>().unwrap().kind);
+    }
+
+    #[test]
+    fn test_prune() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for height in 1..=4 {
+            commit_slot_with_one_tx(&db, height);
+        }
+        // An empty slot
+        db.commit_slot(SlotCommit::<_, u32, u32>::new(MockBlock {
+            header: MockBlockHeader::from_height(5),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        assert!(db.prune(3).unwrap());
+        assert!(!db.prune(3).unwrap());
+        assert_eq!(
+            ItemNumbers {
+                slot_number: 3,
+                batch_number: 3,
+                tx_number: 3,
+                event_number: 3,
+            },
+            db.get_oldest_retained_items().unwrap()
+        );
+
+        let mode = QueryMode::Compact;
+        assert_pruned(db.get_slot_by_number::<u32, u32>(2, mode), "slot");
+        assert_pruned(db.get_batch_by_number::<u32, u32>(2, mode), "batch");
+        assert_pruned(db.get_tx_by_number::<u32>(2, mode), "transaction");
+        assert_pruned(db.get_event_by_number(2), "event");
+        assert!(db
+            .get_slot_by_hash::<u32, u32>(&MockBlockHeader::from_height(2).hash.0, mode)
+            .unwrap()
+            .is_none());
+
+        assert!(db
+            .get_slot_by_number::<u32, u32>(3, mode)
+            .unwrap()
+            .is_some());
+        assert!(db.get_tx_by_hash::<u32>(&[3; 32], mode).unwrap().is_some());
+        assert_eq!(
+            Some(Event::new("height", "3")),
+            db.get_event_by_number(3).unwrap()
+        );
+
+        // Only the empty head slot is retained, numbering resumes after the pruned items on restart
+        assert!(db.prune(1).unwrap());
+        drop(db);
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(
+            ItemNumbers {
+                slot_number: 6,
+                batch_number: 5,
+                tx_number: 5,
+                event_number: 5,
+            },
+            db.get_next_items_numbers()
+        );
+    }
+    #[test]
+    fn test_size_on_disk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(0, db.size_on_disk().unwrap());
+
+        for height in 1..=4 {
+            commit_slot_with_one_tx(&db, height);
+        }
+        // Compacting flushes the slots to the files of the tables
+        db.compact().unwrap();
+        assert!(db.size_on_disk().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_pending_slot_commit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(None, db.get_pending_slot_commit().unwrap());
+
+        db.put_pending_slot_commit(7).unwrap();
+        // The pending commit survives a restart
+        drop(db);
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(Some(7), db.get_pending_slot_commit().unwrap());
+
+        // and is cleared by the commit of the slot
+        commit_slot_with_one_tx(&db, 1);
+        assert_eq!(None, db.get_pending_slot_commit().unwrap());
+
+        db.put_pending_slot_commit(8).unwrap();
+        db.clear_pending_slot_commit().unwrap();
+        assert_eq!(None, db.get_pending_slot_commit().unwrap());
+    }
 
-    use crate::ledger_db::{LedgerDB, SlotCommit};
     #[test]
     fn test_slot_subscription() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -444,4 +658,101 @@ mod tests {
 
         assert_eq!(rx.blocking_recv().unwrap(), 1);
     }
+
+    #[test]
+    fn test_proof_jobs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        db.enqueue_proof_job(1, vec![1]).unwrap();
+        db.enqueue_proof_job(2, vec![2]).unwrap();
+
+        // The first attempt fails and is retried, the second one fails the job
+        db.mark_proof_job_running(1).unwrap();
+        let status = db.mark_proof_job_failed(1, "error".to_string(), 2).unwrap();
+        assert_eq!(ProofJobStatus::Pending, status);
+        db.mark_proof_job_running(1).unwrap();
+        let status = db.mark_proof_job_failed(1, "error".to_string(), 2).unwrap();
+        assert_eq!(ProofJobStatus::Failed, status);
+
+        db.mark_proof_job_running(2).unwrap();
+        db.mark_proof_job_complete(2).unwrap();
+        assert_eq!(
+            None,
+            db.get_proof_job(2).unwrap().unwrap().state_transition_data
+        );
+        assert!(db.get_unfinished_proof_jobs().unwrap().is_empty());
+
+        let jobs = LedgerRpcProvider::get_proof_jobs(&db, 0, 10).unwrap();
+        assert_eq!(2, jobs.len());
+        assert_eq!(ProofJobStatus::Failed, jobs[0].status);
+        assert_eq!(2, jobs[0].attempts);
+        assert_eq!(Some("error".to_string()), jobs[0].last_error);
+        assert_eq!(ProofJobStatus::Complete, jobs[1].status);
+
+        // The failed job can be requeued with its witness, not the complete one
+        let job = LedgerRpcProvider::requeue_proof_job(&db, 1).unwrap();
+        assert_eq!(ProofJobStatus::Pending, job.status);
+        assert_eq!(0, job.attempts);
+        let unfinished = db.get_unfinished_proof_jobs().unwrap();
+        assert_eq!(1, unfinished.len());
+        assert_eq!(Some(vec![1]), unfinished[0].1.state_transition_data);
+        assert!(LedgerRpcProvider::requeue_proof_job(&db, 2).is_err());
+        assert!(LedgerRpcProvider::requeue_proof_job(&db, 3).is_err());
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let checkpoints = LedgerRpcProvider::get_checkpoints(&db).unwrap();
+        assert_eq!(None, checkpoints.last_executed_height);
+        assert_eq!(None, checkpoints.last_proven_height);
+
+        // Execution runs ahead of proving
+        for height in 1..=3 {
+            db.enqueue_proof_job(height, vec![height as u8]).unwrap();
+            db.set_last_executed_height(height).unwrap();
+        }
+        db.mark_proof_job_complete(2).unwrap();
+        let checkpoints = LedgerRpcProvider::get_checkpoints(&db).unwrap();
+        assert_eq!(Some(3), checkpoints.last_executed_height);
+        assert_eq!(Some(2), checkpoints.last_proven_height);
+
+        // An older slot proven later doesn't move the checkpoint back
+        db.mark_proof_job_complete(1).unwrap();
+        assert_eq!(Some(2), db.get_last_proven_height().unwrap());
+    }
+
+    #[test]
+    fn test_verified_proofs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(None, db.get_last_proof_checked_height().unwrap());
+
+        let proof = |byte: u8| StoredVerifiedProof {
+            proof: vec![byte],
+            public_output: vec![byte, byte],
+        };
+        db.put_verified_proofs(1, vec![proof(1), proof(2)]).unwrap();
+        db.put_verified_proofs(2, vec![]).unwrap();
+        db.put_verified_proofs(3, vec![proof(3)]).unwrap();
+        assert_eq!(Some(3), db.get_last_proof_checked_height().unwrap());
+
+        let proofs = LedgerRpcProvider::get_verified_proofs(&db, 0, 10).unwrap();
+        let heights: Vec<u64> = proofs.iter().map(|proof| proof.height).collect();
+        assert_eq!(vec![1, 1, 3], heights);
+        assert_eq!(vec![3], proofs[2].proof);
+        assert_eq!(vec![3, 3], proofs[2].public_output);
+
+        // The limit counts proofs, not heights
+        let proofs = LedgerRpcProvider::get_verified_proofs(&db, 1, 1).unwrap();
+        assert_eq!(1, proofs.len());
+        assert_eq!(vec![1], proofs[0].proof);
+
+        let proofs = LedgerRpcProvider::get_verified_proofs(&db, 2, 10).unwrap();
+        assert_eq!(1, proofs.len());
+        assert_eq!(3, proofs[0].height);
+
+        assert!(LedgerRpcProvider::get_verified_proofs(&db, 0, 21).is_err());
+    }
 }
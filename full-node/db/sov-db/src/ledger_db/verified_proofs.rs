@@ -0,0 +1,48 @@
+use sov_schema_db::SchemaBatch;
+
+use super::LedgerDB;
+use crate::schema::tables::{LastProofCheckedHeight, VerifiedProofs};
+use crate::schema::types::StoredVerifiedProof;
+
+impl LedgerDB {
+    /// Records the proofs posted at DA height `height` which were verified by the node,
+    /// and that the proofs posted at this height were checked.
+    pub fn put_verified_proofs(
+        &self,
+        height: u64,
+        proofs: Vec<StoredVerifiedProof>,
+    ) -> Result<(), anyhow::Error> {
+        let mut schema_batch = SchemaBatch::new();
+        if !proofs.is_empty() {
+            schema_batch.put::<VerifiedProofs>(&height, &proofs)?;
+        }
+        schema_batch.put::<LastProofCheckedHeight>(&(), &height)?;
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Get the verified proofs from DA height `start`, by increasing height, up to `limit` proofs
+    pub fn get_verified_proofs(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, StoredVerifiedProof)>, anyhow::Error> {
+        let mut iter = self.db.iter::<VerifiedProofs>()?;
+        iter.seek(&start)?;
+
+        let mut proofs = Vec::new();
+        for item in iter {
+            if proofs.len() >= limit {
+                break;
+            }
+            let (height, proofs_at_height) = item?.into_tuple();
+            proofs.extend(proofs_at_height.into_iter().map(|proof| (height, proof)));
+        }
+        proofs.truncate(limit);
+        Ok(proofs)
+    }
+
+    /// Get the last DA height whose posted proofs were checked by the node, if any
+    pub fn get_last_proof_checked_height(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.db.get::<LastProofCheckedHeight>(&())
+    }
+}
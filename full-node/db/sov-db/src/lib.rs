@@ -11,6 +11,8 @@
 /// This wrapper implements helper traits for writing blocks to the ledger, and for
 /// serving historical data via RPC
 pub mod ledger_db;
+/// Implements the background task pruning the historical data of the databases.
+pub mod pruning;
 /// Implements helpers for configuring RocksDB.
 pub mod rocks_db_config;
 /// Defines the tables used by the Sovereign SDK.
@@ -42,6 +42,28 @@ impl<Q> NativeDB<Q> {
         )
     }
 
+    /// Deletes from `db` the values written at `from_version` or later.
+    /// Returns false if there was nothing to delete.
+    ///
+    /// This repairs a commit interrupted by a crash, so it is called on the underlying
+    /// [`sov_schema_db::DB`], before any snapshot is created.
+    pub fn truncate(db: &sov_schema_db::DB, from_version: Version) -> anyhow::Result<bool> {
+        let mut batch = SchemaBatch::default();
+        let mut truncated = false;
+        // The values are keyed by key first, so all of them are scanned
+        let mut values = db.iter::<ModuleAccessoryState>()?;
+        values.seek_to_first();
+        for value in values {
+            let ((key, version), _) = value?.into_tuple();
+            if version >= from_version {
+                batch.delete::<ModuleAccessoryState>(&(key, version))?;
+                truncated = true;
+            }
+        }
+        db.write_schemas(batch)?;
+        Ok(truncated)
+    }
+
     /// Convert it to [`ReadOnlyDbSnapshot`] which cannot be edited anymore
     pub fn freeze(self) -> anyhow::Result<ReadOnlyDbSnapshot> {
         let inner = Arc::into_inner(self.db).ok_or(anyhow::anyhow!(
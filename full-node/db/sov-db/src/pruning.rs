@@ -0,0 +1,54 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// The default time between two runs of a [`PruningTask`].
+pub const DEFAULT_PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A background thread periodically deleting the historical data which is no longer retained,
+/// and compacting the database to reclaim its space. The thread stops when the task is dropped.
+#[derive(Debug)]
+pub struct PruningTask {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PruningTask {
+    /// Spawns a thread named `name`, calling `prune` every `interval`.
+    /// Failures are logged, and `prune` is called again at the next interval.
+    pub fn spawn(
+        name: &str,
+        interval: Duration,
+        mut prune: impl FnMut() -> anyhow::Result<()> + Send + 'static,
+    ) -> anyhow::Result<Self> {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let task_name = name.to_string();
+        let thread = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || loop {
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Err(e) = prune() {
+                            tracing::error!("Pruning task {} failed: {:?}", task_name, e);
+                        }
+                    }
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })?;
+
+        Ok(Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for PruningTask {
+    fn drop(&mut self) {
+        // Dropping the sender wakes the thread up
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
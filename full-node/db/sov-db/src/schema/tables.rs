@@ -17,10 +17,31 @@
 //! - `(EventKey, TxNumber) -> EventNumber`
 //! - `EventNumber -> (EventKey, EventValue)`
 //!
+//! Ledger Pruning Tables:
+//! - `() -> ItemNumbers`
+//!
+//! Proof Job Tables:
+//! - `DaHeight -> StoredProofJob`
+//! - `DaHeight -> ()`
+//!
+//! Slot Commit Tables:
+//! - `() -> Version`
+//!
+//! Checkpoint Tables:
+//! - `() -> DaHeight`
+//! - `() -> DaHeight`
+//!
+//! Verified Proof Tables:
+//! - `DaHeight -> Vec<StoredVerifiedProof>`
+//! - `() -> DaHeight`
+//!
 //! JMT Tables:
 //! - `KeyHash -> Key`
 //! - `(Key, Version) -> JmtValue`
 //! - `NodeKey -> Node`
+//! - `(Version, NodeKey) -> ()`
+//! - `(Version, Key, Version) -> ()`
+//! - `() -> Version`
 //!
 //! Module Accessory State Table:
 //! - `(ModuleAddress, Key) -> Value`
@@ -35,8 +56,10 @@ use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
     AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, EventNumber, JmtValue, SlotNumber,
-    StateKey, StoredBatch, StoredSlot, StoredTransaction, TxNumber,
+    StateKey, StoredBatch, StoredProofJob, StoredSlot, StoredTransaction, StoredVerifiedProof,
+    TxNumber,
 };
+use crate::ledger_db::ItemNumbers;
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
 /// account balances, nonces, etc.
@@ -44,6 +67,9 @@ pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
     JmtNodes::table_name(),
+    StaleJmtNodes::table_name(),
+    StaleJmtValues::table_name(),
+    OldestRetainedVersion::table_name(),
 ];
 
 /// A list of all tables used by the LedgerDB. These tables store rollup "history" - meaning
@@ -57,6 +83,14 @@ pub const LEDGER_TABLES: &[&str] = &[
     TxByNumber::table_name(),
     EventByKey::table_name(),
     EventByNumber::table_name(),
+    OldestRetainedItems::table_name(),
+    ProofJobs::table_name(),
+    UnfinishedProofJobs::table_name(),
+    PendingSlotCommit::table_name(),
+    LastExecutedHeight::table_name(),
+    LastProvenHeight::table_name(),
+    VerifiedProofs::table_name(),
+    LastProofCheckedHeight::table_name(),
 ];
 
 /// A list of all tables used by the NativeDB. These tables store
@@ -248,6 +282,48 @@ define_table_with_default_codec!(
     (EventByKey) (EventKey, TxNumber, EventNumber) => ()
 );
 
+define_table_with_default_codec!(
+    /// The numbers of the oldest slot, batch, transaction and event which can be read. Older ones were pruned.
+    (OldestRetainedItems) () => ItemNumbers
+);
+
+define_table_with_seek_key_codec!(
+    /// The jobs proving the state transitions of the slots, by DA height
+    (ProofJobs) u64 => StoredProofJob
+);
+
+define_table_with_seek_key_codec!(
+    /// An index of the DA heights of the pending and running proof jobs
+    (UnfinishedProofJobs) u64 => ()
+);
+
+define_table_with_default_codec!(
+    /// The version of the finalized storage before the storage of the slot being committed was finalized.
+    /// It is written before the finalization and deleted along with the commit of the slot, so finding it
+    /// at startup means that the node was killed in between.
+    (PendingSlotCommit) () => u64
+);
+
+define_table_with_default_codec!(
+    /// The DA height of the last slot executed by the runner, which may not be finalized yet
+    (LastExecutedHeight) () => u64
+);
+
+define_table_with_default_codec!(
+    /// The DA height of the last slot whose proof was generated and sent to the DA
+    (LastProvenHeight) () => u64
+);
+
+define_table_with_seek_key_codec!(
+    /// The proofs posted to the DA by other parties which were verified by the node, by DA height
+    (VerifiedProofs) u64 => Vec<StoredVerifiedProof>
+);
+
+define_table_with_default_codec!(
+    /// The last DA height whose posted proofs were checked by the node
+    (LastProofCheckedHeight) () => u64
+);
+
 define_table_without_codec!(
     /// The source of truth for JMT nodes
     (JmtNodes) NodeKey => Node
@@ -316,6 +392,17 @@ impl<T: AsRef<[u8]> + PartialEq + core::fmt::Debug> SeekKeyEncoder<JmtValues> fo
     }
 }
 
+/// A raw prefix of the encoded keys of [`JmtValues`], which sorts before every entry it prefixes.
+/// Used to seek to the entries below all the versions of a key, or below all the keys of a given length.
+#[derive(Debug, PartialEq)]
+pub(crate) struct JmtValuesSeekPrefix(pub(crate) Vec<u8>);
+
+impl SeekKeyEncoder<JmtValues> for JmtValuesSeekPrefix {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        Ok(self.0.clone())
+    }
+}
+
 impl KeyDecoder<JmtValues> for (StateKey, Version) {
     fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
         let mut cursor = maybestd::io::Cursor::new(data);
@@ -335,6 +422,48 @@ impl ValueCodec<JmtValues> for JmtValue {
     }
 }
 
+define_table_without_codec!(
+    /// An index of the JMT nodes replaced at a version, keyed by that version first,
+    /// so that the nodes which are not part of any retained version can be pruned.
+    (StaleJmtNodes) (Version, NodeKey) => ()
+);
+
+impl KeyEncoder<StaleJmtNodes> for (Version, NodeKey) {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        let mut output = self.0.to_be_bytes().to_vec();
+        output.extend(<NodeKey as KeyEncoder<JmtNodes>>::encode_key(&self.1)?);
+        Ok(output)
+    }
+}
+
+impl KeyDecoder<StaleJmtNodes> for (Version, NodeKey) {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        if data.len() < 8 {
+            return Err(CodecError::InvalidKeyLength {
+                expected: 16,
+                got: data.len(),
+            });
+        }
+        let mut version = [0u8; 8];
+        version.copy_from_slice(&data[..8]);
+        let node_key = <NodeKey as KeyDecoder<JmtNodes>>::decode_key(&data[8..])?;
+        Ok((u64::from_be_bytes(version), node_key))
+    }
+}
+
+impl_borsh_value_codec!(StaleJmtNodes, ());
+
+define_table_with_seek_key_codec!(
+    /// An index of the versions of the JMT values replaced at a version, keyed by that version first:
+    /// `(replaced at, key, version of the replaced value)`.
+    (StaleJmtValues) (Version, StateKey, Version) => ()
+);
+
+define_table_with_default_codec!(
+    /// The oldest version of the state which can be read. Older versions were pruned.
+    (OldestRetainedVersion) () => Version
+);
+
 define_table_with_default_codec!(
     /// A mapping from key-hashes to their preimages and latest version. Since we store raw
     /// key-value pairs instead of keyHash->value pairs,
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::rpc::{BatchResponse, TxIdentifier, TxResponse};
+use sov_rollup_interface::rpc::{BatchResponse, ProofJobStatus, TxIdentifier, TxResponse};
 use sov_rollup_interface::stf::{Event, EventKey, TransactionReceipt};
 
 /// A cheaply cloneable bytes abstraction for use within the trust boundary of the node
@@ -67,6 +67,28 @@ pub struct StoredSlot {
     pub batches: std::ops::Range<BatchNumber>,
 }
 
+/// The on-disk format of the job proving the state transition of a slot.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredProofJob {
+    /// The status of the job.
+    pub status: ProofJobStatus,
+    /// The number of times the proof generation was started since the job was last queued.
+    pub attempts: u32,
+    /// The error of the last failed attempt, if any.
+    pub last_error: Option<String>,
+    /// The serialized `StateTransitionData` of the slot, which is dropped once the proof is complete.
+    pub state_transition_data: Option<Vec<u8>>,
+}
+
+/// The on-disk format of a proof posted to the DA by another party, which was verified by the node.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredVerifiedProof {
+    /// The serialized proof, as verified by the zkVM.
+    pub proof: Vec<u8>,
+    /// The public output of the proof.
+    pub public_output: Vec<u8>,
+}
+
 /// The on-disk format for a batch. Stores the hash and identifies the range of transactions
 /// included in the batch.
 #[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
@@ -1,13 +1,16 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use jmt::storage::{HasPreimage, TreeReader, TreeWriter};
+use jmt::storage::{HasPreimage, StaleNodeIndex, TreeReader, TreeWriter};
 use jmt::{KeyHash, Version};
 use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
+use crate::schema::tables::{
+    JmtNodes, JmtValues, JmtValuesSeekPrefix, KeyHashToKey, OldestRetainedVersion, StaleJmtNodes,
+    StaleJmtValues, STATE_TABLES,
+};
 use crate::schema::types::StateKey;
 
 /// A typed wrapper around the db for storing rollup state. Internally,
@@ -50,6 +53,140 @@ impl<Q> StateDB<Q> {
         )
     }
 
+    /// Deletes from `db` the JMT nodes and values which are not needed to read any of the latest
+    /// `retained_versions` versions of the state, and records the oldest version which can still be read.
+    /// Returns false if there was nothing to prune.
+    ///
+    /// Only finalized data is pruned, so this is called on the underlying [`sov_schema_db::DB`]
+    /// rather than on a snapshot.
+    pub fn prune(db: &sov_schema_db::DB, retained_versions: u64) -> anyhow::Result<bool> {
+        let mut nodes = db.iter::<JmtNodes>()?;
+        nodes.seek_to_last();
+        let Some(latest_node) = nodes.next().transpose()? else {
+            return Ok(false);
+        };
+        let latest_version = latest_node.into_tuple().0.version();
+        let oldest_retained_version = (latest_version + 1).saturating_sub(retained_versions.max(1));
+        if oldest_retained_version <= db.get::<OldestRetainedVersion>(&())?.unwrap_or_default() {
+            return Ok(false);
+        }
+
+        let mut batch = SchemaBatch::new();
+        let mut stale_nodes = db.iter::<StaleJmtNodes>()?;
+        stale_nodes.seek_to_first();
+        for stale_node in stale_nodes {
+            let (stale_node, ()) = stale_node?.into_tuple();
+            if stale_node.0 > oldest_retained_version {
+                break;
+            }
+            batch.delete::<JmtNodes>(&stale_node.1)?;
+            batch.delete::<StaleJmtNodes>(&stale_node)?;
+        }
+
+        let mut stale_values = db.iter::<StaleJmtValues>()?;
+        stale_values.seek_to_first();
+        for stale_value in stale_values {
+            let ((stale_since, key, version), ()) = stale_value?.into_tuple();
+            if stale_since > oldest_retained_version {
+                break;
+            }
+            batch.delete::<StaleJmtValues>(&(stale_since, key.clone(), version))?;
+            batch.delete::<JmtValues>(&(key, version))?;
+        }
+
+        batch.put::<OldestRetainedVersion>(&(), &oldest_retained_version)?;
+        db.write_schemas(batch)?;
+        Ok(true)
+    }
+
+    /// Returns the latest version of the state written to `db`, or 0 if the state is empty.
+    pub fn latest_version(db: &sov_schema_db::DB) -> anyhow::Result<Version> {
+        let mut nodes = db.iter::<JmtNodes>()?;
+        nodes.seek_to_last();
+        Ok(nodes
+            .next()
+            .transpose()?
+            .map(|node| node.into_tuple().0.version())
+            .unwrap_or_default())
+    }
+
+    /// Deletes from `db` the versions of the state starting at `from_version`, so that the next write is
+    /// at `from_version`. Returns false if there was nothing to delete.
+    ///
+    /// This repairs a commit interrupted by a crash, so like [`StateDB::prune`] it is called on
+    /// the underlying [`sov_schema_db::DB`], before any snapshot is created.
+    pub fn truncate(db: &sov_schema_db::DB, from_version: Version) -> anyhow::Result<bool> {
+        if Self::latest_version(db)? < from_version {
+            return Ok(false);
+        }
+        let oldest_retained_version = db.get::<OldestRetainedVersion>(&())?.unwrap_or_default();
+        anyhow::ensure!(
+            from_version > oldest_retained_version,
+            "Cannot truncate the state from version {}, the oldest retained version is {}",
+            from_version,
+            oldest_retained_version
+        );
+
+        let mut batch = SchemaBatch::new();
+        // The tables keyed by version first are iterated from the latest version
+        let mut nodes = db.iter::<JmtNodes>()?;
+        nodes.seek_to_last();
+        for node in nodes.rev() {
+            let (node_key, _) = node?.into_tuple();
+            if node_key.version() < from_version {
+                break;
+            }
+            batch.delete::<JmtNodes>(&node_key)?;
+        }
+
+        // The nodes and values replaced by the deleted versions are part of the latest version again
+        let mut stale_nodes = db.iter::<StaleJmtNodes>()?;
+        stale_nodes.seek_to_last();
+        for stale_node in stale_nodes.rev() {
+            let (stale_node, ()) = stale_node?.into_tuple();
+            if stale_node.0 < from_version {
+                break;
+            }
+            batch.delete::<StaleJmtNodes>(&stale_node)?;
+        }
+
+        let mut stale_values = db.iter::<StaleJmtValues>()?;
+        stale_values.seek_to_last();
+        for stale_value in stale_values.rev() {
+            let (stale_value, ()) = stale_value?.into_tuple();
+            if stale_value.0 < from_version {
+                break;
+            }
+            batch.delete::<StaleJmtValues>(&stale_value)?;
+        }
+
+        // The values are keyed by key first, so all of them are scanned
+        let mut values = db.iter::<JmtValues>()?;
+        values.seek_to_first();
+        for value in values {
+            let ((key, version), _) = value?.into_tuple();
+            if version >= from_version {
+                batch.delete::<JmtValues>(&(key, version))?;
+            }
+        }
+
+        db.write_schemas(batch)?;
+        Ok(true)
+    }
+
+    /// Compacts the tables of `db` from which [`StateDB::prune`] deletes entries.
+    pub fn compact(db: &sov_schema_db::DB) -> anyhow::Result<()> {
+        for table in [
+            JmtNodes::table_name(),
+            JmtValues::table_name(),
+            StaleJmtNodes::table_name(),
+            StaleJmtValues::table_name(),
+        ] {
+            db.compact_cf(table)?;
+        }
+        Ok(())
+    }
+
     /// Convert it to [`ReadOnlyDbSnapshot`] which cannot be edited anymore
     pub fn freeze(self) -> anyhow::Result<ReadOnlyDbSnapshot> {
         let inner = Arc::into_inner(self.db).ok_or(anyhow::anyhow!(
@@ -103,6 +240,80 @@ impl<Q: QueryManager> StateDB<Q> {
         }
     }
 
+    /// Get the values of the keys in the range `[start, end)` at `version`, sorted by key.
+    /// Keys are compared as byte strings, and the range is unbounded above if `end` is `None`.
+    /// Deleted keys are skipped.
+    pub fn get_values_in_range(
+        &self,
+        version: Version,
+        start: &[u8],
+        end: Option<&[u8]>,
+    ) -> anyhow::Result<Vec<(StateKey, jmt::OwnedValue)>> {
+        // Entries are sorted by the length prefix of their key first, so the scan walks down every
+        // group of keys of the same length, jumping over the keys outside of the range.
+        let mut values = Vec::new();
+        let mut found = self.db.get_largest::<JmtValues>()?;
+        while let Some(((key, _), _)) = found {
+            let mut seek_prefix = (key.len() as u32).to_le_bytes().to_vec();
+            match end {
+                Some(end) if key.as_slice() >= end => {
+                    // Seek to the largest key of the same length below `end`
+                    if end.len() > key.len() {
+                        seek_prefix.extend_from_slice(&end[..key.len()]);
+                        seek_prefix.extend_from_slice(&Version::MAX.to_be_bytes());
+                    } else {
+                        seek_prefix.extend_from_slice(end);
+                        seek_prefix.resize(seek_prefix.len() + key.len() - end.len(), 0);
+                    }
+                }
+                // The remaining keys of the same length are all below `start`
+                _ if key.as_slice() < start => {}
+                _ => {
+                    if let Some(value) = self.get_value_option_by_key(version, &key)? {
+                        values.push((key.clone(), value));
+                    }
+                    seek_prefix.extend_from_slice(&key);
+                }
+            }
+            found = self
+                .db
+                .get_prev::<JmtValues>(&JmtValuesSeekPrefix(seek_prefix))?;
+        }
+        values.sort_by(|(left, _), (right, _)| left.cmp(right));
+        Ok(values)
+    }
+
+    /// Get the oldest version of the state which can be read. Older versions were pruned.
+    pub fn get_oldest_retained_version(&self) -> anyhow::Result<Version> {
+        Ok(self
+            .db
+            .read::<OldestRetainedVersion>(&())?
+            .unwrap_or_default())
+    }
+
+    /// Record the oldest version of the state which can be read, when the history before it was never written,
+    /// like in a state imported from a snapshot.
+    pub fn put_oldest_retained_version(&self, version: Version) -> anyhow::Result<()> {
+        let mut batch = SchemaBatch::new();
+        batch.put::<OldestRetainedVersion>(&(), &version)?;
+        self.db.write_many(batch)
+    }
+
+    /// Record the JMT nodes replaced by a batch of writes, so that they can be pruned
+    /// once none of the retained versions of the state needs them.
+    pub fn put_stale_node_indices<'a>(
+        &self,
+        stale_node_indices: impl IntoIterator<Item = &'a StaleNodeIndex>,
+    ) -> anyhow::Result<()> {
+        let mut batch = SchemaBatch::new();
+        for index in stale_node_indices {
+            batch
+                .put::<StaleJmtNodes>(&(index.stale_since_version, index.node_key.clone()), &())?;
+        }
+        self.db.write_many(batch)?;
+        Ok(())
+    }
+
     /// Increment the `next_version` counter by 1.
     pub fn inc_next_version(&self) {
         let mut version = self.next_version.lock().unwrap();
@@ -173,6 +384,18 @@ impl<Q: QueryManager> TreeWriter for StateDB<Q> {
                     .ok_or(anyhow::format_err!(
                         "Could not find preimage for key hash {key_hash:?}. Has `StateDB::put_preimage` been called for this key?"
                     ))?;
+            // The previous value of the key is only needed to read older versions from now on
+            if let Some(((previous_key, previous_version), _)) = self
+                .db
+                .get_prev::<JmtValues>(&(&key_preimage, version.saturating_sub(1)))?
+            {
+                if previous_key == key_preimage && previous_version < *version {
+                    batch.put::<StaleJmtValues>(
+                        &(*version, key_preimage.clone(), previous_version),
+                        &(),
+                    )?;
+                }
+            }
             batch.put::<JmtValues>(&(key_preimage, *version), value)?;
         }
         self.db.write_many(batch)?;
@@ -216,4 +439,56 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+    #[test]
+    fn test_get_values_in_range() {
+        let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let db_snapshot = DbSnapshot::<NoopQueryManager>::new(0, manager);
+        let db = StateDB::with_db_snapshot(db_snapshot).unwrap();
+        let keys: Vec<Vec<u8>> = [&b"a"[..], b"ab", b"b", b"ba", b"c"]
+            .iter()
+            .map(|key| key.to_vec())
+            .collect();
+        let key_hashes: Vec<KeyHash> = (0..keys.len() as u8).map(|i| KeyHash([i; 32])).collect();
+        db.put_preimages(key_hashes.iter().copied().zip(keys.iter()))
+            .unwrap();
+
+        let mut batch = NodeBatch::default();
+        batch.extend(
+            vec![],
+            key_hashes
+                .iter()
+                .zip(keys.iter())
+                .map(|(key_hash, key)| ((0, *key_hash), Some(key.clone())))
+                .collect::<Vec<_>>(),
+        );
+        db.write_node_batch(&batch).unwrap();
+        // Delete "b" at version 1
+        let mut batch = NodeBatch::default();
+        batch.extend(vec![], vec![((1, key_hashes[2]), None)]);
+        db.write_node_batch(&batch).unwrap();
+
+        let keys_in_range = |version, start: &[u8], end: Option<&[u8]>| -> Vec<Vec<u8>> {
+            db.get_values_in_range(version, start, end)
+                .unwrap()
+                .into_iter()
+                .map(|(key, value)| {
+                    assert_eq!(key, value);
+                    key
+                })
+                .collect()
+        };
+        assert_eq!(
+            keys_in_range(0, b"a", Some(b"c")),
+            vec![b"a".to_vec(), b"ab".to_vec(), b"b".to_vec(), b"ba".to_vec()]
+        );
+        assert_eq!(
+            keys_in_range(1, b"a", Some(b"c")),
+            vec![b"a".to_vec(), b"ab".to_vec(), b"ba".to_vec()]
+        );
+        assert_eq!(
+            keys_in_range(1, b"ab", None),
+            vec![b"ab".to_vec(), b"ba".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(keys_in_range(1, b"b", Some(b"ba")), Vec::<Vec<u8>>::new());
+    }
 }
@@ -268,6 +268,13 @@ impl DB {
         Ok(self.inner.flush_cf(self.get_cf_handle(cf_name)?)?)
     }
 
+    /// Compacts the whole column family `cf_name`, reclaiming the space of deleted entries.
+    pub fn compact_cf(&self, cf_name: &str) -> anyhow::Result<()> {
+        self.inner
+            .compact_range_cf(self.get_cf_handle(cf_name)?, None::<&[u8]>, None::<&[u8]>);
+        Ok(())
+    }
+
     /// Returns the current RocksDB property value for the provided column family name
     /// and property name.
     pub fn get_property(&self, cf_name: &str, property_name: &str) -> anyhow::Result<u64> {
@@ -5,7 +5,8 @@
 
 use jsonrpsee::proc_macros::rpc;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, CheckpointsResponse, EventIdentifier, ProofJobResponse, QueryMode,
+    SlotIdentifier, TxIdentifier, VerifiedProofResponse,
 };
 use sov_rollup_interface::stf::Event;
 
@@ -138,6 +139,31 @@ where
         query_mode: QueryMode,
     ) -> RpcResult<Vec<Option<Tx>>>;
 
+    /// Gets the proof jobs of the slots from DA height `start`, by increasing
+    /// height, up to `limit` jobs.
+    #[method(name = "getProofJobs")]
+    async fn get_proof_jobs(&self, start: u64, limit: u64) -> RpcResult<Vec<ProofJobResponse>>;
+
+    /// Queues the failed or stuck proof job of the slot at DA height `height`
+    /// again.
+    #[method(name = "requeueProofJob")]
+    async fn requeue_proof_job(&self, height: u64) -> RpcResult<ProofJobResponse>;
+
+    /// Gets the DA heights of the last executed slot and of the last proven
+    /// slot, whose difference is the proving lag.
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(&self) -> RpcResult<CheckpointsResponse>;
+
+    /// Gets the proofs posted to the DA by other parties which were verified
+    /// by the node, from DA height `start`, by increasing height, up to
+    /// `limit` proofs.
+    #[method(name = "getVerifiedProofs")]
+    async fn get_verified_proofs(
+        &self,
+        start: u64,
+        limit: u64,
+    ) -> RpcResult<Vec<VerifiedProofResponse>>;
+
     /// Subscription method to receive a notification each time a slot is
     /// processed.
     #[subscription(name = "subscribeSlots", item = u64)]
@@ -4,9 +4,10 @@ use futures::future::Either;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::{RpcModule, SubscriptionMessage};
 use serde::de::DeserializeOwned;
-use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::utils::{to_jsonrpsee_error_object, to_pruned_error_object};
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventIdentifier, LedgerRpcProvider, PrunedError, QueryMode, SlotIdentifier,
+    TxIdentifier,
 };
 
 use crate::HexHash;
@@ -48,7 +49,7 @@ where
         let query_mode = params.optional_next()?.unwrap_or(QueryMode::Compact);
         ledger
             .get_head::<B, Tx>(query_mode)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
 
     // Primary getters.
@@ -56,24 +57,23 @@ where
         let args: QueryArgs<Vec<SlotIdentifier>> = extract_query_args(params)?;
         ledger
             .get_slots::<B, Tx>(&args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getBatches", move |params, ledger| {
         let args: QueryArgs<Vec<BatchIdentifier>> = extract_query_args(params)?;
         ledger
             .get_batches::<B, Tx>(&args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getTransactions", move |params, ledger| {
         let args: QueryArgs<Vec<TxIdentifier>> = extract_query_args(params)?;
         ledger
             .get_transactions::<Tx>(&args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getEvents", move |params, db| {
         let ids: Vec<EventIdentifier> = params.parse().or_else(|_| params.one())?;
-        db.get_events(&ids)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+        db.get_events(&ids).map_err(to_ledger_error_object)
     })?;
 
     // By-hash getters.
@@ -81,19 +81,19 @@ where
         let args: QueryArgs<HexHash> = extract_query_args(params)?;
         ledger
             .get_slot_by_hash::<B, Tx>(&args.0 .0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getBatchByHash", move |params, ledger| {
         let args: QueryArgs<HexHash> = extract_query_args(params)?;
         ledger
             .get_batch_by_hash::<B, Tx>(&args.0 .0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getTransactionByHash", move |params, ledger| {
         let args: QueryArgs<HexHash> = extract_query_args(params)?;
         ledger
             .get_tx_by_hash::<Tx>(&args.0 .0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
 
     // By-number getters.
@@ -101,25 +101,25 @@ where
         let args: QueryArgs<u64> = extract_query_args(params)?;
         ledger
             .get_slot_by_number::<B, Tx>(args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getBatchByNumber", move |params, ledger| {
         let args: QueryArgs<u64> = extract_query_args(params)?;
         ledger
             .get_batch_by_number::<B, Tx>(args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getTransactionByNumber", move |params, ledger| {
         let args: QueryArgs<u64> = extract_query_args(params)?;
         ledger
             .get_tx_by_number::<Tx>(args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getEventByNumber", move |params, ledger| {
         let args: u64 = params.one()?;
         ledger
             .get_event_by_number(args)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
 
     // Range getters.
@@ -127,19 +127,44 @@ where
         let args: RangeArgs = params.parse()?;
         ledger
             .get_slots_range::<B, Tx>(args.0, args.1, args.2)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getBatchesRange", move |params, ledger| {
         let args: RangeArgs = params.parse()?;
         ledger
             .get_batches_range::<B, Tx>(args.0, args.1, args.2)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
     })?;
     rpc.register_method("ledger_getTransactionsRange", move |params, ledger| {
         let args: RangeArgs = params.parse()?;
         ledger
             .get_transactions_range::<Tx>(args.0, args.1, args.2)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+            .map_err(to_ledger_error_object)
+    })?;
+
+    // Proof jobs.
+    rpc.register_method("ledger_getProofJobs", move |params, ledger| {
+        let args: (u64, u64) = params.parse()?;
+        ledger
+            .get_proof_jobs(args.0, args.1)
+            .map_err(to_ledger_error_object)
+    })?;
+    rpc.register_method("ledger_requeueProofJob", move |params, ledger| {
+        let height: u64 = params.one()?;
+        ledger
+            .requeue_proof_job(height)
+            .map_err(to_ledger_error_object)
+    })?;
+    rpc.register_method("ledger_getCheckpoints", move |_, ledger| {
+        ledger.get_checkpoints().map_err(to_ledger_error_object)
+    })?;
+
+    // Verified proofs.
+    rpc.register_method("ledger_getVerifiedProofs", move |params, ledger| {
+        let args: (u64, u64) = params.parse()?;
+        ledger
+            .get_verified_proofs(args.0, args.1)
+            .map_err(to_ledger_error_object)
     })?;
 
     rpc.register_subscription(
@@ -148,9 +173,7 @@ where
         "ledger_unsubscribeSlots",
         |_, pending_subscription, db| async move {
             // Register with the ledgerDB to receive callbacks
-            let mut rx = db
-                .subscribe_slots()
-                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+            let mut rx = db.subscribe_slots().map_err(to_ledger_error_object)?;
 
             // Accept the subscription. This message is sent immediately
             let subscription = pending_subscription.accept().await?;
@@ -181,6 +204,15 @@ where
     Ok(rpc)
 }
 
+/// Requests for pruned items fail with the [`PRUNED_ERROR_CODE`](sov_modules_api::utils::PRUNED_ERROR_CODE) code.
+fn to_ledger_error_object(e: anyhow::Error) -> ErrorObjectOwned {
+    if e.downcast_ref::<PrunedError>().is_some() {
+        to_pruned_error_object(e)
+    } else {
+        to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR)
+    }
+}
+
 #[derive(serde::Deserialize)]
 struct RangeArgs(u64, u64, #[serde(default)] QueryMode);
 
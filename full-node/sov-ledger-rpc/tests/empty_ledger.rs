@@ -103,6 +103,10 @@ async fn getters_succeed() {
         .get_txs_range(0, 1, QueryMode::Compact)
         .await
         .unwrap();
+
+    rpc_client.get_proof_jobs(0, 10).await.unwrap();
+    rpc_client.get_checkpoints().await.unwrap();
+    rpc_client.get_verified_proofs(0, 10).await.unwrap();
 }
 
 #[tokio::test]
@@ -0,0 +1,211 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+use std::collections::VecDeque;
+
+use anyhow::{bail, ensure};
+use borsh::BorshDeserialize;
+use sov_modules_core::{Storage, StorageKey, StorageProof, StorageValue};
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::rpc::StateProofResponse;
+use sov_rollup_interface::zk::{AggregatedStateTransition, Proof, ValidityConditionChecker, Zkvm};
+
+/// A state root of the rollup, verified by the light client, and the DA block after which the rollup has this state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedCheckpoint<Da: DaSpec, Root> {
+    /// The state root of the rollup.
+    pub state_root: Root,
+    /// The hash of the DA block.
+    pub slot_hash: Da::SlotHash,
+    /// The height of the DA block.
+    pub height: u64,
+}
+
+/// Tracks the chain of the verified state roots of a rollup from the DA headers and the aggregated proofs
+/// posted to the DA, without executing the rollup nor trusting a full node.
+///
+/// The client starts from a trusted [`VerifiedCheckpoint`], e.g. the genesis state root of the rollup. It only
+/// keeps the latest verified checkpoint and the hashes of the DA headers which follow it: the headers are dropped
+/// once an aggregated proof covers them, so the storage is bounded by the number of slots between two proofs.
+pub struct LightClient<Da, Vm, S, Checker>
+where
+    Da: DaSpec,
+    Vm: Zkvm,
+    S: Storage,
+    Checker: ValidityConditionChecker<Da::ValidityCondition>,
+{
+    code_commitment: Vm::CodeCommitment,
+    checker: Checker,
+    latest: VerifiedCheckpoint<Da, S::Root>,
+    // The hashes of the DA headers following the latest checkpoint, by increasing height.
+    pending_slot_hashes: VecDeque<Da::SlotHash>,
+}
+
+impl<Da, Vm, S, Checker> LightClient<Da, Vm, S, Checker>
+where
+    Da: DaSpec,
+    Vm: Zkvm,
+    S: Storage,
+    Checker: ValidityConditionChecker<Da::ValidityCondition>,
+{
+    /// Creates a light client verifying the proofs of the aggregation program with the given `code_commitment`,
+    /// starting from the `trusted` checkpoint. The validity conditions of the proofs are checked with `checker`.
+    pub fn new(
+        code_commitment: Vm::CodeCommitment,
+        checker: Checker,
+        trusted: VerifiedCheckpoint<Da, S::Root>,
+    ) -> Self {
+        Self {
+            code_commitment,
+            checker,
+            latest: trusted,
+            pending_slot_hashes: VecDeque::new(),
+        }
+    }
+
+    /// Returns the latest verified state root.
+    pub fn latest_verified_root(&self) -> &S::Root {
+        &self.latest.state_root
+    }
+
+    /// Returns the latest verified checkpoint.
+    pub fn latest_checkpoint(&self) -> &VerifiedCheckpoint<Da, S::Root> {
+        &self.latest
+    }
+
+    /// Returns the number of DA headers received after the latest verified checkpoint.
+    pub fn pending_headers(&self) -> usize {
+        self.pending_slot_hashes.len()
+    }
+
+    /// Appends the next DA header to the chain tracked by the client. Fails if `header` doesn't extend the
+    /// last header received, in which case the DA layer was reorganized or the header is forged.
+    pub fn process_header(&mut self, header: &Da::BlockHeader) -> anyhow::Result<()> {
+        let (last_hash, last_height) = match self.pending_slot_hashes.back() {
+            Some(hash) => (
+                hash,
+                self.latest.height + self.pending_slot_hashes.len() as u64,
+            ),
+            None => (&self.latest.slot_hash, self.latest.height),
+        };
+        ensure!(
+            header.height() == last_height + 1,
+            "Expected the DA header at height {}, got height {}",
+            last_height + 1,
+            header.height()
+        );
+        ensure!(
+            &header.prev_hash() == last_hash,
+            "The DA header {:?} at height {} doesn't extend the last header {:?}",
+            header.hash(),
+            header.height(),
+            last_hash
+        );
+
+        self.pending_slot_hashes.push_back(header.hash());
+        Ok(())
+    }
+
+    /// Verifies a proof posted to the DA, in the borsh encoding of [`Proof`], and moves the latest checkpoint
+    /// to the end of its state transition. The proof must be a full proof of the aggregation program, covering
+    /// the slots right after the latest checkpoint, starting from its state root, with a valid validity condition.
+    /// The DA headers of these slots must have been received with [`LightClient::process_header`].
+    pub fn process_proof(
+        &mut self,
+        posted_proof: &[u8],
+    ) -> anyhow::Result<&VerifiedCheckpoint<Da, S::Root>> {
+        let serialized_proof = match Proof::try_from_slice(posted_proof)? {
+            Proof::Full(serialized_proof) => serialized_proof,
+            Proof::PublicInput(_) => bail!("The proof only holds public input"),
+        };
+        let transition: AggregatedStateTransition<Da, S::Root> =
+            Vm::verify_and_extract_aggregated_output(&serialized_proof, &self.code_commitment)
+                .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?;
+
+        ensure!(
+            transition.initial_state_root == self.latest.state_root,
+            "The proof starts from state root {:?}, but the latest verified root is {:?}",
+            transition.initial_state_root,
+            self.latest.state_root
+        );
+        let slot_count = transition.slot_count as usize;
+        ensure!(
+            slot_count > 0 && slot_count <= self.pending_slot_hashes.len(),
+            "The proof covers {} slots, but {} DA headers follow the latest checkpoint",
+            slot_count,
+            self.pending_slot_hashes.len()
+        );
+        ensure!(
+            self.pending_slot_hashes[0] == transition.initial_slot_hash,
+            "The proof starts at slot {:?}, but the slot following the latest checkpoint is {:?}",
+            transition.initial_slot_hash,
+            self.pending_slot_hashes[0]
+        );
+        ensure!(
+            self.pending_slot_hashes[slot_count - 1] == transition.final_slot_hash,
+            "The proof ends at slot {:?}, but the DA header at this distance is {:?}",
+            transition.final_slot_hash,
+            self.pending_slot_hashes[slot_count - 1]
+        );
+        self.checker
+            .check(&transition.validity_condition)
+            .map_err(Into::<anyhow::Error>::into)?;
+
+        self.pending_slot_hashes.drain(..slot_count);
+        self.latest = VerifiedCheckpoint {
+            state_root: transition.final_state_root,
+            slot_hash: transition.final_slot_hash,
+            height: self.latest.height + transition.slot_count,
+        };
+        Ok(&self.latest)
+    }
+
+    /// Verifies a proof that a key of the rollup state has a value, or is absent, against the latest verified
+    /// root, and returns the opened key and value.
+    pub fn verify_state_proof(
+        &self,
+        proof: StorageProof<S::Proof>,
+    ) -> anyhow::Result<(StorageKey, Option<StorageValue>)> {
+        S::open_proof(self.latest.state_root.clone(), proof)
+    }
+
+    /// Verifies a response of the `rollup_getProof` RPC method for `key` against the latest verified root,
+    /// with [`verify_state_proof_response`].
+    pub fn verify_state_proof_response(
+        &self,
+        key: &[u8],
+        response: &StateProofResponse,
+    ) -> anyhow::Result<Option<StorageValue>> {
+        verify_state_proof_response::<S>(&self.latest.state_root, key, response)
+    }
+}
+
+/// Verifies a response of the `rollup_getProof` RPC method of a full node, and returns the value of `key` in
+/// the state with root `state_root`, or `None` if `key` is absent from this state. Fails if the response is
+/// for another key or another state root, or if its proof doesn't open against `state_root`.
+pub fn verify_state_proof_response<S: Storage>(
+    state_root: &S::Root,
+    key: &[u8],
+    response: &StateProofResponse,
+) -> anyhow::Result<Option<StorageValue>> {
+    ensure!(
+        response.key == key,
+        "The response proves the key {:?}, not the key {:?}",
+        response.key,
+        key
+    );
+    ensure!(
+        response.state_root.as_slice() == state_root.as_ref(),
+        "The response is a proof against the state root {:?}, not {:?}",
+        response.state_root,
+        state_root
+    );
+
+    let proof = StorageProof {
+        key: StorageKey::from(response.key.clone()),
+        value: response.value.clone().map(StorageValue::from),
+        proof: S::Proof::try_from_slice(&response.proof)?,
+    };
+    let (_, value) = S::open_proof(state_root.clone(), proof)?;
+    Ok(value)
+}
@@ -0,0 +1,236 @@
+use borsh::BorshSerialize;
+use sov_light_client::{verify_state_proof_response, LightClient, VerifiedCheckpoint};
+use sov_mock_da::{MockBlockHeader, MockDaSpec, MockValidityCond, MockValidityCondChecker};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::rpc::StateProofResponse;
+use sov_rollup_interface::zk::{AggregatedStateTransition, Proof};
+use sov_state::storage::{CacheKey, CacheValue, NativeStorage, StorageKey, StorageValue};
+use sov_state::{ArrayWitness, DefaultStorageSpec, OrderedReadsAndWrites, Storage, ZkStorage};
+
+type S = ZkStorage<DefaultStorageSpec>;
+type Root = <S as Storage>::Root;
+type TestLightClient = LightClient<
+    MockDaSpec,
+    MockZkvm<MockValidityCond>,
+    S,
+    MockValidityCondChecker<MockValidityCond>,
+>;
+
+const PROGRAM_ID: MockCodeCommitment = MockCodeCommitment([1; 32]);
+
+fn new_client(genesis_root: Root) -> TestLightClient {
+    let genesis = MockBlockHeader::from_height(0);
+    TestLightClient::new(
+        PROGRAM_ID,
+        MockValidityCondChecker::new(),
+        VerifiedCheckpoint {
+            state_root: genesis_root,
+            slot_hash: genesis.hash(),
+            height: 0,
+        },
+    )
+}
+
+/// Returns a posted proof of the aggregation program for the slots from `first_height` to `last_height`.
+fn posted_proof(
+    initial_state_root: Root,
+    final_state_root: Root,
+    first_height: u64,
+    last_height: u64,
+    is_valid: bool,
+) -> Vec<u8> {
+    let transition = AggregatedStateTransition::<MockDaSpec, Root> {
+        initial_state_root,
+        final_state_root,
+        initial_slot_hash: MockBlockHeader::from_height(first_height).hash(),
+        final_slot_hash: MockBlockHeader::from_height(last_height).hash(),
+        slot_count: last_height - first_height + 1,
+        validity_condition: MockValidityCond { is_valid },
+    };
+    let output = bincode::serialize(&transition).unwrap();
+    let proof = MockProof {
+        program_id: PROGRAM_ID,
+        is_valid: true,
+        log: &output,
+    };
+    Proof::Full(proof.encode_to_vec()).try_to_vec().unwrap()
+}
+
+#[test]
+fn test_tracks_verified_roots() {
+    let [root_0, root_1, root_2] = [[0; 32], [1; 32], [2; 32]].map(jmt::RootHash);
+    let mut client = new_client(root_0);
+
+    for height in 1..=3 {
+        client
+            .process_header(&MockBlockHeader::from_height(height))
+            .unwrap();
+    }
+    // Headers must be consecutive
+    assert!(client
+        .process_header(&MockBlockHeader::from_height(5))
+        .is_err());
+
+    // The proof must cover headers which were received
+    assert!(client
+        .process_proof(&posted_proof(root_0, root_1, 1, 4, true))
+        .is_err());
+
+    let checkpoint = client
+        .process_proof(&posted_proof(root_0, root_1, 1, 2, true))
+        .unwrap();
+    assert_eq!(2, checkpoint.height);
+    assert_eq!(&root_1, client.latest_verified_root());
+    assert_eq!(1, client.pending_headers());
+
+    // Stale proofs, and proofs with an invalid validity condition, are rejected
+    assert!(client
+        .process_proof(&posted_proof(root_0, root_1, 1, 2, true))
+        .is_err());
+    assert!(client
+        .process_proof(&posted_proof(root_1, root_2, 3, 3, false))
+        .is_err());
+    assert_eq!(&root_1, client.latest_verified_root());
+
+    client
+        .process_proof(&posted_proof(root_1, root_2, 3, 3, true))
+        .unwrap();
+    assert_eq!(&root_2, client.latest_verified_root());
+    assert_eq!(3, client.latest_checkpoint().height);
+    assert_eq!(0, client.pending_headers());
+}
+
+#[test]
+fn test_rejects_unverifiable_proofs() {
+    let [root_0, root_1] = [[0; 32], [1; 32]].map(jmt::RootHash);
+    let mut client = new_client(root_0);
+    client
+        .process_header(&MockBlockHeader::from_height(1))
+        .unwrap();
+
+    let public_input = Proof::PublicInput(vec![1, 2, 3]).try_to_vec().unwrap();
+    assert!(client.process_proof(&public_input).is_err());
+
+    let invalid_proof = Proof::Full(
+        MockProof {
+            program_id: PROGRAM_ID,
+            is_valid: false,
+            log: &[],
+        }
+        .encode_to_vec(),
+    )
+    .try_to_vec()
+    .unwrap();
+    assert!(client.process_proof(&invalid_proof).is_err());
+    assert!(client.process_proof(&[1, 2, 3]).is_err());
+
+    assert_eq!(&root_0, client.latest_verified_root());
+    client
+        .process_proof(&posted_proof(root_0, root_1, 1, 1, true))
+        .unwrap();
+}
+
+#[test]
+fn test_verify_state_proof() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage =
+        sov_prover_storage_manager::new_orphan_storage::<DefaultStorageSpec>(tmpdir.path())
+            .unwrap();
+
+    let key = CacheKey {
+        key: std::sync::Arc::new(b"key".to_vec()),
+    };
+    let value = CacheValue {
+        value: std::sync::Arc::new(b"value".to_vec()),
+    };
+    let mut state_operations = OrderedReadsAndWrites::default();
+    state_operations.ordered_writes.push((key, Some(value)));
+    let witness = ArrayWitness::default();
+    let (root, state_update) = storage
+        .compute_state_update(state_operations, &witness)
+        .unwrap();
+    storage.commit(&state_update, &OrderedReadsAndWrites::default());
+
+    let client = new_client(root);
+    let proof = storage.get_with_proof(StorageKey::from("key"));
+    let (opened_key, opened_value) = client.verify_state_proof(proof).unwrap();
+    assert_eq!(StorageKey::from("key"), opened_key);
+    assert_eq!(Some(StorageValue::from("value")), opened_value);
+
+    // A proof of another state doesn't open against the verified root
+    let proof = storage.get_with_proof(StorageKey::from("other key"));
+    let other_client = new_client(jmt::RootHash([0; 32]));
+    assert!(other_client.verify_state_proof(proof).is_err());
+}
+
+#[test]
+fn test_verify_state_proof_response() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage =
+        sov_prover_storage_manager::new_orphan_storage::<DefaultStorageSpec>(tmpdir.path())
+            .unwrap();
+
+    let mut roots = Vec::new();
+    for value in [b"v1", b"v2"] {
+        let key = CacheKey {
+            key: std::sync::Arc::new(b"key".to_vec()),
+        };
+        let value = CacheValue {
+            value: std::sync::Arc::new(value.to_vec()),
+        };
+        let mut state_operations = OrderedReadsAndWrites::default();
+        state_operations.ordered_writes.push((key, Some(value)));
+        let (root, state_update) = storage
+            .compute_state_update(state_operations, &ArrayWitness::default())
+            .unwrap();
+        storage.commit(&state_update, &OrderedReadsAndWrites::default());
+        roots.push(root);
+    }
+
+    // The response of a full node, as served by the `rollup_getProof` RPC method
+    let response_at = |key: &[u8], version: u64| {
+        let proof = storage
+            .get_with_proof_at(StorageKey::from(key.to_vec()), version)
+            .unwrap();
+        StateProofResponse {
+            state_root: storage.get_root_hash(version).unwrap().into(),
+            version,
+            key: key.to_vec(),
+            value: proof.value.map(|value| value.value().to_vec()),
+            proof: proof.proof.try_to_vec().unwrap(),
+        }
+    };
+
+    let client = new_client(roots[1].clone());
+    assert_eq!(
+        Some(StorageValue::from("v2")),
+        client
+            .verify_state_proof_response(b"key", &response_at(b"key", 2))
+            .unwrap()
+    );
+    assert_eq!(
+        None,
+        client
+            .verify_state_proof_response(b"other key", &response_at(b"other key", 2))
+            .unwrap()
+    );
+    // A proof against an older root
+    assert_eq!(
+        Some(StorageValue::from("v1")),
+        verify_state_proof_response::<S>(&roots[0], b"key", &response_at(b"key", 1)).unwrap()
+    );
+
+    // Responses for another key or another root are rejected
+    assert!(client
+        .verify_state_proof_response(b"other key", &response_at(b"key", 2))
+        .is_err());
+    assert!(client
+        .verify_state_proof_response(b"key", &response_at(b"key", 1))
+        .is_err());
+
+    // A forged value doesn't open against the root
+    let mut forged = response_at(b"key", 2);
+    forged.value = Some(b"v3".to_vec());
+    assert!(client.verify_state_proof_response(b"key", &forged).is_err());
+}
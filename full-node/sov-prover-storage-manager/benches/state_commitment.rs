@@ -0,0 +1,177 @@
+//! Compares the state commitments which can be selected by a [`MerkleProofSpec`].
+//!
+//! For every backend, measures the native computation of a state update, the size of the witness
+//! it produces, and the verification of that witness by [`ZkStorage`], which is the work done by the guest.
+//! Guest cycle counts are reported by the prover benchmarks of `demo-rollup`.
+extern crate criterion;
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sov_mock_da::MockBlockHeader;
+use sov_prover_storage_manager::{ProverStorageManager, SnapshotManager};
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::storage::{CacheKey, CacheValue, StorageKey, StorageValue};
+use sov_state::{
+    ArrayWitness, MerkleProofSpec, OrderedReadsAndWrites, ProverStorage, Storage, ZkStorage,
+};
+
+type Da = sov_mock_da::MockDaSpec;
+
+type Accesses = Vec<(CacheKey, Option<CacheValue>)>;
+
+fn random_entry<R: Rng>(rng: &mut R) -> (CacheKey, CacheValue) {
+    let key: Vec<u8> = (0..rng.gen_range(32..=64)).map(|_| rng.gen()).collect();
+    let value: Vec<u8> = (0..rng.gen_range(32..=256)).map(|_| rng.gen()).collect();
+    (
+        CacheKey { key: Arc::new(key) },
+        CacheValue {
+            value: Arc::new(value),
+        },
+    )
+}
+
+fn accesses(reads: &Accesses, writes: &Accesses) -> OrderedReadsAndWrites {
+    OrderedReadsAndWrites {
+        ordered_reads: reads.clone(),
+        ordered_writes: writes.clone(),
+    }
+}
+
+/// Populates the state with `rollup_height` slots of `num_accesses` writes, and returns the storage
+/// of the next slot with `num_accesses` reads of existing keys and `num_accesses` writes,
+/// half of them overwriting existing keys.
+fn setup_storage<S: MerkleProofSpec>(
+    path: &std::path::Path,
+    rollup_height: u64,
+    num_accesses: usize,
+) -> (
+    ProverStorageManager<Da, S>,
+    ProverStorage<S, SnapshotManager>,
+    Accesses,
+    Accesses,
+) {
+    let config = sov_state::config::Config {
+        path: path.to_path_buf(),
+        pruning: Default::default(),
+    };
+    let mut storage_manager = ProverStorageManager::<Da, S>::new(config).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+    let mut existing_keys = Vec::new();
+
+    for h in 1..=rollup_height {
+        let block_header = MockBlockHeader::from_height(h);
+        let storage = storage_manager.create_storage_on(&block_header).unwrap();
+        let ordered_writes = (0..num_accesses)
+            .map(|_| {
+                let (key, value) = random_entry(&mut rng);
+                existing_keys.push(key.clone());
+                (key, Some(value))
+            })
+            .collect();
+        let witness = S::Witness::default();
+        let (_, state_update) = storage
+            .compute_state_update(
+                OrderedReadsAndWrites {
+                    ordered_reads: Default::default(),
+                    ordered_writes,
+                },
+                &witness,
+            )
+            .unwrap();
+        storage.commit(&state_update, &OrderedReadsAndWrites::default());
+        storage_manager
+            .save_change_set(&block_header, storage)
+            .unwrap();
+        storage_manager.finalize(&block_header).unwrap();
+    }
+
+    let block_header = MockBlockHeader::from_height(rollup_height + 1);
+    let storage = storage_manager.create_storage_on(&block_header).unwrap();
+    let witness = S::Witness::default();
+    existing_keys.shuffle(&mut rng);
+    let reads = existing_keys[..num_accesses]
+        .iter()
+        .map(|key| {
+            let key = key.clone();
+            let value = storage
+                .get(&StorageKey::from(key.clone()), None, &witness)
+                .map(StorageValue::into_cache_value);
+            (key, value)
+        })
+        .collect();
+    let writes = (0..num_accesses)
+        .map(|i| {
+            let (key, value) = random_entry(&mut rng);
+            if i % 2 == 0 {
+                (existing_keys[i].clone(), Some(value))
+            } else {
+                (key, Some(value))
+            }
+        })
+        .collect();
+
+    (storage_manager, storage, reads, writes)
+}
+
+fn bench_state_commitment<S: MerkleProofSpec<Witness = ArrayWitness>>(
+    c: &mut Criterion,
+    backend: &str,
+) {
+    let mut group = c.benchmark_group(format!("StateCommitment/{backend}"));
+    group.noise_threshold(0.3);
+    for num_accesses in [10, 100, 1_000] {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (_storage_manager, storage, reads, writes) =
+            setup_storage::<S>(tmpdir.path(), 10, num_accesses);
+
+        let witness = ArrayWitness::default();
+        storage
+            .compute_state_update(accesses(&reads, &writes), &witness)
+            .unwrap();
+        println!(
+            "StateCommitment/{backend}/accesses={num_accesses}: witness size {} bytes",
+            witness.size_in_bytes()
+        );
+        let serialized_witness = serde_json::to_vec(&witness).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("compute_update/accesses", num_accesses),
+            &storage,
+            |b, storage| {
+                b.iter_batched(
+                    || (accesses(&reads, &writes), ArrayWitness::default()),
+                    |(accesses, witness)| storage.compute_state_update(accesses, &witness),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("verify_update/accesses", num_accesses),
+            &ZkStorage::<S>::new(),
+            |b, zk_storage| {
+                b.iter_batched(
+                    || {
+                        let witness: ArrayWitness =
+                            serde_json::from_slice(&serialized_witness).unwrap();
+                        (accesses(&reads, &writes), witness)
+                    },
+                    |(accesses, witness)| {
+                        zk_storage.compute_state_update(accesses, &witness).unwrap()
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+}
+
+fn state_commitment_benchmark(c: &mut Criterion) {
+    bench_state_commitment::<sov_state::DefaultStorageSpec>(c, "jmt");
+}
+
+criterion_group!(benches, state_commitment_benchmark);
+criterion_main!(benches);
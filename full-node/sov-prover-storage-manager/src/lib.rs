@@ -4,10 +4,13 @@ use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
 
 use sov_db::native_db::NativeDB;
+use sov_db::pruning::{PruningTask, DEFAULT_PRUNING_INTERVAL};
 use sov_db::state_db::StateDB;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock, SnapshotId};
+use sov_state::config::PruningMode;
+use sov_state::snapshot::StateSnapshot;
 use sov_state::{MerkleProofSpec, ProverStorage};
 
 pub use crate::snapshot_manager::SnapshotManager;
@@ -36,6 +39,11 @@ pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     state_snapshot_manager: Arc<RwLock<SnapshotManager>>,
     accessory_snapshot_manager: Arc<RwLock<SnapshotManager>>,
 
+    // Which finalized versions of the state are kept
+    pruning: PruningMode,
+    // Deletes the versions of the state which are not retained in the background
+    pruning_task: Option<PruningTask>,
+
     phantom_mp_spec: PhantomData<S>,
 }
 
@@ -59,6 +67,8 @@ where
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
+            pruning: PruningMode::Archive,
+            pruning_task: None,
             phantom_mp_spec: Default::default(),
         }
     }
@@ -69,7 +79,59 @@ where
         let state_db = StateDB::<SnapshotManager>::setup_schema_db(&path)?;
         let native_db = NativeDB::<SnapshotManager>::setup_schema_db(&path)?;
 
-        Ok(Self::with_db_handles(state_db, native_db))
+        let mut storage_manager = Self::with_db_handles(state_db, native_db);
+        storage_manager.pruning = config.pruning;
+        if let Some(retained_versions) = config.pruning.retained_versions() {
+            let state_db = storage_manager
+                .state_snapshot_manager
+                .read()
+                .unwrap()
+                .db_handle();
+            storage_manager.pruning_task = Some(PruningTask::spawn(
+                "state-pruning",
+                DEFAULT_PRUNING_INTERVAL,
+                move || prune_state_db(&state_db, retained_versions),
+            )?);
+        }
+        Ok(storage_manager)
+    }
+
+    /// Deletes the finalized versions of the state which are not retained by the pruning mode, and compacts the database.
+    /// This is done periodically in the background by storage managers created with [`ProverStorageManager::new`].
+    pub fn prune(&self) -> anyhow::Result<()> {
+        match self.pruning.retained_versions() {
+            Some(retained_versions) => {
+                let state_db = self.state_snapshot_manager.read().unwrap().db_handle();
+                prune_state_db(&state_db, retained_versions)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Takes a snapshot of the finalized state at `version`.
+    pub fn export_snapshot(&mut self, version: u64) -> anyhow::Result<StateSnapshot> {
+        self.create_finalized_storage()?.export_snapshot(version)
+    }
+
+    /// Imports `snapshot` into the empty finalized state. Blocks can then be processed on top of the snapshot,
+    /// as if the state had been built by processing the blocks up to its version.
+    pub fn import_snapshot(&mut self, snapshot: StateSnapshot) -> anyhow::Result<()> {
+        if !self.block_hash_to_snapshot_id.is_empty() {
+            anyhow::bail!("Cannot import a snapshot while blocks are being processed");
+        }
+
+        self.latest_snapshot_id += 1;
+        let snapshot_id = self.latest_snapshot_id;
+        let storage = self.get_storage_with_snapshot_id(snapshot_id)?;
+        storage.import_snapshot(snapshot)?;
+        let (state_snapshot, native_snapshot) = storage.freeze()?;
+
+        let mut state_manager = self.state_snapshot_manager.write().unwrap();
+        let mut native_manager = self.accessory_snapshot_manager.write().unwrap();
+        state_manager.add_snapshot(state_snapshot);
+        native_manager.add_snapshot(native_snapshot);
+        state_manager.commit_snapshot(&snapshot_id)?;
+        native_manager.commit_snapshot(&snapshot_id)
     }
 
     #[cfg(test)]
@@ -324,6 +386,41 @@ where
         let prev_block_hash = block_header.prev_hash();
         self.finalize_by_hash_pair(prev_block_hash, current_block_hash)
     }
+
+    fn finalized_version(&self) -> anyhow::Result<u64> {
+        let state_manager = self.state_snapshot_manager.read().unwrap();
+        StateDB::<SnapshotManager>::latest_version(state_manager.db())
+    }
+
+    fn rollback_finalized(&mut self, version: u64) -> anyhow::Result<()> {
+        if !self.block_hash_to_snapshot_id.is_empty() {
+            anyhow::bail!(
+                "Cannot roll back the finalized storage while blocks are being processed"
+            );
+        }
+
+        let state_manager = self.state_snapshot_manager.write().unwrap();
+        let native_manager = self.accessory_snapshot_manager.write().unwrap();
+        // The accessory writes of a block are recorded at the version of the state it is built on,
+        // so they are deleted from `version`, and the state from the next one.
+        let state_truncated =
+            StateDB::<SnapshotManager>::truncate(state_manager.db(), version + 1)?;
+        let native_truncated = NativeDB::<SnapshotManager>::truncate(native_manager.db(), version)?;
+        tracing::info!(
+            "Rolled back the finalized storage to version={}, state truncated={}, accessory state truncated={}",
+            version,
+            state_truncated,
+            native_truncated
+        );
+        Ok(())
+    }
+}
+
+fn prune_state_db(state_db: &sov_schema_db::DB, retained_versions: u64) -> anyhow::Result<()> {
+    if StateDB::<SnapshotManager>::prune(state_db, retained_versions)? {
+        StateDB::<SnapshotManager>::compact(state_db)?;
+    }
+    Ok(())
 }
 
 /// Creates orphan [`ProverStorage`] which just points directly to the underlying database for previous data
@@ -347,7 +444,7 @@ pub fn new_orphan_storage<S: MerkleProofSpec>(
 mod tests {
     use sov_mock_da::{MockBlockHeader, MockHash};
     use sov_rollup_interface::da::Time;
-    use sov_state::storage::{CacheKey, CacheValue};
+    use sov_state::storage::{CacheKey, CacheValue, NativeStorage};
     use sov_state::{ArrayWitness, OrderedReadsAndWrites, Storage};
 
     use super::*;
@@ -866,6 +963,193 @@ mod tests {
         assert!(storage_manager.is_empty());
     }
 
+    #[test]
+    fn prune_finalized_versions() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        storage_manager.pruning = PruningMode::KeepRecent(2);
+
+        // Every block overwrites the same key with its height, at the version equal to its height
+        let witness = ArrayWitness::default();
+        for height in 1..=4 {
+            let block = MockBlockHeader::from_height(height);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(1, height));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(&block, storage).unwrap();
+            storage_manager.finalize(&block).unwrap();
+        }
+        storage_manager.prune().unwrap();
+
+        let storage = storage_manager
+            .create_storage_on(&MockBlockHeader::from_height(5))
+            .unwrap();
+        assert_eq!(3, storage.oldest_retained_version());
+        for version in 3..=4 {
+            assert_eq!(
+                Some(value_from(version).into()),
+                storage.get(&key_from(1).into(), Some(version), &witness)
+            );
+            assert!(storage.get_root_hash(version).is_ok());
+        }
+        assert_eq!(None, storage.get(&key_from(1).into(), Some(2), &witness));
+        assert!(storage.get_root_hash(2).is_err());
+    }
+
+    #[test]
+    fn export_and_import_snapshot() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        let witness = ArrayWitness::default();
+        for height in 1..=3 {
+            let block = MockBlockHeader::from_height(height);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations
+                .ordered_writes
+                .push(write_op(height, height));
+            if height == 3 {
+                state_operations.ordered_writes.push(delete_op(1));
+            }
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(&block, storage).unwrap();
+            storage_manager.finalize(&block).unwrap();
+        }
+
+        let snapshot = storage_manager.export_snapshot(3).unwrap();
+        assert_eq!(3, snapshot.version);
+        assert_eq!(
+            vec![
+                (key_from(2).key.to_vec(), value_from(2).value.to_vec()),
+                (key_from(3).key.to_vec(), value_from(3).value.to_vec()),
+            ],
+            snapshot.entries
+        );
+
+        let mut corrupted = snapshot.clone();
+        corrupted.entries[0].1 = value_from(5).value.to_vec();
+        let other_tmpdir = tempfile::tempdir().unwrap();
+        let (state_db, native_db) = build_dbs(other_tmpdir.path());
+        let mut imported_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        assert!(imported_manager.import_snapshot(corrupted).is_err());
+
+        let other_tmpdir = tempfile::tempdir().unwrap();
+        let (state_db, native_db) = build_dbs(other_tmpdir.path());
+        let mut imported_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        imported_manager.import_snapshot(snapshot.clone()).unwrap();
+
+        // Blocks are processed on top of the imported state
+        let block = MockBlockHeader::from_height(4);
+        let storage = imported_manager.create_storage_on(&block).unwrap();
+        assert_eq!(3, storage.oldest_retained_version());
+        assert_eq!(
+            snapshot.root,
+            <[u8; 32]>::from(storage.get_root_hash(3).unwrap())
+        );
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get(&key_from(2).into(), None, &witness)
+        );
+        assert_eq!(None, storage.get(&key_from(1).into(), None, &witness));
+
+        let mut state_operations = OrderedReadsAndWrites::default();
+        state_operations.ordered_writes.push(write_op(4, 4));
+        let (_, state_update) = storage
+            .compute_state_update(state_operations, &witness)
+            .unwrap();
+        storage.commit(&state_update, &OrderedReadsAndWrites::default());
+        imported_manager.save_change_set(&block, storage).unwrap();
+        imported_manager.finalize(&block).unwrap();
+        assert_eq!(
+            3,
+            imported_manager.export_snapshot(4).unwrap().entries.len()
+        );
+    }
+
+    #[test]
+    fn rollback_interrupted_finalization() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        // Every block overwrites key 1 and the accessory key 100 with its height, and inserts a key equal to its height.
+        // Block 3 also deletes key 2.
+        let witness = ArrayWitness::default();
+        let apply_block = |storage_manager: &mut ProverStorageManager<Da, S>, height: u64| {
+            let block = MockBlockHeader::from_height(height);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(1, height));
+            state_operations
+                .ordered_writes
+                .push(write_op(height, height));
+            if height == 3 {
+                state_operations.ordered_writes.push(delete_op(2));
+            }
+            let mut native_operations = OrderedReadsAndWrites::default();
+            native_operations.ordered_writes.push(write_op(100, height));
+            let (root, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &native_operations);
+            storage_manager.save_change_set(&block, storage).unwrap();
+            storage_manager.finalize(&block).unwrap();
+            root
+        };
+
+        for height in 1..=2 {
+            apply_block(&mut storage_manager, height);
+        }
+        let version = storage_manager.finalized_version().unwrap();
+        assert_eq!(2, version);
+
+        // The node is killed after block 3 is finalized, but before it is committed to the ledger
+        let root = apply_block(&mut storage_manager, 3);
+        assert_eq!(3, storage_manager.finalized_version().unwrap());
+        storage_manager.rollback_finalized(version).unwrap();
+        assert_eq!(version, storage_manager.finalized_version().unwrap());
+
+        let storage = storage_manager
+            .create_storage_on(&MockBlockHeader::from_height(3))
+            .unwrap();
+        assert!(storage.get_root_hash(3).is_err());
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get(&key_from(1).into(), None, &witness)
+        );
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get(&key_from(2).into(), None, &witness)
+        );
+        assert_eq!(None, storage.get(&key_from(3).into(), None, &witness));
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get_accessory(&key_from(100).into(), None)
+        );
+        drop(storage);
+
+        // Processing the block again gives the same state
+        assert_eq!(root, apply_block(&mut storage_manager, 3));
+        assert_eq!(3, storage_manager.finalized_version().unwrap());
+    }
+
     #[test]
     fn lifecycle_simulation() {
         let tmpdir = tempfile::tempdir().unwrap();
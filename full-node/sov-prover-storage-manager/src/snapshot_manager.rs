@@ -15,7 +15,7 @@ use crate::snapshot_manager::DataLocation::Snapshot;
 /// down to DB level
 /// Managed externally by [`crate::ProverStorageManager`]
 pub struct SnapshotManager {
-    db: sov_schema_db::DB,
+    db: Arc<sov_schema_db::DB>,
     snapshots: HashMap<SnapshotId, ReadOnlyDbSnapshot>,
     /// Hierarchical
     to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
@@ -27,7 +27,7 @@ impl SnapshotManager {
         to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
     ) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent,
         }
@@ -37,7 +37,7 @@ impl SnapshotManager {
     /// So it only reads from database.
     pub fn orphan(db: sov_schema_db::DB) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent: Arc::new(RwLock::new(Default::default())),
         }
@@ -63,6 +63,16 @@ impl SnapshotManager {
         self.db.write_schemas(snapshot.into())
     }
 
+    /// The underlying database, which holds the committed snapshots.
+    pub(crate) fn db(&self) -> &sov_schema_db::DB {
+        &self.db
+    }
+
+    /// A shared handle to the underlying database, which can be used without locking the manager.
+    pub(crate) fn db_handle(&self) -> Arc<sov_schema_db::DB> {
+        self.db.clone()
+    }
+
     #[cfg(test)]
     pub(crate) fn is_empty(&self) -> bool {
         self.snapshots.is_empty()
@@ -239,8 +249,18 @@ where
 }
 
 impl QueryManager for SnapshotManager {
-    type Iter<'a, S> = SnapshotManagerIter<'a, S, Rev<btree_map::Iter<'a, SchemaKey, Operation>>> where S: Sized, S: Schema, Self: 'a;
-    type RangeIter<'a, S: Schema> = SnapshotManagerIter<'a, S, Rev<btree_map::Range<'a, SchemaKey, Operation>>> where S: Sized, S: Schema, Self: 'a;
+    type Iter<'a, S>
+        = SnapshotManagerIter<'a, S, Rev<btree_map::Iter<'a, SchemaKey, Operation>>>
+    where
+        S: Sized,
+        S: Schema,
+        Self: 'a;
+    type RangeIter<'a, S: Schema>
+        = SnapshotManagerIter<'a, S, Rev<btree_map::Range<'a, SchemaKey, Operation>>>
+    where
+        S: Sized,
+        S: Schema,
+        Self: 'a;
 
     fn get<S: Schema>(
         &self,
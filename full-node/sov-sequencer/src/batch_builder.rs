@@ -1,173 +1,151 @@
-use std::collections::VecDeque;
 use std::io::Cursor;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context as ErrorContext};
 use borsh::BorshDeserialize;
-use sov_modules_api::digest::Digest;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, DispatchCall, PublicKey, Spec, WorkingSet};
-use sov_rollup_interface::services::batch_builder::BatchBuilder;
+use sov_modules_api::{Context, DispatchCall, WorkingSet};
+use sov_rollup_interface::services::batch_builder::{BatchBuilder, MempoolTx};
 use tracing::{info, warn};
 
-/// Transaction stored in the mempool.
-pub struct PooledTransaction<C: Context, R: DispatchCall<Context = C>> {
-    /// Raw transaction bytes.
-    raw: Vec<u8>,
-    /// Deserialized transaction.
-    tx: Transaction<C>,
-    /// The decoded runtime message, cached during initial verification.
-    msg: Option<R::Decodable>,
+use crate::mempool::{Mempool, MempoolConfig, PooledTransaction};
+use crate::metrics::{SEQUENCER_MEMPOOL_SIZE_BYTES, SEQUENCER_MEMPOOL_TXS};
+
+/// The limits of a batch built by the [`PriorityBatchBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchPolicy {
+    /// The maximum sum of the sizes of the transactions in a batch, in bytes.
+    pub max_batch_size_bytes: usize,
+    /// The maximum sum of the gas limits of the transactions in a batch.
+    pub max_batch_gas: u64,
+    /// The time after which the batch is closed with the transactions executed so far.
+    pub deadline: Duration,
 }
 
-impl<C, R> std::fmt::Debug for PooledTransaction<C, R>
-where
-    C: Context,
-    R: DispatchCall<Context = C>,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("PooledTransaction")
-            .field("raw", &hex::encode(&self.raw))
-            .field("tx", &self.tx)
-            .finish()
-    }
-}
-
-impl<C, R> PooledTransaction<C, R>
-where
-    C: Context,
-    R: DispatchCall<Context = C>,
-{
-    fn calculate_hash(&self) -> [u8; 32] {
-        <C as Spec>::Hasher::digest(&self.raw[..]).into()
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            max_batch_size_bytes: 1024 * 100,
+            max_batch_gas: u64::MAX,
+            deadline: Duration::from_secs(1),
+        }
     }
 }
 
-/// BatchBuilder that creates batches of transactions in the order they were submitted
+/// BatchBuilder that creates batches of the transactions with the highest gas tips, see [`Mempool`].
 /// Only transactions that were successfully dispatched are included.
-pub struct FiFoStrictBatchBuilder<C: Context, R: DispatchCall<Context = C>> {
-    mempool: VecDeque<PooledTransaction<C, R>>,
-    mempool_max_txs_count: usize,
+pub struct PriorityBatchBuilder<C: Context, R: DispatchCall<Context = C>> {
+    mempool: Mempool<C, R>,
+    policy: BatchPolicy,
     runtime: R,
-    max_batch_size_bytes: usize,
     current_storage: C::Storage,
     sequencer: C::Address,
 }
 
-impl<C, R> FiFoStrictBatchBuilder<C, R>
+impl<C, R> PriorityBatchBuilder<C, R>
 where
     C: Context,
     R: DispatchCall<Context = C>,
 {
     /// BatchBuilder constructor.
     pub fn new(
-        max_batch_size_bytes: usize,
-        mempool_max_txs_count: usize,
+        policy: BatchPolicy,
+        mempool_config: MempoolConfig,
         runtime: R,
         current_storage: C::Storage,
         sequencer: C::Address,
     ) -> Self {
         Self {
-            mempool: VecDeque::new(),
-            mempool_max_txs_count,
-            max_batch_size_bytes,
+            mempool: Mempool::new(mempool_config),
+            policy,
             runtime,
             current_storage,
             sequencer,
         }
     }
-}
 
-impl<C, R> BatchBuilder for FiFoStrictBatchBuilder<C, R>
-where
-    C: Context,
-    R: DispatchCall<Context = C>,
-{
-    /// Attempt to add transaction to the mempool.
-    ///
-    /// The transaction is discarded if:
-    /// - mempool is full
-    /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
-    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<()> {
-        if self.mempool.len() >= self.mempool_max_txs_count {
-            bail!("Mempool is full")
-        }
-
-        if raw.len() > self.max_batch_size_bytes {
-            bail!(
-                "Transaction too big. Max allowed size: {}",
-                self.max_batch_size_bytes
-            )
-        }
-
-        // Deserialize
-        let mut data = Cursor::new(&raw);
-        let tx = Transaction::<C>::deserialize_reader(&mut data)
-            .context("Failed to deserialize transaction")?;
-
-        // Verify
-        tx.verify().context("Failed to verify transaction")?;
-
-        // Decode
-        let msg = R::decode_call(tx.runtime_msg())
-            .map_err(anyhow::Error::new)
-            .context("Failed to decode message in transaction")?;
-
-        self.mempool.push_back(PooledTransaction {
-            raw,
-            tx,
-            msg: Some(msg),
-        });
-        Ok(())
+    fn update_mempool_metrics(&self) {
+        SEQUENCER_MEMPOOL_TXS.set(self.mempool.len() as i64);
+        SEQUENCER_MEMPOOL_SIZE_BYTES.set(self.mempool.size_bytes() as i64);
     }
 
-    /// Builds a new batch of valid transactions in order they were added to mempool
-    /// Only transactions, which are dispatched successfully are included in the batch
-    fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+    /// Builds a new batch of valid transactions, whose sizes sum to at most `max_size_bytes` bytes.
+    fn build_batch(&mut self, max_size_bytes: usize) -> anyhow::Result<Vec<Vec<u8>>> {
+        let deadline = Instant::now() + self.policy.deadline;
+        self.mempool.evict_expired(Instant::now());
+
         let mut working_set = WorkingSet::new(self.current_storage.clone());
         let mut txs = Vec::new();
         let mut current_batch_size = 0;
+        let mut current_batch_gas = 0u64;
+        let mut candidates = self.mempool.executable();
+
+        while let Some(hash) = self.mempool.pop_highest(&mut candidates) {
+            if Instant::now() >= deadline {
+                info!("Batch building deadline has been reached");
+                break;
+            }
+
+            let pooled = self
+                .mempool
+                .get_mut(&hash)
+                .expect("Candidates are pooled transactions");
+
+            // In order to fill batch as big as possible, the transactions which don't fit
+            // are skipped, with the rest of their nonce chain.
+            let tx_len = pooled.raw.len();
+            let tx_gas = pooled.tx.gas_limit();
+            if current_batch_size + tx_len > max_size_bytes
+                || current_batch_gas.saturating_add(tx_gas) > self.policy.max_batch_gas
+            {
+                continue;
+            }
 
-        while let Some(mut pooled) = self.mempool.pop_front() {
             // Take the decoded runtime message cached upon accepting transaction
             // into the pool or attempt to decode the message again if
             // the transaction was previously executed,
-            // but discarded from the batch due to the batch size.
+            // but discarded from the batch due to the deadline.
             let msg = pooled.msg.take().unwrap_or_else(||
                     // SAFETY: The transaction was accepted into the pool,
-                    // so we know that the runtime message is valid. 
+                    // so we know that the runtime message is valid.
                     R::decode_call(pooled.tx.runtime_msg()).expect("noop; qed"));
 
             // Execute
             {
-                // TODO: Bug(!), because potential discrepancy. Should be resolved by https://github.com/Sovereign-Labs/sovereign-sdk/issues/434
-                let sender_address: C::Address = pooled.tx.pub_key().to_address();
+                let sender_address: C::Address = pooled.sender().clone();
                 // FIXME! This should use the correct height
                 let ctx = C::new(sender_address, self.sequencer.clone(), 0);
 
-                if let Err(error) = self.runtime.dispatch_call(msg, &mut working_set, &ctx) {
+                let mut savepoint = working_set.savepoint();
+                if let Err(error) = self.runtime.dispatch_call(msg, &mut savepoint, &ctx) {
+                    savepoint.rollback();
                     warn!(%error, tx = hex::encode(&pooled.raw), "Error during transaction dispatch");
+                    self.mempool.remove_with_descendants(&hash);
                     continue;
                 }
+                savepoint.commit();
             }
 
-            // In order to fill batch as big as possible, we only check if valid tx can fit in the batch.
-            let tx_len = pooled.raw.len();
-            if current_batch_size + tx_len > self.max_batch_size_bytes {
-                self.mempool.push_front(pooled);
-                break;
+            if let Some(next) = self.mempool.next_in_chain(&hash) {
+                candidates.push(next);
             }
+            let pooled = self
+                .mempool
+                .mark_included(&hash)
+                .expect("Candidates are pooled transactions");
 
-            // Update size of current batch
+            // Update size and gas of current batch
             current_batch_size += tx_len;
+            current_batch_gas = current_batch_gas.saturating_add(tx_gas);
 
-            let tx_hash: [u8; 32] = pooled.calculate_hash();
             info!(
-                hash = hex::encode(tx_hash),
+                hash = hex::encode(hash),
                 "Transaction has been included in the batch",
             );
             txs.push(pooled.raw);
         }
 
+        self.update_mempool_metrics();
         if txs.is_empty() {
             bail!("No valid transactions are available");
         }
@@ -176,8 +154,74 @@ where
     }
 }
 
+impl<C, R> BatchBuilder for PriorityBatchBuilder<C, R>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+{
+    /// Attempt to add transaction to the mempool.
+    ///
+    /// The transaction is discarded if:
+    /// - it doesn't fit in a batch
+    /// - mempool is full of transactions with higher gas tips
+    /// - it reuses the nonce of a pooled transaction without raising the gas tip enough
+    /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
+    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<()> {
+        if raw.len() > self.policy.max_batch_size_bytes {
+            bail!(
+                "Transaction too big. Max allowed size: {}",
+                self.policy.max_batch_size_bytes
+            )
+        }
+
+        // Deserialize
+        let mut data = Cursor::new(&raw);
+        let tx = Transaction::<C>::deserialize_reader(&mut data)
+            .context("Failed to deserialize transaction")?;
+
+        if tx.gas_limit() > self.policy.max_batch_gas {
+            bail!(
+                "Transaction gas limit too high. Max allowed gas: {}",
+                self.policy.max_batch_gas
+            )
+        }
+
+        // Verify
+        tx.verify().context("Failed to verify transaction")?;
+
+        // Decode
+        let msg = R::decode_call(tx.runtime_msg())
+            .map_err(anyhow::Error::new)
+            .context("Failed to decode message in transaction")?;
+
+        self.mempool.insert(PooledTransaction::new(raw, tx, msg))?;
+        self.update_mempool_metrics();
+        Ok(())
+    }
+
+    /// Builds a new batch of valid transactions by decreasing gas tip, keeping the transactions of each sender
+    /// in the order of their nonces. Only transactions which are dispatched successfully are included in the batch.
+    /// A transaction which fails is dropped with the following transactions of its nonce chain.
+    fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        self.build_batch(self.policy.max_batch_size_bytes)
+    }
+
+    /// Builds a new batch like [`BatchBuilder::get_next_blob`], of at most `max_size_bytes` bytes,
+    /// or of at most the `max_batch_size_bytes` of the [`BatchPolicy`] if it's lower.
+    fn get_next_blob_with_max_size(
+        &mut self,
+        max_size_bytes: usize,
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        self.build_batch(max_size_bytes.min(self.policy.max_batch_size_bytes))
+    }
+
+    fn mempool_txs(&self) -> Vec<MempoolTx> {
+        self.mempool.describe()
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use borsh::BorshSerialize;
     use rand::Rng;
     use sov_modules_api::default_context::DefaultContext;
@@ -186,7 +230,7 @@ mod tests {
     use sov_modules_api::macros::DefaultRuntime;
     use sov_modules_api::transaction::Transaction;
     use sov_modules_api::{
-        Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, PrivateKey,
+        Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, PrivateKey, PublicKey,
     };
     use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
     use sov_rollup_interface::services::batch_builder::BatchBuilder;
@@ -201,7 +245,7 @@ mod tests {
 
     #[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
     #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
-    struct TestRuntime<T: Context> {
+    pub(crate) struct TestRuntime<T: Context> {
         value_setter: sov_value_setter::ValueSetter<T>,
     }
 
@@ -209,16 +253,15 @@ mod tests {
         let private_key = DefaultPrivateKey::generate();
         let mut rng = rand::thread_rng();
         let value: u32 = rng.gen();
-        generate_valid_tx(&private_key, value)
+        generate_valid_tx(&private_key, value, 1)
     }
 
-    fn generate_valid_tx(private_key: &DefaultPrivateKey, value: u32) -> Vec<u8> {
+    fn generate_valid_tx(private_key: &DefaultPrivateKey, value: u32, nonce: u64) -> Vec<u8> {
         let msg = CallMessage::SetValue(value);
         let msg = <TestRuntime<C> as EncodeCall<ValueSetter<DefaultContext>>>::encode_call(msg);
         let chain_id = 0;
         let gas_tip = 0;
         let gas_limit = 0;
-        let nonce = 1;
 
         Transaction::<DefaultContext>::new_signed_tx(
             private_key,
@@ -232,6 +275,30 @@ mod tests {
         .unwrap()
     }
 
+    fn generate_tx_in_lane(
+        private_key: &DefaultPrivateKey,
+        value: u32,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        let msg = CallMessage::SetValue(value);
+        let msg = <TestRuntime<C> as EncodeCall<ValueSetter<DefaultContext>>>::encode_call(msg);
+
+        Transaction::<DefaultContext>::new_signed_tx_in_lane(
+            private_key,
+            msg,
+            0,
+            gas_tip,
+            gas_limit,
+            nonce_lane,
+            nonce,
+        )
+        .try_to_vec()
+        .unwrap()
+    }
+
     fn generate_random_bytes() -> Vec<u8> {
         let mut rng = rand::thread_rng();
 
@@ -263,15 +330,23 @@ mod tests {
         batch_size_bytes: usize,
         tmpdir: &TempDir,
     ) -> (
-        FiFoStrictBatchBuilder<C, TestRuntime<C>>,
+        PriorityBatchBuilder<C, TestRuntime<C>>,
         ProverStorage<DefaultStorageSpec, SnapshotManager>,
     ) {
         let storage = new_orphan_storage(tmpdir.path()).unwrap();
 
         let sequencer = Address::from([0; 32]);
-        let batch_builder = FiFoStrictBatchBuilder::new(
-            batch_size_bytes,
-            MAX_TX_POOL_SIZE,
+        let policy = BatchPolicy {
+            max_batch_size_bytes: batch_size_bytes,
+            ..Default::default()
+        };
+        let mempool_config = MempoolConfig {
+            max_txs_count: MAX_TX_POOL_SIZE,
+            ..Default::default()
+        };
+        let batch_builder = PriorityBatchBuilder::new(
+            policy,
+            mempool_config,
             TestRuntime::<C>::default(),
             storage.clone(),
             sequencer,
@@ -382,7 +457,10 @@ mod tests {
 
             let tmpdir = tempfile::tempdir().unwrap();
             let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
-            batch_builder.mempool_max_txs_count = 0;
+            batch_builder.mempool = Mempool::new(MempoolConfig {
+                max_txs_count: 0,
+                ..Default::default()
+            });
 
             let accept_result = batch_builder.accept_tx(tx);
             assert!(accept_result.is_err());
@@ -412,8 +490,8 @@ mod tests {
             let value_setter_admin = DefaultPrivateKey::generate();
             let txs = [
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 1),
-                generate_valid_tx(&value_setter_admin, 2),
+                generate_valid_tx(&value_setter_admin, 1, 1),
+                generate_valid_tx(&value_setter_admin, 2, 2),
             ];
 
             let tmpdir = tempfile::tempdir().unwrap();
@@ -440,13 +518,13 @@ mod tests {
             let value_setter_admin = DefaultPrivateKey::generate();
             let txs = [
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 1),
+                generate_valid_tx(&value_setter_admin, 1, 1),
                 // Should be rejected, not admin
                 generate_random_valid_tx(),
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 2),
+                generate_valid_tx(&value_setter_admin, 2, 2),
                 // Should be skipped, more than batch size
-                generate_valid_tx(&value_setter_admin, 3),
+                generate_valid_tx(&value_setter_admin, 3, 3),
             ];
 
             let tmpdir = tempfile::tempdir().unwrap();
@@ -469,5 +547,114 @@ mod tests {
             assert!(!blob.contains(&txs[3]));
             assert_eq!(1, batch_builder.mempool.len());
         }
+
+        #[test]
+        fn builds_batch_by_gas_tip_in_nonce_order() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_tx_in_lane(&value_setter_admin, 1, 1, 0, 0, 1),
+                generate_tx_in_lane(&value_setter_admin, 2, 100, 0, 0, 2),
+                generate_tx_in_lane(&value_setter_admin, 3, 50, 0, 1, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+            let mempool_txs = batch_builder.mempool_txs();
+            let nonces: Vec<(u64, u64)> = mempool_txs
+                .iter()
+                .map(|tx| (tx.nonce_lane, tx.nonce))
+                .collect();
+            assert_eq!(vec![(1, 1), (0, 1), (0, 2)], nonces);
+
+            // The second transaction of the lane 0 has the highest gas tip, but waits for the first one
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(vec![txs[2].clone(), txs[0].clone(), txs[1].clone()], blob);
+            assert!(batch_builder.mempool.is_empty());
+        }
+
+        #[test]
+        fn builds_batch_under_gas_limit() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_tx_in_lane(&value_setter_admin, 1, 0, 10, 0, 1),
+                generate_tx_in_lane(&value_setter_admin, 2, 0, 10, 1, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            batch_builder.policy.max_batch_gas = 15;
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            let too_much_gas = generate_tx_in_lane(&value_setter_admin, 3, 0, 20, 2, 1);
+            assert_eq!(
+                "Transaction gas limit too high. Max allowed gas: 15",
+                batch_builder
+                    .accept_tx(too_much_gas)
+                    .unwrap_err()
+                    .to_string()
+            );
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            assert_eq!(vec![txs[0].clone()], batch_builder.get_next_blob().unwrap());
+            assert_eq!(vec![txs[1].clone()], batch_builder.get_next_blob().unwrap());
+        }
+
+        #[test]
+        fn builds_batch_under_max_size() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx(&value_setter_admin, 1, 1),
+                generate_valid_tx(&value_setter_admin, 2, 2),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            let max_size = txs[0].len() + txs[1].len() - 1;
+            assert_eq!(
+                vec![txs[0].clone()],
+                batch_builder.get_next_blob_with_max_size(max_size).unwrap()
+            );
+            assert_eq!(
+                vec![txs[1].clone()],
+                batch_builder.get_next_blob_with_max_size(max_size).unwrap()
+            );
+        }
+
+        #[test]
+        fn failed_tx_drops_its_nonce_chain() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let other = DefaultPrivateKey::generate();
+            let txs = [
+                // Should be rejected, not admin
+                generate_valid_tx(&other, 1, 1),
+                // Can't be executed after the failure of the previous nonce
+                generate_valid_tx(&other, 2, 2),
+                generate_valid_tx(&value_setter_admin, 3, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            assert_eq!(vec![txs[2].clone()], batch_builder.get_next_blob().unwrap());
+            assert!(batch_builder.mempool.is_empty());
+        }
     }
 }
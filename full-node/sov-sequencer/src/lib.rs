@@ -4,31 +4,114 @@ use std::sync::Mutex;
 
 /// Concrete implementations of `[BatchBuilder]`
 pub mod batch_builder;
+/// Fee-prioritized mempool used by the [`batch_builder::PriorityBatchBuilder`]
+pub mod mempool;
+mod metrics;
 /// Utilities for the sequencer rpc
 pub mod utils;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
-use sov_rollup_interface::services::batch_builder::BatchBuilder;
-use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::services::batch_builder::{BatchBuilder, MempoolTx};
+use sov_rollup_interface::services::da::{DaService, Fee};
+
+use crate::metrics::SEQUENCER_BATCH_SUBMISSION_LATENCY_SECONDS;
 
 const SEQUENCER_RPC_ERROR: &str = "SEQUENCER_RPC_ERROR";
 
+/// How the [`Sequencer`] sizes its batches according to the fees of the DA layer,
+/// estimated with [`DaService::estimate_fee`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeePolicy {
+    /// The maximum fee paid to post a batch. The fees aren't estimated if it is unset.
+    pub max_batch_fee: Option<Fee>,
+    /// The size of the smallest batch worth posting, in bytes. When even such a batch costs more
+    /// than `max_batch_fee`, the sequencer waits for the fees to drop, and keeps the transactions in the mempool.
+    pub min_batch_size_bytes: usize,
+    /// The size of the batches posted while the fees allow it, in bytes.
+    pub max_batch_size_bytes: usize,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self {
+            max_batch_fee: None,
+            min_batch_size_bytes: 0,
+            max_batch_size_bytes: 1024 * 100,
+        }
+    }
+}
+
 /// Single data structure that manages mempool and batch producing.
 pub struct Sequencer<B: BatchBuilder, T: DaService> {
     batch_builder: Mutex<B>,
     da_service: T,
+    fee_policy: FeePolicy,
 }
 
 impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T> {
     /// Creates new Sequencer from BatchBuilder and DaService
     pub fn new(batch_builder: B, da_service: T) -> Self {
+        Self::with_fee_policy(batch_builder, da_service, FeePolicy::default())
+    }
+
+    /// Creates new Sequencer from BatchBuilder and DaService, which sizes its batches according to `fee_policy`.
+    pub fn with_fee_policy(batch_builder: B, da_service: T, fee_policy: FeePolicy) -> Self {
         Self {
             batch_builder: Mutex::new(batch_builder),
             da_service,
+            fee_policy,
+        }
+    }
+
+    /// Returns the size of the largest batch, up to `max_batch_size_bytes`, which can be posted
+    /// for at most `max_batch_fee`, or fails if even a batch of `min_batch_size_bytes` costs more.
+    async fn affordable_batch_size(&self) -> anyhow::Result<usize> {
+        let policy = &self.fee_policy;
+        let Some(max_batch_fee) = policy.max_batch_fee else {
+            return Ok(policy.max_batch_size_bytes);
+        };
+
+        if self
+            .is_affordable(policy.max_batch_size_bytes, max_batch_fee)
+            .await?
+        {
+            return Ok(policy.max_batch_size_bytes);
+        }
+        if !self
+            .is_affordable(policy.min_batch_size_bytes, max_batch_fee)
+            .await?
+        {
+            bail!(
+                "A batch of {} bytes costs more than {} on the DA layer, waiting for DA fees to drop",
+                policy.min_batch_size_bytes,
+                max_batch_fee
+            );
+        }
+
+        // The fees grow with the size of the batch, so the largest affordable size is found by bisection
+        let (mut affordable, mut too_expensive) =
+            (policy.min_batch_size_bytes, policy.max_batch_size_bytes);
+        while too_expensive - affordable > 1 {
+            let size = affordable + (too_expensive - affordable) / 2;
+            if self.is_affordable(size, max_batch_fee).await? {
+                affordable = size;
+            } else {
+                too_expensive = size;
+            }
         }
+        Ok(affordable)
+    }
+
+    async fn is_affordable(&self, batch_size: usize, max_batch_fee: Fee) -> anyhow::Result<bool> {
+        let fee = self
+            .da_service
+            .estimate_fee(batch_size)
+            .await
+            .map_err(|e| anyhow!("failed to estimate DA fee: {:?}", e))?;
+        Ok(fee <= max_batch_fee)
     }
 
     async fn submit_batch(&self) -> anyhow::Result<usize> {
@@ -37,17 +120,21 @@ impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T>
         // It can be improved with atomics,
         // so a new batch is only created after previous was submitted.
         tracing::info!("Submit batch request has been received!");
+        let max_batch_size = self.affordable_batch_size().await?;
         let blob = {
             let mut batch_builder = self
                 .batch_builder
                 .lock()
                 .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
-            batch_builder.get_next_blob()?
+            batch_builder.get_next_blob_with_max_size(max_batch_size)?
         };
         let num_txs = blob.len();
         let blob: Vec<u8> = borsh::to_vec(&blob)?;
 
-        match self.da_service.send_transaction(&blob).await {
+        let submission_timer = SEQUENCER_BATCH_SUBMISSION_LATENCY_SECONDS.start_timer();
+        let result = self.da_service.send_transaction(&blob).await;
+        submission_timer.observe_duration();
+        match result {
             Ok(_) => Ok(num_txs),
             Err(e) => Err(anyhow!("failed to submit batch: {:?}", e)),
         }
@@ -62,6 +149,14 @@ impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T>
         batch_builder.accept_tx(tx)?;
         Ok(())
     }
+
+    fn mempool_txs(&self) -> anyhow::Result<Vec<MempoolTx>> {
+        let batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        Ok(batch_builder.mempool_txs())
+    }
 }
 
 fn register_txs_rpc_methods<B, D>(
@@ -96,6 +191,11 @@ where
         };
         Ok::<_, ErrorObjectOwned>(response)
     })?;
+    rpc.register_method("sequencer_getMempool", move |_, sequencer| {
+        sequencer
+            .mempool_txs()
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
 
     Ok(())
 }
@@ -106,7 +206,20 @@ where
     B: BatchBuilder + Send + Sync + 'static,
     D: DaService,
 {
-    let sequencer = Sequencer::new(batch_builder, da_service);
+    get_sequencer_rpc_with_fee_policy(batch_builder, da_service, FeePolicy::default())
+}
+
+/// Creates an RPC module with the methods of a sequencer sizing its batches according to `fee_policy`
+pub fn get_sequencer_rpc_with_fee_policy<B, D>(
+    batch_builder: B,
+    da_service: D,
+    fee_policy: FeePolicy,
+) -> RpcModule<Sequencer<B, D>>
+where
+    B: BatchBuilder + Send + Sync + 'static,
+    D: DaService,
+{
+    let sequencer = Sequencer::with_fee_policy(batch_builder, da_service, fee_policy);
     let mut rpc = RpcModule::new(sequencer);
     register_txs_rpc_methods::<B, D>(&mut rpc).expect("Failed to register sequencer RPC methods");
     rpc
@@ -172,6 +285,35 @@ mod tests {
                 .collect();
             Ok(txs)
         }
+
+        // Takes the transactions while their first bytes fit in `max_size_bytes`
+        fn get_next_blob_with_max_size(
+            &mut self,
+            max_size_bytes: usize,
+        ) -> anyhow::Result<Vec<Vec<u8>>> {
+            let rest = self
+                .mempool
+                .split_off(max_size_bytes.min(self.mempool.len()));
+            let blob = self.get_next_blob();
+            self.mempool = rest;
+            blob
+        }
+
+        fn mempool_txs(&self) -> Vec<MempoolTx> {
+            self.mempool
+                .iter()
+                .map(|tx| MempoolTx {
+                    hash: [0; 32],
+                    sender: hex::encode(tx),
+                    nonce_lane: 0,
+                    nonce: 0,
+                    gas_tip: 0,
+                    gas_limit: 0,
+                    size: tx.len(),
+                    age_secs: 0,
+                })
+                .collect()
+        }
     }
 
     #[tokio::test]
@@ -239,6 +381,81 @@ mod tests {
         assert_eq!(expected, block_data);
     }
 
+    #[tokio::test]
+    async fn test_get_mempool() {
+        let batch_builder = MockBatchBuilder { mempool: vec![] };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = get_sequencer_rpc(batch_builder, da_service);
+
+        let tx: Vec<u8> = vec![1, 2, 3];
+        let request = SubmitTransaction { body: tx.clone() };
+        let _: SubmitTransactionResponse = rpc.call("sequencer_acceptTx", [request]).await.unwrap();
+
+        let arg: &[u8] = &[];
+        let mempool: Vec<MempoolTx> = rpc.call("sequencer_getMempool", arg).await.unwrap();
+        assert_eq!(1, mempool.len());
+        assert_eq!(hex::encode(&tx), mempool[0].sender);
+        assert_eq!(tx.len(), mempool[0].size);
+    }
+
+    #[tokio::test]
+    async fn test_submit_shrinks_batch_to_fee_budget() {
+        let txs = vec![vec![1], vec![2], vec![3], vec![4]];
+        let batch_builder = MockBatchBuilder {
+            mempool: txs.clone(),
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        da_service.set_fee_per_byte(10);
+        let fee_policy = FeePolicy {
+            max_batch_fee: Some(Fee(25)),
+            min_batch_size_bytes: 1,
+            max_batch_size_bytes: 4,
+        };
+        let rpc = get_sequencer_rpc_with_fee_policy(batch_builder, da_service.clone(), fee_policy);
+
+        let arg: &[u8] = &[];
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let block_data = submitted_block.blobs[0].full_data();
+
+        // Only 2 bytes are affordable
+        let expected: Vec<u8> = borsh::to_vec(&txs[..2]).unwrap();
+        assert_eq!(expected, block_data);
+        let mempool: Vec<MempoolTx> = rpc.call("sequencer_getMempool", arg).await.unwrap();
+        assert_eq!(2, mempool.len());
+    }
+
+    #[tokio::test]
+    async fn test_submit_waits_for_fees_to_drop() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1], vec![2]],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        da_service.set_fee_per_byte(100);
+        let fee_policy = FeePolicy {
+            max_batch_fee: Some(Fee(50)),
+            min_batch_size_bytes: 1,
+            max_batch_size_bytes: 2,
+        };
+        let rpc = get_sequencer_rpc_with_fee_policy(batch_builder, da_service.clone(), fee_policy);
+
+        let arg: &[u8] = &[];
+        let result: Result<String, jsonrpsee::core::Error> =
+            rpc.call("sequencer_publishBatch", arg).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("waiting for DA fees to drop"));
+        let mempool: Vec<MempoolTx> = rpc.call("sequencer_getMempool", arg).await.unwrap();
+        assert_eq!(2, mempool.len());
+
+        da_service.set_fee_per_byte(10);
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        let mempool: Vec<MempoolTx> = rpc.call("sequencer_getMempool", arg).await.unwrap();
+        assert!(mempool.is_empty());
+    }
+
     #[tokio::test]
     #[ignore = "TBD"]
     async fn test_full_flow() {}
@@ -0,0 +1,566 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, DispatchCall, PublicKey, Spec};
+use sov_rollup_interface::services::batch_builder::MempoolTx;
+use tracing::debug;
+
+/// The hash of a raw transaction.
+pub type TxHash = [u8; 32];
+
+/// The limits of the [`Mempool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolConfig {
+    /// The maximum number of transactions in the mempool.
+    pub max_txs_count: usize,
+    /// The maximum sum of the sizes of the transactions in the mempool, in bytes.
+    pub max_size_bytes: usize,
+    /// The time after which a transaction which wasn't included in a batch is evicted.
+    pub max_tx_age: Duration,
+    /// The minimum increase of the gas tip, in percent, for a transaction to replace
+    /// the pooled transaction with the same sender, nonce lane and nonce.
+    pub replacement_tip_bump_percent: u64,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            max_txs_count: 10_000,
+            max_size_bytes: 64 * 1024 * 1024,
+            max_tx_age: Duration::from_secs(3 * 60 * 60),
+            replacement_tip_bump_percent: 10,
+        }
+    }
+}
+
+/// Transaction stored in the mempool.
+pub struct PooledTransaction<C: Context, R: DispatchCall<Context = C>> {
+    /// Raw transaction bytes.
+    pub(crate) raw: Vec<u8>,
+    /// Deserialized transaction.
+    pub(crate) tx: Transaction<C>,
+    /// The decoded runtime message, cached during initial verification.
+    pub(crate) msg: Option<R::Decodable>,
+    /// The address of the signer of the transaction.
+    sender: C::Address,
+    /// The hash of the raw transaction.
+    hash: TxHash,
+    /// The arrival order of the transaction, which breaks ties between equal gas tips.
+    seq: u64,
+    /// The time at which the transaction was accepted.
+    accepted_at: Instant,
+}
+
+impl<C, R> std::fmt::Debug for PooledTransaction<C, R>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledTransaction")
+            .field("raw", &hex::encode(&self.raw))
+            .field("tx", &self.tx)
+            .finish()
+    }
+}
+
+impl<C, R> PooledTransaction<C, R>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+{
+    /// Wraps a verified transaction and its decoded runtime message.
+    pub(crate) fn new(raw: Vec<u8>, tx: Transaction<C>, msg: R::Decodable) -> Self {
+        // TODO: Bug(!), because potential discrepancy. Should be resolved by https://github.com/Sovereign-Labs/sovereign-sdk/issues/434
+        let sender = tx.pub_key().to_address();
+        let hash = <C as Spec>::Hasher::digest(&raw[..]).into();
+        Self {
+            raw,
+            tx,
+            msg: Some(msg),
+            sender,
+            hash,
+            seq: 0,
+            accepted_at: Instant::now(),
+        }
+    }
+
+    /// The hash of the raw transaction.
+    pub fn hash(&self) -> TxHash {
+        self.hash
+    }
+
+    /// The address of the signer of the transaction.
+    pub fn sender(&self) -> &C::Address {
+        &self.sender
+    }
+
+    fn chain_key(&self) -> ChainKey<C> {
+        (self.sender.clone(), self.tx.nonce_lane())
+    }
+
+    /// Orders the transactions by decreasing gas tip, then by arrival.
+    fn priority(&self) -> (u64, std::cmp::Reverse<u64>) {
+        (self.tx.gas_tip(), std::cmp::Reverse(self.seq))
+    }
+}
+
+/// The sender and the nonce lane of a nonce chain.
+type ChainKey<C> = (<C as Spec>::Address, u64);
+
+/// The pooled transactions of a sender in one of its nonce lanes.
+struct NonceChain {
+    /// The hashes of the pooled transactions, by nonce.
+    txs: BTreeMap<u64, TxHash>,
+    /// The nonce of the last transaction of the chain included in a batch.
+    last_included: Option<u64>,
+}
+
+impl NonceChain {
+    /// Returns the transaction which can be executed next, if the chain has no gap before it.
+    fn head(&self) -> Option<(u64, TxHash)> {
+        let (nonce, hash) = self.txs.first_key_value()?;
+        match self.last_included {
+            Some(last) if last.checked_add(1) != Some(*nonce) => None,
+            _ => Some((*nonce, *hash)),
+        }
+    }
+}
+
+/// A mempool ordering transactions by gas tip, while keeping the transactions of each sender and nonce lane
+/// in the order of their nonces.
+///
+/// - A transaction is only executable once the transactions with the previous nonces of its chain
+///   were included in a batch.
+/// - A transaction replaces the pooled transaction with the same nonce if it raises the gas tip by at least
+///   [`MempoolConfig::replacement_tip_bump_percent`].
+/// - When the mempool is full, the transaction with the lowest priority among the last transactions of the
+///   chains is evicted, if the new transaction has a higher gas tip.
+/// - Transactions older than [`MempoolConfig::max_tx_age`] are evicted.
+pub struct Mempool<C: Context, R: DispatchCall<Context = C>> {
+    config: MempoolConfig,
+    txs: HashMap<TxHash, PooledTransaction<C, R>>,
+    chains: HashMap<ChainKey<C>, NonceChain>,
+    size_bytes: usize,
+    next_seq: u64,
+}
+
+impl<C, R> Mempool<C, R>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+{
+    /// Creates an empty mempool.
+    pub fn new(config: MempoolConfig) -> Self {
+        Self {
+            config,
+            txs: HashMap::new(),
+            chains: HashMap::new(),
+            size_bytes: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// The limits of the mempool.
+    pub fn config(&self) -> &MempoolConfig {
+        &self.config
+    }
+
+    /// The number of pooled transactions.
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    /// Returns true if there are no pooled transactions.
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    /// The sum of the sizes of the pooled transactions, in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+
+    /// Returns the pooled transaction `hash`.
+    pub fn get(&self, hash: &TxHash) -> Option<&PooledTransaction<C, R>> {
+        self.txs.get(hash)
+    }
+
+    pub(crate) fn get_mut(&mut self, hash: &TxHash) -> Option<&mut PooledTransaction<C, R>> {
+        self.txs.get_mut(hash)
+    }
+
+    /// Removes and returns the transaction with the highest priority among `candidates`.
+    pub fn pop_highest(&self, candidates: &mut Vec<TxHash>) -> Option<TxHash> {
+        let (index, _) = candidates
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, hash)| self.txs[*hash].priority())?;
+        Some(candidates.swap_remove(index))
+    }
+
+    /// Adds a transaction, replacing the pooled transaction with the same nonce or evicting
+    /// lower priority transactions if needed.
+    pub fn insert(&mut self, mut pooled: PooledTransaction<C, R>) -> anyhow::Result<()> {
+        self.evict_expired(Instant::now());
+
+        if self.txs.contains_key(&pooled.hash) {
+            bail!("Transaction is already in the mempool")
+        }
+        if pooled.raw.len() > self.config.max_size_bytes {
+            bail!("Mempool is full")
+        }
+
+        let key = pooled.chain_key();
+        let nonce = pooled.tx.nonce();
+        let gas_tip = pooled.tx.gas_tip();
+        let replaced = match self.chains.get(&key) {
+            Some(chain) => {
+                if matches!(chain.last_included, Some(last) if nonce <= last) {
+                    bail!("Nonce {} was already included in a batch", nonce)
+                }
+                chain.txs.get(&nonce).copied()
+            }
+            None => None,
+        };
+
+        if let Some(replaced) = replaced {
+            let current_tip = self.txs[&replaced].tx.gas_tip();
+            let min_tip = current_tip as u128
+                * (100 + self.config.replacement_tip_bump_percent as u128)
+                / 100;
+            if gas_tip as u128 <= current_tip as u128 || (gas_tip as u128) < min_tip {
+                bail!(
+                    "Replacement transaction underpriced. Minimum gas tip: {}",
+                    min_tip.max(current_tip as u128 + 1)
+                )
+            }
+        }
+
+        // Collect the evictions first, so that a rejected transaction leaves the mempool untouched.
+        let mut evicted = Vec::new();
+        let mut count = self.txs.len() - usize::from(replaced.is_some());
+        let mut size = self.size_bytes - replaced.map_or(0, |hash| self.txs[&hash].raw.len());
+        while count >= self.config.max_txs_count
+            || size + pooled.raw.len() > self.config.max_size_bytes
+        {
+            let Some(victim) = self.eviction_candidate(&pooled, &evicted, replaced.as_ref()) else {
+                bail!("Mempool is full")
+            };
+            let victim_tx = &self.txs[&victim];
+            if victim_tx.tx.gas_tip() >= gas_tip {
+                bail!("Mempool is full")
+            }
+            count -= 1;
+            size -= victim_tx.raw.len();
+            evicted.push(victim);
+        }
+
+        for hash in evicted.into_iter().chain(replaced) {
+            debug!(
+                hash = hex::encode(hash),
+                "Transaction has been evicted from the mempool"
+            );
+            self.remove(&hash);
+        }
+
+        pooled.seq = self.next_seq;
+        self.next_seq += 1;
+        self.size_bytes += pooled.raw.len();
+        self.chains
+            .entry(key)
+            .or_insert_with(|| NonceChain {
+                txs: BTreeMap::new(),
+                last_included: None,
+            })
+            .txs
+            .insert(nonce, pooled.hash);
+        self.txs.insert(pooled.hash, pooled);
+        Ok(())
+    }
+
+    /// Removes the transaction `hash`.
+    pub fn remove(&mut self, hash: &TxHash) -> Option<PooledTransaction<C, R>> {
+        let pooled = self.txs.remove(hash)?;
+        self.size_bytes -= pooled.raw.len();
+        let key = pooled.chain_key();
+        if let Some(chain) = self.chains.get_mut(&key) {
+            chain.txs.remove(&pooled.tx.nonce());
+            if chain.txs.is_empty() {
+                self.chains.remove(&key);
+            }
+        }
+        Some(pooled)
+    }
+
+    /// Removes the transaction `hash` and the following transactions of its nonce chain,
+    /// which can't be executed without it.
+    pub fn remove_with_descendants(&mut self, hash: &TxHash) {
+        let Some(pooled) = self.txs.get(hash) else {
+            return;
+        };
+        let nonce = pooled.tx.nonce();
+        let descendants: Vec<TxHash> = self
+            .chains
+            .get(&pooled.chain_key())
+            .map(|chain| chain.txs.range(nonce..).map(|(_, hash)| *hash).collect())
+            .unwrap_or_default();
+        for hash in descendants {
+            self.remove(&hash);
+        }
+    }
+
+    /// Removes the transaction `hash`, which was included in a batch, and makes the next transaction of its
+    /// nonce chain executable.
+    pub fn mark_included(&mut self, hash: &TxHash) -> Option<PooledTransaction<C, R>> {
+        let pooled = self.remove(hash)?;
+        if let Some(chain) = self.chains.get_mut(&pooled.chain_key()) {
+            chain.last_included = Some(pooled.tx.nonce());
+        }
+        Some(pooled)
+    }
+
+    /// Evicts the transactions accepted more than [`MempoolConfig::max_tx_age`] before `now`.
+    pub fn evict_expired(&mut self, now: Instant) {
+        let expired: Vec<TxHash> = self
+            .txs
+            .values()
+            .filter(|pooled| {
+                now.saturating_duration_since(pooled.accepted_at) > self.config.max_tx_age
+            })
+            .map(|pooled| pooled.hash)
+            .collect();
+        for hash in expired {
+            debug!(hash = hex::encode(hash), "Transaction has expired");
+            self.remove(&hash);
+        }
+    }
+
+    /// Returns the executable transactions, by decreasing priority: the first transaction of each nonce
+    /// chain, if the transactions with the previous nonces were included in a batch.
+    pub fn executable(&self) -> Vec<TxHash> {
+        let mut heads: Vec<&PooledTransaction<C, R>> = self
+            .chains
+            .values()
+            .filter_map(|chain| chain.head())
+            .map(|(_, hash)| &self.txs[&hash])
+            .collect();
+        heads.sort_by_key(|pooled| std::cmp::Reverse(pooled.priority()));
+        heads.into_iter().map(|pooled| pooled.hash).collect()
+    }
+
+    /// Returns the transaction following `hash` in its nonce chain, if its nonce is the next one.
+    pub fn next_in_chain(&self, hash: &TxHash) -> Option<TxHash> {
+        let pooled = self.txs.get(hash)?;
+        let nonce = pooled.tx.nonce();
+        let chain = self.chains.get(&pooled.chain_key())?;
+        let (next_nonce, next) = chain.txs.range(nonce + 1..).next()?;
+        (*next_nonce == nonce + 1).then_some(*next)
+    }
+
+    /// Describes the pooled transactions, in the order they would be considered for the next batch
+    /// if they all were executed successfully.
+    pub fn describe(&self) -> Vec<MempoolTx> {
+        let now = Instant::now();
+        let mut ordered = Vec::with_capacity(self.txs.len());
+        let mut candidates = self.executable();
+        while let Some(hash) = self.pop_highest(&mut candidates) {
+            if let Some(next) = self.next_in_chain(&hash) {
+                candidates.push(next);
+            }
+            ordered.push(hash);
+        }
+        let ordered_set: HashSet<TxHash> = ordered.iter().copied().collect();
+        // The transactions blocked by a gap in their nonce chain come last.
+        let mut blocked: Vec<&PooledTransaction<C, R>> = self
+            .txs
+            .values()
+            .filter(|pooled| !ordered_set.contains(&pooled.hash))
+            .collect();
+        blocked.sort_by_key(|pooled| std::cmp::Reverse(pooled.priority()));
+        ordered.extend(blocked.into_iter().map(|pooled| pooled.hash));
+
+        ordered
+            .into_iter()
+            .map(|hash| {
+                let pooled = &self.txs[&hash];
+                MempoolTx {
+                    hash,
+                    sender: pooled.sender.to_string(),
+                    nonce_lane: pooled.tx.nonce_lane(),
+                    nonce: pooled.tx.nonce(),
+                    gas_tip: pooled.tx.gas_tip(),
+                    gas_limit: pooled.tx.gas_limit(),
+                    size: pooled.raw.len(),
+                    age_secs: now.saturating_duration_since(pooled.accepted_at).as_secs(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the last transaction of a nonce chain with the lowest priority, skipping the transactions
+    /// already selected for eviction, the transaction being replaced, and the transactions `new` depends on.
+    fn eviction_candidate(
+        &self,
+        new: &PooledTransaction<C, R>,
+        evicted: &[TxHash],
+        replaced: Option<&TxHash>,
+    ) -> Option<TxHash> {
+        let new_key = new.chain_key();
+        self.chains
+            .iter()
+            .filter_map(|(key, chain)| {
+                let min_nonce = if key == &new_key { new.tx.nonce() } else { 0 };
+                chain
+                    .txs
+                    .range(min_nonce..)
+                    .rev()
+                    .map(|(_, hash)| hash)
+                    .find(|hash| !evicted.contains(hash) && Some(*hash) != replaced)
+            })
+            .map(|hash| &self.txs[hash])
+            .min_by_key(|pooled| pooled.priority())
+            .map(|pooled| pooled.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::{EncodeCall, PrivateKey};
+    use sov_value_setter::{CallMessage, ValueSetter};
+
+    use super::*;
+    use crate::batch_builder::tests::TestRuntime;
+
+    type C = DefaultContext;
+    type TestMempool = Mempool<C, TestRuntime<C>>;
+
+    fn pooled_tx(
+        private_key: &DefaultPrivateKey,
+        gas_tip: u64,
+        nonce: u64,
+    ) -> PooledTransaction<C, TestRuntime<C>> {
+        let msg =
+            <TestRuntime<C> as EncodeCall<ValueSetter<C>>>::encode_call(CallMessage::SetValue(0));
+        let tx = Transaction::<C>::new_signed_tx(private_key, msg, 0, gas_tip, 0, nonce);
+        let raw = tx.try_to_vec().unwrap();
+        let msg = TestRuntime::<C>::decode_call(tx.runtime_msg()).unwrap();
+        PooledTransaction::new(raw, tx, msg)
+    }
+
+    fn config(max_txs_count: usize) -> MempoolConfig {
+        MempoolConfig {
+            max_txs_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn orders_by_gas_tip_within_nonce_chains() {
+        let alice = DefaultPrivateKey::generate();
+        let bob = DefaultPrivateKey::generate();
+        let mut mempool = TestMempool::new(config(10));
+
+        let alice_0 = pooled_tx(&alice, 1, 0);
+        let alice_1 = pooled_tx(&alice, 100, 1);
+        let bob_0 = pooled_tx(&bob, 50, 0);
+        let hashes = [alice_0.hash(), alice_1.hash(), bob_0.hash()];
+        // Inserted out of nonce order
+        mempool.insert(alice_1).unwrap();
+        mempool.insert(alice_0).unwrap();
+        mempool.insert(bob_0).unwrap();
+
+        assert_eq!(vec![hashes[2], hashes[0]], mempool.executable());
+        let ordered: Vec<TxHash> = mempool.describe().into_iter().map(|tx| tx.hash).collect();
+        assert_eq!(vec![hashes[2], hashes[0], hashes[1]], ordered);
+
+        mempool.mark_included(&hashes[0]);
+        assert_eq!(vec![hashes[1], hashes[2]], mempool.executable());
+        assert!(mempool.insert(pooled_tx(&alice, 1, 0)).is_err());
+    }
+
+    #[test]
+    fn gap_blocks_nonce_chain() {
+        let alice = DefaultPrivateKey::generate();
+        let mut mempool = TestMempool::new(config(10));
+
+        let alice_0 = pooled_tx(&alice, 1, 0);
+        let alice_2 = pooled_tx(&alice, 1, 2);
+        let hashes = [alice_0.hash(), alice_2.hash()];
+        mempool.insert(alice_0).unwrap();
+        mempool.insert(alice_2).unwrap();
+
+        mempool.mark_included(&hashes[0]);
+        assert!(mempool.executable().is_empty());
+
+        let alice_1 = pooled_tx(&alice, 1, 1);
+        let hash_1 = alice_1.hash();
+        mempool.insert(alice_1).unwrap();
+        assert_eq!(vec![hash_1], mempool.executable());
+        assert_eq!(Some(hashes[1]), mempool.next_in_chain(&hash_1));
+    }
+
+    #[test]
+    fn replacement_by_fee() {
+        let alice = DefaultPrivateKey::generate();
+        let mut mempool = TestMempool::new(config(10));
+        mempool.insert(pooled_tx(&alice, 100, 0)).unwrap();
+
+        let underpriced = mempool.insert(pooled_tx(&alice, 105, 0));
+        assert_eq!(
+            "Replacement transaction underpriced. Minimum gas tip: 110",
+            underpriced.unwrap_err().to_string()
+        );
+
+        let replacement = pooled_tx(&alice, 110, 0);
+        let hash = replacement.hash();
+        mempool.insert(replacement).unwrap();
+        assert_eq!(1, mempool.len());
+        assert_eq!(vec![hash], mempool.executable());
+    }
+
+    #[test]
+    fn full_mempool_evicts_lowest_priority() {
+        let alice = DefaultPrivateKey::generate();
+        let bob = DefaultPrivateKey::generate();
+        let mut mempool = TestMempool::new(config(2));
+
+        let alice_0 = pooled_tx(&alice, 100, 0);
+        let alice_1 = pooled_tx(&alice, 1, 1);
+        let alice_0_hash = alice_0.hash();
+        mempool.insert(alice_0).unwrap();
+        mempool.insert(alice_1).unwrap();
+
+        // Equal gas tips don't evict
+        let result = mempool.insert(pooled_tx(&bob, 1, 0));
+        assert_eq!("Mempool is full", result.unwrap_err().to_string());
+
+        // The last transaction of the chain is evicted, even though the first one has a higher gas tip
+        let bob_0 = pooled_tx(&bob, 50, 0);
+        let bob_0_hash = bob_0.hash();
+        mempool.insert(bob_0).unwrap();
+        assert_eq!(2, mempool.len());
+        assert_eq!(vec![alice_0_hash, bob_0_hash], mempool.executable());
+    }
+
+    #[test]
+    fn evicts_expired_transactions() {
+        let alice = DefaultPrivateKey::generate();
+        let mut mempool = TestMempool::new(config(10));
+        mempool.insert(pooled_tx(&alice, 1, 0)).unwrap();
+        assert_eq!(1, mempool.len());
+
+        mempool.evict_expired(Instant::now() + mempool.config().max_tx_age / 2);
+        assert_eq!(1, mempool.len());
+
+        mempool.evict_expired(Instant::now() + mempool.config().max_tx_age * 2);
+        assert!(mempool.is_empty());
+        assert_eq!(0, mempool.size_bytes());
+    }
+}
@@ -0,0 +1,31 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_int_gauge, Histogram, IntGauge,
+};
+
+pub static SEQUENCER_BATCH_SUBMISSION_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sov_sequencer_batch_submission_latency_seconds",
+        // metric description
+        "Time to submit a batch to the DA layer, in seconds",
+        exponential_buckets(/*start=*/ 0.01, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_MEMPOOL_TXS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "sov_sequencer_mempool_txs",
+        "Transactions waiting in the mempool"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_MEMPOOL_SIZE_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "sov_sequencer_mempool_size_bytes",
+        "Size of the transactions waiting in the mempool, in bytes"
+    )
+    .unwrap()
+});
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use sov_state::config::PruningMode;
 
 /// Configuration for StateTransitionRunner.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -12,6 +13,20 @@ pub struct RunnerConfig {
     pub start_height: u64,
     /// RPC configuration.
     pub rpc_config: RpcConfig,
+    /// The number of times the proof generation of a slot is attempted before its proof job fails.
+    #[serde(default = "default_max_proof_attempts")]
+    pub max_proof_attempts: u32,
+    /// The number of slots execution can run ahead of proving before it waits for the proofs.
+    #[serde(default = "default_max_proving_lag")]
+    pub max_proving_lag: u64,
+}
+
+fn default_max_proof_attempts() -> u32 {
+    crate::DEFAULT_MAX_PROOF_ATTEMPTS
+}
+
+fn default_max_proving_lag() -> u64 {
+    crate::DEFAULT_MAX_PROVING_LAG
 }
 
 /// RPC configuration.
@@ -21,6 +36,12 @@ pub struct RpcConfig {
     pub bind_host: String,
     /// RPC port.
     pub bind_port: u16,
+    /// Port of the REST gateway, served on `bind_host`. The gateway is disabled if unset.
+    #[serde(default)]
+    pub rest_bind_port: Option<u16>,
+    /// Port of the Prometheus metrics endpoint, served at `/metrics` on `bind_host`. The endpoint is disabled if unset.
+    #[serde(default)]
+    pub metrics_bind_port: Option<u16>,
 }
 
 /// Simple storage configuration
@@ -28,6 +49,9 @@ pub struct RpcConfig {
 pub struct StorageConfig {
     /// Path that can be utilized by concrete implementation
     pub path: PathBuf,
+    /// Which historical versions of the state, and slots of the ledger, are kept. Everything is kept by default.
+    #[serde(default)]
+    pub pruning: PruningMode,
 }
 
 /// Prover service configuration.
@@ -35,6 +59,29 @@ pub struct StorageConfig {
 pub struct ProverServiceConfig {
     /// The "distance"  measured in the number of blocks between two consecutive aggregated proofs.
     pub aggregated_proof_block_jump: u64,
+    /// The number of slots proven in parallel. Defaults to the number of CPUs minus one.
+    #[serde(default)]
+    pub num_workers: Option<usize>,
+}
+
+/// Configuration of the chain, shared by all the nodes and wallets of the rollup.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChainConfig {
+    /// The bech32 "Human-Readable Part" of the addresses of the rollup, e.g. `sov` for `sov1...` addresses.
+    #[serde(default = "default_address_prefix")]
+    pub address_prefix: String,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            address_prefix: default_address_prefix(),
+        }
+    }
+}
+
+fn default_address_prefix() -> String {
+    "sov".to_string()
 }
 
 /// Rollup Configuration
@@ -48,6 +95,9 @@ pub struct RollupConfig<DaServiceConfig> {
     pub da: DaServiceConfig,
     /// Prover service configuration.
     pub prover_service: ProverServiceConfig,
+    /// Chain configuration. The default one is used if the section is missing.
+    #[serde(default)]
+    pub chain: ChainConfig,
 }
 
 /// Reads toml file as a specific type.
@@ -89,6 +139,7 @@ mod tests {
             max_celestia_response_body_size = 980
             [storage]
             path = "/tmp"
+            pruning = { keep_recent = 1000 }
             [runner]
             start_height = 31337
             [runner.rpc_config]
@@ -96,6 +147,8 @@ mod tests {
             bind_port = 12345
             [prover_service]
             aggregated_proof_block_jump = 22
+            [chain]
+            address_prefix = "rol"
         "#;
 
         let config_file = create_config_from(config);
@@ -108,7 +161,11 @@ mod tests {
                 rpc_config: RpcConfig {
                     bind_host: "127.0.0.1".to_string(),
                     bind_port: 12345,
+                    rest_bind_port: None,
+                    metrics_bind_port: None,
                 },
+                max_proof_attempts: 3,
+                max_proving_lag: 16,
             },
 
             da: sov_celestia_adapter::CelestiaConfig {
@@ -116,12 +173,24 @@ mod tests {
                 celestia_rpc_address: "http://localhost:11111/".into(),
                 max_celestia_response_body_size: 980,
                 celestia_rpc_timeout_seconds: 60,
+                gas_price: 1.0,
+                gas_limit_multiplier: 1.0,
+                fee_bump_multiplier: 1.5,
+                inclusion_timeout_blocks: 5,
+                max_resubmissions: 3,
+                max_header_sync_lag: None,
+                light_node: None,
             },
             storage: StorageConfig {
                 path: PathBuf::from("/tmp"),
+                pruning: PruningMode::KeepRecent(1000),
             },
             prover_service: ProverServiceConfig {
                 aggregated_proof_block_jump: 22,
+                num_workers: None,
+            },
+            chain: ChainConfig {
+                address_prefix: "rol".to_string(),
             },
         };
         assert_eq!(config, expected);
@@ -0,0 +1,141 @@
+//! Posting of the aggregated proofs to the DA layer, and verification of the proofs posted by other parties.
+//!
+//! The proofs are posted to the proof namespace of the DA layer in the borsh encoding of [`Proof`],
+//! so that a full node can follow the chain by verifying the proofs instead of re-executing the slots.
+
+use std::marker::PhantomData;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::StoredVerifiedProof;
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{Proof, Zkvm};
+use tracing::{debug, info, warn};
+
+use crate::metrics::{DA_PROOFS_POSTED, DA_PROOFS_REJECTED, DA_PROOFS_VERIFIED};
+
+/// How often the DA layer is polled while the next height to check isn't finalized.
+const POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(1);
+
+/// Posts `proof` to the proof namespace of the DA layer and returns the DA height at which it was included.
+///
+/// Only full proofs are posted: a proof which only holds public input, when proving is skipped or simulated,
+/// can't be verified by other parties, so `None` is returned instead.
+pub async fn post_proof_to_da<Da>(da_service: &Da, proof: &Proof) -> anyhow::Result<Option<u64>>
+where
+    Da: DaService<Error = anyhow::Error>,
+{
+    if let Proof::PublicInput(_) = proof {
+        return Ok(None);
+    }
+
+    let blob = proof.try_to_vec()?;
+    let height = da_service.send_aggregated_zk_proof(&blob).await?;
+    DA_PROOFS_POSTED.inc();
+    Ok(Some(height))
+}
+
+/// Checks the proofs posted to the DA layer by other parties against the code commitment of the
+/// aggregation program, and records the accepted ones in the [`LedgerDB`], where they are served by
+/// the `ledger_getVerifiedProofs` RPC method.
+///
+/// A posted proof is accepted if it is a full proof which `Vm` verifies against the code commitment.
+/// The other blobs of the proof namespace, e.g. malformed or invalid proofs, are rejected and skipped.
+/// Only finalized DA heights are checked, so that an accepted proof can't be dropped by a reorg.
+pub struct DaProofVerifier<Da: DaService, Vm: Zkvm> {
+    da_service: Da,
+    ledger_db: LedgerDB,
+    code_commitment: Vm::CodeCommitment,
+    phantom: PhantomData<Vm>,
+}
+
+impl<Da, Vm> DaProofVerifier<Da, Vm>
+where
+    Da: DaService<Error = anyhow::Error>,
+    Vm: Zkvm,
+{
+    /// Creates a new verifier of the proofs of the program with the given `code_commitment`.
+    pub fn new(da_service: Da, ledger_db: LedgerDB, code_commitment: Vm::CodeCommitment) -> Self {
+        Self {
+            da_service,
+            ledger_db,
+            code_commitment,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Verifies the proofs posted at DA height `height`, records the accepted ones in the ledger,
+    /// and returns them.
+    pub async fn verify_proofs_at(&self, height: u64) -> anyhow::Result<Vec<StoredVerifiedProof>> {
+        let blobs = self.da_service.get_aggregated_proofs_at(height).await?;
+
+        let mut accepted = Vec::new();
+        for blob in blobs {
+            match self.verify_blob(&blob) {
+                Ok(proof) => {
+                    DA_PROOFS_VERIFIED.inc();
+                    accepted.push(proof);
+                }
+                Err(e) => {
+                    DA_PROOFS_REJECTED.inc();
+                    warn!("Rejected a proof posted at DA height {}: {:?}", height, e);
+                }
+            }
+        }
+
+        self.ledger_db
+            .put_verified_proofs(height, accepted.clone())?;
+        Ok(accepted)
+    }
+
+    /// Checks the proofs of every finalized DA height, starting after the last height checked by a previous run,
+    /// or at `start_height` on the first run. Never returns, unless the DA layer or the ledger fails.
+    pub async fn run(&self, start_height: u64) -> anyhow::Result<()> {
+        let mut height = match self.ledger_db.get_last_proof_checked_height()? {
+            Some(last_checked_height) => last_checked_height + 1,
+            None => start_height,
+        };
+
+        loop {
+            let finalized_height = self
+                .da_service
+                .get_last_finalized_block_header()
+                .await?
+                .height();
+            if height > finalized_height {
+                debug!(
+                    "Waiting for DA height {} to be finalized before checking its proofs",
+                    height
+                );
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+
+            let accepted = self.verify_proofs_at(height).await?;
+            if !accepted.is_empty() {
+                info!(
+                    "Verified {} proofs posted at DA height {}",
+                    accepted.len(),
+                    height
+                );
+            }
+            height += 1;
+        }
+    }
+
+    fn verify_blob(&self, blob: &[u8]) -> anyhow::Result<StoredVerifiedProof> {
+        let serialized_proof = match Proof::try_from_slice(blob)? {
+            Proof::Full(serialized_proof) => serialized_proof,
+            Proof::PublicInput(_) => anyhow::bail!("The proof only holds public input"),
+        };
+        let public_output = Vm::verify(&serialized_proof, &self.code_commitment)
+            .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?
+            .to_vec();
+
+        Ok(StoredVerifiedProof {
+            proof: serialized_proof,
+            public_output,
+        })
+    }
+}
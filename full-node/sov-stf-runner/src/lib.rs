@@ -3,11 +3,17 @@
 
 #[cfg(feature = "native")]
 mod config;
+#[cfg(feature = "native")]
+mod da_proofs;
+#[cfg(feature = "native")]
+mod metrics;
 #[cfg(feature = "mock")]
 /// Testing utilities.
 #[cfg(feature = "mock")]
 pub mod mock;
 #[cfg(feature = "native")]
+mod notifications;
+#[cfg(feature = "native")]
 mod prover_service;
 
 #[cfg(feature = "native")]
@@ -20,9 +26,25 @@ pub use config::RpcConfig;
 #[cfg(feature = "native")]
 pub use prover_service::*;
 #[cfg(feature = "native")]
+mod rest;
+#[cfg(feature = "native")]
 mod runner;
 #[cfg(feature = "native")]
-pub use config::{from_toml_path, ProverServiceConfig, RollupConfig, RunnerConfig, StorageConfig};
+pub use config::{
+    from_toml_path, ChainConfig, ProverServiceConfig, RollupConfig, RunnerConfig, StorageConfig,
+};
+#[cfg(feature = "native")]
+pub use da_proofs::{post_proof_to_da, DaProofVerifier};
+#[cfg(feature = "native")]
+pub use metrics::start_metrics_server;
+#[cfg(feature = "native")]
+pub use notifications::{
+    subscriptions_rpc_module, AttributeFilter, BatchNotification, EventFilter, EventNotification,
+    NotificationBus, ReorgNotification, RollupNotification, SlotNotification, TxStatus,
+    TxStatusNotification,
+};
+#[cfg(feature = "native")]
+pub use rest::{start_rest_server, RestEndpointKind, RestGateway, TX_SUBMISSION_METHODS};
 #[cfg(feature = "native")]
 pub use runner::*;
 
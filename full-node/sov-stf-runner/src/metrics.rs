@@ -0,0 +1,192 @@
+//! Prometheus metrics of the full node, served at `/metrics` by [`start_metrics_server`].
+//!
+//! The metrics are registered in the default registry, so the endpoint also serves the metrics
+//! of the other crates of the node, e.g. the DA adapters, the sequencer and the databases.
+
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use jsonrpsee::server::logger::{
+    HttpRequest, Logger, MethodKind, MethodResponseResult, Params, TransportProtocol,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_gauge, Encoder, Histogram, HistogramVec, IntCounter, IntGauge, TextEncoder,
+};
+use tracing::info;
+
+pub static SLOTS_PROCESSED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "sov_rollup_slots_processed",
+        "Slots applied by the runner, whose rate is the number of slots processed per second"
+    )
+    .unwrap()
+});
+
+pub static STF_EXECUTION_TIME_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sov_rollup_stf_execution_time_seconds",
+        // metric description
+        "Time to apply a slot with the state transition function, in seconds",
+        exponential_buckets(/*start=*/ 0.001, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static PROVING_TIME_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sov_rollup_proving_time_seconds",
+        // metric description
+        "Time to prove a slot, in seconds",
+        exponential_buckets(/*start=*/ 0.01, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static PROVING_LAG_SLOTS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "sov_rollup_proving_lag_slots",
+        "Slots executed by the runner whose proofs aren't finalized yet"
+    )
+    .unwrap()
+});
+
+pub static DA_PROOFS_POSTED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "sov_rollup_da_proofs_posted",
+        "Aggregated proofs posted to the DA by the node"
+    )
+    .unwrap()
+});
+
+pub static DA_PROOFS_VERIFIED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "sov_rollup_da_proofs_verified",
+        "Proofs posted to the DA by other parties which were verified and accepted by the node"
+    )
+    .unwrap()
+});
+
+pub static DA_PROOFS_REJECTED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "sov_rollup_da_proofs_rejected",
+        "Blobs of the proof namespace of the DA which failed to decode or to verify"
+    )
+    .unwrap()
+});
+
+pub static LEDGER_DB_SIZE_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "sov_rollup_ledger_db_size_bytes",
+        "Size of the files of the ledger database, in bytes"
+    )
+    .unwrap()
+});
+
+pub static RPC_REQUEST_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "sov_rollup_rpc_request_latency_seconds",
+        // metric description
+        "Time to serve an RPC call, in seconds",
+        // metric labels (dimensions)
+        &["method"],
+        exponential_buckets(/*start=*/ 0.0001, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Records the latency of the calls served by the RPC server.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RpcMetricsLogger;
+
+impl Logger for RpcMetricsLogger {
+    type Instant = Instant;
+
+    fn on_connect(&self, _: SocketAddr, _: &HttpRequest, _: TransportProtocol) {}
+
+    fn on_request(&self, _: TransportProtocol) -> Self::Instant {
+        Instant::now()
+    }
+
+    fn on_call(&self, _: &str, _: Params, _: MethodKind, _: TransportProtocol) {}
+
+    fn on_result(
+        &self,
+        method_name: &str,
+        _: MethodResponseResult,
+        started_at: Self::Instant,
+        _: TransportProtocol,
+    ) {
+        RPC_REQUEST_LATENCY_SECONDS
+            .with_label_values(&[method_name])
+            .observe(started_at.elapsed().as_secs_f64());
+    }
+
+    fn on_response(&self, _: &str, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_disconnect(&self, _: SocketAddr, _: TransportProtocol) {}
+}
+
+fn metrics_router() -> Router {
+    Router::new().route("/metrics", get(metrics))
+}
+
+async fn metrics() -> Response {
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    match encoder.encode(&prometheus::gather(), &mut buffer) {
+        Ok(()) => ([(CONTENT_TYPE, encoder.format_type().to_string())], buffer).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Starts serving the Prometheus metrics of the node at `/metrics` on `listen_address`,
+/// and returns the address it is bound to.
+pub async fn start_metrics_server(listen_address: SocketAddr) -> anyhow::Result<SocketAddr> {
+    let server =
+        axum::Server::try_bind(&listen_address)?.serve(metrics_router().into_make_service());
+    let bound_address = server.local_addr();
+    info!("Starting metrics server at {} ", &bound_address);
+
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            tracing::error!("Metrics server failed: {}", e);
+        }
+    });
+    Ok(bound_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        SLOTS_PROCESSED.inc();
+        STF_EXECUTION_TIME_SECONDS.observe(0.5);
+
+        let response = metrics_router()
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("sov_rollup_slots_processed"));
+        assert!(body.contains("sov_rollup_stf_execution_time_seconds_bucket"));
+    }
+}
@@ -0,0 +1,635 @@
+//! Notifications published by the runner on a broadcast bus, and the RPC subscriptions serving them.
+//!
+//! Every time a slot is applied, the runner publishes a [`BatchNotification`] for each of its batches,
+//! a [`TxStatusNotification`] and an [`EventNotification`] for each transaction and event of the batches,
+//! and finally a [`SlotNotification`]. Transactions are notified again when their slot is finalized, or
+//! dropped by a reorg, which is notified with a [`ReorgNotification`].
+
+use futures::future::Either;
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::stf::{BatchReceipt, Event};
+use tokio::sync::broadcast;
+
+/// The number of notifications buffered for each subscriber. Subscribers lagging further behind
+/// skip the oldest notifications.
+const NOTIFICATIONS_CAPACITY: usize = 1024;
+
+/// Describes a slot which has just been applied by the runner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotNotification {
+    /// The height of the DA block applied in the slot
+    pub da_height: u64,
+    /// The hash of the DA block applied in the slot, hex encoded
+    pub da_hash: String,
+    /// The state root of the rollup after the slot was applied, hex encoded
+    pub state_root: String,
+}
+
+impl SlotNotification {
+    /// Creates a new notification from the raw DA block hash and state root.
+    pub fn new(da_height: u64, da_hash: impl Into<[u8; 32]>, state_root: impl AsRef<[u8]>) -> Self {
+        Self {
+            da_height,
+            da_hash: format!("0x{}", hex::encode(da_hash.into())),
+            state_root: format!("0x{}", hex::encode(state_root.as_ref())),
+        }
+    }
+}
+
+/// Describes a reorg of the DA layer which has just been handled by the runner: the slots of the dropped
+/// DA blocks were rolled back, and the blocks of the canonical branch are applied from `fork_da_height`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReorgNotification {
+    /// The height of the first DA block replaced by the reorg
+    pub fork_da_height: u64,
+    /// The hashes of the DA blocks dropped by the reorg, by increasing height, hex encoded
+    pub dropped_da_hashes: Vec<String>,
+    /// The state root of the rollup the canonical branch is applied on, hex encoded
+    pub state_root: String,
+}
+
+impl ReorgNotification {
+    /// Creates a new notification from the raw DA block hashes and state root.
+    pub fn new<H: Into<[u8; 32]>>(
+        fork_da_height: u64,
+        dropped_da_hashes: impl IntoIterator<Item = H>,
+        state_root: impl AsRef<[u8]>,
+    ) -> Self {
+        Self {
+            fork_da_height,
+            dropped_da_hashes: dropped_da_hashes
+                .into_iter()
+                .map(|hash| to_hex(hash.into()))
+                .collect(),
+            state_root: format!("0x{}", hex::encode(state_root.as_ref())),
+        }
+    }
+}
+
+/// Describes a batch which has just been applied by the runner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchNotification {
+    /// The height of the DA block containing the batch
+    pub da_height: u64,
+    /// The hash of the batch, hex encoded
+    pub batch_hash: String,
+    /// The hashes of the transactions of the batch, hex encoded
+    pub tx_hashes: Vec<String>,
+    /// The receipt of the batch
+    pub receipt: serde_json::Value,
+}
+
+/// The status of a transaction applied by the runner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TxStatus {
+    /// The transaction was applied in a batch of the DA block at `da_height`, which is not finalized yet.
+    Included {
+        /// The height of the DA block containing the transaction
+        da_height: u64,
+        /// The hash of the batch containing the transaction, hex encoded
+        batch_hash: String,
+        /// The receipt of the transaction
+        receipt: serde_json::Value,
+    },
+    /// The DA block containing the transaction was finalized.
+    Finalized {
+        /// The height of the DA block containing the transaction
+        da_height: u64,
+    },
+    /// The DA block containing the transaction was dropped by a reorg. The transaction may be
+    /// included again in another block.
+    Dropped {
+        /// The height of the dropped DA block
+        da_height: u64,
+    },
+}
+
+/// Describes a change of the status of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxStatusNotification {
+    /// The hash of the transaction, hex encoded
+    pub tx_hash: String,
+    /// The new status of the transaction
+    pub status: TxStatus,
+}
+
+/// Describes an event emitted by a transaction which has just been applied by the runner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventNotification {
+    /// The height of the DA block containing the transaction
+    pub da_height: u64,
+    /// The hash of the transaction which emitted the event, hex encoded
+    pub tx_hash: String,
+    /// The event
+    pub event: Event,
+}
+
+/// A notification published on the [`NotificationBus`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RollupNotification {
+    /// A slot was applied
+    Slot(SlotNotification),
+    /// A batch was applied
+    Batch(BatchNotification),
+    /// The status of a transaction changed
+    TxStatus(TxStatusNotification),
+    /// An event was emitted
+    Event(EventNotification),
+    /// Slots were rolled back by a reorg of the DA layer
+    Reorg(ReorgNotification),
+}
+
+/// Selects the events of an events subscription. An event is selected if its key is one of `keys`
+/// (or `keys` is empty), its key starts with `key_prefix` (if set) and it carries all of `attributes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// The accepted event keys. All keys are accepted if empty
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// The prefix of the accepted event keys
+    #[serde(default)]
+    pub key_prefix: Option<String>,
+    /// The indexed attributes the accepted events must carry
+    #[serde(default)]
+    pub attributes: Vec<AttributeFilter>,
+}
+
+/// An indexed attribute of a typed event, with its borsh-encoded value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeFilter {
+    /// The name of the attribute
+    pub name: String,
+    /// The borsh-encoded value of the attribute, hex encoded
+    pub value: String,
+}
+
+impl EventFilter {
+    /// Returns true if `event` is selected by the filter.
+    pub fn matches(&self, event: &Event) -> bool {
+        let key = event.key().inner().as_slice();
+        if !self.keys.is_empty() && !self.keys.iter().any(|k| k.as_bytes() == key) {
+            return false;
+        }
+        if let Some(prefix) = &self.key_prefix {
+            if !key.starts_with(prefix.as_bytes()) {
+                return false;
+            }
+        }
+        self.attributes.iter().all(|filter| {
+            let value = normalize_hex(&filter.value);
+            event.indexed_attributes().iter().any(|attribute| {
+                attribute.name().inner().as_slice() == filter.name.as_bytes()
+                    && hex::encode(attribute.value().inner()) == value
+            })
+        })
+    }
+}
+
+/// The broadcast bus on which the runner publishes [`RollupNotification`]s.
+#[derive(Debug, Clone)]
+pub struct NotificationBus {
+    sender: broadcast::Sender<RollupNotification>,
+}
+
+impl Default for NotificationBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(NOTIFICATIONS_CAPACITY).0,
+        }
+    }
+}
+
+impl NotificationBus {
+    /// Subscribes to the notifications published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<RollupNotification> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes a notification to the current subscribers.
+    pub fn publish(&self, notification: RollupNotification) {
+        // This call returns an error IFF there are no subscribers, so we don't need to check the result
+        let _ = self.sender.send(notification);
+    }
+
+    /// Publishes the notifications of the batches applied in the DA block at `da_height`: the batch itself,
+    /// the inclusion of its transactions and the events they emitted.
+    pub fn publish_batches<B: Serialize, T: Serialize>(
+        &self,
+        da_height: u64,
+        batch_receipts: &[BatchReceipt<B, T>],
+    ) {
+        for batch in batch_receipts {
+            let batch_hash = to_hex(batch.batch_hash);
+            for tx in &batch.tx_receipts {
+                let tx_hash = to_hex(tx.tx_hash);
+                self.publish(RollupNotification::TxStatus(TxStatusNotification {
+                    tx_hash: tx_hash.clone(),
+                    status: TxStatus::Included {
+                        da_height,
+                        batch_hash: batch_hash.clone(),
+                        receipt: to_json(&tx.receipt),
+                    },
+                }));
+                for event in &tx.events {
+                    self.publish(RollupNotification::Event(EventNotification {
+                        da_height,
+                        tx_hash: tx_hash.clone(),
+                        event: event.clone(),
+                    }));
+                }
+            }
+            self.publish(RollupNotification::Batch(BatchNotification {
+                da_height,
+                batch_hash,
+                tx_hashes: batch
+                    .tx_receipts
+                    .iter()
+                    .map(|tx| to_hex(tx.tx_hash))
+                    .collect(),
+                receipt: to_json(&batch.inner),
+            }));
+        }
+    }
+
+    /// Publishes the new `status` of all the transactions of `batch_receipts`.
+    pub fn publish_tx_statuses<B, T>(
+        &self,
+        batch_receipts: &[BatchReceipt<B, T>],
+        status: TxStatus,
+    ) {
+        for tx in batch_receipts.iter().flat_map(|batch| &batch.tx_receipts) {
+            self.publish(RollupNotification::TxStatus(TxStatusNotification {
+                tx_hash: to_hex(tx.tx_hash),
+                status: status.clone(),
+            }));
+        }
+    }
+}
+
+fn to_hex(bytes: [u8; 32]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn to_json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+fn normalize_hex(value: &str) -> String {
+    value.trim_start_matches("0x").to_lowercase()
+}
+
+/// Creates a new [`jsonrpsee::RpcModule`] exposing subscriptions to the notifications of `bus`:
+/// - `chainState_subscribeSlots`, emitting a [`SlotNotification`] every time a slot is applied.
+/// - `chainState_subscribeBatches`, emitting a [`BatchNotification`] every time a batch is applied.
+/// - `chainState_subscribeTxStatus`, emitting a [`TxStatusNotification`] every time the status of a
+///   transaction changes. It takes an optional list of hex encoded transaction hashes to watch.
+/// - `chainState_subscribeEvents`, emitting an [`EventNotification`] for every event selected by the
+///   optional [`EventFilter`] it takes.
+/// - `chainState_subscribeReorgs`, emitting a [`ReorgNotification`] every time slots are rolled back by a
+///   reorg of the DA layer.
+pub fn subscriptions_rpc_module(
+    bus: NotificationBus,
+) -> anyhow::Result<RpcModule<NotificationBus>> {
+    let mut rpc = RpcModule::new(bus);
+
+    rpc.register_subscription(
+        "chainState_subscribeSlots",
+        "chainState_slotProcessed",
+        "chainState_unsubscribeSlots",
+        |_, pending_subscription, bus| async move {
+            forward_notifications(
+                pending_subscription,
+                &bus,
+                |notification| match notification {
+                    RollupNotification::Slot(slot) => Some(slot),
+                    _ => None,
+                },
+            )
+            .await
+        },
+    )?;
+
+    rpc.register_subscription(
+        "chainState_subscribeBatches",
+        "chainState_batchProcessed",
+        "chainState_unsubscribeBatches",
+        |_, pending_subscription, bus| async move {
+            forward_notifications(
+                pending_subscription,
+                &bus,
+                |notification| match notification {
+                    RollupNotification::Batch(batch) => Some(batch),
+                    _ => None,
+                },
+            )
+            .await
+        },
+    )?;
+
+    rpc.register_subscription(
+        "chainState_subscribeTxStatus",
+        "chainState_txStatusChanged",
+        "chainState_unsubscribeTxStatus",
+        |params, pending_subscription, bus| async move {
+            let tx_hashes = match params.sequence().optional_next::<Vec<String>>() {
+                Ok(tx_hashes) => tx_hashes
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|hash| normalize_hex(hash))
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    pending_subscription.reject(e).await;
+                    return Ok(());
+                }
+            };
+            forward_notifications(
+                pending_subscription,
+                &bus,
+                |notification| match notification {
+                    RollupNotification::TxStatus(tx)
+                        if tx_hashes.is_empty()
+                            || tx_hashes.contains(&normalize_hex(&tx.tx_hash)) =>
+                    {
+                        Some(tx)
+                    }
+                    _ => None,
+                },
+            )
+            .await
+        },
+    )?;
+
+    rpc.register_subscription(
+        "chainState_subscribeEvents",
+        "chainState_eventEmitted",
+        "chainState_unsubscribeEvents",
+        |params, pending_subscription, bus| async move {
+            let filter = match params.sequence().optional_next::<EventFilter>() {
+                Ok(filter) => filter.unwrap_or_default(),
+                Err(e) => {
+                    pending_subscription.reject(e).await;
+                    return Ok(());
+                }
+            };
+            forward_notifications(
+                pending_subscription,
+                &bus,
+                |notification| match notification {
+                    RollupNotification::Event(event) if filter.matches(&event.event) => Some(event),
+                    _ => None,
+                },
+            )
+            .await
+        },
+    )?;
+
+    rpc.register_subscription(
+        "chainState_subscribeReorgs",
+        "chainState_reorg",
+        "chainState_unsubscribeReorgs",
+        |_, pending_subscription, bus| async move {
+            forward_notifications(
+                pending_subscription,
+                &bus,
+                |notification| match notification {
+                    RollupNotification::Reorg(reorg) => Some(reorg),
+                    _ => None,
+                },
+            )
+            .await
+        },
+    )?;
+
+    Ok(rpc)
+}
+
+/// Accepts the subscription and sends it the notifications of `bus` mapped by `select`, until the
+/// subscriber cancels. Notifications for which `select` returns `None` are skipped.
+async fn forward_notifications<T: Serialize>(
+    pending_subscription: PendingSubscriptionSink,
+    bus: &NotificationBus,
+    mut select: impl FnMut(RollupNotification) -> Option<T>,
+) -> jsonrpsee::core::SubscriptionResult {
+    let mut rx = bus.subscribe();
+
+    let subscription = pending_subscription.accept().await?;
+    let closed = subscription.closed();
+    futures::pin_mut!(closed);
+
+    loop {
+        let next_msg = rx.recv();
+        futures::pin_mut!(next_msg);
+        match futures::future::select(closed, next_msg).await {
+            Either::Left(_) => break Ok(()),
+            Either::Right((outcome, channel_closing_future)) => {
+                closed = channel_closing_future;
+                let notification = match outcome {
+                    Ok(notification) => notification,
+                    // The subscriber fell behind: keep going from the oldest buffered notification.
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            "A subscriber lagged behind and skipped {} notifications",
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break Ok(()),
+                };
+                let Some(notification) = select(notification) else {
+                    continue;
+                };
+                let msg = SubscriptionMessage::from_json(&notification)?;
+                // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                if subscription.send(msg).await.is_err() {
+                    break Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::rpc_params;
+    use sov_rollup_interface::stf::{EventAttribute, TransactionReceipt};
+
+    use super::*;
+
+    fn batch_receipt() -> BatchReceipt<(), u8> {
+        BatchReceipt {
+            batch_hash: [3; 32],
+            tx_receipts: vec![
+                TransactionReceipt {
+                    tx_hash: [4; 32],
+                    body_to_save: None,
+                    events: vec![Event::new("bank_transfer", "1")],
+                    receipt: 0,
+                    gas_used: vec![],
+                },
+                TransactionReceipt {
+                    tx_hash: [5; 32],
+                    body_to_save: None,
+                    events: vec![Event::new_typed(
+                        "sequencer_slashed",
+                        1,
+                        vec![7],
+                        vec![EventAttribute::new("sequencer", vec![0xab])],
+                    )],
+                    receipt: 1,
+                    gas_used: vec![],
+                },
+            ],
+            inner: (),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_slot_notifications() {
+        let bus = NotificationBus::default();
+        let rpc = subscriptions_rpc_module(bus.clone()).unwrap();
+        let mut subscription = rpc
+            .subscribe_unbounded("chainState_subscribeSlots", rpc_params![])
+            .await
+            .unwrap();
+
+        let notification = SlotNotification::new(3, [1; 32], [2; 32]);
+        assert_eq!(notification.da_hash, format!("0x{}", "01".repeat(32)));
+        bus.publish(RollupNotification::Batch(BatchNotification {
+            da_height: 3,
+            batch_hash: String::new(),
+            tx_hashes: vec![],
+            receipt: serde_json::Value::Null,
+        }));
+        bus.publish(RollupNotification::Slot(notification.clone()));
+
+        let (received, _) = subscription
+            .next::<SlotNotification>()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received, notification);
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_reorg_notifications() {
+        let bus = NotificationBus::default();
+        let rpc = subscriptions_rpc_module(bus.clone()).unwrap();
+        let mut subscription = rpc
+            .subscribe_unbounded("chainState_subscribeReorgs", rpc_params![])
+            .await
+            .unwrap();
+
+        let notification = ReorgNotification::new(3, [[1; 32], [2; 32]], [7; 32]);
+        assert_eq!(
+            notification.dropped_da_hashes,
+            vec![to_hex([1; 32]), to_hex([2; 32])]
+        );
+        bus.publish(RollupNotification::Slot(SlotNotification::new(
+            3, [3; 32], [8; 32],
+        )));
+        bus.publish(RollupNotification::Reorg(notification.clone()));
+
+        let (received, _) = subscription
+            .next::<ReorgNotification>()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received, notification);
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_statuses_of_watched_transactions() {
+        let bus = NotificationBus::default();
+        let rpc = subscriptions_rpc_module(bus.clone()).unwrap();
+        let watched = to_hex([5; 32]);
+        let mut subscription = rpc
+            .subscribe_unbounded(
+                "chainState_subscribeTxStatus",
+                rpc_params![vec![watched.to_uppercase().replace("0X", "0x")]],
+            )
+            .await
+            .unwrap();
+
+        let receipts = [batch_receipt()];
+        bus.publish_batches(7, &receipts);
+        bus.publish_tx_statuses(&receipts, TxStatus::Finalized { da_height: 7 });
+
+        let (included, _) = subscription
+            .next::<TxStatusNotification>()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(included.tx_hash, watched);
+        assert_eq!(
+            included.status,
+            TxStatus::Included {
+                da_height: 7,
+                batch_hash: to_hex([3; 32]),
+                receipt: serde_json::json!(1),
+            }
+        );
+
+        let (finalized, _) = subscription
+            .next::<TxStatusNotification>()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(finalized.tx_hash, watched);
+        assert_eq!(finalized.status, TxStatus::Finalized { da_height: 7 });
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_filtered_events() {
+        let bus = NotificationBus::default();
+        let rpc = subscriptions_rpc_module(bus.clone()).unwrap();
+        let filter = EventFilter {
+            key_prefix: Some("sequencer_".to_string()),
+            attributes: vec![AttributeFilter {
+                name: "sequencer".to_string(),
+                value: "0xAB".to_string(),
+            }],
+            ..Default::default()
+        };
+        let mut subscription = rpc
+            .subscribe_unbounded("chainState_subscribeEvents", rpc_params![filter])
+            .await
+            .unwrap();
+
+        bus.publish_batches(7, &[batch_receipt()]);
+
+        let (received, _) = subscription
+            .next::<EventNotification>()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.tx_hash, to_hex([5; 32]));
+        assert_eq!(received.event.key().inner(), b"sequencer_slashed");
+    }
+
+    #[test]
+    fn event_filter_matches_keys() {
+        let event = Event::new("bank_transfer", "1");
+
+        assert!(EventFilter::default().matches(&event));
+        assert!(EventFilter {
+            keys: vec!["bank_mint".to_string(), "bank_transfer".to_string()],
+            ..Default::default()
+        }
+        .matches(&event));
+        assert!(!EventFilter {
+            key_prefix: Some("sequencer_".to_string()),
+            ..Default::default()
+        }
+        .matches(&event));
+        assert!(!EventFilter {
+            attributes: vec![AttributeFilter {
+                name: "sender".to_string(),
+                value: "00".to_string(),
+            }],
+            ..Default::default()
+        }
+        .matches(&event));
+    }
+}
@@ -1,10 +1,12 @@
 mod parallel;
+mod pipeline;
 use async_trait::async_trait;
 pub use parallel::ParallelProverService;
+pub use pipeline::{ProvingPipeline, DEFAULT_MAX_PROOF_ATTEMPTS, DEFAULT_MAX_PROVING_LAG};
 use serde::Serialize;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
-use sov_rollup_interface::zk::StateTransitionData;
+use sov_rollup_interface::zk::{Proof, StateTransitionData};
 use thiserror::Error;
 
 /// The possible configurations of the prover.
@@ -46,6 +48,22 @@ pub enum ProofProcessingStatus {
     Busy,
 }
 
+/// Represents the status of proof aggregation.
+#[derive(Debug, Eq, PartialEq)]
+pub enum AggregatedProofStatus {
+    /// Proof aggregation isn't configured, see [`ParallelProverService::with_aggregation_vm`].
+    Disabled,
+    /// Fewer slot proofs than required were sent to the DA since the last aggregated proof.
+    NotEnoughProofs {
+        /// The number of slot proofs waiting to be aggregated.
+        pending: u64,
+        /// The number of slot proofs covered by an aggregated proof.
+        required: u64,
+    },
+    /// The slot proofs were aggregated into a single proof.
+    Aggregated(Proof),
+}
+
 /// An error that occurred during ZKP proving.
 #[derive(Error, Debug)]
 pub enum ProverServiceError {
@@ -62,6 +80,8 @@ pub enum ProverServiceError {
 ///     1. Submitting a witness using the `submit_witness` method to a prover service.
 ///     2. Initiating proof generation with the `prove` method.
 /// Once the proof is ready, it can be sent to the DA with `send_proof_to_da` method.
+/// The proofs sent to the DA can then be aggregated with `create_aggregated_proof`, into a single
+/// proof covering `aggregated_proof_block_jump` consecutive slots.
 /// Currently, the cancellation of proving jobs for submitted witnesses is not supported,
 /// but this functionality will be added in the future (#1185).
 #[async_trait]
@@ -95,4 +115,10 @@ pub trait ProverService {
         &self,
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error>;
+
+    /// Aggregates the proofs of the next `aggregated_proof_block_jump` slots sent to the DA into a single
+    /// recursive proof, whose public output is an `AggregatedStateTransition` from the initial state root
+    /// of the first slot to the final state root of the last one. The proofs are kept if the aggregation
+    /// fails, so that it can be retried.
+    async fn create_aggregated_proof(&self) -> Result<AggregatedProofStatus, ProverServiceError>;
 }
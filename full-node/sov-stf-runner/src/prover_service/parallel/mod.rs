@@ -1,8 +1,9 @@
 mod prover;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use prover::Prover;
+use prover::{make_aggregated_proof, Prover};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_rollup_interface::da::DaSpec;
@@ -14,8 +15,8 @@ use super::{ProverService, ProverServiceError};
 use crate::config::ProverServiceConfig;
 use crate::verifier::StateTransitionVerifier;
 use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, RollupProverConfig,
-    WitnessSubmissionStatus,
+    AggregatedProofStatus, ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus,
+    RollupProverConfig, WitnessSubmissionStatus,
 };
 
 /// Prover service that generates proofs in parallel.
@@ -32,11 +33,20 @@ where
 
     zk_storage: V::PreState,
     prover_state: Prover<StateRoot, Witness, Da>,
+    aggregation_vm: Option<Vm>,
 }
 
 impl<StateRoot, Witness, Da, Vm, V> ParallelProverService<StateRoot, Witness, Da, Vm, V>
 where
-    StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
+    StateRoot: Serialize
+        + DeserializeOwned
+        + Clone
+        + AsRef<[u8]>
+        + PartialEq
+        + Debug
+        + Send
+        + Sync
+        + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
     Da: DaService,
     Vm: ZkvmHost,
@@ -73,10 +83,24 @@ where
                 prover_service_config.aggregated_proof_block_jump,
             ),
             zk_storage,
+            aggregation_vm: None,
         }
     }
 
-    /// Creates a new prover.
+    /// Enables proof aggregation: the proofs sent to the DA are kept, and aggregated by
+    /// [`ProverService::create_aggregated_proof`] with `aggregation_vm`, which runs the aggregation program.
+    /// The aggregation program verifies the slot proofs with `AggregatedStateTransition::verify_and_aggregate`,
+    /// and commits its output.
+    ///
+    /// Aggregation stays disabled if the `aggregated_proof_block_jump` of the configuration is zero.
+    pub fn with_aggregation_vm(mut self, aggregation_vm: Vm) -> Self {
+        self.aggregation_vm = Some(aggregation_vm);
+        self.prover_state.enable_aggregation();
+        self
+    }
+
+    /// Creates a new prover, with the number of workers of the `prover_service_config`,
+    /// or the number of CPUs minus one if it isn't set.
     pub fn new_with_default_workers(
         vm: Vm,
         zk_stf: V,
@@ -85,8 +109,15 @@ where
         zk_storage: V::PreState,
         prover_service_config: ProverServiceConfig,
     ) -> Self {
-        let num_cpus = num_cpus::get();
-        assert!(num_cpus > 1, "Unable to create parallel prover service");
+        let num_workers = prover_service_config.num_workers.unwrap_or_else(|| {
+            let num_cpus = num_cpus::get();
+            assert!(num_cpus > 1, "Unable to create parallel prover service");
+            num_cpus - 1
+        });
+        assert!(
+            num_workers > 0,
+            "The prover service needs at least one worker"
+        );
 
         Self::new(
             vm,
@@ -94,7 +125,7 @@ where
             da_verifier,
             config,
             zk_storage,
-            num_cpus - 1,
+            num_workers,
             prover_service_config,
         )
     }
@@ -104,7 +135,15 @@ where
 impl<StateRoot, Witness, Da, Vm, V> ProverService
     for ParallelProverService<StateRoot, Witness, Da, Vm, V>
 where
-    StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
+    StateRoot: Serialize
+        + DeserializeOwned
+        + Clone
+        + AsRef<[u8]>
+        + PartialEq
+        + Debug
+        + Send
+        + Sync
+        + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
     Da: DaService,
     Vm: ZkvmHost + 'static,
@@ -150,4 +189,25 @@ where
         self.prover_state
             .get_proof_submission_status_and_remove_on_success(block_header_hash)
     }
+
+    async fn create_aggregated_proof(&self) -> Result<AggregatedProofStatus, ProverServiceError> {
+        let proofs = match self.prover_state.proofs_to_aggregate() {
+            Ok(proofs) => proofs,
+            Err(status) => return Ok(status),
+        };
+        let vm = self
+            .aggregation_vm
+            .clone()
+            .expect("Aggregation is only enabled with an aggregation vm");
+        let config = self.prover_config.clone();
+
+        let proof = tokio::task::spawn_blocking(move || {
+            make_aggregated_proof::<StateRoot, V, Vm, Da>(vm, config, proofs)
+        })
+        .await
+        .map_err(anyhow::Error::from)??;
+        // The proofs are only removed once aggregated, so that a failed aggregation is retried.
+        self.prover_state.remove_aggregated_proofs();
+        Ok(AggregatedProofStatus::Aggregated(proof))
+    }
 }
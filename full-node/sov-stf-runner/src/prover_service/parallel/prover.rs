@@ -1,5 +1,6 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
 
@@ -8,11 +9,13 @@ use serde::Serialize;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{Proof, StateTransitionData, ZkvmHost};
+use sov_rollup_interface::zk::{AggregatedStateTransition, Proof, StateTransitionData, ZkvmHost};
 
 use super::ProverServiceError;
+use crate::metrics::PROVING_TIME_SECONDS;
 use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, WitnessSubmissionStatus,
+    AggregatedProofStatus, ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus,
+    WitnessSubmissionStatus,
 };
 
 enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
@@ -25,6 +28,8 @@ enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
 struct ProverState<StateRoot, Witness, Da: DaSpec> {
     prover_status: HashMap<Da::SlotHash, ProverStatus<StateRoot, Witness, Da>>,
     pending_tasks_count: usize,
+    // The proofs sent to the DA, in order, which are not aggregated yet.
+    proofs_to_aggregate: Vec<Proof>,
 }
 
 impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
@@ -79,7 +84,8 @@ pub(crate) struct Prover<StateRoot, Witness, Da: DaService> {
     prover_state: Arc<RwLock<ProverState<StateRoot, Witness, Da::Spec>>>,
     num_threads: usize,
     pool: rayon::ThreadPool,
-    _aggregated_proof_block_jump: u64,
+    aggregated_proof_block_jump: u64,
+    aggregation_enabled: bool,
 }
 
 impl<StateRoot, Witness, Da> Prover<StateRoot, Witness, Da>
@@ -88,7 +94,7 @@ where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
 {
-    pub(crate) fn new(num_threads: usize, _aggregated_proof_block_jump: u64) -> Self {
+    pub(crate) fn new(num_threads: usize, aggregated_proof_block_jump: u64) -> Self {
         Self {
             num_threads,
             pool: rayon::ThreadPoolBuilder::new()
@@ -99,11 +105,18 @@ where
             prover_state: Arc::new(RwLock::new(ProverState {
                 prover_status: Default::default(),
                 pending_tasks_count: Default::default(),
+                proofs_to_aggregate: Default::default(),
             })),
-            _aggregated_proof_block_jump,
+            aggregated_proof_block_jump,
+            aggregation_enabled: false,
         }
     }
 
+    /// Keeps the proofs sent to the DA to aggregate them, unless the block jump is zero.
+    pub(crate) fn enable_aggregation(&mut self) {
+        self.aggregation_enabled = self.aggregated_proof_block_jump > 0;
+    }
+
     pub(crate) fn submit_witness(
         &self,
         state_transition_data: StateTransitionData<StateRoot, Witness, Da::Spec>,
@@ -153,7 +166,9 @@ where
 
                     self.pool.spawn(move || {
                         tracing::info_span!("guest_execution").in_scope(|| {
+                            let proving_timer = PROVING_TIME_SECONDS.start_timer();
                             let proof = make_proof(vm, config, zk_storage);
+                            proving_timer.observe_duration();
 
                             let mut prover_state =
                                 prover_state_clone.write().expect("Lock was poisoned");
@@ -165,6 +180,11 @@ where
 
                     Ok(ProofProcessingStatus::ProvingInProgress)
                 } else {
+                    // Keep the witness, so that the proving can be retried once a worker is available.
+                    prover_state.prover_status.insert(
+                        block_header_hash,
+                        ProverStatus::WitnessSubmitted(state_transition_data),
+                    );
                     Ok(ProofProcessingStatus::Busy)
                 }
             }
@@ -194,20 +214,58 @@ where
                 Ok(ProofSubmissionStatus::ProofGenerationInProgress)
             }
             Some(ProverStatus::Proved(_)) => {
-                prover_state.remove(&block_header_hash);
+                if let Some(ProverStatus::Proved(proof)) = prover_state.remove(&block_header_hash) {
+                    if self.aggregation_enabled {
+                        prover_state.proofs_to_aggregate.push(proof);
+                    }
+                }
                 Ok(ProofSubmissionStatus::Success)
             }
             Some(ProverStatus::WitnessSubmitted(_)) => Err(anyhow::anyhow!(
                 "Witness for {:?} was submitted, but the proof generation is not triggered.",
                 block_header_hash
             )),
-            Some(ProverStatus::Err(e)) => Err(anyhow::anyhow!(e.to_string())),
+            Some(ProverStatus::Err(_)) => {
+                // The failed proof is forgotten, so that the witness can be submitted again.
+                match prover_state.remove(&block_header_hash) {
+                    Some(ProverStatus::Err(e)) => Err(e),
+                    _ => unreachable!("The proof generation failed"),
+                }
+            }
             None => Err(anyhow::anyhow!(
                 "Missing witness for: {:?}",
                 block_header_hash
             )),
         }
     }
+
+    /// Returns the proofs of the next `aggregated_proof_block_jump` slots, if they were all sent to the DA.
+    /// Otherwise, returns the reason why they can't be aggregated yet. The proofs are kept until they are
+    /// removed with [`Prover::remove_aggregated_proofs`], so that a failed aggregation can be retried.
+    pub(crate) fn proofs_to_aggregate(&self) -> Result<Vec<Proof>, AggregatedProofStatus> {
+        if !self.aggregation_enabled {
+            return Err(AggregatedProofStatus::Disabled);
+        }
+
+        let prover_state = self.prover_state.read().expect("Lock was poisoned");
+        let pending = prover_state.proofs_to_aggregate.len() as u64;
+        if pending < self.aggregated_proof_block_jump {
+            return Err(AggregatedProofStatus::NotEnoughProofs {
+                pending,
+                required: self.aggregated_proof_block_jump,
+            });
+        }
+
+        Ok(prover_state.proofs_to_aggregate[..self.aggregated_proof_block_jump as usize].to_vec())
+    }
+
+    /// Removes the proofs returned by [`Prover::proofs_to_aggregate`], once they were aggregated.
+    pub(crate) fn remove_aggregated_proofs(&self) {
+        let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
+        prover_state
+            .proofs_to_aggregate
+            .drain(..self.aggregated_proof_block_jump as usize);
+    }
 }
 
 fn make_proof<V, Vm, Da>(
@@ -231,3 +289,45 @@ where
         ProofGenConfig::Prover => vm.run(true),
     }
 }
+
+/// Aggregates the `proofs` of consecutive slots by running the aggregation program in `vm`.
+/// The slots are checked to be consecutive before the program runs.
+pub(crate) fn make_aggregated_proof<StateRoot, V, Vm, Da>(
+    mut vm: Vm,
+    config: Arc<ProofGenConfig<V, Da, Vm>>,
+    proofs: Vec<Proof>,
+) -> Result<Proof, anyhow::Error>
+where
+    StateRoot: Serialize + DeserializeOwned + PartialEq + Debug,
+    Da: DaService,
+    Vm: ZkvmHost + 'static,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
+{
+    match config.deref() {
+        // The slot proofs don't have any public output to aggregate.
+        ProofGenConfig::Skip | ProofGenConfig::Simulate(_) => {
+            Ok(Proof::PublicInput(Vec::default()))
+        }
+        ProofGenConfig::Execute | ProofGenConfig::Prover => {
+            let mut transitions = Vec::with_capacity(proofs.len());
+            for proof in &proofs {
+                let transition = Vm::extract_output::<Da::Spec, StateRoot>(proof).map_err(|e| {
+                    anyhow::anyhow!("Failed to extract a slot proof output: {:?}", e)
+                })?;
+                transitions.push(transition);
+            }
+            AggregatedStateTransition::<Da::Spec, StateRoot>::aggregate::<sha2::Sha256>(
+                transitions,
+            )?;
+
+            let serialized_proofs: Vec<Vec<u8>> = proofs
+                .into_iter()
+                .map(|proof| match proof {
+                    Proof::PublicInput(data) | Proof::Full(data) => data,
+                })
+                .collect();
+            vm.add_hint(serialized_proofs);
+            vm.run(matches!(config.deref(), ProofGenConfig::Prover))
+        }
+    }
+}
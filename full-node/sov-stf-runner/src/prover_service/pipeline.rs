@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+
+use serde::de::DeserializeOwned;
+use sov_db::ledger_db::LedgerDB;
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::rpc::ProofJobStatus;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{Proof, StateTransitionData};
+
+use super::{
+    AggregatedProofStatus, ProofProcessingStatus, ProofSubmissionStatus, ProverService,
+    ProverServiceError,
+};
+use crate::metrics::PROVING_LAG_SLOTS;
+
+type SlotHash<Ps> = <<<Ps as ProverService>::DaService as DaService>::Spec as DaSpec>::SlotHash;
+
+type TransitionData<Ps> = StateTransitionData<
+    <Ps as ProverService>::StateRoot,
+    <Ps as ProverService>::Witness,
+    <<Ps as ProverService>::DaService as DaService>::Spec,
+>;
+
+/// The default number of times the proof generation of a slot is attempted before its proof job fails.
+pub const DEFAULT_MAX_PROOF_ATTEMPTS: u32 = 3;
+
+/// The default number of executed slots whose proofs can be unfinalized before [`ProvingPipeline::submit`] blocks.
+pub const DEFAULT_MAX_PROVING_LAG: u64 = 16;
+
+/// How often the prover service is polled while the pipeline is blocked.
+const POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(100);
+
+struct QueuedSlot<Hash> {
+    height: u64,
+    header_hash: Hash,
+    started: bool,
+    // Whether the proof job of the slot failed, which stops the finalization of the next slots.
+    failed: bool,
+}
+
+/// Pipelines the proving of consecutive slots on a [`ProverService`]: the witness of a slot is submitted as
+/// soon as the slot is executed, and its proof is generated by a worker while the next slots are executed and
+/// proven by the other workers. The proofs complete out of order, but are finalized, i.e. sent to the DA and
+/// aggregated, in slot order.
+///
+/// Execution runs ahead of proving: submitting a witness only waits while more than `max_proving_lag` slots
+/// are executed but not proven, which bounds the number of witnesses kept in memory. The DA heights of the
+/// last executed and last proven slots are recorded in the [`LedgerDB`], so that the lag can be monitored.
+///
+/// Each slot has a proof job in the [`LedgerDB`], which stores its witness until the proof is complete.
+/// A failed proof generation is retried before the next slots, until the job was attempted `max_attempts`
+/// times: the job then fails, and the finalization stops at its slot until it is requeued, e.g. with the
+/// `ledger_requeueProofJob` RPC method, so that the aggregated proofs never skip a slot. The pipeline picks up
+/// the pending and running jobs it doesn't know, i.e. the requeued ones and the ones interrupted by a restart,
+/// so that no slot is silently left unproven.
+pub struct ProvingPipeline<Ps: ProverService> {
+    ledger_db: LedgerDB,
+    max_attempts: u32,
+    max_proving_lag: u64,
+    // The slots whose proof isn't finalized yet, by increasing height.
+    slots: VecDeque<QueuedSlot<SlotHash<Ps>>>,
+}
+
+impl<Ps> ProvingPipeline<Ps>
+where
+    Ps: ProverService,
+    Ps::StateRoot: DeserializeOwned,
+    Ps::Witness: DeserializeOwned,
+{
+    /// Creates a new pipeline storing its proof jobs in `ledger_db`, which attempts to prove each slot
+    /// up to `max_attempts` times, and lets execution run [`DEFAULT_MAX_PROVING_LAG`] slots ahead of proving.
+    pub fn new(ledger_db: LedgerDB, max_attempts: u32) -> Self {
+        Self {
+            ledger_db,
+            max_attempts,
+            max_proving_lag: DEFAULT_MAX_PROVING_LAG,
+            slots: Default::default(),
+        }
+    }
+
+    /// Sets the number of executed slots whose proofs can be unfinalized before [`ProvingPipeline::submit`] blocks.
+    pub fn with_max_proving_lag(mut self, max_proving_lag: u64) -> Self {
+        self.max_proving_lag = max_proving_lag;
+        self
+    }
+
+    /// Returns the number of slots whose proof isn't finalized yet.
+    pub fn pending_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Submits the witness of the next slot, and advances the pipeline. Waits until the proving lag is
+    /// back within the window if needed. Returns the aggregated proofs created while advancing the pipeline.
+    pub async fn submit(
+        &mut self,
+        prover_service: &Ps,
+        state_transition_data: TransitionData<Ps>,
+    ) -> Result<Vec<Proof>, ProverServiceError> {
+        let height = state_transition_data.da_block_header.height();
+        let serialized_data =
+            bincode::serialize(&state_transition_data).map_err(anyhow::Error::from)?;
+        self.ledger_db.enqueue_proof_job(height, serialized_data)?;
+        self.push(prover_service, height, state_transition_data)
+            .await;
+
+        let mut aggregated_proofs = self.advance(prover_service).await?;
+        while self.pending_slots() as u64 > self.max_proving_lag {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            aggregated_proofs.extend(self.advance(prover_service).await?);
+        }
+        Ok(aggregated_proofs)
+    }
+
+    /// Waits until the proofs of all the submitted slots are finalized, and returns the aggregated proofs
+    /// created in the meantime. Fails if the finalization is stopped by a failed proof job.
+    pub async fn finish(&mut self, prover_service: &Ps) -> Result<Vec<Proof>, ProverServiceError> {
+        let mut aggregated_proofs = self.advance(prover_service).await?;
+        while self.pending_slots() > 0 {
+            if let Some(height) = self.failed_height() {
+                return Err(anyhow::anyhow!(
+                    "The proof job of slot {} failed, the proofs of the next slots can't be finalized until it is requeued",
+                    height
+                )
+                .into());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+            aggregated_proofs.extend(self.advance(prover_service).await?);
+        }
+        Ok(aggregated_proofs)
+    }
+
+    /// Returns the height of the slot whose failed proof job stops the finalization, if any.
+    pub fn failed_height(&self) -> Option<u64> {
+        self.slots
+            .front()
+            .filter(|slot| slot.failed)
+            .map(|slot| slot.height)
+    }
+
+    /// Starts proving the waiting slots, by increasing height, while workers are available, then
+    /// finalizes the proven slots up to the first slot which isn't proven yet.
+    async fn advance(&mut self, prover_service: &Ps) -> Result<Vec<Proof>, ProverServiceError> {
+        self.load_unfinished_jobs(prover_service).await?;
+
+        for slot in self.slots.iter_mut().filter(|slot| !slot.started) {
+            match prover_service.prove(slot.header_hash.clone()).await? {
+                ProofProcessingStatus::ProvingInProgress => {
+                    self.ledger_db.mark_proof_job_running(slot.height)?;
+                    slot.started = true;
+                }
+                ProofProcessingStatus::Busy => break,
+            }
+        }
+
+        let mut aggregated_proofs = Vec::new();
+        while let Some(slot) = self.slots.front() {
+            if !slot.started || slot.failed {
+                break;
+            }
+            let height = slot.height;
+            match prover_service
+                .send_proof_to_da(slot.header_hash.clone())
+                .await
+            {
+                Ok(ProofSubmissionStatus::Success) => {
+                    self.slots.pop_front();
+                    self.ledger_db.mark_proof_job_complete(height)?;
+                    aggregated_proofs.extend(Self::aggregate(prover_service).await);
+                }
+                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add timeout handling.
+                Ok(ProofSubmissionStatus::ProofGenerationInProgress) => break,
+                Err(e) => {
+                    let status = self.ledger_db.mark_proof_job_failed(
+                        height,
+                        e.to_string(),
+                        self.max_attempts,
+                    )?;
+                    if status == ProofJobStatus::Failed {
+                        tracing::error!(height, error = ?e, "The proof generation of the slot failed too many times, the finalization stops until its proof job is requeued");
+                        if let Some(slot) = self.slots.front_mut() {
+                            slot.failed = true;
+                        }
+                    } else {
+                        tracing::warn!(height, error = ?e, "The proof generation of the slot failed, retrying");
+                        // The job is pending again, so it's loaded back before the next slots.
+                        self.slots.pop_front();
+                        self.load_unfinished_jobs(prover_service).await?;
+                    }
+                    break;
+                }
+            }
+        }
+        PROVING_LAG_SLOTS.set(self.slots.len() as i64);
+        Ok(aggregated_proofs)
+    }
+
+    /// Aggregates the proofs sent to the DA, while there are enough of them. A failed aggregation doesn't stop
+    /// the pipeline: the prover service keeps the proofs, and the aggregation is retried with the next proof.
+    async fn aggregate(prover_service: &Ps) -> Vec<Proof> {
+        let mut aggregated_proofs = Vec::new();
+        loop {
+            match prover_service.create_aggregated_proof().await {
+                Ok(AggregatedProofStatus::Aggregated(proof)) => aggregated_proofs.push(proof),
+                Ok(_) => break,
+                Err(e) => {
+                    tracing::error!(error = ?e, "The aggregation of the slot proofs failed");
+                    break;
+                }
+            }
+        }
+        aggregated_proofs
+    }
+
+    /// Loads the pending and running proof jobs of the ledger DB which are not in the pipeline.
+    async fn load_unfinished_jobs(
+        &mut self,
+        prover_service: &Ps,
+    ) -> Result<(), ProverServiceError> {
+        for (height, job) in self.ledger_db.get_unfinished_proof_jobs()? {
+            if let Some(index) = self.slots.iter().position(|slot| slot.height == height) {
+                if !self.slots[index].failed {
+                    continue;
+                }
+                // The failed job was requeued, so its slot is proven again.
+                self.slots.remove(index);
+            }
+            let Some(serialized_data) = job.state_transition_data else {
+                continue;
+            };
+            let state_transition_data: TransitionData<Ps> =
+                bincode::deserialize(&serialized_data).map_err(anyhow::Error::from)?;
+            tracing::info!(height, "Resuming the proof job of the slot");
+            self.push(prover_service, height, state_transition_data)
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn push(
+        &mut self,
+        prover_service: &Ps,
+        height: u64,
+        state_transition_data: TransitionData<Ps>,
+    ) {
+        let header_hash = state_transition_data.da_block_header.hash();
+        prover_service.submit_witness(state_transition_data).await;
+        let index = self.slots.partition_point(|slot| slot.height < height);
+        self.slots.insert(
+            index,
+            QueuedSlot {
+                height,
+                header_hash,
+                started: false,
+                failed: false,
+            },
+        );
+    }
+}
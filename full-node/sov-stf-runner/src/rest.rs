@@ -0,0 +1,279 @@
+//! A REST gateway exposing the JSON-RPC methods of the rollup over plain HTTP.
+//!
+//! Every method `<namespace>_<method>` registered in the RPC module is served at
+//! `/v1/<namespace>/<method>`. Queries are served on `GET`, with the parameters taken from the query
+//! string, and on `POST` with the parameters taken from the JSON body. Transaction submissions are
+//! only served on `POST`. The OpenAPI description of the gateway is served at `/openapi.json`.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use jsonrpsee::core::server::MethodCallback;
+use jsonrpsee::types::error::{INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE, PARSE_ERROR_CODE};
+use jsonrpsee::RpcModule;
+use serde_json::{json, Value};
+use tracing::info;
+
+/// The RPC methods submitting transactions to the rollup. They are only served on `POST`.
+pub const TX_SUBMISSION_METHODS: &[&str] = &["sequencer_acceptTx", "sequencer_publishBatch"];
+
+/// How an RPC method is exposed by the [`RestGateway`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestEndpointKind {
+    /// A read-only method, served on `GET` and `POST`.
+    Query,
+    /// A method submitting transactions, served on `POST` only.
+    Submission,
+}
+
+/// Serves the JSON-RPC methods of an [`RpcModule`] as REST endpoints.
+#[derive(Clone)]
+pub struct RestGateway {
+    methods: RpcModule<()>,
+    endpoints: BTreeMap<String, RestEndpointKind>,
+}
+
+impl RestGateway {
+    /// Creates a gateway for all the methods of `methods`. Subscriptions can't be served over REST
+    /// and are skipped, as well as methods without a namespace.
+    pub fn new(methods: RpcModule<()>) -> Self {
+        let endpoints = methods
+            .method_names()
+            .filter(|name| name.contains('_'))
+            .filter(|name| {
+                !matches!(
+                    methods.method(name),
+                    Some(MethodCallback::Subscription(_) | MethodCallback::Unsubscription(_))
+                )
+            })
+            .map(|name| {
+                let kind = if TX_SUBMISSION_METHODS.contains(&name) {
+                    RestEndpointKind::Submission
+                } else {
+                    RestEndpointKind::Query
+                };
+                (name.to_string(), kind)
+            })
+            .collect();
+
+        Self { methods, endpoints }
+    }
+
+    /// Returns the RPC methods served by the gateway, and how they are exposed.
+    pub fn endpoints(&self) -> &BTreeMap<String, RestEndpointKind> {
+        &self.endpoints
+    }
+
+    /// Returns the OpenAPI 3 description of the endpoints of the gateway.
+    pub fn openapi(&self) -> Value {
+        let mut paths = serde_json::Map::new();
+        for (name, kind) in &self.endpoints {
+            let (namespace, method) = name.split_once('_').expect("Endpoints are namespaced");
+            let post = json!({
+                "operationId": name,
+                "tags": [namespace],
+                "summary": format!("Calls the `{}` RPC method", name),
+                "requestBody": {
+                    "description": "The parameters of the method, by name or by position",
+                    "required": false,
+                    "content": { "application/json": { "schema": {
+                        "oneOf": [{ "type": "object" }, { "type": "array" }]
+                    } } }
+                },
+                "responses": responses(),
+            });
+
+            let mut operations = serde_json::Map::new();
+            if *kind == RestEndpointKind::Query {
+                operations.insert(
+                    "get".to_string(),
+                    json!({
+                        "operationId": format!("{}_get", name),
+                        "tags": [namespace],
+                        "summary": format!("Calls the `{}` RPC method", name),
+                        "description": "The query string holds the parameters of the method by name. Values are parsed as JSON when possible, and as strings otherwise",
+                        "responses": responses(),
+                    }),
+                );
+            }
+            operations.insert("post".to_string(), post);
+            paths.insert(format!("/v1/{}/{}", namespace, method), operations.into());
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "Rollup REST API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": paths,
+        })
+    }
+
+    /// Calls the RPC method `method` with `params`, and maps the JSON-RPC response to an HTTP status
+    /// and body. Successful calls return the result of the method, failed calls return the JSON-RPC error.
+    pub async fn call(&self, method: &str, params: Option<Value>) -> (StatusCode, Value) {
+        let mut request = json!({ "jsonrpc": "2.0", "id": 0, "method": method });
+        if let Some(params) = params {
+            request["params"] = params;
+        }
+
+        let response = match self.methods.raw_json_request(&request.to_string(), 1).await {
+            Ok((response, _)) => response,
+            Err(e) => return internal_error(e),
+        };
+        let mut response: Value = match serde_json::from_str(&response.result) {
+            Ok(response) => response,
+            Err(e) => return internal_error(e),
+        };
+
+        match response.get_mut("error") {
+            Some(error) => {
+                let status = match error["code"].as_i64().map(|code| code as i32) {
+                    Some(METHOD_NOT_FOUND_CODE) => StatusCode::NOT_FOUND,
+                    Some(INVALID_PARAMS_CODE | PARSE_ERROR_CODE) => StatusCode::BAD_REQUEST,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, json!({ "error": error.take() }))
+            }
+            None => (StatusCode::OK, response["result"].take()),
+        }
+    }
+
+    /// Returns the [`Router`] serving the gateway.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/openapi.json", get(openapi))
+            .route("/v1/:namespace/:method", get(query).post(submit))
+            .with_state(Arc::new(self))
+    }
+
+    fn endpoint(
+        &self,
+        namespace: &str,
+        method: &str,
+    ) -> Result<(String, RestEndpointKind), Response> {
+        let name = format!("{}_{}", namespace, method);
+        match self.endpoints.get(&name) {
+            Some(kind) => Ok((name, *kind)),
+            None => Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("Unknown RPC method {}", name),
+            )),
+        }
+    }
+}
+
+/// Starts the REST gateway for `methods` at `listen_address`, and returns the address it is bound to.
+pub async fn start_rest_server(
+    methods: RpcModule<()>,
+    listen_address: SocketAddr,
+) -> anyhow::Result<SocketAddr> {
+    let server = axum::Server::try_bind(&listen_address)?
+        .serve(RestGateway::new(methods).router().into_make_service());
+    let bound_address = server.local_addr();
+    info!("Starting REST server at {} ", &bound_address);
+
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            tracing::error!("REST server failed: {}", e);
+        }
+    });
+    Ok(bound_address)
+}
+
+async fn openapi(State(gateway): State<Arc<RestGateway>>) -> Json<Value> {
+    Json(gateway.openapi())
+}
+
+async fn query(
+    State(gateway): State<Arc<RestGateway>>,
+    Path((namespace, method)): Path<(String, String)>,
+    Query(query): Query<BTreeMap<String, String>>,
+) -> Response {
+    let name = match gateway.endpoint(&namespace, &method) {
+        Ok((name, RestEndpointKind::Query)) => name,
+        Ok((name, RestEndpointKind::Submission)) => {
+            return error_response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                format!("{} submits transactions and must be called with POST", name),
+            )
+        }
+        Err(response) => return response,
+    };
+
+    let params = (!query.is_empty()).then(|| {
+        query
+            .into_iter()
+            .map(|(key, value)| {
+                let value = serde_json::from_str(&value).unwrap_or(Value::String(value));
+                (key, value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    });
+
+    let (status, body) = gateway.call(&name, params).await;
+    (status, Json(body)).into_response()
+}
+
+async fn submit(
+    State(gateway): State<Arc<RestGateway>>,
+    Path((namespace, method)): Path<(String, String)>,
+    body: Bytes,
+) -> Response {
+    let name = match gateway.endpoint(&namespace, &method) {
+        Ok((name, _)) => name,
+        Err(response) => return response,
+    };
+
+    let params = if body.is_empty() {
+        None
+    } else {
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(params @ (Value::Object(_) | Value::Array(_))) => Some(params),
+            Ok(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    "The body must be a JSON object or array".to_string(),
+                )
+            }
+            Err(e) => {
+                return error_response(StatusCode::BAD_REQUEST, format!("Invalid JSON body: {}", e))
+            }
+        }
+    };
+
+    let (status, body) = gateway.call(&name, params).await;
+    (status, Json(body)).into_response()
+}
+
+fn responses() -> Value {
+    json!({
+        "200": {
+            "description": "The result of the method",
+            "content": { "application/json": { "schema": {} } }
+        },
+        "400": { "description": "Invalid parameters" },
+        "404": { "description": "Unknown method" },
+        "500": { "description": "The method failed" },
+    })
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, Json(json!({ "error": { "message": message } }))).into_response()
+}
+
+fn internal_error(e: impl std::fmt::Display) -> (StatusCode, Value) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        json!({ "error": { "message": e.to_string() } }),
+    )
+}
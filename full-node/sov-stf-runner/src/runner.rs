@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 use std::net::SocketAddr;
 
+use anyhow::bail;
+use futures::{FutureExt, StreamExt};
 use jsonrpsee::RpcModule;
 use sov_db::ledger_db::{LedgerDB, SlotCommit};
 use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
@@ -9,10 +11,17 @@ use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_rollup_interface::zk::{StateTransitionData, Zkvm, ZkvmHost};
 use tokio::sync::oneshot;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use crate::metrics::{
+    RpcMetricsLogger, LEDGER_DB_SIZE_BYTES, SLOTS_PROCESSED, STF_EXECUTION_TIME_SECONDS,
+};
 use crate::verifier::StateTransitionVerifier;
-use crate::{ProofSubmissionStatus, ProverService, RunnerConfig};
+use crate::{
+    post_proof_to_da, start_metrics_server, start_rest_server, NotificationBus, ProverService,
+    ProvingPipeline, ReorgNotification, RollupNotification, RunnerConfig, SlotNotification,
+    TxStatus,
+};
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 type GenesisParams<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::GenesisParams;
@@ -33,7 +42,11 @@ where
     ledger_db: LedgerDB,
     state_root: StateRoot<Stf, Vm, Da::Spec>,
     listen_address: SocketAddr,
+    rest_listen_address: Option<SocketAddr>,
+    metrics_listen_address: Option<SocketAddr>,
     prover_service: Ps,
+    proving_pipeline: ProvingPipeline<Ps>,
+    notifications: NotificationBus,
 }
 
 /// Represents the possible modes of execution for a zkVM program
@@ -97,6 +110,17 @@ where
     ) -> Result<Self, anyhow::Error> {
         let rpc_config = runner_config.rpc_config;
 
+        // The storage and the ledger are separate databases, so a node killed while committing a slot
+        // may have finalized its storage without recording it in the ledger. The slot is then processed again.
+        if let Some(storage_version) = ledger_db.get_pending_slot_commit()? {
+            warn!(
+                "The node was stopped while committing a slot. Rolling back the finalized storage to version {}",
+                storage_version
+            );
+            storage_manager.rollback_finalized(storage_version)?;
+            ledger_db.clear_pending_slot_commit()?;
+        }
+
         let prev_state_root = match init_variant {
             InitVariant::Initialized(state_root) => {
                 debug!("Chain is already initialized. Skipping initialization.");
@@ -113,7 +137,10 @@ where
                 let storage = storage_manager.create_storage_on(&block_header)?;
                 let (genesis_root, initialized_storage) = stf.init_chain(storage, params);
                 storage_manager.save_change_set(&block_header, initialized_storage)?;
+                // Nothing is committed to the ledger for the genesis, so the pending commit is cleared explicitly
+                ledger_db.put_pending_slot_commit(storage_manager.finalized_version()?)?;
                 storage_manager.finalize(&block_header)?;
+                ledger_db.clear_pending_slot_commit()?;
                 info!(
                     "Chain initialization is done. Genesis root: 0x{}",
                     hex::encode(genesis_root.as_ref()),
@@ -122,13 +149,24 @@ where
             }
         };
 
-        let listen_address = SocketAddr::new(rpc_config.bind_host.parse()?, rpc_config.bind_port);
+        let bind_host = rpc_config.bind_host.parse()?;
+        let listen_address = SocketAddr::new(bind_host, rpc_config.bind_port);
+        let rest_listen_address = rpc_config
+            .rest_bind_port
+            .map(|port| SocketAddr::new(bind_host, port));
+        let metrics_listen_address = rpc_config
+            .metrics_bind_port
+            .map(|port| SocketAddr::new(bind_host, port));
 
         // Start the main rollup loop
         let item_numbers = ledger_db.get_next_items_numbers();
         let last_slot_processed_before_shutdown = item_numbers.slot_number - 1;
         let start_height = runner_config.start_height + last_slot_processed_before_shutdown;
 
+        let proving_pipeline =
+            ProvingPipeline::new(ledger_db.clone(), runner_config.max_proof_attempts)
+                .with_max_proving_lag(runner_config.max_proving_lag);
+
         Ok(Self {
             start_height,
             da_service,
@@ -137,19 +175,35 @@ where
             ledger_db,
             state_root: prev_state_root,
             listen_address,
+            rest_listen_address,
+            metrics_listen_address,
             prover_service,
+            proving_pipeline,
+            notifications: NotificationBus::default(),
         })
     }
 
     /// Starts a RPC server with provided rpc methods.
+    /// If a REST port is configured, the methods are also served by a [`crate::RestGateway`],
+    /// and if a metrics port is configured, the metrics of the node are served at `/metrics`.
     pub async fn start_rpc_server(
         &self,
         methods: RpcModule<()>,
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) {
+        if let Some(rest_listen_address) = self.rest_listen_address {
+            start_rest_server(methods.clone(), rest_listen_address)
+                .await
+                .unwrap();
+        }
+        if let Some(metrics_listen_address) = self.metrics_listen_address {
+            start_metrics_server(metrics_listen_address).await.unwrap();
+        }
+
         let listen_address = self.listen_address;
         let _handle = tokio::spawn(async move {
             let server = jsonrpsee::server::ServerBuilder::default()
+                .set_logger(RpcMetricsLogger)
                 .build([listen_address].as_ref())
                 .await
                 .unwrap();
@@ -165,11 +219,27 @@ where
         });
     }
 
+    /// Returns the bus on which the runner publishes a notification for every slot, batch, transaction
+    /// status change and event. It can be passed to [`crate::subscriptions_rpc_module`] to expose the
+    /// notifications over RPC.
+    pub fn notifications(&self) -> NotificationBus {
+        self.notifications.clone()
+    }
+
     /// Runs the rollup.
     pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
         let mut seen_block_headers: VecDeque<<Da::Spec as DaSpec>::BlockHeader> = VecDeque::new();
         let mut seen_receipts: VecDeque<_> = VecDeque::new();
+        // The state roots the seen blocks were applied on, to roll back to when they are reorganized
+        let mut seen_pre_state_roots: VecDeque<StateRoot<Stf, Vm, Da::Spec>> = VecDeque::new();
         let mut height = self.start_height;
+        // Only the headers finalized after the subscription are streamed
+        let mut finalized_headers = Box::pin(self.da_service.subscribe_finalized_header().await?);
+        let mut last_finalized_height = self
+            .da_service
+            .get_last_finalized_block_header()
+            .await?
+            .height();
         loop {
             debug!("Requesting data for height {}", height);
             let mut filtered_block = self.da_service.get_block_at(height).await?;
@@ -177,20 +247,53 @@ where
             // Checking if reorg happened or not.
             if let Some(prev_block_header) = seen_block_headers.back() {
                 if prev_block_header.hash() != filtered_block.header().prev_hash() {
-                    tracing::warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
+                    warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
+                    let mut dropped_hashes = Vec::new();
+                    let mut fork_block = None;
+                    // The blocks of the new branch are applied on the snapshots of their parents,
+                    // so only the state root needs to be rolled back. The snapshots of the dropped
+                    // blocks are discarded by the storage manager once their siblings are finalized.
                     while let Some(seen_block_header) = seen_block_headers.pop_back() {
-                        seen_receipts.pop_back();
+                        let receipts = seen_receipts
+                            .pop_back()
+                            .expect("Receipts are kept for each seen block");
+                        self.state_root = seen_pre_state_roots
+                            .pop_back()
+                            .expect("A pre-state root is kept for each seen block");
+                        self.notifications.publish_tx_statuses(
+                            receipts.batch_receipts(),
+                            TxStatus::Dropped {
+                                da_height: seen_block_header.height(),
+                            },
+                        );
+                        dropped_hashes.push(seen_block_header.hash());
+
                         let block = self
                             .da_service
                             .get_block_at(seen_block_header.height())
                             .await?;
                         if block.header().prev_hash() == seen_block_header.prev_hash() {
-                            height = seen_block_header.height();
-                            filtered_block = block;
+                            fork_block = Some(block);
                             break;
                         }
                     }
-                    tracing::info!("Resuming execution on height={}", height);
+                    let Some(fork_block) = fork_block else {
+                        bail!(
+                            "The DA layer reorganized finalized blocks: block at height={} doesn't extend the last finalized block",
+                            height - dropped_hashes.len() as u64
+                        );
+                    };
+                    height = fork_block.header().height();
+                    filtered_block = fork_block;
+
+                    dropped_hashes.reverse();
+                    self.notifications
+                        .publish(RollupNotification::Reorg(ReorgNotification::new(
+                            height,
+                            dropped_hashes,
+                            &self.state_root,
+                        )));
+                    info!("Resuming execution on height={}", height);
                 }
             }
 
@@ -215,8 +318,8 @@ where
             let pre_state = self
                 .storage_manager
                 .create_storage_on(filtered_block.header())?;
+            let stf_timer = STF_EXECUTION_TIME_SECONDS.start_timer();
             let slot_result = self.stf.apply_slot(
-                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                 &self.state_root,
                 pre_state,
                 Default::default(),
@@ -224,6 +327,8 @@ where
                 &filtered_block.validity_condition(),
                 &mut blobs,
             );
+            stf_timer.observe_duration();
+            SLOTS_PROCESSED.inc();
 
             for receipt in slot_result.batch_receipts {
                 data_to_commit.add_batch(receipt);
@@ -236,7 +341,6 @@ where
 
             let transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec> =
                 StateTransitionData {
-                    // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                     initial_state_root: self.state_root.clone(),
                     final_state_root: slot_result.state_root.clone(),
                     da_block_header: filtered_block.header().clone(),
@@ -248,54 +352,97 @@ where
 
             self.storage_manager
                 .save_change_set(filtered_block.header(), slot_result.change_set)?;
+            self.ledger_db
+                .set_last_executed_height(filtered_block.header().height())?;
 
             // ----------------
             // Create ZK proof.
             {
-                let header_hash = transition_data.da_block_header.hash();
-                self.prover_service.submit_witness(transition_data).await;
                 // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185):
                 //   This section will be moved and called upon block finalization once we have fork management ready.
-                self.prover_service
-                    .prove(header_hash.clone())
+                // The slot is proven while the next slots are executed, and the proofs are sent to the DA in order.
+                // Execution only waits for the proofs when it is `max_proving_lag` slots ahead.
+                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add handling for DA submission errors.
+                let aggregated_proofs = self
+                    .proving_pipeline
+                    .submit(&self.prover_service, transition_data)
                     .await
                     .expect("The proof creation should succeed");
-
-                loop {
-                    let status = self
-                        .prover_service
-                        .send_proof_to_da(header_hash.clone())
-                        .await;
-
-                    match status {
-                        Ok(ProofSubmissionStatus::Success) => {
-                            break;
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add timeout handling.
-                        Ok(ProofSubmissionStatus::ProofGenerationInProgress) => {
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add handling for DA submission errors.
-                        Err(e) => panic!("{:?}", e),
+                for aggregated_proof in aggregated_proofs {
+                    match post_proof_to_da(&self.da_service, &aggregated_proof).await? {
+                        Some(da_height) => info!(
+                            "Posted the aggregated proof of the slots proven before slot {} at DA height {}",
+                            filtered_block.header().height(),
+                            da_height
+                        ),
+                        None => info!(
+                            "Aggregated the proofs of the slots proven before slot {}",
+                            filtered_block.header().height()
+                        ),
                     }
                 }
             }
             let next_state_root = slot_result.state_root;
 
+            // Notify subscribers
+            self.notifications.publish_batches(
+                filtered_block.header().height(),
+                data_to_commit.batch_receipts(),
+            );
+            self.notifications
+                .publish(RollupNotification::Slot(SlotNotification::new(
+                    filtered_block.header().height(),
+                    filtered_block.header().hash(),
+                    &next_state_root,
+                )));
+
             seen_receipts.push_back(data_to_commit);
 
-            self.state_root = next_state_root;
+            seen_pre_state_roots
+                .push_back(std::mem::replace(&mut self.state_root, next_state_root));
             seen_block_headers.push_back(filtered_block.header().clone());
             height += 1;
 
             // ----------------
             // Finalization. Done after seen block for proper handling of instant finality
             // Can be moved to another thread to improve throughput
-            let last_finalized = self.da_service.get_last_finalized_block_header().await?;
+            // Consuming the headers finalized since the previous block, without waiting for new ones
+            loop {
+                match finalized_headers.next().now_or_never() {
+                    None => break,
+                    Some(Some(Ok(finalized_header))) => {
+                        last_finalized_height =
+                            last_finalized_height.max(finalized_header.height());
+                    }
+                    Some(Some(Err(e))) => {
+                        // Some finalized headers may have been missed, e.g. if the subscription lagged
+                        tracing::warn!(
+                            "Finalized header subscription failed: {}. Fetching the last finalized header",
+                            e
+                        );
+                        last_finalized_height = self
+                            .da_service
+                            .get_last_finalized_block_header()
+                            .await?
+                            .height();
+                    }
+                    Some(None) => {
+                        tracing::warn!("Finalized header subscription was closed. Resubscribing");
+                        finalized_headers =
+                            Box::pin(self.da_service.subscribe_finalized_header().await?);
+                        last_finalized_height = self
+                            .da_service
+                            .get_last_finalized_block_header()
+                            .await?
+                            .height();
+                        break;
+                    }
+                }
+            }
             // For safety we finalize blocks one by one
             tracing::info!(
                 "Last finalized header height is {}, ",
-                last_finalized.height()
+                last_finalized_height
             );
             // Checking all seen blocks, in case if there was delay in getting last finalized header.
             while let Some(earliest_seen_header) = seen_block_headers.front() {
@@ -303,15 +450,30 @@ where
                     "Checking seen header height={}",
                     earliest_seen_header.height()
                 );
-                if earliest_seen_header.height() <= last_finalized.height() {
+                if earliest_seen_header.height() <= last_finalized_height {
                     tracing::debug!(
                         "Finalizing seen header height={}",
                         earliest_seen_header.height()
                     );
+                    // The pending commit is cleared by the commit of the slot to the ledger
+                    self.ledger_db
+                        .put_pending_slot_commit(self.storage_manager.finalized_version()?)?;
                     self.storage_manager.finalize(earliest_seen_header)?;
+                    let finalized_height = earliest_seen_header.height();
                     seen_block_headers.pop_front();
+                    seen_pre_state_roots.pop_front();
                     let receipts = seen_receipts.pop_front().unwrap();
+                    self.notifications.publish_tx_statuses(
+                        receipts.batch_receipts(),
+                        TxStatus::Finalized {
+                            da_height: finalized_height,
+                        },
+                    );
                     self.ledger_db.commit_slot(receipts)?;
+                    match self.ledger_db.size_on_disk() {
+                        Ok(size) => LEDGER_DB_SIZE_BYTES.set(size as i64),
+                        Err(e) => warn!("Failed to read the size of the ledger DB: {}", e),
+                    }
                     continue;
                 }
 
@@ -0,0 +1,99 @@
+use borsh::BorshSerialize;
+use sov_db::ledger_db::LedgerDB;
+use sov_mock_da::{MockAddress, MockDaService, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::Proof;
+use sov_stf_runner::{post_proof_to_da, DaProofVerifier};
+
+const PROGRAM_ID: MockCodeCommitment = MockCodeCommitment([1; 32]);
+
+type TestVerifier = DaProofVerifier<MockDaService, MockZkvm<MockValidityCond>>;
+
+fn full_proof(program_id: MockCodeCommitment, is_valid: bool, log: &[u8]) -> Proof {
+    Proof::Full(
+        MockProof {
+            program_id,
+            is_valid,
+            log,
+        }
+        .encode_to_vec(),
+    )
+}
+
+#[tokio::test]
+async fn test_posted_proofs_are_verified() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+
+    let valid_proof = full_proof(PROGRAM_ID, true, &[1, 2, 3]);
+    let valid_height = post_proof_to_da(&da_service, &valid_proof)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Proofs which only hold public input aren't posted
+    let public_input = Proof::PublicInput(vec![4, 5, 6]);
+    assert_eq!(
+        None,
+        post_proof_to_da(&da_service, &public_input).await.unwrap()
+    );
+
+    // Proofs posted by other parties may be invalid or malformed
+    let invalid_proof = full_proof(PROGRAM_ID, false, &[7]);
+    let other_program_proof = full_proof(MockCodeCommitment([2; 32]), true, &[8]);
+    let mut rejected_heights = Vec::new();
+    for blob in [
+        invalid_proof.try_to_vec().unwrap(),
+        other_program_proof.try_to_vec().unwrap(),
+        public_input.try_to_vec().unwrap(),
+        vec![9, 9, 9],
+    ] {
+        rejected_heights.push(da_service.send_aggregated_zk_proof(&blob).await.unwrap());
+    }
+
+    let verifier = TestVerifier::new(da_service, ledger_db.clone(), PROGRAM_ID);
+    let accepted = verifier.verify_proofs_at(valid_height).await.unwrap();
+    assert_eq!(1, accepted.len());
+    assert_eq!(vec![1, 2, 3], accepted[0].public_output);
+    for height in rejected_heights {
+        assert!(verifier.verify_proofs_at(height).await.unwrap().is_empty());
+    }
+
+    let verified = ledger_db.get_verified_proofs(0, 10).unwrap();
+    assert_eq!(1, verified.len());
+    assert_eq!(valid_height, verified[0].0);
+    let Proof::Full(serialized_proof) = valid_proof else {
+        unreachable!("The valid proof is a full proof")
+    };
+    assert_eq!(serialized_proof, verified[0].1.proof);
+}
+
+#[tokio::test]
+async fn test_verifier_resumes_after_last_checked_height() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+
+    for log in 1..=3 {
+        let proof = full_proof(PROGRAM_ID, true, &[log]);
+        post_proof_to_da(&da_service, &proof).await.unwrap();
+    }
+    // The proof at height 1 was checked by a previous run, which recorded nothing
+    ledger_db.put_verified_proofs(1, vec![]).unwrap();
+
+    let verifier = TestVerifier::new(da_service, ledger_db.clone(), PROGRAM_ID);
+    // The verifier waits for the next DA height, which is never produced
+    let run = tokio::time::timeout(std::time::Duration::from_millis(500), verifier.run(1)).await;
+    assert!(run.is_err());
+
+    assert_eq!(Some(3), ledger_db.get_last_proof_checked_height().unwrap());
+    let heights: Vec<u64> = ledger_db
+        .get_verified_proofs(0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|(height, _)| height)
+        .collect();
+    assert_eq!(vec![2, 3], heights);
+}
@@ -1,13 +1,16 @@
+use sov_db::ledger_db::LedgerDB;
 use sov_mock_da::{
     MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash, MockValidityCond,
 };
 use sov_mock_zkvm::MockZkvm;
 use sov_rollup_interface::da::Time;
+use sov_rollup_interface::rpc::ProofJobStatus;
 use sov_rollup_interface::zk::StateTransitionData;
 use sov_stf_runner::mock::MockStf;
 use sov_stf_runner::{
-    ParallelProverService, ProofProcessingStatus, ProofSubmissionStatus, ProverService,
-    ProverServiceConfig, ProverServiceError, RollupProverConfig, WitnessSubmissionStatus,
+    AggregatedProofStatus, ParallelProverService, ProofProcessingStatus, ProofSubmissionStatus,
+    ProverService, ProverServiceConfig, ProverServiceError, ProvingPipeline, RollupProverConfig,
+    WitnessSubmissionStatus,
 };
 
 #[tokio::test]
@@ -84,10 +87,10 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
             .await
             .unwrap_err();
 
-        // The new job wasn't accepted.
+        // The new job wasn't started, but its witness is kept.
         assert_eq!(
         proof_submission_status.to_string(),
-        "Missing witness for: 0x0000000000000000000000000000000000000000000000000000000000000000");
+        "Witness for 0x0000000000000000000000000000000000000000000000000000000000000000 was submitted, but the proof generation is not triggered.");
     }
 
     vm.make_proof();
@@ -97,6 +100,9 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
 
     // Retry once the prover is available to process new proofs.
     {
+        let status = prover_service.prove(MockHash::from([0; 32])).await?;
+        assert_eq!(ProofProcessingStatus::ProvingInProgress, status);
+
         let header_hash = MockHash::from([(num_worker_threads + 1) as u8; 32]);
         prover_service
             .submit_witness(make_transition_data(header_hash))
@@ -162,6 +168,98 @@ async fn test_generate_multiple_proofs_for_the_same_witness() -> Result<(), anyh
     Ok(())
 }
 
+#[tokio::test]
+async fn test_aggregated_proof() -> Result<(), ProverServiceError> {
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover_with_block_jump(2);
+    assert_eq!(
+        prover_service.create_aggregated_proof().await?,
+        AggregatedProofStatus::Disabled
+    );
+
+    let aggregation_vm = MockZkvm::new(MockValidityCond::default());
+    let prover_service = prover_service.with_aggregation_vm(aggregation_vm.clone());
+    vm.make_proof();
+    aggregation_vm.make_proof();
+
+    for (i, header_hash) in [[1; 32], [2; 32]]
+        .map(MockHash::from)
+        .into_iter()
+        .enumerate()
+    {
+        assert_eq!(
+            prover_service.create_aggregated_proof().await?,
+            AggregatedProofStatus::NotEnoughProofs {
+                pending: i as u64,
+                required: 2
+            }
+        );
+        prover_service
+            .submit_witness(make_transition_data(header_hash))
+            .await;
+        prover_service.prove(header_hash).await?;
+        wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+    }
+
+    let status = prover_service.create_aggregated_proof().await?;
+    assert!(matches!(status, AggregatedProofStatus::Aggregated(_)));
+    // The aggregated proofs are discarded.
+    assert_eq!(
+        prover_service.create_aggregated_proof().await?,
+        AggregatedProofStatus::NotEnoughProofs {
+            pending: 0,
+            required: 2
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_aggregation_keeps_proofs() -> Result<(), ProverServiceError> {
+    let vm = MockZkvm::new(MockValidityCond::default());
+    let prover_service = ParallelProverService::<Vec<u8>, Vec<u8>, MockDaService, _, _>::new(
+        vm.clone(),
+        MockStf::<MockValidityCond>::default(),
+        MockDaVerifier::default(),
+        RollupProverConfig::Execute,
+        (),
+        num_cpus::get(),
+        ProverServiceConfig {
+            aggregated_proof_block_jump: 2,
+            num_workers: None,
+        },
+    )
+    .with_aggregation_vm(MockZkvm::new(MockValidityCond::default()));
+    vm.make_proof();
+
+    // The second slot doesn't start from the state root the first one ends at.
+    for (height, (initial_state_root, final_state_root)) in [(vec![0], vec![1]), (vec![2], vec![3])]
+        .into_iter()
+        .enumerate()
+    {
+        let header_hash = MockHash::from([height as u8 + 1; 32]);
+        let data = make_transition_data_at_height(header_hash, height as u64);
+        let data = StateTransitionData {
+            initial_state_root,
+            final_state_root,
+            da_block_header: data.da_block_header,
+            inclusion_proof: data.inclusion_proof,
+            completeness_proof: data.completeness_proof,
+            blobs: data.blobs,
+            state_transition_witness: data.state_transition_witness,
+        };
+        prover_service.submit_witness(data).await;
+        prover_service.prove(header_hash).await?;
+        wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+    }
+
+    assert!(prover_service.create_aggregated_proof().await.is_err());
+    // The proofs weren't dropped by the failed aggregation, which fails again.
+    assert!(prover_service.create_aggregated_proof().await.is_err());
+    Ok(())
+}
+
 struct TestProver {
     prover_service: ParallelProverService<
         [u8; 0],
@@ -174,15 +272,92 @@ struct TestProver {
     num_worker_threads: usize,
 }
 
+#[tokio::test]
+async fn test_pipelined_proving() -> Result<(), ProverServiceError> {
+    let TestProver {
+        prover_service,
+        vm,
+        num_worker_threads,
+    } = make_new_prover();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let mut pipeline = ProvingPipeline::new(ledger_db.clone(), 3)
+        .with_max_proving_lag(num_worker_threads as u64 + 1);
+
+    // All the workers are busy, and one more witness waits for a worker without blocking the pipeline.
+    for i in 0..=num_worker_threads {
+        let header_hash = MockHash::from([i as u8; 32]);
+        pipeline
+            .submit(
+                &prover_service,
+                make_transition_data_at_height(header_hash, i as u64),
+            )
+            .await?;
+    }
+    assert_eq!(num_worker_threads + 1, pipeline.pending_slots());
+
+    vm.make_proof();
+    pipeline.finish(&prover_service).await?;
+    assert_eq!(0, pipeline.pending_slots());
+
+    // The proofs were sent to the DA, so they can't be sent again.
+    for i in 0..=num_worker_threads {
+        let header_hash = MockHash::from([i as u8; 32]);
+        assert!(prover_service.send_proof_to_da(header_hash).await.is_err());
+    }
+
+    // The proof jobs are complete, and their witnesses were dropped.
+    let jobs = ledger_db.get_proof_jobs(0, num_worker_threads + 2).unwrap();
+    assert_eq!(num_worker_threads + 1, jobs.len());
+    for (_, job) in jobs {
+        assert_eq!(ProofJobStatus::Complete, job.status);
+        assert_eq!(1, job.attempts);
+        assert_eq!(None, job.state_transition_data);
+    }
+    assert!(ledger_db.get_unfinished_proof_jobs().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proving_lag_backpressure() -> Result<(), ProverServiceError> {
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let mut pipeline = ProvingPipeline::new(ledger_db.clone(), 3).with_max_proving_lag(1);
+
+    // Execution runs one slot ahead of proving, then waits for the proofs.
+    pipeline
+        .submit(
+            &prover_service,
+            make_transition_data_at_height(MockHash::from([1; 32]), 1),
+        )
+        .await?;
+    let blocked = tokio::time::timeout(
+        tokio::time::Duration::from_millis(500),
+        pipeline.submit(
+            &prover_service,
+            make_transition_data_at_height(MockHash::from([2; 32]), 2),
+        ),
+    )
+    .await;
+    assert!(blocked.is_err());
+    assert_eq!(2, pipeline.pending_slots());
+    assert_eq!(None, ledger_db.get_last_proven_height().unwrap());
+
+    vm.make_proof();
+    pipeline.finish(&prover_service).await?;
+    assert_eq!(0, pipeline.pending_slots());
+    assert_eq!(Some(2), ledger_db.get_last_proven_height().unwrap());
+
+    Ok(())
+}
+
 async fn wait_for_proof_proof_da_submission(
     header_hash: MockHash,
-    prover_service: &ParallelProverService<
-        [u8; 0],
-        Vec<u8>,
-        MockDaService,
-        MockZkvm<MockValidityCond>,
-        MockStf<MockValidityCond>,
-    >,
+    prover_service: &impl ProverService<DaService = MockDaService>,
 ) {
     for _ in 0..10 {
         let status = prover_service.send_proof_to_da(header_hash).await;
@@ -194,6 +369,10 @@ async fn wait_for_proof_proof_da_submission(
 }
 
 fn make_new_prover() -> TestProver {
+    make_new_prover_with_block_jump(1)
+}
+
+fn make_new_prover_with_block_jump(aggregated_proof_block_jump: u64) -> TestProver {
     let num_threads = num_cpus::get();
     let vm = MockZkvm::new(MockValidityCond::default());
 
@@ -209,7 +388,8 @@ fn make_new_prover() -> TestProver {
             (),
             num_threads,
             ProverServiceConfig {
-                aggregated_proof_block_jump: 1,
+                aggregated_proof_block_jump,
+                num_workers: None,
             },
         ),
         vm,
@@ -219,6 +399,13 @@ fn make_new_prover() -> TestProver {
 
 fn make_transition_data(
     header_hash: MockHash,
+) -> StateTransitionData<[u8; 0], Vec<u8>, MockDaSpec> {
+    make_transition_data_at_height(header_hash, 0)
+}
+
+fn make_transition_data_at_height(
+    header_hash: MockHash,
+    height: u64,
 ) -> StateTransitionData<[u8; 0], Vec<u8>, MockDaSpec> {
     StateTransitionData {
         initial_state_root: [],
@@ -226,7 +413,7 @@ fn make_transition_data(
         da_block_header: MockBlockHeader {
             prev_hash: [0; 32].into(),
             hash: header_hash,
-            height: 0,
+            height,
             time: Time::now(),
         },
         inclusion_proof: [0; 32],
@@ -0,0 +1,134 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use jsonrpsee::RpcModule;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sov_stf_runner::{RestEndpointKind, RestGateway};
+use tower::ServiceExt;
+
+#[derive(Deserialize)]
+struct BalanceQuery {
+    user: String,
+    amount: u64,
+}
+
+fn test_methods() -> RpcModule<()> {
+    let mut rpc = RpcModule::new(());
+    rpc.register_method("bank_balanceOf", |params, _| {
+        let query: BalanceQuery = params.parse()?;
+        Ok::<_, jsonrpsee::types::ErrorObjectOwned>(
+            json!({ "user": query.user, "amount": query.amount }),
+        )
+    })
+    .unwrap();
+    rpc.register_method("sequencer_acceptTx", |params, _| {
+        let tx: Value = params.one()?;
+        Ok::<_, jsonrpsee::types::ErrorObjectOwned>(json!({ "accepted": tx }))
+    })
+    .unwrap();
+    rpc.register_subscription(
+        "chain_subscribe",
+        "chain_notification",
+        "chain_unsubscribe",
+        |_, pending, _| async move {
+            pending.accept().await?;
+            Ok(())
+        },
+    )
+    .unwrap();
+    rpc
+}
+
+async fn send(gateway: &RestGateway, request: Request<Body>) -> (StatusCode, Value) {
+    let response = gateway.clone().router().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn test_endpoints_skip_subscriptions() {
+    let gateway = RestGateway::new(test_methods());
+    let endpoints = gateway.endpoints();
+
+    assert_eq!(endpoints.len(), 2);
+    assert_eq!(endpoints["bank_balanceOf"], RestEndpointKind::Query);
+    assert_eq!(
+        endpoints["sequencer_acceptTx"],
+        RestEndpointKind::Submission
+    );
+}
+
+#[test]
+fn test_openapi() {
+    let openapi = RestGateway::new(test_methods()).openapi();
+    let paths = openapi["paths"].as_object().unwrap();
+
+    assert_eq!(paths.len(), 2);
+    assert!(paths["/v1/bank/balanceOf"].get("get").is_some());
+    assert!(paths["/v1/bank/balanceOf"].get("post").is_some());
+    assert!(paths["/v1/sequencer/acceptTx"].get("get").is_none());
+    assert!(paths["/v1/sequencer/acceptTx"].get("post").is_some());
+}
+
+#[tokio::test]
+async fn test_query_and_submission() {
+    let gateway = RestGateway::new(test_methods());
+
+    let (status, body) = send(
+        &gateway,
+        Request::get("/v1/bank/balanceOf?user=alice&amount=100")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "user": "alice", "amount": 100 }));
+
+    let (status, body) = send(
+        &gateway,
+        Request::post("/v1/bank/balanceOf")
+            .body(Body::from(r#"{ "user": "bob", "amount": 5 }"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "user": "bob", "amount": 5 }));
+
+    let (status, _) = send(
+        &gateway,
+        Request::get("/v1/bank/balanceOf?user=alice")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(
+        &gateway,
+        Request::get("/v1/sequencer/acceptTx?tx=1")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    let (status, body) = send(
+        &gateway,
+        Request::post("/v1/sequencer/acceptTx")
+            .body(Body::from(r#"[{ "body": [1, 2, 3] }]"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "accepted": { "body": [1, 2, 3] } }));
+
+    let (status, _) = send(
+        &gateway,
+        Request::get("/v1/chain/subscribe")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
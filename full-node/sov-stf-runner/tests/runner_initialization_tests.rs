@@ -66,20 +66,27 @@ fn initialize_runner(
     let rollup_config = RollupConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: path.to_path_buf(),
+            pruning: Default::default(),
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
+                rest_bind_port: None,
+                metrics_bind_port: None,
             },
+            max_proof_attempts: 3,
+            max_proving_lag: 16,
         },
         da: MockDaConfig {
             sender_address: address,
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            num_workers: None,
         },
+        chain: Default::default(),
     };
 
     let da_service = MockDaService::new(address);
@@ -90,6 +97,7 @@ fn initialize_runner(
 
     let storage_config = sov_state::config::Config {
         path: path.to_path_buf(),
+        pruning: Default::default(),
     };
     let mut storage_manager = ProverStorageManager::new(storage_config).unwrap();
 
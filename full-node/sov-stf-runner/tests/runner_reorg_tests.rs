@@ -1,11 +1,15 @@
+use std::future::Future;
+use std::time::Duration;
+
 use sov_mock_da::{
     MockAddress, MockBlob, MockBlock, MockBlockHeader, MockDaConfig, MockDaService, MockDaSpec,
     MockDaVerifier, MockValidityCond, PlannedFork,
 };
 use sov_mock_zkvm::MockZkvm;
 use sov_stf_runner::{
-    InitVariant, ParallelProverService, ProverServiceConfig, RollupConfig, RollupProverConfig,
-    RpcConfig, RunnerConfig, StateTransitionRunner, StorageConfig,
+    InitVariant, ParallelProverService, ProverServiceConfig, ReorgNotification, RollupConfig,
+    RollupNotification, RollupProverConfig, RpcConfig, RunnerConfig, StateTransitionRunner,
+    StorageConfig,
 };
 
 mod hash_stf;
@@ -17,6 +21,7 @@ use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_state::storage::NativeStorage;
 use sov_state::{ProverStorage, Storage};
+use tokio::sync::broadcast;
 
 type MockInitVariant =
     InitVariant<HashStf<MockValidityCond>, MockZkvm<MockValidityCond>, MockDaSpec>;
@@ -67,10 +72,23 @@ async fn test_simple_reorg_case() {
         genesis_params,
     };
 
-    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service).await;
+    let (before, after, reorgs) = runner_execution(tmpdir.path(), init_variant, da_service).await;
     assert_ne!(before, after);
     assert_eq!(expected_state_root, after);
 
+    // Blocks 3 and 4 are replaced, and the fork is applied on the state after block 2
+    let (expected_fork_state_root, _) = get_expected_execution_hash_from(
+        &[1, 2, 3, 4, 5],
+        vec![vec![1, 1, 1, 1], vec![2, 2, 2, 2]],
+    );
+    assert_eq!(1, reorgs.len());
+    assert_eq!(3, reorgs[0].fork_da_height);
+    assert_eq!(2, reorgs[0].dropped_da_hashes.len());
+    assert_eq!(
+        format!("0x{}", hex::encode(expected_fork_state_root)),
+        reorgs[0].state_root
+    );
+
     let committed_root_hash = get_saved_root_hash(tmpdir.path()).unwrap().unwrap();
 
     assert_eq!(expected_committed_root_hash.unwrap(), committed_root_hash);
@@ -105,45 +123,159 @@ async fn test_instant_finality_data_stored() {
         genesis_params,
     };
 
-    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service).await;
+    let (before, after, reorgs) = runner_execution(tmpdir.path(), init_variant, da_service).await;
     assert_ne!(before, after);
     assert_eq!(expected_state_root, after);
+    assert!(reorgs.is_empty());
 
     let saved_root_hash = get_saved_root_hash(tmpdir.path()).unwrap().unwrap();
 
     assert_eq!(expected_root_hash.unwrap(), saved_root_hash);
 }
 
+#[tokio::test]
+async fn test_finalization_follows_finalized_headers() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let sequencer_address = MockAddress::new([11u8; 32]);
+    let genesis_params = vec![1, 2, 3, 4, 5];
+
+    let mut da_service = MockDaService::with_finality(sequencer_address, 1);
+    da_service.set_wait_attempts(10);
+    let genesis_header = da_service.get_last_finalized_block_header().await.unwrap();
+    let init_variant: MockInitVariant = InitVariant::Genesis {
+        block_header: genesis_header,
+        genesis_params: genesis_params.clone(),
+    };
+
+    let blobs: Vec<Vec<u8>> = (1..=26u8).map(|i| vec![i; 4]).collect();
+    let producer_da_service = da_service.clone();
+    let producer_blobs = blobs.clone();
+    let produce = |mut notifications: broadcast::Receiver<RollupNotification>,
+                   ledger_db: LedgerDB| async move {
+        let da_service = producer_da_service;
+        let blobs = producer_blobs;
+
+        // The blocks are finalized as their headers are streamed
+        for height in 1..=3 {
+            submit_and_wait(&da_service, &mut notifications, &blobs, height).await;
+        }
+
+        // No header is streamed while finality is paused
+        da_service.pause_finality().await;
+        for height in 4..=23 {
+            submit_and_wait(&da_service, &mut notifications, &blobs, height).await;
+        }
+        assert_eq!(2, ledger_db.get_head_slot().unwrap().unwrap().0 .0);
+
+        // The headers finalized on resume overflow the subscription, so the runner falls back to the
+        // last finalized header
+        da_service.resume_finality().await;
+        submit_and_wait(&da_service, &mut notifications, &blobs, 24).await;
+
+        // The runner resubscribes once the subscription is closed
+        da_service.close_finalized_header_subscriptions();
+        submit_and_wait(&da_service, &mut notifications, &blobs, 25).await;
+        da_service.send_transaction(&blobs[25]).await.unwrap();
+    };
+
+    let (before, after, reorgs) =
+        runner_execution_with(tmpdir.path(), init_variant, da_service, produce).await;
+    assert_ne!(before, after);
+    assert!(reorgs.is_empty());
+    let (expected_state_root, _) = get_expected_execution_hash_from(&genesis_params, blobs.clone());
+    assert_eq!(expected_state_root, after);
+
+    // All the blocks but the last one are finalized
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    assert_eq!(25, ledger_db.get_head_slot().unwrap().unwrap().0 .0);
+    let (_, expected_finalized_root_hash) =
+        get_expected_execution_hash_from(&genesis_params, blobs[..25].to_vec());
+    drop(ledger_db);
+    assert_eq!(
+        expected_finalized_root_hash.unwrap(),
+        get_saved_root_hash(tmpdir.path()).unwrap().unwrap()
+    );
+}
+
+/// Submits the blob of the block at `height`, and waits for the runner to apply it.
+async fn submit_and_wait(
+    da_service: &MockDaService,
+    notifications: &mut broadcast::Receiver<RollupNotification>,
+    blobs: &[Vec<u8>],
+    height: u64,
+) {
+    da_service
+        .send_transaction(&blobs[height as usize - 1])
+        .await
+        .unwrap();
+    let timeout = Duration::from_secs(10);
+    loop {
+        match tokio::time::timeout(timeout, notifications.recv()).await {
+            Ok(Ok(RollupNotification::Slot(slot))) if slot.da_height >= height => return,
+            Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+            Ok(Err(e)) => panic!("The notifications were closed: {}", e),
+            Err(_) => panic!(
+                "The slot at height {} wasn't applied in {:?}",
+                height, timeout
+            ),
+        }
+    }
+}
+
 async fn runner_execution(
     path: &std::path::Path,
     init_variant: MockInitVariant,
     da_service: MockDaService,
-) -> ([u8; 32], [u8; 32]) {
+) -> ([u8; 32], [u8; 32], Vec<ReorgNotification>) {
+    runner_execution_with(path, init_variant, da_service, |_, _| async {}).await
+}
+
+/// Runs the runner until it runs out of DA blocks, while `produce` submits new blocks. `produce` gets
+/// the notifications of the runner and its ledger.
+async fn runner_execution_with<F, Fut>(
+    path: &std::path::Path,
+    init_variant: MockInitVariant,
+    da_service: MockDaService,
+    produce: F,
+) -> ([u8; 32], [u8; 32], Vec<ReorgNotification>)
+where
+    F: FnOnce(broadcast::Receiver<RollupNotification>, LedgerDB) -> Fut,
+    Fut: Future<Output = ()>,
+{
     let rollup_config = RollupConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: path.to_path_buf(),
+            pruning: Default::default(),
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
+                rest_bind_port: None,
+                metrics_bind_port: None,
             },
+            max_proof_attempts: 3,
+            max_proving_lag: 16,
         },
         da: MockDaConfig {
             sender_address: da_service.get_sequencer_address(),
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            num_workers: None,
         },
+        chain: Default::default(),
     };
 
     let ledger_db = LedgerDB::with_path(path).unwrap();
+    let ledger_db_handle = ledger_db.clone();
 
     let stf = HashStf::<MockValidityCond>::new();
 
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path.clone(),
+        pruning: rollup_config.storage.pruning,
     };
     let mut storage_manager = ProverStorageManager::new(storage_config).unwrap();
 
@@ -173,12 +305,21 @@ async fn runner_execution(
     )
     .unwrap();
 
+    let mut notifications = runner.notifications().subscribe();
+    let producer = produce(runner.notifications().subscribe(), ledger_db_handle);
     let before = *runner.get_state_root();
-    let end = runner.run_in_process().await;
+    let (end, ()) = tokio::join!(runner.run_in_process(), producer);
     assert!(end.is_err());
     let after = *runner.get_state_root();
 
-    (before, after)
+    let mut reorgs = Vec::new();
+    while let Ok(notification) = notifications.try_recv() {
+        if let RollupNotification::Reorg(reorg) = notification {
+            reorgs.push(reorg);
+        }
+    }
+
+    (before, after, reorgs)
 }
 
 fn get_saved_root_hash(
@@ -186,6 +327,7 @@ fn get_saved_root_hash(
 ) -> anyhow::Result<Option<<ProverStorage<S, Q> as Storage>::Root>> {
     let storage_config = sov_state::config::Config {
         path: path.to_path_buf(),
+        pruning: Default::default(),
     };
     let mut storage_manager = ProverStorageManager::<MockDaSpec, S>::new(storage_config).unwrap();
     let finalized_storage = storage_manager.create_finalized_storage()?;
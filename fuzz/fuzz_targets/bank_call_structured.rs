@@ -0,0 +1,107 @@
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{self, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use sov_bank::{
+    get_genesis_token_address, get_token_address, Bank, BankConfig, CallMessage, TokenConfig,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{Context, Module, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+type Address = <C as Spec>::Address;
+
+const MAX_CALLS: usize = 64;
+
+// Dispatch structurally valid calls between a few accounts, and check that the supply of every
+// token stays equal to the sum of its balances.
+fuzz_target!(|data: &[u8]| {
+    let _ = run(&mut Unstructured::new(data));
+});
+
+fn run(u: &mut Unstructured) -> arbitrary::Result<()> {
+    let users: Vec<Address> = (1..=4u8).map(|i| Address::from([i; 32])).collect();
+    let sequencer = Address::from([0xff; 32]);
+
+    let config = BankConfig::<C> {
+        tokens: ["sov-fuzz-token", "sov-fuzz-token-2"]
+            .into_iter()
+            .map(|token_name| TokenConfig {
+                token_name: token_name.to_string(),
+                address_and_balances: users.iter().map(|user| (*user, 1_000_000)).collect(),
+                authorized_minters: vec![users[0]],
+                admins: vec![users[0]],
+                max_supply: None,
+                metadata: None,
+                salt: 0,
+            })
+            .collect(),
+        fee_settlement: Default::default(),
+    };
+    let mut tokens: Vec<Address> = config
+        .tokens
+        .iter()
+        .map(|token| get_genesis_token_address::<C>(&token.token_name, token.salt))
+        .collect();
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::<C>::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&config, &mut working_set).unwrap();
+    let mut checkpoint = working_set.checkpoint();
+
+    for height in 0..MAX_CALLS {
+        if u.is_empty() {
+            break;
+        }
+        // The calls only involve known addresses, so that every balance is accounted for.
+        let addresses: Vec<Address> = users.iter().chain(&tokens).copied().collect();
+        let sender = *u.choose(&users)?;
+        let msg =
+            CallMessage::<C>::arbitrary_with_addresses(u, &mut |u| u.choose(&addresses).copied())?;
+        let created_token = match &msg {
+            CallMessage::CreateToken {
+                token_name, salt, ..
+            } => Some(get_token_address::<C>(token_name, sender.as_ref(), *salt)),
+            _ => None,
+        };
+
+        let context = C::new(sender, sequencer, height as u64);
+        let mut working_set = checkpoint.to_revertable();
+        checkpoint = match bank.call(msg, &context, &mut working_set) {
+            Ok(_) => {
+                if let Some(token_address) = created_token {
+                    tokens.push(token_address);
+                }
+                working_set.checkpoint()
+            }
+            Err(_) => working_set.revert(),
+        };
+
+        let mut working_set = checkpoint.to_revertable();
+        for token_address in &tokens {
+            let total_supply = bank
+                .get_total_supply_of(token_address, &mut working_set)
+                .expect("Tokens are never removed");
+            let balances = users
+                .iter()
+                .chain(&tokens)
+                .map(|address| {
+                    bank.get_balance_of(*address, *token_address, &mut working_set)
+                        .unwrap_or_default()
+                })
+                .fold(0u64, |sum, balance| {
+                    sum.checked_add(balance)
+                        .expect("The balances overflow the total supply")
+                });
+            assert_eq!(
+                total_supply, balances,
+                "The total supply of {} isn't the sum of its balances",
+                token_address
+            );
+        }
+        checkpoint = working_set.checkpoint();
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+#![no_main]
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use demo_stf::genesis_config::{get_genesis_config, GenesisPaths};
+use demo_stf::runtime::Runtime;
+use libfuzzer_sys::arbitrary::{self, Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use sov_bank::{get_genesis_token_address, Bank};
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{EncodeCall, PrivateKey, Spec};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisConfig;
+use sov_modules_stf_blueprint::{GenesisParams, SequencerOutcome};
+use sov_sequencer_registry::SequencerRegistry;
+use sov_test_utils::{TestGenesisParams, TestRunner};
+
+type C = DefaultContext;
+type RT = Runtime<C, MockDaSpec>;
+type Address = <C as Spec>::Address;
+
+const MAX_SLOTS: usize = 8;
+const MAX_TXS_PER_SLOT: usize = 8;
+
+const GENESIS_DIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../examples/test-data/genesis/integration-tests"
+);
+const DEPLOYER_KEY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../examples/test-data/keys/token_deployer_private_key.json"
+);
+
+#[derive(serde::Deserialize)]
+struct KeyFile {
+    private_key: DefaultPrivateKey,
+}
+
+// Sign transactions with arbitrary envelopes and messages for the standard modules, and apply them
+// through the STF of the demo rollup. Applying a slot must never panic, whatever the transactions.
+fuzz_target!(|data: &[u8]| {
+    let _ = run(&mut Unstructured::new(data));
+});
+
+fn genesis_params() -> TestGenesisParams<RT> {
+    let dir = Path::new(GENESIS_DIR);
+    let chain_state = std::fs::read_to_string(dir.join("chain_state.json")).unwrap();
+    GenesisParams {
+        runtime: get_genesis_config::<C, MockDaSpec>(&GenesisPaths::from_dir(dir)).unwrap(),
+        kernel: BasicKernelGenesisConfig {
+            chain_state: serde_json::from_str(&chain_state).unwrap(),
+        },
+    }
+}
+
+fn run(u: &mut Unstructured) -> arbitrary::Result<()> {
+    let genesis = genesis_params();
+    let chain_id = genesis.kernel.chain_state.chain_id;
+    let deployer: KeyFile =
+        serde_json::from_str(&std::fs::read_to_string(DEPLOYER_KEY).unwrap()).unwrap();
+    // The deployer holds the tokens of the genesis, the other keys don't have any funds.
+    let mut keys = vec![deployer.private_key];
+    for seed in 1..=2u8 {
+        keys.push(DefaultPrivateKey::arbitrary(&mut Unstructured::new(&[seed; 32])).unwrap());
+    }
+
+    let mut addresses: Vec<Address> = keys
+        .iter()
+        .map(|key| key.pub_key().to_address::<Address>())
+        .collect();
+    addresses.push(get_genesis_token_address::<C>("sov-demo-token", 0));
+
+    let mut runner = TestRunner::<RT>::new(genesis);
+    let mut nonces = HashMap::<usize, u64>::new();
+    for _ in 0..MAX_SLOTS {
+        if u.is_empty() {
+            break;
+        }
+
+        let tx_count = u.int_in_range(0..=MAX_TXS_PER_SLOT)?;
+        for _ in 0..tx_count {
+            let sender = u.choose_index(keys.len())?;
+            let message = match u.int_in_range(0..=2)? {
+                0 => <RT as EncodeCall<Bank<C>>>::encode_call(
+                    sov_bank::CallMessage::arbitrary_with_addresses(u, &mut |u| {
+                        u.choose(&addresses).copied()
+                    })?,
+                ),
+                1 => <RT as EncodeCall<SequencerRegistry<C, MockDaSpec>>>::encode_call(
+                    u.arbitrary()?,
+                ),
+                // Messages which can't be decoded get the sequencer slashed
+                _ => u.arbitrary()?,
+            };
+            // Most transactions use the expected chain id and the next nonce of their sender.
+            let chain_id = if u.ratio(1, 8)? {
+                u.arbitrary()?
+            } else {
+                chain_id
+            };
+            let nonce = nonces.entry(sender).or_default();
+            let tx_nonce = if u.ratio(1, 8)? {
+                u.arbitrary()?
+            } else {
+                *nonce
+            };
+            *nonce += 1;
+
+            runner.submit_tx(Transaction::<C>::new_signed_tx_with_options(
+                &keys[sender],
+                message,
+                chain_id,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                0,
+                tx_nonce,
+                None,
+                u.arbitrary()?,
+            ));
+        }
+
+        let height = runner.height();
+        let receipt = runner.advance_slot();
+        assert_eq!(runner.height(), height + 1);
+        if let Some(batch) = receipt.batch_receipts.first() {
+            if let SequencerOutcome::Rewarded(_) = batch.inner {
+                assert_eq!(
+                    batch.tx_receipts.len(),
+                    tx_count,
+                    "Every transaction of a rewarded batch must have a receipt"
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -2,9 +2,10 @@ use sov_chain_state::ChainState;
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
 use sov_modules_api::macros::DefaultRuntime;
 use sov_modules_api::transaction::Transaction;
+use sov_modules_api::tx_middleware::TxMiddlewares;
 use sov_modules_api::{
     AccessoryWorkingSet, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, MessageCodec,
-    PublicKey, Spec,
+    Spec,
 };
 use sov_modules_stf_blueprint::{Runtime, RuntimeTxHook, SequencerOutcome};
 use sov_state::Storage;
@@ -29,14 +30,12 @@ impl<C: Context> TxHooks for TestRuntime<C> {
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
-        _working_set: &mut sov_modules_api::WorkingSet<C>,
+        working_set: &mut sov_modules_api::WorkingSet<C>,
         arg: &RuntimeTxHook<C>,
     ) -> anyhow::Result<C> {
         let RuntimeTxHook { height, sequencer } = arg;
-        let sender = tx.pub_key().to_address();
-        let sequencer = sequencer.to_address();
-
-        Ok(C::new(sender, sequencer, *height))
+        // Without any middleware, the sender and the sequencer are derived from their public keys
+        TxMiddlewares::new().pre_dispatch(tx, sequencer, *height, working_set)
     }
 
     fn post_dispatch_tx_hook(
@@ -1,18 +1,28 @@
-use anyhow::{ensure, Result};
-use sov_modules_api::{CallResponse, Context, Signature, StateMapAccessor, WorkingSet};
+use anyhow::{bail, ensure, Result};
+use sov_modules_api::{
+    CallResponse, Context, Signature, StateMapAccessor, StateValueAccessor, WorkingSet,
+};
 
-use crate::Accounts;
+use crate::{Accounts, CallPrefix, Guardians, Recovery, SessionKey, MAX_CALL_PREFIXES};
 
 /// To update the account's public key, the sender must sign this message as proof of possession of the new key.
 pub const UPDATE_ACCOUNT_MSG: [u8; 32] = [1; 32];
 
+/// To authorize a session key, the key must sign this prefix followed by the address of the sender, as proof of possession.
+pub const AUTHORIZE_SESSION_KEY_MSG_PREFIX: [u8; 32] = [2; 32];
+
+/// Returns the message a session key signs to be authorized by `account`.
+pub fn authorize_session_key_msg(account: &impl AsRef<[u8]>) -> Vec<u8> {
+    [&AUTHORIZE_SESSION_KEY_MSG_PREFIX[..], account.as_ref()].concat()
+}
+
 /// Represents the available call messages for interacting with the sov-accounts module.
 #[cfg_attr(
     feature = "native",
     derive(schemars::JsonSchema),
     derive(sov_modules_api::macros::CliWalletArg),
     schemars(
-        bound = "C::PublicKey: ::schemars::JsonSchema, C::Signature: ::schemars::JsonSchema",
+        bound = "C::Address: ::schemars::JsonSchema, C::PublicKey: ::schemars::JsonSchema, C::Signature: ::schemars::JsonSchema",
         rename = "CallMessage"
     )
 )]
@@ -31,6 +41,55 @@ pub enum CallMessage<C: Context> {
         /// A valid signature from the new public key
         C::Signature,
     ),
+    /// Authorizes a session key to sign transactions on behalf of the sender, within a scope.
+    /// Replaces the scope of the key if it was already authorized by the sender.
+    /// The sender must be in possession of the session key.
+    AuthorizeSessionKey {
+        /// The session public key.
+        session_key: C::PublicKey,
+        /// A valid signature from the session key of [`authorize_session_key_msg`] of the sender.
+        signature: C::Signature,
+        /// The key can only sign runtime messages starting with one of these prefixes.
+        call_prefixes: Vec<CallPrefix>,
+        /// The maximum amount of gas tokens the transactions signed by the key can reserve for their fees.
+        /// It doesn't bound the tokens moved by the calls.
+        fee_cap: u64,
+        /// The last slot height at which the key can sign transactions.
+        expires_at: u64,
+    },
+    /// Revokes a session key of the sender.
+    RevokeSessionKey {
+        /// The session public key.
+        session_key: C::PublicKey,
+    },
+    /// Sets the guardians of the sender, which can jointly replace its public key if it is lost.
+    /// Cancels the recovery of the sender in progress, if any.
+    SetGuardians {
+        /// The addresses of the guardians.
+        guardians: Vec<C::Address>,
+        /// The number of guardians which must approve the same new public key.
+        threshold: u32,
+        /// The number of slots during which the sender can veto a recovery approved by the guardians.
+        timelock: u64,
+    },
+    /// Removes the guardians of the sender, and cancels its recovery in progress, if any.
+    RemoveGuardians,
+    /// Approves replacing the public key of an account, on behalf of one of its guardians.
+    /// Replaces the previous approval of the guardian for this account, if any.
+    ApproveRecovery {
+        /// The address of the account to recover.
+        account: C::Address,
+        /// The new public key of the account.
+        new_pub_key: C::PublicKey,
+    },
+    /// Replaces the public key of an account with the key approved by its guardians, once the timelock elapsed.
+    /// Can be sent by anyone.
+    ExecuteRecovery {
+        /// The address of the account to recover.
+        account: C::Address,
+    },
+    /// Cancels the recovery of the sender in progress, vetoing the key approved by its guardians.
+    VetoRecovery,
 }
 
 impl<C: Context> Accounts<C> {
@@ -41,27 +100,251 @@ impl<C: Context> Accounts<C> {
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
         self.exit_if_account_exists(&new_pub_key, working_set)?;
+        ensure!(
+            self.session_keys.get(&new_pub_key, working_set).is_none(),
+            "New PublicKey is a session key"
+        );
+
+        // Proof that the sender is in possession of the `new_pub_key`.
+        signature.verify(&new_pub_key, &UPDATE_ACCOUNT_MSG)?;
 
-        let pub_key = self.public_keys.get_or_err(context.sender(), working_set)?;
+        self.replace_public_key(context.sender(), &new_pub_key, working_set)?;
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the public key of the account `addr` by `new_pub_key`. The account data remains the same.
+    fn replace_public_key(
+        &self,
+        addr: &C::Address,
+        new_pub_key: &C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let pub_key = self.public_keys.get_or_err(addr, working_set)?;
 
         let account = self.accounts.remove_or_err(&pub_key, working_set)?;
         // Sanity check
+        ensure!(addr == &account.addr, "Inconsistent account data");
+
+        self.accounts.set(new_pub_key, &account, working_set);
+        self.public_keys.set(addr, new_pub_key, working_set);
+        Ok(())
+    }
+
+    pub(crate) fn set_guardians(
+        &self,
+        guardians: Guardians<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        self.public_keys.get_or_err(context.sender(), working_set)?;
+        guardians.validate(context.sender())?;
+
+        self.guardians
+            .set(context.sender(), &guardians, working_set);
+        self.recoveries.delete(context.sender(), working_set);
+        working_set.add_event(
+            "set_guardians",
+            &format!(
+                "account: {}, guardians: {}, threshold: {}, timelock: {}",
+                context.sender(),
+                guardians.guardians.len(),
+                guardians.threshold,
+                guardians.timelock
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn remove_guardians(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        self.guardians
+            .remove(context.sender(), working_set)
+            .ok_or_else(|| anyhow::anyhow!("The sender has no guardians"))?;
+        self.recoveries.delete(context.sender(), working_set);
+        working_set.add_event(
+            "remove_guardians",
+            &format!("account: {}", context.sender()),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn approve_recovery(
+        &self,
+        account: C::Address,
+        new_pub_key: C::PublicKey,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        let guardians = match self.guardians.get(&account, working_set) {
+            Some(guardians) if guardians.is_guardian(context.sender()) => guardians,
+            _ => bail!("The sender isn't a guardian of account {}", account),
+        };
+        self.exit_if_account_exists(&new_pub_key, working_set)?;
+
+        let mut recovery = self
+            .recoveries
+            .get(&account, working_set)
+            .unwrap_or(Recovery {
+                approvals: vec![],
+                scheduled: None,
+            });
+        recovery.approve(
+            context.sender().clone(),
+            new_pub_key,
+            &guardians,
+            context.slot_height(),
+        );
+        if let Some(scheduled) = &recovery.scheduled {
+            working_set.add_event(
+                "recovery_scheduled",
+                &format!(
+                    "account: {}, executable_at: {}",
+                    account, scheduled.executable_at
+                ),
+            );
+        }
+        self.recoveries.set(&account, &recovery, working_set);
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn execute_recovery(
+        &self,
+        account: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let scheduled = self
+            .recoveries
+            .get(&account, working_set)
+            .and_then(|recovery| recovery.scheduled)
+            .ok_or_else(|| anyhow::anyhow!("No recovery of account {} was approved", account))?;
+        ensure!(
+            context.slot_height() >= scheduled.executable_at,
+            "The recovery of account {} can't be executed before height {}",
+            account,
+            scheduled.executable_at
+        );
+        self.exit_if_account_exists(&scheduled.new_pub_key, working_set)?;
         ensure!(
-            context.sender() == &account.addr,
-            "Inconsistent account data"
+            self.session_keys
+                .get(&scheduled.new_pub_key, working_set)
+                .is_none(),
+            "New PublicKey is a session key"
         );
 
-        // Proof that the sender is in possession of the `new_pub_key`.
-        signature.verify(&new_pub_key, &UPDATE_ACCOUNT_MSG)?;
+        self.replace_public_key(&account, &scheduled.new_pub_key, working_set)?;
+        self.recoveries.delete(&account, working_set);
+        working_set.add_event("execute_recovery", &format!("account: {}", account));
+        Ok(CallResponse::default())
+    }
 
-        // Update the public key (account data remains the same).
-        self.accounts.set(&new_pub_key, &account, working_set);
-        self.public_keys
-            .set(context.sender(), &new_pub_key, working_set);
+    pub(crate) fn veto_recovery(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        self.recoveries
+            .remove(context.sender(), working_set)
+            .ok_or_else(|| anyhow::anyhow!("The sender has no recovery in progress"))?;
+        working_set.add_event("veto_recovery", &format!("account: {}", context.sender()));
         Ok(CallResponse::default())
     }
 
+    pub(crate) fn authorize_session_key(
+        &self,
+        session_key: C::PublicKey,
+        signature: C::Signature,
+        call_prefixes: Vec<CallPrefix>,
+        fee_cap: u64,
+        expires_at: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        self.exit_if_account_exists(&session_key, working_set)?;
+        self.public_keys.get_or_err(context.sender(), working_set)?;
+
+        // Proof that the sender is in possession of the `session_key`.
+        signature.verify(&session_key, &authorize_session_key_msg(context.sender()))?;
+
+        ensure!(
+            !call_prefixes.is_empty() && call_prefixes.len() <= MAX_CALL_PREFIXES,
+            "A session key must have between 1 and {} call prefixes",
+            MAX_CALL_PREFIXES
+        );
+        ensure!(
+            expires_at >= context.slot_height(),
+            "Session key would expire at height {}, before the current height {}",
+            expires_at,
+            context.slot_height()
+        );
+        if let Some(existing) = self.session_keys.get(&session_key, working_set) {
+            ensure!(
+                &existing.account == context.sender(),
+                "Session key is already authorized by another account"
+            );
+        }
+
+        let session = SessionKey {
+            account: context.sender().clone(),
+            call_prefixes,
+            fee_cap,
+            fees_reserved: 0,
+            expires_at,
+        };
+        self.session_keys.set(&session_key, &session, working_set);
+        working_set.add_event(
+            "authorize_session_key",
+            &format!(
+                "account: {}, expires_at: {}, fee_cap: {}",
+                context.sender(),
+                expires_at,
+                fee_cap
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn revoke_session_key(
+        &self,
+        session_key: C::PublicKey,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key(context, working_set)?;
+        match self.session_keys.get(&session_key, working_set) {
+            Some(session) if &session.account == context.sender() => {
+                self.session_keys.delete(&session_key, working_set);
+                working_set.add_event(
+                    "revoke_session_key",
+                    &format!("account: {}", context.sender()),
+                );
+                Ok(CallResponse::default())
+            }
+            _ => bail!("Session key isn't authorized by the sender"),
+        }
+    }
+
+    /// Session keys can't manage the keys of their account, whatever their scope.
+    fn exit_if_session_key(&self, context: &C, working_set: &mut WorkingSet<C>) -> Result<()> {
+        ensure!(
+            self.active_session_keys
+                .get(context.sender(), working_set)
+                .is_none(),
+            "Session keys can't manage the keys of an account"
+        );
+        Ok(())
+    }
+
     fn exit_if_account_exists(
         &self,
         new_pub_key: &C::PublicKey,
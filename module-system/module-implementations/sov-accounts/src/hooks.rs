@@ -1,9 +1,18 @@
 use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
+use sov_modules_api::tx_middleware::{TxMiddleware, TxMiddlewareState};
+use sov_modules_api::{Context, StateMapAccessor, StateValueAccessor, WorkingSet};
 
 use crate::{Account, Accounts};
 
+/// The arguments of the pre-dispatch tx hook.
+pub struct AccountsTxHookArg<C: Context> {
+    /// The sequencer public key
+    pub sequencer: C::PublicKey,
+    /// The slot height at which the transaction is executed, checked against the expiry of session keys
+    pub height: u64,
+}
+
 /// The computed addresses of a pre-dispatch tx hook.
 pub struct AccountsTxHook<C: Context> {
     /// The tx sender address
@@ -23,30 +32,69 @@ impl<C: Context> Accounts<C> {
             .map(Ok)
             .unwrap_or_else(|| self.create_default_account(pubkey, working_set))
     }
+
+    /// Returns the next nonce of `account` in the nonce lane `nonce_lane`.
+    pub(crate) fn lane_nonce(
+        &self,
+        account: &Account<C>,
+        nonce_lane: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        match nonce_lane {
+            0 => account.nonce,
+            _ => self
+                .lane_nonces
+                .get(&(account.addr.clone(), nonce_lane), working_set)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 impl<C: Context> TxHooks for Accounts<C> {
     type Context = C;
-    type PreArg = C::PublicKey;
+    type PreArg = AccountsTxHookArg<C>;
     type PreResult = AccountsTxHook<C>;
 
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<C>,
         working_set: &mut WorkingSet<C>,
-        sequencer: &C::PublicKey,
+        arg: &AccountsTxHookArg<C>,
     ) -> anyhow::Result<AccountsTxHook<C>> {
-        let sender = self.get_or_create_default(tx.pub_key(), working_set)?;
+        let AccountsTxHookArg { sequencer, height } = arg;
+        let session_key = self.session_keys.get(tx.pub_key(), working_set);
+        let sender = match &session_key {
+            Some(session_key) => {
+                let pub_key = self
+                    .public_keys
+                    .get_or_err(&session_key.account, working_set)?;
+                self.accounts.get_or_err(&pub_key, working_set)?
+            }
+            None => self.get_or_create_default(tx.pub_key(), working_set)?,
+        };
+        // The post-dispatch hook isn't invoked when a pre-dispatch hook fails, so the marker of the
+        // previous transaction of the sender may be left over.
+        self.active_session_keys.delete(&sender.addr, working_set);
         let sequencer = self.get_or_create_default(sequencer, working_set)?;
         let tx_nonce = tx.nonce();
+        let lane_nonce = self.lane_nonce(&sender, tx.nonce_lane(), working_set);
 
         anyhow::ensure!(
-            sender.nonce == tx_nonce,
-            "Tx bad nonce, expected: {}, but found: {}",
+            lane_nonce == tx_nonce,
+            "Tx bad nonce in lane {}, expected: {}, but found: {}",
+            tx.nonce_lane(),
             tx_nonce,
-            sender.nonce
+            lane_nonce
         );
 
+        if let Some(mut session_key) = session_key {
+            session_key.authorize(tx, *height)?;
+            self.session_keys
+                .set(tx.pub_key(), &session_key, working_set);
+            self.active_session_keys
+                .set(&sender.addr, tx.pub_key(), working_set);
+        }
+
         Ok(AccountsTxHook {
             sender: sender.addr,
             sequencer: sequencer.addr,
@@ -56,12 +104,58 @@ impl<C: Context> TxHooks for Accounts<C> {
     fn post_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
-        _ctx: &C,
+        ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        let mut account = self.accounts.get_or_err(tx.pub_key(), working_set)?;
-        account.nonce += 1;
-        self.accounts.set(tx.pub_key(), &account, working_set);
+        // Transactions signed by a session key increment the nonce of the account the key acts for
+        let pub_key = match self.active_session_keys.get(ctx.sender(), working_set) {
+            Some(_) => {
+                self.active_session_keys.delete(ctx.sender(), working_set);
+                self.public_keys.get_or_err(ctx.sender(), working_set)?
+            }
+            None => tx.pub_key().clone(),
+        };
+        let mut account = self.accounts.get_or_err(&pub_key, working_set)?;
+        match tx.nonce_lane() {
+            0 => {
+                account.nonce += 1;
+                self.accounts.set(&pub_key, &account, working_set);
+            }
+            nonce_lane => {
+                let lane = (account.addr, nonce_lane);
+                let nonce = self.lane_nonces.get(&lane, working_set).unwrap_or_default();
+                self.lane_nonces.set(&lane, &(nonce + 1), working_set);
+            }
+        }
         Ok(())
     }
 }
+
+/// Authenticates the sender of the transaction, possibly through a session key, and checks and increments its nonce.
+impl<C: Context> TxMiddleware<C> for Accounts<C> {
+    fn pre_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        state: &mut TxMiddlewareState<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let arg = AccountsTxHookArg {
+            sequencer: state.sequencer_pub_key.clone(),
+            height: state.height,
+        };
+        let AccountsTxHook { sender, sequencer } =
+            self.pre_dispatch_tx_hook(tx, working_set, &arg)?;
+        state.sender = sender;
+        state.sequencer = sequencer;
+        Ok(())
+    }
+
+    fn post_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        self.post_dispatch_tx_hook(tx, ctx, working_set)
+    }
+}
@@ -5,6 +5,8 @@ mod call;
 mod fuzz;
 mod genesis;
 mod hooks;
+mod recovery;
+mod session;
 pub use genesis::*;
 #[cfg(feature = "native")]
 mod query;
@@ -13,8 +15,12 @@ pub use query::*;
 #[cfg(test)]
 mod tests;
 
-pub use call::{CallMessage, UPDATE_ACCOUNT_MSG};
-pub use hooks::AccountsTxHook;
+pub use call::{
+    authorize_session_key_msg, CallMessage, AUTHORIZE_SESSION_KEY_MSG_PREFIX, UPDATE_ACCOUNT_MSG,
+};
+pub use hooks::{AccountsTxHook, AccountsTxHookArg};
+pub use recovery::{Guardians, Recovery, ScheduledRecovery, MAX_GUARDIANS};
+pub use session::{CallPrefix, SessionKey, MAX_CALL_PREFIXES};
 use sov_modules_api::{Context, Error, ModuleInfo, WorkingSet};
 
 impl<C: Context> FromIterator<C::PublicKey> for AccountConfig<C> {
@@ -30,7 +36,7 @@ impl<C: Context> FromIterator<C::PublicKey> for AccountConfig<C> {
 pub struct Account<C: Context> {
     /// The address of the account.
     pub addr: C::Address,
-    /// The current nonce value associated with the account.
+    /// The current nonce value associated with the account, in the default nonce lane `0`.
     pub nonce: u64,
 }
 
@@ -50,6 +56,28 @@ pub struct Accounts<C: Context> {
     /// Mapping from a public key to a corresponding account.
     #[state]
     pub(crate) accounts: sov_modules_api::StateMap<C::PublicKey, Account<C>>,
+
+    /// Mapping from an account address and a nonce lane to the next nonce of the lane.
+    /// The nonce of the default lane `0` is stored in the account itself.
+    #[state]
+    pub(crate) lane_nonces: sov_modules_api::StateMap<(C::Address, u64), u64>,
+
+    /// Mapping from a session public key to the account it acts for and its scope.
+    #[state]
+    pub(crate) session_keys: sov_modules_api::StateMap<C::PublicKey, SessionKey<C>>,
+
+    /// Mapping from an account address to the session key signing its transaction being executed, if any.
+    /// Set by the pre-dispatch hook and cleared by the post-dispatch hook.
+    #[state]
+    pub(crate) active_session_keys: sov_modules_api::StateMap<C::Address, C::PublicKey>,
+
+    /// Mapping from an account address to the guardians which can recover it.
+    #[state]
+    pub(crate) guardians: sov_modules_api::StateMap<C::Address, Guardians<C>>,
+
+    /// Mapping from an account address to its recovery in progress.
+    #[state]
+    pub(crate) recoveries: sov_modules_api::StateMap<C::Address, Recovery<C>>,
 }
 
 impl<C: Context> sov_modules_api::Module for Accounts<C> {
@@ -75,6 +103,46 @@ impl<C: Context> sov_modules_api::Module for Accounts<C> {
             call::CallMessage::UpdatePublicKey(new_pub_key, sig) => {
                 Ok(self.update_public_key(new_pub_key, sig, context, working_set)?)
             }
+            call::CallMessage::AuthorizeSessionKey {
+                session_key,
+                signature,
+                call_prefixes,
+                fee_cap,
+                expires_at,
+            } => Ok(self.authorize_session_key(
+                session_key,
+                signature,
+                call_prefixes,
+                fee_cap,
+                expires_at,
+                context,
+                working_set,
+            )?),
+            call::CallMessage::RevokeSessionKey { session_key } => {
+                Ok(self.revoke_session_key(session_key, context, working_set)?)
+            }
+            call::CallMessage::SetGuardians {
+                guardians,
+                threshold,
+                timelock,
+            } => Ok(self.set_guardians(
+                Guardians {
+                    guardians,
+                    threshold,
+                    timelock,
+                },
+                context,
+                working_set,
+            )?),
+            call::CallMessage::RemoveGuardians => Ok(self.remove_guardians(context, working_set)?),
+            call::CallMessage::ApproveRecovery {
+                account,
+                new_pub_key,
+            } => Ok(self.approve_recovery(account, new_pub_key, context, working_set)?),
+            call::CallMessage::ExecuteRecovery { account } => {
+                Ok(self.execute_recovery(account, context, working_set)?)
+            }
+            call::CallMessage::VetoRecovery => Ok(self.veto_recovery(context, working_set)?),
         }
     }
 }
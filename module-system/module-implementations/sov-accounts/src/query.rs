@@ -2,8 +2,10 @@
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::{AddressBech32, StateMapAccessor, WorkingSet};
+use sov_state::codec::BorshCodec;
+use sov_state::storage::StorageKey;
 
-use crate::{Account, Accounts};
+use crate::{Account, Accounts, Guardians, Recovery, SessionKey};
 
 /// This is the response returned from the accounts_getAccount endpoint.
 #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
@@ -42,4 +44,71 @@ impl<C: sov_modules_api::Context> Accounts<C> {
 
         Ok(response)
     }
+
+    #[rpc_method(name = "getNonce")]
+    /// Get the next nonce of the account corresponding to the given public key, in the nonce lane `nonce_lane`.
+    pub fn get_nonce(
+        &self,
+        pub_key: C::PublicKey,
+        nonce_lane: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<u64> {
+        Ok(match self.accounts.get(&pub_key, working_set) {
+            Some(account) => self.lane_nonce(&account, nonce_lane, working_set),
+            None => 0,
+        })
+    }
+
+    #[rpc_method(name = "getSessionKey")]
+    /// Get the account and the scope of the given session key, if it is authorized.
+    pub fn get_session_key(
+        &self,
+        session_key: C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<SessionKey<C>>> {
+        Ok(self.session_keys.get(&session_key, working_set))
+    }
+
+    #[rpc_method(name = "getGuardians")]
+    /// Get the guardians of the given account, if it has any.
+    pub fn get_guardians(
+        &self,
+        account: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Guardians<C>>> {
+        Ok(self.guardians.get(&account, working_set))
+    }
+
+    #[rpc_method(name = "getRecovery")]
+    /// Get the recovery in progress of the given account, if any.
+    pub fn get_recovery(
+        &self,
+        account: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Recovery<C>>> {
+        Ok(self.recoveries.get(&account, working_set))
+    }
+}
+
+impl<C: sov_modules_api::Context> Accounts<C> {
+    /// Returns the storage keys read and written by the transaction hooks for a transaction signed by `pub_key`,
+    /// the key of the account at the address `address`, e.g. to declare them in the access list of the transaction.
+    /// Transactions signed by a session key also read the account the key acts for.
+    pub fn tx_hooks_storage_keys(
+        &self,
+        pub_key: &C::PublicKey,
+        address: &C::Address,
+    ) -> (Vec<StorageKey>, Vec<StorageKey>) {
+        let reads = vec![StorageKey::new(
+            self.session_keys.prefix(),
+            pub_key,
+            &BorshCodec,
+        )];
+        let writes = vec![
+            StorageKey::new(self.accounts.prefix(), pub_key, &BorshCodec),
+            StorageKey::new(self.public_keys.prefix(), address, &BorshCodec),
+            StorageKey::new(self.active_session_keys.prefix(), address, &BorshCodec),
+        ];
+        (reads, writes)
+    }
 }
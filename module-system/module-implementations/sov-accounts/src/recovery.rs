@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use sov_modules_api::Context;
+
+/// The maximum number of guardians of an account.
+pub const MAX_GUARDIANS: usize = 16;
+
+/// The guardians of an account, which can jointly replace its public key if the owner loses it.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct Guardians<C: Context> {
+    /// The addresses of the guardians.
+    pub guardians: Vec<C::Address>,
+    /// The number of guardians which must approve the same new public key to recover the account.
+    pub threshold: u32,
+    /// The number of slots between the approval of a recovery by the guardians and its execution,
+    /// during which the owner can veto it.
+    pub timelock: u64,
+}
+
+impl<C: Context> Guardians<C> {
+    pub(crate) fn validate(&self, account: &C::Address) -> Result<()> {
+        if self.guardians.is_empty() || self.guardians.len() > MAX_GUARDIANS {
+            bail!(
+                "An account must have between 1 and {} guardians",
+                MAX_GUARDIANS
+            )
+        }
+        let mut unique_guardians = HashSet::new();
+        if !self
+            .guardians
+            .iter()
+            .all(|guardian| unique_guardians.insert(guardian.as_ref()))
+        {
+            bail!("The guardians of an account must be unique")
+        }
+        if self.guardians.contains(account) {
+            bail!("An account can't be its own guardian")
+        }
+        if self.threshold == 0 || self.threshold as usize > self.guardians.len() {
+            bail!(
+                "The threshold {} must be between 1 and the number of guardians {}",
+                self.threshold,
+                self.guardians.len()
+            )
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_guardian(&self, address: &C::Address) -> bool {
+        self.guardians.contains(address)
+    }
+}
+
+/// A recovery of an account in progress.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct Recovery<C: Context> {
+    /// The new public key approved by each guardian.
+    pub approvals: Vec<(C::Address, C::PublicKey)>,
+    /// The new public key approved by the threshold of guardians, if any.
+    pub scheduled: Option<ScheduledRecovery<C>>,
+}
+
+/// A new public key approved by the threshold of guardians of an account.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound = "")]
+pub struct ScheduledRecovery<C: Context> {
+    /// The new public key of the account.
+    pub new_pub_key: C::PublicKey,
+    /// The first slot height at which the new public key can replace the key of the account.
+    pub executable_at: u64,
+}
+
+impl<C: Context> Recovery<C> {
+    /// Records the approval of `new_pub_key` by `guardian`, replacing its previous approval, and updates the
+    /// scheduled recovery: a key approved by the threshold of guardians at `height` can be executed after the timelock.
+    pub(crate) fn approve(
+        &mut self,
+        guardian: C::Address,
+        new_pub_key: C::PublicKey,
+        guardians: &Guardians<C>,
+        height: u64,
+    ) {
+        self.approvals.retain(|(approver, _)| approver != &guardian);
+        self.approvals.push((guardian, new_pub_key.clone()));
+
+        let threshold = guardians.threshold as usize;
+        let still_approved = self
+            .scheduled
+            .take()
+            .filter(|scheduled| self.approvals_of(&scheduled.new_pub_key) >= threshold);
+        self.scheduled = still_approved.or_else(|| {
+            (self.approvals_of(&new_pub_key) >= threshold).then(|| ScheduledRecovery {
+                new_pub_key,
+                executable_at: height.saturating_add(guardians.timelock),
+            })
+        });
+    }
+
+    fn approvals_of(&self, pub_key: &C::PublicKey) -> usize {
+        self.approvals
+            .iter()
+            .filter(|(_, approved)| approved == pub_key)
+            .count()
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "native")]
+use core::str::FromStr;
+
+use anyhow::{bail, ensure, Result};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::Context;
+
+/// The maximum number of call prefixes in the scope of a session key.
+pub const MAX_CALL_PREFIXES: usize = 16;
+
+/// A prefix of the serialized runtime messages a session key can sign.
+///
+/// Runtime messages are borsh-encoded enums, so the first byte selects the module and,
+/// for modules with an enum call message, the second byte selects the call type:
+/// `[1]` allows any call to the second module of the runtime, and `[1, 0]` only its first call type.
+/// An empty prefix allows any call.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+pub struct CallPrefix(pub Vec<u8>);
+
+impl CallPrefix {
+    /// Returns true if the serialized runtime message `msg` starts with the prefix.
+    pub fn matches(&self, msg: &[u8]) -> bool {
+        msg.starts_with(&self.0)
+    }
+}
+
+/// Parses a hex-encoded prefix.
+#[cfg(feature = "native")]
+impl FromStr for CallPrefix {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        hex::decode(s.trim_start_matches("0x")).map(Self)
+    }
+}
+
+/// A secondary key allowed to sign transactions on behalf of an account, within a limited scope.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct SessionKey<C: Context> {
+    /// The address of the account the key acts for.
+    pub account: C::Address,
+    /// The key can only sign runtime messages starting with one of these prefixes.
+    pub call_prefixes: Vec<CallPrefix>,
+    /// The maximum amount of gas tokens the transactions signed by the key can reserve for their fees.
+    /// It only bounds the fees: the tokens moved by the calls are limited by the call prefixes of the key.
+    pub fee_cap: u64,
+    /// The amount of gas tokens already reserved for their fees by the transactions signed by the key.
+    pub fees_reserved: u64,
+    /// The last slot height at which the key can sign transactions.
+    pub expires_at: u64,
+}
+
+impl<C: Context> SessionKey<C> {
+    /// Checks that the transaction `tx`, executed at the slot `height`, is within the scope of the key,
+    /// and records its fees against the fee cap.
+    pub(crate) fn authorize(&mut self, tx: &Transaction<C>, height: u64) -> Result<()> {
+        ensure!(
+            height <= self.expires_at,
+            "Session key of account {} expired at height {}",
+            self.account,
+            self.expires_at
+        );
+        if !self
+            .call_prefixes
+            .iter()
+            .any(|prefix| prefix.matches(tx.runtime_msg()))
+        {
+            bail!(
+                "The call is out of the scope of the session key of account {}",
+                self.account
+            )
+        }
+
+        let fees = tx.gas_limit().saturating_add(tx.gas_tip());
+        let fees_reserved = self
+            .fees_reserved
+            .checked_add(fees)
+            .filter(|fees_reserved| *fees_reserved <= self.fee_cap)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The fees of the transaction exceed the fee cap of the session key of account {}",
+                    self.account
+                )
+            })?;
+        self.fees_reserved = fees_reserved;
+        Ok(())
+    }
+}
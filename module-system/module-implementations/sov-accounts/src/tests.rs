@@ -1,12 +1,14 @@
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
     AddressBech32, Context, Module, PrivateKey, PublicKey, Spec, StateMapAccessor, WorkingSet,
 };
 use sov_prover_storage_manager::new_orphan_storage;
 
 use crate::query::{self, Response};
-use crate::{call, AccountConfig, Accounts};
+use crate::{call, AccountConfig, Accounts, AccountsTxHookArg, CallPrefix};
 
 type C = DefaultContext;
 
@@ -164,6 +166,299 @@ fn test_get_account_after_pub_key_update() {
     assert_eq!(acc.addr, sender_1_addr)
 }
 
+#[test]
+fn test_session_key() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let accounts = &mut Accounts::<C>::default();
+
+    let sender = DefaultPrivateKey::generate().pub_key();
+    let sequencer = DefaultPrivateKey::generate().pub_key();
+    let sender_addr = sender.to_address::<<C as Spec>::Address>();
+    let sender_context = C::new(sender_addr, sequencer.to_address(), 1);
+    accounts
+        .create_default_account(&sender, working_set)
+        .unwrap();
+
+    let session_priv_key = DefaultPrivateKey::generate();
+    let session_key = session_priv_key.pub_key();
+    let authorize = |signature| call::CallMessage::<C>::AuthorizeSessionKey {
+        session_key: session_key.clone(),
+        signature,
+        call_prefixes: vec![CallPrefix(vec![1])],
+        fee_cap: 150,
+        expires_at: 10,
+    };
+
+    // The sender must prove the possession of the session key, for its own address
+    let other_addr = DefaultPrivateKey::generate()
+        .pub_key()
+        .to_address::<<C as Spec>::Address>();
+    accounts
+        .call(
+            authorize(
+                DefaultPrivateKey::generate().sign(&call::authorize_session_key_msg(&sender_addr)),
+            ),
+            &sender_context,
+            working_set,
+        )
+        .expect_err("The signature isn't from the session key");
+    accounts
+        .call(
+            authorize(session_priv_key.sign(&call::authorize_session_key_msg(&other_addr))),
+            &sender_context,
+            working_set,
+        )
+        .expect_err("The signature authorizes the session key for another account");
+    accounts
+        .call(
+            authorize(session_priv_key.sign(&call::authorize_session_key_msg(&sender_addr))),
+            &sender_context,
+            working_set,
+        )
+        .expect("Authorizing the session key failed");
+    let session = accounts
+        .get_session_key(session_key.clone(), working_set)
+        .unwrap()
+        .expect("The session key should be authorized");
+    assert_eq!(session.account, sender_addr);
+
+    let hook_arg = |height| AccountsTxHookArg {
+        sequencer: sequencer.clone(),
+        height,
+    };
+    let signed_tx = |msg: Vec<u8>, nonce| {
+        Transaction::<C>::new_signed_tx(&session_priv_key, msg, 0, 0, 100, nonce)
+    };
+
+    // The session key signs on behalf of the account, and increments its nonce
+    let tx = signed_tx(vec![1, 0], 0);
+    let hook = accounts
+        .pre_dispatch_tx_hook(&tx, working_set, &hook_arg(5))
+        .expect("The call is in the scope of the session key");
+    assert_eq!(hook.sender, sender_addr);
+    let session_context = C::new(hook.sender, hook.sequencer, 5);
+    accounts
+        .call(
+            call::CallMessage::<C>::RevokeSessionKey {
+                session_key: session_key.clone(),
+            },
+            &session_context,
+            working_set,
+        )
+        .expect_err("Session keys can't manage the keys of their account");
+    accounts
+        .post_dispatch_tx_hook(&tx, &session_context, working_set)
+        .unwrap();
+    assert_eq!(
+        accounts.accounts.get(&sender, working_set).unwrap().nonce,
+        1
+    );
+    assert!(accounts.accounts.get(&session_key, working_set).is_none());
+
+    // Out of scope, over the fee cap and expired transactions are rejected
+    accounts
+        .pre_dispatch_tx_hook(&signed_tx(vec![2, 0], 1), working_set, &hook_arg(5))
+        .expect_err("The call is out of the scope of the session key");
+    accounts
+        .pre_dispatch_tx_hook(&signed_tx(vec![1, 0], 1), working_set, &hook_arg(5))
+        .expect_err("The fees exceed the fee cap of the session key");
+    accounts
+        .pre_dispatch_tx_hook(&signed_tx(vec![1, 0], 1), working_set, &hook_arg(11))
+        .expect_err("The session key expired");
+
+    // A revoked session key can't sign transactions anymore
+    accounts
+        .call(
+            call::CallMessage::<C>::RevokeSessionKey {
+                session_key: session_key.clone(),
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Revoking the session key failed");
+    assert!(accounts
+        .get_session_key(session_key, working_set)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_guardian_recovery() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let accounts = &mut Accounts::<C>::default();
+
+    let owner = DefaultPrivateKey::generate().pub_key();
+    let owner_addr = owner.to_address::<<C as Spec>::Address>();
+    let sequencer_addr = DefaultPrivateKey::generate()
+        .pub_key()
+        .to_address::<<C as Spec>::Address>();
+    let guardians: Vec<<C as Spec>::Address> = (0..3)
+        .map(|_| DefaultPrivateKey::generate().pub_key().to_address())
+        .collect();
+    let owner_context = |height| C::new(owner_addr, sequencer_addr, height);
+    let guardian_context = |i: usize, height| C::new(guardians[i], sequencer_addr, height);
+    accounts
+        .create_default_account(&owner, working_set)
+        .unwrap();
+
+    let set_guardians =
+        |guardians: Vec<<C as Spec>::Address>, threshold| call::CallMessage::<C>::SetGuardians {
+            guardians,
+            threshold,
+            timelock: 10,
+        };
+    accounts
+        .call(
+            set_guardians(guardians.clone(), 4),
+            &owner_context(1),
+            working_set,
+        )
+        .expect_err("The threshold exceeds the number of guardians");
+    accounts
+        .call(
+            set_guardians(vec![guardians[0], guardians[0]], 1),
+            &owner_context(1),
+            working_set,
+        )
+        .expect_err("The guardians must be unique");
+    accounts
+        .call(
+            set_guardians(vec![owner_addr], 1),
+            &owner_context(1),
+            working_set,
+        )
+        .expect_err("The owner can't be its own guardian");
+    accounts
+        .call(
+            set_guardians(guardians.clone(), 2),
+            &owner_context(1),
+            working_set,
+        )
+        .expect("Setting the guardians failed");
+
+    let new_pub_key = DefaultPrivateKey::generate().pub_key();
+    let approve = |new_pub_key: &<C as Spec>::PublicKey| call::CallMessage::<C>::ApproveRecovery {
+        account: owner_addr,
+        new_pub_key: new_pub_key.clone(),
+    };
+    let execute = call::CallMessage::<C>::ExecuteRecovery {
+        account: owner_addr,
+    };
+
+    // The recovery is scheduled once the threshold of guardians approved the same key
+    accounts
+        .call(approve(&new_pub_key), &owner_context(2), working_set)
+        .expect_err("Only guardians can approve a recovery");
+    accounts
+        .call(approve(&new_pub_key), &guardian_context(0, 2), working_set)
+        .unwrap();
+    accounts
+        .call(
+            approve(&DefaultPrivateKey::generate().pub_key()),
+            &guardian_context(1, 2),
+            working_set,
+        )
+        .unwrap();
+    accounts
+        .call(execute.clone(), &guardian_context(0, 20), working_set)
+        .expect_err("The recovery isn't approved by the threshold of guardians");
+    accounts
+        .call(approve(&new_pub_key), &guardian_context(2, 3), working_set)
+        .unwrap();
+    let scheduled = accounts
+        .get_recovery(owner_addr, working_set)
+        .unwrap()
+        .and_then(|recovery| recovery.scheduled)
+        .expect("The recovery should be scheduled");
+    assert_eq!(scheduled.new_pub_key, new_pub_key);
+    assert_eq!(scheduled.executable_at, 13);
+
+    // The owner vetoes the recovery before the end of the timelock
+    accounts
+        .call(execute.clone(), &guardian_context(0, 12), working_set)
+        .expect_err("The timelock didn't elapse");
+    accounts
+        .call(
+            call::CallMessage::<C>::VetoRecovery,
+            &owner_context(12),
+            working_set,
+        )
+        .expect("Vetoing the recovery failed");
+    accounts
+        .call(execute.clone(), &guardian_context(0, 13), working_set)
+        .expect_err("The recovery was vetoed");
+
+    // Without a veto, the key of the account is replaced after the timelock
+    accounts
+        .call(approve(&new_pub_key), &guardian_context(0, 14), working_set)
+        .unwrap();
+    accounts
+        .call(approve(&new_pub_key), &guardian_context(1, 14), working_set)
+        .unwrap();
+    accounts
+        .call(execute, &guardian_context(2, 24), working_set)
+        .expect("Executing the recovery failed");
+    assert!(accounts.accounts.get(&owner, working_set).is_none());
+    assert_eq!(
+        accounts
+            .accounts
+            .get(&new_pub_key, working_set)
+            .unwrap()
+            .addr,
+        owner_addr
+    );
+    assert_eq!(
+        accounts.public_keys.get(&owner_addr, working_set).unwrap(),
+        new_pub_key
+    );
+    assert!(accounts
+        .get_recovery(owner_addr, working_set)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_nonce_lanes() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let accounts = &mut Accounts::<C>::default();
+
+    let priv_key = DefaultPrivateKey::generate();
+    let sender = priv_key.pub_key();
+    let sequencer = DefaultPrivateKey::generate().pub_key();
+    let hook_arg = AccountsTxHookArg {
+        sequencer: sequencer.clone(),
+        height: 1,
+    };
+    let mut send = |nonce_lane, nonce| {
+        let tx =
+            Transaction::<C>::new_signed_tx_in_lane(&priv_key, vec![], 0, 0, 0, nonce_lane, nonce);
+        let hook = accounts.pre_dispatch_tx_hook(&tx, working_set, &hook_arg)?;
+        let context = C::new(hook.sender, hook.sequencer, 1);
+        accounts.post_dispatch_tx_hook(&tx, &context, working_set)
+    };
+
+    // Each lane has its own sequence of nonces
+    send(0, 0).expect("First transaction of lane 0");
+    send(7, 0).expect("First transaction of lane 7");
+    send(7, 1).expect("Second transaction of lane 7");
+    send(0, 1).expect("Second transaction of lane 0");
+    send(7, 1).expect_err("The transaction is replayed in lane 7");
+    send(3, 1).expect_err("The nonce of lane 3 is 0");
+
+    assert_eq!(
+        accounts.get_nonce(sender.clone(), 0, working_set).unwrap(),
+        2
+    );
+    assert_eq!(
+        accounts.get_nonce(sender.clone(), 7, working_set).unwrap(),
+        2
+    );
+    assert_eq!(accounts.get_nonce(sender, 3, working_set).unwrap(), 0);
+}
+
 #[test]
 fn test_response_serialization() {
     let addr: Vec<u8> = (1..=32).collect();
@@ -193,6 +193,10 @@ pub enum AttesterIncentiveErrors {
     #[error("Error when trying to mint the reward token")]
     /// An error occurred when trying to mint the reward token
     MintFailure,
+
+    #[error("The challenge window of the transition is closed")]
+    /// The transition was attested more than `rollup_finality_period` slots ago, and can't be challenged anymore
+    ChallengeWindowClosed,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -822,6 +826,18 @@ where
             return Err(AttesterIncentiveErrors::UserNotBonded);
         }
 
+        // A transition can only be challenged during the `rollup_finality_period` slots following it.
+        // After that, light clients consider it final.
+        let finality_period = self
+            .rollup_finality_period
+            .get(working_set)
+            .expect("Should be set at genesis");
+        if self.chain_state.true_slot_height(working_set)
+            > transition_num.saturating_add(finality_period)
+        {
+            return Err(AttesterIncentiveErrors::ChallengeWindowClosed);
+        }
+
         let code_commitment = self
             .commitment_to_allowed_challenge_method
             .get(working_set)
@@ -162,12 +162,25 @@ where
             call::CallMessage::UnbondChallenger => self.unbond_challenger(context, working_set),
             call::CallMessage::ProcessAttestation(attestation) => self
                 .process_attestation(context, attestation, working_set)
+                .or_else(keep_slashing)
                 .map_err(|error| error.into()),
 
             call::CallMessage::ProcessChallenge(proof, transition) => self
                 .process_challenge(context, &proof, &transition, working_set)
+                .or_else(keep_slashing)
                 .map_err(|error| error.into()),
         }
         .map_err(|e| e.into())
     }
 }
+
+/// Slashing the losing party of an attestation or a challenge must not be reverted with the transaction,
+/// so a slashed user is reported as a successful call. The slashing is recorded by the `user_slashed` event.
+fn keep_slashing(
+    error: AttesterIncentiveErrors,
+) -> Result<sov_modules_api::CallResponse, AttesterIncentiveErrors> {
+    match error {
+        AttesterIncentiveErrors::UserSlashed(_) => Ok(sov_modules_api::CallResponse::default()),
+        error => Err(error),
+    }
+}
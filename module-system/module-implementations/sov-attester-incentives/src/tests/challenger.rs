@@ -9,8 +9,8 @@ use sov_rollup_interface::zk::StateTransition;
 
 use crate::call::{AttesterIncentiveErrors, SlashingReason};
 use crate::tests::helpers::{
-    commit_get_new_working_set, execution_simulation, setup, BOND_AMOUNT, INITIAL_BOND_AMOUNT,
-    INIT_HEIGHT,
+    commit_get_new_working_set, execution_simulation, setup, BOND_AMOUNT, DEFAULT_ROLLUP_FINALITY,
+    INITIAL_BOND_AMOUNT, INIT_HEIGHT,
 };
 
 /// Test that given an invalid transition, a challenger can successfully challenge it and get rewarded
@@ -331,3 +331,81 @@ fn test_invalid_challenge() {
         );
     }
 }
+
+/// Test that a transition can't be challenged once its challenge window is closed
+#[test]
+fn test_challenge_after_window() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+    let (module, _token_address, attester_address, challenger_address, sequencer) =
+        setup(&mut working_set);
+
+    let (_, working_set) = commit_get_new_working_set(&storage, working_set);
+
+    // Simulate the execution of the chain past the finality period of the first transition
+    let rounds = u8::try_from(DEFAULT_ROLLUP_FINALITY).unwrap() + 3;
+    let (exec_vars, mut working_set) =
+        execution_simulation(rounds, &module, &storage, attester_address, working_set);
+
+    module
+        .bond_user_helper(
+            BOND_AMOUNT,
+            &challenger_address,
+            crate::call::Role::Challenger,
+            &mut working_set,
+        )
+        .unwrap();
+
+    // Set a bad transition to get a reward from
+    module
+        .bad_transition_pool
+        .set(&(INIT_HEIGHT + 1), &BOND_AMOUNT, &mut working_set);
+
+    let context = DefaultContext::new(challenger_address, sequencer, INIT_HEIGHT + 2);
+    let transition = StateTransition::<MockDaSpec, _> {
+        initial_state_root: exec_vars[0].state_root,
+        slot_hash: [1; 32].into(),
+        final_state_root: exec_vars[1].state_root,
+        validity_condition: MockValidityCond { is_valid: true },
+    };
+    let serialized_transition = transition.try_to_vec().unwrap();
+    let commitment = module
+        .commitment_to_allowed_challenge_method
+        .get(&mut working_set)
+        .expect("Should be set at genesis");
+    let proof = &MockProof {
+        program_id: commitment,
+        is_valid: true,
+        log: serialized_transition.as_slice(),
+    }
+    .encode_to_vec();
+
+    let err = module
+        .process_challenge(
+            &context,
+            proof.as_slice(),
+            &(INIT_HEIGHT + 1),
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert_eq!(err, AttesterIncentiveErrors::ChallengeWindowClosed);
+
+    // The challenger isn't slashed, and the bad transition stays in the pool
+    assert_eq!(
+        module
+            .get_bond_amount(
+                challenger_address,
+                crate::call::Role::Challenger,
+                &mut working_set
+            )
+            .value,
+        BOND_AMOUNT
+    );
+    assert_eq!(
+        module
+            .bad_transition_pool
+            .get(&(INIT_HEIGHT + 1), &mut working_set),
+        Some(BOND_AMOUNT)
+    );
+}
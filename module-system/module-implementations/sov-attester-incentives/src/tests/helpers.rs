@@ -59,12 +59,16 @@ pub(crate) fn create_bank_config_with_token(
         token_name,
         address_and_balances: address_and_balances.clone(),
         authorized_minters: vec![address_and_balances.first().unwrap().0],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
         salt,
     };
 
     (
         BankConfig {
             tokens: vec![token_config],
+            fee_settlement: Default::default(),
         },
         address_and_balances
             .into_iter()
@@ -108,6 +112,15 @@ pub(crate) fn setup(
     let chain_state_config = sov_chain_state::ChainStateConfig {
         initial_slot_height: INIT_HEIGHT,
         current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
     };
 
     let chain_state = sov_chain_state::ChainState::<C, MockDaSpec>::default();
@@ -193,12 +206,15 @@ pub(crate) fn execution_simulation<Checker: ValidityConditionChecker<MockValidit
             blobs: Default::default(),
         };
         let kernel = MockKernel::<C, MockDaSpec>::new(i as u64, i as u64);
-        module.chain_state.begin_slot_hook(
-            &slot_data.header,
-            &slot_data.validity_cond,
-            &root_hash,
-            &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
-        );
+        module
+            .chain_state
+            .begin_slot_hook(
+                &slot_data.header,
+                &slot_data.validity_cond,
+                &root_hash,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .expect("Chain state begin slot hook must succeed");
     }
 
     (ret_exec_vars, working_set)
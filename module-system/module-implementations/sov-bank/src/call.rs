@@ -1,9 +1,13 @@
 use anyhow::{bail, Context, Result};
 #[cfg(feature = "native")]
 use sov_modules_api::macros::CliWalletArg;
-use sov_modules_api::{CallResponse, StateMapAccessor, WorkingSet};
+use sov_modules_api::{CallResponse, StateMapAccessor, StateVecAccessor, WorkingSet};
 
-use crate::{Amount, Bank, Coins, Token};
+use crate::events::{Approve, GrantRole, RevokeRole, TransferFrom};
+use crate::{
+    Amount, Bank, Coins, ConversionRate, Token, TokenMetadata, TokenRole, TransferHooks,
+    TransferOutput,
+};
 
 /// This enumeration represents the available call messages for interacting with the sov-bank module.
 #[cfg_attr(
@@ -31,6 +35,10 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         minter_address: C::Address,
         /// Authorized minter list.
         authorized_minters: Vec<C::Address>,
+        /// The maximum total supply of the new token. Unlimited if not set.
+        max_supply: Option<Amount>,
+        /// The symbol, decimals and metadata URI of the new token, displayed by wallets.
+        metadata: Option<TokenMetadata>,
     },
 
     /// Transfers a specified amount of tokens to the specified address.
@@ -41,6 +49,13 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         coins: Coins<C>,
     },
 
+    /// Transfers tokens to several addresses at once, in a single transaction.
+    /// Either all the transfers succeed, or none of them is applied.
+    TransferMany {
+        /// The recipients and the amounts of tokens transferred to each of them.
+        outputs: Vec<TransferOutput<C>>,
+    },
+
     /// Burns a specified amount of tokens.
     Burn {
         /// The amount of tokens to burn.
@@ -60,10 +75,59 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         /// Address of the token to be frozen
         token_address: C::Address,
     },
+
+    /// Grants a role on a token to an address. Only the admins of the token can grant roles.
+    GrantRole {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The role to grant.
+        role: TokenRole,
+        /// The address receiving the role.
+        account: C::Address,
+    },
+
+    /// Revokes a role on a token from an address. Only the admins of the token can revoke roles.
+    RevokeRole {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The role to revoke.
+        role: TokenRole,
+        /// The address losing the role.
+        account: C::Address,
+    },
+
+    /// Allows `spender` to transfer up to the specified amount of the sender's tokens.
+    /// Replaces any previous allowance of `spender` for the same token.
+    Approve {
+        /// The address allowed to spend the tokens.
+        spender: C::Address,
+        /// The token and the maximum amount that can be spent.
+        coins: Coins<C>,
+    },
+
+    /// Transfers tokens from `from` to `to`, spending the allowance granted to the sender by `from`.
+    TransferFrom {
+        /// The address from which the tokens will be transferred.
+        from: C::Address,
+        /// The address to which the tokens will be transferred.
+        to: C::Address,
+        /// The amount of tokens to transfer.
+        coins: Coins<C>,
+    },
+
+    /// Accepts a token for fee payment at a conversion rate to the gas token, or stops accepting it.
+    /// Only the admins of the gas token can set the fee tokens.
+    SetFeeToken {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The amount of tokens worth a gas token, or `None` to stop accepting the token.
+        rate: Option<ConversionRate>,
+    },
 }
 
-impl<C: sov_modules_api::Context> Bank<C> {
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
     /// Creates a token from a set of configuration parameters.
+    /// The sender becomes the admin of the token, and the token is added to the token registry.
     /// Checks if a token already exists at that address. If so return an error.
     #[allow(clippy::too_many_arguments)]
     pub fn create_token(
@@ -73,13 +137,18 @@ impl<C: sov_modules_api::Context> Bank<C> {
         initial_balance: Amount,
         minter_address: C::Address,
         authorized_minters: Vec<C::Address>,
+        max_supply: Option<Amount>,
+        metadata: Option<TokenMetadata>,
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<C::Address> {
         let (token_address, token) = Token::<C>::create(
             &token_name,
-            &[(minter_address, initial_balance)],
+            &[(minter_address.clone(), initial_balance)],
             &authorized_minters,
+            &[context.sender().clone()],
+            max_supply,
+            metadata,
             context.sender().as_ref(),
             salt,
             self.tokens.prefix(),
@@ -95,6 +164,14 @@ impl<C: sov_modules_api::Context> Bank<C> {
         }
 
         self.tokens.set(&token_address, &token, working_set);
+        self.token_addresses.push(&token_address, working_set);
+        self.record_holder(&token_address, &minter_address, working_set);
+
+        let minted = Coins {
+            amount: initial_balance,
+            token_address: token_address.clone(),
+        };
+        H::on_mint(&minter_address, &minted, working_set)?;
         Ok(token_address)
     }
 
@@ -109,16 +186,47 @@ impl<C: sov_modules_api::Context> Bank<C> {
         self.transfer_from(context.sender(), &to, coins, working_set)
     }
 
+    /// Transfers the coins of each of the `outputs` from `context.sender()` to the recipient of the output.
+    /// Returns an error if there are no outputs or if any of the transfers fails. In that case, the transfers
+    /// already applied are reverted along with the transaction.
+    pub fn transfer_many(
+        &self,
+        outputs: Vec<TransferOutput<C>>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        if outputs.is_empty() {
+            bail!("A transfer to many recipients must have at least one output");
+        }
+
+        for TransferOutput { to, coins } in outputs {
+            self.transfer_from(context.sender(), &to, coins, working_set)?;
+        }
+        Ok(CallResponse::default())
+    }
+
     /// Burns the set of `coins`.
     ///
     /// If there is no token at the address specified in the
     /// [`Coins`] structure, return an error; on success it updates the total
-    /// supply of tokens.
+    /// supply of tokens and invokes the [`TransferHooks::on_burn`] hook.
     pub fn burn(
         &self,
         coins: Coins<C>,
         owner: &C::Address,
         working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.burn_without_hooks(&coins, owner, working_set)?;
+        H::on_burn(owner, &coins, working_set)
+            .with_context(|| format!("Failed to burn coins({}) from owner {}", coins, owner))
+    }
+
+    /// Burns the set of `coins` without invoking the transfer hooks. Used to collect gas.
+    pub(crate) fn burn_without_hooks(
+        &self,
+        coins: &Coins<C>,
+        owner: &C::Address,
+        working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
         let context_logger = || format!("Failed to burn coins({}) from owner {}", coins, owner,);
         let mut token = self
@@ -163,7 +271,8 @@ impl<C: sov_modules_api::Context> Bank<C> {
     /// Mints the `coins` to the address `mint_to_address` if `authorizer` is an allowed minter.
     /// Returns an error if the token address doesn't exist or `context.sender()` is not authorized to mint tokens.
     ///
-    /// On success, it updates the `self.tokens` set to store the new minted address.
+    /// On success, it updates the `self.tokens` set to store the new minted address and invokes
+    /// the [`TransferHooks::on_mint`] hook.
     pub fn mint(
         &self,
         coins: &Coins<C>,
@@ -185,8 +294,9 @@ impl<C: sov_modules_api::Context> Bank<C> {
             .mint(authorizer, mint_to_address, coins.amount, working_set)
             .with_context(context_logger)?;
         self.tokens.set(&coins.token_address, &token, working_set);
+        self.record_holder(&coins.token_address, mint_to_address, working_set);
 
-        Ok(())
+        H::on_mint(mint_to_address, coins, working_set).with_context(context_logger)
     }
 
     /// Tries to freeze the token address `token_address`.
@@ -216,10 +326,159 @@ impl<C: sov_modules_api::Context> Bank<C> {
 
         Ok(CallResponse::default())
     }
+
+    /// Grants the `role` on the token `token_address` to `account`.
+    /// Returns an error if the token address doesn't exist or `context.sender()` is not an admin of the token.
+    pub(crate) fn grant_role(
+        &self,
+        token_address: C::Address,
+        role: TokenRole,
+        account: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed grant of role {} to {} on token_address={} by sender {}",
+                role,
+                account,
+                token_address,
+                context.sender()
+            )
+        };
+        let mut token = self
+            .tokens
+            .get_or_err(&token_address, working_set)
+            .with_context(context_logger)?;
+        token
+            .grant_role(context.sender(), role, &account)
+            .with_context(context_logger)?;
+        self.tokens.set(&token_address, &token, working_set);
+        working_set.emit_event(&GrantRole::<C> {
+            token_address,
+            role,
+            account,
+        });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Revokes the `role` on the token `token_address` from `account`.
+    /// Returns an error if the token address doesn't exist or `context.sender()` is not an admin of the token.
+    pub(crate) fn revoke_role(
+        &self,
+        token_address: C::Address,
+        role: TokenRole,
+        account: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed revocation of role {} from {} on token_address={} by sender {}",
+                role,
+                account,
+                token_address,
+                context.sender()
+            )
+        };
+        let mut token = self
+            .tokens
+            .get_or_err(&token_address, working_set)
+            .with_context(context_logger)?;
+        token
+            .revoke_role(context.sender(), role, &account)
+            .with_context(context_logger)?;
+        self.tokens.set(&token_address, &token, working_set);
+        working_set.emit_event(&RevokeRole::<C> {
+            token_address,
+            role,
+            account,
+        });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Sets the amount of the token `coins.token_address` that `spender` can transfer on behalf of
+    /// `context.sender()` to `coins.amount`, overwriting the current allowance.
+    /// Returns an error if the token address doesn't exist.
+    pub fn approve(
+        &self,
+        spender: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owner = context.sender();
+        let context_logger = || {
+            format!(
+                "Failed approve of coins({}) by owner={} for spender={}",
+                coins, owner, spender
+            )
+        };
+        self.tokens
+            .get_or_err(&coins.token_address, working_set)
+            .with_context(context_logger)?;
+
+        self.allowances.set(
+            &(coins.token_address, owner.clone(), spender.clone()),
+            &coins.amount,
+            working_set,
+        );
+        working_set.emit_event(&Approve::<C> {
+            token_address: coins.token_address,
+            owner: owner.clone(),
+            spender,
+            amount: coins.amount,
+        });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the set of `coins` from the address `from` to the address `to` on behalf of `context.sender()`,
+    /// decreasing the allowance granted to the sender by `from` accordingly.
+    /// Returns an error if the allowance is lower than the amount transferred.
+    pub fn transfer_from_approved(
+        &self,
+        from: C::Address,
+        to: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let spender = context.sender();
+        let allowance_key = (coins.token_address.clone(), from.clone(), spender.clone());
+        let allowance = self
+            .allowances
+            .get(&allowance_key, working_set)
+            .unwrap_or_default();
+        let Some(remaining) = allowance.checked_sub(coins.amount) else {
+            bail!(
+                "Insufficient allowance of spender={} on owner={} for coins({}): allowed {}",
+                spender,
+                from,
+                coins,
+                allowance
+            );
+        };
+
+        self.transfer_from(&from, &to, coins.clone(), working_set)?;
+        self.allowances.set(&allowance_key, &remaining, working_set);
+        working_set.emit_event(&TransferFrom::<C> {
+            token_address: coins.token_address,
+            from,
+            to,
+            spender: spender.clone(),
+            amount: coins.amount,
+        });
+
+        Ok(CallResponse::default())
+    }
 }
 
-impl<C: sov_modules_api::Context> Bank<C> {
-    /// Transfers the set of `coins` from the address `from` to the address `to`.
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
+    /// Transfers the set of `coins` from the address `from` to the address `to`, and invokes
+    /// the [`TransferHooks::on_transfer`] hook.
     ///
     /// Returns an error if the token address doesn't exist.
     pub fn transfer_from(
@@ -229,6 +488,25 @@ impl<C: sov_modules_api::Context> Bank<C> {
         coins: Coins<C>,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
+        self.transfer_without_hooks(from, to, &coins, working_set)?;
+        H::on_transfer(from, to, &coins, working_set).with_context(|| {
+            format!(
+                "Failed transfer from={} to={} of coins({})",
+                from, to, coins
+            )
+        })?;
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the set of `coins` from the address `from` to the address `to` without invoking
+    /// the transfer hooks. Used to pay for gas.
+    pub(crate) fn transfer_without_hooks(
+        &self,
+        from: &C::Address,
+        to: &C::Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
         let context_logger = || {
             format!(
                 "Failed transfer from={} to={} of coins({})",
@@ -242,7 +520,8 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token
             .transfer(from, to, coins.amount, working_set)
             .with_context(context_logger)?;
-        Ok(CallResponse::default())
+        self.record_holder(&coins.token_address, to, working_set);
+        Ok(())
     }
 
     /// Helper function used by the rpc method [`balance_of`](Bank::balance_of) to return the balance of the token stored at `token_address`
@@ -259,6 +538,54 @@ impl<C: sov_modules_api::Context> Bank<C> {
             .and_then(|token| token.balances.get(&user_address, working_set))
     }
 
+    /// Returns the amount of the token stored at `token_address` that `spender` can transfer on behalf of `owner`.
+    /// Used by the rpc method [`allowance`](Bank::allowance).
+    pub fn get_allowance(
+        &self,
+        token_address: C::Address,
+        owner: C::Address,
+        spender: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.allowances
+            .get(&(token_address, owner, spender), working_set)
+    }
+
+    /// Returns true if `address` holds the `role` on the token stored at `token_address`.
+    pub fn has_role(
+        &self,
+        token_address: &C::Address,
+        role: TokenRole,
+        address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.tokens
+            .get(token_address, working_set)
+            .is_some_and(|token| token.has_role(role, address))
+    }
+
+    /// Maximum supply of a token stored at the address `token_address`, if the supply is capped
+    pub fn get_max_supply_of(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.tokens
+            .get(token_address, working_set)
+            .and_then(|token| token.max_supply)
+    }
+
+    /// Returns the metadata of the token stored at `token_address`, if the token exists and was created with metadata.
+    pub fn get_token_metadata(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<TokenMetadata> {
+        self.tokens
+            .get(token_address, working_set)
+            .and_then(|token| token.metadata)
+    }
+
     /// Get the name of a token by address
     pub fn get_token_name(
         &self,
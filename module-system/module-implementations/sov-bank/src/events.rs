@@ -0,0 +1,112 @@
+//! The typed events emitted by the bank module.
+use sov_modules_api::ModuleEvent;
+
+use crate::{Amount, ConversionRate, TokenRole};
+
+/// Emitted when `account` is granted `role` on the token `token_address`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "grant_role")]
+pub struct GrantRole<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The granted role.
+    pub role: TokenRole,
+    /// The account receiving the role.
+    #[indexed]
+    pub account: C::Address,
+}
+
+/// Emitted when `role` on the token `token_address` is revoked from `account`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "revoke_role")]
+pub struct RevokeRole<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The revoked role.
+    pub role: TokenRole,
+    /// The account losing the role.
+    #[indexed]
+    pub account: C::Address,
+}
+
+/// Emitted when `owner` sets the allowance of `spender` on the token `token_address`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "approve")]
+pub struct Approve<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The account granting the allowance.
+    #[indexed]
+    pub owner: C::Address,
+    /// The account allowed to spend the tokens.
+    #[indexed]
+    pub spender: C::Address,
+    /// The new allowance.
+    pub amount: Amount,
+}
+
+/// Emitted when `spender` transfers tokens from `from` to `to` out of its allowance.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "transfer_from")]
+pub struct TransferFrom<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The account the tokens are taken from.
+    #[indexed]
+    pub from: C::Address,
+    /// The account receiving the tokens.
+    #[indexed]
+    pub to: C::Address,
+    /// The account spending its allowance.
+    pub spender: C::Address,
+    /// The transferred amount.
+    pub amount: Amount,
+}
+
+/// Emitted when the token `token_address` is accepted for fee payment at the conversion `rate`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "set_fee_token")]
+pub struct SetFeeToken<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The conversion rate of the gas token in the fee token.
+    pub rate: ConversionRate,
+}
+
+/// Emitted when the token `token_address` isn't accepted for fee payment anymore.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "remove_fee_token")]
+pub struct RemoveFeeToken<C: sov_modules_api::Context> {
+    /// The address of the token.
+    #[indexed]
+    pub token_address: C::Address,
+}
+
+/// Emitted when the fee of a transaction is settled, after its execution. The amounts are in the token
+/// `token_address` paid by the sender.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "settle_fee")]
+pub struct SettleFee<C: sov_modules_api::Context> {
+    /// The address of the token in which the fee was paid.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The sender of the transaction.
+    #[indexed]
+    pub sender: C::Address,
+    /// The sequencer which included the transaction.
+    #[indexed]
+    pub sequencer: C::Address,
+    /// The unused gas, refunded to the sender.
+    pub refund: Amount,
+    /// The consumed gas and the tip kept by the sequencer.
+    pub sequencer_fee: Amount,
+    /// The share of the consumed gas paid to the treasury.
+    pub treasury_fee: Amount,
+    /// The treasury, or `None` if its share was burnt.
+    pub treasury_address: Option<C::Address>,
+}
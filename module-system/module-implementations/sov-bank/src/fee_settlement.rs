@@ -0,0 +1,133 @@
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{StateValueAccessor, WorkingSet};
+
+use crate::events::SettleFee;
+use crate::hooks::gas_token_address;
+use crate::{Amount, Bank, Coins, TransferHooks};
+
+/// The number of basis points in a whole.
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// How the gas consumed by transactions is shared between the sequencer and the treasury.
+/// The tips are always paid to the sequencer.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct FeeSettlementConfig<C: sov_modules_api::Context> {
+    /// The share of the consumed gas, in basis points, paid to the treasury instead of the sequencer.
+    #[serde(default)]
+    pub treasury_share_bps: u16,
+    /// The address of the treasury. Its share is burnt if it isn't set.
+    #[serde(default)]
+    pub treasury_address: Option<C::Address>,
+}
+
+impl<C: sov_modules_api::Context> Default for FeeSettlementConfig<C> {
+    fn default() -> Self {
+        Self {
+            treasury_share_bps: 0,
+            treasury_address: None,
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> FeeSettlementConfig<C> {
+    /// Checks that the treasury share is at most [`MAX_BASIS_POINTS`].
+    pub fn validate(&self) -> Result<()> {
+        if self.treasury_share_bps > MAX_BASIS_POINTS {
+            bail!(
+                "The treasury share is {} basis points, more than {}",
+                self.treasury_share_bps,
+                MAX_BASIS_POINTS
+            )
+        }
+        Ok(())
+    }
+
+    /// The share of `consumed` paid to the treasury, rounded down in favor of the sequencer.
+    pub fn treasury_share_of(&self, consumed: Amount) -> Amount {
+        let share = consumed as u128 * self.treasury_share_bps as u128 / MAX_BASIS_POINTS as u128;
+        // The share is at most `consumed`, so it fits in an `Amount`
+        share as Amount
+    }
+}
+
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
+    /// Returns how the consumed gas is shared between the sequencer and the treasury.
+    pub fn get_fee_settlement(&self, working_set: &mut WorkingSet<C>) -> FeeSettlementConfig<C> {
+        self.fee_settlement.get(working_set).unwrap_or_default()
+    }
+
+    /// Settles the fee of a transaction, once it was executed. The sequencer was paid `reserved` gas tokens
+    /// when the transaction was dispatched, and `remaining` of them weren't consumed:
+    /// - the unused gas is refunded to the sender,
+    /// - the treasury share of the consumed gas is paid to the treasury, or burnt,
+    /// - the sequencer keeps the rest of the consumed gas and the tip.
+    ///
+    /// The amounts are converted to the fee token paid by the sender, if it isn't the gas token.
+    pub(crate) fn settle_fee(
+        &self,
+        sender: &C::Address,
+        sequencer: &C::Address,
+        reserved: Amount,
+        consumed: Amount,
+        remaining: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let settlement = self.get_fee_settlement(working_set);
+        let mut token_address = gas_token_address::<C>()?;
+        let mut paid = reserved;
+        let mut refund = remaining;
+        let mut treasury_fee = settlement.treasury_share_of(consumed);
+        if let Some(payment) = self.pending_fee_payments.get(sender, working_set) {
+            self.pending_fee_payments.delete(sender, working_set);
+            // The reserved amount was converted without overflow when it was paid.
+            paid = payment.rate.convert_up(reserved).unwrap_or(Amount::MAX);
+            refund = payment.rate.convert_down(refund);
+            treasury_fee = payment.rate.convert_down(treasury_fee);
+            token_address = payment.token_address;
+        }
+
+        if refund > 0 {
+            let coins = Coins {
+                amount: refund,
+                token_address: token_address.clone(),
+            };
+            self.transfer_without_hooks(sequencer, sender, &coins, working_set)?;
+        }
+
+        if treasury_fee > 0 {
+            let coins = Coins {
+                amount: treasury_fee,
+                token_address: token_address.clone(),
+            };
+            match &settlement.treasury_address {
+                Some(treasury) => {
+                    self.transfer_without_hooks(sequencer, treasury, &coins, working_set)?
+                }
+                None => self.burn_without_hooks(&coins, sequencer, working_set)?,
+            }
+        }
+
+        working_set.emit_event(&SettleFee::<C> {
+            token_address,
+            sender: sender.clone(),
+            sequencer: sequencer.clone(),
+            refund,
+            sequencer_fee: paid.saturating_sub(refund).saturating_sub(treasury_fee),
+            treasury_fee,
+            treasury_address: settlement.treasury_address,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+#[cfg(feature = "native")]
+use core::str::FromStr;
+#[cfg(feature = "native")]
+use std::num::ParseIntError;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{CallResponse, StateMapAccessor, StateValueAccessor, WorkingSet};
+#[cfg(feature = "native")]
+use thiserror::Error;
+
+use crate::events::{RemoveFeeToken, SetFeeToken};
+use crate::hooks::gas_token_address;
+use crate::{Amount, Bank, Coins, TokenRole, TransferHooks};
+
+/// The maximum number of tokens accepted for fee payment, besides the gas token.
+pub const MAX_FEE_TOKENS: usize = 16;
+
+/// The price of the gas token in another token: `amount` gas tokens are worth
+/// `amount * numerator / denominator` tokens.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct ConversionRate {
+    /// The numerator of the rate.
+    pub numerator: u64,
+    /// The denominator of the rate.
+    pub denominator: u64,
+}
+
+impl ConversionRate {
+    /// Checks that both terms of the rate are positive.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.numerator == 0 || self.denominator == 0 {
+            bail!(
+                "Conversion rate {}/{} must have a positive numerator and denominator",
+                self.numerator,
+                self.denominator
+            )
+        }
+        Ok(())
+    }
+
+    /// Converts an amount of gas tokens, rounding up so that fees are never undercharged.
+    /// Returns `None` on overflow.
+    pub fn convert_up(&self, amount: Amount) -> Option<Amount> {
+        let numerator = amount as u128 * self.numerator as u128;
+        let converted = numerator.div_ceil(self.denominator as u128);
+        Amount::try_from(converted).ok()
+    }
+
+    /// Converts an amount of gas tokens, rounding down so that refunds never exceed the charged fees.
+    pub fn convert_down(&self, amount: Amount) -> Amount {
+        let converted = amount as u128 * self.numerator as u128 / self.denominator as u128;
+        Amount::try_from(converted).unwrap_or(Amount::MAX)
+    }
+}
+
+/// The errors that might arise when parsing a `ConversionRate` struct from a string.
+#[cfg(feature = "native")]
+#[derive(Debug, Error)]
+pub enum ConversionRateFromStrError {
+    /// The input string was malformed, so the `denominator` substring could not be extracted.
+    #[error("No denominator was provided. Make sure that your input is in the format: numerator/denominator. Example: 3/2")]
+    NoDenominatorProvided,
+    /// A term of the rate could not be parsed as a u64.
+    #[error("Could not parse {input} as a valid term of the rate: {err}")]
+    InvalidTerm { input: String, err: ParseIntError },
+}
+
+#[cfg(feature = "native")]
+impl FromStr for ConversionRate {
+    type Err = ConversionRateFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator_str, denominator_str) = s
+            .split_once('/')
+            .ok_or(ConversionRateFromStrError::NoDenominatorProvided)?;
+        let parse = |input: &str| {
+            input
+                .parse::<u64>()
+                .map_err(|err| ConversionRateFromStrError::InvalidTerm {
+                    input: input.into(),
+                    err,
+                })
+        };
+
+        Ok(Self {
+            numerator: parse(numerator_str)?,
+            denominator: parse(denominator_str)?,
+        })
+    }
+}
+
+/// The token in which the sender of the transaction being executed pays its fees,
+/// and the conversion rate applied to the reserved gas.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FeePayment<C: sov_modules_api::Context> {
+    pub(crate) token_address: C::Address,
+    pub(crate) rate: ConversionRate,
+}
+
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
+    /// Accepts the token `token_address` for fee payment at the conversion `rate`, or stops accepting it
+    /// if `rate` is `None`. Only the admins of the gas token can set the fee tokens, e.g. a governance
+    /// module or an account relaying an oracle feed.
+    pub(crate) fn set_fee_token(
+        &self,
+        token_address: C::Address,
+        rate: Option<ConversionRate>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let gas_token_address = gas_token_address::<C>()?;
+        let gas_token = self
+            .tokens
+            .get_or_err(&gas_token_address, working_set)
+            .context("Failed to load the gas token")?;
+        if !gas_token.has_role(TokenRole::Admin, context.sender()) {
+            bail!(
+                "Sender {} is not an admin of the gas token, and can't set the fee tokens",
+                context.sender()
+            )
+        }
+        if token_address == gas_token_address {
+            bail!("The gas token is always accepted for fee payment")
+        }
+
+        let mut fee_token_addresses = self
+            .fee_token_addresses
+            .get(working_set)
+            .unwrap_or_default();
+        match rate {
+            Some(rate) => {
+                rate.validate()?;
+                self.tokens
+                    .get_or_err(&token_address, working_set)
+                    .with_context(|| format!("Fee token {} doesn't exist", token_address))?;
+                if !fee_token_addresses.contains(&token_address) {
+                    if fee_token_addresses.len() >= MAX_FEE_TOKENS {
+                        bail!("At most {} fee tokens can be accepted", MAX_FEE_TOKENS)
+                    }
+                    fee_token_addresses.push(token_address.clone());
+                }
+                self.fee_tokens.set(&token_address, &rate, working_set);
+                working_set.emit_event(&SetFeeToken::<C> {
+                    token_address: token_address.clone(),
+                    rate,
+                });
+            }
+            None => {
+                let Some(position) = fee_token_addresses
+                    .iter()
+                    .position(|address| address == &token_address)
+                else {
+                    bail!("Token {} isn't accepted for fee payment", token_address)
+                };
+                fee_token_addresses.remove(position);
+                self.fee_tokens.delete(&token_address, working_set);
+                working_set.emit_event(&RemoveFeeToken::<C> {
+                    token_address: token_address.clone(),
+                });
+            }
+        }
+        self.fee_token_addresses
+            .set(&fee_token_addresses, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the conversion rate of the token `token_address`, if it is accepted for fee payment.
+    pub fn get_fee_token_rate(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<ConversionRate> {
+        self.fee_tokens.get(token_address, working_set)
+    }
+
+    /// Selects the token in which `sender` pays `amount` gas tokens: the gas token if the sender holds enough of it,
+    /// otherwise the first accepted fee token, in the order they were added, of which the sender holds enough.
+    /// Returns `None` if the gas token is selected or no fee token can cover the amount.
+    pub(crate) fn select_fee_payment(
+        &self,
+        sender: &C::Address,
+        amount: Amount,
+        gas_token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<(FeePayment<C>, Coins<C>)> {
+        let gas_balance = self
+            .get_balance_of(sender.clone(), gas_token_address.clone(), working_set)
+            .unwrap_or_default();
+        if gas_balance >= amount {
+            return None;
+        }
+
+        for token_address in self
+            .fee_token_addresses
+            .get(working_set)
+            .unwrap_or_default()
+        {
+            let Some(rate) = self.fee_tokens.get(&token_address, working_set) else {
+                continue;
+            };
+            let Some(converted) = rate.convert_up(amount) else {
+                continue;
+            };
+            let balance = self
+                .get_balance_of(sender.clone(), token_address.clone(), working_set)
+                .unwrap_or_default();
+            if balance >= converted {
+                let coins = Coins {
+                    amount: converted,
+                    token_address: token_address.clone(),
+                };
+                return Some((
+                    FeePayment {
+                        token_address,
+                        rate,
+                    },
+                    coins,
+                ));
+            }
+        }
+        None
+    }
+}
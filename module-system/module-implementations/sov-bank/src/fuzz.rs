@@ -0,0 +1,139 @@
+use arbitrary::{Arbitrary, Unstructured};
+use sov_modules_api::Context;
+
+use crate::{CallMessage, Coins, TransferOutput};
+
+impl<'a, C> Arbitrary<'a> for Coins<C>
+where
+    C: Context,
+    C::Address: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_with_addresses(u, &mut |u| u.arbitrary())
+    }
+}
+
+impl<C: Context> Coins<C> {
+    /// Creates arbitrary coins of the token whose address is produced by `address`.
+    pub fn arbitrary_with_addresses<'a>(
+        u: &mut Unstructured<'a>,
+        address: &mut impl FnMut(&mut Unstructured<'a>) -> arbitrary::Result<C::Address>,
+    ) -> arbitrary::Result<Self> {
+        Ok(Self {
+            amount: u.arbitrary()?,
+            token_address: address(u)?,
+        })
+    }
+}
+
+impl<'a, C> Arbitrary<'a> for TransferOutput<C>
+where
+    C: Context,
+    C::Address: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_with_addresses(u, &mut |u| u.arbitrary())
+    }
+}
+
+impl<C: Context> TransferOutput<C> {
+    /// Creates an arbitrary transfer output, whose addresses are produced by `address`.
+    pub fn arbitrary_with_addresses<'a>(
+        u: &mut Unstructured<'a>,
+        address: &mut impl FnMut(&mut Unstructured<'a>) -> arbitrary::Result<C::Address>,
+    ) -> arbitrary::Result<Self> {
+        Ok(Self {
+            to: address(u)?,
+            coins: Coins::arbitrary_with_addresses(u, address)?,
+        })
+    }
+}
+
+impl<'a, C> Arbitrary<'a> for CallMessage<C>
+where
+    C: Context,
+    C::Address: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_with_addresses(u, &mut |u| u.arbitrary())
+    }
+}
+
+impl<C: Context> CallMessage<C> {
+    /// Creates an arbitrary call message, whose addresses are produced by `address`.
+    ///
+    /// Random addresses almost never match an existing token or account, so fuzzers should pick them
+    /// from a small set of known addresses instead to reach the interesting code paths.
+    pub fn arbitrary_with_addresses<'a>(
+        u: &mut Unstructured<'a>,
+        address: &mut impl FnMut(&mut Unstructured<'a>) -> arbitrary::Result<C::Address>,
+    ) -> arbitrary::Result<Self> {
+        let msg = match u.int_in_range(0..=10)? {
+            0 => Self::CreateToken {
+                salt: u.arbitrary()?,
+                token_name: u.arbitrary()?,
+                initial_balance: u.arbitrary()?,
+                minter_address: address(u)?,
+                authorized_minters: arbitrary_addresses::<C>(u, address)?,
+                max_supply: u.arbitrary()?,
+                metadata: u.arbitrary()?,
+            },
+            1 => Self::Transfer {
+                to: address(u)?,
+                coins: Coins::arbitrary_with_addresses(u, address)?,
+            },
+            2 => {
+                let mut outputs = Vec::new();
+                for _ in 0..u.arbitrary_len::<[u8; 40]>()? {
+                    outputs.push(TransferOutput::arbitrary_with_addresses(u, address)?);
+                }
+                Self::TransferMany { outputs }
+            }
+            3 => Self::Burn {
+                coins: Coins::arbitrary_with_addresses(u, address)?,
+            },
+            4 => Self::Mint {
+                coins: Coins::arbitrary_with_addresses(u, address)?,
+                minter_address: address(u)?,
+            },
+            5 => Self::Freeze {
+                token_address: address(u)?,
+            },
+            6 => Self::GrantRole {
+                token_address: address(u)?,
+                role: u.arbitrary()?,
+                account: address(u)?,
+            },
+            7 => Self::RevokeRole {
+                token_address: address(u)?,
+                role: u.arbitrary()?,
+                account: address(u)?,
+            },
+            8 => Self::Approve {
+                spender: address(u)?,
+                coins: Coins::arbitrary_with_addresses(u, address)?,
+            },
+            9 => Self::TransferFrom {
+                from: address(u)?,
+                to: address(u)?,
+                coins: Coins::arbitrary_with_addresses(u, address)?,
+            },
+            _ => Self::SetFeeToken {
+                token_address: address(u)?,
+                rate: u.arbitrary()?,
+            },
+        };
+        Ok(msg)
+    }
+}
+
+fn arbitrary_addresses<'a, C: Context>(
+    u: &mut Unstructured<'a>,
+    address: &mut impl FnMut(&mut Unstructured<'a>) -> arbitrary::Result<C::Address>,
+) -> arbitrary::Result<Vec<C::Address>> {
+    let mut addresses = Vec::new();
+    for _ in 0..u.arbitrary_len::<[u8; 32]>()? {
+        addresses.push(address(u)?);
+    }
+    Ok(addresses)
+}
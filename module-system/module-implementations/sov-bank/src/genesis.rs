@@ -1,10 +1,10 @@
 use anyhow::{bail, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_modules_api::{StateMapAccessor, StateValueAccessor, StateVecAccessor, WorkingSet};
 
 use crate::token::Token;
-use crate::Bank;
+use crate::{Amount, Bank, FeeSettlementConfig, TokenMetadata, TransferHooks};
 
 /// Initial configuration for sov-bank module.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -12,6 +12,10 @@ use crate::Bank;
 pub struct BankConfig<C: sov_modules_api::Context> {
     /// A list of configurations for the initial tokens.
     pub tokens: Vec<TokenConfig<C>>,
+    /// How the consumed gas is shared between the sequencer and the treasury.
+    /// The sequencer keeps all of it by default.
+    #[serde(default)]
+    pub fee_settlement: FeeSettlementConfig<C>,
 }
 
 /// [`TokenConfig`] specifies a configuration used when generating a token for the bank
@@ -25,6 +29,15 @@ pub struct TokenConfig<C: sov_modules_api::Context> {
     pub address_and_balances: Vec<(C::Address, u64)>,
     /// The addresses that are authorized to mint the token.
     pub authorized_minters: Vec<C::Address>,
+    /// The addresses that are allowed to grant and revoke roles on the token.
+    #[serde(default)]
+    pub admins: Vec<C::Address>,
+    /// The maximum total supply of the token. Unlimited if not set.
+    #[serde(default)]
+    pub max_supply: Option<Amount>,
+    /// The symbol, decimals and metadata URI of the token.
+    #[serde(default)]
+    pub metadata: Option<TokenMetadata>,
     /// A salt used to encrypt the token address.
     pub salt: u64,
 }
@@ -32,21 +45,27 @@ pub struct TokenConfig<C: sov_modules_api::Context> {
 /// The address of the deployment node. For now, set to [0; 32]
 pub(crate) const DEPLOYER: [u8; 32] = [0; 32];
 
-impl<C: sov_modules_api::Context> Bank<C> {
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
     /// Init an instance of the bank module from the configuration `config`.
     /// For each token in the `config`, calls the [`Token::create`] function to create
-    /// the token. Upon success, updates the token set if the token address doesn't already exist.
+    /// the token. Upon success, updates the token set and the token registry if the token address doesn't already exist.
     pub(crate) fn init_module(
         &self,
         config: &<Self as sov_modules_api::Module>::Config,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
+        config.fee_settlement.validate()?;
+        self.fee_settlement.set(&config.fee_settlement, working_set);
+
         let parent_prefix = self.tokens.prefix();
         for token_config in config.tokens.iter() {
             let (token_address, token) = Token::<C>::create(
                 &token_config.token_name,
                 &token_config.address_and_balances,
                 &token_config.authorized_minters,
+                &token_config.admins,
+                token_config.max_supply,
+                token_config.metadata.clone(),
                 &DEPLOYER,
                 token_config.salt,
                 parent_prefix,
@@ -58,6 +77,10 @@ impl<C: sov_modules_api::Context> Bank<C> {
             }
 
             self.tokens.set(&token_address, &token, working_set);
+            self.token_addresses.push(&token_address, working_set);
+            for (address, _) in &token_config.address_and_balances {
+                self.record_holder(&token_address, address, working_set);
+            }
         }
         Ok(())
     }
@@ -85,8 +108,12 @@ mod tests {
                 token_name: "sov-demo-token".to_owned(),
                 address_and_balances: vec![(address, 100000000)],
                 authorized_minters: vec![address],
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
                 salt: 0,
             }],
+            fee_settlement: Default::default(),
         };
 
         let data = r#"
@@ -3,9 +3,10 @@ use core::str::FromStr;
 use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::macros::config_constant;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, GasUnit, WorkingSet};
+use sov_modules_api::tx_middleware::{TxMiddleware, TxMiddlewareState};
+use sov_modules_api::{Context, GasUnit, StateValueAccessor, WorkingSet};
 
-use crate::{Bank, Coins};
+use crate::{Bank, Coins, TransferHooks};
 
 #[config_constant]
 // This constant is a fixed value, expected to be generated as
@@ -21,6 +22,12 @@ use crate::{Bank, Coins};
 // https://github.com/Sovereign-Labs/sovereign-sdk/issues/1234
 const GAS_TOKEN_ADDRESS: &'static str;
 
+/// Returns the address of the gas token, in which the gas is priced.
+pub(crate) fn gas_token_address<C: Context>() -> anyhow::Result<C::Address> {
+    C::Address::from_str(GAS_TOKEN_ADDRESS)
+        .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))
+}
+
 /// The computed addresses of a pre-dispatch tx hook.
 pub struct BankTxHook<C: Context> {
     /// The tx sender address
@@ -29,7 +36,7 @@ pub struct BankTxHook<C: Context> {
     pub sequencer: C::Address,
 }
 
-impl<C: Context> TxHooks for Bank<C> {
+impl<C: Context, H: TransferHooks<C>> TxHooks for Bank<C, H> {
     type Context = C;
     type PreArg = BankTxHook<C>;
     type PreResult = ();
@@ -46,8 +53,7 @@ impl<C: Context> TxHooks for Bank<C> {
         let gas_fixed_cost = tx.gas_fixed_cost();
         if working_set.charge_gas(&gas_fixed_cost).is_err() {
             let amount = gas_fixed_cost.value(working_set.gas_price());
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
+            let token_address = gas_token_address::<C>()?;
             let coins = Coins {
                 amount,
                 token_address,
@@ -59,7 +65,7 @@ impl<C: Context> TxHooks for Bank<C> {
             // as the staked amount ought to be sufficient for executing any transaction count they
             // choose. However, if a sequencer lacks sufficient staked funds, it indicates a
             // critical design flaw in the sequencer registry.
-            self.burn(coins, sequencer, working_set).expect("Unrecoverable error: the sequencer doesn't have enough funds to pay for the transaction base cost.");
+            self.burn_without_hooks(&coins, sequencer, working_set).expect("Unrecoverable error: the sequencer doesn't have enough funds to pay for the transaction base cost.");
 
             anyhow::bail!(
                 "Transaction sender doesn't have enough funds to pay for the transaction base cost"
@@ -68,15 +74,24 @@ impl<C: Context> TxHooks for Bank<C> {
 
         let amount = tx.gas_limit().saturating_add(tx.gas_tip());
         if amount > 0 {
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
+            let token_address = gas_token_address::<C>()?;
             let from = sender;
             let to = sequencer;
-            let coins = Coins {
-                amount,
-                token_address,
-            };
-            self.transfer_from(from, to, coins, working_set)?;
+            // Senders lacking gas tokens pay in the first accepted fee token they hold enough of.
+            // The payment is recorded so that the unused gas is refunded in the same token.
+            match self.select_fee_payment(from, amount, &token_address, working_set) {
+                Some((payment, coins)) => {
+                    self.transfer_without_hooks(from, to, &coins, working_set)?;
+                    self.pending_fee_payments.set(from, &payment, working_set);
+                }
+                None => {
+                    let coins = Coins {
+                        amount,
+                        token_address,
+                    };
+                    self.transfer_without_hooks(from, to, &coins, working_set)?;
+                }
+            }
         }
 
         Ok(())
@@ -84,24 +99,50 @@ impl<C: Context> TxHooks for Bank<C> {
 
     fn post_dispatch_tx_hook(
         &self,
-        _tx: &Transaction<Self::Context>,
+        tx: &Transaction<Self::Context>,
         ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        let amount = working_set.gas_remaining_funds();
-
-        if amount > 0 {
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
-            let from = ctx.sequencer();
-            let to = ctx.sender();
-            let coins = Coins {
-                amount,
-                token_address,
-            };
-            self.transfer_from(from, to, coins, working_set)?;
+        let reserved = tx.gas_limit().saturating_add(tx.gas_tip());
+        if reserved == 0 {
+            return Ok(());
         }
 
-        Ok(())
+        let remaining = working_set.gas_remaining_funds();
+        let consumed = tx.gas_limit().saturating_sub(remaining);
+        self.settle_fee(
+            ctx.sender(),
+            ctx.sequencer(),
+            reserved,
+            consumed,
+            remaining,
+            working_set,
+        )
+    }
+}
+
+/// Charges the gas of the transaction to its sender, and refunds the unused gas after its execution.
+/// Must run after the middleware authenticating the sender.
+impl<C: Context, H: TransferHooks<C>> TxMiddleware<C> for Bank<C, H> {
+    fn pre_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        state: &mut TxMiddlewareState<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let hook = BankTxHook {
+            sender: state.sender.clone(),
+            sequencer: state.sequencer.clone(),
+        };
+        self.pre_dispatch_tx_hook(tx, working_set, &hook)
+    }
+
+    fn post_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        self.post_dispatch_tx_hook(tx, ctx, working_set)
     }
 }
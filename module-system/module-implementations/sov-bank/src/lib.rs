@@ -1,22 +1,40 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 mod call;
+pub mod events;
+mod fee_settlement;
+mod fee_tokens;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
 mod genesis;
 mod hooks;
+mod metadata;
 #[cfg(feature = "native")]
 mod query;
 #[cfg(feature = "native")]
 pub use query::*;
+mod supply;
 mod token;
+mod transfer_hooks;
 /// Util functions for bank
 pub mod utils;
+use std::marker::PhantomData;
+
 pub use call::*;
+pub use fee_settlement::{FeeSettlementConfig, MAX_BASIS_POINTS};
+#[cfg(feature = "native")]
+pub use fee_tokens::ConversionRateFromStrError;
+use fee_tokens::FeePayment;
+pub use fee_tokens::{ConversionRate, MAX_FEE_TOKENS};
 pub use genesis::*;
 pub use hooks::BankTxHook;
+pub use metadata::*;
 use sov_modules_api::{CallResponse, Error, GasUnit, ModuleInfo, WorkingSet};
+pub use supply::SupplyAudit;
 use token::Token;
 /// Specifies an interface to interact with tokens.
-pub use token::{Amount, Coins};
+pub use token::{Amount, Coins, TokenRole, TransferOutput};
+pub use transfer_hooks::TransferHooks;
 /// Methods to get a token address.
 pub use utils::{get_genesis_token_address, get_token_address};
 
@@ -37,15 +55,38 @@ pub struct BankGasConfig<GU: GasUnit> {
 
     /// Gas price multiplier for the freeze operation
     pub freeze: GU,
+
+    /// Gas price multiplier for the grant role operation
+    pub grant_role: GU,
+
+    /// Gas price multiplier for the revoke role operation
+    pub revoke_role: GU,
+
+    /// Gas price multiplier for the approve operation
+    pub approve: GU,
+
+    /// Gas price multiplier for the transfer from operation
+    pub transfer_from: GU,
+
+    /// Gas price multiplier for the set fee token operation
+    pub set_fee_token: GU,
 }
 
 /// The sov-bank module manages user balances. It provides functionality for:
 /// - Token creation.
 /// - Token transfers.
 /// - Token burn.
+/// - Token metadata, with a registry of all the tokens.
+/// - Role-based token administration, with minters, freezers and admins.
+/// - Allowances, to let other accounts transfer tokens on behalf of their owner.
+/// - Fee payment in other tokens than the gas token, at conversion rates set by the gas token admins.
+/// - Fee settlement: unused gas is refunded, and the consumed gas is shared between the sequencer and the treasury.
+/// - Supply audits, recomputing the supply of a token from the balances of its holders.
+///
+/// Other modules can react to token movements through the [`TransferHooks`] `H`.
 #[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
 #[derive(ModuleInfo, Clone)]
-pub struct Bank<C: sov_modules_api::Context> {
+pub struct Bank<C: sov_modules_api::Context, H: TransferHooks<C> = ()> {
     /// The address of the sov-bank module.
     #[address]
     pub(crate) address: C::Address,
@@ -57,9 +98,52 @@ pub struct Bank<C: sov_modules_api::Context> {
     /// A mapping of addresses to tokens in the sov-bank.
     #[state]
     pub(crate) tokens: sov_modules_api::StateMap<C::Address, Token<C>>,
+
+    /// The addresses of all the tokens, in creation order.
+    #[state]
+    pub(crate) token_addresses: sov_modules_api::StateVec<C::Address>,
+
+    /// The amounts that spenders are allowed to transfer on behalf of owners,
+    /// keyed by `(token_address, owner, spender)`.
+    #[state]
+    pub(crate) allowances: sov_modules_api::StateMap<(C::Address, C::Address, C::Address), Amount>,
+
+    /// The conversion rates of the tokens accepted for fee payment, besides the gas token.
+    #[state]
+    pub(crate) fee_tokens: sov_modules_api::StateMap<C::Address, ConversionRate>,
+
+    /// The addresses of the tokens accepted for fee payment, in the order they were added.
+    #[state]
+    pub(crate) fee_token_addresses: sov_modules_api::StateValue<Vec<C::Address>>,
+
+    /// Mapping from a sender address to the fee payment of its transaction being executed, if it isn't paid
+    /// in the gas token. Set by the pre-dispatch hook and cleared by the post-dispatch hook.
+    #[state]
+    pub(crate) pending_fee_payments: sov_modules_api::StateMap<C::Address, FeePayment<C>>,
+
+    /// How the consumed gas is shared between the sequencer and the treasury. Set at genesis.
+    #[state]
+    pub(crate) fee_settlement: sov_modules_api::StateValue<FeeSettlementConfig<C>>,
+
+    /// Used only by the supply audits: the number of addresses which ever held each token.
+    #[state]
+    pub(crate) holder_counts: sov_modules_api::AccessoryStateMap<C::Address, u64>,
+
+    /// Used only by the supply audits: the addresses which ever held each token, keyed by
+    /// `(token_address, index)`.
+    #[state]
+    pub(crate) holders: sov_modules_api::AccessoryStateMap<(C::Address, u64), C::Address>,
+
+    /// Used only by the supply audits: the index of each holder, keyed by `(token_address, holder)`.
+    #[state]
+    pub(crate) holder_indices: sov_modules_api::AccessoryStateMap<(C::Address, C::Address), u64>,
+
+    /// The hooks invoked on token transfers, mints and burns.
+    #[phantom]
+    pub(crate) hooks: PhantomData<H>,
 }
 
-impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> sov_modules_api::Module for Bank<C, H> {
     type Context = C;
 
     type Config = BankConfig<C>;
@@ -85,6 +169,8 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                 initial_balance,
                 minter_address,
                 authorized_minters,
+                max_supply,
+                metadata,
             } => {
                 self.charge_gas(working_set, &self.gas.create_token)?;
                 self.create_token(
@@ -93,6 +179,8 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                     initial_balance,
                     minter_address,
                     authorized_minters,
+                    max_supply,
+                    metadata,
                     context,
                     working_set,
                 )?;
@@ -104,6 +192,14 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                 Ok(self.transfer(to, coins, context, working_set)?)
             }
 
+            call::CallMessage::TransferMany { outputs } => {
+                // Each output is charged as a separate transfer
+                for _ in &outputs {
+                    self.charge_gas(working_set, &self.gas.transfer)?;
+                }
+                Ok(self.transfer_many(outputs, context, working_set)?)
+            }
+
             call::CallMessage::Burn { coins } => {
                 self.charge_gas(working_set, &self.gas.burn)?;
                 Ok(self.burn_from_eoa(coins, context, working_set)?)
@@ -122,6 +218,42 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                 self.charge_gas(working_set, &self.gas.freeze)?;
                 Ok(self.freeze(token_address, context, working_set)?)
             }
+
+            call::CallMessage::GrantRole {
+                token_address,
+                role,
+                account,
+            } => {
+                self.charge_gas(working_set, &self.gas.grant_role)?;
+                Ok(self.grant_role(token_address, role, account, context, working_set)?)
+            }
+
+            call::CallMessage::RevokeRole {
+                token_address,
+                role,
+                account,
+            } => {
+                self.charge_gas(working_set, &self.gas.revoke_role)?;
+                Ok(self.revoke_role(token_address, role, account, context, working_set)?)
+            }
+
+            call::CallMessage::Approve { spender, coins } => {
+                self.charge_gas(working_set, &self.gas.approve)?;
+                Ok(self.approve(spender, coins, context, working_set)?)
+            }
+
+            call::CallMessage::TransferFrom { from, to, coins } => {
+                self.charge_gas(working_set, &self.gas.transfer_from)?;
+                Ok(self.transfer_from_approved(from, to, coins, context, working_set)?)
+            }
+
+            call::CallMessage::SetFeeToken {
+                token_address,
+                rate,
+            } => {
+                self.charge_gas(working_set, &self.gas.set_fee_token)?;
+                Ok(self.set_fee_token(token_address, rate, context, working_set)?)
+            }
         }
     }
 }
@@ -0,0 +1,119 @@
+#[cfg(feature = "native")]
+use core::str::FromStr;
+#[cfg(feature = "native")]
+use std::num::ParseIntError;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "native")]
+use thiserror::Error;
+
+/// The maximum length of a token symbol, in characters.
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+
+/// The maximum number of decimals of a token. An [`Amount`](crate::Amount) has at most 20 digits,
+/// so more decimals would make every amount a fraction of a unit.
+pub const MAX_DECIMALS: u8 = 18;
+
+/// The maximum length of a token metadata URI, in bytes.
+pub const MAX_METADATA_URI_LENGTH: usize = 256;
+
+/// Describes how a token is displayed by wallets and explorers.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct TokenMetadata {
+    /// The ticker symbol of the token, e.g. `SOV`.
+    pub symbol: String,
+    /// The number of decimals used to display the amounts of the token.
+    /// An amount of `1234` with `2` decimals is displayed as `12.34`.
+    pub decimals: u8,
+    /// A URI pointing to additional off-chain metadata, such as a logo or a description.
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+impl TokenMetadata {
+    /// Checks that the symbol is a non-empty ASCII alphanumeric string of at most [`MAX_SYMBOL_LENGTH`] characters,
+    /// that there are at most [`MAX_DECIMALS`] decimals, and that the URI isn't empty nor longer
+    /// than [`MAX_METADATA_URI_LENGTH`] bytes.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.symbol.is_empty() || self.symbol.len() > MAX_SYMBOL_LENGTH {
+            bail!(
+                "Token symbol {} must have between 1 and {} characters",
+                self.symbol,
+                MAX_SYMBOL_LENGTH
+            )
+        }
+        if !self.symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(
+                "Token symbol {} must only contain ASCII letters and digits",
+                self.symbol
+            )
+        }
+        if self.decimals > MAX_DECIMALS {
+            bail!(
+                "Token decimals {} exceed the maximum of {}",
+                self.decimals,
+                MAX_DECIMALS
+            )
+        }
+        if let Some(uri) = &self.uri {
+            if uri.is_empty() || uri.len() > MAX_METADATA_URI_LENGTH {
+                bail!(
+                    "Token metadata URI must have between 1 and {} bytes",
+                    MAX_METADATA_URI_LENGTH
+                )
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The errors that might arise when parsing a `TokenMetadata` struct from a string.
+#[cfg(feature = "native")]
+#[derive(Debug, Error)]
+pub enum TokenMetadataFromStrError {
+    /// The input string was malformed, so the `decimals` substring could not be extracted.
+    #[error("No decimals were provided. Make sure that your input is in the format: symbol,decimals[,uri]. Example: SOV,6,https://example.com/sov.json")]
+    NoDecimalsProvided,
+    /// The decimals could not be parsed as a u8.
+    #[error("Could not parse {input} as valid decimals: {err}")]
+    InvalidDecimals { input: String, err: ParseIntError },
+}
+
+#[cfg(feature = "native")]
+impl FromStr for TokenMetadata {
+    type Err = TokenMetadataFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The URI may itself contain commas, so it is everything after the decimals
+        let mut parts = s.splitn(3, ',');
+
+        let symbol = parts.next().unwrap_or_default().to_owned();
+        let decimals_str = parts
+            .next()
+            .ok_or(TokenMetadataFromStrError::NoDecimalsProvided)?;
+        let decimals = decimals_str.parse::<u8>().map_err(|err| {
+            TokenMetadataFromStrError::InvalidDecimals {
+                input: decimals_str.into(),
+                err,
+            }
+        })?;
+
+        Ok(Self {
+            symbol,
+            decimals,
+            uri: parts.next().map(ToOwned::to_owned),
+        })
+    }
+}
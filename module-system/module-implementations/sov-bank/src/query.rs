@@ -1,9 +1,16 @@
 //! Defines rpc queries exposed by the bank module, along with the relevant types
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::utils::set_rpc_archival_version;
+use sov_modules_api::{StateMapAccessor, StateValueAccessor, StateVecAccessor, WorkingSet};
+use sov_state::codec::BorshCodec;
+use sov_state::storage::StorageKey;
 
-use crate::{Amount, Bank};
+use crate::call::prefix_from_address_with_parent;
+use crate::{Amount, Bank, ConversionRate, SupplyAudit, TokenMetadata};
+
+/// The maximum number of tokens returned by a single call to the `listTokens` rpc method.
+pub const MAX_LISTED_TOKENS: u64 = 100;
 
 /// Structure returned by the `balance_of` rpc method.
 #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
@@ -12,6 +19,14 @@ pub struct BalanceResponse {
     pub amount: Option<Amount>,
 }
 
+/// Structure returned by the `balanceStorageKey` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct StorageKeyResponse {
+    /// The hex encoded storage key.
+    #[serde(with = "sov_rollup_interface::rpc::utils::rpc_hex")]
+    pub key: Vec<u8>,
+}
+
 /// Structure returned by the `supply_of` rpc method.
 #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
 pub struct TotalSupplyResponse {
@@ -19,6 +34,80 @@ pub struct TotalSupplyResponse {
     pub amount: Option<Amount>,
 }
 
+/// Structure returned by the `verifySupply` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct SupplyAuditResponse {
+    /// The result of the audit, if the token exists.
+    pub audit: Option<SupplyAudit>,
+    /// Whether the total supply of the token is the sum of the balances of its holders.
+    pub consistent: bool,
+}
+
+/// Structure returned by the `allowance` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct AllowanceResponse {
+    /// The amount a spender is allowed to transfer on behalf of an owner. Equivalent to u64.
+    pub amount: Option<Amount>,
+}
+
+/// Information about a token, returned by the `tokenInfo` and `listTokens` rpc methods.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct TokenInfo<C: sov_modules_api::Context> {
+    /// The address of the token.
+    pub address: C::Address,
+    /// The name of the token.
+    pub name: String,
+    /// The total supply of the token.
+    pub total_supply: Amount,
+    /// The maximum total supply of the token, if the supply is capped.
+    pub max_supply: Option<Amount>,
+    /// Whether the token supply is frozen.
+    pub frozen: bool,
+    /// The symbol, decimals and metadata URI of the token, if provided at creation.
+    pub metadata: Option<TokenMetadata>,
+}
+
+/// Structure returned by the `tokenInfo` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct TokenInfoResponse<C: sov_modules_api::Context> {
+    /// The information about the token, if it exists.
+    pub token: Option<TokenInfo<C>>,
+}
+
+/// Structure returned by the `listTokens` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct ListTokensResponse<C: sov_modules_api::Context> {
+    /// The tokens of the requested page, in creation order.
+    pub tokens: Vec<TokenInfo<C>>,
+    /// The total number of tokens.
+    pub total: u64,
+}
+
+/// A token accepted for fee payment, returned by the `feeTokens` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct FeeToken<C: sov_modules_api::Context> {
+    /// The address of the token.
+    pub address: C::Address,
+    /// The amount of tokens worth a gas token.
+    pub rate: ConversionRate,
+}
+
 #[rpc_gen(client, server, namespace = "bank")]
 impl<C: sov_modules_api::Context> Bank<C> {
     #[rpc_method(name = "balanceOf")]
@@ -31,14 +120,30 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token_address: C::Address,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<BalanceResponse> {
-        if let Some(v) = version {
-            working_set.set_archival_version(v)
-        }
+        set_rpc_archival_version(version, working_set)?;
         Ok(BalanceResponse {
             amount: self.get_balance_of(user_address, token_address, working_set),
         })
     }
 
+    #[rpc_method(name = "balanceStorageKey")]
+    /// Rpc method that returns the storage key of the balance of the user at the address `user_address`
+    /// for the token stored at the address `token_address`. Passing this key to the `rollup_getProof` rpc
+    /// method proves the balance against a state root: the proven value is the borsh encoding of an [`Amount`].
+    pub fn balance_storage_key_of(
+        &self,
+        user_address: C::Address,
+        token_address: C::Address,
+        _working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<StorageKeyResponse> {
+        Ok(StorageKeyResponse {
+            key: self
+                .balance_storage_key(user_address, token_address)
+                .key()
+                .to_vec(),
+        })
+    }
+
     #[rpc_method(name = "supplyOf")]
     /// Rpc method that returns the supply of a token stored at the address `token_address`.
     pub fn supply_of(
@@ -47,11 +152,139 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token_address: C::Address,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<TotalSupplyResponse> {
-        if let Some(v) = version {
-            working_set.set_archival_version(v)
-        }
+        set_rpc_archival_version(version, working_set)?;
         Ok(TotalSupplyResponse {
             amount: self.get_total_supply_of(&token_address, working_set),
         })
     }
+
+    #[rpc_method(name = "verifySupply")]
+    /// Rpc method that recomputes the supply of the token stored at the address `token_address` from the
+    /// balances of its holders, and compares it with its total supply.
+    pub fn verify_supply_of(
+        &self,
+        version: Option<u64>,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<SupplyAuditResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        let audit = self.verify_supply(&token_address, working_set);
+        Ok(SupplyAuditResponse {
+            consistent: audit.is_some_and(|audit| audit.is_consistent()),
+            audit,
+        })
+    }
+
+    #[rpc_method(name = "allowance")]
+    /// Rpc method that returns the amount of the token stored at the address `token_address`
+    /// that `spender` can transfer on behalf of `owner`.
+    pub fn allowance(
+        &self,
+        version: Option<u64>,
+        token_address: C::Address,
+        owner: C::Address,
+        spender: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AllowanceResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        Ok(AllowanceResponse {
+            amount: self.get_allowance(token_address, owner, spender, working_set),
+        })
+    }
+
+    #[rpc_method(name = "tokenInfo")]
+    /// Rpc method that returns the name, supply and metadata of the token stored at the address `token_address`.
+    pub fn token_info(
+        &self,
+        version: Option<u64>,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<TokenInfoResponse<C>> {
+        set_rpc_archival_version(version, working_set)?;
+        Ok(TokenInfoResponse {
+            token: self.get_token_info(token_address, working_set),
+        })
+    }
+
+    #[rpc_method(name = "listTokens")]
+    /// Rpc method that returns up to `limit` tokens in creation order, skipping the first `offset` tokens.
+    /// At most [`MAX_LISTED_TOKENS`] tokens are returned.
+    pub fn list_tokens(
+        &self,
+        version: Option<u64>,
+        offset: u64,
+        limit: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ListTokensResponse<C>> {
+        set_rpc_archival_version(version, working_set)?;
+        let total = self.token_addresses.len(working_set) as u64;
+        let end = offset
+            .saturating_add(limit.min(MAX_LISTED_TOKENS))
+            .min(total);
+
+        let mut tokens = Vec::new();
+        for index in offset..end {
+            if let Some(token) = self
+                .token_addresses
+                .get(index as usize, working_set)
+                .and_then(|token_address| self.get_token_info(token_address, working_set))
+            {
+                tokens.push(token);
+            }
+        }
+        Ok(ListTokensResponse { tokens, total })
+    }
+
+    #[rpc_method(name = "feeTokens")]
+    /// Rpc method that returns the tokens accepted for fee payment besides the gas token, in the order
+    /// in which they are tried, with their conversion rates.
+    pub fn fee_token_rates(&self, working_set: &mut WorkingSet<C>) -> RpcResult<Vec<FeeToken<C>>> {
+        let mut fee_tokens = Vec::new();
+        for address in self
+            .fee_token_addresses
+            .get(working_set)
+            .unwrap_or_default()
+        {
+            if let Some(rate) = self.fee_tokens.get(&address, working_set) {
+                fee_tokens.push(FeeToken { address, rate });
+            }
+        }
+        Ok(fee_tokens)
+    }
+}
+
+impl<C: sov_modules_api::Context> Bank<C> {
+    /// Returns the storage key of the balance of the user at the address `user_address` for the token
+    /// stored at the address `token_address`.
+    pub fn balance_storage_key(
+        &self,
+        user_address: C::Address,
+        token_address: C::Address,
+    ) -> StorageKey {
+        let prefix = prefix_from_address_with_parent::<C>(self.tokens.prefix(), &token_address);
+        StorageKey::new(&prefix, &user_address, &BorshCodec)
+    }
+
+    /// Returns the storage key of the token stored at the address `token_address`, which is read by every
+    /// transfer of the token, e.g. to declare it in the access list of a transaction.
+    pub fn token_storage_key(&self, token_address: C::Address) -> StorageKey {
+        StorageKey::new(self.tokens.prefix(), &token_address, &BorshCodec)
+    }
+
+    fn get_token_info(
+        &self,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<TokenInfo<C>> {
+        self.tokens
+            .get(&token_address, working_set)
+            .map(|token| TokenInfo {
+                address: token_address,
+                name: token.name,
+                total_supply: token.total_supply,
+                max_supply: token.max_supply,
+                frozen: token.authorized_minters.is_empty(),
+                metadata: token.metadata,
+            })
+    }
 }
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{StateMapAccessor, WorkingSet};
+
+use crate::{Amount, Bank, TransferHooks};
+
+/// The result of the audit of the supply of a token, comparing its total supply with the sum of the
+/// balances of its holders.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SupplyAudit {
+    /// The total supply of the token, updated on each mint and burn.
+    pub total_supply: Amount,
+    /// The sum of the balances of all the addresses which ever held the token.
+    pub sum_of_balances: u128,
+    /// The number of addresses which ever held the token.
+    pub holder_count: u64,
+}
+
+impl SupplyAudit {
+    /// Returns true if the total supply is the sum of the balances.
+    pub fn is_consistent(&self) -> bool {
+        self.total_supply as u128 == self.sum_of_balances
+    }
+}
+
+impl<C: sov_modules_api::Context, H: TransferHooks<C>> Bank<C, H> {
+    /// Records that `holder` may hold the token stored at `token_address`, so that its balance is
+    /// included in the supply audits. The holders are kept in the accessory state, outside of the
+    /// state root, so this is a no-op for the zk prover.
+    pub(crate) fn record_holder(
+        &self,
+        token_address: &C::Address,
+        holder: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        if !cfg!(feature = "native") {
+            return;
+        }
+
+        let mut accessory_state = working_set.accessory_state();
+        let key = (token_address.clone(), holder.clone());
+        if self
+            .holder_indices
+            .get(&key, &mut accessory_state)
+            .is_some()
+        {
+            return;
+        }
+        let index = self
+            .holder_counts
+            .get(token_address, &mut accessory_state)
+            .unwrap_or_default();
+        self.holders.set(
+            &(token_address.clone(), index),
+            holder,
+            &mut accessory_state,
+        );
+        self.holder_indices.set(&key, &index, &mut accessory_state);
+        self.holder_counts
+            .set(token_address, &(index + 1), &mut accessory_state);
+    }
+
+    /// Recomputes the supply of the token stored at `token_address` from the balances of its holders,
+    /// and compares it with its total supply. Returns `None` if the token doesn't exist.
+    ///
+    /// The audit reads the state at the archival version of the `working_set`, if it is set.
+    #[cfg(feature = "native")]
+    pub fn verify_supply(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<SupplyAudit> {
+        let token = self.tokens.get(token_address, working_set)?;
+        let holder_count = self
+            .holder_counts
+            .get(token_address, &mut working_set.accessory_state())
+            .unwrap_or_default();
+
+        let mut sum_of_balances = 0u128;
+        for index in 0..holder_count {
+            let Some(holder) = self.holders.get(
+                &(token_address.clone(), index),
+                &mut working_set.accessory_state(),
+            ) else {
+                continue;
+            };
+            sum_of_balances += token.balances.get(&holder, working_set).unwrap_or_default() as u128;
+        }
+
+        Some(SupplyAudit {
+            total_supply: token.total_supply,
+            sum_of_balances,
+            holder_count,
+        })
+    }
+}
@@ -13,6 +13,7 @@ use sov_state::Prefix;
 use thiserror::Error;
 
 use crate::call::prefix_from_address_with_parent;
+use crate::TokenMetadata;
 
 /// Type alias to store an amount of token.
 pub type Amount = u64;
@@ -104,6 +105,108 @@ impl<C: sov_modules_api::Context> std::fmt::Display for Coins<C> {
     }
 }
 
+/// A recipient of a [`CallMessage::TransferMany`](crate::CallMessage::TransferMany), along with the coins
+/// transferred to it.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "C::Address: ::schemars::JsonSchema",
+        rename = "TransferOutput"
+    )
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct TransferOutput<C: sov_modules_api::Context> {
+    /// The address to which the tokens will be transferred.
+    pub to: C::Address,
+    /// The amount of tokens to transfer.
+    pub coins: Coins<C>,
+}
+
+/// The errors that might arise when parsing a `TransferOutput` struct from a string.
+#[cfg(feature = "native")]
+#[derive(Debug, Error)]
+pub enum TransferOutputFromStrError {
+    /// The input string was malformed, so the `to` substring could not be extracted.
+    #[error("No coins were provided. Make sure that your input is in the format: to,amount,token_address. Example: sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94,100,sov15vspj48hpttzyvxu8kzq5klhvaczcpyxn6z6k0hwpwtzs4a6wkvqmlyjd6")]
+    NoCoinsProvided,
+    /// The recipient could not be parsed as a valid address.
+    #[error("Could not parse {input} as a valid address: {err}")]
+    InvalidRecipient { input: String, err: anyhow::Error },
+    /// The coins could not be parsed.
+    #[error(transparent)]
+    InvalidCoins(#[from] CoinsFromStrError),
+}
+
+#[cfg(feature = "native")]
+impl<C: sov_modules_api::Context> FromStr for TransferOutput<C> {
+    type Err = TransferOutputFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (to_str, coins_str) = s
+            .split_once(',')
+            .ok_or(TransferOutputFromStrError::NoCoinsProvided)?;
+
+        let to = C::Address::from_str(to_str).map_err(|err| {
+            TransferOutputFromStrError::InvalidRecipient {
+                input: to_str.into(),
+                err,
+            }
+        })?;
+
+        Ok(Self {
+            to,
+            coins: Coins::from_str(coins_str)?,
+        })
+    }
+}
+
+/// The roles that can be granted on a token. Each role is held by a set of addresses.
+#[cfg_attr(
+    feature = "native",
+    derive(clap::ValueEnum),
+    derive(schemars::JsonSchema)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub enum TokenRole {
+    /// Allowed to mint new tokens, as long as the token isn't frozen.
+    Minter,
+    /// Allowed to freeze the token supply.
+    Freezer,
+    /// Allowed to grant and revoke roles.
+    Admin,
+}
+
+impl std::fmt::Display for TokenRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenRole::Minter => write!(f, "minter"),
+            TokenRole::Freezer => write!(f, "freezer"),
+            TokenRole::Admin => write!(f, "admin"),
+        }
+    }
+}
+
 /// This struct represents a token in the sov-bank module.
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
 pub(crate) struct Token<C: sov_modules_api::Context> {
@@ -120,6 +223,14 @@ pub(crate) struct Token<C: sov_modules_api::Context> {
     /// Freezing a token requires emptying the vector
     /// NOTE: This is explicit so if a creator doesn't add themselves, then they can't mint
     pub(crate) authorized_minters: Vec<C::Address>,
+    /// Vector containing the addresses allowed to freeze the token.
+    pub(crate) freezers: Vec<C::Address>,
+    /// Vector containing the addresses allowed to grant and revoke roles.
+    pub(crate) admins: Vec<C::Address>,
+    /// The maximum total supply of the token, if any.
+    pub(crate) max_supply: Option<Amount>,
+    /// How the token is displayed, if provided at creation.
+    pub(crate) metadata: Option<TokenMetadata>,
 }
 
 impl<C: sov_modules_api::Context> Token<C> {
@@ -168,7 +279,7 @@ impl<C: sov_modules_api::Context> Token<C> {
         if self.authorized_minters.is_empty() {
             bail!("Token {} is already frozen", self.name)
         }
-        self.is_authorized_minter(sender)?;
+        self.check_role(TokenRole::Freezer, sender)?;
         self.authorized_minters = vec![];
         Ok(())
     }
@@ -176,7 +287,7 @@ impl<C: sov_modules_api::Context> Token<C> {
     /// Mints a given `amount` of token sent by `sender` to the specified `mint_to_address`.
     /// Checks that the `authorized_minters` set is not empty for the token and that the `sender`
     /// is an `authorized_minter`. If so, update the balances of token for the `mint_to_address` by
-    /// adding the minted tokens. Updates the `total_supply` of that token.
+    /// adding the minted tokens. Updates the `total_supply` of that token, which can't exceed the `max_supply`.
     pub(crate) fn mint(
         &mut self,
         authorizer: &C::Address,
@@ -188,7 +299,24 @@ impl<C: sov_modules_api::Context> Token<C> {
             bail!("Attempt to mint frozen token {}", self.name)
         }
 
-        self.is_authorized_minter(authorizer)?;
+        self.check_role(TokenRole::Minter, authorizer)?;
+        let total_supply = self
+            .total_supply
+            .checked_add(amount)
+            .ok_or(anyhow::Error::msg(
+                "Total Supply overflow in the mint method of bank module",
+            ))?;
+        if let Some(max_supply) = self.max_supply {
+            if total_supply > max_supply {
+                bail!(
+                    "Minting {} would exceed the max supply {} of token {}",
+                    amount,
+                    max_supply,
+                    self.name
+                )
+            }
+        }
+
         let to_balance: Amount = self
             .balances
             .get(mint_to_address, working_set)
@@ -199,20 +327,75 @@ impl<C: sov_modules_api::Context> Token<C> {
             ))?;
 
         self.balances.set(mint_to_address, &to_balance, working_set);
-        self.total_supply = self
-            .total_supply
-            .checked_add(amount)
-            .ok_or(anyhow::Error::msg(
-                "Total Supply overflow in the mint method of bank module",
-            ))?;
+        self.total_supply = total_supply;
+        Ok(())
+    }
+
+    /// Grants the `role` to `account`. Only admins can grant roles, and the minter role can't be granted
+    /// on a frozen token.
+    pub(crate) fn grant_role(
+        &mut self,
+        sender: &C::Address,
+        role: TokenRole,
+        account: &C::Address,
+    ) -> Result<()> {
+        self.check_role(TokenRole::Admin, sender)?;
+        if role == TokenRole::Minter && self.authorized_minters.is_empty() {
+            bail!(
+                "Attempt to grant the minter role on frozen token {}",
+                self.name
+            )
+        }
+
+        let members = self.role_members_mut(role);
+        if members.contains(account) {
+            bail!("{} is already a {} of token {}", account, role, self.name)
+        }
+        members.push(account.clone());
         Ok(())
     }
 
-    fn is_authorized_minter(&self, sender: &C::Address) -> Result<()> {
-        if !self.authorized_minters.contains(sender) {
+    /// Revokes the `role` from `account`. Only admins can revoke roles.
+    /// Revoking the last minter freezes the token.
+    pub(crate) fn revoke_role(
+        &mut self,
+        sender: &C::Address,
+        role: TokenRole,
+        account: &C::Address,
+    ) -> Result<()> {
+        self.check_role(TokenRole::Admin, sender)?;
+
+        let members = self.role_members_mut(role);
+        let Some(position) = members.iter().position(|member| member == account) else {
+            bail!("{} is not a {} of token {}", account, role, self.name)
+        };
+        members.remove(position);
+        Ok(())
+    }
+
+    /// Returns true if `address` holds the `role`.
+    pub(crate) fn has_role(&self, role: TokenRole, address: &C::Address) -> bool {
+        match role {
+            TokenRole::Minter => self.authorized_minters.contains(address),
+            TokenRole::Freezer => self.freezers.contains(address),
+            TokenRole::Admin => self.admins.contains(address),
+        }
+    }
+
+    fn role_members_mut(&mut self, role: TokenRole) -> &mut Vec<C::Address> {
+        match role {
+            TokenRole::Minter => &mut self.authorized_minters,
+            TokenRole::Freezer => &mut self.freezers,
+            TokenRole::Admin => &mut self.admins,
+        }
+    }
+
+    fn check_role(&self, role: TokenRole, sender: &C::Address) -> Result<()> {
+        if !self.has_role(role, sender) {
             bail!(
-                "Sender {} is not an authorized minter of token {}",
+                "Sender {} is not an authorized {} of token {}",
                 sender,
+                role,
                 self.name
             )
         }
@@ -239,16 +422,27 @@ impl<C: sov_modules_api::Context> Token<C> {
     /// The `token_name`, `sender` address (as a `u8` slice), and the `salt` (`u64` number) are used as an input
     /// to an hash function that computes the token address. Then the initial accounts and balances are populated
     /// from the `address_and_balances` slice and the `total_supply` of tokens is updated each time.
+    /// The authorized minters are also allowed to freeze the token, and the `metadata` is validated if provided.
     /// Returns a tuple containing the computed `token_address` and the created `token` object.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn create(
         token_name: &str,
         address_and_balances: &[(C::Address, u64)],
         authorized_minters: &[C::Address],
+        admins: &[C::Address],
+        max_supply: Option<Amount>,
+        metadata: Option<TokenMetadata>,
         sender: &[u8],
         salt: u64,
         parent_prefix: &Prefix,
         working_set: &mut WorkingSet<C>,
     ) -> Result<(C::Address, Self)> {
+        if let Some(metadata) = &metadata {
+            metadata
+                .validate()
+                .with_context(|| format!("Invalid metadata for token {}", token_name))?;
+        }
+
         let token_address = super::get_token_address::<C>(token_name, sender, salt);
         let token_prefix = prefix_from_address_with_parent::<C>(parent_prefix, &token_address);
         let balances = sov_modules_api::StateMap::new(token_prefix);
@@ -264,22 +458,44 @@ impl<C: sov_modules_api::Context> Token<C> {
             None => bail!("Total supply overflow"),
         };
 
-        let mut indices = HashSet::new();
-        let mut auth_minter_list = Vec::new();
-
-        for (i, item) in authorized_minters.iter().enumerate() {
-            if indices.insert(item.as_ref()) {
-                auth_minter_list.push(authorized_minters[i].clone());
+        if let Some(max_supply) = max_supply {
+            if total_supply > max_supply {
+                bail!(
+                    "Initial supply {} exceeds the max supply {} of token {}",
+                    total_supply,
+                    max_supply,
+                    token_name
+                )
             }
         }
 
+        let auth_minter_list = dedup_addresses::<C>(authorized_minters);
+
         let token = Token::<C> {
             name: token_name.to_owned(),
             total_supply,
             balances,
+            freezers: auth_minter_list.clone(),
             authorized_minters: auth_minter_list,
+            admins: dedup_addresses::<C>(admins),
+            max_supply,
+            metadata,
         };
 
         Ok((token_address, token))
     }
 }
+
+/// Removes the duplicates from `addresses`, keeping the first occurrence of each address.
+fn dedup_addresses<C: sov_modules_api::Context>(addresses: &[C::Address]) -> Vec<C::Address> {
+    let mut indices = HashSet::new();
+    let mut list = Vec::new();
+
+    for (i, item) in addresses.iter().enumerate() {
+        if indices.insert(item.as_ref()) {
+            list.push(addresses[i].clone());
+        }
+    }
+
+    list
+}
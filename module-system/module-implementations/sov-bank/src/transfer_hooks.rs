@@ -0,0 +1,77 @@
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Coins;
+
+/// Callbacks invoked by the bank when tokens are transferred, minted or burned, so that other modules
+/// can react to token movements (e.g. for fee sharing, vesting or blacklisting) without forking the bank.
+///
+/// The hooks are registered with the type parameter of the [`Bank`](crate::Bank) used by the runtime,
+/// e.g. `Bank<C, Vesting<C>>`. Several hooks can be registered at once with a tuple, e.g. `Bank<C, (A, B)>`.
+/// Note that bank instances nested in other modules are independent, and invoke their own hooks.
+/// The RPC methods are implemented for the bank without hooks, which reads the same state.
+///
+/// The hooks are called with the working set of the operation, once the balances have been updated.
+/// Returning an error fails the operation, which reverts the transaction.
+/// The hooks are not called for the balances set at genesis, nor for the gas payments.
+pub trait TransferHooks<C: Context>: Send + Sync + 'static {
+    /// Called when `coins` are transferred from `from` to `to`.
+    fn on_transfer(
+        _from: &C::Address,
+        _to: &C::Address,
+        _coins: &Coins<C>,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called when `coins` are minted to `to`, including the initial balance of a new token.
+    fn on_mint(
+        _to: &C::Address,
+        _coins: &Coins<C>,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called when `coins` are burned from `from`.
+    fn on_burn(
+        _from: &C::Address,
+        _coins: &Coins<C>,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// No hooks: the default for the [`Bank`](crate::Bank).
+impl<C: Context> TransferHooks<C> for () {}
+
+impl<C: Context, A: TransferHooks<C>, B: TransferHooks<C>> TransferHooks<C> for (A, B) {
+    fn on_transfer(
+        from: &C::Address,
+        to: &C::Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        A::on_transfer(from, to, coins, working_set)?;
+        B::on_transfer(from, to, coins, working_set)
+    }
+
+    fn on_mint(
+        to: &C::Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        A::on_mint(to, coins, working_set)?;
+        B::on_mint(to, coins, working_set)
+    }
+
+    fn on_burn(
+        from: &C::Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        A::on_burn(from, coins, working_set)?;
+        B::on_burn(from, coins, working_set)
+    }
+}
@@ -29,9 +29,9 @@ pub fn get_genesis_token_address<C: sov_modules_api::Context>(
 mod tests {
     use sov_modules_api::Context;
 
-    use crate::{Bank, BankGasConfig};
+    use crate::{Bank, BankGasConfig, TransferHooks};
 
-    impl<C: Context> Bank<C> {
+    impl<C: Context, H: TransferHooks<C>> Bank<C, H> {
         /// Returns the underlying gas config
         pub fn gas_config(&self) -> &BankGasConfig<C::GasUnit> {
             &self.gas
@@ -0,0 +1,167 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::events::Approve;
+use sov_bank::{get_genesis_token_address, AllowanceResponse, Bank, CallMessage, Coins};
+use sov_modules_api::{Context, Module, ModuleEvent, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn approve_and_transfer_from() {
+    let initial_balance = 100;
+    let approved_amount = 30;
+    let bank_config = create_bank_config_with_token(4, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0;
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0;
+    let receiver_address = bank_config.tokens[0].address_and_balances[2].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[3].0;
+    let owner_context = C::new(owner_address, sequencer_address, 1);
+    let spender_context = C::new(spender_address, sequencer_address, 1);
+
+    let query_allowance = |working_set: &mut WorkingSet<C>| -> Option<u64> {
+        let allowance: AllowanceResponse = bank
+            .allowance(
+                None,
+                token_address,
+                owner_address,
+                spender_address,
+                working_set,
+            )
+            .unwrap();
+        allowance.amount
+    };
+    assert_eq!(None, query_allowance(&mut working_set));
+
+    let transfer_from = |amount: u64| CallMessage::TransferFrom {
+        from: owner_address,
+        to: receiver_address,
+        coins: Coins {
+            amount,
+            token_address,
+        },
+    };
+
+    // Nothing can be spent before the approval
+    assert!(bank
+        .call(transfer_from(1), &spender_context, &mut working_set)
+        .is_err());
+
+    let approve_message = CallMessage::Approve {
+        spender: spender_address,
+        coins: Coins {
+            amount: approved_amount,
+            token_address,
+        },
+    };
+    bank.call(approve_message, &owner_context, &mut working_set)
+        .expect("Approve call failed");
+    assert_eq!(Some(approved_amount), query_allowance(&mut working_set));
+    assert_eq!(1, working_set.events().len());
+    let approve_event = Approve::<C> {
+        token_address,
+        owner: owner_address,
+        spender: spender_address,
+        amount: approved_amount,
+    };
+    assert_eq!(working_set.events()[0], approve_event.to_event());
+    let indexed: Vec<_> = working_set.events()[0]
+        .indexed_attributes()
+        .iter()
+        .map(|attribute| attribute.name().inner().as_slice())
+        .collect();
+    assert_eq!(indexed, vec![&b"token_address"[..], b"owner", b"spender"]);
+
+    // The allowance is spent
+    bank.call(transfer_from(10), &spender_context, &mut working_set)
+        .expect("TransferFrom call failed");
+    assert_eq!(
+        Some(approved_amount - 10),
+        query_allowance(&mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance - 10),
+        bank.get_balance_of(owner_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 10),
+        bank.get_balance_of(receiver_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance),
+        bank.get_balance_of(spender_address, token_address, &mut working_set)
+    );
+
+    // The remaining allowance can't be exceeded
+    assert!(bank
+        .call(
+            transfer_from(approved_amount),
+            &spender_context,
+            &mut working_set
+        )
+        .is_err());
+    assert_eq!(
+        Some(approved_amount - 10),
+        query_allowance(&mut working_set)
+    );
+
+    // Only the owner's allowances are spent: the owner has no allowance on the spender
+    let reverse_transfer_from = CallMessage::TransferFrom {
+        from: spender_address,
+        to: owner_address,
+        coins: Coins {
+            amount: 1,
+            token_address,
+        },
+    };
+    assert!(bank
+        .call(reverse_transfer_from, &owner_context, &mut working_set)
+        .is_err());
+
+    // The allowance can be revoked
+    let revoke_message = CallMessage::Approve {
+        spender: spender_address,
+        coins: Coins {
+            amount: 0,
+            token_address,
+        },
+    };
+    bank.call(revoke_message, &owner_context, &mut working_set)
+        .expect("Approve call failed");
+    assert_eq!(Some(0), query_allowance(&mut working_set));
+    assert!(bank
+        .call(transfer_from(1), &spender_context, &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn approve_non_existent_token() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0;
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0;
+    let owner_context = C::new(owner_address, owner_address, 1);
+
+    let approve_message = CallMessage::Approve {
+        spender: spender_address,
+        coins: Coins {
+            amount: 10,
+            token_address: generate_address("unknown_token"),
+        },
+    };
+    assert!(bank
+        .call(approve_message, &owner_context, &mut working_set)
+        .is_err());
+}
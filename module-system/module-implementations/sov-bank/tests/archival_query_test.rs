@@ -1,11 +1,12 @@
 mod helpers;
 
+use borsh::BorshDeserialize;
 use helpers::*;
 use sov_bank::{get_genesis_token_address, Amount, Bank, CallMessage, Coins};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::{Address, Context, Module, StateReaderAndWriter, WorkingSet};
 use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
-use sov_state::storage::{StorageKey, StorageValue};
+use sov_state::storage::{NativeStorage, StorageKey, StorageValue};
 use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
 
 #[test]
@@ -15,7 +16,7 @@ fn transfer_initial_token() {
     let tmpdir = tempfile::tempdir().unwrap();
     let prover_storage = new_orphan_storage(tmpdir.path()).unwrap();
     let mut working_set = WorkingSet::new(prover_storage.clone());
-    let bank = Bank::default();
+    let bank = Bank::<C>::default();
     bank.genesis(&bank_config, &mut working_set).unwrap();
 
     let token_address = get_genesis_token_address::<C>(
@@ -84,7 +85,7 @@ fn transfer_initial_token() {
 
     let archival_slot: u64 = 2;
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
-    working_set.set_archival_version(archival_slot);
+    working_set.set_archival_version(archival_slot).unwrap();
 
     let (sender_balance, receiver_balance) = query_sender_receiver_balances(
         &bank,
@@ -117,7 +118,7 @@ fn transfer_initial_token() {
 
     let archival_slot: u64 = 1;
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
-    working_set.set_archival_version(archival_slot);
+    working_set.set_archival_version(archival_slot).unwrap();
     let (sender_balance, receiver_balance) = query_sender_receiver_balances(
         &bank,
         token_address,
@@ -214,7 +215,7 @@ fn transfer_initial_token() {
 
     let archival_slot = 3;
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
-    working_set.set_archival_version(archival_slot);
+    working_set.set_archival_version(archival_slot).unwrap();
     let mut accessory_state = working_set.accessory_state();
     let val = accessory_state.get(&StorageKey::from("k")).unwrap();
     assert_eq!("v1", String::from_utf8(val.value().to_vec()).unwrap());
@@ -231,6 +232,58 @@ fn transfer_initial_token() {
     assert_eq!("v2", String::from_utf8(val.value().to_vec()).unwrap());
 }
 
+#[test]
+fn prove_balances_at_archival_versions() {
+    let bank_config = create_bank_config_with_token(4, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let prover_storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(prover_storage.clone());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+    commit(working_set, prover_storage.clone());
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let sender_address = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[3].0;
+    let receiver_address = bank_config.tokens[0].address_and_balances[1].0;
+
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
+    transfer(
+        &bank,
+        token_address,
+        sender_address,
+        sequencer_address,
+        receiver_address,
+        10,
+        &mut working_set,
+    );
+    commit(working_set, prover_storage.clone());
+    assert_eq!(2, prover_storage.latest_version());
+
+    let key = bank.balance_storage_key(sender_address, token_address);
+    for (version, expected_balance) in [(1, 100), (2, 90)] {
+        let proof = prover_storage
+            .get_with_proof_at(key.clone(), version)
+            .unwrap();
+        let root = prover_storage.get_root_hash(version).unwrap();
+        let (_, value) =
+            ProverStorage::<DefaultStorageSpec, SnapshotManager>::open_proof(root, proof).unwrap();
+        let balance = Amount::try_from_slice(value.unwrap().value()).unwrap();
+        assert_eq!(expected_balance, balance);
+    }
+
+    // The balance isn't proven against another version
+    let proof = prover_storage.get_with_proof_at(key.clone(), 1).unwrap();
+    let root = prover_storage.get_root_hash(2).unwrap();
+    assert!(ProverStorage::<DefaultStorageSpec, SnapshotManager>::open_proof(root, proof).is_err());
+
+    // The next version isn't committed yet
+    assert!(prover_storage.get_with_proof_at(key, 3).is_err());
+}
+
 fn query_sender_receiver_balances(
     bank: &Bank<DefaultContext>,
     token_address: Address,
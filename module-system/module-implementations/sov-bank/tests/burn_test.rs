@@ -19,7 +19,10 @@ fn burn_deployed_tokens() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        fee_settlement: Default::default(),
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address("just_sender");
@@ -41,6 +44,8 @@ fn burn_deployed_tokens() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: None,
     };
     bank.call(mint_message, &minter_context, &mut working_set)
         .expect("Failed to mint token");
@@ -189,7 +194,7 @@ fn burn_initial_tokens() {
     let bank_config = create_bank_config_with_token(2, initial_balance);
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let bank = Bank::default();
+    let bank = Bank::<C>::default();
     bank.genesis(&bank_config, &mut working_set).unwrap();
 
     let token_address = get_genesis_token_address::<C>(
@@ -11,7 +11,7 @@ fn initial_and_deployed_token() {
     let bank_config = create_bank_config_with_token(1, 100);
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let bank = Bank::default();
+    let bank = Bank::<C>::default();
     bank.genesis(&bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address::<C>("sender");
@@ -28,6 +28,8 @@ fn initial_and_deployed_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: None,
     };
 
     bank.call(create_token_message, &sender_context, &mut working_set)
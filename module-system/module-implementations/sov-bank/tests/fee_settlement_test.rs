@@ -0,0 +1,133 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::events::SettleFee;
+use sov_bank::{
+    get_genesis_token_address, Bank, BankConfig, BankTxHook, FeeSettlementConfig, TokenConfig,
+};
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Address, Context, Module, ModuleEvent, PrivateKey, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+fn gas_token_config(address_and_balances: Vec<(Address, u64)>) -> TokenConfig<C> {
+    TokenConfig {
+        token_name: "sov-gas-token".to_owned(),
+        address_and_balances,
+        authorized_minters: vec![],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
+        salt: 0,
+    }
+}
+
+#[test]
+fn consumed_gas_is_shared_with_the_treasury() {
+    let sender = generate_address("sender");
+    let sequencer = generate_address("sequencer");
+    let treasury = generate_address("treasury");
+    let gas_token_address = get_genesis_token_address::<C>("sov-gas-token", 0);
+    let fee_settlement = FeeSettlementConfig {
+        treasury_share_bps: 2500,
+        treasury_address: Some(treasury),
+    };
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(
+        &BankConfig {
+            tokens: vec![gas_token_config(vec![(sender, 1000)])],
+            fee_settlement: fee_settlement.clone(),
+        },
+        &mut working_set,
+    )
+    .unwrap();
+    assert_eq!(bank.get_fee_settlement(&mut working_set), fee_settlement);
+
+    // The sender reserves 100 gas tokens and tips 10 of them
+    let gas_limit = 100;
+    let tx = Transaction::<C>::new_signed_tx(
+        &DefaultPrivateKey::generate(),
+        vec![],
+        0,
+        10,
+        gas_limit,
+        0,
+    );
+    working_set.set_gas(gas_limit, [1, 1]);
+    bank.pre_dispatch_tx_hook(&tx, &mut working_set, &BankTxHook { sender, sequencer })
+        .unwrap();
+
+    // 20 gas tokens are consumed: the sender is refunded 80 of them, and the treasury gets a quarter
+    // of the consumed ones. The sequencer keeps the rest and the tip.
+    working_set.charge_gas(&[10, 10]).unwrap();
+    bank.post_dispatch_tx_hook(&tx, &C::new(sender, sequencer, 1), &mut working_set)
+        .unwrap();
+    let balance_of = |address, working_set: &mut WorkingSet<C>| {
+        bank.get_balance_of(address, gas_token_address, working_set)
+    };
+    assert_eq!(balance_of(sender, &mut working_set), Some(970));
+    assert_eq!(balance_of(treasury, &mut working_set), Some(5));
+    assert_eq!(balance_of(sequencer, &mut working_set), Some(25));
+
+    let settlement = SettleFee::<C> {
+        token_address: gas_token_address,
+        sender,
+        sequencer,
+        refund: 80,
+        sequencer_fee: 25,
+        treasury_fee: 5,
+        treasury_address: Some(treasury),
+    };
+    assert_eq!(working_set.events().last(), Some(&settlement.to_event()));
+}
+
+#[test]
+fn treasury_share_is_burnt_without_treasury() {
+    let sender = generate_address("sender");
+    let sequencer = generate_address("sequencer");
+    let gas_token_address = get_genesis_token_address::<C>("sov-gas-token", 0);
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    let config = |treasury_share_bps| BankConfig {
+        tokens: vec![gas_token_config(vec![(sender, 1000)])],
+        fee_settlement: FeeSettlementConfig {
+            treasury_share_bps,
+            treasury_address: None,
+        },
+    };
+    bank.genesis(&config(10_001), &mut working_set)
+        .expect_err("The treasury share can't exceed 100%");
+    bank.genesis(&config(10_000), &mut working_set).unwrap();
+
+    // All the consumed gas is burnt, the sequencer only keeps the tip
+    let gas_limit = 100;
+    let tx = Transaction::<C>::new_signed_tx(
+        &DefaultPrivateKey::generate(),
+        vec![],
+        0,
+        10,
+        gas_limit,
+        0,
+    );
+    working_set.set_gas(gas_limit, [1, 1]);
+    bank.pre_dispatch_tx_hook(&tx, &mut working_set, &BankTxHook { sender, sequencer })
+        .unwrap();
+    working_set.charge_gas(&[10, 10]).unwrap();
+    bank.post_dispatch_tx_hook(&tx, &C::new(sender, sequencer, 1), &mut working_set)
+        .unwrap();
+
+    assert_eq!(
+        bank.get_balance_of(sequencer, gas_token_address, &mut working_set),
+        Some(10)
+    );
+    assert_eq!(
+        bank.get_total_supply_of(&gas_token_address, &mut working_set),
+        Some(980)
+    );
+}
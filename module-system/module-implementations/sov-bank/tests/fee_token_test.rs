@@ -0,0 +1,120 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{
+    get_genesis_token_address, Bank, BankConfig, BankTxHook, CallMessage, ConversionRate, FeeToken,
+    TokenConfig,
+};
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, Module, PrivateKey, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn fees_are_paid_in_accepted_fee_tokens() {
+    let admin = generate_address("admin");
+    let sender = generate_address("sender");
+    let sequencer = generate_address("sequencer");
+    let token_config = |token_name: &str, address_and_balances| TokenConfig {
+        token_name: token_name.to_owned(),
+        address_and_balances,
+        authorized_minters: vec![],
+        admins: vec![admin],
+        max_supply: None,
+        metadata: None,
+        salt: 0,
+    };
+    let bank_config = BankConfig::<C> {
+        tokens: vec![
+            token_config("sov-gas-token", vec![(sequencer, 1000)]),
+            token_config("FeeToken", vec![(sender, 1000)]),
+        ],
+        fee_settlement: Default::default(),
+    };
+    let gas_token_address = get_genesis_token_address::<C>("sov-gas-token", 0);
+    let fee_token_address = get_genesis_token_address::<C>("FeeToken", 0);
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let set_fee_token = |rate| CallMessage::SetFeeToken {
+        token_address: fee_token_address,
+        rate,
+    };
+    let rate = ConversionRate {
+        numerator: 3,
+        denominator: 2,
+    };
+
+    // Only the gas token admins can set the fee tokens
+    let sender_context = C::new(sender, sequencer, 1);
+    bank.call(set_fee_token(Some(rate)), &sender_context, &mut working_set)
+        .expect_err("The sender isn't an admin of the gas token");
+    let admin_context = C::new(admin, sequencer, 1);
+    bank.call(
+        set_fee_token(Some(ConversionRate {
+            numerator: 0,
+            denominator: 1,
+        })),
+        &admin_context,
+        &mut working_set,
+    )
+    .expect_err("The rate must be positive");
+    bank.call(set_fee_token(Some(rate)), &admin_context, &mut working_set)
+        .expect("Setting the fee token failed");
+    assert_eq!(
+        bank.fee_token_rates(&mut working_set).unwrap(),
+        vec![FeeToken {
+            address: fee_token_address,
+            rate
+        }]
+    );
+
+    // The sender has no gas tokens, so 100 gas tokens are reserved as 150 fee tokens
+    let gas_limit = 100;
+    let tx =
+        Transaction::<C>::new_signed_tx(&DefaultPrivateKey::generate(), vec![], 0, 0, gas_limit, 0);
+    let hook = BankTxHook { sender, sequencer };
+    working_set.set_gas(gas_limit, [1, 1]);
+    bank.pre_dispatch_tx_hook(&tx, &mut working_set, &hook)
+        .expect("The fee should be paid in fee tokens");
+    let balance_of = |address, token_address, working_set: &mut WorkingSet<C>| {
+        bank.get_balance_of(address, token_address, working_set)
+    };
+    assert_eq!(
+        balance_of(sender, fee_token_address, &mut working_set),
+        Some(850)
+    );
+    assert_eq!(
+        balance_of(sequencer, fee_token_address, &mut working_set),
+        Some(150)
+    );
+
+    // The 80 unused gas tokens are refunded as 120 fee tokens
+    working_set.charge_gas(&[10, 10]).unwrap();
+    bank.post_dispatch_tx_hook(&tx, &sender_context, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        balance_of(sender, fee_token_address, &mut working_set),
+        Some(970)
+    );
+    assert_eq!(
+        balance_of(sequencer, fee_token_address, &mut working_set),
+        Some(30)
+    );
+    assert_eq!(
+        balance_of(sequencer, gas_token_address, &mut working_set),
+        Some(1000)
+    );
+
+    // Once the fee token isn't accepted anymore, the sender can't pay the fees
+    bank.call(set_fee_token(None), &admin_context, &mut working_set)
+        .expect("Removing the fee token failed");
+    assert!(bank.fee_token_rates(&mut working_set).unwrap().is_empty());
+    working_set.set_gas(gas_limit, [1, 1]);
+    bank.pre_dispatch_tx_hook(&tx, &mut working_set, &hook)
+        .expect_err("The sender has no gas tokens");
+}
@@ -15,7 +15,10 @@ fn freeze_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        fee_settlement: Default::default(),
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address::<DefaultContext>("minter");
@@ -35,6 +38,8 @@ fn freeze_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -84,6 +89,8 @@ fn freeze_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -91,7 +98,7 @@ fn freeze_token() {
     // No events at the moment. If there are, needs to be checked
     assert!(working_set.events().is_empty());
 
-    // Try to freeze with a non authorized minter
+    // Try to freeze with a non authorized freezer
     let unauthorized_address = generate_address::<C>("unauthorized_address");
     let sequencer_address = generate_address::<C>("sequencer");
     let unauthorized_context = C::new(unauthorized_address, sequencer_address, 1);
@@ -115,7 +122,7 @@ fn freeze_token() {
     );
     assert_eq!(
         format!(
-            "Sender {} is not an authorized minter of token {}",
+            "Sender {} is not an authorized freezer of token {}",
             unauthorized_address, token_name_2
         ),
         message_2
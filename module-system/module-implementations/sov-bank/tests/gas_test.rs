@@ -136,8 +136,12 @@ impl BankGasTestCase {
                 token_name: base_token_name.to_string(),
                 address_and_balances,
                 authorized_minters,
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
                 salt,
             }],
+            fee_settlement: Default::default(),
         };
 
         // create a context using the generated account as sender
@@ -147,7 +151,7 @@ impl BankGasTestCase {
         let ctx = C::new(sender_address, sequencer_address, height);
 
         // create a bank instance
-        let bank = Bank::default();
+        let bank = Bank::<C>::default();
         let storage = new_orphan_storage(tmpdir.path()).unwrap();
         let mut ws = WorkingSet::new(storage);
         bank.genesis(&bank_config, &mut ws).unwrap();
@@ -165,6 +169,8 @@ impl BankGasTestCase {
             initial_balance,
             minter_address,
             authorized_minters: vec![minter_address],
+            max_supply: None,
+            metadata: None,
         };
 
         Self {
@@ -186,6 +192,11 @@ impl BankGasTestCase {
             burn: Default::default(),
             mint: Default::default(),
             freeze: Default::default(),
+            grant_role: Default::default(),
+            revoke_role: Default::default(),
+            approve: Default::default(),
+            transfer_from: Default::default(),
+            set_fee_token: Default::default(),
         });
         self
     }
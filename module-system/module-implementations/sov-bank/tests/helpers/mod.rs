@@ -29,10 +29,14 @@ pub fn create_bank_config_with_token(
         token_name: "InitialToken".to_owned(),
         address_and_balances,
         authorized_minters: vec![],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
         salt: 5,
     };
 
     BankConfig {
         tokens: vec![token_config],
+        fee_settlement: Default::default(),
     }
 }
@@ -0,0 +1,157 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{
+    get_genesis_token_address, get_token_address, Bank, CallMessage, ListTokensResponse,
+    TokenInfoResponse, TokenMetadata,
+};
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn create_token_with_metadata() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let minter_address = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[1].0;
+    let minter_context = C::new(minter_address, sequencer_address, 1);
+    let create_message = |token_name: &str, metadata| CallMessage::CreateToken {
+        salt: 0,
+        token_name: token_name.to_owned(),
+        initial_balance: 100,
+        minter_address,
+        authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: Some(metadata),
+    };
+
+    let invalid_metadata = [
+        TokenMetadata {
+            symbol: "".to_owned(),
+            decimals: 6,
+            uri: None,
+        },
+        TokenMetadata {
+            symbol: "SOV-1".to_owned(),
+            decimals: 6,
+            uri: None,
+        },
+        TokenMetadata {
+            symbol: "SOV".to_owned(),
+            decimals: 19,
+            uri: None,
+        },
+        TokenMetadata {
+            symbol: "SOV".to_owned(),
+            decimals: 6,
+            uri: Some("".to_owned()),
+        },
+    ];
+    for metadata in invalid_metadata {
+        assert!(bank
+            .call(
+                create_message("InvalidToken", metadata),
+                &minter_context,
+                &mut working_set
+            )
+            .is_err());
+    }
+
+    let metadata = TokenMetadata {
+        symbol: "SOV".to_owned(),
+        decimals: 6,
+        uri: Some("https://example.com/sov.json".to_owned()),
+    };
+    bank.call(
+        create_message("Token1", metadata.clone()),
+        &minter_context,
+        &mut working_set,
+    )
+    .expect("Failed to create token");
+
+    let token_address = get_token_address::<C>("Token1", minter_address.as_ref(), 0);
+    assert_eq!(
+        Some(metadata.clone()),
+        bank.get_token_metadata(&token_address, &mut working_set)
+    );
+
+    let TokenInfoResponse { token } = bank
+        .token_info(None, token_address, &mut working_set)
+        .unwrap();
+    let token = token.expect("The token should exist");
+    assert_eq!(token_address, token.address);
+    assert_eq!("Token1", token.name);
+    assert_eq!(100, token.total_supply);
+    assert!(!token.frozen);
+    assert_eq!(Some(metadata), token.metadata);
+
+    let TokenInfoResponse { token } = bank
+        .token_info(None, generate_address("unknown_token"), &mut working_set)
+        .unwrap();
+    assert_eq!(None, token);
+}
+
+#[test]
+fn list_tokens() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let genesis_token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let minter_address = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[1].0;
+    let minter_context = C::new(minter_address, sequencer_address, 1);
+    for salt in 0..3 {
+        bank.call(
+            CallMessage::CreateToken {
+                salt,
+                token_name: "Token".to_owned(),
+                initial_balance: 100,
+                minter_address,
+                authorized_minters: vec![minter_address],
+                max_supply: None,
+                metadata: None,
+            },
+            &minter_context,
+            &mut working_set,
+        )
+        .expect("Failed to create token");
+    }
+
+    // The genesis token comes first
+    let ListTokensResponse { tokens, total } =
+        bank.list_tokens(None, 0, 2, &mut working_set).unwrap();
+    assert_eq!(4, total);
+    assert_eq!(2, tokens.len());
+    assert_eq!(genesis_token_address, tokens[0].address);
+    assert_eq!(
+        get_token_address::<C>("Token", minter_address.as_ref(), 0),
+        tokens[1].address
+    );
+
+    let ListTokensResponse { tokens, .. } =
+        bank.list_tokens(None, 2, 10, &mut working_set).unwrap();
+    assert_eq!(
+        vec![
+            get_token_address::<C>("Token", minter_address.as_ref(), 1),
+            get_token_address::<C>("Token", minter_address.as_ref(), 2),
+        ],
+        tokens
+            .into_iter()
+            .map(|token| token.address)
+            .collect::<Vec<_>>()
+    );
+
+    let ListTokensResponse { tokens, .. } =
+        bank.list_tokens(None, 10, 10, &mut working_set).unwrap();
+    assert!(tokens.is_empty());
+}
@@ -15,7 +15,10 @@ fn mint_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        fee_settlement: Default::default(),
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address::<C>("minter");
@@ -35,6 +38,8 @@ fn mint_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        max_supply: None,
+        metadata: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -131,6 +136,8 @@ fn mint_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![authorized_minter_address_1, authorized_minter_address_2],
+        max_supply: None,
+        metadata: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
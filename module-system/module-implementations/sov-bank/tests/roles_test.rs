@@ -0,0 +1,235 @@
+use helpers::C;
+use sov_bank::{get_token_address, Bank, BankConfig, CallMessage, Coins, TokenRole};
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Error, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+mod helpers;
+
+#[test]
+fn mint_up_to_max_supply() {
+    let bank = Bank::<C>::default();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    bank.genesis(
+        &BankConfig::<C> {
+            tokens: vec![],
+            fee_settlement: Default::default(),
+        },
+        &mut working_set,
+    )
+    .unwrap();
+
+    let minter_address = generate_address::<C>("minter");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let minter_context = C::new(minter_address, sequencer_address, 1);
+    let token_name = "CappedToken".to_owned();
+    let token_address = get_token_address::<C>(&token_name, minter_address.as_ref(), 0);
+
+    // The initial balance can't exceed the max supply
+    let create_message = |initial_balance| CallMessage::CreateToken {
+        salt: 0,
+        token_name: token_name.clone(),
+        initial_balance,
+        minter_address,
+        authorized_minters: vec![minter_address],
+        max_supply: Some(150),
+        metadata: None,
+    };
+    assert!(bank
+        .call(create_message(200), &minter_context, &mut working_set)
+        .is_err());
+
+    bank.call(create_message(100), &minter_context, &mut working_set)
+        .expect("Failed to create token");
+    assert_eq!(
+        Some(150),
+        bank.get_max_supply_of(&token_address, &mut working_set)
+    );
+
+    let mint_message = |amount| CallMessage::Mint {
+        coins: Coins {
+            amount,
+            token_address,
+        },
+        minter_address,
+    };
+    bank.call(mint_message(50), &minter_context, &mut working_set)
+        .expect("Minting up to the max supply should succeed");
+
+    let mint = bank.call(mint_message(1), &minter_context, &mut working_set);
+    let Error::ModuleError(err) = mint.unwrap_err();
+    let mut chain = err.chain();
+    let _ = chain.next().unwrap();
+    assert_eq!(
+        format!(
+            "Minting 1 would exceed the max supply 150 of token {}",
+            token_name
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert_eq!(
+        Some(150),
+        bank.get_total_supply_of(&token_address, &mut working_set)
+    );
+}
+
+#[test]
+fn grant_and_revoke_roles() {
+    let bank = Bank::<C>::default();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    bank.genesis(
+        &BankConfig::<C> {
+            tokens: vec![],
+            fee_settlement: Default::default(),
+        },
+        &mut working_set,
+    )
+    .unwrap();
+
+    let admin_address = generate_address::<C>("admin");
+    let new_minter_address = generate_address::<C>("new_minter");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let admin_context = C::new(admin_address, sequencer_address, 1);
+    let new_minter_context = C::new(new_minter_address, sequencer_address, 1);
+    let token_name = "Token1".to_owned();
+    let token_address = get_token_address::<C>(&token_name, admin_address.as_ref(), 0);
+
+    let create_message = CallMessage::CreateToken {
+        salt: 0,
+        token_name: token_name.clone(),
+        initial_balance: 100,
+        minter_address: admin_address,
+        authorized_minters: vec![admin_address],
+        max_supply: None,
+        metadata: None,
+    };
+    bank.call(create_message, &admin_context, &mut working_set)
+        .expect("Failed to create token");
+
+    // The creator is the admin, and the minters can freeze the token
+    assert!(bank.has_role(
+        &token_address,
+        TokenRole::Admin,
+        &admin_address,
+        &mut working_set
+    ));
+    assert!(bank.has_role(
+        &token_address,
+        TokenRole::Freezer,
+        &admin_address,
+        &mut working_set
+    ));
+
+    let grant_message = |role| CallMessage::GrantRole {
+        token_address,
+        role,
+        account: new_minter_address,
+    };
+    let revoke_message = |role, account| CallMessage::RevokeRole {
+        token_address,
+        role,
+        account,
+    };
+    let mint_message = CallMessage::Mint {
+        coins: Coins {
+            amount: 10,
+            token_address,
+        },
+        minter_address: new_minter_address,
+    };
+
+    // Only admins can grant roles
+    let grant = bank.call(
+        grant_message(TokenRole::Minter),
+        &new_minter_context,
+        &mut working_set,
+    );
+    let Error::ModuleError(err) = grant.unwrap_err();
+    let mut chain = err.chain();
+    let _ = chain.next().unwrap();
+    assert_eq!(
+        format!(
+            "Sender {} is not an authorized admin of token {}",
+            new_minter_address, token_name
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert!(working_set.events().is_empty());
+
+    bank.call(
+        grant_message(TokenRole::Minter),
+        &admin_context,
+        &mut working_set,
+    )
+    .expect("Failed to grant the minter role");
+    assert_eq!(1, working_set.events().len());
+    assert!(bank
+        .call(
+            grant_message(TokenRole::Minter),
+            &admin_context,
+            &mut working_set
+        )
+        .is_err());
+    bank.call(mint_message.clone(), &new_minter_context, &mut working_set)
+        .expect("The new minter should be able to mint");
+
+    // The new minter isn't a freezer
+    let freeze_message = CallMessage::Freeze { token_address };
+    assert!(bank
+        .call(
+            freeze_message.clone(),
+            &new_minter_context,
+            &mut working_set
+        )
+        .is_err());
+
+    bank.call(
+        revoke_message(TokenRole::Minter, new_minter_address),
+        &admin_context,
+        &mut working_set,
+    )
+    .expect("Failed to revoke the minter role");
+    assert!(!bank.has_role(
+        &token_address,
+        TokenRole::Minter,
+        &new_minter_address,
+        &mut working_set
+    ));
+    assert!(bank
+        .call(mint_message, &new_minter_context, &mut working_set)
+        .is_err());
+
+    // The minter role can't be granted once the token is frozen
+    bank.call(freeze_message, &admin_context, &mut working_set)
+        .expect("Failed to freeze token");
+    assert!(bank
+        .call(
+            grant_message(TokenRole::Minter),
+            &admin_context,
+            &mut working_set
+        )
+        .is_err());
+    bank.call(
+        grant_message(TokenRole::Admin),
+        &admin_context,
+        &mut working_set,
+    )
+    .expect("Failed to grant the admin role");
+
+    // Admins can renounce their role
+    bank.call(
+        revoke_message(TokenRole::Admin, admin_address),
+        &admin_context,
+        &mut working_set,
+    )
+    .expect("Failed to revoke the admin role");
+    assert!(bank
+        .call(
+            revoke_message(TokenRole::Admin, new_minter_address),
+            &admin_context,
+            &mut working_set
+        )
+        .is_err());
+}
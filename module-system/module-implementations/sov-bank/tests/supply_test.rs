@@ -0,0 +1,76 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank, CallMessage, Coins};
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn supply_is_recomputed_from_the_balances_of_all_holders() {
+    let bank_config = create_bank_config_with_token(3, 100);
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let holder = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer = generate_address("sequencer");
+    let receiver = generate_address("receiver");
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let audit = bank
+        .verify_supply(&token_address, &mut working_set)
+        .unwrap();
+    assert_eq!(audit.total_supply, 300);
+    assert_eq!(audit.sum_of_balances, 300);
+    assert_eq!(audit.holder_count, 3);
+
+    // A transfer to a new address adds a holder, and burning keeps the supply consistent
+    let context = C::new(holder, sequencer, 1);
+    bank.call(
+        CallMessage::Transfer {
+            to: receiver,
+            coins: Coins {
+                amount: 40,
+                token_address,
+            },
+        },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+    bank.call(
+        CallMessage::Burn {
+            coins: Coins {
+                amount: 10,
+                token_address,
+            },
+        },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+
+    let audit = bank
+        .verify_supply(&token_address, &mut working_set)
+        .unwrap();
+    assert!(audit.is_consistent());
+    assert_eq!(audit.total_supply, 290);
+    assert_eq!(audit.holder_count, 4);
+
+    let response = bank
+        .verify_supply_of(None, token_address, &mut working_set)
+        .unwrap();
+    assert!(response.consistent);
+    assert_eq!(response.audit, Some(audit));
+
+    // Unknown tokens can't be audited
+    let response = bank
+        .verify_supply_of(None, generate_address("unknown"), &mut working_set)
+        .unwrap();
+    assert!(!response.consistent);
+    assert_eq!(response.audit, None);
+}
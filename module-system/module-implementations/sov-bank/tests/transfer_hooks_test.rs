@@ -0,0 +1,120 @@
+mod helpers;
+
+use anyhow::bail;
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank, CallMessage, Coins, TransferHooks};
+use sov_modules_api::{Address, Context, Event, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+/// Rejects the transfers to a blacklisted address, and reports every mint and burn as an event.
+struct BlacklistHooks;
+
+impl TransferHooks<C> for BlacklistHooks {
+    fn on_transfer(
+        _from: &Address,
+        to: &Address,
+        _coins: &Coins<C>,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        if to == &generate_address("blacklisted") {
+            bail!("{} is blacklisted", to);
+        }
+        Ok(())
+    }
+
+    fn on_mint(
+        to: &Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        working_set.add_event("mint", &format!("to: {}, coins: {}", to, coins));
+        Ok(())
+    }
+
+    fn on_burn(
+        from: &Address,
+        coins: &Coins<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        working_set.add_event("burn", &format!("from: {}, coins: {}", from, coins));
+        Ok(())
+    }
+}
+
+#[test]
+fn transfer_hooks_are_invoked() {
+    let initial_balance = 100;
+    let bank_config = create_bank_config_with_token(2, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C, BlacklistHooks>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+    // The genesis balances don't invoke the hooks
+    assert!(working_set.events().is_empty());
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let sender_address = bank_config.tokens[0].address_and_balances[0].0;
+    let receiver_address = bank_config.tokens[0].address_and_balances[1].0;
+    let sender_context = C::new(sender_address, receiver_address, 1);
+    let coins = Coins {
+        amount: 10,
+        token_address,
+    };
+
+    bank.call(
+        CallMessage::Transfer {
+            to: receiver_address,
+            coins: coins.clone(),
+        },
+        &sender_context,
+        &mut working_set,
+    )
+    .expect("Transfer call failed");
+
+    let blacklisted_transfer = bank.call(
+        CallMessage::Transfer {
+            to: generate_address("blacklisted"),
+            coins: coins.clone(),
+        },
+        &sender_context,
+        &mut working_set,
+    );
+    assert!(blacklisted_transfer.is_err());
+
+    bank.call(
+        CallMessage::Burn {
+            coins: coins.clone(),
+        },
+        &sender_context,
+        &mut working_set,
+    )
+    .expect("Burn call failed");
+    assert_eq!(1, working_set.events().len());
+    assert_eq!(
+        &working_set.events()[0],
+        &Event::new(
+            "burn",
+            &format!("from: {}, coins: {}", sender_address, coins)
+        )
+    );
+
+    bank.call(
+        CallMessage::CreateToken {
+            salt: 0,
+            token_name: "NewToken".to_owned(),
+            initial_balance,
+            minter_address: sender_address,
+            authorized_minters: vec![sender_address],
+            max_supply: None,
+            metadata: None,
+        },
+        &sender_context,
+        &mut working_set,
+    )
+    .expect("Create token call failed");
+    assert_eq!(2, working_set.events().len());
+    assert_eq!(b"mint", working_set.events()[1].key().inner().as_slice());
+}
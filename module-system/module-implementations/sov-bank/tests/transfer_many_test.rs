@@ -0,0 +1,97 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank, CallMessage, Coins, TransferOutput};
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn transfer_many_is_atomic() {
+    let initial_balance = 100;
+    let bank_config = create_bank_config_with_token(4, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let sender_address = bank_config.tokens[0].address_and_balances[0].0;
+    let receiver_addresses = [
+        bank_config.tokens[0].address_and_balances[1].0,
+        bank_config.tokens[0].address_and_balances[2].0,
+    ];
+    let sequencer_address = bank_config.tokens[0].address_and_balances[3].0;
+    let sender_context = C::new(sender_address, sequencer_address, 1);
+
+    let output = |to, amount| TransferOutput {
+        to,
+        coins: Coins {
+            amount,
+            token_address,
+        },
+    };
+    let query_balance = |address, working_set: &mut WorkingSet<C>| -> Option<u64> {
+        bank.get_balance_of(address, token_address, working_set)
+    };
+
+    // An empty batch is rejected
+    assert!(bank
+        .call(
+            CallMessage::TransferMany { outputs: vec![] },
+            &sender_context,
+            &mut working_set
+        )
+        .is_err());
+
+    bank.call(
+        CallMessage::TransferMany {
+            outputs: vec![
+                output(receiver_addresses[0], 10),
+                output(receiver_addresses[1], 20),
+                output(receiver_addresses[0], 5),
+            ],
+        },
+        &sender_context,
+        &mut working_set,
+    )
+    .expect("TransferMany call failed");
+    assert_eq!(
+        Some(initial_balance - 35),
+        query_balance(sender_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 15),
+        query_balance(receiver_addresses[0], &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 20),
+        query_balance(receiver_addresses[1], &mut working_set)
+    );
+    assert!(working_set.events().is_empty());
+
+    // The last transfer exceeds the balance of the sender, so the whole batch fails
+    let mut working_set = working_set.checkpoint().to_revertable();
+    let transfer_many = bank.call(
+        CallMessage::TransferMany {
+            outputs: vec![
+                output(receiver_addresses[0], 10),
+                output(receiver_addresses[1], initial_balance),
+            ],
+        },
+        &sender_context,
+        &mut working_set,
+    );
+    assert!(transfer_many.is_err());
+    let mut working_set = working_set.revert().to_revertable();
+    assert_eq!(
+        Some(initial_balance - 35),
+        query_balance(sender_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 15),
+        query_balance(receiver_addresses[0], &mut working_set)
+    );
+}
@@ -21,7 +21,7 @@ fn transfer_initial_token() {
     let token_name = bank_config.tokens[0].token_name.clone();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let bank = Bank::default();
+    let bank = Bank::<C>::default();
     bank.genesis(&bank_config, &mut working_set).unwrap();
 
     let token_address = get_genesis_token_address::<C>(
@@ -262,7 +262,10 @@ fn transfer_deployed_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        fee_settlement: Default::default(),
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address::<C>("just_sender");
@@ -303,6 +306,8 @@ fn transfer_deployed_token() {
         initial_balance,
         minter_address: sender_address,
         authorized_minters: vec![sender_address],
+        max_supply: None,
+        metadata: None,
     };
     bank.call(mint_message, &sender_context, &mut working_set)
         .expect("Failed to mint token");
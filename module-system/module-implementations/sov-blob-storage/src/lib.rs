@@ -95,7 +95,8 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> BlobStorage<C, Da
         self.chain_state.true_slot_height(working_set.inner)
     }
 
-    pub(crate) fn get_deferred_slots_count(&self, _working_set: &mut WorkingSet<C>) -> u64 {
+    /// Returns the number of slots for which a blob may be deferred before it must be executed.
+    pub fn get_deferred_slots_count(&self, _working_set: &mut WorkingSet<C>) -> u64 {
         DEFERRED_SLOTS_COUNT
     }
 }
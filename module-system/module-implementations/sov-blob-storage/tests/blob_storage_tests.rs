@@ -19,6 +19,15 @@ fn empty_test() {
     let chain_state_config = ChainStateConfig {
         initial_slot_height,
         current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
     };
     chain_state
         .genesis(&chain_state_config, &mut working_set)
@@ -42,6 +51,15 @@ fn store_and_retrieve_standard() {
     let chain_state_config = ChainStateConfig {
         initial_slot_height,
         current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
     };
     chain_state
         .genesis(&chain_state_config, &mut working_set)
@@ -0,0 +1,98 @@
+use anyhow::{ensure, Result};
+use sov_bank::Coins;
+use sov_modules_api::{CallResponse, Context, StateValueAccessor, StateVecAccessor, WorkingSet};
+use sov_outbox::merkle;
+
+use crate::events::WithdrawalInitiated;
+use crate::{Bridge, Withdrawal, MAX_RECIPIENT_SIZE};
+
+/// This enumeration represents the available call messages for interacting with the sov-bridge module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CliWalletArg),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Burns tokens of the sender, to be released to `recipient` on the DA layer.
+    Withdraw {
+        /// The address of the token. It must be a bridgeable token.
+        token_address: C::Address,
+        /// The amount of tokens to withdraw.
+        amount: u64,
+        /// The serialized address of the recipient on the DA layer.
+        recipient: Vec<u8>,
+    },
+}
+
+impl<C: Context> Bridge<C> {
+    /// Burns `amount` of the token `token_address` from the sender, and appends the withdrawal to the
+    /// withdrawals tree.
+    pub(crate) fn withdraw(
+        &self,
+        token_address: C::Address,
+        amount: u64,
+        recipient: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let tokens = self.tokens.get(working_set).unwrap_or_default();
+        ensure!(
+            tokens.contains(&token_address),
+            "The token {} can't be withdrawn to the DA layer",
+            token_address
+        );
+        ensure!(amount > 0, "The amount to withdraw must be positive");
+        ensure!(
+            !recipient.is_empty() && recipient.len() <= MAX_RECIPIENT_SIZE,
+            "The recipient must be between 1 and {} bytes",
+            MAX_RECIPIENT_SIZE
+        );
+
+        let id = self.withdrawals.len(working_set) as u64;
+        ensure!(
+            id >> merkle::OUTBOX_TREE_DEPTH == 0,
+            "The withdrawals tree is full"
+        );
+
+        self.bank.burn(
+            Coins {
+                amount,
+                token_address: token_address.clone(),
+            },
+            context.sender(),
+            working_set,
+        )?;
+
+        let withdrawal = Withdrawal::<C> {
+            id,
+            token_address,
+            amount,
+            sender: context.sender().clone(),
+            recipient,
+            height: context.slot_height(),
+        };
+        let withdrawal_hash = withdrawal.hash();
+
+        let mut frontier = self.frontier.get(working_set).unwrap_or_default();
+        merkle::append::<C>(&mut frontier, id, withdrawal_hash);
+        self.root
+            .set(&merkle::root::<C>(&frontier, id + 1), working_set);
+        self.frontier.set(&frontier, working_set);
+        self.withdrawals.push(&withdrawal, working_set);
+
+        working_set.emit_event(&WithdrawalInitiated::<C> {
+            id,
+            token_address: withdrawal.token_address,
+            amount,
+            withdrawal_hash,
+        });
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,18 @@
+//! The typed events emitted by the bridge module.
+use sov_modules_api::ModuleEvent;
+
+/// Emitted when tokens are burned to be withdrawn to the DA layer.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "withdrawal_initiated")]
+pub struct WithdrawalInitiated<C: sov_modules_api::Context> {
+    /// The id of the withdrawal, which is its index in the withdrawals tree.
+    #[indexed]
+    pub id: u64,
+    /// The address of the burned token.
+    #[indexed]
+    pub token_address: C::Address,
+    /// The amount of burned tokens.
+    pub amount: u64,
+    /// The leaf of the withdrawal in the withdrawals tree.
+    pub withdrawal_hash: [u8; 32],
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+use sov_outbox::merkle;
+
+use crate::Bridge;
+
+/// Initial configuration for sov-bridge module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct BridgeConfig<C: sov_modules_api::Context> {
+    /// The tokens which can be withdrawn to the DA layer.
+    pub tokens: Vec<C::Address>,
+}
+
+impl<C: sov_modules_api::Context> Bridge<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.tokens.set(&config.tokens, working_set);
+        self.root.set(&merkle::root::<C>(&[], 0), working_set);
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+mod proofs;
+#[cfg(feature = "native")]
+mod query;
+
+pub use call::*;
+pub use genesis::*;
+pub use proofs::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{
+    CallResponse, Context, Error, ModuleInfo, StateValue, StateValueAccessor, StateVec, WorkingSet,
+};
+use sov_outbox::merkle;
+
+/// The maximum size of the recipient of a withdrawal on the DA layer, in bytes.
+pub const MAX_RECIPIENT_SIZE: usize = 64;
+
+const LEAF_PREFIX: u8 = 0;
+
+/// A withdrawal of rollup tokens to the DA layer. The tokens are burned on the rollup, and released on the
+/// DA layer by a contract verifying the inclusion of the withdrawal in the state root of the rollup.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct Withdrawal<C: Context> {
+    /// The index of the withdrawal in the withdrawals tree. The DA-layer contract releases each withdrawal once.
+    pub id: u64,
+    /// The address of the burned token.
+    pub token_address: C::Address,
+    /// The amount of burned tokens.
+    pub amount: u64,
+    /// The rollup account which burned the tokens.
+    pub sender: C::Address,
+    /// The serialized address of the recipient on the DA layer.
+    pub recipient: Vec<u8>,
+    /// The slot height at which the tokens were burned.
+    pub height: u64,
+}
+
+impl<C: Context> Withdrawal<C> {
+    /// Returns the leaf of the withdrawal in the withdrawals tree.
+    pub fn hash(&self) -> [u8; 32] {
+        C::Hasher::new()
+            .chain_update([LEAF_PREFIX])
+            .chain_update(
+                borsh::BorshSerialize::try_to_vec(self)
+                    .expect("Serializing a withdrawal to a vector can't fail"),
+            )
+            .finalize()
+            .into()
+    }
+}
+
+/// The `sov-bridge` module moves tokens from the rollup to the DA layer (or to the L1 it settles on).
+/// It provides functionality for:
+/// - Withdrawing bridgeable tokens: the tokens are burned, and the withdrawal is appended to an append-only
+///   merkle tree, built like the tree of the [`sov-outbox`](sov_outbox) module.
+/// - Proving a withdrawal to the DA-layer contract which releases the funds: the root of the withdrawals tree
+///   is proven against the state root with [`Bridge::get_withdrawals_root_with_proof`], and the withdrawal is
+///   proven against this root with a merkle proof. See [`Bridge::verify_withdrawal`].
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Bridge<C: sov_modules_api::Context> {
+    /// The address of the sov-bridge module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The tokens which can be withdrawn.
+    #[state]
+    pub(crate) tokens: StateValue<Vec<C::Address>>,
+
+    /// The withdrawals, indexed by their id.
+    #[state]
+    pub(crate) withdrawals: StateVec<Withdrawal<C>>,
+
+    /// The last left node of each level of the withdrawals tree.
+    #[state]
+    pub(crate) frontier: StateValue<Vec<[u8; 32]>>,
+
+    /// The root of the withdrawals tree.
+    #[state]
+    pub(crate) root: StateValue<[u8; 32]>,
+
+    /// Reference to the bank module, which burns the withdrawn tokens.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Bridge<C> {
+    type Context = C;
+
+    type Config = BridgeConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Withdraw {
+                token_address,
+                amount,
+                recipient,
+            } => Ok(self.withdraw(token_address, amount, recipient, context, working_set)?),
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> Bridge<C> {
+    /// Returns the root of the withdrawals tree, committing to all the withdrawals.
+    pub fn withdrawals_root(&self, working_set: &mut WorkingSet<C>) -> [u8; 32] {
+        self.root
+            .get(working_set)
+            .unwrap_or_else(|| merkle::root::<C>(&[], 0))
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{anyhow, bail, ensure};
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::WorkingSet;
+use sov_outbox::merkle::verify_inclusion;
+use sov_state::codec::BorshCodec;
+#[cfg(feature = "native")]
+use sov_state::storage::NativeStorage;
+use sov_state::storage::{StateValueCodec, StorageKey, StorageProof};
+use sov_state::Storage;
+
+use crate::{Bridge, Withdrawal};
+
+impl<C: sov_modules_api::Context> Bridge<C> {
+    /// Returns the storage key under which the root of the withdrawals tree is stored.
+    pub fn withdrawals_root_storage_key(&self) -> StorageKey {
+        StorageKey::singleton(self.root.prefix())
+    }
+
+    /// Returns the root of the withdrawals tree, along with a proof of it against the current state root.
+    /// Together with the merkle proof of a withdrawal, this proves the withdrawal to the DA layer.
+    #[cfg(feature = "native")]
+    pub fn get_withdrawals_root_with_proof(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> StorageProof<<C::Storage as Storage>::Proof>
+    where
+        C::Storage: NativeStorage,
+    {
+        working_set.get_with_proof(self.withdrawals_root_storage_key())
+    }
+
+    /// Checks that `withdrawal` was committed by the rollup with the state root `state_root`: `root_proof`, produced by
+    /// [`Bridge::get_withdrawals_root_with_proof`], proves the root of the withdrawals tree against `state_root`, and
+    /// `merkle_proof` proves the inclusion of the withdrawal in this root.
+    ///
+    /// This doesn't require access to the state. It is the check a DA-layer contract performs before releasing funds.
+    pub fn verify_withdrawal(
+        &self,
+        state_root: <C::Storage as Storage>::Root,
+        root_proof: StorageProof<<C::Storage as Storage>::Proof>,
+        withdrawal: &Withdrawal<C>,
+        merkle_proof: &[[u8; 32]],
+    ) -> anyhow::Result<()> {
+        let (storage_key, storage_value) = C::Storage::open_proof(state_root, root_proof)?;
+        ensure!(
+            storage_key == self.withdrawals_root_storage_key(),
+            "The storage key from the proof doesn't match the withdrawals root"
+        );
+
+        let Some(storage_value) = storage_value else {
+            bail!("The state doesn't hold a withdrawals root");
+        };
+        let withdrawals_root: [u8; 32] = BorshCodec
+            .try_decode_value(storage_value.value())
+            .map_err(|e| anyhow!("Failed to decode the withdrawals root: {e:?}"))?;
+        ensure!(
+            verify_inclusion::<C>(
+                &withdrawals_root,
+                withdrawal.hash(),
+                withdrawal.id,
+                merkle_proof
+            ),
+            "Invalid proof of inclusion of the withdrawal {}",
+            withdrawal.id
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Defines rpc queries exposed by the bridge module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateVecAccessor, WorkingSet};
+use sov_outbox::merkle;
+
+use crate::{Bridge, Withdrawal};
+
+/// Structure returned by the `getWithdrawalsRoot` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct WithdrawalsRootResponse {
+    /// The root of the withdrawals tree.
+    pub root: [u8; 32],
+    /// The number of withdrawals.
+    pub withdrawal_count: u64,
+}
+
+/// Structure returned by the `getWithdrawalProof` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct WithdrawalProofResponse<C: sov_modules_api::Context> {
+    /// The withdrawal.
+    pub withdrawal: Withdrawal<C>,
+    /// The current root of the withdrawals tree.
+    pub root: [u8; 32],
+    /// The proof of inclusion of the withdrawal in `root`.
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[rpc_gen(client, server, namespace = "bridge")]
+impl<C: sov_modules_api::Context> Bridge<C> {
+    #[rpc_method(name = "getWithdrawal")]
+    /// Rpc method that returns the withdrawal with the id `id`, if any.
+    pub fn withdrawal(
+        &self,
+        id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Withdrawal<C>>> {
+        Ok(self.withdrawals.get(id as usize, working_set))
+    }
+
+    #[rpc_method(name = "getWithdrawalsRoot")]
+    /// Rpc method that returns the root of the withdrawals tree and the number of withdrawals.
+    pub fn withdrawals_root_info(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<WithdrawalsRootResponse> {
+        Ok(WithdrawalsRootResponse {
+            root: self.withdrawals_root(working_set),
+            withdrawal_count: self.withdrawals.len(working_set) as u64,
+        })
+    }
+
+    #[rpc_method(name = "getWithdrawalProof")]
+    /// Rpc method that returns the withdrawal with the id `id`, and the proof of its inclusion in the current root
+    /// of the withdrawals tree. The root itself is proven against the state root with
+    /// [`Bridge::get_withdrawals_root_with_proof`].
+    pub fn withdrawal_proof(
+        &self,
+        id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<WithdrawalProofResponse<C>>> {
+        let Some(withdrawal) = self.withdrawals.get(id as usize, working_set) else {
+            return Ok(None);
+        };
+        let leaves: Vec<[u8; 32]> = self
+            .withdrawals
+            .iter(working_set)
+            .map(|withdrawal| withdrawal.hash())
+            .collect();
+        Ok(Some(WithdrawalProofResponse {
+            withdrawal,
+            root: self.withdrawals_root(working_set),
+            proof: merkle::inclusion_proof::<C>(&leaves, id),
+        }))
+    }
+}
@@ -0,0 +1,134 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, TokenConfig};
+use sov_bridge::{Bridge, BridgeConfig, CallMessage, MAX_RECIPIENT_SIZE};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
+
+type C = DefaultContext;
+
+const INITIAL_BALANCE: u64 = 1000;
+
+fn context(sender: Address, height: u64) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), height)
+}
+
+#[test]
+fn test_withdraw_and_prove() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::<C>::new(storage.clone());
+
+    let sender = generate_address::<C>("sender");
+    let token_name = "BridgedToken".to_owned();
+    let token_address = get_genesis_token_address::<C>(&token_name, 0);
+    let other_token_name = "OtherToken".to_owned();
+    let other_token_address = get_genesis_token_address::<C>(&other_token_name, 0);
+    let token_config = |token_name| TokenConfig {
+        token_name,
+        address_and_balances: vec![(sender, INITIAL_BALANCE)],
+        authorized_minters: vec![],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
+        salt: 0,
+    };
+
+    let bank = Bank::<C>::default();
+    bank.genesis(
+        &BankConfig {
+            tokens: vec![token_config(token_name), token_config(other_token_name)],
+            fee_settlement: Default::default(),
+        },
+        &mut working_set,
+    )
+    .unwrap();
+    let bridge = Bridge::<C>::default();
+    bridge
+        .genesis(
+            &BridgeConfig {
+                tokens: vec![token_address],
+            },
+            &mut working_set,
+        )
+        .unwrap();
+
+    for (i, amount) in [100, 250].into_iter().enumerate() {
+        bridge
+            .call(
+                CallMessage::Withdraw {
+                    token_address,
+                    amount,
+                    recipient: vec![i as u8; 20],
+                },
+                &context(sender, 5),
+                &mut working_set,
+            )
+            .unwrap();
+    }
+    assert_eq!(
+        bank.get_balance_of(sender, token_address, &mut working_set),
+        Some(INITIAL_BALANCE - 350)
+    );
+    assert_eq!(
+        bank.get_total_supply_of(&token_address, &mut working_set),
+        Some(INITIAL_BALANCE - 350)
+    );
+
+    // Tokens which aren't bridgeable, empty withdrawals, oversized recipients and overdrafts are rejected
+    for (token_address, amount, recipient_size) in [
+        (other_token_address, 1, 20),
+        (token_address, 0, 20),
+        (token_address, 1, MAX_RECIPIENT_SIZE + 1),
+        (token_address, INITIAL_BALANCE, 20),
+    ] {
+        bridge
+            .call(
+                CallMessage::Withdraw {
+                    token_address,
+                    amount,
+                    recipient: vec![0; recipient_size],
+                },
+                &context(sender, 5),
+                &mut working_set,
+            )
+            .unwrap_err();
+    }
+    let root_info = bridge.withdrawals_root_info(&mut working_set).unwrap();
+    assert_eq!(root_info.withdrawal_count, 2);
+
+    // Commit the state, and prove the second withdrawal against the state root
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let state_root = storage
+        .validate_and_commit(reads_writes, &witness)
+        .expect("Should be able to commit");
+    let mut working_set = WorkingSet::<C>::new(storage.clone());
+
+    let proof = bridge
+        .withdrawal_proof(1, &mut working_set)
+        .unwrap()
+        .expect("The withdrawal was made");
+    assert_eq!(proof.root, root_info.root);
+    assert_eq!(proof.withdrawal.amount, 250);
+    assert_eq!(proof.withdrawal.sender, sender);
+    assert_eq!(proof.withdrawal.height, 5);
+
+    let root_proof = bridge.get_withdrawals_root_with_proof(&mut working_set);
+    bridge
+        .verify_withdrawal(
+            state_root,
+            root_proof.clone(),
+            &proof.withdrawal,
+            &proof.proof,
+        )
+        .expect("The withdrawal is committed by the state root");
+
+    // A tampered withdrawal isn't proven
+    let mut tampered = proof.withdrawal.clone();
+    tampered.amount = 1000;
+    bridge
+        .verify_withdrawal(state_root, root_proof, &tampered, &proof.proof)
+        .unwrap_err();
+    assert_eq!(bridge.withdrawal_proof(2, &mut working_set).unwrap(), None);
+}
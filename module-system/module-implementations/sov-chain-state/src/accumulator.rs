@@ -0,0 +1,230 @@
+use anyhow::ensure;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::digest::Digest;
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+use sov_state::Storage;
+
+use crate::{ChainState, StateTransitionId, TransitionHeight};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+const PEAK_PREFIX: u8 = 2;
+
+/// A proof that a transition is a leaf of the transition accumulator, see
+/// [`ChainState::transition_accumulator_root`].
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransitionMembershipProof {
+    /// The position of the transition among all the accumulated transitions
+    pub leaf_index: u64,
+    /// The number of transitions accumulated when the proof was generated
+    pub leaf_count: u64,
+    /// The siblings on the path from the leaf to the peak of its mountain, from the bottom up
+    pub siblings: Vec<[u8; 32]>,
+    /// The peaks of all the mountains, from the highest to the lowest
+    pub peaks: Vec<[u8; 32]>,
+}
+
+/// The transitions are accumulated in a Merkle mountain range: a list of perfect binary Merkle trees
+/// ("mountains") of decreasing heights, one for each bit set in the number of leaves.
+/// The accumulator root commits to all the peaks.
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Returns the root of the accumulator over all the completed transitions, including pruned ones.
+    /// A light client can check any past transition against this root using
+    /// [`ChainState::verify_transition_membership`].
+    pub fn transition_accumulator_root(&self, working_set: &mut WorkingSet<C>) -> [u8; 32] {
+        self.transition_accumulator_root
+            .get(working_set)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of transitions in the accumulator.
+    pub fn transition_accumulator_size(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.transition_accumulator_size
+            .get(working_set)
+            .unwrap_or_default()
+    }
+
+    /// Appends the transition completed at `height` to the accumulator.
+    pub(crate) fn accumulate_transition(
+        &self,
+        height: TransitionHeight,
+        transition: &StateTransitionId<Da, <C::Storage as Storage>::Root>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let leaf_index = self.transition_accumulator_size(working_set);
+        self.transition_leaf_indexes
+            .set(&height, &leaf_index, working_set);
+
+        let mut node = Self::leaf_hash(height, transition);
+        let mut level = 0u8;
+        let mut index = leaf_index;
+        self.transition_accumulator_nodes
+            .set(&(level, index), &node, working_set);
+
+        // Merge the new node with its left sibling for as long as it completes a mountain
+        while index % 2 == 1 {
+            let sibling = self
+                .transition_accumulator_nodes
+                .get(&(level, index - 1), working_set)
+                .expect("The left sibling of an accumulator node must be stored");
+            node = Self::node_hash(&sibling, &node);
+            level += 1;
+            index /= 2;
+            self.transition_accumulator_nodes
+                .set(&(level, index), &node, working_set);
+        }
+
+        let leaf_count = leaf_index + 1;
+        let peaks = self.accumulator_peaks(leaf_count, working_set);
+        self.transition_accumulator_size
+            .set(&leaf_count, working_set);
+        self.transition_accumulator_root
+            .set(&Self::bag_peaks(&peaks), working_set);
+    }
+
+    /// Generates a proof that the transition completed at `height` is included in the current accumulator.
+    /// Proofs can also be generated for pruned transitions.
+    pub fn prove_transition_membership(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<TransitionMembershipProof> {
+        let leaf_index = self.transition_leaf_indexes.get(&height, working_set)?;
+        let leaf_count = self.transition_accumulator_size(working_set);
+        let (mountain_height, _) = mountain_of(leaf_index, leaf_count);
+
+        // Nodes are indexed by their position within their level, so the sibling at each level
+        // is found by flipping the lowest bit of the index of the node on the path.
+        let siblings = (0..mountain_height)
+            .map(|level| {
+                self.transition_accumulator_nodes
+                    .get(&(level, (leaf_index >> level) ^ 1), working_set)
+                    .expect("All the nodes of a complete mountain must be stored")
+            })
+            .collect();
+
+        Some(TransitionMembershipProof {
+            leaf_index,
+            leaf_count,
+            siblings,
+            peaks: self.accumulator_peaks(leaf_count, working_set),
+        })
+    }
+
+    /// Checks that `transition`, completed at `height`, is included in the accumulator with the given `root`.
+    /// This doesn't require access to the state, so it can be used by light clients.
+    pub fn verify_transition_membership(
+        root: &[u8; 32],
+        height: TransitionHeight,
+        transition: &StateTransitionId<Da, <C::Storage as Storage>::Root>,
+        proof: &TransitionMembershipProof,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            proof.leaf_index < proof.leaf_count,
+            "The leaf index {} is out of range",
+            proof.leaf_index
+        );
+        ensure!(
+            proof.peaks.len() == proof.leaf_count.count_ones() as usize,
+            "The number of peaks doesn't match the number of accumulated transitions"
+        );
+        ensure!(
+            &Self::bag_peaks(&proof.peaks) == root,
+            "The peaks don't match the accumulator root"
+        );
+
+        let (mountain_height, mountain_start) = mountain_of(proof.leaf_index, proof.leaf_count);
+        ensure!(
+            proof.siblings.len() == mountain_height as usize,
+            "The path length doesn't match the height of the mountain"
+        );
+
+        let mut node = Self::leaf_hash(height, transition);
+        let mut index = proof.leaf_index - mountain_start;
+        for sibling in &proof.siblings {
+            node = if index % 2 == 0 {
+                Self::node_hash(&node, sibling)
+            } else {
+                Self::node_hash(sibling, &node)
+            };
+            index /= 2;
+        }
+
+        // The mountains are ordered from the highest to the lowest, so the position of the peak
+        // is the number of higher mountains.
+        let peak_position = proof
+            .leaf_count
+            .checked_shr(u32::from(mountain_height) + 1)
+            .unwrap_or_default()
+            .count_ones() as usize;
+        ensure!(
+            proof.peaks[peak_position] == node,
+            "The transition at height {height} isn't included in the accumulator"
+        );
+
+        Ok(())
+    }
+
+    fn accumulator_peaks(&self, leaf_count: u64, working_set: &mut WorkingSet<C>) -> Vec<[u8; 32]> {
+        let mut peaks = Vec::with_capacity(leaf_count.count_ones() as usize);
+        let mut covered = 0u64;
+        for level in (0..u64::BITS as u8).rev() {
+            if leaf_count & (1 << level) != 0 {
+                peaks.push(
+                    self.transition_accumulator_nodes
+                        .get(&(level, covered >> level), working_set)
+                        .expect("The peaks of the accumulator must be stored"),
+                );
+                covered += 1 << level;
+            }
+        }
+        peaks
+    }
+
+    fn leaf_hash(
+        height: TransitionHeight,
+        transition: &StateTransitionId<Da, <C::Storage as Storage>::Root>,
+    ) -> [u8; 32] {
+        let mut hasher = C::Hasher::new();
+        hasher.update([LEAF_PREFIX]);
+        hasher.update(height.to_le_bytes());
+        hasher.update(
+            bcs::to_bytes(transition).expect("Serializing a state transition should never fail"),
+        );
+        hasher.finalize().into()
+    }
+
+    fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = C::Hasher::new();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+
+    fn bag_peaks(peaks: &[[u8; 32]]) -> [u8; 32] {
+        let mut hasher = C::Hasher::new();
+        hasher.update([PEAK_PREFIX]);
+        for peak in peaks {
+            hasher.update(peak);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Returns the height of the mountain containing the leaf at `leaf_index`, and the index of its first leaf.
+fn mountain_of(leaf_index: u64, leaf_count: u64) -> (u8, u64) {
+    let mut start = 0u64;
+    for level in (0..u64::BITS as u8).rev() {
+        let size = 1u64 << level;
+        if leaf_count & size != 0 {
+            if leaf_index < start + size {
+                return (level, start);
+            }
+            start += size;
+        }
+    }
+    unreachable!("The leaf index must be lower than the leaf count")
+}
@@ -1,20 +1,141 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::digest::Digest;
 use sov_modules_api::prelude::*;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::{CallResponse, WorkingSet};
 use sov_state::storage::KernelWorkingSet;
 use sov_state::Storage;
+use thiserror::Error;
 
-use crate::{ChainState, StateTransitionId, TransitionHeight};
+use crate::{ChainState, StateTransitionId, TransitionHeight, UpgradeDescriptor};
+
+/// The call messages accepted by the chain state.
+/// The chain state is a kernel module, so these messages must be routed to [`ChainState::call`] by the kernel.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum CallMessage {
+    /// Schedules a protocol upgrade. Only the upgrade authority configured at genesis may send this message.
+    ScheduleUpgrade {
+        /// The slot height at which the upgrade activates
+        activation_height: TransitionHeight,
+        /// The upgrade to activate
+        upgrade: UpgradeDescriptor,
+    },
+}
+
+/// The errors that might arise while updating the chain state.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChainStateError {
+    /// The slot height was read before being set at genesis.
+    #[error("The slot height must be initialized at genesis")]
+    SlotHeightNotInitialized,
+    /// The slot height can't be incremented any further.
+    #[error("The slot height {0} can't be incremented without overflowing")]
+    SlotHeightOverflow(TransitionHeight),
+    /// A slot was completed without having been started.
+    #[error("There is no transition in progress")]
+    NoTransitionInProgress,
+    /// The visible slot height can't move past the true slot height.
+    #[error(
+        "The visible slot height {visible_height} can't exceed the true slot height {true_height}"
+    )]
+    VisibleSlotHeightAheadOfTrueHeight {
+        /// The requested visible slot height
+        visible_height: TransitionHeight,
+        /// The current true slot height
+        true_height: TransitionHeight,
+    },
+    /// The visible slot height can't go backwards.
+    #[error("The visible slot height can't decrease from {current} to {requested}")]
+    VisibleSlotHeightDecreased {
+        /// The current visible slot height
+        current: TransitionHeight,
+        /// The requested visible slot height
+        requested: TransitionHeight,
+    },
+}
 
 impl<C, Da> ChainState<C, Da>
 where
     C: sov_modules_api::Context,
     Da: sov_modules_api::DaSpec,
 {
-    /// Increment the current slot height
-    pub(crate) fn increment_true_slot_height(&self, working_set: &mut KernelWorkingSet<C>) {
-        let current_height = self.true_height.get(working_set.inner).unwrap_or_default();
-        self.true_height
-            .set(&(current_height.saturating_add(1)), working_set.inner);
+    /// Executes a [`CallMessage`] sent by `context.sender()`.
+    pub fn call(
+        &self,
+        msg: CallMessage,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        match msg {
+            CallMessage::ScheduleUpgrade {
+                activation_height,
+                upgrade,
+            } => self.schedule_upgrade(activation_height, upgrade, context, working_set)?,
+        }
+        Ok(CallResponse::default())
+    }
+
+    /// Increment the current slot height, and return the new height.
+    pub(crate) fn increment_true_slot_height(
+        &self,
+        working_set: &mut KernelWorkingSet<C>,
+    ) -> Result<TransitionHeight, ChainStateError> {
+        let current_height = self
+            .true_height
+            .get(working_set.inner)
+            .ok_or(ChainStateError::SlotHeightNotInitialized)?;
+        let new_height = current_height
+            .checked_add(1)
+            .ok_or(ChainStateError::SlotHeightOverflow(current_height))?;
+        self.true_height.set(&new_height, working_set.inner);
+
+        working_set.inner.add_event(
+            "increment_slot_height",
+            &format!("previous_height: {current_height}, height: {new_height}"),
+        );
+
+        Ok(new_height)
+    }
+
+    /// Sets the visible slot height, which is the height user-facing logic observes.
+    /// The visible height may lag behind the true height (e.g. while catching up on deferred blobs),
+    /// but it can never exceed it, and it never decreases.
+    pub fn update_visible_slot_height(
+        &self,
+        visible_height: TransitionHeight,
+        working_set: &mut KernelWorkingSet<C>,
+    ) -> Result<(), ChainStateError> {
+        let true_height = self
+            .true_height
+            .get(working_set.inner)
+            .ok_or(ChainStateError::SlotHeightNotInitialized)?;
+        if visible_height > true_height {
+            return Err(ChainStateError::VisibleSlotHeightAheadOfTrueHeight {
+                visible_height,
+                true_height,
+            });
+        }
+
+        let current = self
+            .visible_height
+            .get(working_set.inner)
+            .unwrap_or_default();
+        if visible_height < current {
+            return Err(ChainStateError::VisibleSlotHeightDecreased {
+                current,
+                requested: visible_height,
+            });
+        }
+
+        if visible_height != current {
+            self.visible_height.set(&visible_height, working_set.inner);
+            working_set.inner.add_event(
+                "update_visible_slot_height",
+                &format!("previous_height: {current}, height: {visible_height}"),
+            );
+        }
+
+        Ok(())
     }
 
     /// Store the previous state transition
@@ -24,7 +145,126 @@ where
         transition: StateTransitionId<Da, <C::Storage as Storage>::Root>,
         working_set: &mut WorkingSet<C>,
     ) {
+        // The pre-state root is the post-state root of the previous slot
+        let pre_state_root = height
+            .checked_sub(1)
+            .and_then(|previous_height| self.state_roots.get(&previous_height, working_set));
+
+        working_set.add_event(
+            "store_state_transition",
+            &format!(
+                "height: {height}, pre_state_root: {}, post_state_root: {}, validity_condition_digest: {}",
+                pre_state_root.map_or_else(|| "unknown".to_string(), hex::encode),
+                hex::encode(transition.post_state_root()),
+                hex::encode(Self::validity_condition_digest(transition.validity_condition())),
+            ),
+        );
+
+        self.state_roots
+            .set(&height, transition.post_state_root(), working_set);
+        self.heights_by_da_hash
+            .set(transition.da_block_hash(), &height, working_set);
+        self.accumulate_transition(height, &transition, working_set);
         self.historical_transitions
             .set(&height, &transition, working_set);
     }
+
+    /// Record the sequencers whose batches were selected for execution in the current slot.
+    /// Each sequencer is only recorded once, in order of first appearance.
+    pub fn record_slot_sequencers(
+        &self,
+        sequencers: impl IntoIterator<Item = Da::Address>,
+        working_set: &mut KernelWorkingSet<C>,
+    ) {
+        let mut recorded: Vec<Da::Address> = Vec::new();
+        for sequencer in sequencers {
+            if !recorded.contains(&sequencer) {
+                recorded.push(sequencer);
+            }
+        }
+
+        let height = self.true_slot_height(working_set.inner);
+        self.slot_sequencers
+            .set(&height, &recorded, working_set.inner);
+    }
+
+    pub(crate) fn validity_condition_digest(
+        validity_condition: &Da::ValidityCondition,
+    ) -> [u8; 32] {
+        C::Hasher::digest(
+            bcs::to_bytes(validity_condition)
+                .expect("Serializing a validity condition should never fail"),
+        )
+        .into()
+    }
+
+    /// Remove the historical transitions which fall out of the retention window ending at `latest_height`,
+    /// along with everything else recorded for their heights: state roots, timestamps, sequencers,
+    /// base fees and events roots. Only the positions of the transitions in the transition accumulator are kept,
+    /// so that their membership can still be proven.
+    /// If enabled at genesis, the pruned transitions are folded into the running digest of pruned transitions.
+    pub(crate) fn prune_historical_transitions(
+        &self,
+        latest_height: TransitionHeight,
+        kernel_working_set: &mut KernelWorkingSet<C>,
+    ) {
+        let working_set = &mut *kernel_working_set.inner;
+        let Some(retention) = self.transition_retention.get(working_set) else {
+            return;
+        };
+
+        // Keep the `retention` most recent transitions, up to and including `latest_height`
+        let cutoff = latest_height.saturating_add(1).saturating_sub(retention);
+        let accumulate = self
+            .accumulate_pruned_transitions
+            .get(working_set)
+            .unwrap_or_default();
+        let mut earliest = self
+            .earliest_retained_height
+            .get(working_set)
+            .unwrap_or_default();
+
+        let pruned_from = earliest;
+        while earliest < cutoff {
+            if let Some(transition) = self.historical_transitions.remove(&earliest, working_set) {
+                self.heights_by_da_hash
+                    .delete(transition.da_block_hash(), working_set);
+                if accumulate {
+                    self.fold_pruned_transition(earliest, &transition, working_set);
+                }
+            }
+            self.state_roots.delete(&earliest, working_set);
+            self.slot_sequencers.delete(&earliest, working_set);
+            self.base_fee_per_gas.delete(&earliest, working_set);
+            self.events_roots.delete(&earliest, working_set);
+            earliest += 1;
+        }
+        self.earliest_retained_height.set(&earliest, working_set);
+
+        for height in pruned_from..earliest {
+            StateMapAccessor::delete(&self.time, &height, kernel_working_set);
+        }
+    }
+
+    fn fold_pruned_transition(
+        &self,
+        height: TransitionHeight,
+        transition: &StateTransitionId<Da, <C::Storage as Storage>::Root>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let previous = self
+            .pruned_transitions_digest
+            .get(working_set)
+            .unwrap_or_default();
+
+        let mut hasher = C::Hasher::new();
+        hasher.update(previous);
+        hasher.update(height.to_le_bytes());
+        hasher.update(
+            bcs::to_bytes(transition).expect("Serializing a state transition should never fail"),
+        );
+
+        let digest: [u8; 32] = hasher.finalize().into();
+        self.pruned_transitions_digest.set(&digest, working_set);
+    }
 }
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+use thiserror::Error;
+
+use crate::{ChainState, TransitionHeight};
+
+/// An inconsistency found in the state of the chain state by [`ChainState::validate_consistency`].
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ChainStateInconsistency {
+    /// The slot heights are missing, so the chain state was never initialized.
+    #[error("The slot heights are not initialized")]
+    NotInitialized,
+    /// The true slot height is lower than the genesis height.
+    #[error(
+        "The true slot height {true_height} is lower than the genesis height {genesis_height}"
+    )]
+    TrueHeightBelowGenesis {
+        /// The true slot height
+        true_height: TransitionHeight,
+        /// The height of genesis
+        genesis_height: TransitionHeight,
+    },
+    /// The visible slot height is greater than the true slot height.
+    #[error(
+        "The visible slot height {visible_height} is greater than the true slot height {true_height}"
+    )]
+    VisibleHeightAheadOfTrueHeight {
+        /// The visible slot height
+        visible_height: TransitionHeight,
+        /// The true slot height
+        true_height: TransitionHeight,
+    },
+    /// The state root stored at the genesis height isn't the genesis hash.
+    #[error("The state root at the genesis height {0} doesn't match the genesis hash")]
+    GenesisHashMismatch(TransitionHeight),
+    /// A transition within the retention window is missing.
+    #[error("No transition is stored at height {0}")]
+    MissingTransition(TransitionHeight),
+    /// A state root within the retention window is missing.
+    #[error("No state root is stored at height {0}")]
+    MissingStateRoot(TransitionHeight),
+    /// The post-state root of a transition doesn't match the state root stored at its height,
+    /// which is the pre-state root of the next transition.
+    #[error(
+        "The post-state root of the transition at height {0} doesn't match the stored state root"
+    )]
+    PostStateRootMismatch(TransitionHeight),
+    /// The DA block hash of a transition isn't indexed at the height of the transition.
+    #[error("The DA block hash of the transition at height {height} is indexed at height {indexed_height:?}")]
+    DaHashIndexMismatch {
+        /// The height of the transition
+        height: TransitionHeight,
+        /// The height indexed for the DA block hash of the transition
+        indexed_height: Option<TransitionHeight>,
+    },
+    /// The transitions weren't appended to the transition accumulator in height order.
+    #[error("The transition at height {0} isn't accumulated after the previous transition")]
+    AccumulatorOrderMismatch(TransitionHeight),
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Walks the stored transitions, checking that the slot heights are monotone and that each transition
+    /// is linked to the state roots before and after it. Returns the first inconsistency found, if any.
+    ///
+    /// This reads every transition within the retention window, so it is meant for debugging rather
+    /// than for use during slot execution.
+    pub fn validate_consistency(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ChainStateInconsistency> {
+        let (Some(genesis_height), Some(true_height)) = (
+            self.genesis_height.get(working_set),
+            self.true_height.get(working_set),
+        ) else {
+            return Err(ChainStateInconsistency::NotInitialized);
+        };
+
+        if true_height < genesis_height {
+            return Err(ChainStateInconsistency::TrueHeightBelowGenesis {
+                true_height,
+                genesis_height,
+            });
+        }
+
+        let visible_height = self.visible_slot_height(working_set);
+        if visible_height > true_height {
+            return Err(ChainStateInconsistency::VisibleHeightAheadOfTrueHeight {
+                visible_height,
+                true_height,
+            });
+        }
+
+        // No slot has been completed yet
+        let Some(genesis_hash) = self.genesis_hash.get(working_set) else {
+            return Ok(());
+        };
+        // The state roots are pruned along with the transitions
+        let earliest_retained_height = self.earliest_retained_height(working_set);
+        if earliest_retained_height <= genesis_height
+            && self.state_roots.get(&genesis_height, working_set).as_ref() != Some(&genesis_hash)
+        {
+            return Err(ChainStateInconsistency::GenesisHashMismatch(genesis_height));
+        }
+
+        // The transition of the current slot is stored during the next slot
+        let first_height = earliest_retained_height.max(genesis_height + 1);
+        let mut previous_leaf_index = None;
+        for height in first_height..true_height {
+            let transition = self
+                .historical_transitions
+                .get(&height, working_set)
+                .ok_or(ChainStateInconsistency::MissingTransition(height))?;
+
+            if height > earliest_retained_height
+                && self.state_roots.get(&(height - 1), working_set).is_none()
+            {
+                return Err(ChainStateInconsistency::MissingStateRoot(height - 1));
+            }
+            let state_root = self
+                .state_roots
+                .get(&height, working_set)
+                .ok_or(ChainStateInconsistency::MissingStateRoot(height))?;
+            if &state_root != transition.post_state_root() {
+                return Err(ChainStateInconsistency::PostStateRootMismatch(height));
+            }
+
+            let indexed_height = self
+                .heights_by_da_hash
+                .get(transition.da_block_hash(), working_set);
+            if indexed_height != Some(height) {
+                return Err(ChainStateInconsistency::DaHashIndexMismatch {
+                    height,
+                    indexed_height,
+                });
+            }
+
+            let leaf_index = self.transition_leaf_indexes.get(&height, working_set);
+            if leaf_index.is_none() || leaf_index <= previous_leaf_index {
+                return Err(ChainStateInconsistency::AccumulatorOrderMismatch(height));
+            }
+            previous_leaf_index = leaf_index;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::Path;
+
+use anyhow::{ensure, Context as _};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::da::Time;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{DaSpec, StateMap, WorkingSet};
+use sov_state::codec::BorshCodec;
+use sov_state::storage::{NativeStorage, StateValueCodec, StorageKey, StorageProof};
+use sov_state::Storage;
+
+use crate::{ChainState, StateTransitionId, TransitionHeight, TransitionMembershipProof};
+
+/// A historical transition, as exported by [`ChainState::export_transitions`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "Root: Serialize + DeserializeOwned")]
+pub struct ExportedTransition<Da: DaSpec, Root> {
+    /// The height at which the transition was completed
+    pub height: TransitionHeight,
+    /// The transition itself
+    pub transition: StateTransitionId<Da, Root>,
+    /// The time reported by the DA layer for the slot. It isn't covered by the integrity checks.
+    pub time: Option<Time>,
+    /// A proof that the transition is included in the transition accumulator
+    pub membership_proof: TransitionMembershipProof,
+}
+
+/// A range of historical transitions, all proven against the same transition accumulator root.
+/// The export is tied to a state root with [`ChainState::verify_transitions_export`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "Root: Serialize + DeserializeOwned")]
+pub struct TransitionsExport<Da: DaSpec, Root> {
+    /// The root of the transition accumulator when the export was produced
+    pub accumulator_root: [u8; 32],
+    /// The exported transitions, in increasing height order
+    pub transitions: Vec<ExportedTransition<Da, Root>>,
+}
+
+impl<Da: DaSpec, Root: Serialize + DeserializeOwned> TransitionsExport<Da, Root> {
+    /// Writes the export to `path`, as JSON.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let data = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, data)
+            .with_context(|| format!("Failed to write transitions to {}", path.display()))
+    }
+
+    /// Reads an export previously written with [`TransitionsExport::write_to_file`].
+    pub fn read_from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read transitions from {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to parse transitions from {}", path.display()))
+    }
+}
+
+/// The type of the exports produced by [`ChainState::export_transitions`].
+pub type ChainStateExport<C, Da> =
+    TransitionsExport<Da, <<C as sov_modules_api::Spec>::Storage as Storage>::Root>;
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Exports the historical transitions in the inclusive range `start..=end`, along with their timestamps
+    /// and proofs of their inclusion in the transition accumulator.
+    /// Fails if any transition in the range is not stored, e.g. because it was pruned.
+    pub fn export_transitions(
+        &self,
+        start: TransitionHeight,
+        end: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<ChainStateExport<C, Da>> {
+        ensure!(start <= end, "The range {start}..={end} is empty");
+
+        let transitions = (start..=end)
+            .map(|height| {
+                let transition = self
+                    .get_historical_transitions(height, working_set)
+                    .with_context(|| format!("No transition is stored at height {height}"))?;
+                let membership_proof = self
+                    .prove_transition_membership(height, working_set)
+                    .with_context(|| {
+                        format!("The transition at height {height} isn't accumulated")
+                    })?;
+                Ok(ExportedTransition {
+                    height,
+                    transition,
+                    time: self.stored_time_at(height, working_set),
+                    membership_proof,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(TransitionsExport {
+            accumulator_root: self.transition_accumulator_root(working_set),
+            transitions,
+        })
+    }
+
+    /// Returns the current transition accumulator root, along with a proof of it against the current
+    /// state root. See [`ChainState::verify_transitions_export`].
+    pub fn get_transition_accumulator_root_with_proof(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> StorageProof<<C::Storage as Storage>::Proof>
+    where
+        C::Storage: NativeStorage,
+    {
+        working_set.get_with_proof(self.transition_accumulator_root_storage_key())
+    }
+
+    /// Checks every transition of `export` against the trusted `state_root`, and returns the verified
+    /// transitions. `accumulator_root_proof` must be produced by
+    /// [`ChainState::get_transition_accumulator_root_with_proof`] on the state the export was taken from.
+    ///
+    /// This doesn't require access to the state, so it can be used to bootstrap an auxiliary verifier.
+    pub fn verify_transitions_export(
+        &self,
+        state_root: <C::Storage as Storage>::Root,
+        accumulator_root_proof: StorageProof<<C::Storage as Storage>::Proof>,
+        export: ChainStateExport<C, Da>,
+    ) -> anyhow::Result<Vec<ExportedTransition<Da, <C::Storage as Storage>::Root>>> {
+        let (storage_key, storage_value) =
+            C::Storage::open_proof(state_root, accumulator_root_proof)?;
+        ensure!(
+            storage_key == self.transition_accumulator_root_storage_key(),
+            "The storage key from the proof doesn't match the transition accumulator root"
+        );
+
+        let proven_root: [u8; 32] = storage_value
+            .map(|value| {
+                BorshCodec
+                    .try_decode_value(value.value())
+                    .map_err(|e| anyhow::anyhow!("Failed to decode the accumulator root: {e:?}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        ensure!(
+            proven_root == export.accumulator_root,
+            "The exported accumulator root doesn't match the state root"
+        );
+
+        for exported in &export.transitions {
+            Self::verify_transition_membership(
+                &export.accumulator_root,
+                exported.height,
+                &exported.transition,
+                &exported.membership_proof,
+            )?;
+        }
+
+        Ok(export.transitions)
+    }
+
+    /// The time is a versioned value, which is stored as a map from slot heights to values.
+    /// Reading it as such gives access to past versions without a kernel working set.
+    fn stored_time_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Time> {
+        StateMap::<TransitionHeight, Time>::new(self.time.prefix().clone())
+            .get(&height, working_set)
+    }
+
+    fn transition_accumulator_root_storage_key(&self) -> StorageKey {
+        StorageKey::singleton(self.transition_accumulator_root.prefix())
+    }
+}
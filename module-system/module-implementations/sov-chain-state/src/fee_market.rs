@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{GasUnit, WorkingSet};
+
+use crate::{ChainState, TransitionHeight};
+
+/// Bounds the change of the base fee between two consecutive slots to `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR`
+/// of its value, as in EIP-1559.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Initial configuration of the fee market maintained by the chain state.
+/// Every field holds one value per gas dimension.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FeeMarketConfig {
+    /// The base fee per unit of gas of the first slot
+    pub initial_base_fee_per_gas: Vec<u64>,
+    /// The amount of gas consumed per slot that the fee market aims for. The base fee rises after
+    /// slots which used more gas than the target, and falls after slots which used less.
+    /// The base fee of a dimension without a target (or with a zero target) never changes.
+    pub slot_gas_target: Vec<u64>,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Returns the base fee per unit of gas charged in the slot at `height`, if it is known.
+    pub fn base_fee_per_gas_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<C::GasUnit> {
+        self.base_fee_per_gas
+            .get(&height, working_set)
+            .map(|dimensions| C::GasUnit::from_arbitrary_dimensions(&dimensions))
+    }
+
+    /// Returns the base fee per unit of gas charged in the current slot.
+    pub fn base_fee_per_gas(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        let height = self.true_slot_height(working_set);
+        self.base_fee_per_gas_at(height, working_set)
+            .unwrap_or(C::GasUnit::ZEROED)
+    }
+
+    /// Adds `gas_used` to the gas consumed in the current slot, which determines the base fee of the next slot.
+    pub fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>) {
+        let mut slot_gas_used = self
+            .slot_gas_used
+            .get(working_set)
+            .map(|dimensions| C::GasUnit::from_arbitrary_dimensions(&dimensions))
+            .unwrap_or(C::GasUnit::ZEROED);
+        slot_gas_used.combine(gas_used);
+        self.slot_gas_used
+            .set(&slot_gas_used.to_dimensions(), working_set);
+    }
+
+    /// Computes the base fee of the slot at `height` from the base fee and the gas consumption of the
+    /// previous slot, then resets the gas consumption for the new slot.
+    pub(crate) fn update_base_fee(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let previous_base_fee = height
+            .checked_sub(1)
+            .and_then(|previous_height| self.base_fee_per_gas.get(&previous_height, working_set))
+            .unwrap_or_default();
+        let gas_used = self.slot_gas_used.get(working_set).unwrap_or_default();
+        let gas_target = self.slot_gas_target.get(working_set).unwrap_or_default();
+
+        let base_fee: Vec<u64> = previous_base_fee
+            .iter()
+            .enumerate()
+            .map(|(dimension, base_fee)| {
+                next_base_fee(
+                    *base_fee,
+                    gas_used.get(dimension).copied().unwrap_or_default(),
+                    gas_target.get(dimension).copied().unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        self.base_fee_per_gas.set(&height, &base_fee, working_set);
+        self.slot_gas_used.delete(working_set);
+    }
+}
+
+/// Adjusts `base_fee` towards the price at which slots consume `gas_target`, as in EIP-1559.
+pub(crate) fn next_base_fee(base_fee: u64, gas_used: u64, gas_target: u64) -> u64 {
+    if gas_target == 0 || gas_used == gas_target {
+        return base_fee;
+    }
+
+    let delta = |gas_delta: u64| {
+        u128::from(base_fee) * u128::from(gas_delta)
+            / u128::from(gas_target)
+            / u128::from(BASE_FEE_MAX_CHANGE_DENOMINATOR)
+    };
+
+    if gas_used > gas_target {
+        // The excess is capped at the target, so that the base fee rises by at most the maximum change,
+        // and by at least one, otherwise a zero base fee could never grow.
+        let increase = delta((gas_used - gas_target).min(gas_target)).max(1);
+        u64::try_from(u128::from(base_fee) + increase).unwrap_or(u64::MAX)
+    } else {
+        let decrease = delta(gas_target - gas_used);
+        // `decrease` is at most `base_fee / BASE_FEE_MAX_CHANGE_DENOMINATOR`, so this can't underflow.
+        base_fee - decrease as u64
+    }
+}
@@ -1,17 +1,87 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use sov_modules_api::da::Time;
-use sov_modules_api::{StateValueAccessor, WorkingSet};
+use sov_modules_api::{Context, DaSpec, StateMapAccessor, StateValueAccessor, WorkingSet};
+use thiserror::Error;
 
-use crate::{ChainState, TransitionHeight};
+use crate::{ChainState, FeeMarketConfig, TransitionHeight};
 
 /// Initial configuration of the chain state
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
-pub struct ChainStateConfig {
+pub struct ChainStateConfig<C: Context, Da: DaSpec> {
     /// Initial slot height
     pub initial_slot_height: TransitionHeight,
     /// The time at genesis
     pub current_time: Time,
+    /// The identifier of the rollup. Transactions must be signed for this chain id.
+    #[serde(default)]
+    pub chain_id: u64,
+    /// The protocol version the rollup starts with
+    #[serde(default)]
+    pub protocol_version: u64,
+    /// The hash of the DA block the rollup is started from.
+    /// Must be provided together with `initial_validity_condition`.
+    #[serde(default)]
+    pub genesis_da_hash: Option<Da::SlotHash>,
+    /// The validity condition of the DA block the rollup is started from.
+    /// Must be provided together with `genesis_da_hash`.
+    #[serde(default)]
+    pub initial_validity_condition: Option<Da::ValidityCondition>,
+    /// The number of most recent historical transitions kept in state, along with the state roots,
+    /// timestamps, sequencers, base fees and events roots of their slots.
+    /// All transitions are kept if `None`.
+    #[serde(default)]
+    pub transition_retention: Option<u64>,
+    /// If set, pruned transitions are folded into a running digest,
+    /// see [`ChainState::pruned_transitions_digest`].
+    #[serde(default)]
+    pub accumulate_pruned_transitions: bool,
+    /// The initial base fee and the gas target of the fee market
+    #[serde(default)]
+    pub fee_market: FeeMarketConfig,
+    /// The address allowed to schedule protocol upgrades.
+    /// No upgrade can be scheduled if `None`.
+    #[serde(default)]
+    pub upgrade_authority: Option<C::Address>,
+    /// The versions of the state of the modules which don't start at version `0`,
+    /// indexed by module address.
+    #[serde(default)]
+    pub module_versions: Vec<(C::Address, u64)>,
+}
+
+/// The errors that might arise when validating a [`ChainStateConfig`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChainStateConfigError {
+    /// The slot height can't be incremented past the initial one.
+    #[error("The initial slot height {0} leaves no room to process any slot")]
+    InitialSlotHeightTooLarge(TransitionHeight),
+    /// Only one of the genesis DA hash and the initial validity condition was provided.
+    #[error("The genesis DA hash and the initial validity condition must be provided together")]
+    IncompleteGenesisDaBlock,
+    /// At least one historical transition must be retained.
+    #[error("The transition retention must be strictly positive")]
+    ZeroTransitionRetention,
+}
+
+impl<C: Context, Da: DaSpec> ChainStateConfig<C, Da> {
+    /// Checks that the configuration can be used to initialize the module.
+    pub fn validate(&self) -> Result<(), ChainStateConfigError> {
+        if self.initial_slot_height == TransitionHeight::MAX {
+            return Err(ChainStateConfigError::InitialSlotHeightTooLarge(
+                self.initial_slot_height,
+            ));
+        }
+
+        if self.genesis_da_hash.is_some() != self.initial_validity_condition.is_some() {
+            return Err(ChainStateConfigError::IncompleteGenesisDaBlock);
+        }
+
+        if self.transition_retention == Some(0) {
+            return Err(ChainStateConfigError::ZeroTransitionRetention);
+        }
+
+        Ok(())
+    }
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -20,13 +90,57 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         config: &<Self as sov_modules_api::KernelModule>::Config,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
+        config.validate()?;
+
         self.genesis_height
             .set(&config.initial_slot_height, working_set);
 
         self.true_height
             .set(&config.initial_slot_height, working_set);
 
+        self.visible_height
+            .set(&config.initial_slot_height, working_set);
+
         self.time.set_genesis(&config.current_time, working_set);
+
+        self.chain_id.set(&config.chain_id, working_set);
+        self.protocol_version
+            .set(&config.protocol_version, working_set);
+
+        self.earliest_retained_height
+            .set(&config.initial_slot_height, working_set);
+
+        self.base_fee_per_gas.set(
+            &config.initial_slot_height,
+            &config.fee_market.initial_base_fee_per_gas,
+            working_set,
+        );
+        self.slot_gas_target
+            .set(&config.fee_market.slot_gas_target, working_set);
+
+        if let Some(retention) = config.transition_retention {
+            self.transition_retention.set(&retention, working_set);
+            self.accumulate_pruned_transitions
+                .set(&config.accumulate_pruned_transitions, working_set);
+        }
+
+        if let Some(upgrade_authority) = &config.upgrade_authority {
+            self.upgrade_authority.set(upgrade_authority, working_set);
+        }
+
+        for (module_address, version) in &config.module_versions {
+            self.module_versions
+                .set(module_address, version, working_set);
+        }
+
+        if let Some(genesis_da_hash) = &config.genesis_da_hash {
+            self.genesis_da_hash.set(genesis_da_hash, working_set);
+        }
+
+        if let Some(validity_condition) = &config.initial_validity_condition {
+            self.initial_validity_condition
+                .set(validity_condition, working_set);
+        }
         Ok(())
     }
 }
@@ -6,27 +6,40 @@ use sov_state::storage::KernelWorkingSet;
 use sov_state::Storage;
 
 use super::ChainState;
-use crate::{StateTransitionId, TransitionInProgress};
+use crate::{ChainStateError, StateTransitionId, TransitionHeight, TransitionInProgress};
 
 impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
-    /// Update the chain state at the beginning of the slot
+    /// Update the chain state at the beginning of the slot: store the transition completed by the previous slot,
+    /// increment the slot height and record the header of the slot being processed.
+    /// Kernels call this from their `begin_slot_hook`, so runtimes never need to update the chain state themselves.
+    /// Returns an error, before modifying the state, if the chain state was not properly initialized.
     pub fn begin_slot_hook(
         &self,
         slot_header: &Da::BlockHeader,
         validity_condition: &Da::ValidityCondition,
         pre_state_root: &<<C as Spec>::Storage as Storage>::Root,
         working_set: &mut KernelWorkingSet<C>,
-    ) {
+    ) -> Result<(), ChainStateError> {
+        let current_height = self
+            .true_height
+            .get(working_set.inner)
+            .ok_or(ChainStateError::SlotHeightNotInitialized)?;
+        if current_height == TransitionHeight::MAX {
+            return Err(ChainStateError::SlotHeightOverflow(current_height));
+        }
+
         if self.genesis_hash.get(working_set.inner).is_none() {
             // The genesis hash is not set, hence this is the
             // first transition right after the genesis block
-            self.genesis_hash.set(pre_state_root, working_set.inner)
+            self.genesis_hash.set(pre_state_root, working_set.inner);
+            self.state_roots
+                .set(&current_height, pre_state_root, working_set.inner);
         } else {
             let transition: StateTransitionId<Da, <<C as Spec>::Storage as Storage>::Root> = {
                 let last_transition_in_progress = self
                     .in_progress_transition
                     .get(working_set)
-                    .expect("There should always be a transition in progress");
+                    .ok_or(ChainStateError::NoTransitionInProgress)?;
 
                 StateTransitionId {
                     da_block_hash: last_transition_in_progress.da_block_hash,
@@ -35,17 +48,17 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
                 }
             };
 
-            self.store_state_transition(
-                self.true_height
-                    .get(working_set.inner)
-                    .expect("Block height must be set"),
-                transition,
-                working_set.inner,
-            );
+            self.store_state_transition(current_height, transition, working_set.inner);
+            self.prune_historical_transitions(current_height, working_set);
         }
 
-        self.increment_true_slot_height(working_set);
-        self.time.set_current(&slot_header.time(), working_set);
+        let height = self.increment_true_slot_height(working_set)?;
+        self.update_base_fee(height, working_set.inner);
+        self.activate_upgrade(height, working_set.inner);
+
+        // Record the timestamp under the height of the slot being processed, so that
+        // `time_at` returns the time of the DA block that was applied at that height.
+        StateMapAccessor::set(&self.time, &height, &slot_header.time(), working_set);
 
         self.in_progress_transition.set(
             &TransitionInProgress {
@@ -54,9 +67,14 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
             },
             working_set,
         );
+
+        Ok(())
     }
 
-    /// Update the chain state at the end of each slot, if necessary
+    /// Update the chain state at the end of each slot, if necessary. Kernels call this from their `end_slot_hook`.
+    ///
+    /// This is currently a no-op: the transition of a slot is only stored once its post-state root is known,
+    /// which is by the [`ChainState::begin_slot_hook`] of the next slot.
     pub fn end_slot_hook(&self, _working_set: &mut KernelWorkingSet<C>) {}
 }
 
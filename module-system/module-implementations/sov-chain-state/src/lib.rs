@@ -3,12 +3,44 @@
 
 /// Contains the call methods used by the module
 mod call;
+pub use call::{CallMessage, ChainStateError};
 #[cfg(test)]
 mod tests;
 
 mod genesis;
 pub use genesis::*;
 
+/// The base fee market maintained across slots
+mod fee_market;
+pub use fee_market::{FeeMarketConfig, BASE_FEE_MAX_CHANGE_DENOMINATOR};
+
+/// The registry of scheduled protocol upgrades
+mod upgrades;
+pub use upgrades::{UpgradeDescriptor, UpgradeError};
+
+/// The versions of the state of the modules, and their migrations
+mod migrations;
+#[cfg(feature = "native")]
+pub use migrations::MigrationDryRun;
+pub use migrations::MigrationError;
+
+/// State proofs for historical transitions
+mod proofs;
+
+/// The accumulator over all historical transitions
+mod accumulator;
+pub use accumulator::TransitionMembershipProof;
+
+/// Consistency checks over the stored transitions
+mod consistency;
+pub use consistency::ChainStateInconsistency;
+
+/// Export and import of historical transitions
+#[cfg(feature = "native")]
+mod export;
+#[cfg(feature = "native")]
+pub use export::{ChainStateExport, ExportedTransition, TransitionsExport};
+
 /// Hook implementation for the module
 pub mod hooks;
 
@@ -22,7 +54,9 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sov_modules_api::da::Time;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{DaSpec, Error, KernelModuleInfo, ValidityConditionChecker, WorkingSet};
+use sov_modules_api::{
+    DaSpec, Error, KernelModuleInfo, ValidityCondition, ValidityConditionChecker, WorkingSet,
+};
 use sov_state::codec::BcsCodec;
 use sov_state::storage::kernel_state::VersionReader;
 use sov_state::storage::KernelWorkingSet;
@@ -31,6 +65,10 @@ use sov_state::Storage;
 /// Type alias that contains the height of a given transition
 pub type TransitionHeight = u64;
 
+/// The maximum number of transitions whose validity conditions can be combined by
+/// [`ChainState::aggregate_validity_conditions`] at once.
+pub const MAX_AGGREGATED_TRANSITIONS: u64 = 1024;
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 /// Structure that contains the information needed to represent a single state transition.
 pub struct StateTransitionId<Da: DaSpec, StateRoot> {
@@ -130,12 +168,13 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     #[state]
     true_height: sov_modules_api::StateValue<TransitionHeight>,
 
-    /// The current time, as reported by the DA layer
+    /// The time reported by the DA layer for each slot, indexed by true slot height
     #[state]
     time: sov_modules_api::VersionedStateValue<Time>,
 
     /// A record of all previous state transitions which are available to the VM.
-    /// Currently, this includes *all* historical state transitions, but that may change in the future.
+    /// Only the transitions within the retention window configured at genesis are kept, see
+    /// `transition_retention`.
     /// This state map is delayed by one transition. In other words - the transition that happens in time i
     /// is stored during transition i+1. This is mainly due to the fact that this structure depends on the
     /// rollup's root hash which is only stored once the transition has completed.
@@ -147,6 +186,47 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
         BcsCodec,
     >,
 
+    /// The post-state root of every completed slot, indexed by height. The root stored at the genesis
+    /// height is the genesis hash. The roots are pruned along with `historical_transitions`.
+    #[state]
+    state_roots: sov_modules_api::StateMap<TransitionHeight, <C::Storage as Storage>::Root>,
+
+    /// The number of transitions appended to the transition accumulator
+    #[state]
+    transition_accumulator_size: sov_modules_api::StateValue<u64>,
+
+    /// The root of the transition accumulator, committing to every completed transition
+    #[state]
+    transition_accumulator_root: sov_modules_api::StateValue<[u8; 32]>,
+
+    /// The nodes of the transition accumulator, indexed by level and position within the level
+    #[state]
+    transition_accumulator_nodes: sov_modules_api::StateMap<(u8, u64), [u8; 32]>,
+
+    /// The position of each completed transition among the leaves of the transition accumulator
+    #[state]
+    transition_leaf_indexes: sov_modules_api::StateMap<TransitionHeight, u64>,
+
+    /// The DA addresses of the sequencers whose batches were selected for execution in each slot
+    #[state]
+    slot_sequencers: sov_modules_api::StateMap<TransitionHeight, Vec<Da::Address>, BcsCodec>,
+
+    /// The base fee per unit of gas charged in each slot, for each gas dimension
+    #[state]
+    base_fee_per_gas: sov_modules_api::StateMap<TransitionHeight, Vec<u64>>,
+
+    /// The gas consumed so far in the current slot, for each gas dimension
+    #[state]
+    slot_gas_used: sov_modules_api::StateValue<Vec<u64>>,
+
+    /// The gas consumption per slot targeted by the fee market, for each gas dimension
+    #[state]
+    slot_gas_target: sov_modules_api::StateValue<Vec<u64>>,
+
+    /// An index from the DA block hash of each transition in `historical_transitions` to its height
+    #[state]
+    heights_by_da_hash: sov_modules_api::StateMap<Da::SlotHash, TransitionHeight, BcsCodec>,
+
     /// The transition that is currently processed
     #[state]
     in_progress_transition: sov_modules_api::KernelStateValue<TransitionInProgress<Da>, BcsCodec>,
@@ -161,6 +241,55 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     // TODO: This should be made read-only
     #[state]
     genesis_height: sov_modules_api::StateValue<TransitionHeight>,
+
+    /// The identifier of the rollup, which every transaction must be signed for
+    #[state]
+    chain_id: sov_modules_api::StateValue<u64>,
+
+    /// The version of the protocol the rollup is currently running
+    #[state]
+    protocol_version: sov_modules_api::StateValue<u64>,
+
+    /// The protocol upgrades scheduled so far, indexed by activation height
+    #[state]
+    upgrades: sov_modules_api::StateMap<TransitionHeight, UpgradeDescriptor>,
+
+    /// The address allowed to schedule protocol upgrades, if any
+    #[state]
+    upgrade_authority: sov_modules_api::StateValue<C::Address>,
+
+    /// The version of the state of each module. Modules missing from the map are at version `0`
+    #[state]
+    module_versions: sov_modules_api::StateMap<C::Address, u64>,
+
+    /// The number of most recent historical transitions kept in state. All transitions are kept if unset.
+    #[state]
+    transition_retention: sov_modules_api::StateValue<u64>,
+
+    /// Whether pruned transitions are folded into `pruned_transitions_digest`
+    #[state]
+    accumulate_pruned_transitions: sov_modules_api::StateValue<bool>,
+
+    /// The lowest height which hasn't been pruned from `historical_transitions`
+    #[state]
+    earliest_retained_height: sov_modules_api::StateValue<TransitionHeight>,
+
+    /// A running digest over all the transitions pruned from `historical_transitions`
+    #[state]
+    pruned_transitions_digest: sov_modules_api::StateValue<[u8; 32]>,
+
+    /// The hash of the DA block the rollup was started from, if provided at genesis
+    #[state]
+    genesis_da_hash: sov_modules_api::StateValue<Da::SlotHash, BcsCodec>,
+
+    /// The validity condition of the DA block the rollup was started from, if provided at genesis
+    #[state]
+    initial_validity_condition: sov_modules_api::StateValue<Da::ValidityCondition, BcsCodec>,
+
+    /// The root of the tree over the events emitted by the transactions of each slot,
+    /// see [`sov_modules_api::events`]
+    #[state]
+    events_roots: sov_modules_api::StateMap<TransitionHeight, [u8; 32]>,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -169,7 +298,8 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.true_height.get(working_set).unwrap_or_default()
     }
 
-    /// Returns transition height in the current slot
+    /// Returns the height which user-facing logic observes in the current slot.
+    /// It may lag behind [`ChainState::true_slot_height`], but never exceeds it.
     pub fn visible_slot_height(&self, working_set: &mut WorkingSet<C>) -> TransitionHeight {
         self.visible_height.get(working_set).unwrap_or_default()
     }
@@ -181,6 +311,16 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
             .expect("Time must be set at initialization")
     }
 
+    /// Returns the time reported by the DA layer for the slot at `height`.
+    /// Slots which are not yet visible to the working set, or were pruned, return `None`.
+    pub fn time_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut impl VersionReader,
+    ) -> Option<Time> {
+        self.time.get_at(height, working_set)
+    }
+
     /// Return the genesis hash of the module.
     pub fn get_genesis_hash(
         &self,
@@ -194,6 +334,47 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.genesis_height.get(working_set)
     }
 
+    /// Returns the identifier of the rollup. Transactions signed for any other chain are rejected.
+    pub fn get_chain_id(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.chain_id.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the version of the protocol the rollup is currently running.
+    pub fn get_protocol_version(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.protocol_version.get(working_set).unwrap_or_default()
+    }
+
+    /// Records the root of the tree over the events emitted by the transactions of the current slot.
+    /// Kernels call this once all the batches of the slot are applied.
+    pub fn record_events_root(&self, events_root: [u8; 32], working_set: &mut WorkingSet<C>) {
+        let height = self.true_slot_height(working_set);
+        self.events_roots.set(&height, &events_root, working_set);
+    }
+
+    /// Returns the root of the tree over the events emitted by the transactions of the slot at `height`.
+    /// The inclusion of an event can be checked against this root with an
+    /// [`EventInclusionProof`](sov_modules_api::events::EventInclusionProof).
+    pub fn get_events_root(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<[u8; 32]> {
+        self.events_roots.get(&height, working_set)
+    }
+
+    /// Returns the hash of the DA block the rollup was started from.
+    pub fn get_genesis_da_hash(&self, working_set: &mut WorkingSet<C>) -> Option<Da::SlotHash> {
+        self.genesis_da_hash.get(working_set)
+    }
+
+    /// Returns the validity condition of the DA block the rollup was started from.
+    pub fn get_initial_validity_condition(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Da::ValidityCondition> {
+        self.initial_validity_condition.get(working_set)
+    }
+
     /// Returns the transition in progress of the module.
     pub fn get_in_progress_transition(
         &self,
@@ -202,6 +383,88 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.in_progress_transition.get(working_set)
     }
 
+    /// Returns the state root of the rollup after the slot at `height` was applied,
+    /// or `None` if the slot isn't completed or was pruned.
+    pub fn state_root_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<<C::Storage as Storage>::Root> {
+        self.state_roots.get(&height, working_set)
+    }
+
+    /// Combines the validity conditions of the completed transitions in the inclusive range
+    /// `start..=end` into a single condition, using [`ValidityCondition::combine`].
+    /// Fails if the range is empty or longer than [`MAX_AGGREGATED_TRANSITIONS`], or if any transition
+    /// in the range is not stored.
+    pub fn aggregate_validity_conditions(
+        &self,
+        start: TransitionHeight,
+        end: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<Da::ValidityCondition> {
+        anyhow::ensure!(
+            start <= end,
+            "Invalid transition range: start {start} is greater than end {end}"
+        );
+        anyhow::ensure!(
+            end - start < MAX_AGGREGATED_TRANSITIONS,
+            "Invalid transition range: {start}..={end} is longer than {MAX_AGGREGATED_TRANSITIONS} transitions"
+        );
+
+        let mut aggregated: Option<Da::ValidityCondition> = None;
+        for height in start..=end {
+            let transition = self
+                .historical_transitions
+                .get(&height, working_set)
+                .ok_or_else(|| anyhow::anyhow!("No transition is stored at height {height}"))?;
+
+            aggregated = Some(match aggregated {
+                None => transition.validity_condition,
+                Some(condition) => condition
+                    .combine::<C::Hasher>(transition.validity_condition)
+                    .map_err(Into::<anyhow::Error>::into)?,
+            });
+        }
+
+        Ok(aggregated.expect("The range is not empty"))
+    }
+
+    /// Returns the DA addresses of the sequencers whose batches were selected for execution
+    /// in the slot at `height`, in execution order.
+    pub fn get_slot_sequencers(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<Da::Address> {
+        self.slot_sequencers
+            .get(&height, working_set)
+            .unwrap_or_default()
+    }
+
+    /// Returns the height of the completed transition for the DA block with the provided `da_block_hash`.
+    pub fn get_height_by_da_hash(
+        &self,
+        da_block_hash: &Da::SlotHash,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<TransitionHeight> {
+        self.heights_by_da_hash.get(da_block_hash, working_set)
+    }
+
+    /// Returns the lowest height for which a completed transition may still be stored.
+    /// Transitions below this height have been pruned.
+    pub fn earliest_retained_height(&self, working_set: &mut WorkingSet<C>) -> TransitionHeight {
+        self.earliest_retained_height
+            .get(working_set)
+            .unwrap_or_default()
+    }
+
+    /// Returns the running digest of all the pruned transitions, if any transition has been
+    /// pruned and accumulation was enabled at genesis.
+    pub fn pruned_transitions_digest(&self, working_set: &mut WorkingSet<C>) -> Option<[u8; 32]> {
+        self.pruned_transitions_digest.get(working_set)
+    }
+
     /// Returns the completed transition associated with the provided `transition_num`.
     pub fn get_historical_transitions(
         &self,
@@ -218,7 +481,7 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> sov_modules_api::
 {
     type Context = C;
 
-    type Config = ChainStateConfig;
+    type Config = ChainStateConfig<C, Da>;
 
     fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
         // The initialization logic
@@ -0,0 +1,138 @@
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::Event;
+use sov_modules_api::{Migration, ModuleError, WorkingSet};
+use thiserror::Error;
+
+use crate::ChainState;
+
+/// The errors that might arise when migrating the state of a module.
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    /// The state of the module is more recent than its code, e.g. because the node runs an outdated binary.
+    #[error("The state of module {module} is at version {state_version}, ahead of the version {code_version} of its code")]
+    StateAhead {
+        /// The address of the module
+        module: String,
+        /// The version of the state of the module
+        state_version: u64,
+        /// The version of the code of the module
+        code_version: u64,
+    },
+    /// A migration step failed.
+    #[error("Migrating module {module} from version {from_version} failed: {source}")]
+    Failed {
+        /// The address of the module
+        module: String,
+        /// The version the failed step migrated from
+        from_version: u64,
+        /// The error returned by the module
+        source: ModuleError,
+    },
+}
+
+/// The outcome of a migration executed without committing its changes.
+#[cfg(feature = "native")]
+#[derive(Debug)]
+pub struct MigrationDryRun {
+    /// The version of the state before the migration
+    pub from_version: u64,
+    /// The version of the state after the migration
+    pub to_version: u64,
+    /// The events emitted by the migration steps
+    pub events: Vec<Event>,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Returns the version of the state of the module at `module_address`. Modules start at version `0`.
+    pub fn get_module_version(
+        &self,
+        module_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        self.module_versions
+            .get(module_address, working_set)
+            .unwrap_or_default()
+    }
+
+    /// Returns true if a protocol upgrade activated at the current slot.
+    pub fn is_upgrade_activation_slot(&self, working_set: &mut WorkingSet<C>) -> bool {
+        let height = self.true_slot_height(working_set);
+        self.upgrades
+            .get(&height, working_set)
+            .is_some_and(|upgrade| {
+                upgrade.protocol_version == self.get_protocol_version(working_set)
+            })
+    }
+
+    /// Migrates the state of `module` to the version of its code, one version at a time, if a protocol upgrade
+    /// activated at the current slot. Does nothing at other slots.
+    ///
+    /// Runtimes call this from their `begin_slot_hook` for every module implementing [`Migration`],
+    /// after the kernel activated the upgrades of the slot.
+    pub fn migrate_module<M: Migration<Context = C>>(
+        &self,
+        module: &M,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), MigrationError> {
+        if !self.is_upgrade_activation_slot(working_set) {
+            return Ok(());
+        }
+        self.apply_migrations(module, working_set).map(|_| ())
+    }
+
+    /// Executes the migrations of `module` against `storage` regardless of the current slot,
+    /// and discards their changes. Lets operators check a migration before scheduling the upgrade.
+    #[cfg(feature = "native")]
+    pub fn dry_run_migration<M: Migration<Context = C>>(
+        &self,
+        module: &M,
+        storage: C::Storage,
+    ) -> Result<MigrationDryRun, MigrationError> {
+        let mut working_set = WorkingSet::new(storage);
+        let from_version = self.apply_migrations(module, &mut working_set)?;
+        Ok(MigrationDryRun {
+            from_version,
+            to_version: M::VERSION,
+            events: working_set.take_events(),
+        })
+    }
+
+    /// Runs the migration steps of `module` from the recorded version to the version of its code.
+    /// Returns the version the state was migrated from.
+    fn apply_migrations<M: Migration<Context = C>>(
+        &self,
+        module: &M,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<u64, MigrationError> {
+        let module_address = module.address();
+        let from_version = self.get_module_version(module_address, working_set);
+        if from_version > M::VERSION {
+            return Err(MigrationError::StateAhead {
+                module: module_address.to_string(),
+                state_version: from_version,
+                code_version: M::VERSION,
+            });
+        }
+
+        for version in from_version..M::VERSION {
+            module
+                .migrate(version, working_set)
+                .map_err(|source| MigrationError::Failed {
+                    module: module_address.to_string(),
+                    from_version: version,
+                    source,
+                })?;
+            self.module_versions
+                .set(module_address, &(version + 1), working_set);
+            working_set.add_event(
+                "migrate_module",
+                &format!(
+                    "module: {module_address}, from_version: {version}, to_version: {}",
+                    version + 1
+                ),
+            );
+        }
+        Ok(from_version)
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::ensure;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::WorkingSet;
+#[cfg(feature = "native")]
+use sov_state::storage::NativeStorage;
+use sov_state::storage::{StateValueCodec, StorageKey, StorageProof};
+use sov_state::Storage;
+
+use crate::{ChainState, StateTransitionId, TransitionHeight};
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Returns the storage key under which the transition at `height` is stored.
+    pub fn historical_transition_storage_key(&self, height: TransitionHeight) -> StorageKey {
+        let prefix = self.historical_transitions.prefix();
+        let codec = self.historical_transitions.codec();
+        StorageKey::new(prefix, &height, codec)
+    }
+
+    /// Returns the transition stored at `height`, along with a proof of its inclusion (or absence)
+    /// against the current state root.
+    #[cfg(feature = "native")]
+    pub fn get_historical_transition_with_proof(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> StorageProof<<C::Storage as Storage>::Proof>
+    where
+        C::Storage: NativeStorage,
+    {
+        working_set.get_with_proof(self.historical_transition_storage_key(height))
+    }
+
+    /// Verifies a proof produced by [`ChainState::get_historical_transition_with_proof`] against
+    /// `state_root`, and returns the transition stored at `height`, if any.
+    ///
+    /// This doesn't require access to the state, so it can be used by light clients and bridges to check
+    /// that the rollup committed to a given transition.
+    pub fn verify_historical_transition_proof(
+        &self,
+        height: TransitionHeight,
+        state_root: <C::Storage as Storage>::Root,
+        proof: StorageProof<<C::Storage as Storage>::Proof>,
+    ) -> anyhow::Result<Option<StateTransitionId<Da, <C::Storage as Storage>::Root>>> {
+        let (storage_key, storage_value) = C::Storage::open_proof(state_root, proof)?;
+
+        ensure!(
+            storage_key == self.historical_transition_storage_key(height),
+            "The storage key from the proof doesn't match the transition at height {height}"
+        );
+
+        storage_value
+            .map(|value| {
+                self.historical_transitions
+                    .codec()
+                    .try_decode_value(value.value())
+                    .map_err(|e| anyhow::anyhow!("Failed to decode the proven transition: {e:?}"))
+            })
+            .transpose()
+    }
+}
@@ -1,19 +1,173 @@
-// use jsonrpsee::core::RpcResult;
+//! Defines rpc queries exposed by the chain state module
+use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{GasUnit, WorkingSet};
+use sov_state::Storage;
 
-// use sov_modules_api::WorkingSet;
-use crate::ChainState;
+use crate::{
+    ChainState, ChainStateExport, ChainStateInconsistency, StateTransitionId, TransitionHeight,
+    TransitionMembershipProof, UpgradeDescriptor,
+};
+
+const EXPORT_TRANSITIONS_ERROR: &str = "EXPORT_TRANSITIONS_ERROR";
 
 #[rpc_gen(client, server, namespace = "chainState")]
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
-    // TODO: Re-enable this RPC method once the `KernelWorkingSet` type is removed
-    // /// Get the height of the current slot.
-    // /// Panics if the slot height is not set
-    // #[rpc_method(name = "getSlotHeight")]
-    // pub fn get_slot_height_rpc(
-    //     &self,
-    //     working_set: &mut WorkingSet<C>,
-    // ) -> RpcResult<TransitionHeight> {
-    //     Ok(self.get_slot_height(working_set))
-    // }
+    /// Get the true height of the current slot.
+    #[rpc_method(name = "slotHeight")]
+    pub fn slot_height(&self, working_set: &mut WorkingSet<C>) -> RpcResult<TransitionHeight> {
+        Ok(self.true_slot_height(working_set))
+    }
+
+    /// Get the visible height of the current slot, which may lag behind the true height.
+    #[rpc_method(name = "visibleSlotHeight")]
+    pub fn visible_height(&self, working_set: &mut WorkingSet<C>) -> RpcResult<TransitionHeight> {
+        Ok(self.visible_slot_height(working_set))
+    }
+
+    /// Get the genesis hash of the rollup.
+    /// Returns `None` until the first slot after genesis has been processed.
+    #[rpc_method(name = "genesisHash")]
+    pub fn genesis_hash(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<<C::Storage as Storage>::Root>> {
+        Ok(self.get_genesis_hash(working_set))
+    }
+
+    /// Get the identifier of the rollup.
+    #[rpc_method(name = "chainId")]
+    pub fn chain_id(&self, working_set: &mut WorkingSet<C>) -> RpcResult<u64> {
+        Ok(self.get_chain_id(working_set))
+    }
+
+    /// Get the version of the protocol the rollup is currently running.
+    #[rpc_method(name = "protocolVersion")]
+    pub fn protocol_version(&self, working_set: &mut WorkingSet<C>) -> RpcResult<u64> {
+        Ok(self.get_protocol_version(working_set))
+    }
+
+    /// Get the protocol upgrade scheduled to activate at the given `height`, if any.
+    #[rpc_method(name = "upgradeAt")]
+    pub fn upgrade_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<UpgradeDescriptor>> {
+        Ok(self.get_upgrade(height, working_set))
+    }
+
+    /// Get the root of the tree over the events emitted by the transactions of the slot at the given `height`.
+    #[rpc_method(name = "eventsRootAt")]
+    pub fn events_root_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<[u8; 32]>> {
+        Ok(self.get_events_root(height, working_set))
+    }
+
+    /// Get the version of the state of the module at the given `module_address`.
+    #[rpc_method(name = "moduleVersion")]
+    pub fn module_version(
+        &self,
+        module_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<u64> {
+        Ok(self.get_module_version(&module_address, working_set))
+    }
+
+    /// Get the completed state transition stored at the given `height`.
+    #[rpc_method(name = "transitionAt")]
+    pub fn transition_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<StateTransitionId<Da, <C::Storage as Storage>::Root>>> {
+        Ok(self.get_historical_transitions(height, working_set))
+    }
+
+    /// Get the state root of the rollup after the slot at the given `height` was applied.
+    #[rpc_method(name = "stateRootAt")]
+    pub fn state_root_at_rpc(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<<C::Storage as Storage>::Root>> {
+        Ok(self.state_root_at(height, working_set))
+    }
+
+    /// Get the root of the accumulator over all the completed transitions.
+    #[rpc_method(name = "transitionAccumulatorRoot")]
+    pub fn transition_accumulator_root_rpc(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<[u8; 32]> {
+        Ok(self.transition_accumulator_root(working_set))
+    }
+
+    /// Get a proof that the transition completed at the given `height` is included in the transition accumulator.
+    #[rpc_method(name = "transitionMembershipProof")]
+    pub fn transition_membership_proof(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<TransitionMembershipProof>> {
+        Ok(self.prove_transition_membership(height, working_set))
+    }
+
+    /// Export the completed transitions in the inclusive range `start..=end`, along with their timestamps
+    /// and proofs of their inclusion in the transition accumulator.
+    #[rpc_method(name = "exportTransitions")]
+    pub fn export_transitions_rpc(
+        &self,
+        start: TransitionHeight,
+        end: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ChainStateExport<C, Da>> {
+        self.export_transitions(start, end, working_set)
+            .map_err(|e| to_jsonrpsee_error_object(e, EXPORT_TRANSITIONS_ERROR))
+    }
+
+    /// Check the consistency of the stored transitions, and get the first inconsistency found, if any.
+    #[rpc_method(name = "validateConsistency")]
+    pub fn validate_consistency_rpc(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<ChainStateInconsistency>> {
+        Ok(self.validate_consistency(working_set).err())
+    }
+
+    /// Get the DA addresses of the sequencers whose batches were executed in the slot at the given `height`.
+    #[rpc_method(name = "sequencersAt")]
+    pub fn sequencers_at(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<Da::Address>> {
+        Ok(self.get_slot_sequencers(height, working_set))
+    }
+
+    /// Get the base fee per unit of gas charged in the slot at the given `height`, for each gas dimension.
+    #[rpc_method(name = "baseFeePerGasAt")]
+    pub fn base_fee_per_gas_at_rpc(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Vec<u64>>> {
+        Ok(self
+            .base_fee_per_gas_at(height, working_set)
+            .map(|base_fee| base_fee.to_dimensions()))
+    }
+
+    /// Get the height of the completed transition for the DA block with the given hash.
+    #[rpc_method(name = "heightByDaHash")]
+    pub fn height_by_da_hash(
+        &self,
+        da_block_hash: Da::SlotHash,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<TransitionHeight>> {
+        Ok(self.get_height_by_da_hash(&da_block_hash, working_set))
+    }
 }
@@ -1,13 +1,49 @@
+use sov_mock_da::{MockBlockHeader, MockDaSpec, MockHash, MockValidityCond};
 use sov_modules_api::da::{NanoSeconds, Time};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::prelude::*;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    Context, Event, KernelModule, KernelWorkingSet, Migration, ModuleError, ModuleInfo, Spec,
+    StateValue, WorkingSet,
+};
+use sov_modules_core::runtime::capabilities::mocks::MockKernel;
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
 
-use crate::ChainStateConfig;
+use crate::fee_market::next_base_fee;
+use crate::{
+    CallMessage, ChainState, ChainStateConfig, ChainStateConfigError, ChainStateError,
+    ChainStateExport, ChainStateInconsistency, MigrationError, StateTransitionId,
+    UpgradeDescriptor, UpgradeError, MAX_AGGREGATED_TRANSITIONS,
+};
+
+type C = DefaultContext;
+
+fn header(height: u64) -> MockBlockHeader {
+    MockBlockHeader {
+        prev_hash: [height as u8; 32].into(),
+        hash: [height as u8 + 1; 32].into(),
+        height,
+        time: Time::now(),
+    }
+}
 
 #[test]
 fn test_config_serialization() {
     let time = Time::new(2, NanoSeconds::new(3).unwrap());
-    let config = ChainStateConfig {
+    let config = ChainStateConfig::<C, MockDaSpec> {
         initial_slot_height: 1,
         current_time: time,
+        chain_id: 4321,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
     };
 
     let data = r#"
@@ -16,9 +52,1094 @@ fn test_config_serialization() {
         "current_time":{
             "secs":2,
             "nanos":3
-        }
+        },
+        "chain_id":4321
     }"#;
 
-    let parsed_config: ChainStateConfig = serde_json::from_str(data).unwrap();
+    let parsed_config: ChainStateConfig<C, MockDaSpec> = serde_json::from_str(data).unwrap();
     assert_eq!(config, parsed_config)
 }
+
+#[test]
+fn test_config_validation() {
+    let mut config = ChainStateConfig::<C, MockDaSpec> {
+        initial_slot_height: 1,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: Some(MockHash([1; 32])),
+        initial_validity_condition: Some(MockValidityCond::default()),
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    assert_eq!(config.validate(), Ok(()));
+
+    config.initial_validity_condition = None;
+    assert_eq!(
+        config.validate(),
+        Err(ChainStateConfigError::IncompleteGenesisDaBlock)
+    );
+
+    config.genesis_da_hash = None;
+    config.initial_slot_height = u64::MAX;
+    assert_eq!(
+        config.validate(),
+        Err(ChainStateConfigError::InitialSlotHeightTooLarge(u64::MAX))
+    );
+
+    config.initial_slot_height = 1;
+    config.transition_retention = Some(0);
+    assert_eq!(
+        config.validate(),
+        Err(ChainStateConfigError::ZeroTransitionRetention)
+    );
+}
+
+#[test]
+fn test_begin_slot_hook_requires_genesis() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(storage);
+
+    assert_eq!(
+        chain_state.begin_slot_hook(
+            &header(1),
+            &MockValidityCond::default(),
+            &root,
+            &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+        ),
+        Err(ChainStateError::SlotHeightNotInitialized)
+    );
+}
+
+#[test]
+fn test_begin_slot_hook_overflow() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: u64::MAX - 1,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let kernel = MockKernel::<C, MockDaSpec>::new(u64::MAX - 1, u64::MAX - 1);
+    let mut working_set = WorkingSet::new(storage);
+    let mut working_set = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+
+    chain_state
+        .begin_slot_hook(
+            &header(1),
+            &MockValidityCond::default(),
+            &root,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(chain_state.true_slot_height(working_set.inner), u64::MAX);
+
+    assert_eq!(
+        chain_state.begin_slot_hook(
+            &header(2),
+            &MockValidityCond::default(),
+            &root,
+            &mut working_set
+        ),
+        Err(ChainStateError::SlotHeightOverflow(u64::MAX))
+    );
+}
+
+#[test]
+fn test_update_visible_slot_height() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 5,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    assert_eq!(chain_state.visible_slot_height(&mut working_set), 5);
+
+    let kernel = MockKernel::<C, MockDaSpec>::new(5, 5);
+    let mut working_set = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+    chain_state
+        .increment_true_slot_height(&mut working_set)
+        .unwrap();
+    chain_state
+        .increment_true_slot_height(&mut working_set)
+        .unwrap();
+
+    assert_eq!(
+        chain_state.update_visible_slot_height(8, &mut working_set),
+        Err(ChainStateError::VisibleSlotHeightAheadOfTrueHeight {
+            visible_height: 8,
+            true_height: 7,
+        })
+    );
+
+    chain_state
+        .update_visible_slot_height(6, &mut working_set)
+        .unwrap();
+    assert_eq!(chain_state.visible_slot_height(working_set.inner), 6);
+    assert_eq!(chain_state.true_slot_height(working_set.inner), 7);
+
+    assert_eq!(
+        chain_state.update_visible_slot_height(5, &mut working_set),
+        Err(ChainStateError::VisibleSlotHeightDecreased {
+            current: 6,
+            requested: 5,
+        })
+    );
+}
+
+#[test]
+fn test_next_base_fee() {
+    // No target, or the target is met: the base fee doesn't change
+    assert_eq!(next_base_fee(1000, 500, 0), 1000);
+    assert_eq!(next_base_fee(1000, 500, 500), 1000);
+
+    // Full slots raise the base fee by 1/8th, empty ones lower it by 1/8th
+    assert_eq!(next_base_fee(1000, 1000, 500), 1125);
+    assert_eq!(next_base_fee(1000, 0, 500), 875);
+
+    // Slots using more than twice the target don't raise the base fee by more than 1/8th
+    assert_eq!(next_base_fee(1000, 1500, 500), 1125);
+    assert_eq!(next_base_fee(1000, 500_000, 500), 1125);
+    assert_eq!(next_base_fee(1000, u64::MAX, 500), 1125);
+
+    // A zero base fee can still grow
+    assert_eq!(next_base_fee(0, 1000, 500), 1);
+    assert_eq!(next_base_fee(u64::MAX, u64::MAX, 1), u64::MAX);
+}
+
+#[test]
+fn test_upgrade_activation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let authority = generate_address::<C>("authority");
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 1,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: Some(authority),
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    let upgrade = UpgradeDescriptor {
+        protocol_version: 2,
+        name: "v2".to_string(),
+    };
+    let schedule = |activation_height| CallMessage::ScheduleUpgrade {
+        activation_height,
+        upgrade: upgrade.clone(),
+    };
+
+    let intruder = C::new(generate_address::<C>("intruder"), authority, 0);
+    let err = chain_state
+        .call(schedule(2), &intruder, &mut working_set)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<UpgradeError>().unwrap(),
+        UpgradeError::Unauthorized
+    );
+
+    let context = C::new(authority, authority, 0);
+    let err = chain_state
+        .call(schedule(0), &context, &mut working_set)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<UpgradeError>().unwrap(),
+        UpgradeError::ActivationHeightNotInFuture {
+            activation_height: 0,
+            current_height: 0,
+        }
+    );
+
+    chain_state
+        .call(schedule(2), &context, &mut working_set)
+        .unwrap();
+    assert_eq!(chain_state.get_upgrade(2, &mut working_set), Some(upgrade));
+
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+
+    for height in 1..=2 {
+        let mut working_set = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut working_set,
+            )
+            .unwrap();
+        assert_eq!(
+            chain_state.is_protocol_version_active(2, working_set.inner),
+            height == 2
+        );
+    }
+    assert_eq!(chain_state.get_protocol_version(&mut working_set), 2);
+}
+
+/// A module whose state went through two layout changes: the value was multiplied by 10, then incremented.
+#[derive(ModuleInfo)]
+struct VersionedModule<C: Context> {
+    #[address]
+    address: C::Address,
+
+    #[state]
+    value: StateValue<u64>,
+}
+
+impl<C: Context> Migration for VersionedModule<C> {
+    const VERSION: u64 = 2;
+
+    fn migrate(
+        &self,
+        from_version: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ModuleError> {
+        let value = self.value.get_or_err(working_set)?;
+        let migrated = match from_version {
+            0 => value * 10,
+            _ => value + 1,
+        };
+        self.value.set(&migrated, working_set);
+        working_set.add_event("migrate", &format!("value: {migrated}"));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_module_migration() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let authority = generate_address::<C>("authority");
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let module = VersionedModule::<C>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 1,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: Some(authority),
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    module.value.set(&4, &mut working_set);
+    chain_state
+        .call(
+            CallMessage::ScheduleUpgrade {
+                activation_height: 2,
+                upgrade: UpgradeDescriptor {
+                    protocol_version: 2,
+                    name: "v2".to_string(),
+                },
+            },
+            &C::new(authority, authority, 0),
+            &mut working_set,
+        )
+        .unwrap();
+
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    // The dry run reports the migration without changing the state
+    let dry_run = chain_state
+        .dry_run_migration(&module, storage.clone())
+        .unwrap();
+    assert_eq!((dry_run.from_version, dry_run.to_version), (0, 2));
+    assert_eq!(dry_run.events.len(), 4);
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    assert_eq!(module.value.get(&mut working_set), Some(4));
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+
+    // The migration only runs at the activation slot of the upgrade
+    for height in 1..=3 {
+        let mut kernel_working_set = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut kernel_working_set,
+            )
+            .unwrap();
+        chain_state
+            .migrate_module(&module, &mut working_set)
+            .unwrap();
+        let expected = if height < 2 { (0, 4) } else { (2, 41) };
+        assert_eq!(
+            (
+                chain_state.get_module_version(module.address(), &mut working_set),
+                module.value.get(&mut working_set).unwrap()
+            ),
+            expected
+        );
+    }
+
+    // A node running outdated code can't migrate the state backwards
+    chain_state
+        .module_versions
+        .set(module.address(), &3, &mut working_set);
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    storage.validate_and_commit(reads_writes, &witness).unwrap();
+    assert!(matches!(
+        chain_state.dry_run_migration(&module, storage),
+        Err(MigrationError::StateAhead {
+            state_version: 3,
+            code_version: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_transition_membership_proofs() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let transition = |height: u64| {
+        StateTransitionId::new(MockHash([height as u8; 32]), root, Default::default())
+    };
+
+    for height in 1..=7 {
+        chain_state.store_state_transition(height, transition(height), &mut working_set);
+    }
+    assert_eq!(chain_state.transition_accumulator_size(&mut working_set), 7);
+
+    let accumulator_root = chain_state.transition_accumulator_root(&mut working_set);
+    for height in 1..=7 {
+        let proof = chain_state
+            .prove_transition_membership(height, &mut working_set)
+            .unwrap();
+        ChainState::<C, MockDaSpec>::verify_transition_membership(
+            &accumulator_root,
+            height,
+            &transition(height),
+            &proof,
+        )
+        .unwrap();
+
+        // The proof doesn't hold for another transition, nor for another height
+        assert!(ChainState::<C, MockDaSpec>::verify_transition_membership(
+            &accumulator_root,
+            height,
+            &transition(height + 1),
+            &proof,
+        )
+        .is_err());
+        assert!(ChainState::<C, MockDaSpec>::verify_transition_membership(
+            &accumulator_root,
+            height + 1,
+            &transition(height),
+            &proof,
+        )
+        .is_err());
+    }
+
+    assert!(chain_state
+        .prove_transition_membership(8, &mut working_set)
+        .is_none());
+}
+
+#[test]
+fn test_export_transitions() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let genesis_root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    let transition = |height: u64| {
+        StateTransitionId::new(
+            MockHash([height as u8; 32]),
+            genesis_root,
+            Default::default(),
+        )
+    };
+    for height in 1..=5 {
+        chain_state.store_state_transition(height, transition(height), &mut working_set);
+    }
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let mut working_set = WorkingSet::new(storage);
+    let export = chain_state
+        .export_transitions(2, 4, &mut working_set)
+        .unwrap();
+    let accumulator_root_proof =
+        chain_state.get_transition_accumulator_root_with_proof(&mut working_set);
+
+    // The export survives a round trip through a file
+    let path = tmpdir.path().join("transitions.json");
+    export.write_to_file(&path).unwrap();
+    let imported = ChainStateExport::<C, MockDaSpec>::read_from_file(&path).unwrap();
+    assert_eq!(imported, export);
+
+    let transitions = chain_state
+        .verify_transitions_export(root, accumulator_root_proof.clone(), imported)
+        .unwrap();
+    assert_eq!(
+        transitions
+            .iter()
+            .map(|exported| (exported.height, exported.transition.clone()))
+            .collect::<Vec<_>>(),
+        (2..=4)
+            .map(|height| (height, transition(height)))
+            .collect::<Vec<_>>()
+    );
+
+    // Tampering with an exported transition is detected
+    let mut tampered = export.clone();
+    tampered.transitions[1].transition = transition(7);
+    assert!(chain_state
+        .verify_transitions_export(root, accumulator_root_proof.clone(), tampered)
+        .is_err());
+
+    // So is an export which doesn't match the state root
+    let mut tampered = export;
+    tampered.accumulator_root = [1; 32];
+    assert!(chain_state
+        .verify_transitions_export(root, accumulator_root_proof, tampered)
+        .is_err());
+
+    // Transitions which aren't stored can't be exported
+    assert!(chain_state
+        .export_transitions(4, 6, &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn test_validate_consistency() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    assert_eq!(
+        chain_state.validate_consistency(&mut working_set),
+        Err(ChainStateInconsistency::NotInitialized)
+    );
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    assert_eq!(chain_state.validate_consistency(&mut working_set), Ok(()));
+
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    for height in 0..4 {
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .unwrap();
+    }
+    assert_eq!(chain_state.validate_consistency(&mut working_set), Ok(()));
+
+    // Drop the index entry of a transition
+    let transition = chain_state
+        .get_historical_transitions(2, &mut working_set)
+        .unwrap();
+    chain_state
+        .heights_by_da_hash
+        .delete(transition.da_block_hash(), &mut working_set);
+    assert_eq!(
+        chain_state.validate_consistency(&mut working_set),
+        Err(ChainStateInconsistency::DaHashIndexMismatch {
+            height: 2,
+            indexed_height: None,
+        })
+    );
+
+    // The first inconsistency is reported
+    chain_state
+        .historical_transitions
+        .delete(&1, &mut working_set);
+    assert_eq!(
+        chain_state.validate_consistency(&mut working_set),
+        Err(ChainStateInconsistency::MissingTransition(1))
+    );
+}
+
+#[test]
+fn test_pruning_past_transition_retention() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: Some(2),
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let mut working_set = WorkingSet::new(storage);
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    for height in 1..=6 {
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .unwrap();
+        chain_state.record_events_root([height as u8; 32], &mut working_set);
+    }
+
+    // The transitions of the slots 4 and 5 are retained, along with everything recorded for their heights
+    assert_eq!(chain_state.earliest_retained_height(&mut working_set), 4);
+    let address = generate_address::<C>("sender");
+    for height in 0..=5 {
+        let retained = height >= 4;
+        let da_hash = header(height).hash;
+        assert_eq!(
+            chain_state
+                .get_historical_transitions(height, &mut working_set)
+                .is_some(),
+            retained
+        );
+        assert_eq!(
+            chain_state.state_root_at(height, &mut working_set),
+            retained.then_some(root)
+        );
+        assert_eq!(
+            chain_state.get_height_by_da_hash(&da_hash, &mut working_set),
+            retained.then_some(height)
+        );
+        assert_eq!(
+            chain_state
+                .get_events_root(height, &mut working_set)
+                .is_some(),
+            retained
+        );
+        let mut versioned_working_set = working_set.versioned_state(&C::new(address, address, 6));
+        assert_eq!(
+            chain_state
+                .time_at(height, &mut versioned_working_set)
+                .is_some(),
+            retained
+        );
+    }
+    assert_eq!(chain_state.validate_consistency(&mut working_set), Ok(()));
+}
+
+struct RpcStorage {
+    storage: <C as Spec>::Storage,
+}
+
+impl ChainStateRpcImpl<C, MockDaSpec> for RpcStorage {
+    fn get_working_set(&self) -> WorkingSet<C> {
+        WorkingSet::new(self.storage.clone())
+    }
+}
+
+#[test]
+fn test_rpc_queries() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: Some(2),
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let rpc = RpcStorage {
+        storage: storage.clone(),
+    };
+
+    // Before the first slot, there is no genesis hash and no transition yet
+    assert_eq!(
+        <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::slot_height(&rpc).unwrap(),
+        0
+    );
+    assert_eq!(
+        <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::genesis_hash(&rpc).unwrap(),
+        None
+    );
+    assert_eq!(
+        <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::transition_at(&rpc, 0).unwrap(),
+        None
+    );
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    for height in 1..=6 {
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .unwrap();
+    }
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    assert_eq!(
+        <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::slot_height(&rpc).unwrap(),
+        6
+    );
+    assert_eq!(
+        <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::genesis_hash(&rpc).unwrap(),
+        Some(root)
+    );
+
+    // The transitions of the slots 4 and 5 are retained, the earlier ones are pruned
+    for height in 0..=5 {
+        let transition =
+            <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::transition_at(&rpc, height)
+                .unwrap();
+        if height >= 4 {
+            let transition = transition.unwrap();
+            assert_eq!(transition.da_block_hash(), &header(height).hash);
+            assert_eq!(transition.post_state_root(), &root);
+        } else {
+            assert_eq!(transition, None);
+        }
+    }
+
+    // The current slot is not completed yet, and later heights never existed
+    for height in [6, 100, u64::MAX] {
+        assert_eq!(
+            <RpcStorage as ChainStateRpcServer<C, MockDaSpec>>::transition_at(&rpc, height)
+                .unwrap(),
+            None
+        );
+    }
+}
+
+#[test]
+fn test_time_at() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let time = |secs| Time::new(secs, NanoSeconds::new(0).unwrap());
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: time(1),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let mut working_set = WorkingSet::new(storage);
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    for height in 1..=3 {
+        let header = MockBlockHeader {
+            time: time(height as i64 * 10),
+            ..header(height)
+        };
+        chain_state
+            .begin_slot_hook(
+                &header,
+                &MockValidityCond::default(),
+                &root,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .unwrap();
+    }
+
+    // Seen from the slot at height 2, the times of the past and current slots are visible, but not the next one
+    let address = generate_address::<C>("sender");
+    let mut versioned_working_set = working_set.versioned_state(&C::new(address, address, 2));
+    assert_eq!(
+        chain_state.time_at(0, &mut versioned_working_set),
+        Some(time(1))
+    );
+    assert_eq!(
+        chain_state.time_at(1, &mut versioned_working_set),
+        Some(time(10))
+    );
+    assert_eq!(
+        chain_state.time_at(2, &mut versioned_working_set),
+        Some(time(20))
+    );
+    assert_eq!(chain_state.time_at(3, &mut versioned_working_set), None);
+    assert_eq!(chain_state.get_time(&mut versioned_working_set), time(20));
+}
+
+#[test]
+fn test_slot_events() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        chain_id: 0,
+        protocol_version: 0,
+        genesis_da_hash: None,
+        initial_validity_condition: None,
+        transition_retention: None,
+        accumulate_pruned_transitions: false,
+        fee_market: Default::default(),
+        upgrade_authority: None,
+        module_versions: vec![],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let mut working_set = WorkingSet::new(storage);
+    let kernel = MockKernel::<C, MockDaSpec>::default();
+    let mut begin_slot = |height| {
+        chain_state
+            .begin_slot_hook(
+                &header(height),
+                &MockValidityCond::default(),
+                &root,
+                &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+            )
+            .unwrap();
+        working_set.take_events()
+    };
+
+    // The first slot has no previous transition to store
+    assert_eq!(
+        begin_slot(1),
+        vec![Event::new(
+            "increment_slot_height",
+            "previous_height: 0, height: 1"
+        )]
+    );
+    assert_eq!(
+        begin_slot(2),
+        vec![
+            Event::new(
+                "store_state_transition",
+                &format!(
+                    "height: 1, pre_state_root: {}, post_state_root: {}, validity_condition_digest: {}",
+                    hex::encode(root),
+                    hex::encode(root),
+                    hex::encode(ChainState::<C, MockDaSpec>::validity_condition_digest(
+                        &MockValidityCond::default()
+                    )),
+                )
+            ),
+            Event::new("increment_slot_height", "previous_height: 1, height: 2"),
+        ]
+    );
+}
+
+#[test]
+fn test_historical_transition_proofs() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let genesis_root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    let transition = |height: u64| {
+        StateTransitionId::new(
+            MockHash([height as u8; 32]),
+            genesis_root,
+            Default::default(),
+        )
+    };
+    for height in 1..=2 {
+        chain_state.store_state_transition(height, transition(height), &mut working_set);
+    }
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+
+    let mut working_set = WorkingSet::new(storage);
+    let proof = |height, working_set: &mut WorkingSet<C>| {
+        chain_state.get_historical_transition_with_proof(height, working_set)
+    };
+
+    // Stored transitions, and the absence of the others, are proven against the state root
+    for height in 1..=2 {
+        assert_eq!(
+            chain_state
+                .verify_historical_transition_proof(height, root, proof(height, &mut working_set))
+                .unwrap(),
+            Some(transition(height))
+        );
+    }
+    assert_eq!(
+        chain_state
+            .verify_historical_transition_proof(3, root, proof(3, &mut working_set))
+            .unwrap(),
+        None
+    );
+
+    // A proof doesn't hold for another height, nor against another state root
+    assert!(chain_state
+        .verify_historical_transition_proof(2, root, proof(1, &mut working_set))
+        .is_err());
+    assert!(chain_state
+        .verify_historical_transition_proof(1, genesis_root, proof(1, &mut working_set))
+        .is_err());
+
+    // Tampering with the proven value is detected
+    let mut tampered = proof(1, &mut working_set);
+    tampered.value = proof(2, &mut working_set).value;
+    assert!(chain_state
+        .verify_historical_transition_proof(1, root, tampered)
+        .is_err());
+    let mut tampered = proof(1, &mut working_set);
+    tampered.value = None;
+    assert!(chain_state
+        .verify_historical_transition_proof(1, root, tampered)
+        .is_err());
+}
+
+#[test]
+fn test_height_by_da_hash() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    let da_hash = |height: u64| MockHash([height as u8; 32]);
+    for height in 1..=3 {
+        chain_state.store_state_transition(
+            height,
+            StateTransitionId::new(da_hash(height), root, Default::default()),
+            &mut working_set,
+        );
+    }
+    for height in 1..=3 {
+        assert_eq!(
+            chain_state.get_height_by_da_hash(&da_hash(height), &mut working_set),
+            Some(height)
+        );
+    }
+    assert_eq!(
+        chain_state.get_height_by_da_hash(&da_hash(4), &mut working_set),
+        None
+    );
+
+    // Only the most recent transition is retained
+    chain_state.transition_retention.set(&1, &mut working_set);
+    let kernel = MockKernel::<C, MockDaSpec>::new(4, 4);
+    chain_state.prune_historical_transitions(
+        3,
+        &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+    );
+    for height in 1..=3 {
+        assert_eq!(
+            chain_state.get_height_by_da_hash(&da_hash(height), &mut working_set),
+            (height == 3).then_some(height)
+        );
+    }
+}
+
+#[test]
+fn test_state_root_at() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let first_root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+    chain_state.chain_id.set(&1, &mut working_set);
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let second_root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    assert_ne!(first_root, second_root);
+
+    let mut working_set = WorkingSet::new(storage);
+    for (height, root) in [(1, first_root), (2, second_root)] {
+        assert_eq!(chain_state.state_root_at(height, &mut working_set), None);
+        chain_state.store_state_transition(
+            height,
+            StateTransitionId::new(MockHash([height as u8; 32]), root, Default::default()),
+            &mut working_set,
+        );
+    }
+    assert_eq!(
+        chain_state.state_root_at(1, &mut working_set),
+        Some(first_root)
+    );
+    assert_eq!(
+        chain_state.state_root_at(2, &mut working_set),
+        Some(second_root)
+    );
+    assert_eq!(chain_state.state_root_at(3, &mut working_set), None);
+
+    // The roots are pruned with the transitions
+    chain_state.transition_retention.set(&1, &mut working_set);
+    let kernel = MockKernel::<C, MockDaSpec>::new(3, 3);
+    chain_state.prune_historical_transitions(
+        2,
+        &mut KernelWorkingSet::from_kernel(&kernel, &mut working_set),
+    );
+    assert_eq!(chain_state.state_root_at(1, &mut working_set), None);
+    assert_eq!(
+        chain_state.state_root_at(2, &mut working_set),
+        Some(second_root)
+    );
+}
+
+#[test]
+fn test_aggregate_validity_conditions() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let (reads_writes, witness) = WorkingSet::<C>::new(storage.clone()).checkpoint().freeze();
+    let root = storage.validate_and_commit(reads_writes, &witness).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    // Only the transition at height 3 is invalid
+    let chain_state = ChainState::<C, MockDaSpec>::default();
+    for height in 1..=4 {
+        chain_state.store_state_transition(
+            height,
+            StateTransitionId::new(
+                MockHash([height as u8; 32]),
+                root,
+                MockValidityCond {
+                    is_valid: height != 3,
+                },
+            ),
+            &mut working_set,
+        );
+    }
+
+    let aggregate = |start, end, working_set: &mut WorkingSet<C>| {
+        chain_state.aggregate_validity_conditions(start, end, working_set)
+    };
+    assert_eq!(
+        aggregate(2, 2, &mut working_set).unwrap(),
+        MockValidityCond { is_valid: true }
+    );
+    assert_eq!(
+        aggregate(1, 2, &mut working_set).unwrap(),
+        MockValidityCond { is_valid: true }
+    );
+    assert_eq!(
+        aggregate(2, 4, &mut working_set).unwrap(),
+        MockValidityCond { is_valid: false }
+    );
+
+    // Empty ranges, ranges with a missing transition and too long ranges are rejected
+    assert!(aggregate(3, 2, &mut working_set).is_err());
+    assert!(aggregate(4, 5, &mut working_set).is_err());
+    assert!(aggregate(0, 1, &mut working_set).is_err());
+    // The length of the range is checked before any transition is read
+    assert!(
+        aggregate(1, MAX_AGGREGATED_TRANSITIONS + 1, &mut working_set)
+            .unwrap_err()
+            .to_string()
+            .contains("is longer than")
+    );
+}
@@ -0,0 +1,137 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+use thiserror::Error;
+
+use crate::{ChainState, TransitionHeight};
+
+/// A protocol upgrade scheduled to activate at a given slot height.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeDescriptor {
+    /// The protocol version the rollup runs once the upgrade is active
+    pub protocol_version: u64,
+    /// A human-readable name for the upgrade
+    pub name: String,
+}
+
+/// The errors that might arise when scheduling a protocol upgrade.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum UpgradeError {
+    /// No upgrade authority was configured at genesis, or the sender isn't the authority.
+    #[error("Only the upgrade authority can schedule upgrades")]
+    Unauthorized,
+    /// Upgrades can only be scheduled for future slots.
+    #[error("The activation height {activation_height} must be greater than the current height {current_height}")]
+    ActivationHeightNotInFuture {
+        /// The requested activation height
+        activation_height: TransitionHeight,
+        /// The current true slot height
+        current_height: TransitionHeight,
+    },
+    /// Another upgrade is already scheduled at this height.
+    #[error("An upgrade is already scheduled at height {0}")]
+    AlreadyScheduled(TransitionHeight),
+    /// Upgrades must move the protocol version forward.
+    #[error("The upgrade version {requested} must be greater than the current protocol version {current}")]
+    VersionNotIncreasing {
+        /// The current protocol version
+        current: u64,
+        /// The protocol version of the upgrade
+        requested: u64,
+    },
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Returns the upgrade scheduled to activate at `height`, if any.
+    pub fn get_upgrade(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<UpgradeDescriptor> {
+        self.upgrades.get(&height, working_set)
+    }
+
+    /// Returns the address allowed to schedule upgrades, if one was configured at genesis.
+    pub fn get_upgrade_authority(&self, working_set: &mut WorkingSet<C>) -> Option<C::Address> {
+        self.upgrade_authority.get(working_set)
+    }
+
+    /// Returns true if the rollup runs at least the protocol `version`.
+    pub fn is_protocol_version_active(
+        &self,
+        version: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.get_protocol_version(working_set) >= version
+    }
+
+    /// Schedules `upgrade` to activate at `activation_height`. Only the upgrade authority may schedule upgrades.
+    pub(crate) fn schedule_upgrade(
+        &self,
+        activation_height: TransitionHeight,
+        upgrade: UpgradeDescriptor,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), UpgradeError> {
+        if self.upgrade_authority.get(working_set).as_ref() != Some(context.sender()) {
+            return Err(UpgradeError::Unauthorized);
+        }
+
+        let current_height = self.true_slot_height(working_set);
+        if activation_height <= current_height {
+            return Err(UpgradeError::ActivationHeightNotInFuture {
+                activation_height,
+                current_height,
+            });
+        }
+
+        if self.upgrades.get(&activation_height, working_set).is_some() {
+            return Err(UpgradeError::AlreadyScheduled(activation_height));
+        }
+
+        let current = self.get_protocol_version(working_set);
+        if upgrade.protocol_version <= current {
+            return Err(UpgradeError::VersionNotIncreasing {
+                current,
+                requested: upgrade.protocol_version,
+            });
+        }
+
+        working_set.add_event(
+            "schedule_upgrade",
+            &format!(
+                "activation_height: {activation_height}, protocol_version: {}, name: {}",
+                upgrade.protocol_version, upgrade.name
+            ),
+        );
+        self.upgrades.set(&activation_height, &upgrade, working_set);
+
+        Ok(())
+    }
+
+    /// Switches the protocol version if an upgrade is scheduled to activate at `height`.
+    pub(crate) fn activate_upgrade(
+        &self,
+        height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let Some(upgrade) = self.upgrades.get(&height, working_set) else {
+            return;
+        };
+
+        // Upgrades scheduled at lower versions than the current one (e.g. because a later
+        // upgrade activated first) are ignored: the protocol version never goes backwards.
+        if upgrade.protocol_version > self.get_protocol_version(working_set) {
+            self.protocol_version
+                .set(&upgrade.protocol_version, working_set);
+            working_set.add_event(
+                "activate_upgrade",
+                &format!(
+                    "height: {height}, protocol_version: {}, name: {}",
+                    upgrade.protocol_version, upgrade.name
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{bail, Result};
+use sov_modules_api::{CallResponse, Context, StateValueAccessor, WorkingSet};
+
+use crate::events::{AuthorityTransferred, ModulePaused, ModuleUnpaused};
+use crate::CircuitBreaker;
+
+/// This enumeration represents the available call messages for interacting with the sov-circuit-breaker module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CliWalletArg),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Pauses the call messages of a module. Only the authority can pause a module.
+    Pause {
+        /// The address of the module.
+        module: C::Address,
+    },
+    /// Unpauses the call messages of a module. Only the authority can unpause a module.
+    Unpause {
+        /// The address of the module.
+        module: C::Address,
+    },
+    /// Transfers the authority to a new address.
+    TransferAuthority {
+        /// The new authority.
+        new_authority: C::Address,
+    },
+}
+
+impl<C: Context> CircuitBreaker<C> {
+    /// Pauses the call messages of `module`.
+    pub(crate) fn pause(
+        &self,
+        module: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_authority(context, working_set)?;
+        if module == self.address {
+            bail!("The circuit breaker can't be paused")
+        }
+
+        let mut paused_modules = self.paused_modules.get(working_set).unwrap_or_default();
+        if paused_modules.contains(&module) {
+            bail!("Module {} is already paused", module)
+        }
+        paused_modules.push(module.clone());
+        self.paused_modules.set(&paused_modules, working_set);
+
+        working_set.emit_event(&ModulePaused::<C> { module });
+        Ok(CallResponse::default())
+    }
+
+    /// Unpauses the call messages of `module`.
+    pub(crate) fn unpause(
+        &self,
+        module: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_authority(context, working_set)?;
+
+        let mut paused_modules = self.paused_modules.get(working_set).unwrap_or_default();
+        let Some(index) = paused_modules.iter().position(|paused| paused == &module) else {
+            bail!("Module {} isn't paused", module)
+        };
+        paused_modules.remove(index);
+        self.paused_modules.set(&paused_modules, working_set);
+
+        working_set.emit_event(&ModuleUnpaused::<C> { module });
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the authority to `new_authority`.
+    pub(crate) fn transfer_authority(
+        &self,
+        new_authority: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_authority(context, working_set)?;
+        self.authority.set(&new_authority, working_set);
+
+        working_set.emit_event(&AuthorityTransferred::<C> { new_authority });
+        Ok(CallResponse::default())
+    }
+
+    fn ensure_authority(&self, context: &C, working_set: &mut WorkingSet<C>) -> Result<()> {
+        let authority = self.authority.get_or_err(working_set)?;
+        if &authority != context.sender() {
+            bail!(
+                "Only the authority {} can pause and unpause modules, not {}",
+                authority,
+                context.sender()
+            )
+        }
+        Ok(())
+    }
+}
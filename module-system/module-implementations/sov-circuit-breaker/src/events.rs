@@ -0,0 +1,29 @@
+//! The typed events emitted by the circuit breaker module.
+use sov_modules_api::ModuleEvent;
+
+/// Emitted when the authority pauses the call messages of `module`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "module_paused")]
+pub struct ModulePaused<C: sov_modules_api::Context> {
+    /// The address of the paused module.
+    #[indexed]
+    pub module: C::Address,
+}
+
+/// Emitted when the authority unpauses the call messages of `module`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "module_unpaused")]
+pub struct ModuleUnpaused<C: sov_modules_api::Context> {
+    /// The address of the unpaused module.
+    #[indexed]
+    pub module: C::Address,
+}
+
+/// Emitted when the authority transfers its role to `new_authority`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "authority_transferred")]
+pub struct AuthorityTransferred<C: sov_modules_api::Context> {
+    /// The new authority.
+    #[indexed]
+    pub new_authority: C::Address,
+}
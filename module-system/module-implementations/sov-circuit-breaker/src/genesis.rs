@@ -0,0 +1,33 @@
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::CircuitBreaker;
+
+/// Initial configuration for sov-circuit-breaker module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct CircuitBreakerConfig<C: sov_modules_api::Context> {
+    /// The address allowed to pause and unpause modules.
+    pub authority: C::Address,
+    /// The addresses of the modules paused at genesis.
+    #[serde(default)]
+    pub paused_modules: Vec<C::Address>,
+}
+
+impl<C: sov_modules_api::Context> CircuitBreaker<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if config.paused_modules.contains(&self.address) {
+            bail!("The circuit breaker can't be paused")
+        }
+        self.authority.set(&config.authority, working_set);
+        self.paused_modules.set(&config.paused_modules, working_set);
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::{
+    CallResponse, Error, ModuleInfo, StateValue, StateValueAccessor, WorkingSet,
+};
+
+/// The `sov-circuit-breaker` module lets an authority halt the call messages of other modules of the runtime,
+/// e.g. after a bug is found in one of them. It provides functionality for:
+/// - Pausing and unpausing the call messages of a module.
+/// - Transferring the authority, e.g. to a multisig or a governance module.
+///
+/// The runtime checks [`CircuitBreaker::is_paused`] before dispatching each call message. Queries and the
+/// hooks of paused modules keep working, and the call messages of the circuit breaker itself can't be paused.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct CircuitBreaker<C: sov_modules_api::Context> {
+    /// The address of the sov-circuit-breaker module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address allowed to pause and unpause modules.
+    #[state]
+    pub(crate) authority: StateValue<C::Address>,
+
+    /// The addresses of the paused modules.
+    #[state]
+    pub(crate) paused_modules: StateValue<Vec<C::Address>>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for CircuitBreaker<C> {
+    type Context = C;
+
+    type Config = CircuitBreakerConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Pause { module } => Ok(self.pause(module, context, working_set)?),
+
+            call::CallMessage::Unpause { module } => {
+                Ok(self.unpause(module, context, working_set)?)
+            }
+
+            call::CallMessage::TransferAuthority { new_authority } => {
+                Ok(self.transfer_authority(new_authority, context, working_set)?)
+            }
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> CircuitBreaker<C> {
+    /// Returns true if the call messages of `module` are paused.
+    pub fn is_paused(&self, module: &C::Address, working_set: &mut WorkingSet<C>) -> bool {
+        self.paused_modules
+            .get(working_set)
+            .unwrap_or_default()
+            .contains(module)
+    }
+}
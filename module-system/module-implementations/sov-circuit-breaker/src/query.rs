@@ -0,0 +1,34 @@
+//! Defines rpc queries exposed by the circuit breaker module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateValueAccessor, WorkingSet};
+
+use crate::CircuitBreaker;
+
+/// Structure returned by the `getPausedModules` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct PausedModulesResponse<C: sov_modules_api::Context> {
+    /// The authority allowed to pause and unpause modules.
+    pub authority: Option<C::Address>,
+    /// The addresses of the paused modules.
+    pub paused_modules: Vec<C::Address>,
+}
+
+#[rpc_gen(client, server, namespace = "circuitBreaker")]
+impl<C: sov_modules_api::Context> CircuitBreaker<C> {
+    #[rpc_method(name = "getPausedModules")]
+    /// Rpc method that returns the paused modules and the authority.
+    pub fn paused_modules(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<PausedModulesResponse<C>> {
+        Ok(PausedModulesResponse {
+            authority: self.authority.get(working_set),
+            paused_modules: self.paused_modules.get(working_set).unwrap_or_default(),
+        })
+    }
+}
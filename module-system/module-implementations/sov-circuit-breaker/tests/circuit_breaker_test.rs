@@ -0,0 +1,94 @@
+use sov_circuit_breaker::{CallMessage, CircuitBreaker, CircuitBreakerConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, ModuleInfo, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+fn context(sender: Address) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), 1)
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+    let authority = generate_address::<C>("authority");
+    let new_authority = generate_address::<C>("new_authority");
+    let bank = generate_address::<C>("bank");
+    let circuit_breaker = CircuitBreaker::<C>::default();
+    circuit_breaker
+        .genesis(
+            &CircuitBreakerConfig {
+                authority,
+                paused_modules: vec![],
+            },
+            working_set,
+        )
+        .unwrap();
+    assert!(!circuit_breaker.is_paused(&bank, working_set));
+
+    // Only the authority can pause a module
+    circuit_breaker
+        .call(
+            CallMessage::Pause { module: bank },
+            &context(new_authority),
+            working_set,
+        )
+        .unwrap_err();
+    circuit_breaker
+        .call(
+            CallMessage::Pause { module: bank },
+            &context(authority),
+            working_set,
+        )
+        .unwrap();
+    assert!(circuit_breaker.is_paused(&bank, working_set));
+    circuit_breaker
+        .call(
+            CallMessage::Pause { module: bank },
+            &context(authority),
+            working_set,
+        )
+        .expect_err("The module is already paused");
+
+    // The circuit breaker can't pause itself, so that modules can always be unpaused
+    circuit_breaker
+        .call(
+            CallMessage::Pause {
+                module: *circuit_breaker.address(),
+            },
+            &context(authority),
+            working_set,
+        )
+        .unwrap_err();
+
+    circuit_breaker
+        .call(
+            CallMessage::TransferAuthority { new_authority },
+            &context(authority),
+            working_set,
+        )
+        .unwrap();
+    circuit_breaker
+        .call(
+            CallMessage::Unpause { module: bank },
+            &context(authority),
+            working_set,
+        )
+        .expect_err("The previous authority can't unpause modules");
+    circuit_breaker
+        .call(
+            CallMessage::Unpause { module: bank },
+            &context(new_authority),
+            working_set,
+        )
+        .unwrap();
+    assert!(!circuit_breaker.is_paused(&bank, working_set));
+
+    let response = circuit_breaker.paused_modules(working_set).unwrap();
+    assert_eq!(response.authority, Some(new_authority));
+    assert!(response.paused_modules.is_empty());
+}
@@ -0,0 +1,312 @@
+use anyhow::{bail, Context as _, Result};
+use sov_bank::{Amount, Coins};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, DispatchCall, WorkingSet};
+
+use crate::{
+    Governance, GovernanceParams, Proposal, ProposalId, ProposalStatus, Tally, Vote, VoteOption,
+    BASIS_POINTS,
+};
+
+/// The maximum number of proposals being voted on or waiting for their execution at the same time.
+pub const MAX_ACTIVE_PROPOSALS: usize = 32;
+
+/// This enumeration represents the available call messages for interacting with the sov-governance module.
+#[cfg_attr(feature = "native", derive(CliWalletArg), derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Submits a runtime call to the vote of the governance token holders. The deposit is locked until
+    /// the end of the voting period, and burnt if the proposal doesn't reach the quorum.
+    Submit {
+        /// The serialized runtime call.
+        call: Vec<u8>,
+        /// The amount of governance tokens deposited with the proposal.
+        deposit: Amount,
+    },
+
+    /// Votes on a proposal, locking governance tokens until the end of the voting period.
+    /// Each address can vote once on a proposal, with a weight equal to the amount of locked tokens.
+    Vote {
+        /// The identifier of the proposal.
+        proposal_id: ProposalId,
+        /// The chosen option.
+        option: VoteOption,
+        /// The amount of governance tokens to lock.
+        amount: Amount,
+    },
+
+    /// Returns the tokens locked with a vote, once the voting period of the proposal is over.
+    Withdraw {
+        /// The identifier of the proposal.
+        proposal_id: ProposalId,
+    },
+
+    /// Replaces the governance parameters. Only the governance module itself can send this call,
+    /// which must therefore be submitted as a proposal.
+    UpdateParams {
+        /// The minimum amount of governance tokens deposited with a proposal.
+        min_deposit: Amount,
+        /// The number of slots during which a proposal can be voted on.
+        voting_period: u64,
+        /// The number of slots between the end of the voting period and the execution of a passed proposal.
+        execution_delay: u64,
+        /// The share of the total supply which must vote on a proposal, in basis points.
+        quorum: u64,
+        /// The share of the `Yes` votes among the `Yes` and `No` votes that a proposal must exceed to pass,
+        /// in basis points.
+        threshold: u64,
+    },
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Governance<C, Rt> {
+    /// Submits the serialized runtime `call` as a proposal, and locks the `deposit` of the sender.
+    /// Returns the identifier of the proposal.
+    pub fn submit(
+        &self,
+        call: Vec<u8>,
+        deposit: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<ProposalId> {
+        let params = self.params.get_or_err(working_set)?;
+        if deposit < params.min_deposit {
+            bail!(
+                "The deposit {} is lower than the minimum deposit {}",
+                deposit,
+                params.min_deposit
+            )
+        }
+        Rt::decode_call(&call).context("The proposed call can't be decoded by the runtime")?;
+
+        let mut active_proposals = self.active_proposals.get(working_set).unwrap_or_default();
+        if active_proposals.len() >= MAX_ACTIVE_PROPOSALS {
+            bail!(
+                "There are already {} active proposals",
+                MAX_ACTIVE_PROPOSALS
+            )
+        }
+
+        self.lock_tokens(context.sender(), deposit, working_set)
+            .context("Failed to lock the deposit")?;
+
+        let proposal_id = self.next_proposal_id.get(working_set).unwrap_or_default();
+        self.next_proposal_id.set(&(proposal_id + 1), working_set);
+
+        let voting_end = context.slot_height().saturating_add(params.voting_period);
+        let proposal = Proposal {
+            proposer: context.sender().clone(),
+            call,
+            deposit,
+            voting_end,
+            execute_at: voting_end
+                .saturating_add(params.execution_delay)
+                .saturating_add(1),
+            tally: Tally::default(),
+            status: ProposalStatus::Voting,
+        };
+        self.proposals.set(&proposal_id, &proposal, working_set);
+        active_proposals.push(proposal_id);
+        self.active_proposals.set(&active_proposals, working_set);
+
+        working_set.add_event(
+            "submit",
+            &format!(
+                "proposal_id: {}, proposer: {}, voting_end: {}",
+                proposal_id,
+                context.sender(),
+                voting_end
+            ),
+        );
+        Ok(proposal_id)
+    }
+
+    /// Votes `option` on the proposal `proposal_id`, with the weight of the `amount` of governance tokens
+    /// locked from the sender.
+    pub fn vote(
+        &self,
+        proposal_id: ProposalId,
+        option: VoteOption,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut proposal = self.get_proposal_or_err(proposal_id, working_set)?;
+        if proposal.status != ProposalStatus::Voting || context.slot_height() > proposal.voting_end
+        {
+            bail!("The voting period of proposal {} is over", proposal_id)
+        }
+        if amount == 0 {
+            bail!("A vote must lock a non-zero amount of tokens")
+        }
+
+        let key = (proposal_id, context.sender().clone());
+        if self.votes.get(&key, working_set).is_some() {
+            bail!(
+                "{} already voted on proposal {}",
+                context.sender(),
+                proposal_id
+            )
+        }
+
+        self.lock_tokens(context.sender(), amount, working_set)
+            .context("Failed to lock the vote")?;
+
+        let vote = Vote { option, amount };
+        proposal.tally.add(&vote);
+        self.proposals.set(&proposal_id, &proposal, working_set);
+        self.votes.set(&key, &vote, working_set);
+
+        working_set.add_event(
+            "vote",
+            &format!(
+                "proposal_id: {}, voter: {}, option: {:?}, amount: {}",
+                proposal_id,
+                context.sender(),
+                option,
+                amount
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the tokens that the sender locked to vote on the proposal `proposal_id`.
+    /// The tokens can be withdrawn once the proposal was tallied.
+    pub fn withdraw(
+        &self,
+        proposal_id: ProposalId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let proposal = self.get_proposal_or_err(proposal_id, working_set)?;
+        if proposal.status == ProposalStatus::Voting {
+            bail!("Proposal {} wasn't tallied yet", proposal_id)
+        }
+
+        let key = (proposal_id, context.sender().clone());
+        let vote = self.votes.get(&key, working_set).with_context(|| {
+            format!(
+                "{} has no tokens locked on proposal {}",
+                context.sender(),
+                proposal_id
+            )
+        })?;
+        self.votes.delete(&key, working_set);
+        self.release_tokens(context.sender(), vote.amount, working_set)?;
+
+        working_set.add_event(
+            "withdraw",
+            &format!(
+                "proposal_id: {}, voter: {}, amount: {}",
+                proposal_id,
+                context.sender(),
+                vote.amount
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the governance parameters. The sender must be the governance module itself,
+    /// executing a passed proposal.
+    pub fn update_params(
+        &self,
+        params: GovernanceParams,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        if context.sender() != &self.address {
+            bail!(
+                "Sender {} can't update the governance parameters, only a proposal can",
+                context.sender()
+            )
+        }
+        validate_params(&params)?;
+        self.params.set(&params, working_set);
+
+        working_set.add_event("update_params", &format!("{:?}", params));
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the proposal `proposal_id`, if it exists.
+    pub fn get_proposal(
+        &self,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Proposal<C>> {
+        self.proposals.get(&proposal_id, working_set)
+    }
+
+    /// Returns the vote of `voter` on the proposal `proposal_id`, unless its tokens were withdrawn.
+    pub fn get_vote(
+        &self,
+        proposal_id: ProposalId,
+        voter: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Vote> {
+        self.votes.get(&(proposal_id, voter.clone()), working_set)
+    }
+
+    /// Returns the governance parameters.
+    pub fn get_params(&self, working_set: &mut WorkingSet<C>) -> Option<GovernanceParams> {
+        self.params.get(working_set)
+    }
+
+    fn get_proposal_or_err(
+        &self,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Proposal<C>> {
+        self.proposals
+            .get(&proposal_id, working_set)
+            .with_context(|| format!("Proposal {} doesn't exist", proposal_id))
+    }
+
+    fn lock_tokens(
+        &self,
+        owner: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let coins = Coins {
+            amount,
+            token_address: self.token_address.get_or_err(working_set)?,
+        };
+        self.bank
+            .transfer_from(owner, &self.address, coins, working_set)
+    }
+
+    pub(crate) fn release_tokens(
+        &self,
+        owner: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let coins = Coins {
+            amount,
+            token_address: self.token_address.get_or_err(working_set)?,
+        };
+        self.bank
+            .transfer_from(&self.address, owner, coins, working_set)
+    }
+}
+
+/// Checks that the quorum and the threshold are valid ratios, and that the voting period isn't empty.
+pub(crate) fn validate_params(params: &GovernanceParams) -> Result<()> {
+    if params.voting_period == 0 {
+        bail!("The voting period must last at least one slot")
+    }
+    if params.quorum > BASIS_POINTS || params.threshold >= BASIS_POINTS {
+        bail!(
+            "The quorum must be at most {} and the threshold lower than {} basis points",
+            BASIS_POINTS,
+            BASIS_POINTS
+        )
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::call::validate_params;
+use crate::{Governance, GovernanceParams};
+
+/// Initial configuration for sov-governance module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct GovernanceConfig<C: sov_modules_api::Context> {
+    /// The address of the `sov-bank` token used for deposits and votes.
+    pub token_address: C::Address,
+    /// The initial parameters of the governance process.
+    pub params: GovernanceParams,
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Governance<C, Rt> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        validate_params(&config.params)?;
+        self.token_address.set(&config.token_address, working_set);
+        self.params.set(&config.params, working_set);
+        self.next_proposal_id.set(&0, working_set);
+        self.active_proposals.set(&vec![], working_set);
+        Ok(())
+    }
+}
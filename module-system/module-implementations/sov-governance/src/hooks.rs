@@ -0,0 +1,135 @@
+use sov_bank::Coins;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::{Governance, Proposal, ProposalId, ProposalStatus};
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Governance<C, Rt> {
+    /// Logic executed at the beginning of the slot `slot_height`, which the runtime sets to the visible slot height.
+    /// The proposals whose voting period is over are tallied, and the passed proposals whose execution height
+    /// is reached are executed, with the governance module as the sender.
+    ///
+    /// The state changes of a call are kept even if it returns an error afterwards: the proposal is then marked
+    /// as [`ProposalStatus::Failed`].
+    pub fn begin_slot_hook(&self, slot_height: u64, working_set: &mut WorkingSet<C>) {
+        let active_proposals = self.active_proposals.get(working_set).unwrap_or_default();
+        if active_proposals.is_empty() {
+            return;
+        }
+
+        let mut still_active = Vec::with_capacity(active_proposals.len());
+        let mut executable = Vec::new();
+        for proposal_id in active_proposals {
+            let Some(mut proposal) = self.proposals.get(&proposal_id, working_set) else {
+                continue;
+            };
+
+            if proposal.status == ProposalStatus::Voting && slot_height > proposal.voting_end {
+                self.tally(proposal_id, &mut proposal, working_set);
+                self.proposals.set(&proposal_id, &proposal, working_set);
+            }
+
+            match proposal.status {
+                ProposalStatus::Passed if slot_height >= proposal.execute_at => {
+                    executable.push((proposal_id, proposal))
+                }
+                ProposalStatus::Voting | ProposalStatus::Passed => still_active.push(proposal_id),
+                _ => {}
+            }
+        }
+        // The active proposals are updated first, so that the executed calls can submit new proposals
+        self.active_proposals.set(&still_active, working_set);
+
+        for (proposal_id, proposal) in executable {
+            self.execute(proposal_id, proposal, slot_height, working_set);
+        }
+    }
+
+    /// Decides whether the `proposal` passed, and settles its deposit: the deposit is returned to the proposer
+    /// if the proposal reached the quorum, and burnt otherwise.
+    fn tally(
+        &self,
+        proposal_id: ProposalId,
+        proposal: &mut Proposal<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let params = self
+            .params
+            .get(working_set)
+            .expect("The governance parameters are set at genesis");
+        let token_address = self
+            .token_address
+            .get(working_set)
+            .expect("The governance token is set at genesis");
+        let total_supply = self
+            .bank
+            .get_total_supply_of(&token_address, working_set)
+            .unwrap_or_default();
+
+        let reaches_quorum = proposal.tally.reaches_quorum(params.quorum, total_supply);
+        proposal.status = if reaches_quorum && proposal.tally.exceeds_threshold(params.threshold) {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        if reaches_quorum {
+            self.release_tokens(&proposal.proposer, proposal.deposit, working_set)
+                .expect("The governance module holds the deposits");
+        } else {
+            let coins = Coins {
+                amount: proposal.deposit,
+                token_address,
+            };
+            self.bank
+                .burn(coins, &self.address, working_set)
+                .expect("The governance module holds the deposits");
+        }
+
+        working_set.add_event(
+            "tally",
+            &format!(
+                "proposal_id: {}, status: {:?}, yes: {}, no: {}, abstain: {}",
+                proposal_id,
+                proposal.status,
+                proposal.tally.yes,
+                proposal.tally.no,
+                proposal.tally.abstain
+            ),
+        );
+    }
+
+    /// Dispatches the call of the passed `proposal` with the governance module as the sender.
+    fn execute(
+        &self,
+        proposal_id: ProposalId,
+        mut proposal: Proposal<C>,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        // The proposal is marked as executed first, so that the call can't execute it again
+        proposal.status = ProposalStatus::Executed;
+        self.proposals.set(&proposal_id, &proposal, working_set);
+
+        let context = C::new(self.address.clone(), self.address.clone(), slot_height);
+        let result = Rt::decode_call(&proposal.call)
+            .map_err(anyhow::Error::from)
+            .and_then(|call| {
+                Rt::default()
+                    .dispatch_call(call, working_set, &context)
+                    .map_err(|sov_modules_api::Error::ModuleError(err)| err)
+            });
+
+        match result {
+            Ok(_) => working_set.add_event("execute", &format!("proposal_id: {}", proposal_id)),
+            Err(err) => {
+                proposal.status = ProposalStatus::Failed;
+                self.proposals.set(&proposal_id, &proposal, working_set);
+                working_set.add_event(
+                    "execution_failed",
+                    &format!("proposal_id: {}, error: {}", proposal_id, err),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+use std::marker::PhantomData;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_modules_api::{
+    CallResponse, DispatchCall, Error, ModuleInfo, StateMap, StateValue, WorkingSet,
+};
+
+/// The identifier of a proposal.
+pub type ProposalId = u64;
+
+/// The denominator of the quorum and threshold ratios.
+pub const BASIS_POINTS: u64 = 10_000;
+
+/// The parameters of the governance process. They can only be updated by a proposal.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+pub struct GovernanceParams {
+    /// The minimum amount of governance tokens deposited with a proposal.
+    pub min_deposit: Amount,
+    /// The number of slots during which a proposal can be voted on.
+    pub voting_period: u64,
+    /// The number of slots between the end of the voting period and the execution of a passed proposal.
+    pub execution_delay: u64,
+    /// The share of the total supply of the governance token which must vote on a proposal, in basis points.
+    pub quorum: u64,
+    /// The share of the `Yes` votes among the `Yes` and `No` votes that a proposal must exceed to pass,
+    /// in basis points.
+    pub threshold: u64,
+}
+
+/// The options of a vote.
+#[cfg_attr(
+    feature = "native",
+    derive(clap::ValueEnum),
+    derive(schemars::JsonSchema)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub enum VoteOption {
+    /// In favour of the proposal.
+    Yes,
+    /// Against the proposal.
+    No,
+    /// Counts towards the quorum, but not towards the threshold.
+    Abstain,
+}
+
+/// A vote on a proposal. Its weight is the amount of governance tokens locked with it.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct Vote {
+    /// The chosen option.
+    pub option: VoteOption,
+    /// The amount of governance tokens locked until the end of the voting period.
+    pub amount: Amount,
+}
+
+/// The sum of the votes on a proposal, for each option.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct Tally {
+    /// The weight of the `Yes` votes.
+    pub yes: Amount,
+    /// The weight of the `No` votes.
+    pub no: Amount,
+    /// The weight of the `Abstain` votes.
+    pub abstain: Amount,
+}
+
+impl Tally {
+    /// Adds the weight of a vote to the tally.
+    pub fn add(&mut self, vote: &Vote) {
+        let total = match vote.option {
+            VoteOption::Yes => &mut self.yes,
+            VoteOption::No => &mut self.no,
+            VoteOption::Abstain => &mut self.abstain,
+        };
+        *total = total.saturating_add(vote.amount);
+    }
+
+    /// Returns the total weight of the votes, which is compared with the quorum.
+    pub fn total(&self) -> Amount {
+        self.yes
+            .saturating_add(self.no)
+            .saturating_add(self.abstain)
+    }
+
+    /// Returns true if the tally reaches the `quorum` of `total_supply`.
+    pub fn reaches_quorum(&self, quorum: u64, total_supply: Amount) -> bool {
+        self.total() as u128 * BASIS_POINTS as u128 >= quorum as u128 * total_supply as u128
+    }
+
+    /// Returns true if the share of the `Yes` votes among the `Yes` and `No` votes exceeds the `threshold`.
+    pub fn exceeds_threshold(&self, threshold: u64) -> bool {
+        let decisive = self.yes as u128 + self.no as u128;
+        decisive > 0 && self.yes as u128 * BASIS_POINTS as u128 > threshold as u128 * decisive
+    }
+}
+
+/// The lifecycle of a proposal.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub enum ProposalStatus {
+    /// The proposal can be voted on.
+    Voting,
+    /// The proposal passed, and its call is executed at the start of a later slot.
+    Passed,
+    /// The proposal didn't reach the quorum or the threshold.
+    Rejected,
+    /// The call of the proposal was executed.
+    Executed,
+    /// The call of the proposal returned an error.
+    Failed,
+}
+
+/// A runtime call submitted to the vote of the governance token holders.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C::Address: serde::Serialize",
+        deserialize = "C::Address: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Proposal<C: sov_modules_api::Context> {
+    /// The address which submitted the proposal.
+    pub proposer: C::Address,
+    /// The serialized runtime call, executed with the governance module as the sender.
+    pub call: Vec<u8>,
+    /// The amount of governance tokens deposited by the proposer.
+    pub deposit: Amount,
+    /// The last slot height at which the proposal can be voted on.
+    pub voting_end: u64,
+    /// The slot height from which a passed proposal is executed.
+    pub execute_at: u64,
+    /// The sum of the votes on the proposal.
+    pub tally: Tally,
+    /// The status of the proposal.
+    pub status: ProposalStatus,
+}
+
+/// The `sov-governance` module lets the holders of a governance token decide on runtime calls. It provides
+/// functionality for:
+/// - Submitting a proposal wrapping a runtime call, backed by a deposit.
+/// - Voting on a proposal with locked governance tokens, and withdrawing them once the vote is over.
+/// - Executing the passed proposals at the start of a later slot, see [`Governance::begin_slot_hook`].
+/// - Updating the governance parameters, through a proposal.
+///
+/// The runtime `Rt` decodes and dispatches the proposed calls.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Governance<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> {
+    /// The address of the sov-governance module, which holds the deposits and the locked votes.
+    /// It is also the sender of the executed calls.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address of the governance token.
+    #[state]
+    pub(crate) token_address: StateValue<C::Address>,
+
+    /// The parameters of the governance process.
+    #[state]
+    pub(crate) params: StateValue<GovernanceParams>,
+
+    /// The identifier of the next proposal.
+    #[state]
+    pub(crate) next_proposal_id: StateValue<ProposalId>,
+
+    /// The proposals, keyed by their identifier.
+    #[state]
+    pub(crate) proposals: StateMap<ProposalId, Proposal<C>>,
+
+    /// The proposals which are being voted on or waiting for their execution.
+    #[state]
+    pub(crate) active_proposals: StateValue<Vec<ProposalId>>,
+
+    /// The votes whose tokens weren't withdrawn yet, keyed by proposal and voter.
+    #[state]
+    pub(crate) votes: StateMap<(ProposalId, C::Address), Vote>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// The runtime dispatching the proposed calls.
+    #[phantom]
+    pub(crate) runtime: PhantomData<Rt>,
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> sov_modules_api::Module
+    for Governance<C, Rt>
+{
+    type Context = C;
+
+    type Config = GovernanceConfig<C>;
+
+    type CallMessage = call::CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Submit { call, deposit } => {
+                self.submit(call, deposit, context, working_set)?;
+                Ok(CallResponse::default())
+            }
+
+            call::CallMessage::Vote {
+                proposal_id,
+                option,
+                amount,
+            } => Ok(self.vote(proposal_id, option, amount, context, working_set)?),
+
+            call::CallMessage::Withdraw { proposal_id } => {
+                Ok(self.withdraw(proposal_id, context, working_set)?)
+            }
+
+            call::CallMessage::UpdateParams {
+                min_deposit,
+                voting_period,
+                execution_delay,
+                quorum,
+                threshold,
+            } => {
+                let params = GovernanceParams {
+                    min_deposit,
+                    voting_period,
+                    execution_delay,
+                    quorum,
+                    threshold,
+                };
+                Ok(self.update_params(params, context, working_set)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Defines rpc queries exposed by the governance module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::{Governance, GovernanceParams, Proposal, ProposalId, Vote};
+
+/// Structure returned by the `getProposal` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct ProposalResponse<C: sov_modules_api::Context> {
+    /// The proposal, if it exists.
+    pub proposal: Option<Proposal<C>>,
+}
+
+/// Structure returned by the `getVote` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct VoteResponse {
+    /// The vote, unless it doesn't exist or its tokens were withdrawn.
+    pub vote: Option<Vote>,
+}
+
+/// Structure returned by the `getActiveProposals` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ActiveProposalsResponse {
+    /// The identifiers of the proposals being voted on or waiting for their execution.
+    pub proposal_ids: Vec<ProposalId>,
+}
+
+#[rpc_gen(client, server, namespace = "governance")]
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Governance<C, Rt> {
+    #[rpc_method(name = "getProposal")]
+    /// Rpc method that returns the proposal `proposal_id`, with its tally and status.
+    pub fn proposal(
+        &self,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ProposalResponse<C>> {
+        Ok(ProposalResponse {
+            proposal: self.get_proposal(proposal_id, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getVote")]
+    /// Rpc method that returns the vote of `voter` on the proposal `proposal_id`.
+    pub fn vote_of(
+        &self,
+        proposal_id: ProposalId,
+        voter: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<VoteResponse> {
+        Ok(VoteResponse {
+            vote: self.get_vote(proposal_id, &voter, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getActiveProposals")]
+    /// Rpc method that returns the identifiers of the proposals being voted on or waiting for their execution.
+    pub fn active_proposal_ids(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ActiveProposalsResponse> {
+        Ok(ActiveProposalsResponse {
+            proposal_ids: self.active_proposals.get(working_set).unwrap_or_default(),
+        })
+    }
+
+    #[rpc_method(name = "getParams")]
+    /// Rpc method that returns the governance parameters.
+    pub fn governance_params(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<GovernanceParams>> {
+        Ok(self.get_params(working_set))
+    }
+}
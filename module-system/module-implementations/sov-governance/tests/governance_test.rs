@@ -0,0 +1,255 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, TokenConfig};
+use sov_governance::{
+    CallMessage, Governance, GovernanceConfig, GovernanceParams, ProposalStatus, VoteOption,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, Module, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    bank: Bank<C>,
+    governance: Governance<C, TestRuntime<C>>,
+}
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    proposer: Address,
+    voters: [Address; 2],
+    token_address: Address,
+}
+
+impl TestSetup {
+    fn new(working_set: &mut WorkingSet<C>) -> Self {
+        let proposer = generate_address::<C>("proposer");
+        let voters = [
+            generate_address::<C>("voter_1"),
+            generate_address::<C>("voter_2"),
+        ];
+
+        let token_name = "GovToken".to_owned();
+        let token_address = get_genesis_token_address::<C>(&token_name, 0);
+        let bank_config = BankConfig::<C> {
+            tokens: vec![TokenConfig {
+                token_name,
+                address_and_balances: vec![(proposer, 500), (voters[0], 300), (voters[1], 200)],
+                authorized_minters: vec![],
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
+                salt: 0,
+            }],
+            fee_settlement: Default::default(),
+        };
+        let governance_config = GovernanceConfig::<C> {
+            token_address,
+            params: params(100),
+        };
+
+        let runtime = TestRuntime::<C>::default();
+        runtime.bank.genesis(&bank_config, working_set).unwrap();
+        runtime
+            .governance
+            .genesis(&governance_config, working_set)
+            .unwrap();
+
+        Self {
+            runtime,
+            proposer,
+            voters,
+            token_address,
+        }
+    }
+
+    fn context(&self, sender: Address, height: u64) -> C {
+        C::new(sender, generate_address::<C>("sequencer"), height)
+    }
+
+    fn balance_of(&self, address: Address, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.runtime
+            .bank
+            .get_balance_of(address, self.token_address, working_set)
+    }
+
+    fn submit(&self, proposal: CallMessage, working_set: &mut WorkingSet<C>) {
+        let call =
+            <TestRuntime<C> as EncodeCall<Governance<C, TestRuntime<C>>>>::encode_call(proposal);
+        self.runtime
+            .governance
+            .call(
+                CallMessage::Submit { call, deposit: 100 },
+                &self.context(self.proposer, 1),
+                working_set,
+            )
+            .expect("Submit call failed");
+    }
+
+    fn vote(
+        &self,
+        voter: Address,
+        option: VoteOption,
+        amount: u64,
+        height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<sov_modules_api::CallResponse, sov_modules_api::Error> {
+        self.runtime.governance.call(
+            CallMessage::Vote {
+                proposal_id: 0,
+                option,
+                amount,
+            },
+            &self.context(voter, height),
+            working_set,
+        )
+    }
+}
+
+fn params(min_deposit: u64) -> GovernanceParams {
+    GovernanceParams {
+        min_deposit,
+        voting_period: 10,
+        execution_delay: 2,
+        quorum: 3_000,
+        threshold: 5_000,
+    }
+}
+
+fn update_params_call(min_deposit: u64) -> CallMessage {
+    let params = params(min_deposit);
+    CallMessage::UpdateParams {
+        min_deposit: params.min_deposit,
+        voting_period: params.voting_period,
+        execution_delay: params.execution_delay,
+        quorum: params.quorum,
+        threshold: params.threshold,
+    }
+}
+
+#[test]
+fn passed_proposal_is_executed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let governance = &setup.runtime.governance;
+
+    // The parameters can only be updated through a proposal
+    assert!(governance
+        .call(
+            update_params_call(50),
+            &setup.context(setup.proposer, 1),
+            &mut working_set
+        )
+        .is_err());
+
+    setup.submit(update_params_call(50), &mut working_set);
+    assert_eq!(
+        Some(400),
+        setup.balance_of(setup.proposer, &mut working_set)
+    );
+
+    setup
+        .vote(setup.voters[0], VoteOption::Yes, 300, 5, &mut working_set)
+        .expect("Vote call failed");
+    setup
+        .vote(setup.voters[1], VoteOption::No, 200, 11, &mut working_set)
+        .expect("Vote call failed");
+    // Each address votes once, and only during the voting period
+    assert!(setup
+        .vote(setup.voters[0], VoteOption::Yes, 1, 6, &mut working_set)
+        .is_err());
+    assert!(setup
+        .vote(setup.proposer, VoteOption::Yes, 100, 12, &mut working_set)
+        .is_err());
+
+    // The tokens are locked until the proposal is tallied
+    let withdraw = CallMessage::Withdraw { proposal_id: 0 };
+    assert!(governance
+        .call(
+            withdraw.clone(),
+            &setup.context(setup.voters[0], 11),
+            &mut working_set
+        )
+        .is_err());
+
+    governance.begin_slot_hook(11, &mut working_set);
+    let proposal = governance.get_proposal(0, &mut working_set).unwrap();
+    assert_eq!(ProposalStatus::Voting, proposal.status);
+
+    governance.begin_slot_hook(12, &mut working_set);
+    let proposal = governance.get_proposal(0, &mut working_set).unwrap();
+    assert_eq!(ProposalStatus::Passed, proposal.status);
+    assert_eq!(300, proposal.tally.yes);
+    assert_eq!(200, proposal.tally.no);
+    // The deposit is returned once the quorum is reached
+    assert_eq!(
+        Some(500),
+        setup.balance_of(setup.proposer, &mut working_set)
+    );
+
+    governance
+        .call(
+            withdraw.clone(),
+            &setup.context(setup.voters[0], 12),
+            &mut working_set,
+        )
+        .expect("Withdraw call failed");
+    assert_eq!(
+        Some(300),
+        setup.balance_of(setup.voters[0], &mut working_set)
+    );
+    assert!(governance
+        .call(
+            withdraw,
+            &setup.context(setup.voters[0], 12),
+            &mut working_set
+        )
+        .is_err());
+
+    // The proposal is executed after the execution delay
+    governance.begin_slot_hook(13, &mut working_set);
+    assert_eq!(Some(params(100)), governance.get_params(&mut working_set));
+    governance.begin_slot_hook(14, &mut working_set);
+    let proposal = governance.get_proposal(0, &mut working_set).unwrap();
+    assert_eq!(ProposalStatus::Executed, proposal.status);
+    assert_eq!(Some(params(50)), governance.get_params(&mut working_set));
+}
+
+#[test]
+fn proposal_without_quorum_is_rejected() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let governance = &setup.runtime.governance;
+
+    setup.submit(update_params_call(50), &mut working_set);
+    setup
+        .vote(setup.voters[1], VoteOption::Yes, 200, 2, &mut working_set)
+        .expect("Vote call failed");
+
+    governance.begin_slot_hook(12, &mut working_set);
+    let proposal = governance.get_proposal(0, &mut working_set).unwrap();
+    assert_eq!(ProposalStatus::Rejected, proposal.status);
+
+    // The deposit is burnt
+    assert_eq!(
+        Some(400),
+        setup.balance_of(setup.proposer, &mut working_set)
+    );
+    assert_eq!(
+        Some(900),
+        setup
+            .runtime
+            .bank
+            .get_total_supply_of(&setup.token_address, &mut working_set)
+    );
+
+    governance.begin_slot_hook(20, &mut working_set);
+    assert_eq!(Some(params(100)), governance.get_params(&mut working_set));
+}
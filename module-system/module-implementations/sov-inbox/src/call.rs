@@ -0,0 +1,147 @@
+use anyhow::{bail, ensure, Result};
+use sov_modules_api::{CallResponse, Context, StateMapAccessor, StateValueAccessor, WorkingSet};
+use sov_outbox::merkle::verify_inclusion;
+use sov_outbox::CrossRollupMessage;
+
+use crate::events::{CounterpartRegistered, MessageReceived, RootSubmitted};
+use crate::Inbox;
+
+/// This enumeration represents the available call messages for interacting with the sov-inbox module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CliWalletArg),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Registers a counterpart rollup, or replaces the authority of a registered one. Only the admin can
+    /// register counterparts.
+    RegisterCounterpart {
+        /// The chain ID of the counterpart.
+        chain_id: u64,
+        /// The authority allowed to submit the outbox roots of the counterpart, such as a relayer following
+        /// the counterpart on the DA layer, or a module verifying the proofs of the counterpart.
+        root_authority: C::Address,
+    },
+    /// Records an outbox root of a counterpart. Only the root authority of the counterpart can submit roots.
+    SubmitRoot {
+        /// The chain ID of the counterpart.
+        chain_id: u64,
+        /// The root of the outbox of the counterpart.
+        root: [u8; 32],
+    },
+    /// Receives a message sent by a counterpart. Anyone can relay a message.
+    Receive {
+        /// The message.
+        message: CrossRollupMessage,
+        /// A recorded outbox root of the sending counterpart.
+        root: [u8; 32],
+        /// The proof of inclusion of the message in `root`.
+        proof: Vec<[u8; 32]>,
+    },
+}
+
+impl<C: Context> Inbox<C> {
+    /// Registers the counterpart `chain_id`, whose outbox roots are submitted by `root_authority`.
+    pub(crate) fn register_counterpart(
+        &self,
+        chain_id: u64,
+        root_authority: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let admin = self.admin.get_or_err(working_set)?;
+        if &admin != context.sender() {
+            bail!(
+                "Only the admin {} can register counterparts, not {}",
+                admin,
+                context.sender()
+            )
+        }
+        ensure!(
+            chain_id != self.chain_id.get_or_err(working_set)?,
+            "A rollup can't be its own counterpart"
+        );
+        self.counterparts
+            .set(&chain_id, &root_authority, working_set);
+
+        working_set.emit_event(&CounterpartRegistered::<C> {
+            chain_id,
+            root_authority,
+        });
+        Ok(CallResponse::default())
+    }
+
+    /// Records the outbox root `root` of the counterpart `chain_id`.
+    pub(crate) fn submit_root(
+        &self,
+        chain_id: u64,
+        root: [u8; 32],
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let Some(root_authority) = self.counterparts.get(&chain_id, working_set) else {
+            bail!("Chain {} isn't a registered counterpart", chain_id)
+        };
+        if &root_authority != context.sender() {
+            bail!(
+                "Only the root authority {} of chain {} can submit its roots, not {}",
+                root_authority,
+                chain_id,
+                context.sender()
+            )
+        }
+        self.roots.set(&(chain_id, root), &(), working_set);
+
+        working_set.emit_event(&RootSubmitted { chain_id, root });
+        Ok(CallResponse::default())
+    }
+
+    /// Verifies the inclusion of `message` in the recorded root `root` of its sender, and records it as received.
+    pub(crate) fn receive(
+        &self,
+        message: CrossRollupMessage,
+        root: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let chain_id = self.chain_id.get_or_err(working_set)?;
+        ensure!(
+            message.destination_chain_id == chain_id,
+            "The message is sent to chain {}, not to this chain {}",
+            message.destination_chain_id,
+            chain_id
+        );
+        ensure!(
+            self.is_root_known(message.source_chain_id, root, working_set),
+            "The root isn't a recorded outbox root of chain {}",
+            message.source_chain_id
+        );
+        let key = (message.source_chain_id, message.nonce);
+        ensure!(
+            self.received.get(&key, working_set).is_none(),
+            "The message {} of chain {} was already received",
+            message.nonce,
+            message.source_chain_id
+        );
+        let message_hash = message.hash::<C>();
+        ensure!(
+            verify_inclusion::<C>(&root, message_hash, message.nonce, &proof),
+            "Invalid proof of inclusion of the message"
+        );
+        self.received.set(&key, &message, working_set);
+
+        working_set.emit_event(&MessageReceived {
+            source_chain_id: message.source_chain_id,
+            nonce: message.nonce,
+            message_hash,
+        });
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,38 @@
+//! The typed events emitted by the inbox module.
+use sov_modules_api::ModuleEvent;
+
+/// Emitted when the admin registers a counterpart rollup.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "counterpart_registered")]
+pub struct CounterpartRegistered<C: sov_modules_api::Context> {
+    /// The chain ID of the counterpart.
+    #[indexed]
+    pub chain_id: u64,
+    /// The authority allowed to submit the outbox roots of the counterpart.
+    pub root_authority: C::Address,
+}
+
+/// Emitted when an outbox root of a counterpart is recorded.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "root_submitted")]
+pub struct RootSubmitted {
+    /// The chain ID of the counterpart.
+    #[indexed]
+    pub chain_id: u64,
+    /// The outbox root.
+    pub root: [u8; 32],
+}
+
+/// Emitted when a message from a counterpart is received.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "message_received")]
+pub struct MessageReceived {
+    /// The chain ID of the sending counterpart.
+    #[indexed]
+    pub source_chain_id: u64,
+    /// The nonce of the message in the outbox of the counterpart.
+    #[indexed]
+    pub nonce: u64,
+    /// The leaf of the message in the outbox tree of the counterpart.
+    pub message_hash: [u8; 32],
+}
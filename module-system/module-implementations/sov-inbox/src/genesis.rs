@@ -0,0 +1,39 @@
+use anyhow::{ensure, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::Inbox;
+
+/// Initial configuration for sov-inbox module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct InboxConfig<C: sov_modules_api::Context> {
+    /// The chain ID of the rollup. Only the messages sent to this chain ID are received.
+    pub chain_id: u64,
+    /// The address allowed to register counterparts.
+    pub admin: C::Address,
+    /// The counterparts registered at genesis, with the authorities allowed to submit their outbox roots.
+    #[serde(default)]
+    pub counterparts: Vec<(u64, C::Address)>,
+}
+
+impl<C: sov_modules_api::Context> Inbox<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.chain_id.set(&config.chain_id, working_set);
+        self.admin.set(&config.admin, working_set);
+        for (chain_id, root_authority) in &config.counterparts {
+            ensure!(
+                *chain_id != config.chain_id,
+                "A rollup can't be its own counterpart"
+            );
+            self.counterparts.set(chain_id, root_authority, working_set);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::{
+    CallResponse, Error, ModuleInfo, StateMap, StateMapAccessor, StateValue, WorkingSet,
+};
+use sov_outbox::CrossRollupMessage;
+
+/// The `sov-inbox` module receives the messages sent to the rollup through the
+/// [`sov-outbox`](sov_outbox) module of other rollups. It provides functionality for:
+/// - Registering a counterpart rollup, with the authority allowed to submit the roots of its outbox.
+/// - Recording the outbox roots of the counterparts, as learned from the DA layer or from an aggregated proof.
+/// - Receiving a message, by verifying the proof of its inclusion in a recorded root. Each message is
+///   received at most once.
+///
+/// Other modules read the received messages with [`Inbox::received_message`].
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Inbox<C: sov_modules_api::Context> {
+    /// The address of the sov-inbox module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The chain ID of the rollup.
+    #[state]
+    pub(crate) chain_id: StateValue<u64>,
+
+    /// The address allowed to register counterparts.
+    #[state]
+    pub(crate) admin: StateValue<C::Address>,
+
+    /// The authority allowed to submit the outbox roots of each counterpart, keyed by the chain ID of the counterpart.
+    #[state]
+    pub(crate) counterparts: StateMap<u64, C::Address>,
+
+    /// The recorded outbox roots, keyed by the chain ID of their counterpart and the root.
+    #[state]
+    pub(crate) roots: StateMap<(u64, [u8; 32]), ()>,
+
+    /// The received messages, keyed by the chain ID of their sender and their nonce.
+    #[state]
+    pub(crate) received: StateMap<(u64, u64), CrossRollupMessage>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Inbox<C> {
+    type Context = C;
+
+    type Config = InboxConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::RegisterCounterpart {
+                chain_id,
+                root_authority,
+            } => Ok(self.register_counterpart(chain_id, root_authority, context, working_set)?),
+
+            call::CallMessage::SubmitRoot { chain_id, root } => {
+                Ok(self.submit_root(chain_id, root, context, working_set)?)
+            }
+
+            call::CallMessage::Receive {
+                message,
+                root,
+                proof,
+            } => Ok(self.receive(message, root, proof, working_set)?),
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> Inbox<C> {
+    /// Returns the message with the nonce `nonce` received from the rollup `source_chain_id`, if any.
+    pub fn received_message(
+        &self,
+        source_chain_id: u64,
+        nonce: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<CrossRollupMessage> {
+        self.received.get(&(source_chain_id, nonce), working_set)
+    }
+
+    /// Returns true if `root` is a recorded outbox root of the rollup `chain_id`.
+    pub fn is_root_known(
+        &self,
+        chain_id: u64,
+        root: [u8; 32],
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.roots.get(&(chain_id, root), working_set).is_some()
+    }
+}
@@ -0,0 +1,43 @@
+//! Defines rpc queries exposed by the inbox module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_outbox::CrossRollupMessage;
+
+use crate::Inbox;
+
+#[rpc_gen(client, server, namespace = "inbox")]
+impl<C: sov_modules_api::Context> Inbox<C> {
+    #[rpc_method(name = "getRootAuthority")]
+    /// Rpc method that returns the authority allowed to submit the outbox roots of the counterpart `chain_id`,
+    /// if it is registered.
+    pub fn root_authority(
+        &self,
+        chain_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<C::Address>> {
+        Ok(self.counterparts.get(&chain_id, working_set))
+    }
+
+    #[rpc_method(name = "isRootKnown")]
+    /// Rpc method that returns true if `root` is a recorded outbox root of the counterpart `chain_id`.
+    pub fn root_known(
+        &self,
+        chain_id: u64,
+        root: [u8; 32],
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<bool> {
+        Ok(self.is_root_known(chain_id, root, working_set))
+    }
+
+    #[rpc_method(name = "getReceivedMessage")]
+    /// Rpc method that returns the message with the nonce `nonce` received from the counterpart `source_chain_id`.
+    pub fn received(
+        &self,
+        source_chain_id: u64,
+        nonce: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<CrossRollupMessage>> {
+        Ok(self.received_message(source_chain_id, nonce, working_set))
+    }
+}
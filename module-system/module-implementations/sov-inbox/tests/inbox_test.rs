@@ -0,0 +1,122 @@
+use sov_inbox::{CallMessage, Inbox, InboxConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, WorkingSet};
+use sov_outbox::{Outbox, OutboxConfig};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+const SOURCE_CHAIN_ID: u64 = 1;
+const CHAIN_ID: u64 = 2;
+
+fn context(sender: Address) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), 1)
+}
+
+#[test]
+fn test_receive_messages() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+    let admin = generate_address::<C>("admin");
+    let relayer = generate_address::<C>("relayer");
+    let sender = generate_address::<C>("sender");
+
+    // The outbox of the source rollup
+    let outbox = Outbox::<C>::default();
+    outbox
+        .genesis(
+            &OutboxConfig {
+                chain_id: SOURCE_CHAIN_ID,
+            },
+            working_set,
+        )
+        .unwrap();
+    outbox
+        .send_message(&sender, CHAIN_ID, b"hello".to_vec(), working_set)
+        .unwrap();
+    outbox
+        .send_message(&sender, CHAIN_ID + 1, b"elsewhere".to_vec(), working_set)
+        .unwrap();
+    let hello = outbox.message_proof(0, working_set).unwrap().unwrap();
+    let elsewhere = outbox.message_proof(1, working_set).unwrap().unwrap();
+
+    let inbox = Inbox::<C>::default();
+    inbox
+        .genesis(
+            &InboxConfig {
+                chain_id: CHAIN_ID,
+                admin,
+                counterparts: vec![],
+            },
+            working_set,
+        )
+        .unwrap();
+
+    let receive = |proof: &sov_outbox::MessageProofResponse| CallMessage::Receive {
+        message: proof.message.clone(),
+        root: proof.root,
+        proof: proof.proof.clone(),
+    };
+
+    // Messages from an unregistered counterpart are rejected
+    inbox
+        .call(receive(&hello), &context(relayer), working_set)
+        .expect_err("The root isn't recorded");
+    inbox
+        .call(
+            CallMessage::SubmitRoot {
+                chain_id: SOURCE_CHAIN_ID,
+                root: hello.root,
+            },
+            &context(relayer),
+            working_set,
+        )
+        .expect_err("The counterpart isn't registered");
+
+    // Only the admin registers counterparts, and only their root authority submits their roots
+    let register = CallMessage::RegisterCounterpart {
+        chain_id: SOURCE_CHAIN_ID,
+        root_authority: relayer,
+    };
+    inbox
+        .call(register.clone(), &context(relayer), working_set)
+        .unwrap_err();
+    inbox.call(register, &context(admin), working_set).unwrap();
+    let submit_root = CallMessage::SubmitRoot {
+        chain_id: SOURCE_CHAIN_ID,
+        root: hello.root,
+    };
+    inbox
+        .call(submit_root.clone(), &context(admin), working_set)
+        .unwrap_err();
+    inbox
+        .call(submit_root, &context(relayer), working_set)
+        .unwrap();
+    assert!(inbox.is_root_known(SOURCE_CHAIN_ID, hello.root, working_set));
+
+    // A message with an invalid proof is rejected
+    let mut tampered = hello.clone();
+    tampered.message.payload = b"goodbye".to_vec();
+    inbox
+        .call(receive(&tampered), &context(sender), working_set)
+        .expect_err("The proof doesn't hold for a tampered message");
+
+    // A message sent to another chain is rejected
+    inbox
+        .call(receive(&elsewhere), &context(sender), working_set)
+        .expect_err("The message isn't sent to this chain");
+
+    // A valid message is received once
+    inbox
+        .call(receive(&hello), &context(sender), working_set)
+        .unwrap();
+    assert_eq!(
+        inbox.received_message(SOURCE_CHAIN_ID, 0, working_set),
+        Some(hello.message.clone())
+    );
+    inbox
+        .call(receive(&hello), &context(sender), working_set)
+        .expect_err("The message was already received");
+}
@@ -0,0 +1,406 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Context as _, Result};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, DispatchCall, WorkingSet};
+
+use crate::utils::get_multisig_address;
+use crate::{Multisig, MultisigAccount, Proposal, ProposalId};
+
+/// The maximum number of members of a multisig account.
+pub const MAX_MEMBERS: usize = 64;
+
+/// This enumeration represents the available call messages for interacting with the sov-multisig module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: sov_modules_api::Context> {
+    /// Creates a new multisig account, whose address is derived from the sender and the salt.
+    CreateMultisig {
+        /// The addresses allowed to propose and approve calls.
+        members: Vec<C::Address>,
+        /// The number of approvals needed to execute a call.
+        threshold: u32,
+        /// Random value used to create a unique multisig address.
+        salt: u64,
+    },
+
+    /// Proposes a runtime call on behalf of a multisig account. Only members can propose calls,
+    /// and the proposal counts as an approval from the proposer.
+    Propose {
+        /// The address of the multisig account.
+        multisig: C::Address,
+        /// The serialized runtime call.
+        call: Vec<u8>,
+        /// The number of slots during which the proposal can be approved.
+        expires_in: u64,
+    },
+
+    /// Approves a proposal. The proposed call is executed once the threshold is met.
+    Approve {
+        /// The address of the multisig account.
+        multisig: C::Address,
+        /// The identifier of the proposal.
+        proposal_id: ProposalId,
+    },
+
+    /// Cancels a proposal. Proposals can be cancelled by their proposer, or by any member once expired.
+    Cancel {
+        /// The address of the multisig account.
+        multisig: C::Address,
+        /// The identifier of the proposal.
+        proposal_id: ProposalId,
+    },
+
+    /// Replaces the members and the threshold of the sender, which must be a multisig account.
+    /// This call is meant to be proposed to the multisig account itself.
+    UpdateMembers {
+        /// The new members.
+        members: Vec<C::Address>,
+        /// The new threshold.
+        threshold: u32,
+    },
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Multisig<C, Rt> {
+    /// Creates a multisig account with the given `members` and `threshold`.
+    /// Returns the address of the new account, or an error if an account already exists at that address.
+    pub fn create_multisig(
+        &self,
+        members: Vec<C::Address>,
+        threshold: u32,
+        salt: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<C::Address> {
+        let multisig = get_multisig_address::<C>(context.sender().as_ref(), salt);
+        self.insert_account(&multisig, members, threshold, working_set)?;
+
+        working_set.add_event(
+            "create_multisig",
+            &format!("multisig: {}, threshold: {}", multisig, threshold),
+        );
+        Ok(multisig)
+    }
+
+    /// Proposes the serialized runtime `call` on behalf of the `multisig` account, and approves it.
+    /// The call is executed right away if the threshold is one.
+    /// Returns the identifier of the proposal.
+    pub fn propose(
+        &self,
+        multisig: C::Address,
+        call: Vec<u8>,
+        expires_in: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<ProposalId> {
+        let mut account = self.get_member_account(&multisig, context.sender(), working_set)?;
+        if expires_in == 0 {
+            bail!("A proposal must be approvable for at least one slot");
+        }
+        Rt::decode_call(&call).context("The proposed call can't be decoded by the runtime")?;
+
+        let proposal_id = account.next_proposal_id;
+        account.next_proposal_id += 1;
+        self.accounts.set(&multisig, &account, working_set);
+
+        let proposal = Proposal {
+            proposer: context.sender().clone(),
+            call,
+            approvals: vec![context.sender().clone()],
+            expires_at: context.slot_height().saturating_add(expires_in),
+        };
+        working_set.add_event(
+            "propose",
+            &format!(
+                "multisig: {}, proposal_id: {}, proposer: {}",
+                multisig,
+                proposal_id,
+                context.sender()
+            ),
+        );
+
+        self.execute_if_approved(
+            &multisig,
+            proposal_id,
+            &account,
+            proposal,
+            context,
+            working_set,
+        )?;
+        Ok(proposal_id)
+    }
+
+    /// Approves the proposal `proposal_id` of the `multisig` account, and executes the proposed call
+    /// if the threshold is met. Returns the response of the executed call.
+    /// An error of the executed call fails the approval, which can be sent again later.
+    pub fn approve(
+        &self,
+        multisig: C::Address,
+        proposal_id: ProposalId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let account = self.get_member_account(&multisig, context.sender(), working_set)?;
+        let mut proposal = self.get_proposal(&multisig, proposal_id, working_set)?;
+
+        if context.slot_height() > proposal.expires_at {
+            bail!(
+                "Proposal {} of multisig {} expired at height {}",
+                proposal_id,
+                multisig,
+                proposal.expires_at
+            )
+        }
+        if proposal.approvals.contains(context.sender()) {
+            bail!(
+                "{} already approved proposal {} of multisig {}",
+                context.sender(),
+                proposal_id,
+                multisig
+            )
+        }
+        proposal.approvals.push(context.sender().clone());
+
+        working_set.add_event(
+            "approve",
+            &format!(
+                "multisig: {}, proposal_id: {}, member: {}",
+                multisig,
+                proposal_id,
+                context.sender()
+            ),
+        );
+
+        self.execute_if_approved(
+            &multisig,
+            proposal_id,
+            &account,
+            proposal,
+            context,
+            working_set,
+        )
+    }
+
+    /// Cancels the proposal `proposal_id` of the `multisig` account.
+    pub fn cancel(
+        &self,
+        multisig: C::Address,
+        proposal_id: ProposalId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let account = self.get_account(&multisig, working_set)?;
+        let proposal = self.get_proposal(&multisig, proposal_id, working_set)?;
+
+        let sender = context.sender();
+        let is_expired = context.slot_height() > proposal.expires_at;
+        if sender != &proposal.proposer && !(is_expired && account.members.contains(sender)) {
+            bail!(
+                "Sender {} is not allowed to cancel proposal {} of multisig {}",
+                sender,
+                proposal_id,
+                multisig
+            )
+        }
+
+        self.proposals
+            .delete(&(multisig.clone(), proposal_id), working_set);
+        working_set.add_event(
+            "cancel",
+            &format!("multisig: {}, proposal_id: {}", multisig, proposal_id),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the members and the threshold of the multisig account sending the call.
+    /// The approvals of the pending proposals only count if their members remain.
+    pub fn update_members(
+        &self,
+        members: Vec<C::Address>,
+        threshold: u32,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let multisig = context.sender();
+        let mut account = self
+            .accounts
+            .get(multisig, working_set)
+            .with_context(|| format!("Sender {} is not a multisig account", multisig))?;
+
+        validate_members::<C>(&members, threshold)?;
+        account.members = members;
+        account.threshold = threshold;
+        self.accounts.set(multisig, &account, working_set);
+
+        working_set.add_event(
+            "update_members",
+            &format!("multisig: {}, threshold: {}", multisig, threshold),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn insert_account(
+        &self,
+        multisig: &C::Address,
+        members: Vec<C::Address>,
+        threshold: u32,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if self.accounts.get(multisig, working_set).is_some() {
+            bail!("Multisig {} already exists", multisig);
+        }
+        validate_members::<C>(&members, threshold)?;
+
+        let account = MultisigAccount {
+            members,
+            threshold,
+            next_proposal_id: 0,
+        };
+        self.accounts.set(multisig, &account, working_set);
+        Ok(())
+    }
+
+    /// Stores the `proposal`, unless enough current members approved it. In that case, the proposal is removed
+    /// and its call is dispatched by the runtime, with the `multisig` account as the sender.
+    fn execute_if_approved(
+        &self,
+        multisig: &C::Address,
+        proposal_id: ProposalId,
+        account: &MultisigAccount<C>,
+        proposal: Proposal<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|approval| account.members.contains(approval))
+            .count();
+        let key = (multisig.clone(), proposal_id);
+        if approvals < account.threshold as usize {
+            self.proposals.set(&key, &proposal, working_set);
+            return Ok(CallResponse::default());
+        }
+
+        // The proposal is removed first, so that the executed call can't approve it again
+        self.proposals.delete(&key, working_set);
+        let call = Rt::decode_call(&proposal.call)
+            .context("The proposed call can't be decoded by the runtime")?;
+        let multisig_context = C::new(
+            multisig.clone(),
+            context.sequencer().clone(),
+            context.slot_height(),
+        );
+        let response = Rt::default()
+            .dispatch_call(call, working_set, &multisig_context)
+            .map_err(|sov_modules_api::Error::ModuleError(err)| {
+                err.context(format!(
+                    "Failed to execute proposal {} of multisig {}",
+                    proposal_id, multisig
+                ))
+            })?;
+
+        working_set.add_event(
+            "execute",
+            &format!("multisig: {}, proposal_id: {}", multisig, proposal_id),
+        );
+        Ok(response)
+    }
+
+    fn get_account(
+        &self,
+        multisig: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<MultisigAccount<C>> {
+        self.accounts
+            .get(multisig, working_set)
+            .with_context(|| format!("Multisig {} doesn't exist", multisig))
+    }
+
+    fn get_member_account(
+        &self,
+        multisig: &C::Address,
+        sender: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<MultisigAccount<C>> {
+        let account = self.get_account(multisig, working_set)?;
+        if !account.members.contains(sender) {
+            bail!("Sender {} is not a member of multisig {}", sender, multisig)
+        }
+        Ok(account)
+    }
+
+    fn get_proposal(
+        &self,
+        multisig: &C::Address,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Proposal<C>> {
+        self.proposals
+            .get(&(multisig.clone(), proposal_id), working_set)
+            .with_context(|| {
+                format!(
+                    "Proposal {} of multisig {} doesn't exist",
+                    proposal_id, multisig
+                )
+            })
+    }
+
+    /// Returns the multisig account stored at `multisig`, if any.
+    pub fn get_multisig(
+        &self,
+        multisig: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<MultisigAccount<C>> {
+        self.accounts.get(multisig, working_set)
+    }
+
+    /// Returns the pending proposal `proposal_id` of the `multisig` account, if any.
+    /// Executed and cancelled proposals are removed.
+    pub fn get_pending_proposal(
+        &self,
+        multisig: &C::Address,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Proposal<C>> {
+        self.proposals
+            .get(&(multisig.clone(), proposal_id), working_set)
+    }
+}
+
+/// Checks that the `members` are unique, and that the `threshold` can be met.
+fn validate_members<C: sov_modules_api::Context>(
+    members: &[C::Address],
+    threshold: u32,
+) -> Result<()> {
+    if members.is_empty() || members.len() > MAX_MEMBERS {
+        bail!("A multisig must have between 1 and {} members", MAX_MEMBERS)
+    }
+    let mut unique_members = HashSet::new();
+    if !members
+        .iter()
+        .all(|member| unique_members.insert(member.as_ref()))
+    {
+        bail!("The members of a multisig must be unique")
+    }
+    if threshold == 0 || threshold as usize > members.len() {
+        bail!(
+            "The threshold {} must be between 1 and the number of members {}",
+            threshold,
+            members.len()
+        )
+    }
+    Ok(())
+}
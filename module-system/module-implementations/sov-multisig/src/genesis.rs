@@ -0,0 +1,51 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::utils::get_genesis_multisig_address;
+use crate::Multisig;
+
+/// Initial configuration for sov-multisig module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct MultisigConfig<C: sov_modules_api::Context> {
+    /// The multisig accounts created at genesis, e.g. to hold the treasury of the rollup.
+    pub multisigs: Vec<MultisigAccountConfig<C>>,
+}
+
+/// [`MultisigAccountConfig`] specifies a multisig account created at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct MultisigAccountConfig<C: sov_modules_api::Context> {
+    /// The addresses allowed to propose and approve calls.
+    pub members: Vec<C::Address>,
+    /// The number of approvals needed to execute a call.
+    pub threshold: u32,
+    /// A salt used to derive the address of the multisig account.
+    pub salt: u64,
+}
+
+/// The address of the deployment node. For now, set to [0; 32]
+pub(crate) const DEPLOYER: [u8; 32] = [0; 32];
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Multisig<C, Rt> {
+    /// Init an instance of the multisig module from the configuration `config`.
+    /// The address of each multisig account is derived from its salt, see [`get_genesis_multisig_address`].
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        for multisig_config in config.multisigs.iter() {
+            let multisig = get_genesis_multisig_address::<C>(multisig_config.salt);
+            self.insert_account(
+                &multisig,
+                multisig_config.members.clone(),
+                multisig_config.threshold,
+                working_set,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+/// Util functions for multisig
+pub mod utils;
+use std::marker::PhantomData;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::{CallResponse, DispatchCall, Error, ModuleInfo, StateMap, WorkingSet};
+/// Methods to get a multisig address.
+pub use utils::{get_genesis_multisig_address, get_multisig_address};
+
+/// The identifier of a proposal, unique within its multisig account.
+pub type ProposalId = u64;
+
+/// An account controlled by a set of members, which executes a call once enough members approved it.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C::Address: serde::Serialize",
+        deserialize = "C::Address: serde::Deserialize<'de>"
+    ))
+)]
+pub struct MultisigAccount<C: sov_modules_api::Context> {
+    /// The addresses allowed to propose and approve calls.
+    pub members: Vec<C::Address>,
+    /// The number of approvals needed to execute a call.
+    pub threshold: u32,
+    /// The identifier of the next proposal.
+    pub next_proposal_id: ProposalId,
+}
+
+/// A call proposed for execution by a multisig account.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C::Address: serde::Serialize",
+        deserialize = "C::Address: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Proposal<C: sov_modules_api::Context> {
+    /// The member who made the proposal.
+    pub proposer: C::Address,
+    /// The serialized runtime call, executed with the multisig account as the sender.
+    pub call: Vec<u8>,
+    /// The members who approved the proposal.
+    pub approvals: Vec<C::Address>,
+    /// The last slot height at which the proposal can be approved.
+    pub expires_at: u64,
+}
+
+/// The `sov-multisig` module manages multisig accounts. It provides functionality for:
+/// - Creating a multisig account with a set of members and an approval threshold.
+/// - Proposing a runtime call on behalf of a multisig account.
+/// - Approving a proposal, which executes the call once the threshold is met.
+/// - Cancelling a proposal.
+/// - Updating the members and the threshold of a multisig account, through a proposal.
+///
+/// The runtime `Rt` decodes and dispatches the proposed calls.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Multisig<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> {
+    /// The address of the sov-multisig module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The multisig accounts, keyed by their address.
+    #[state]
+    pub(crate) accounts: StateMap<C::Address, MultisigAccount<C>>,
+
+    /// The pending proposals, keyed by the address of their multisig account and their identifier.
+    #[state]
+    pub(crate) proposals: StateMap<(C::Address, ProposalId), Proposal<C>>,
+
+    /// The runtime dispatching the proposed calls.
+    #[phantom]
+    pub(crate) runtime: PhantomData<Rt>,
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> sov_modules_api::Module
+    for Multisig<C, Rt>
+{
+    type Context = C;
+
+    type Config = MultisigConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::CreateMultisig {
+                members,
+                threshold,
+                salt,
+            } => {
+                self.create_multisig(members, threshold, salt, context, working_set)?;
+                Ok(CallResponse::default())
+            }
+
+            call::CallMessage::Propose {
+                multisig,
+                call,
+                expires_in,
+            } => {
+                self.propose(multisig, call, expires_in, context, working_set)?;
+                Ok(CallResponse::default())
+            }
+
+            call::CallMessage::Approve {
+                multisig,
+                proposal_id,
+            } => Ok(self.approve(multisig, proposal_id, context, working_set)?),
+
+            call::CallMessage::Cancel {
+                multisig,
+                proposal_id,
+            } => Ok(self.cancel(multisig, proposal_id, context, working_set)?),
+
+            call::CallMessage::UpdateMembers { members, threshold } => {
+                Ok(self.update_members(members, threshold, context, working_set)?)
+            }
+        }
+    }
+}
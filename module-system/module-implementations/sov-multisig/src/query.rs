@@ -0,0 +1,57 @@
+//! Defines rpc queries exposed by the multisig module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::{Multisig, MultisigAccount, Proposal, ProposalId};
+
+/// Structure returned by the `getMultisig` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct MultisigResponse<C: sov_modules_api::Context> {
+    /// The multisig account, if it exists.
+    pub multisig: Option<MultisigAccount<C>>,
+}
+
+/// Structure returned by the `getProposal` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct ProposalResponse<C: sov_modules_api::Context> {
+    /// The proposal, if it is pending.
+    pub proposal: Option<Proposal<C>>,
+}
+
+#[rpc_gen(client, server, namespace = "multisig")]
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Multisig<C, Rt> {
+    #[rpc_method(name = "getMultisig")]
+    /// Rpc method that returns the members, the threshold and the next proposal identifier of the multisig
+    /// account stored at `multisig`.
+    pub fn multisig(
+        &self,
+        multisig: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<MultisigResponse<C>> {
+        Ok(MultisigResponse {
+            multisig: self.get_multisig(&multisig, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getProposal")]
+    /// Rpc method that returns the pending proposal `proposal_id` of the multisig account stored at `multisig`.
+    pub fn proposal(
+        &self,
+        multisig: C::Address,
+        proposal_id: ProposalId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ProposalResponse<C>> {
+        Ok(ProposalResponse {
+            proposal: self.get_pending_proposal(&multisig, proposal_id, working_set),
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use sov_modules_api::digest::Digest;
+
+use crate::genesis::DEPLOYER;
+
+/// Derives the address of a multisig account from its `creator` and a `salt`.
+pub fn get_multisig_address<C: sov_modules_api::Context>(creator: &[u8], salt: u64) -> C::Address {
+    let mut hasher = C::Hasher::new();
+    hasher.update(b"multisig");
+    hasher.update(creator);
+    hasher.update(salt.to_le_bytes());
+
+    let hash: [u8; 32] = hasher.finalize().into();
+    C::Address::from(hash)
+}
+
+/// Gets the address of a multisig account created at genesis, using the `DEPLOYER` address as the creator.
+pub fn get_genesis_multisig_address<C: sov_modules_api::Context>(salt: u64) -> C::Address {
+    get_multisig_address::<C>(&DEPLOYER, salt)
+}
@@ -0,0 +1,332 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, Module, WorkingSet,
+};
+use sov_multisig::{
+    get_genesis_multisig_address, CallMessage, Multisig, MultisigAccountConfig, MultisigConfig,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    bank: Bank<C>,
+    multisig: Multisig<C, TestRuntime<C>>,
+}
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    multisig: Address,
+    members: [Address; 3],
+    receiver: Address,
+    token_address: Address,
+}
+
+fn setup(working_set: &mut WorkingSet<C>) -> TestSetup {
+    let members = [
+        generate_address::<C>("member_1"),
+        generate_address::<C>("member_2"),
+        generate_address::<C>("member_3"),
+    ];
+    let multisig = get_genesis_multisig_address::<C>(0);
+    let receiver = generate_address::<C>("receiver");
+
+    let token_name = "Treasury".to_owned();
+    let bank_config = BankConfig::<C> {
+        tokens: vec![TokenConfig {
+            token_name: token_name.clone(),
+            address_and_balances: vec![(multisig, 100)],
+            authorized_minters: vec![],
+            admins: vec![],
+            max_supply: None,
+            metadata: None,
+            salt: 0,
+        }],
+        fee_settlement: Default::default(),
+    };
+    let multisig_config = MultisigConfig::<C> {
+        multisigs: vec![MultisigAccountConfig {
+            members: members.to_vec(),
+            threshold: 2,
+            salt: 0,
+        }],
+    };
+
+    let runtime = TestRuntime::<C>::default();
+    runtime.bank.genesis(&bank_config, working_set).unwrap();
+    runtime
+        .multisig
+        .genesis(&multisig_config, working_set)
+        .unwrap();
+
+    TestSetup {
+        runtime,
+        multisig,
+        members,
+        receiver,
+        token_address: get_genesis_token_address::<C>(&token_name, 0),
+    }
+}
+
+fn transfer_call(setup: &TestSetup, amount: u64) -> Vec<u8> {
+    <TestRuntime<C> as EncodeCall<Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+        to: setup.receiver,
+        coins: Coins {
+            amount,
+            token_address: setup.token_address,
+        },
+    })
+}
+
+#[test]
+fn propose_approve_and_execute() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = setup(&mut working_set);
+    let multisig = &setup.runtime.multisig;
+    let sequencer = generate_address::<C>("sequencer");
+    let member_context = |index: usize| C::new(setup.members[index], sequencer, 1);
+    let outsider_context = C::new(generate_address::<C>("outsider"), sequencer, 1);
+
+    // Only the calls of the runtime can be proposed
+    let invalid_proposal = CallMessage::Propose {
+        multisig: setup.multisig,
+        call: vec![0xff; 4],
+        expires_in: 5,
+    };
+    assert!(multisig
+        .call(invalid_proposal, &member_context(0), &mut working_set)
+        .is_err());
+
+    let proposal = CallMessage::Propose {
+        multisig: setup.multisig,
+        call: transfer_call(&setup, 10),
+        expires_in: 5,
+    };
+    assert!(multisig
+        .call(proposal.clone(), &outsider_context, &mut working_set)
+        .is_err());
+    multisig
+        .call(proposal, &member_context(0), &mut working_set)
+        .expect("Propose call failed");
+
+    let pending = multisig
+        .get_pending_proposal(&setup.multisig, 0, &mut working_set)
+        .expect("The proposal should be pending");
+    assert_eq!(vec![setup.members[0]], pending.approvals);
+    assert_eq!(
+        None,
+        setup
+            .runtime
+            .bank
+            .get_balance_of(setup.receiver, setup.token_address, &mut working_set)
+    );
+
+    let approve = CallMessage::Approve {
+        multisig: setup.multisig,
+        proposal_id: 0,
+    };
+    assert!(multisig
+        .call(approve.clone(), &outsider_context, &mut working_set)
+        .is_err());
+    assert!(multisig
+        .call(approve.clone(), &member_context(0), &mut working_set)
+        .is_err());
+
+    // The second approval meets the threshold, so the transfer is executed by the multisig
+    multisig
+        .call(approve.clone(), &member_context(1), &mut working_set)
+        .expect("Approve call failed");
+    assert_eq!(
+        Some(10),
+        setup
+            .runtime
+            .bank
+            .get_balance_of(setup.receiver, setup.token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(90),
+        setup
+            .runtime
+            .bank
+            .get_balance_of(setup.multisig, setup.token_address, &mut working_set)
+    );
+    assert_eq!(
+        None,
+        multisig.get_pending_proposal(&setup.multisig, 0, &mut working_set)
+    );
+
+    // An executed proposal can't be approved again
+    assert!(multisig
+        .call(approve, &member_context(2), &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn expire_and_cancel_proposals() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = setup(&mut working_set);
+    let multisig = &setup.runtime.multisig;
+    let sequencer = generate_address::<C>("sequencer");
+    let member_context = |index: usize, height| C::new(setup.members[index], sequencer, height);
+
+    let proposal = CallMessage::Propose {
+        multisig: setup.multisig,
+        call: transfer_call(&setup, 10),
+        expires_in: 2,
+    };
+    multisig
+        .call(proposal.clone(), &member_context(0, 1), &mut working_set)
+        .expect("Propose call failed");
+
+    let approve = CallMessage::Approve {
+        multisig: setup.multisig,
+        proposal_id: 0,
+    };
+    assert!(multisig
+        .call(approve, &member_context(1, 4), &mut working_set)
+        .is_err());
+
+    // Other members can only cancel the proposal once expired
+    let cancel = |proposal_id| CallMessage::Cancel {
+        multisig: setup.multisig,
+        proposal_id,
+    };
+    assert!(multisig
+        .call(cancel(0), &member_context(1, 3), &mut working_set)
+        .is_err());
+    multisig
+        .call(cancel(0), &member_context(1, 4), &mut working_set)
+        .expect("Cancel call failed");
+    assert_eq!(
+        None,
+        multisig.get_pending_proposal(&setup.multisig, 0, &mut working_set)
+    );
+
+    // The proposer can cancel the proposal at any time
+    multisig
+        .call(proposal, &member_context(0, 4), &mut working_set)
+        .expect("Propose call failed");
+    multisig
+        .call(cancel(1), &member_context(0, 4), &mut working_set)
+        .expect("Cancel call failed");
+    assert_eq!(
+        None,
+        setup
+            .runtime
+            .bank
+            .get_balance_of(setup.receiver, setup.token_address, &mut working_set)
+    );
+}
+
+#[test]
+fn update_members_through_a_proposal() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = setup(&mut working_set);
+    let multisig = &setup.runtime.multisig;
+    let sequencer = generate_address::<C>("sequencer");
+    let member_context = |index: usize| C::new(setup.members[index], sequencer, 1);
+
+    let update_members = CallMessage::UpdateMembers {
+        members: vec![setup.members[0]],
+        threshold: 1,
+    };
+    // Only the multisig account itself can update its members
+    assert!(multisig
+        .call(update_members.clone(), &member_context(0), &mut working_set)
+        .is_err());
+
+    let proposal = CallMessage::Propose {
+        multisig: setup.multisig,
+        call: <TestRuntime<C> as EncodeCall<Multisig<C, TestRuntime<C>>>>::encode_call(
+            update_members,
+        ),
+        expires_in: 5,
+    };
+    multisig
+        .call(proposal, &member_context(0), &mut working_set)
+        .expect("Propose call failed");
+    multisig
+        .call(
+            CallMessage::Approve {
+                multisig: setup.multisig,
+                proposal_id: 0,
+            },
+            &member_context(2),
+            &mut working_set,
+        )
+        .expect("Approve call failed");
+
+    let account = multisig
+        .get_multisig(&setup.multisig, &mut working_set)
+        .expect("The multisig should exist");
+    assert_eq!(vec![setup.members[0]], account.members);
+    assert_eq!(1, account.threshold);
+
+    // The remaining member now executes calls alone
+    multisig
+        .call(
+            CallMessage::Propose {
+                multisig: setup.multisig,
+                call: transfer_call(&setup, 10),
+                expires_in: 5,
+            },
+            &member_context(0),
+            &mut working_set,
+        )
+        .expect("Propose call failed");
+    assert_eq!(
+        Some(10),
+        setup
+            .runtime
+            .bank
+            .get_balance_of(setup.receiver, setup.token_address, &mut working_set)
+    );
+}
+
+#[test]
+fn invalid_members_and_threshold() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = setup(&mut working_set);
+    let multisig = &setup.runtime.multisig;
+    let creator_context = C::new(setup.members[0], generate_address::<C>("sequencer"), 1);
+
+    let create = |members: Vec<Address>, threshold| CallMessage::CreateMultisig {
+        members,
+        threshold,
+        salt: 0,
+    };
+    for invalid in [
+        create(vec![], 0),
+        create(vec![setup.members[0], setup.members[1]], 0),
+        create(vec![setup.members[0], setup.members[1]], 3),
+        create(vec![setup.members[0], setup.members[0]], 1),
+    ] {
+        assert!(multisig
+            .call(invalid, &creator_context, &mut working_set)
+            .is_err());
+    }
+
+    multisig
+        .call(
+            create(setup.members.to_vec(), 2),
+            &creator_context,
+            &mut working_set,
+        )
+        .expect("CreateMultisig call failed");
+    assert!(multisig
+        .call(
+            create(setup.members.to_vec(), 2),
+            &creator_context,
+            &mut working_set
+        )
+        .is_err());
+}
@@ -1,9 +1,13 @@
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
+use sov_bank::Coins;
 use sov_modules_api::{CallResponse, Context, StateMapAccessor, WorkingSet};
 
 use crate::address::UserAddress;
-use crate::offchain::{update_collection, update_nft};
-use crate::{Collection, CollectionAddress, Nft, NftIdentifier, NonFungibleToken, TokenId};
+use crate::offchain::{delete_nft, update_collection, update_nft};
+use crate::{
+    Collection, CollectionAddress, Listing, Nft, NftIdentifier, NonFungibleToken, OwnedNft,
+    OwnerAddress, Royalty, TokenId, ROYALTY_BASIS_POINTS,
+};
 
 #[cfg_attr(
     feature = "native",
@@ -24,6 +28,8 @@ pub enum CallMessage<C: Context> {
         name: String,
         /// meta data url for collection
         collection_uri: String,
+        /// Optional royalty paid to a recipient when an NFT of the collection is sold through the module
+        royalty: Option<Royalty<C>>,
     },
     /// update collection metadata
     UpdateCollection {
@@ -72,6 +78,54 @@ pub enum CallMessage<C: Context> {
         /// Target address of the user to transfer the NFT to
         to: UserAddress<C>,
     },
+    /// Allow another address to transfer or burn an owned NFT, until the NFT changes hands
+    ApproveNft {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the owned token
+        token_id: TokenId,
+        /// Address allowed to transfer the NFT. Replaces any previous approval
+        approved: UserAddress<C>,
+    },
+    /// Revoke the approval of an owned NFT
+    RevokeApproval {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the owned token
+        token_id: TokenId,
+    },
+    /// Burn an NFT, as its owner or an approved address
+    BurnNft {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the token to burn
+        token_id: TokenId,
+    },
+    /// Offer an owned NFT for sale, replacing any previous listing
+    ListNft {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the owned token
+        token_id: TokenId,
+        /// Price paid by the buyer
+        price: Coins<C>,
+    },
+    /// Withdraw an owned NFT from sale
+    CancelListing {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the owned token
+        token_id: TokenId,
+    },
+    /// Buy a listed NFT. The royalty of the collection is paid out of the price
+    BuyNft {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the listed token
+        token_id: TokenId,
+        /// Expected price, which must match the listing
+        price: Coins<C>,
+    },
 }
 
 impl<C: Context> NonFungibleToken<C> {
@@ -79,12 +133,14 @@ impl<C: Context> NonFungibleToken<C> {
         &self,
         collection_name: &str,
         collection_uri: &str,
+        royalty: Option<Royalty<C>>,
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
         let (collection_address, collection) = Collection::new(
             collection_name,
             collection_uri,
+            royalty,
             &self.collections,
             context,
             working_set,
@@ -163,11 +219,16 @@ impl<C: Context> NonFungibleToken<C> {
             &self.nfts,
             working_set,
         )?;
-        self.nfts.set(
-            &NftIdentifier(token_id, collection_address.clone()),
-            &new_nft,
-            working_set,
-        );
+        let nft_id = NftIdentifier(token_id, collection_address.clone());
+        self.nfts.set(&nft_id, &new_nft, working_set);
+        self.add_owned_nft(new_nft.get_owner(), &nft_id, working_set);
+        let mut collection_nfts = self
+            .collection_nfts
+            .get(&collection_address, working_set)
+            .unwrap_or_default();
+        collection_nfts.push(token_id);
+        self.collection_nfts
+            .set(&collection_address, &collection_nfts, working_set);
         collection.increment_supply();
         self.collections
             .set(&collection_address, collection.inner(), working_set);
@@ -186,16 +247,15 @@ impl<C: Context> NonFungibleToken<C> {
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
-        let mut owned_nft =
-            Nft::get_owned_nft(nft_id, collection_address, &self.nfts, context, working_set)?;
-        let original_owner = owned_nft.inner().get_owner().clone();
-        owned_nft.set_owner(to);
-        self.nfts.set(
-            &NftIdentifier(nft_id, collection_address.clone()),
-            owned_nft.inner(),
+        let owned_nft = Nft::get_transferable_nft(
+            nft_id,
+            collection_address,
+            &self.nfts,
+            &self.approvals,
+            context,
             working_set,
-        );
-        update_nft(owned_nft.inner(), Some(original_owner.clone()));
+        )?;
+        self.move_nft(owned_nft, to, working_set);
         Ok(CallResponse::default())
     }
 
@@ -230,4 +290,271 @@ impl<C: Context> NonFungibleToken<C> {
         update_nft(mutable_nft.inner(), None);
         Ok(CallResponse::default())
     }
+
+    pub(crate) fn approve_nft(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        approved: &UserAddress<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        Nft::get_owned_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            context,
+            working_set,
+        )?;
+        self.approvals.set(
+            &NftIdentifier(token_id, collection_address.clone()),
+            approved,
+            working_set,
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn revoke_approval(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        Nft::get_owned_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            context,
+            working_set,
+        )?;
+        self.approvals.delete(
+            &NftIdentifier(token_id, collection_address.clone()),
+            working_set,
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn burn_nft(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owned_nft = Nft::get_transferable_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            &self.approvals,
+            context,
+            working_set,
+        )?;
+        let nft_id = NftIdentifier(token_id, collection_address.clone());
+        self.nfts.delete(&nft_id, working_set);
+        self.approvals.delete(&nft_id, working_set);
+        self.listings.delete(&nft_id, working_set);
+        self.remove_owned_nft(owned_nft.inner().get_owner(), &nft_id, working_set);
+
+        let mut collection_nfts = self
+            .collection_nfts
+            .get(collection_address, working_set)
+            .unwrap_or_default();
+        collection_nfts.retain(|id| *id != token_id);
+        self.collection_nfts
+            .set(collection_address, &collection_nfts, working_set);
+
+        let mut collection = self
+            .collections
+            .get(collection_address, working_set)
+            .with_context(|| format!("Collection {} does not exist", collection_address))?;
+        collection.decrement_supply();
+        self.collections
+            .set(collection_address, &collection, working_set);
+
+        update_collection(&collection);
+        delete_nft(owned_nft.inner());
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn list_nft(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        price: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owned_nft = Nft::get_owned_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            context,
+            working_set,
+        )?;
+        if price.amount == 0 {
+            bail!("NFT with token_id: {} can't be listed for free", token_id)
+        }
+        let listing = Listing {
+            seller: owned_nft.inner().get_owner().clone(),
+            price,
+        };
+        self.listings.set(
+            &NftIdentifier(token_id, collection_address.clone()),
+            &listing,
+            working_set,
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn cancel_listing(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        Nft::get_owned_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            context,
+            working_set,
+        )?;
+        self.listings.delete(
+            &NftIdentifier(token_id, collection_address.clone()),
+            working_set,
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Settles the sale of a listed NFT: the royalty of the collection is paid to its recipient,
+    /// the rest of the price to the seller, and the NFT is transferred to the buyer.
+    pub(crate) fn buy_nft(
+        &self,
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        price: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let listing = self
+            .listings
+            .get(
+                &NftIdentifier(token_id, collection_address.clone()),
+                working_set,
+            )
+            .with_context(|| {
+                format!(
+                    "Nft with token_id: {} in collection_address: {} is not listed",
+                    token_id, collection_address
+                )
+            })?;
+        if listing.price != price {
+            bail!(
+                "Nft with token_id: {} in collection_address: {} is listed for {}, not {}",
+                token_id,
+                collection_address,
+                listing.price,
+                price
+            )
+        }
+        let owned_nft = Nft::get_listed_nft(
+            token_id,
+            collection_address,
+            &self.nfts,
+            &listing.seller,
+            working_set,
+        )?;
+
+        let royalty = self
+            .collections
+            .get(collection_address, working_set)
+            .and_then(|collection| collection.get_royalty().cloned());
+        let mut seller_amount = price.amount;
+        if let Some(royalty) = royalty {
+            let royalty_amount = (price.amount as u128 * royalty.basis_points as u128
+                / ROYALTY_BASIS_POINTS as u128) as u64;
+            if royalty_amount > 0 {
+                let coins = Coins {
+                    amount: royalty_amount,
+                    token_address: price.token_address.clone(),
+                };
+                self.bank
+                    .transfer_from(
+                        context.sender(),
+                        royalty.recipient.get_address(),
+                        coins,
+                        working_set,
+                    )
+                    .context("Failed to pay the royalty")?;
+                seller_amount -= royalty_amount;
+            }
+        }
+        if seller_amount > 0 {
+            let coins = Coins {
+                amount: seller_amount,
+                token_address: price.token_address,
+            };
+            self.bank
+                .transfer_from(
+                    context.sender(),
+                    listing.seller.get_address(),
+                    coins,
+                    working_set,
+                )
+                .context("Failed to pay the seller")?;
+        }
+
+        let buyer = UserAddress::new(context.sender());
+        self.move_nft(owned_nft, &buyer, working_set);
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the NFT to `to`, and clears its approval and listing
+    fn move_nft(
+        &self,
+        mut owned_nft: OwnedNft<C>,
+        to: &UserAddress<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let original_owner = owned_nft.inner().get_owner().clone();
+        owned_nft.set_owner(to);
+        let nft_id = NftIdentifier(
+            owned_nft.inner().get_token_id(),
+            owned_nft.inner().get_collection_address().clone(),
+        );
+        self.nfts.set(&nft_id, owned_nft.inner(), working_set);
+        self.approvals.delete(&nft_id, working_set);
+        self.listings.delete(&nft_id, working_set);
+        self.remove_owned_nft(&original_owner, &nft_id, working_set);
+        self.add_owned_nft(owned_nft.inner().get_owner(), &nft_id, working_set);
+        update_nft(owned_nft.inner(), Some(original_owner));
+    }
+
+    fn add_owned_nft(
+        &self,
+        owner: &OwnerAddress<C>,
+        nft_id: &NftIdentifier<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut owned_nfts = self.owned_nfts.get(owner, working_set).unwrap_or_default();
+        owned_nfts.push(nft_id.clone());
+        self.owned_nfts.set(owner, &owned_nfts, working_set);
+    }
+
+    fn remove_owned_nft(
+        &self,
+        owner: &OwnerAddress<C>,
+        nft_id: &NftIdentifier<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut owned_nfts = self.owned_nfts.get(owner, working_set).unwrap_or_default();
+        owned_nfts.retain(|id| id != nft_id);
+        if owned_nfts.is_empty() {
+            self.owned_nfts.delete(owner, working_set);
+        } else {
+            self.owned_nfts.set(owner, &owned_nfts, working_set);
+        }
+    }
 }
@@ -3,7 +3,29 @@ use sov_modules_api::{Context, StateMap, StateMapAccessor, WorkingSet};
 
 use crate::address::CollectionAddress;
 use crate::utils::get_collection_address;
-use crate::CreatorAddress;
+use crate::{CreatorAddress, UserAddress};
+
+/// The denominator of the royalty rates.
+pub const ROYALTY_BASIS_POINTS: u64 = 10_000;
+
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "Royalty")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+/// Royalty paid to the creator of a collection when one of its NFTs is sold through the module
+pub struct Royalty<C: Context> {
+    /// Address receiving the royalty
+    pub recipient: UserAddress<C>,
+    /// Share of the sale price paid as royalty, in basis points
+    pub basis_points: u64,
+}
 
 #[cfg_attr(
     feature = "native",
@@ -29,6 +51,8 @@ pub struct Collection<C: Context> {
     supply: u64,
     /// collection metadata stored at this url
     collection_uri: String,
+    /// Optional royalty enforced on the sales of the NFTs of the collection
+    royalty: Option<Royalty<C>>,
 }
 
 pub enum CollectionState<C: Context> {
@@ -53,6 +77,7 @@ impl<C: Context> Collection<C> {
     pub fn new(
         collection_name: &str,
         collection_uri: &str,
+        royalty: Option<Royalty<C>>,
         collections: &StateMap<CollectionAddress<C>, Collection<C>>,
         context: &C,
         working_set: &mut WorkingSet<C>,
@@ -66,6 +91,15 @@ impl<C: Context> Collection<C> {
                 collection_name,
                 creator
             ))
+        } else if royalty
+            .as_ref()
+            .is_some_and(|royalty| royalty.basis_points > ROYALTY_BASIS_POINTS)
+        {
+            Err(anyhow!(
+                "Royalty of collection with name: {} can't exceed {} basis points",
+                collection_name,
+                ROYALTY_BASIS_POINTS
+            ))
         } else {
             Ok((
                 collection_address,
@@ -75,6 +109,7 @@ impl<C: Context> Collection<C> {
                     frozen: false,
                     supply: 0,
                     collection_uri: collection_uri.to_string(),
+                    royalty,
                 },
             ))
         }
@@ -132,6 +167,15 @@ impl<C: Context> Collection<C> {
     pub fn get_collection_uri(&self) -> &str {
         &self.collection_uri
     }
+    #[allow(dead_code)]
+    pub fn get_royalty(&self) -> Option<&Royalty<C>> {
+        self.royalty.as_ref()
+    }
+
+    /// Burning is allowed in frozen collections, so the supply can still decrease once frozen
+    pub fn decrement_supply(&mut self) {
+        self.supply -= 1;
+    }
 }
 
 // We use a NewType instead of &mut on the Collection because we don't want all
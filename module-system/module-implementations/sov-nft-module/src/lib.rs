@@ -9,6 +9,9 @@ pub use address::*;
 pub use genesis::*;
 mod collection;
 use collection::*;
+pub use collection::{Royalty, ROYALTY_BASIS_POINTS};
+mod market;
+pub use market::*;
 mod nft;
 use nft::*;
 #[cfg(feature = "native")]
@@ -38,6 +41,26 @@ pub struct NonFungibleToken<C: Context> {
     #[state]
     /// Mapping of tokens to their owners
     nfts: StateMap<NftIdentifier<C>, Nft<C>>,
+
+    #[state]
+    /// Mapping of tokens to the address their owner approved to transfer them
+    approvals: StateMap<NftIdentifier<C>, UserAddress<C>>,
+
+    #[state]
+    /// Mapping of tokens to their listing, when their owner offers them for sale
+    listings: StateMap<NftIdentifier<C>, Listing<C>>,
+
+    #[state]
+    /// Mapping of owners to the tokens they own
+    owned_nfts: StateMap<OwnerAddress<C>, Vec<NftIdentifier<C>>>,
+
+    #[state]
+    /// Mapping of collections to the ids of their tokens, in minting order
+    collection_nfts: StateMap<CollectionAddress<C>, Vec<TokenId>>,
+
+    #[module]
+    /// Reference to the Bank module, which settles the sales
+    bank: sov_bank::Bank<C>,
 }
 
 impl<C: Context> Module for NonFungibleToken<C> {
@@ -67,7 +90,8 @@ impl<C: Context> Module for NonFungibleToken<C> {
             CallMessage::CreateCollection {
                 name,
                 collection_uri,
-            } => self.create_collection(&name, &collection_uri, context, working_set),
+                royalty,
+            } => self.create_collection(&name, &collection_uri, royalty, context, working_set),
             CallMessage::FreezeCollection { collection_name } => {
                 self.freeze_collection(&collection_name, context, working_set)
             }
@@ -108,6 +132,39 @@ impl<C: Context> Module for NonFungibleToken<C> {
                 context,
                 working_set,
             ),
+            CallMessage::ApproveNft {
+                collection_address,
+                token_id,
+                approved,
+            } => self.approve_nft(
+                token_id,
+                &collection_address,
+                &approved,
+                context,
+                working_set,
+            ),
+            CallMessage::RevokeApproval {
+                collection_address,
+                token_id,
+            } => self.revoke_approval(token_id, &collection_address, context, working_set),
+            CallMessage::BurnNft {
+                collection_address,
+                token_id,
+            } => self.burn_nft(token_id, &collection_address, context, working_set),
+            CallMessage::ListNft {
+                collection_address,
+                token_id,
+                price,
+            } => self.list_nft(token_id, &collection_address, price, context, working_set),
+            CallMessage::CancelListing {
+                collection_address,
+                token_id,
+            } => self.cancel_listing(token_id, &collection_address, context, working_set),
+            CallMessage::BuyNft {
+                collection_address,
+                token_id,
+                price,
+            } => self.buy_nft(token_id, &collection_address, price, context, working_set),
         };
         Ok(call_result?)
     }
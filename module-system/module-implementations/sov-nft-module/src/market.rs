@@ -0,0 +1,19 @@
+use sov_bank::Coins;
+use sov_modules_api::Context;
+
+use crate::OwnerAddress;
+
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+/// An NFT offered for sale by its owner. The sale is settled by the module, which enforces
+/// the royalty of the collection
+pub struct Listing<C: Context> {
+    /// Owner of the NFT when it was listed. The listing is void if the NFT changed hands since
+    pub seller: OwnerAddress<C>,
+    /// Price paid by the buyer
+    pub price: Coins<C>,
+}
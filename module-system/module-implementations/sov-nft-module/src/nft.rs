@@ -134,6 +134,50 @@ impl<C: Context> Nft<C> {
         OwnedNft::new(nft, context)
     }
 
+    /// Returns the NFT if the sender owns it, or was approved by its owner to transfer it
+    pub fn get_transferable_nft(
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        nfts: &StateMap<NftIdentifier<C>, Nft<C>>,
+        approvals: &StateMap<NftIdentifier<C>, UserAddress<C>>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<OwnedNft<C>> {
+        let nft_identifier = NftIdentifier(token_id, collection_address.clone());
+        let approved = approvals.get(&nft_identifier, working_set);
+        if approved.as_ref().map(|approved| approved.get_address()) == Some(context.sender()) {
+            let nft = nfts
+                .get(&nft_identifier, working_set)
+                .ok_or_else(|| anyhow!("NFT not found"))?;
+            return Ok(OwnedNft(nft));
+        }
+        Self::get_owned_nft(token_id, collection_address, nfts, context, working_set)
+    }
+
+    /// Returns the NFT of a listing, which is void if `seller` no longer owns the NFT
+    pub fn get_listed_nft(
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        nfts: &StateMap<NftIdentifier<C>, Nft<C>>,
+        seller: &OwnerAddress<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<OwnedNft<C>> {
+        let nft = nfts
+            .get(
+                &NftIdentifier(token_id, collection_address.clone()),
+                working_set,
+            )
+            .ok_or_else(|| anyhow!("NFT not found"))?;
+        if &nft.owner != seller {
+            bail!(
+                "Listing of nft with token_id: {} in collection_address: {} is void, the NFT changed hands",
+                token_id,
+                collection_address
+            )
+        }
+        Ok(OwnedNft(nft))
+    }
+
     pub fn get_mutable_nft(
         token_id: TokenId,
         collection_name: &str,
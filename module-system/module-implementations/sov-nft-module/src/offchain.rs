@@ -121,3 +121,23 @@ pub fn update_nft<C: sov_modules_api::Context>(nft: &Nft<C>, old_owner: Option<O
         }
     })
 }
+
+/// Removes a burnt NFT from the table "nfts" in postgres, and decrements the count of NFTs
+/// held by its last owner in the collection.
+#[offchain]
+pub fn delete_nft<C: sov_modules_api::Context>(nft: &Nft<C>) {
+    let collection_address = nft.get_collection_address().to_string();
+    let nft_id = nft.get_token_id();
+    let owner_str = nft.get_owner().to_string();
+
+    tokio::task::block_in_place(|| {
+        if let Ok(conn_string) = std::env::var("POSTGRES_CONNECTION_STRING") {
+            let mut client = postgres::Client::connect(&conn_string, NoTls).unwrap();
+            let _ = client.execute(
+                DECREMENT_COUNT_FOR_OLD_OWNER,
+                &[&owner_str, &collection_address],
+            );
+            let _ = client.execute(DELETE_NFT, &[&collection_address, &(nft_id as i64)]);
+        }
+    })
+}
@@ -4,13 +4,17 @@ use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
 
 use crate::utils::get_collection_address;
 use crate::{
-    CollectionAddress, CreatorAddress, NftIdentifier, NonFungibleToken, OwnerAddress, TokenId,
+    CollectionAddress, CreatorAddress, Listing, NftIdentifier, NonFungibleToken, OwnerAddress,
+    Royalty, TokenId, UserAddress,
 };
 
+/// Maximum number of token ids returned by one `getCollectionNfts` call
+pub const MAX_LISTED_NFTS: usize = 100;
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(bound(
-    serialize = "CreatorAddress<C>: serde::Serialize",
-    deserialize = "CreatorAddress<C>: serde::Deserialize<'de>"
+    serialize = "CreatorAddress<C>: serde::Serialize, Royalty<C>: serde::Serialize",
+    deserialize = "CreatorAddress<C>: serde::Deserialize<'de>, Royalty<C>: serde::Deserialize<'de>"
 ))]
 /// Response for `getCollection` method
 pub struct CollectionResponse<C: Context> {
@@ -24,6 +28,8 @@ pub struct CollectionResponse<C: Context> {
     pub supply: u64,
     /// Collection metadata uri
     pub collection_uri: String,
+    /// Royalty paid on the sales settled by the module
+    pub royalty: Option<Royalty<C>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -76,6 +82,7 @@ impl<C: Context> NonFungibleToken<C> {
             frozen: c.is_frozen(),
             supply: c.get_supply(),
             collection_uri: c.get_collection_uri().to_string(),
+            royalty: c.get_royalty().cloned(),
         })
     }
     #[rpc_method(name = "getCollectionAddress")]
@@ -109,4 +116,69 @@ impl<C: Context> NonFungibleToken<C> {
             collection_address: n.get_collection_address().clone(),
         })
     }
+
+    #[rpc_method(name = "getOwner")]
+    /// Get the owner of an NFT, if it exists
+    pub fn get_owner(
+        &self,
+        collection_address: CollectionAddress<C>,
+        token_id: TokenId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<OwnerAddress<C>>> {
+        let nft_id = NftIdentifier(token_id, collection_address);
+        Ok(self
+            .nfts
+            .get(&nft_id, working_set)
+            .map(|n| n.get_owner().clone()))
+    }
+    #[rpc_method(name = "getNftsOf")]
+    /// Get the NFTs owned by an address, across collections
+    pub fn get_nfts_of(
+        &self,
+        owner: OwnerAddress<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<NftIdentifier<C>>> {
+        Ok(self.owned_nfts.get(&owner, working_set).unwrap_or_default())
+    }
+    #[rpc_method(name = "getCollectionNfts")]
+    /// Get up to `limit` token ids of a collection, in minting order, skipping the first `offset`.
+    /// `limit` is capped at [`MAX_LISTED_NFTS`].
+    pub fn get_collection_nfts(
+        &self,
+        collection_address: CollectionAddress<C>,
+        offset: usize,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<TokenId>> {
+        Ok(self
+            .collection_nfts
+            .get(&collection_address, working_set)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(offset)
+            .take(limit.min(MAX_LISTED_NFTS))
+            .collect())
+    }
+    #[rpc_method(name = "getListing")]
+    /// Get the listing of an NFT, if it's for sale
+    pub fn get_listing(
+        &self,
+        collection_address: CollectionAddress<C>,
+        token_id: TokenId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Listing<C>>> {
+        let nft_id = NftIdentifier(token_id, collection_address);
+        Ok(self.listings.get(&nft_id, working_set))
+    }
+    #[rpc_method(name = "getApproval")]
+    /// Get the address approved to transfer an NFT, if any
+    pub fn get_approval(
+        &self,
+        collection_address: CollectionAddress<C>,
+        token_id: TokenId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<UserAddress<C>>> {
+        let nft_id = NftIdentifier(token_id, collection_address);
+        Ok(self.approvals.get(&nft_id, working_set))
+    }
 }
@@ -45,6 +45,7 @@ pub fn get_create_collection_message<C: sov_modules_api::Context>(
     CallMessage::<C>::CreateCollection {
         name: collection_name.to_string(),
         collection_uri,
+        royalty: None,
     }
 }
 
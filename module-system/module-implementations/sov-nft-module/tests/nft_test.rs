@@ -1,8 +1,12 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins, TokenConfig};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::utils::generate_address;
 use sov_modules_api::{Context, Module, WorkingSet};
 use sov_nft_module::utils::get_collection_address;
-use sov_nft_module::{CallMessage, NonFungibleToken, OwnerAddress, UserAddress};
+use sov_nft_module::{
+    CallMessage, NftIdentifier, NonFungibleToken, OwnerAddress, Royalty, UserAddress,
+};
 use sov_prover_storage_manager::new_orphan_storage;
 use sov_state::DefaultStorageSpec;
 
@@ -45,6 +49,7 @@ fn mints_and_transfers() {
     let create_collection_message = CallMessage::CreateCollection {
         name: collection_name.to_string(),
         collection_uri: collection_uri.to_string(),
+        royalty: None,
     };
 
     let creator_context = DefaultContext::new(creator_address, sequencer_address, 1);
@@ -455,3 +460,311 @@ fn mints_and_transfers() {
     // ensure supply hasn't changed with a transfer
     assert_eq!(actual_collection.supply, 1);
 }
+
+#[test]
+fn approves_and_burns() {
+    let creator_address = generate_address::<DefaultContext>("creator");
+    let owner_address = generate_address::<DefaultContext>("owner");
+    let operator_address = generate_address::<DefaultContext>("operator");
+    let sequencer_address = generate_address::<DefaultContext>("sequencer");
+    let collection_name = "Test Collection";
+    let collection_address =
+        get_collection_address::<DefaultContext>(collection_name, creator_address.as_ref());
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage::<DefaultStorageSpec>(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let nft = NonFungibleToken::default();
+
+    let creator_context = DefaultContext::new(creator_address, sequencer_address, 1);
+    let owner_context = DefaultContext::new(owner_address, sequencer_address, 1);
+    let operator_context = DefaultContext::new(operator_address, sequencer_address, 1);
+
+    nft.call(
+        CallMessage::CreateCollection {
+            name: collection_name.to_string(),
+            collection_uri: "http://foo.bar/test_collection".to_string(),
+            royalty: None,
+        },
+        &creator_context,
+        &mut working_set,
+    )
+    .expect("Creating Collection failed");
+    for token_id in [1, 2] {
+        nft.call(
+            CallMessage::MintNft {
+                collection_name: collection_name.to_string(),
+                token_uri: format!("http://foo.bar/test_collection/{}", token_id),
+                token_id,
+                owner: UserAddress::new(&owner_address),
+                frozen: false,
+            },
+            &creator_context,
+            &mut working_set,
+        )
+        .expect("Minting NFT failed");
+    }
+    assert_eq!(
+        nft.get_collection_nfts(collection_address.clone(), 0, 10, &mut working_set)
+            .unwrap(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        nft.get_nfts_of(OwnerAddress::new(&owner_address), &mut working_set)
+            .unwrap(),
+        vec![
+            NftIdentifier(1, collection_address.clone()),
+            NftIdentifier(2, collection_address.clone())
+        ]
+    );
+
+    // Only the owner can approve
+    nft.call(
+        CallMessage::ApproveNft {
+            collection_address: collection_address.clone(),
+            token_id: 1,
+            approved: UserAddress::new(&operator_address),
+        },
+        &operator_context,
+        &mut working_set,
+    )
+    .expect_err("The operator doesn't own the NFT");
+    nft.call(
+        CallMessage::ApproveNft {
+            collection_address: collection_address.clone(),
+            token_id: 1,
+            approved: UserAddress::new(&operator_address),
+        },
+        &owner_context,
+        &mut working_set,
+    )
+    .expect("Approving NFT failed");
+    assert_eq!(
+        nft.get_approval(collection_address.clone(), 1, &mut working_set)
+            .unwrap(),
+        Some(UserAddress::new(&operator_address))
+    );
+
+    // The approved address transfers the NFT, which clears the approval
+    nft.call(
+        CallMessage::TransferNft {
+            collection_address: collection_address.clone(),
+            token_id: 1,
+            to: UserAddress::new(&operator_address),
+        },
+        &operator_context,
+        &mut working_set,
+    )
+    .expect("Transfer by the approved address failed");
+    assert_eq!(
+        nft.get_owner(collection_address.clone(), 1, &mut working_set)
+            .unwrap(),
+        Some(OwnerAddress::new(&operator_address))
+    );
+    assert_eq!(
+        nft.get_approval(collection_address.clone(), 1, &mut working_set)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        nft.get_nfts_of(OwnerAddress::new(&owner_address), &mut working_set)
+            .unwrap(),
+        vec![NftIdentifier(2, collection_address.clone())]
+    );
+
+    // A revoked approval doesn't allow burning
+    for message in [
+        CallMessage::ApproveNft {
+            collection_address: collection_address.clone(),
+            token_id: 2,
+            approved: UserAddress::new(&operator_address),
+        },
+        CallMessage::RevokeApproval {
+            collection_address: collection_address.clone(),
+            token_id: 2,
+        },
+    ] {
+        nft.call(message, &owner_context, &mut working_set).unwrap();
+    }
+    nft.call(
+        CallMessage::BurnNft {
+            collection_address: collection_address.clone(),
+            token_id: 2,
+        },
+        &operator_context,
+        &mut working_set,
+    )
+    .expect_err("The approval was revoked");
+
+    // The owner burns the NFT
+    nft.call(
+        CallMessage::BurnNft {
+            collection_address: collection_address.clone(),
+            token_id: 2,
+        },
+        &owner_context,
+        &mut working_set,
+    )
+    .expect("Burning NFT failed");
+    assert_eq!(
+        nft.get_owner(collection_address.clone(), 2, &mut working_set)
+            .unwrap(),
+        None
+    );
+    assert!(nft
+        .get_nfts_of(OwnerAddress::new(&owner_address), &mut working_set)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        nft.get_collection_nfts(collection_address.clone(), 0, 10, &mut working_set)
+            .unwrap(),
+        vec![1]
+    );
+    let actual_collection = nft
+        .get_collection(collection_address, &mut working_set)
+        .unwrap();
+    assert_eq!(actual_collection.supply, 1);
+}
+
+#[test]
+fn sells_with_royalty() {
+    let creator_address = generate_address::<DefaultContext>("creator");
+    let seller_address = generate_address::<DefaultContext>("seller");
+    let buyer_address = generate_address::<DefaultContext>("buyer");
+    let sequencer_address = generate_address::<DefaultContext>("sequencer");
+    let collection_name = "Test Collection";
+    let collection_address =
+        get_collection_address::<DefaultContext>(collection_name, creator_address.as_ref());
+    let token_id = 42;
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage::<DefaultStorageSpec>(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let nft = NonFungibleToken::default();
+
+    let token_name = "PaymentToken".to_owned();
+    let token_address = get_genesis_token_address::<DefaultContext>(&token_name, 0);
+    let bank = Bank::<DefaultContext>::default();
+    let bank_config = BankConfig::<DefaultContext> {
+        tokens: vec![TokenConfig {
+            token_name,
+            address_and_balances: vec![(buyer_address, 1000)],
+            authorized_minters: vec![],
+            admins: vec![],
+            max_supply: None,
+            metadata: None,
+            salt: 0,
+        }],
+        fee_settlement: Default::default(),
+    };
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let creator_context = DefaultContext::new(creator_address, sequencer_address, 1);
+    let seller_context = DefaultContext::new(seller_address, sequencer_address, 1);
+    let buyer_context = DefaultContext::new(buyer_address, sequencer_address, 1);
+
+    // Royalties can't exceed the price
+    nft.call(
+        CallMessage::CreateCollection {
+            name: collection_name.to_string(),
+            collection_uri: "http://foo.bar/test_collection".to_string(),
+            royalty: Some(Royalty {
+                recipient: UserAddress::new(&creator_address),
+                basis_points: 10_001,
+            }),
+        },
+        &creator_context,
+        &mut working_set,
+    )
+    .expect_err("The royalty exceeds 100%");
+
+    let royalty = Royalty {
+        recipient: UserAddress::new(&creator_address),
+        basis_points: 500,
+    };
+    nft.call(
+        CallMessage::CreateCollection {
+            name: collection_name.to_string(),
+            collection_uri: "http://foo.bar/test_collection".to_string(),
+            royalty: Some(royalty.clone()),
+        },
+        &creator_context,
+        &mut working_set,
+    )
+    .expect("Creating Collection failed");
+    let actual_collection = nft
+        .get_collection(collection_address.clone(), &mut working_set)
+        .unwrap();
+    assert_eq!(actual_collection.royalty, Some(royalty));
+
+    nft.call(
+        CallMessage::MintNft {
+            collection_name: collection_name.to_string(),
+            token_uri: "http://foo.bar/test_collection/42".to_string(),
+            token_id,
+            owner: UserAddress::new(&seller_address),
+            frozen: false,
+        },
+        &creator_context,
+        &mut working_set,
+    )
+    .expect("Minting NFT failed");
+
+    let price = Coins {
+        amount: 200,
+        token_address,
+    };
+    nft.call(
+        CallMessage::ListNft {
+            collection_address: collection_address.clone(),
+            token_id,
+            price: price.clone(),
+        },
+        &seller_context,
+        &mut working_set,
+    )
+    .expect("Listing NFT failed");
+
+    // The buyer must agree to the listed price
+    nft.call(
+        CallMessage::BuyNft {
+            collection_address: collection_address.clone(),
+            token_id,
+            price: Coins {
+                amount: 100,
+                token_address,
+            },
+        },
+        &buyer_context,
+        &mut working_set,
+    )
+    .expect_err("The price doesn't match the listing");
+
+    nft.call(
+        CallMessage::BuyNft {
+            collection_address: collection_address.clone(),
+            token_id,
+            price,
+        },
+        &buyer_context,
+        &mut working_set,
+    )
+    .expect("Buying NFT failed");
+
+    assert_eq!(
+        nft.get_owner(collection_address.clone(), token_id, &mut working_set)
+            .unwrap(),
+        Some(OwnerAddress::new(&buyer_address))
+    );
+    assert_eq!(
+        nft.get_listing(collection_address, token_id, &mut working_set)
+            .unwrap(),
+        None
+    );
+    let balance_of = |address, working_set: &mut WorkingSet<DefaultContext>| -> Option<u64> {
+        bank.get_balance_of(address, token_address, working_set)
+    };
+    assert_eq!(balance_of(buyer_address, &mut working_set), Some(800));
+    assert_eq!(balance_of(creator_address, &mut working_set), Some(10));
+    assert_eq!(balance_of(seller_address, &mut working_set), Some(190));
+}
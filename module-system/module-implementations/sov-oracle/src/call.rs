@@ -0,0 +1,240 @@
+use anyhow::{bail, Result};
+use sov_modules_api::{CallResponse, Context, StateMapAccessor, StateValueAccessor, WorkingSet};
+
+use crate::events::{FeederAdded, FeederRemoved, PairAdded, PriceAggregated, PriceSubmitted};
+use crate::{median, AggregatedPrice, Oracle, Submission};
+
+/// The maximum number of feeders.
+pub const MAX_FEEDERS: usize = 32;
+
+/// The maximum length of the name of a pair, in bytes.
+pub const MAX_PAIR_LENGTH: usize = 32;
+
+/// This enumeration represents the available call messages for interacting with the sov-oracle module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CliWalletArg),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Posts the price of a pair. Only the feeders can post prices.
+    SubmitPrice {
+        /// The name of the pair, e.g. `ETH/USD`.
+        pair: String,
+        /// The price, with the decimals of the pair.
+        price: u64,
+    },
+    /// Allows an address to post prices. Only the admin can add feeders.
+    AddFeeder {
+        /// The address of the new feeder.
+        feeder: C::Address,
+    },
+    /// Disallows a feeder to post prices, and discards its prices. Only the admin can remove feeders.
+    RemoveFeeder {
+        /// The address of the feeder.
+        feeder: C::Address,
+    },
+    /// Adds a pair the feeders can post prices for. Only the admin can add pairs.
+    AddPair {
+        /// The name of the pair, e.g. `ETH/USD`.
+        pair: String,
+        /// The number of decimals of the prices of the pair.
+        decimals: u8,
+    },
+}
+
+impl<C: Context> Oracle<C> {
+    /// Records the price posted by the sender, and aggregates the fresh prices of `pair`.
+    pub(crate) fn submit_price(
+        &self,
+        pair: String,
+        price: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let feeder = context.sender();
+        if !self.is_feeder(feeder, working_set) {
+            bail!("Only the feeders can post prices, not {}", feeder)
+        }
+        if self.pairs.get(&pair, working_set).is_none() {
+            bail!("Pair {} isn't known by the oracle", pair)
+        }
+        if price == 0 {
+            bail!("The price of {} can't be zero", pair)
+        }
+
+        let submission = Submission {
+            price,
+            submitted_at: context.slot_height(),
+        };
+        self.submissions
+            .set(&(pair.clone(), feeder.clone()), &submission, working_set);
+        working_set.emit_event(&PriceSubmitted::<C> {
+            pair: pair.clone(),
+            feeder: feeder.clone(),
+            price,
+        });
+
+        self.aggregate(pair, context.slot_height(), working_set)?;
+        Ok(CallResponse::default())
+    }
+
+    /// Allows `feeder` to post prices.
+    pub(crate) fn add_feeder(
+        &self,
+        feeder: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+
+        let mut feeders = self.feeders.get(working_set).unwrap_or_default();
+        if feeders.contains(&feeder) {
+            bail!("Address {} is already a feeder", feeder)
+        }
+        if feeders.len() >= MAX_FEEDERS {
+            bail!("The oracle can't have more than {} feeders", MAX_FEEDERS)
+        }
+        feeders.push(feeder.clone());
+        self.feeders.set(&feeders, working_set);
+
+        working_set.emit_event(&FeederAdded::<C> { feeder });
+        Ok(CallResponse::default())
+    }
+
+    /// Disallows `feeder` to post prices, and discards the prices it posted.
+    /// The aggregated prices are left as they are until the next price is posted.
+    pub(crate) fn remove_feeder(
+        &self,
+        feeder: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+
+        let mut feeders = self.feeders.get(working_set).unwrap_or_default();
+        let Some(index) = feeders.iter().position(|f| f == &feeder) else {
+            bail!("Address {} isn't a feeder", feeder)
+        };
+        let min_submissions = self.min_submissions.get_or_err(working_set)?;
+        if feeders.len() <= min_submissions as usize {
+            bail!(
+                "The oracle needs at least {} feeders to aggregate prices",
+                min_submissions
+            )
+        }
+        feeders.remove(index);
+        self.feeders.set(&feeders, working_set);
+
+        for pair in self.pair_names.get(working_set).unwrap_or_default() {
+            self.submissions
+                .delete(&(pair, feeder.clone()), working_set);
+        }
+
+        working_set.emit_event(&FeederRemoved::<C> { feeder });
+        Ok(CallResponse::default())
+    }
+
+    /// Adds `pair`, with prices having `decimals` decimals.
+    pub(crate) fn add_pair(
+        &self,
+        pair: String,
+        decimals: u8,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+        self.insert_pair(pair.clone(), decimals, working_set)?;
+
+        working_set.emit_event(&PairAdded { pair, decimals });
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn insert_pair(
+        &self,
+        pair: String,
+        decimals: u8,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if pair.is_empty() || pair.len() > MAX_PAIR_LENGTH {
+            bail!(
+                "The name of a pair must have between 1 and {} bytes, {} has {}",
+                MAX_PAIR_LENGTH,
+                pair,
+                pair.len()
+            )
+        }
+        if self.pairs.get(&pair, working_set).is_some() {
+            bail!("Pair {} is already known by the oracle", pair)
+        }
+        self.pairs.set(&pair, &decimals, working_set);
+
+        let mut pair_names = self.pair_names.get(working_set).unwrap_or_default();
+        pair_names.push(pair);
+        self.pair_names.set(&pair_names, working_set);
+        Ok(())
+    }
+
+    /// Sets the aggregated price of `pair` to the median of the fresh prices posted by the feeders,
+    /// if there are at least `min_submissions` of them.
+    fn aggregate(
+        &self,
+        pair: String,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let max_staleness = self.max_staleness.get_or_err(working_set)?;
+        let min_submissions = self.min_submissions.get_or_err(working_set)?;
+
+        let mut prices = Vec::new();
+        for feeder in self.feeders.get(working_set).unwrap_or_default() {
+            let Some(submission) = self.submissions.get(&(pair.clone(), feeder), working_set)
+            else {
+                continue;
+            };
+            if slot_height.saturating_sub(submission.submitted_at) <= max_staleness {
+                prices.push(submission.price);
+            }
+        }
+
+        let feeder_count = prices.len() as u32;
+        if feeder_count < min_submissions {
+            return Ok(());
+        }
+        let Some(price) = median(prices) else {
+            return Ok(());
+        };
+
+        let aggregated = AggregatedPrice {
+            price,
+            updated_at: slot_height,
+            feeder_count,
+        };
+        self.prices.set(&pair, &aggregated, working_set);
+        working_set.emit_event(&PriceAggregated {
+            pair,
+            price,
+            feeder_count,
+        });
+        Ok(())
+    }
+
+    fn ensure_admin(&self, context: &C, working_set: &mut WorkingSet<C>) -> Result<()> {
+        let admin = self.admin.get_or_err(working_set)?;
+        if &admin != context.sender() {
+            bail!(
+                "Only the admin {} can manage the feeders and the pairs, not {}",
+                admin,
+                context.sender()
+            )
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! The typed events emitted by the oracle module.
+use sov_modules_api::ModuleEvent;
+
+/// Emitted when `feeder` posts the price of `pair`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "price_submitted")]
+pub struct PriceSubmitted<C: sov_modules_api::Context> {
+    /// The name of the pair.
+    #[indexed]
+    pub pair: String,
+    /// The address of the feeder.
+    #[indexed]
+    pub feeder: C::Address,
+    /// The posted price.
+    pub price: u64,
+}
+
+/// Emitted when the aggregated price of `pair` is updated.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "price_aggregated")]
+pub struct PriceAggregated {
+    /// The name of the pair.
+    #[indexed]
+    pub pair: String,
+    /// The median of the fresh prices of the pair.
+    pub price: u64,
+    /// The number of fresh prices the median was computed from.
+    pub feeder_count: u32,
+}
+
+/// Emitted when the admin allows `feeder` to post prices.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "feeder_added")]
+pub struct FeederAdded<C: sov_modules_api::Context> {
+    /// The address of the new feeder.
+    #[indexed]
+    pub feeder: C::Address,
+}
+
+/// Emitted when the admin removes `feeder`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "feeder_removed")]
+pub struct FeederRemoved<C: sov_modules_api::Context> {
+    /// The address of the removed feeder.
+    #[indexed]
+    pub feeder: C::Address,
+}
+
+/// Emitted when the admin adds `pair`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "pair_added")]
+pub struct PairAdded {
+    /// The name of the pair.
+    #[indexed]
+    pub pair: String,
+    /// The number of decimals of the prices of the pair.
+    pub decimals: u8,
+}
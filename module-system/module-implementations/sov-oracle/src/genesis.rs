@@ -0,0 +1,67 @@
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::{Oracle, MAX_FEEDERS};
+
+/// A pair known by the oracle at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PairConfig {
+    /// The name of the pair, e.g. `ETH/USD`.
+    pub pair: String,
+    /// The number of decimals of the prices of the pair.
+    pub decimals: u8,
+}
+
+/// Initial configuration for sov-oracle module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct OracleConfig<C: sov_modules_api::Context> {
+    /// The address allowed to manage the feeders and the pairs.
+    pub admin: C::Address,
+    /// The addresses allowed to post prices.
+    pub feeders: Vec<C::Address>,
+    /// The pairs the feeders can post prices for.
+    #[serde(default)]
+    pub pairs: Vec<PairConfig>,
+    /// The number of slots after which a price isn't fresh anymore.
+    pub max_staleness: u64,
+    /// The minimum number of fresh prices from distinct feeders to aggregate a price.
+    pub min_submissions: u32,
+}
+
+impl<C: sov_modules_api::Context> Oracle<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if config.feeders.len() > MAX_FEEDERS {
+            bail!("The oracle can't have more than {} feeders", MAX_FEEDERS)
+        }
+        for (i, feeder) in config.feeders.iter().enumerate() {
+            if config.feeders[..i].contains(feeder) {
+                bail!("Feeder {} is duplicated", feeder)
+            }
+        }
+        if config.min_submissions == 0 || config.min_submissions as usize > config.feeders.len() {
+            bail!(
+                "The minimum number of prices must be between 1 and the number of feeders {}, not {}",
+                config.feeders.len(),
+                config.min_submissions
+            )
+        }
+
+        self.admin.set(&config.admin, working_set);
+        self.feeders.set(&config.feeders, working_set);
+        self.max_staleness.set(&config.max_staleness, working_set);
+        self.min_submissions
+            .set(&config.min_submissions, working_set);
+        for pair in &config.pairs {
+            self.insert_pair(pair.pair.clone(), pair.decimals, working_set)?;
+        }
+        Ok(())
+    }
+}
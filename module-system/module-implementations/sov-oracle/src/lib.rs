@@ -0,0 +1,219 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+
+use anyhow::{bail, Context as _};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::{
+    CallResponse, Error, ModuleInfo, StateMap, StateMapAccessor, StateValue, StateValueAccessor,
+    WorkingSet,
+};
+
+/// A price posted by a feeder.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub struct Submission {
+    /// The price, with the decimals of its pair.
+    pub price: u64,
+    /// The slot height at which the price was posted.
+    pub submitted_at: u64,
+}
+
+/// The median of the fresh prices of a pair, computed when a price is posted.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub struct AggregatedPrice {
+    /// The median price, with the decimals of its pair.
+    pub price: u64,
+    /// The slot height at which the price was aggregated.
+    pub updated_at: u64,
+    /// The number of fresh prices the median was computed from.
+    pub feeder_count: u32,
+}
+
+/// The `sov-oracle` module aggregates the prices posted by authorized feeders, and serves them to the other
+/// modules of the runtime. It provides functionality for:
+/// - Posting prices, for the feeders.
+/// - Aggregating the fresh prices of a pair into their median, once enough feeders posted one.
+/// - Managing the feeders and the pairs, for the admin.
+///
+/// Other modules read prices with [`Oracle::get_price`], which fails if the aggregated price is stale.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Oracle<C: sov_modules_api::Context> {
+    /// The address of the sov-oracle module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address allowed to manage the feeders and the pairs.
+    #[state]
+    pub(crate) admin: StateValue<C::Address>,
+
+    /// The addresses allowed to post prices.
+    #[state]
+    pub(crate) feeders: StateValue<Vec<C::Address>>,
+
+    /// The pairs and the number of decimals of their prices.
+    #[state]
+    pub(crate) pairs: StateMap<String, u8>,
+
+    /// The names of the pairs, in the order they were added.
+    #[state]
+    pub(crate) pair_names: StateValue<Vec<String>>,
+
+    /// The number of slots after which a price isn't fresh anymore.
+    #[state]
+    pub(crate) max_staleness: StateValue<u64>,
+
+    /// The minimum number of fresh prices from distinct feeders to aggregate a price.
+    #[state]
+    pub(crate) min_submissions: StateValue<u32>,
+
+    /// The last price posted by each feeder, keyed by pair and feeder.
+    #[state]
+    pub(crate) submissions: StateMap<(String, C::Address), Submission>,
+
+    /// The aggregated price of each pair.
+    #[state]
+    pub(crate) prices: StateMap<String, AggregatedPrice>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Oracle<C> {
+    type Context = C;
+
+    type Config = OracleConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::SubmitPrice { pair, price } => {
+                Ok(self.submit_price(pair, price, context, working_set)?)
+            }
+
+            call::CallMessage::AddFeeder { feeder } => {
+                Ok(self.add_feeder(feeder, context, working_set)?)
+            }
+
+            call::CallMessage::RemoveFeeder { feeder } => {
+                Ok(self.remove_feeder(feeder, context, working_set)?)
+            }
+
+            call::CallMessage::AddPair { pair, decimals } => {
+                Ok(self.add_pair(pair, decimals, context, working_set)?)
+            }
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> Oracle<C> {
+    /// Returns the aggregated price of `pair` at the slot height `slot_height`, with the number of decimals
+    /// of the pair. Fails if the pair has no aggregated price, or if it is older than the maximum staleness.
+    pub fn get_price(
+        &self,
+        pair: &str,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<(u64, u8)> {
+        let pair = pair.to_owned();
+        let decimals = self
+            .pairs
+            .get(&pair, working_set)
+            .with_context(|| format!("Pair {} isn't known by the oracle", pair))?;
+        let price = self
+            .prices
+            .get(&pair, working_set)
+            .with_context(|| format!("Pair {} has no price yet", pair))?;
+        let max_staleness = self.max_staleness.get_or_err(working_set)?;
+        if slot_height.saturating_sub(price.updated_at) > max_staleness {
+            bail!(
+                "The price of {} was updated at slot {}, and is stale at slot {}",
+                pair,
+                price.updated_at,
+                slot_height
+            )
+        }
+        Ok((price.price, decimals))
+    }
+
+    /// Returns the last aggregated price of `pair`, whether it is fresh or not.
+    pub fn get_aggregated_price(
+        &self,
+        pair: &str,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<AggregatedPrice> {
+        self.prices.get(&pair.to_owned(), working_set)
+    }
+
+    /// Returns the last price posted by `feeder` for `pair`.
+    pub fn get_submission(
+        &self,
+        pair: &str,
+        feeder: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Submission> {
+        self.submissions
+            .get(&(pair.to_owned(), feeder.clone()), working_set)
+    }
+
+    /// Returns true if `address` is allowed to post prices.
+    pub fn is_feeder(&self, address: &C::Address, working_set: &mut WorkingSet<C>) -> bool {
+        self.feeders
+            .get(working_set)
+            .unwrap_or_default()
+            .contains(address)
+    }
+}
+
+/// Returns the median of `prices`, or the floor of the mean of the two middle prices if their number is even.
+/// Returns `None` if `prices` is empty.
+pub fn median(mut prices: Vec<u64>) -> Option<u64> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_unstable();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(prices[middle])
+    } else {
+        let (low, high) = (prices[middle - 1], prices[middle]);
+        // Can't overflow, unlike `(low + high) / 2`
+        Some(low + (high - low) / 2)
+    }
+}
@@ -0,0 +1,52 @@
+//! Defines rpc queries exposed by the oracle module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateMapAccessor, StateValueAccessor, WorkingSet};
+
+use crate::{AggregatedPrice, Oracle};
+
+/// Structure returned by the `getPrice` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct PriceResponse {
+    /// The last aggregated price of the pair, whether it is fresh or not.
+    pub price: Option<AggregatedPrice>,
+    /// The number of decimals of the prices of the pair, if the pair is known.
+    pub decimals: Option<u8>,
+}
+
+/// Structure returned by the `getFeeders` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct FeedersResponse<C: sov_modules_api::Context> {
+    /// The admin allowed to manage the feeders and the pairs.
+    pub admin: Option<C::Address>,
+    /// The addresses allowed to post prices.
+    pub feeders: Vec<C::Address>,
+    /// The known pairs.
+    pub pairs: Vec<String>,
+}
+
+#[rpc_gen(client, server, namespace = "oracle")]
+impl<C: sov_modules_api::Context> Oracle<C> {
+    #[rpc_method(name = "getPrice")]
+    /// Rpc method that returns the last aggregated price of `pair`.
+    pub fn price(&self, pair: String, working_set: &mut WorkingSet<C>) -> RpcResult<PriceResponse> {
+        Ok(PriceResponse {
+            price: self.prices.get(&pair, working_set),
+            decimals: self.pairs.get(&pair, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getFeeders")]
+    /// Rpc method that returns the feeders, the pairs and the admin.
+    pub fn feeders(&self, working_set: &mut WorkingSet<C>) -> RpcResult<FeedersResponse<C>> {
+        Ok(FeedersResponse {
+            admin: self.admin.get(working_set),
+            feeders: self.feeders.get(working_set).unwrap_or_default(),
+            pairs: self.pair_names.get(working_set).unwrap_or_default(),
+        })
+    }
+}
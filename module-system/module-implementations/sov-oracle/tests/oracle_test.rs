@@ -0,0 +1,202 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, WorkingSet};
+use sov_oracle::{median, CallMessage, Oracle, OracleConfig, PairConfig};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+const PAIR: &str = "ETH/USD";
+
+fn context(sender: Address, slot_height: u64) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), slot_height)
+}
+
+fn feeders() -> Vec<Address> {
+    ["feeder_1", "feeder_2", "feeder_3"]
+        .into_iter()
+        .map(generate_address::<C>)
+        .collect()
+}
+
+fn setup(working_set: &mut WorkingSet<C>) -> Oracle<C> {
+    let oracle = Oracle::<C>::default();
+    oracle
+        .genesis(
+            &OracleConfig {
+                admin: generate_address::<C>("admin"),
+                feeders: feeders(),
+                pairs: vec![PairConfig {
+                    pair: PAIR.to_owned(),
+                    decimals: 6,
+                }],
+                max_staleness: 10,
+                min_submissions: 2,
+            },
+            working_set,
+        )
+        .unwrap();
+    oracle
+}
+
+fn submit(
+    oracle: &Oracle<C>,
+    feeder: Address,
+    price: u64,
+    slot_height: u64,
+    working_set: &mut WorkingSet<C>,
+) -> Result<(), sov_modules_api::Error> {
+    oracle
+        .call(
+            CallMessage::SubmitPrice {
+                pair: PAIR.to_owned(),
+                price,
+            },
+            &context(feeder, slot_height),
+            working_set,
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn test_median() {
+    assert_eq!(median(vec![]), None);
+    assert_eq!(median(vec![3, 1, 2]), Some(2));
+    assert_eq!(median(vec![4, 1, 3, 2]), Some(2));
+    assert_eq!(median(vec![u64::MAX, u64::MAX - 2]), Some(u64::MAX - 1));
+}
+
+#[test]
+fn test_prices_are_aggregated_once_enough_feeders_posted_them() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let oracle = setup(working_set);
+    let feeders = feeders();
+
+    // Only the feeders can post prices, for the known pairs
+    submit(&oracle, generate_address::<C>("admin"), 100, 1, working_set)
+        .expect_err("Only the feeders can post prices");
+    oracle
+        .call(
+            CallMessage::SubmitPrice {
+                pair: "BTC/USD".to_owned(),
+                price: 100,
+            },
+            &context(feeders[0], 1),
+            working_set,
+        )
+        .expect_err("The pair isn't known");
+
+    // A single price isn't aggregated
+    submit(&oracle, feeders[0], 100, 1, working_set).unwrap();
+    assert_eq!(oracle.get_aggregated_price(PAIR, working_set), None);
+    oracle.get_price(PAIR, 1, working_set).unwrap_err();
+
+    // The median of two prices is their mean
+    submit(&oracle, feeders[1], 300, 2, working_set).unwrap();
+    assert_eq!(oracle.get_price(PAIR, 2, working_set).unwrap(), (200, 6));
+
+    // An outlier doesn't move the median of three prices
+    submit(&oracle, feeders[2], 1_000_000, 3, working_set).unwrap();
+    let aggregated = oracle.get_aggregated_price(PAIR, working_set).unwrap();
+    assert_eq!(aggregated.price, 300);
+    assert_eq!(aggregated.updated_at, 3);
+    assert_eq!(aggregated.feeder_count, 3);
+
+    // The price is stale after 10 slots
+    assert_eq!(oracle.get_price(PAIR, 13, working_set).unwrap(), (300, 6));
+    oracle.get_price(PAIR, 14, working_set).unwrap_err();
+
+    // Stale prices are left out of the median: the previous prices are stale at slot 14,
+    // so the new price alone isn't aggregated
+    submit(&oracle, feeders[0], 150, 14, working_set).unwrap();
+    assert_eq!(
+        oracle.get_aggregated_price(PAIR, working_set),
+        Some(aggregated)
+    );
+    submit(&oracle, feeders[1], 250, 14, working_set).unwrap();
+    assert_eq!(oracle.get_price(PAIR, 14, working_set).unwrap(), (200, 6));
+}
+
+#[test]
+fn test_admin_manages_feeders_and_pairs() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let oracle = setup(working_set);
+    let admin = generate_address::<C>("admin");
+    let feeders = feeders();
+    let new_feeder = generate_address::<C>("new_feeder");
+
+    oracle
+        .call(
+            CallMessage::AddFeeder { feeder: new_feeder },
+            &context(feeders[0], 1),
+            working_set,
+        )
+        .expect_err("Only the admin can add feeders");
+    oracle
+        .call(
+            CallMessage::AddFeeder { feeder: new_feeder },
+            &context(admin, 1),
+            working_set,
+        )
+        .unwrap();
+    assert!(oracle.is_feeder(&new_feeder, working_set));
+
+    // The prices of a removed feeder are discarded
+    submit(&oracle, feeders[0], 100, 1, working_set).unwrap();
+    oracle
+        .call(
+            CallMessage::RemoveFeeder { feeder: feeders[0] },
+            &context(admin, 1),
+            working_set,
+        )
+        .unwrap();
+    assert!(!oracle.is_feeder(&feeders[0], working_set));
+    assert_eq!(oracle.get_submission(PAIR, &feeders[0], working_set), None);
+    submit(&oracle, feeders[0], 100, 1, working_set).unwrap_err();
+    submit(&oracle, feeders[1], 200, 1, working_set).unwrap();
+    assert_eq!(oracle.get_aggregated_price(PAIR, working_set), None);
+
+    // The oracle keeps enough feeders to aggregate prices
+    oracle
+        .call(
+            CallMessage::RemoveFeeder { feeder: feeders[1] },
+            &context(admin, 1),
+            working_set,
+        )
+        .unwrap();
+    oracle
+        .call(
+            CallMessage::RemoveFeeder { feeder: feeders[2] },
+            &context(admin, 1),
+            working_set,
+        )
+        .expect_err("The oracle needs at least 2 feeders");
+
+    oracle
+        .call(
+            CallMessage::AddPair {
+                pair: "BTC/USD".to_owned(),
+                decimals: 8,
+            },
+            &context(admin, 1),
+            working_set,
+        )
+        .unwrap();
+    oracle
+        .call(
+            CallMessage::AddPair {
+                pair: PAIR.to_owned(),
+                decimals: 8,
+            },
+            &context(admin, 1),
+            working_set,
+        )
+        .expect_err("The pair is already known");
+
+    let response = oracle.feeders(working_set).unwrap();
+    assert_eq!(response.admin, Some(admin));
+    assert_eq!(response.feeders, vec![feeders[2], new_feeder]);
+    assert_eq!(response.pairs, vec![PAIR.to_owned(), "BTC/USD".to_owned()]);
+}
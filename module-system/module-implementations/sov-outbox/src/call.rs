@@ -0,0 +1,22 @@
+/// This enumeration represents the available call messages for interacting with the sov-outbox module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CliWalletArg),
+    schemars(rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Sends a message from the sender of the transaction to another rollup.
+    Send {
+        /// The chain ID of the receiving rollup.
+        destination_chain_id: u64,
+        /// The content of the message.
+        payload: Vec<u8>,
+    },
+}
@@ -0,0 +1,16 @@
+//! The typed events emitted by the outbox module.
+use sov_modules_api::ModuleEvent;
+
+/// Emitted when a message is appended to the outbox.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "message_sent")]
+pub struct MessageSent {
+    /// The chain ID of the receiving rollup.
+    #[indexed]
+    pub destination_chain_id: u64,
+    /// The nonce of the message, which is its index in the outbox.
+    #[indexed]
+    pub nonce: u64,
+    /// The leaf of the message in the outbox tree.
+    pub message_hash: [u8; 32],
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::{merkle, Outbox};
+
+/// Initial configuration for sov-outbox module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OutboxConfig {
+    /// The chain ID of the rollup, which identifies it as the source of its messages.
+    pub chain_id: u64,
+}
+
+impl<C: sov_modules_api::Context> Outbox<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.chain_id.set(&config.chain_id, working_set);
+        self.root.set(&merkle::root::<C>(&[], 0), working_set);
+        Ok(())
+    }
+}
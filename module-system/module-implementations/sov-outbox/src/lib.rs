@@ -0,0 +1,184 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+pub mod merkle;
+#[cfg(feature = "native")]
+mod query;
+
+use anyhow::{ensure, Result};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{
+    CallResponse, Context, Error, ModuleInfo, StateValue, StateValueAccessor, StateVec,
+    StateVecAccessor, WorkingSet,
+};
+
+use crate::events::MessageSent;
+
+/// The maximum size of the payload of a message, in bytes.
+pub const MAX_PAYLOAD_SIZE: usize = 64 * 1024;
+
+const LEAF_PREFIX: u8 = 0;
+
+/// A message sent from a rollup to another rollup.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct CrossRollupMessage {
+    /// The chain ID of the sending rollup.
+    pub source_chain_id: u64,
+    /// The chain ID of the receiving rollup.
+    pub destination_chain_id: u64,
+    /// The index of the message in the outbox of the sending rollup.
+    pub nonce: u64,
+    /// The serialized address of the sender, in the sending rollup.
+    pub sender: Vec<u8>,
+    /// The content of the message, interpreted by the receiving rollup.
+    pub payload: Vec<u8>,
+}
+
+impl CrossRollupMessage {
+    /// Returns the leaf of the message in the outbox tree.
+    pub fn hash<C: Context>(&self) -> [u8; 32] {
+        C::Hasher::new()
+            .chain_update([LEAF_PREFIX])
+            .chain_update(
+                borsh::BorshSerialize::try_to_vec(self)
+                    .expect("Serializing a message to a vector can't fail"),
+            )
+            .finalize()
+            .into()
+    }
+}
+
+/// The `sov-outbox` module collects the messages sent by a rollup to other rollups. It provides functionality for:
+/// - Sending a message, from an account or from another module with [`Outbox::send_message`].
+/// - Committing the messages into an append-only merkle tree, whose root is part of the state and can be
+///   proven to another rollup. The [`sov-inbox`](https://docs.rs/sov-inbox) module of the receiving rollup
+///   verifies the inclusion of a message in this root.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Outbox<C: sov_modules_api::Context> {
+    /// The address of the sov-outbox module.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The chain ID of the rollup.
+    #[state]
+    pub(crate) chain_id: StateValue<u64>,
+
+    /// The sent messages, indexed by their nonce.
+    #[state]
+    pub(crate) messages: StateVec<CrossRollupMessage>,
+
+    /// The last left node of each level of the outbox tree.
+    #[state]
+    pub(crate) frontier: StateValue<Vec<[u8; 32]>>,
+
+    /// The root of the outbox tree.
+    #[state]
+    pub(crate) root: StateValue<[u8; 32]>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Outbox<C> {
+    type Context = C;
+
+    type Config = OutboxConfig;
+
+    type CallMessage = call::CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Send {
+                destination_chain_id,
+                payload,
+            } => {
+                self.send_message(context.sender(), destination_chain_id, payload, working_set)?;
+                Ok(CallResponse::default())
+            }
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> Outbox<C> {
+    /// Appends a message from `sender` to the outbox, and returns its nonce. Other modules can send messages
+    /// on behalf of their own address.
+    pub fn send_message(
+        &self,
+        sender: &C::Address,
+        destination_chain_id: u64,
+        payload: Vec<u8>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<u64> {
+        ensure!(
+            payload.len() <= MAX_PAYLOAD_SIZE,
+            "The payload of {} bytes exceeds the maximum of {} bytes",
+            payload.len(),
+            MAX_PAYLOAD_SIZE
+        );
+        let source_chain_id = self.chain_id.get_or_err(working_set)?;
+        ensure!(
+            destination_chain_id != source_chain_id,
+            "A rollup can't send messages to itself"
+        );
+
+        let nonce = self.messages.len(working_set) as u64;
+        ensure!(
+            nonce >> merkle::OUTBOX_TREE_DEPTH == 0,
+            "The outbox is full"
+        );
+        let message = CrossRollupMessage {
+            source_chain_id,
+            destination_chain_id,
+            nonce,
+            sender: sender.as_ref().to_vec(),
+            payload,
+        };
+        let message_hash = message.hash::<C>();
+
+        let mut frontier = self.frontier.get(working_set).unwrap_or_default();
+        merkle::append::<C>(&mut frontier, nonce, message_hash);
+        self.root
+            .set(&merkle::root::<C>(&frontier, nonce + 1), working_set);
+        self.frontier.set(&frontier, working_set);
+        self.messages.push(&message, working_set);
+
+        working_set.emit_event(&MessageSent {
+            destination_chain_id,
+            nonce,
+            message_hash,
+        });
+        Ok(nonce)
+    }
+
+    /// Returns the root of the outbox tree, committing to all the sent messages.
+    pub fn outbox_root(&self, working_set: &mut WorkingSet<C>) -> [u8; 32] {
+        self.root
+            .get(working_set)
+            .unwrap_or_else(|| merkle::root::<C>(&[], 0))
+    }
+}
@@ -0,0 +1,100 @@
+//! The append-only merkle tree of the outbox, and the verification of the inclusion of its messages.
+//!
+//! The tree has a fixed depth of [`OUTBOX_TREE_DEPTH`]. The empty leaves are zero, and the module only stores
+//! the frontier of the tree (the last left node of each level), so that appending a message costs a
+//! constant number of hashes.
+
+use sov_modules_api::digest::Digest;
+use sov_modules_api::Context;
+
+/// The depth of the outbox tree, which holds up to `2^32` messages.
+pub const OUTBOX_TREE_DEPTH: usize = 32;
+
+const NODE_PREFIX: u8 = 1;
+
+fn hash_node<C: Context>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    C::Hasher::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// The roots of the empty subtrees of each level.
+fn zero_hashes<C: Context>() -> Vec<[u8; 32]> {
+    let mut zeros = vec![[0u8; 32]];
+    for level in 0..OUTBOX_TREE_DEPTH {
+        zeros.push(hash_node::<C>(&zeros[level], &zeros[level]));
+    }
+    zeros
+}
+
+/// Appends `leaf` at the index `count` to the tree with the frontier `frontier`.
+pub fn append<C: Context>(frontier: &mut Vec<[u8; 32]>, count: u64, leaf: [u8; 32]) {
+    frontier.resize(OUTBOX_TREE_DEPTH, [0u8; 32]);
+    let mut node = leaf;
+    let mut size = count;
+    for left in frontier.iter_mut() {
+        if size & 1 == 0 {
+            *left = node;
+            return;
+        }
+        node = hash_node::<C>(left, &node);
+        size >>= 1;
+    }
+}
+
+/// Returns the root of the tree holding `count` leaves, with the frontier `frontier`.
+pub fn root<C: Context>(frontier: &[[u8; 32]], count: u64) -> [u8; 32] {
+    let zeros = zero_hashes::<C>();
+    let mut node = zeros[0];
+    let mut size = count;
+    for level in 0..OUTBOX_TREE_DEPTH {
+        node = if size & 1 == 1 {
+            hash_node::<C>(&frontier[level], &node)
+        } else {
+            hash_node::<C>(&node, &zeros[level])
+        };
+        size >>= 1;
+    }
+    node
+}
+
+/// Returns the proof of inclusion of the leaf at `index` in the tree holding `leaves`.
+pub fn inclusion_proof<C: Context>(leaves: &[[u8; 32]], index: u64) -> Vec<[u8; 32]> {
+    let zeros = zero_hashes::<C>();
+    let mut layer = leaves.to_vec();
+    let mut index = index as usize;
+    let mut proof = Vec::with_capacity(OUTBOX_TREE_DEPTH);
+    for zero in zeros.iter().take(OUTBOX_TREE_DEPTH) {
+        proof.push(layer.get(index ^ 1).copied().unwrap_or(*zero));
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_node::<C>(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+        index >>= 1;
+    }
+    proof
+}
+
+/// Returns true if `proof` proves the inclusion of `leaf` at `index` in the tree with the root `root`.
+pub fn verify_inclusion<C: Context>(
+    root: &[u8; 32],
+    leaf: [u8; 32],
+    index: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    if proof.len() != OUTBOX_TREE_DEPTH || index >> OUTBOX_TREE_DEPTH != 0 {
+        return false;
+    }
+    let mut node = leaf;
+    for (level, sibling) in proof.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            hash_node::<C>(&node, sibling)
+        } else {
+            hash_node::<C>(sibling, &node)
+        };
+    }
+    &node == root
+}
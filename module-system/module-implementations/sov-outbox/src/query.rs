@@ -0,0 +1,61 @@
+//! Defines rpc queries exposed by the outbox module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateVecAccessor, WorkingSet};
+
+use crate::{merkle, CrossRollupMessage, Outbox};
+
+/// Structure returned by the `getRoot` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct OutboxRootResponse {
+    /// The root of the outbox tree.
+    pub root: [u8; 32],
+    /// The number of sent messages.
+    pub message_count: u64,
+}
+
+/// Structure returned by the `getMessageProof` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct MessageProofResponse {
+    /// The message.
+    pub message: CrossRollupMessage,
+    /// The current root of the outbox tree.
+    pub root: [u8; 32],
+    /// The proof of inclusion of the message in `root`.
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[rpc_gen(client, server, namespace = "outbox")]
+impl<C: sov_modules_api::Context> Outbox<C> {
+    #[rpc_method(name = "getRoot")]
+    /// Rpc method that returns the root of the outbox tree and the number of sent messages.
+    pub fn root_info(&self, working_set: &mut WorkingSet<C>) -> RpcResult<OutboxRootResponse> {
+        Ok(OutboxRootResponse {
+            root: self.outbox_root(working_set),
+            message_count: self.messages.len(working_set) as u64,
+        })
+    }
+
+    #[rpc_method(name = "getMessageProof")]
+    /// Rpc method that returns the message with the nonce `nonce`, and the proof of its inclusion in the current
+    /// root of the outbox tree. Relayers submit it to the inbox of the receiving rollup.
+    pub fn message_proof(
+        &self,
+        nonce: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<MessageProofResponse>> {
+        let Some(message) = self.messages.get(nonce as usize, working_set) else {
+            return Ok(None);
+        };
+        let leaves: Vec<[u8; 32]> = self
+            .messages
+            .iter(working_set)
+            .map(|message| message.hash::<C>())
+            .collect();
+        Ok(Some(MessageProofResponse {
+            message,
+            root: self.outbox_root(working_set),
+            proof: merkle::inclusion_proof::<C>(&leaves, nonce),
+        }))
+    }
+}
@@ -0,0 +1,94 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, WorkingSet};
+use sov_outbox::merkle::verify_inclusion;
+use sov_outbox::{CallMessage, Outbox, OutboxConfig, MAX_PAYLOAD_SIZE};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+const CHAIN_ID: u64 = 1;
+const DESTINATION_CHAIN_ID: u64 = 2;
+
+fn context(sender: Address) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), 1)
+}
+
+#[test]
+fn test_send_messages() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+    let sender = generate_address::<C>("sender");
+    let outbox = Outbox::<C>::default();
+    outbox
+        .genesis(&OutboxConfig { chain_id: CHAIN_ID }, working_set)
+        .unwrap();
+    let empty_root = outbox.outbox_root(working_set);
+
+    for i in 0..3u8 {
+        outbox
+            .call(
+                CallMessage::Send {
+                    destination_chain_id: DESTINATION_CHAIN_ID,
+                    payload: vec![i],
+                },
+                &context(sender),
+                working_set,
+            )
+            .unwrap();
+    }
+    let root = outbox.root_info(working_set).unwrap();
+    assert_eq!(root.message_count, 3);
+    assert_ne!(root.root, empty_root);
+
+    // Each message is proven against the root
+    for nonce in 0..3 {
+        let proof = outbox
+            .message_proof(nonce, working_set)
+            .unwrap()
+            .expect("The message was sent");
+        assert_eq!(proof.root, root.root);
+        assert_eq!(proof.message.nonce, nonce);
+        assert_eq!(proof.message.source_chain_id, CHAIN_ID);
+        assert_eq!(proof.message.sender, sender.as_ref().to_vec());
+        assert_eq!(proof.message.payload, vec![nonce as u8]);
+        assert!(verify_inclusion::<C>(
+            &root.root,
+            proof.message.hash::<C>(),
+            nonce,
+            &proof.proof
+        ));
+        // The proof doesn't hold for another index
+        assert!(!verify_inclusion::<C>(
+            &root.root,
+            proof.message.hash::<C>(),
+            nonce + 1,
+            &proof.proof
+        ));
+    }
+    assert_eq!(outbox.message_proof(3, working_set).unwrap(), None);
+
+    // Messages to the rollup itself and oversized messages are rejected
+    outbox
+        .call(
+            CallMessage::Send {
+                destination_chain_id: CHAIN_ID,
+                payload: vec![],
+            },
+            &context(sender),
+            working_set,
+        )
+        .unwrap_err();
+    outbox
+        .call(
+            CallMessage::Send {
+                destination_chain_id: DESTINATION_CHAIN_ID,
+                payload: vec![0; MAX_PAYLOAD_SIZE + 1],
+            },
+            &context(sender),
+            working_set,
+        )
+        .unwrap_err();
+    assert_eq!(outbox.root_info(working_set).unwrap().message_count, 3);
+}
@@ -1,8 +1,9 @@
 use std::fmt::Debug;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use borsh::{BorshDeserialize, BorshSerialize};
 use sov_bank::Coins;
+use sov_modules_api::digest::Digest;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
@@ -20,6 +21,10 @@ pub enum CallMessage {
     UnbondProver,
     /// Verifies the provided proof (of format `Vec<u8>`)
     VerifyProof(Vec<u8>),
+    /// Deposits the provided amount of bonding tokens into the reward pool, such as collected fees.
+    FundRewards(u64),
+    /// Withdraws the rewards earned by the prover.
+    ClaimRewards,
 }
 
 impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C, Vm> {
@@ -52,6 +57,12 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
         let total_balance = old_balance + bond_amount;
         self.bonded_provers.set(prover, &total_balance, working_set);
 
+        let mut provers = self.provers.get(working_set).unwrap_or_default();
+        if !provers.contains(prover) {
+            provers.push(prover.clone());
+            self.provers.set(&provers, working_set);
+        }
+
         // Emit the bonding event
         working_set.add_event(
             "bonded_prover",
@@ -122,14 +133,25 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
         // Check that the prover has enough balance to process the proof.
         let minimum_bond = self.minimum_bond.get_or_err(working_set)?;
 
-        anyhow::ensure!(old_balance >= minimum_bond, "Prover is not bonded");
+        ensure!(old_balance >= minimum_bond, "Prover is not bonded");
         let code_commitment = self
             .commitment_of_allowed_verifier_method
             .get_or_err(working_set)?;
 
-        // Lock the prover's bond amount.
-        self.bonded_provers
-            .set(context.sender(), &(old_balance - minimum_bond), working_set);
+        // Reject proofs which were already processed, so that a proof can't be rewarded or slashed twice.
+        let proof_hash: [u8; 32] = C::Hasher::digest(proof).into();
+        ensure!(
+            self.processed_proofs
+                .get(&proof_hash, working_set)
+                .is_none(),
+            "Proof was already processed"
+        );
+        self.processed_proofs.set(&proof_hash, &(), working_set);
+
+        let mut standing = self
+            .standings
+            .get(context.sender(), working_set)
+            .unwrap_or_default();
 
         // Don't return an error for invalid proofs - those are expected and shouldn't cause reverts.
         if let Ok(_public_outputs) =
@@ -137,23 +159,122 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
         {
             // TODO: decide what the proof output is and do something with it
             //     https://github.com/Sovereign-Labs/sovereign-sdk/issues/272
+            let reward = self.pay_reward(working_set);
+            let pending = self
+                .pending_rewards
+                .get(context.sender(), working_set)
+                .unwrap_or_default();
+            self.pending_rewards
+                .set(context.sender(), &(pending + reward), working_set);
 
-            // Unlock the prover's bond
-            // TODO: reward the prover with newly minted tokens as appropriate based on gas fees.
-            //     https://github.com/Sovereign-Labs/sovereign-sdk/issues/271
-            self.bonded_provers
-                .set(context.sender(), &old_balance, working_set);
+            standing.accepted_proofs += 1;
+            standing.total_rewards += reward;
 
             working_set.add_event(
                 "processed_valid_proof",
-                &format!("prover: {:?}", context.sender()),
+                &format!("prover: {:?}, reward: {reward:?}", context.sender()),
             );
         } else {
+            // Slash the minimum bond of the prover. The slashed tokens fund the rewards of the other provers.
+            self.bonded_provers
+                .set(context.sender(), &(old_balance - minimum_bond), working_set);
+            let pool = self.reward_pool.get(working_set).unwrap_or_default();
+            self.reward_pool.set(&(pool + minimum_bond), working_set);
+
+            standing.invalid_proofs += 1;
+
             working_set.add_event(
                 "processed_invalid_proof",
                 &format!("slashed_prover: {:?}", context.sender()),
             );
         }
+        self.standings.set(context.sender(), &standing, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    /// Takes the reward of an accepted proof from the reward pool, and mints the part of the reward exceeding
+    /// the pool if the module is allowed to mint the bonding token. Returns the reward, held by the module
+    /// until the prover claims it.
+    fn pay_reward(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        let reward_per_proof = self.reward_per_proof.get(working_set).unwrap_or_default();
+        let pool = self.reward_pool.get(working_set).unwrap_or_default();
+        let from_pool = pool.min(reward_per_proof);
+        self.reward_pool.set(&(pool - from_pool), working_set);
+
+        let to_mint = reward_per_proof - from_pool;
+        if to_mint == 0 {
+            return from_pool;
+        }
+        let coins = Coins {
+            token_address: self
+                .bonding_token_address
+                .get(working_set)
+                .expect("Bonding token address must be set"),
+            amount: to_mint,
+        };
+        match self
+            .bank
+            .mint(&coins, &self.address, &self.address, working_set)
+        {
+            Ok(()) => reward_per_proof,
+            // The module isn't a minter of the bonding token: rewards are only funded by the pool
+            Err(_) => from_pool,
+        }
+    }
+
+    /// Transfers `amount` bonding tokens from context.sender() to the reward pool.
+    pub(crate) fn fund_rewards(
+        &self,
+        amount: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let coins = Coins {
+            token_address: self
+                .bonding_token_address
+                .get(working_set)
+                .expect("Bonding token address must be set"),
+            amount,
+        };
+        self.bank
+            .transfer_from(context.sender(), &self.address, coins, working_set)?;
+
+        let pool = self.reward_pool.get(working_set).unwrap_or_default();
+        self.reward_pool.set(&(pool + amount), working_set);
+
+        working_set.add_event(
+            "funded_rewards",
+            &format!("amount: {amount:?}. reward_pool: {:?}", pool + amount),
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the pending rewards of context.sender() to it.
+    pub(crate) fn claim_rewards(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let pending = self
+            .pending_rewards
+            .get(context.sender(), working_set)
+            .unwrap_or_default();
+        ensure!(pending > 0, "No rewards to claim");
+
+        let coins = Coins {
+            token_address: self
+                .bonding_token_address
+                .get(working_set)
+                .expect("Bonding token address must be set"),
+            amount: pending,
+        };
+        self.bank
+            .transfer_from(&self.address, context.sender(), coins, working_set)?;
+        self.pending_rewards.remove(context.sender(), working_set);
+
+        working_set.add_event("claimed_rewards", &format!("amount: {pending:?}"));
 
         Ok(CallResponse::default())
     }
@@ -7,8 +7,8 @@ use crate::ProverIncentives;
 
 /// Configuration of the prover incentives module. Specifies the
 /// address of the bonding token, the minimum bond, the commitment to
-/// the allowed verifier method and a set of initial provers with their
-/// bonding amount.
+/// the allowed verifier method, the reward of each accepted proof and a set
+/// of initial provers with their bonding amount.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProverIncentivesConfig<C: sov_modules_api::Context, Vm: Zkvm> {
     /// The address of the token to be used for bonding.
@@ -19,6 +19,10 @@ pub struct ProverIncentivesConfig<C: sov_modules_api::Context, Vm: Zkvm> {
     pub commitment_of_allowed_verifier_method: Vm::CodeCommitment,
     /// A list of initial provers and their bonded amount.
     pub initial_provers: Vec<(C::Address, u64)>,
+    /// The reward of each accepted proof. The part of the reward which isn't funded by the reward pool is
+    /// minted, if the module is a minter of the bonding token.
+    #[serde(default)]
+    pub reward_per_proof: u64,
 }
 
 impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C, Vm> {
@@ -40,6 +44,8 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
             .set(&config.commitment_of_allowed_verifier_method, working_set);
         self.bonding_token_address
             .set(&config.bonding_token_address, working_set);
+        self.reward_per_proof
+            .set(&config.reward_per_proof, working_set);
 
         for (prover, bond) in config.initial_provers.iter() {
             self.bond_prover_helper(*bond, prover, working_set)?;
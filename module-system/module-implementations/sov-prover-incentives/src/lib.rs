@@ -17,6 +17,27 @@ pub use query::*;
 use sov_modules_api::{Context, Error, ModuleInfo, WorkingSet, Zkvm};
 use sov_state::codec::BcsCodec;
 
+/// The proofs processed for a prover, and the rewards it earned.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct ProverStanding {
+    /// The number of valid proofs submitted by the prover.
+    pub accepted_proofs: u64,
+    /// The number of invalid proofs submitted by the prover, for which it was slashed.
+    pub invalid_proofs: u64,
+    /// The total rewards earned by the prover, claimed or not.
+    pub total_rewards: u64,
+}
+
 /// A new module:
 /// - Must derive `ModuleInfo`
 /// - Must contain `[address]` field
@@ -45,6 +66,30 @@ pub struct ProverIncentives<C: Context, Vm: Zkvm> {
     #[state]
     pub minimum_bond: sov_modules_api::StateValue<u64>,
 
+    /// The addresses of the provers which ever bonded, in bonding order.
+    #[state]
+    pub provers: sov_modules_api::StateValue<Vec<C::Address>>,
+
+    /// The reward of each accepted proof.
+    #[state]
+    pub reward_per_proof: sov_modules_api::StateValue<u64>,
+
+    /// The bonding tokens held by the module to fund the rewards: deposited fees and slashed bonds.
+    #[state]
+    pub reward_pool: sov_modules_api::StateValue<u64>,
+
+    /// The rewards earned by each prover and not claimed yet.
+    #[state]
+    pub pending_rewards: sov_modules_api::StateMap<C::Address, u64>,
+
+    /// The standing of each prover.
+    #[state]
+    pub standings: sov_modules_api::StateMap<C::Address, ProverStanding>,
+
+    /// The hashes of the proofs which were already processed.
+    #[state]
+    pub processed_proofs: sov_modules_api::StateMap<[u8; 32], ()>,
+
     /// Reference to the Bank module.
     #[module]
     pub(crate) bank: sov_bank::Bank<C>,
@@ -78,6 +123,10 @@ impl<C: Context, Vm: Zkvm> sov_modules_api::Module for ProverIncentives<C, Vm> {
             call::CallMessage::VerifyProof(proof) => {
                 self.process_proof(&proof, context, working_set)
             }
+            call::CallMessage::FundRewards(amount) => {
+                self.fund_rewards(amount, context, working_set)
+            }
+            call::CallMessage::ClaimRewards => self.claim_rewards(context, working_set),
         }
         .map_err(|e| e.into())
     }
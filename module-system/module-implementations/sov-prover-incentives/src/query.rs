@@ -1,7 +1,10 @@
+use jsonrpsee::core::RpcResult;
 use serde::{Deserialize, Serialize};
-use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{StateMapAccessor, StateValueAccessor, WorkingSet};
 
 use super::ProverIncentives;
+use crate::ProverStanding;
 
 /// The structure containing the response returned by the `get_bond_amount` query.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
@@ -10,6 +13,17 @@ pub struct Response {
     pub value: u64,
 }
 
+/// Structure returned by the `getProverStanding` rpc method.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct ProverStandingResponse {
+    /// The bonded amount of the prover.
+    pub bond: u64,
+    /// The rewards earned by the prover and not claimed yet.
+    pub pending_rewards: u64,
+    /// The proofs processed for the prover, and the rewards it earned.
+    pub standing: ProverStanding,
+}
+
 impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C, Vm> {
     /// Queries the state of the module and returns the bond amount of the address `address`.
     /// If the `address` is not bonded, returns a default value.
@@ -26,3 +40,38 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
         }
     }
 }
+
+#[rpc_gen(client, server, namespace = "proverIncentives")]
+impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C, Vm> {
+    #[rpc_method(name = "getProverStanding")]
+    /// Rpc method that returns the bond, the pending rewards and the standing of `prover`.
+    pub fn prover_standing(
+        &self,
+        prover: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ProverStandingResponse> {
+        Ok(ProverStandingResponse {
+            bond: self
+                .bonded_provers
+                .get(&prover, working_set)
+                .unwrap_or_default(),
+            pending_rewards: self
+                .pending_rewards
+                .get(&prover, working_set)
+                .unwrap_or_default(),
+            standing: self.standings.get(&prover, working_set).unwrap_or_default(),
+        })
+    }
+
+    #[rpc_method(name = "getProvers")]
+    /// Rpc method that returns the addresses of the provers which ever bonded.
+    pub fn prover_addresses(&self, working_set: &mut WorkingSet<C>) -> RpcResult<Vec<C::Address>> {
+        Ok(self.provers.get(working_set).unwrap_or_default())
+    }
+
+    #[rpc_method(name = "getRewardPool")]
+    /// Rpc method that returns the amount of bonding tokens available to fund the rewards.
+    pub fn reward_pool_amount(&self, working_set: &mut WorkingSet<C>) -> RpcResult<u64> {
+        Ok(self.reward_pool.get(working_set).unwrap_or_default())
+    }
+}
@@ -6,11 +6,12 @@ use sov_modules_api::prelude::*;
 use sov_modules_api::{Address, Context, Module, Spec, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
 
-use crate::ProverIncentives;
+use crate::{ProverIncentives, ProverStanding};
 
 type C = DefaultContext;
 
 const BOND_AMOUNT: u64 = 1000;
+const REWARD_PER_PROOF: u64 = 100;
 const MOCK_CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([0u8; 32]);
 
 /// Generates an address by hashing the provided `key`.
@@ -19,7 +20,9 @@ pub fn generate_address(key: &str) -> <C as Spec>::Address {
     Address::from(hash)
 }
 
-fn create_bank_config() -> (
+fn create_bank_config(
+    module_address: <C as Spec>::Address,
+) -> (
     sov_bank::BankConfig<C>,
     <C as Spec>::Address,
     <C as Spec>::Address,
@@ -30,13 +33,17 @@ fn create_bank_config() -> (
     let token_config = sov_bank::TokenConfig {
         token_name: "InitialToken".to_owned(),
         address_and_balances: vec![(prover_address, BOND_AMOUNT * 5)],
-        authorized_minters: vec![prover_address],
+        authorized_minters: vec![prover_address, module_address],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
         salt: 2,
     };
 
     (
         sov_bank::BankConfig {
             tokens: vec![token_config],
+            fee_settlement: Default::default(),
         },
         prover_address,
         sequencer_address,
@@ -50,8 +57,10 @@ fn setup(
     Address,
     Address,
 ) {
+    let module = ProverIncentives::<C, MockZkvm<MockValidityCond>>::default();
+
     // Initialize bank
-    let (bank_config, prover_address, sequencer) = create_bank_config();
+    let (bank_config, prover_address, sequencer) = create_bank_config(module.address);
     let bank = sov_bank::Bank::<C>::default();
     bank.genesis(&bank_config, working_set)
         .expect("bank genesis must succeed");
@@ -62,12 +71,12 @@ fn setup(
     );
 
     // initialize prover incentives
-    let module = ProverIncentives::<C, MockZkvm<MockValidityCond>>::default();
     let config = crate::ProverIncentivesConfig {
         bonding_token_address: token_address,
         minimum_bond: BOND_AMOUNT,
         commitment_of_allowed_verifier_method: MockCodeCommitment([0u8; 32]),
         initial_provers: vec![(prover_address, BOND_AMOUNT)],
+        reward_per_proof: REWARD_PER_PROOF,
     };
 
     module
@@ -232,3 +241,106 @@ fn test_prover_not_bonded() {
             .is_err())
     }
 }
+
+#[test]
+fn test_reward_and_claim() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let (module, prover_address, sequencer) = setup(&mut working_set);
+    let context = DefaultContext::new(prover_address, sequencer, 1);
+    let token_address = module
+        .bonding_token_address
+        .get(&mut working_set)
+        .expect("bonding token address was set at genesis");
+    let initial_unlocked_balance = module
+        .bank
+        .get_balance_of(prover_address, token_address, &mut working_set)
+        .unwrap_or_default();
+
+    // The reward pool is empty: the reward of the first proof is minted
+    let first_proof = MockProof {
+        program_id: MOCK_CODE_COMMITMENT,
+        is_valid: true,
+        log: &[],
+    }
+    .encode_to_vec();
+    module
+        .process_proof(first_proof.as_ref(), &context, &mut working_set)
+        .expect("A valid proof must be processed");
+
+    // The reward of the second proof is half funded by the pool, and half minted
+    module
+        .fund_rewards(REWARD_PER_PROOF / 2, &context, &mut working_set)
+        .expect("Funding the rewards should succeed");
+    let second_proof = MockProof {
+        program_id: MOCK_CODE_COMMITMENT,
+        is_valid: true,
+        log: &[1],
+    }
+    .encode_to_vec();
+    module
+        .process_proof(second_proof.as_ref(), &context, &mut working_set)
+        .expect("A valid proof must be processed");
+    assert_eq!(module.reward_pool_amount(&mut working_set).unwrap(), 0);
+
+    // A proof can't be rewarded twice
+    assert!(module
+        .process_proof(first_proof.as_ref(), &context, &mut working_set)
+        .is_err());
+
+    let standing = module
+        .prover_standing(prover_address, &mut working_set)
+        .unwrap();
+    assert_eq!(standing.bond, BOND_AMOUNT);
+    assert_eq!(standing.pending_rewards, 2 * REWARD_PER_PROOF);
+    assert_eq!(
+        standing.standing,
+        ProverStanding {
+            accepted_proofs: 2,
+            invalid_proofs: 0,
+            total_rewards: 2 * REWARD_PER_PROOF,
+        }
+    );
+
+    module
+        .claim_rewards(&context, &mut working_set)
+        .expect("Claiming the rewards should succeed");
+    assert_eq!(
+        module
+            .bank
+            .get_balance_of(prover_address, token_address, &mut working_set),
+        Some(initial_unlocked_balance - REWARD_PER_PROOF / 2 + 2 * REWARD_PER_PROOF)
+    );
+    assert!(module.claim_rewards(&context, &mut working_set).is_err());
+}
+
+#[test]
+fn test_slashed_bond_funds_rewards() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let (module, prover_address, sequencer) = setup(&mut working_set);
+    let context = DefaultContext::new(prover_address, sequencer, 1);
+
+    let proof = MockProof {
+        program_id: MOCK_CODE_COMMITMENT,
+        is_valid: false,
+        log: &[],
+    };
+    module
+        .process_proof(proof.encode_to_vec().as_ref(), &context, &mut working_set)
+        .expect("An invalid proof is not an error");
+
+    assert_eq!(
+        module.reward_pool_amount(&mut working_set).unwrap(),
+        BOND_AMOUNT
+    );
+    let standing = module
+        .prover_standing(prover_address, &mut working_set)
+        .unwrap();
+    assert_eq!(standing.standing.invalid_proofs, 1);
+    assert_eq!(standing.pending_rewards, 0);
+    assert_eq!(
+        module.prover_addresses(&mut working_set).unwrap(),
+        vec![prover_address]
+    );
+}
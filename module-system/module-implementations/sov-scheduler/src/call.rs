@@ -0,0 +1,216 @@
+use anyhow::{bail, Context as _, Result};
+use sov_bank::{Amount, Coins};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, DispatchCall, WorkingSet};
+
+use crate::events::{TaskCancelled, TaskScheduled};
+use crate::{Scheduler, Task, TaskId, TaskStatus};
+
+/// The maximum number of tasks waiting for their next execution at the same time.
+pub const MAX_SCHEDULED_TASKS: usize = 256;
+
+/// This enumeration represents the available call messages for interacting with the sov-scheduler module.
+#[cfg_attr(feature = "native", derive(CliWalletArg), derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Schedules a runtime call, executed with the sender as the sender at the start of a future slot.
+    /// The deposit of all the executions is locked, and each execution consumes its share.
+    Schedule {
+        /// The serialized runtime call.
+        call: Vec<u8>,
+        /// The slot height from which the first execution is due.
+        execute_at: u64,
+        /// The gas reserved for each execution.
+        gas_limit: u64,
+        /// The number of slots between two executions. Ignored if there are no repetitions.
+        interval: u64,
+        /// The number of executions following the first one.
+        repetitions: u32,
+    },
+
+    /// Cancels a scheduled task, and refunds the deposit of its remaining executions to its owner.
+    Cancel {
+        /// The identifier of the task.
+        task_id: TaskId,
+    },
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Scheduler<C, Rt> {
+    /// Schedules the serialized runtime `call` on behalf of the sender, first at `execute_at` and then every
+    /// `interval` slots for `repetitions` more executions. Locks the deposit of all the executions.
+    /// Returns the identifier of the task.
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule(
+        &self,
+        call: Vec<u8>,
+        execute_at: u64,
+        gas_limit: u64,
+        interval: u64,
+        repetitions: u32,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<TaskId> {
+        if execute_at <= context.slot_height() {
+            bail!(
+                "The execution height {} must be after the current slot height {}",
+                execute_at,
+                context.slot_height()
+            )
+        }
+        if repetitions > 0 && interval == 0 {
+            bail!("A recurring task must have a non-zero interval")
+        }
+        let max_gas_per_slot = self.max_gas_per_slot.get_or_err(working_set)?;
+        if gas_limit == 0 || gas_limit > max_gas_per_slot {
+            bail!(
+                "The gas limit {} must be non-zero and at most the gas budget of a slot {}",
+                gas_limit,
+                max_gas_per_slot
+            )
+        }
+        Rt::decode_call(&call).context("The scheduled call can't be decoded by the runtime")?;
+
+        let executions = repetitions.checked_add(1).context("Too many repetitions")?;
+        let deposit = self
+            .deposit_per_gas
+            .get_or_err(working_set)?
+            .checked_mul(gas_limit)
+            .and_then(|deposit| deposit.checked_mul(Amount::from(executions)))
+            .context("The execution deposit overflows")?;
+
+        let queue = self.queue.get(working_set).unwrap_or_default();
+        if queue.len() >= MAX_SCHEDULED_TASKS {
+            bail!("There are already {} scheduled tasks", MAX_SCHEDULED_TASKS)
+        }
+
+        self.transfer_deposit(context.sender(), &self.address, deposit, working_set)
+            .context("Failed to lock the execution deposit")?;
+
+        let task_id = self.next_task_id.get(working_set).unwrap_or_default();
+        self.next_task_id.set(&(task_id + 1), working_set);
+
+        let task = Task {
+            owner: context.sender().clone(),
+            call,
+            execute_at,
+            gas_limit,
+            interval,
+            remaining_executions: executions,
+            deposit,
+            status: TaskStatus::Scheduled,
+        };
+        self.tasks.set(&task_id, &task, working_set);
+        self.enqueue(task_id, execute_at, working_set);
+
+        working_set.emit_event(&TaskScheduled::<C> {
+            task_id,
+            owner: context.sender().clone(),
+            execute_at,
+            executions,
+            deposit,
+        });
+        Ok(task_id)
+    }
+
+    /// Cancels the task `task_id`, which must be owned by the sender, and refunds the deposit of its remaining
+    /// executions.
+    pub fn cancel(
+        &self,
+        task_id: TaskId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut task = self.get_task_or_err(task_id, working_set)?;
+        if &task.owner != context.sender() {
+            bail!(
+                "Sender {} can't cancel task {} owned by {}",
+                context.sender(),
+                task_id,
+                task.owner
+            )
+        }
+        if task.status != TaskStatus::Scheduled {
+            bail!("Task {} isn't scheduled anymore", task_id)
+        }
+
+        let refund = task.deposit;
+        task.deposit = 0;
+        task.status = TaskStatus::Cancelled;
+        self.tasks.set(&task_id, &task, working_set);
+        self.dequeue(task_id, working_set);
+        self.transfer_deposit(&self.address, &task.owner, refund, working_set)?;
+
+        working_set.emit_event(&TaskCancelled::<C> {
+            task_id,
+            owner: task.owner,
+            refund,
+        });
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the task `task_id`, if it exists.
+    pub fn get_task(&self, task_id: TaskId, working_set: &mut WorkingSet<C>) -> Option<Task<C>> {
+        self.tasks.get(&task_id, working_set)
+    }
+
+    /// Returns the identifiers of the scheduled tasks, in the order of their next execution.
+    pub fn get_scheduled_tasks(&self, working_set: &mut WorkingSet<C>) -> Vec<TaskId> {
+        self.queue
+            .get(working_set)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, task_id)| task_id)
+            .collect()
+    }
+
+    fn get_task_or_err(&self, task_id: TaskId, working_set: &mut WorkingSet<C>) -> Result<Task<C>> {
+        self.tasks
+            .get(&task_id, working_set)
+            .with_context(|| format!("Task {} doesn't exist", task_id))
+    }
+
+    /// Inserts the task `task_id` in the queue, keeping it ordered.
+    pub(crate) fn enqueue(
+        &self,
+        task_id: TaskId,
+        execute_at: u64,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut queue = self.queue.get(working_set).unwrap_or_default();
+        let entry = (execute_at, task_id);
+        let index = queue.partition_point(|queued| queued < &entry);
+        queue.insert(index, entry);
+        self.queue.set(&queue, working_set);
+    }
+
+    fn dequeue(&self, task_id: TaskId, working_set: &mut WorkingSet<C>) {
+        let mut queue = self.queue.get(working_set).unwrap_or_default();
+        queue.retain(|(_, queued)| *queued != task_id);
+        self.queue.set(&queue, working_set);
+    }
+
+    pub(crate) fn transfer_deposit(
+        &self,
+        from: &C::Address,
+        to: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let coins = Coins {
+            amount,
+            token_address: self.token_address.get_or_err(working_set)?,
+        };
+        self.bank.transfer_from(from, to, coins, working_set)?;
+        Ok(())
+    }
+}
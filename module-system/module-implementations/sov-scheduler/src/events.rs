@@ -0,0 +1,68 @@
+//! The typed events emitted by the scheduler module.
+use sov_bank::Amount;
+use sov_modules_api::ModuleEvent;
+
+use crate::TaskId;
+
+/// Emitted when `owner` schedules the task `task_id`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "task_scheduled")]
+pub struct TaskScheduled<C: sov_modules_api::Context> {
+    /// The identifier of the task.
+    #[indexed]
+    pub task_id: TaskId,
+    /// The owner of the task.
+    #[indexed]
+    pub owner: C::Address,
+    /// The slot height from which the first execution is due.
+    pub execute_at: u64,
+    /// The number of executions of the task.
+    pub executions: u32,
+    /// The deposit locked for all the executions.
+    pub deposit: Amount,
+}
+
+/// Emitted when `owner` cancels the task `task_id`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "task_cancelled")]
+pub struct TaskCancelled<C: sov_modules_api::Context> {
+    /// The identifier of the task.
+    #[indexed]
+    pub task_id: TaskId,
+    /// The owner of the task.
+    #[indexed]
+    pub owner: C::Address,
+    /// The deposit of the remaining executions, returned to the owner.
+    pub refund: Amount,
+}
+
+/// Emitted when the call of the task `task_id` succeeds at the start of the slot `slot_height`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "task_executed")]
+pub struct TaskExecuted<C: sov_modules_api::Context> {
+    /// The identifier of the task.
+    #[indexed]
+    pub task_id: TaskId,
+    /// The owner of the task, and the sender of the call.
+    #[indexed]
+    pub owner: C::Address,
+    /// The slot height of the execution.
+    pub slot_height: u64,
+}
+
+/// Emitted when the call of the task `task_id` returns an error at the start of the slot `slot_height`.
+/// The state changes of the call are rolled back.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "task_failed")]
+pub struct TaskFailed<C: sov_modules_api::Context> {
+    /// The identifier of the task.
+    #[indexed]
+    pub task_id: TaskId,
+    /// The owner of the task, and the sender of the call.
+    #[indexed]
+    pub owner: C::Address,
+    /// The slot height of the execution.
+    pub slot_height: u64,
+    /// The error returned by the call.
+    pub error: String,
+}
@@ -0,0 +1,40 @@
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::Scheduler;
+
+/// Initial configuration for sov-scheduler module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct SchedulerConfig<C: sov_modules_api::Context> {
+    /// The address of the `sov-bank` token used for the execution deposits.
+    pub token_address: C::Address,
+    /// The amount of tokens deposited per unit of gas reserved by an execution.
+    pub deposit_per_gas: Amount,
+    /// The sum of the gas limits of the tasks executed at the start of a slot.
+    pub max_gas_per_slot: u64,
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Scheduler<C, Rt> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if config.max_gas_per_slot == 0 {
+            bail!("The gas budget of a slot must be non-zero")
+        }
+        self.token_address.set(&config.token_address, working_set);
+        self.deposit_per_gas
+            .set(&config.deposit_per_gas, working_set);
+        self.max_gas_per_slot
+            .set(&config.max_gas_per_slot, working_set);
+        self.next_task_id.set(&0, working_set);
+        self.queue.set(&vec![], working_set);
+        Ok(())
+    }
+}
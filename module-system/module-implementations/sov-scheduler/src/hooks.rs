@@ -0,0 +1,113 @@
+use sov_bank::Coins;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::events::{TaskExecuted, TaskFailed};
+use crate::{Scheduler, TaskId, TaskStatus};
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Scheduler<C, Rt> {
+    /// Logic executed at the beginning of the slot `slot_height`, which the runtime sets to the visible slot height.
+    /// The due tasks are executed in the order of the queue, as long as the sum of their gas limits fits in the gas
+    /// budget of the slot. The tasks which don't fit are deferred to the next slot, ahead of the tasks due later.
+    ///
+    /// The state changes of a call which returns an error are rolled back. The execution is consumed anyway, and
+    /// its deposit is burnt.
+    pub fn begin_slot_hook(&self, slot_height: u64, working_set: &mut WorkingSet<C>) {
+        let queue = self.queue.get(working_set).unwrap_or_default();
+        if !matches!(queue.first(), Some((execute_at, _)) if *execute_at <= slot_height) {
+            return;
+        }
+
+        let mut gas_budget = self.max_gas_per_slot.get(working_set).unwrap_or_default();
+        let mut dequeued = 0;
+        let mut due = Vec::new();
+        for (execute_at, task_id) in queue.iter().copied() {
+            if execute_at > slot_height {
+                break;
+            }
+            if let Some(task) = self.tasks.get(&task_id, working_set) {
+                if task.gas_limit > gas_budget {
+                    break;
+                }
+                gas_budget -= task.gas_limit;
+                due.push(task_id);
+            }
+            dequeued += 1;
+        }
+        // The queue is updated first, so that the executed calls can schedule new tasks
+        self.queue.set(&queue[dequeued..].to_vec(), working_set);
+
+        for task_id in due {
+            self.execute(task_id, slot_height, working_set);
+        }
+    }
+
+    /// Dispatches the call of the task `task_id` with its owner as the sender, and reschedules it if it recurs.
+    fn execute(&self, task_id: TaskId, slot_height: u64, working_set: &mut WorkingSet<C>) {
+        // A task executed earlier in the slot may have cancelled this one
+        let Some(mut task) = self.tasks.get(&task_id, working_set) else {
+            return;
+        };
+        if task.status != TaskStatus::Scheduled {
+            return;
+        }
+
+        // The task is updated first, so that the call can't execute it again
+        let fee = task.deposit_per_execution();
+        task.deposit -= fee;
+        task.remaining_executions -= 1;
+        if task.remaining_executions == 0 {
+            task.status = TaskStatus::Completed;
+        } else {
+            task.execute_at = task
+                .execute_at
+                .saturating_add(task.interval)
+                .max(slot_height.saturating_add(1));
+            self.enqueue(task_id, task.execute_at, working_set);
+        }
+        self.tasks.set(&task_id, &task, working_set);
+
+        if fee > 0 {
+            let coins = Coins {
+                amount: fee,
+                token_address: self
+                    .token_address
+                    .get(working_set)
+                    .expect("The execution token is set at genesis"),
+            };
+            self.bank
+                .burn(coins, &self.address, working_set)
+                .expect("The scheduler module holds the execution deposits");
+        }
+
+        let context = C::new(task.owner.clone(), self.address.clone(), slot_height);
+        let mut savepoint = working_set.savepoint();
+        let result = Rt::decode_call(&task.call)
+            .map_err(anyhow::Error::from)
+            .and_then(|call| {
+                Rt::default()
+                    .dispatch_call(call, &mut savepoint, &context)
+                    .map_err(|sov_modules_api::Error::ModuleError(err)| err)
+            });
+
+        match result {
+            Ok(_) => {
+                savepoint.commit();
+                working_set.emit_event(&TaskExecuted::<C> {
+                    task_id,
+                    owner: task.owner,
+                    slot_height,
+                });
+            }
+            Err(err) => {
+                savepoint.rollback();
+                working_set.emit_event(&TaskFailed::<C> {
+                    task_id,
+                    owner: task.owner,
+                    slot_height,
+                    error: err.to_string(),
+                });
+            }
+        }
+    }
+}
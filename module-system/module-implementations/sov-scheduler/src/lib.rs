@@ -0,0 +1,175 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+pub mod events;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+use std::marker::PhantomData;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_modules_api::{
+    CallResponse, DispatchCall, Error, ModuleInfo, StateMap, StateValue, WorkingSet,
+};
+
+/// The identifier of a scheduled task.
+pub type TaskId = u64;
+
+/// The lifecycle of a task.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub enum TaskStatus {
+    /// The task is waiting for its next execution.
+    Scheduled,
+    /// All the executions of the task took place, whether their calls succeeded or not.
+    Completed,
+    /// The task was cancelled by its owner before its last execution.
+    Cancelled,
+}
+
+/// A runtime call executed at the start of a future slot, on behalf of the address which scheduled it.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C::Address: serde::Serialize",
+        deserialize = "C::Address: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Task<C: sov_modules_api::Context> {
+    /// The address which scheduled the task, and the sender of the executed call.
+    pub owner: C::Address,
+    /// The serialized runtime call.
+    pub call: Vec<u8>,
+    /// The slot height from which the next execution is due.
+    pub execute_at: u64,
+    /// The gas reserved for each execution, counted against the gas budget of the slot.
+    pub gas_limit: u64,
+    /// The number of slots between two executions of a recurring task.
+    pub interval: u64,
+    /// The number of executions left, including the next one.
+    pub remaining_executions: u32,
+    /// The deposit locked for the remaining executions.
+    pub deposit: Amount,
+    /// The status of the task.
+    pub status: TaskStatus,
+}
+
+impl<C: sov_modules_api::Context> Task<C> {
+    /// Returns the share of the deposit paying for the next execution.
+    pub fn deposit_per_execution(&self) -> Amount {
+        self.deposit / Amount::from(self.remaining_executions.max(1))
+    }
+}
+
+/// The `sov-scheduler` module executes runtime calls at future slots, without relying on off-chain keepers.
+/// It provides functionality for:
+/// - Scheduling a one-off or recurring runtime call, backed by an execution deposit.
+/// - Cancelling a scheduled task, which refunds the deposit of its remaining executions.
+/// - Executing the due tasks at the start of each slot under a gas budget, see [`Scheduler::begin_slot_hook`].
+///
+/// Modules schedule their own calls, e.g. to settle auctions or expire offers, by sending the
+/// [`CallMessage::Schedule`] message with [`WorkingSet::call_module`]. The runtime `Rt` decodes and dispatches
+/// the scheduled calls.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Scheduler<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> {
+    /// The address of the sov-scheduler module, which holds the execution deposits.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address of the token paying for the executions.
+    #[state]
+    pub(crate) token_address: StateValue<C::Address>,
+
+    /// The amount of tokens deposited per unit of gas reserved by an execution.
+    #[state]
+    pub(crate) deposit_per_gas: StateValue<Amount>,
+
+    /// The sum of the gas limits of the tasks executed at the start of a slot.
+    #[state]
+    pub(crate) max_gas_per_slot: StateValue<u64>,
+
+    /// The identifier of the next task.
+    #[state]
+    pub(crate) next_task_id: StateValue<TaskId>,
+
+    /// The tasks, keyed by their identifier.
+    #[state]
+    pub(crate) tasks: StateMap<TaskId, Task<C>>,
+
+    /// The scheduled tasks, ordered by the height of their next execution and then by identifier.
+    #[state]
+    pub(crate) queue: StateValue<Vec<(u64, TaskId)>>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// The runtime dispatching the scheduled calls.
+    #[phantom]
+    pub(crate) runtime: PhantomData<Rt>,
+}
+
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> sov_modules_api::Module
+    for Scheduler<C, Rt>
+{
+    type Context = C;
+
+    type Config = SchedulerConfig<C>;
+
+    type CallMessage = call::CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Schedule {
+                call,
+                execute_at,
+                gas_limit,
+                interval,
+                repetitions,
+            } => {
+                self.schedule(
+                    call,
+                    execute_at,
+                    gas_limit,
+                    interval,
+                    repetitions,
+                    context,
+                    working_set,
+                )?;
+                Ok(CallResponse::default())
+            }
+
+            call::CallMessage::Cancel { task_id } => {
+                Ok(self.cancel(task_id, context, working_set)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Defines rpc queries exposed by the scheduler module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{DispatchCall, WorkingSet};
+
+use crate::{Scheduler, Task, TaskId};
+
+/// Structure returned by the `getTask` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct TaskResponse<C: sov_modules_api::Context> {
+    /// The task, if it exists.
+    pub task: Option<Task<C>>,
+}
+
+/// Structure returned by the `getScheduledTasks` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ScheduledTasksResponse {
+    /// The identifiers of the tasks waiting for their next execution, in the order of execution.
+    pub task_ids: Vec<TaskId>,
+}
+
+#[rpc_gen(client, server, namespace = "scheduler")]
+impl<C: sov_modules_api::Context, Rt: DispatchCall<Context = C> + Default> Scheduler<C, Rt> {
+    #[rpc_method(name = "getTask")]
+    /// Rpc method that returns the task `task_id`, with its next execution height and status.
+    pub fn task(
+        &self,
+        task_id: TaskId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<TaskResponse<C>> {
+        Ok(TaskResponse {
+            task: self.get_task(task_id, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getScheduledTasks")]
+    /// Rpc method that returns the identifiers of the tasks waiting for their next execution.
+    pub fn scheduled_task_ids(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ScheduledTasksResponse> {
+        Ok(ScheduledTasksResponse {
+            task_ids: self.get_scheduled_tasks(working_set),
+        })
+    }
+}
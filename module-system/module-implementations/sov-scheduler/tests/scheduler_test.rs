@@ -0,0 +1,238 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, Module, ModuleEvent,
+    ModuleInfo, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_scheduler::events::TaskExecuted;
+use sov_scheduler::{CallMessage, Scheduler, SchedulerConfig, TaskId, TaskStatus};
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    bank: Bank<C>,
+    scheduler: Scheduler<C, TestRuntime<C>>,
+}
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    owner: Address,
+    recipient: Address,
+    token_address: Address,
+}
+
+impl TestSetup {
+    fn new(working_set: &mut WorkingSet<C>) -> Self {
+        let owner = generate_address::<C>("owner");
+        let recipient = generate_address::<C>("recipient");
+
+        let token_name = "Token".to_owned();
+        let token_address = get_genesis_token_address::<C>(&token_name, 0);
+        let bank_config = BankConfig::<C> {
+            tokens: vec![TokenConfig {
+                token_name,
+                address_and_balances: vec![(owner, 1000)],
+                authorized_minters: vec![],
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
+                salt: 0,
+            }],
+            fee_settlement: Default::default(),
+        };
+        let scheduler_config = SchedulerConfig::<C> {
+            token_address,
+            deposit_per_gas: 1,
+            max_gas_per_slot: 100,
+        };
+
+        let runtime = TestRuntime::<C>::default();
+        runtime.bank.genesis(&bank_config, working_set).unwrap();
+        runtime
+            .scheduler
+            .genesis(&scheduler_config, working_set)
+            .unwrap();
+
+        Self {
+            runtime,
+            owner,
+            recipient,
+            token_address,
+        }
+    }
+
+    fn context(&self, height: u64) -> C {
+        C::new(self.owner, generate_address::<C>("sequencer"), height)
+    }
+
+    fn balance_of(&self, address: Address, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.runtime
+            .bank
+            .get_balance_of(address, self.token_address, working_set)
+    }
+
+    fn transfer_call(&self, amount: u64) -> Vec<u8> {
+        <TestRuntime<C> as EncodeCall<Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+            to: self.recipient,
+            coins: Coins {
+                amount,
+                token_address: self.token_address,
+            },
+        })
+    }
+
+    fn schedule(
+        &self,
+        amount: u64,
+        execute_at: u64,
+        gas_limit: u64,
+        interval: u64,
+        repetitions: u32,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<sov_modules_api::CallResponse, sov_modules_api::Error> {
+        self.runtime.scheduler.call(
+            CallMessage::Schedule {
+                call: self.transfer_call(amount),
+                execute_at,
+                gas_limit,
+                interval,
+                repetitions,
+            },
+            &self.context(1),
+            working_set,
+        )
+    }
+
+    fn status_of(&self, task_id: TaskId, working_set: &mut WorkingSet<C>) -> TaskStatus {
+        self.runtime
+            .scheduler
+            .get_task(task_id, working_set)
+            .unwrap()
+            .status
+    }
+}
+
+#[test]
+fn due_tasks_are_executed_under_gas_budget() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let scheduler = &setup.runtime.scheduler;
+
+    // Tasks must be in the future, and fit in the gas budget of a slot
+    assert!(setup.schedule(10, 1, 60, 0, 0, &mut working_set).is_err());
+    assert!(setup.schedule(10, 5, 101, 0, 0, &mut working_set).is_err());
+
+    setup
+        .schedule(10, 5, 60, 0, 0, &mut working_set)
+        .expect("Schedule call failed");
+    setup
+        .schedule(20, 5, 60, 0, 0, &mut working_set)
+        .expect("Schedule call failed");
+    // The owner doesn't hold enough tokens for this transfer
+    setup
+        .schedule(2000, 6, 30, 0, 0, &mut working_set)
+        .expect("Schedule call failed");
+    assert_eq!(Some(850), setup.balance_of(setup.owner, &mut working_set));
+    assert_eq!(
+        vec![0, 1, 2],
+        scheduler.get_scheduled_tasks(&mut working_set)
+    );
+
+    scheduler.begin_slot_hook(4, &mut working_set);
+    assert_eq!(None, setup.balance_of(setup.recipient, &mut working_set));
+
+    // The second task doesn't fit in the gas budget, and is deferred
+    working_set.take_events();
+    scheduler.begin_slot_hook(5, &mut working_set);
+    assert_eq!(
+        Some(10),
+        setup.balance_of(setup.recipient, &mut working_set)
+    );
+    assert_eq!(TaskStatus::Completed, setup.status_of(0, &mut working_set));
+    assert_eq!(vec![1, 2], scheduler.get_scheduled_tasks(&mut working_set));
+    let executed = TaskExecuted::<C> {
+        task_id: 0,
+        owner: setup.owner,
+        slot_height: 5,
+    };
+    assert!(working_set.events().contains(&executed.to_event()));
+
+    // The failed call is rolled back, and its deposit is burnt anyway
+    working_set.take_events();
+    scheduler.begin_slot_hook(6, &mut working_set);
+    assert_eq!(
+        Some(30),
+        setup.balance_of(setup.recipient, &mut working_set)
+    );
+    assert_eq!(Some(820), setup.balance_of(setup.owner, &mut working_set));
+    assert_eq!(TaskStatus::Completed, setup.status_of(1, &mut working_set));
+    assert_eq!(TaskStatus::Completed, setup.status_of(2, &mut working_set));
+    assert!(working_set
+        .events()
+        .iter()
+        .any(|event| event.key().inner() == b"task_failed"));
+    assert!(scheduler.get_scheduled_tasks(&mut working_set).is_empty());
+    assert_eq!(
+        Some(0),
+        setup.balance_of(*scheduler.address(), &mut working_set)
+    );
+}
+
+#[test]
+fn recurring_task_can_be_cancelled() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let scheduler = &setup.runtime.scheduler;
+
+    // A recurring task needs an interval
+    assert!(setup.schedule(10, 5, 10, 0, 2, &mut working_set).is_err());
+
+    setup
+        .schedule(10, 5, 10, 3, 2, &mut working_set)
+        .expect("Schedule call failed");
+    assert_eq!(Some(970), setup.balance_of(setup.owner, &mut working_set));
+
+    scheduler.begin_slot_hook(5, &mut working_set);
+    scheduler.begin_slot_hook(7, &mut working_set);
+    let task = scheduler.get_task(0, &mut working_set).unwrap();
+    assert_eq!(8, task.execute_at);
+    assert_eq!(2, task.remaining_executions);
+    assert_eq!(
+        Some(10),
+        setup.balance_of(setup.recipient, &mut working_set)
+    );
+
+    scheduler.begin_slot_hook(8, &mut working_set);
+    assert_eq!(
+        Some(20),
+        setup.balance_of(setup.recipient, &mut working_set)
+    );
+
+    // Only the owner can cancel the task, which refunds the last execution
+    let cancel = CallMessage::Cancel { task_id: 0 };
+    let other = C::new(setup.recipient, setup.recipient, 9);
+    assert!(scheduler
+        .call(cancel.clone(), &other, &mut working_set)
+        .is_err());
+    scheduler
+        .call(cancel.clone(), &setup.context(9), &mut working_set)
+        .expect("Cancel call failed");
+    assert_eq!(TaskStatus::Cancelled, setup.status_of(0, &mut working_set));
+    assert_eq!(Some(960), setup.balance_of(setup.owner, &mut working_set));
+    assert!(scheduler
+        .call(cancel, &setup.context(9), &mut working_set)
+        .is_err());
+
+    scheduler.begin_slot_hook(11, &mut working_set);
+    assert_eq!(
+        Some(20),
+        setup.balance_of(setup.recipient, &mut working_set)
+    );
+}
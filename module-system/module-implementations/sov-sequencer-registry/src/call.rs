@@ -1,10 +1,10 @@
-use anyhow::bail;
+use anyhow::{bail, Context as _};
 #[cfg(feature = "native")]
 use sov_modules_api::macros::CliWalletArg;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
-use crate::SequencerRegistry;
+use crate::{PendingExit, SequencerRegistry};
 
 /// This enumeration represents the available call messages for interacting with
 /// the `sov-sequencer-registry` module.
@@ -25,11 +25,51 @@ pub enum CallMessage {
         /// The raw Da address of the sequencer you're registering.
         da_address: Vec<u8>,
     },
-    /// Remove a sequencer from the sequencer registry.
+    /// Remove a sequencer from the sequencer registry. Its bond stays locked, and can still be slashed,
+    /// until the end of the exit delay.
     Exit {
         /// The raw Da address of the sequencer you're removing.
         da_address: Vec<u8>,
     },
+    /// Withdraw the bond of a sequencer which exited, once the exit delay is over.
+    Withdraw {
+        /// The raw Da address of the sequencer which exited.
+        da_address: Vec<u8>,
+    },
+    /// Report a sequencer which signed two different batches for the same DA height.
+    /// The sequencer is slashed and removed from the registry, see [`crate::SlashingParams`].
+    ReportEquivocation {
+        /// The raw Da address of the reported sequencer.
+        da_address: Vec<u8>,
+        /// The DA height of both batches.
+        da_height: u64,
+        /// The serialized public key of the sequencer.
+        pub_key: Vec<u8>,
+        /// The hash of the first batch.
+        first_batch_hash: Vec<u8>,
+        /// The serialized signature of the [`crate::BatchHeader`] of the first batch.
+        first_signature: Vec<u8>,
+        /// The hash of the second batch.
+        second_batch_hash: Vec<u8>,
+        /// The serialized signature of the [`crate::BatchHeader`] of the second batch.
+        second_signature: Vec<u8>,
+    },
+    /// Report a sequencer which signed a batch containing a transaction without a valid signature.
+    /// The sequencer is slashed and removed from the registry, see [`crate::SlashingParams`].
+    ReportInvalidTransaction {
+        /// The raw Da address of the reported sequencer.
+        da_address: Vec<u8>,
+        /// The DA height of the batch.
+        da_height: u64,
+        /// The serialized public key of the sequencer.
+        pub_key: Vec<u8>,
+        /// The serialized signature of the [`crate::BatchHeader`] of the batch.
+        signature: Vec<u8>,
+        /// The serialized batch, as posted on the DA layer.
+        batch: Vec<u8>,
+        /// The index of the badly signed transaction in the batch.
+        tx_index: u32,
+    },
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
@@ -50,8 +90,6 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<CallResponse> {
-        let locker = &self.address;
-        let coins = self.coins_to_lock.get_or_err(working_set)?;
         let sequencer = context.sender();
 
         let belongs_to = self
@@ -62,16 +100,72 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
             bail!("Unauthorized exit attempt");
         }
 
+        let exit_delay = self.exit_delay.get(working_set).unwrap_or_default();
+        let pending_exit = PendingExit {
+            sequencer: belongs_to,
+            bond: self.bond_of(da_address, working_set)?,
+            withdrawable_at: context.slot_height().saturating_add(exit_delay),
+        };
         self.delete(da_address, working_set);
 
+        self.pending_exits
+            .set(da_address, &pending_exit, working_set);
+        let mut pending_exit_list = self.pending_exit_list.get(working_set).unwrap_or_default();
+        pending_exit_list.push(da_address.clone());
+        self.pending_exit_list.set(&pending_exit_list, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn withdraw(
+        &self,
+        da_address: &Da::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let locker = &self.address;
+        let mut coins = self.coins_to_lock.get_or_err(working_set)?;
+        let sequencer = context.sender();
+
+        let pending_exit = self
+            .pending_exits
+            .get(da_address, working_set)
+            .with_context(|| format!("Sequencer {} has no pending exit", da_address))?;
+
+        if sequencer != &pending_exit.sequencer {
+            bail!("Unauthorized withdrawal attempt");
+        }
+        if context.slot_height() < pending_exit.withdrawable_at {
+            bail!(
+                "The bond of sequencer {} can't be withdrawn before slot {}",
+                da_address,
+                pending_exit.withdrawable_at
+            );
+        }
+
+        self.remove_pending_exit(da_address, working_set);
+
+        coins.amount = pending_exit.bond;
         self.bank
             .transfer_from(locker, sequencer, coins, working_set)?;
 
         Ok(CallResponse::default())
     }
 
+    pub(crate) fn remove_pending_exit(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        self.pending_exits.delete(da_address, working_set);
+        let mut pending_exit_list = self.pending_exit_list.get(working_set).unwrap_or_default();
+        pending_exit_list.retain(|pending| pending != da_address);
+        self.pending_exit_list.set(&pending_exit_list, working_set);
+    }
+
     pub(crate) fn delete(&self, da_address: &Da::Address, working_set: &mut WorkingSet<C>) {
         self.allowed_sequencers.delete(da_address, working_set);
+        self.bonds.delete(da_address, working_set);
 
         if let Some(preferred_sequencer) = self.preferred_sequencer.get(working_set) {
             if da_address == &preferred_sequencer {
@@ -0,0 +1,27 @@
+//! The typed events emitted by the sequencer registry module.
+use sov_bank::Amount;
+use sov_modules_api::ModuleEvent;
+
+use crate::Misbehavior;
+
+/// Emitted when the sequencer `sequencer` is slashed for a misbehavior reported by `reporter`.
+#[derive(borsh::BorshSerialize, ModuleEvent, Debug, Clone, PartialEq, Eq)]
+#[event(name = "sequencer_slashed")]
+pub struct SequencerSlashed<C: sov_modules_api::Context> {
+    /// The rollup address of the slashed sequencer.
+    #[indexed]
+    pub sequencer: C::Address,
+    /// The raw DA address of the slashed sequencer.
+    pub da_address: Vec<u8>,
+    /// The proven misbehavior.
+    pub misbehavior: Misbehavior,
+    /// The address which submitted the evidence.
+    #[indexed]
+    pub reporter: C::Address,
+    /// The slashed tokens rewarded to the reporter.
+    pub reward: Amount,
+    /// The slashed tokens which were burnt.
+    pub burnt: Amount,
+    /// The part of the bond returned to the sequencer.
+    pub refund: Amount,
+}
@@ -0,0 +1,277 @@
+use anyhow::{bail, Context as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_bank::Amount;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::prelude::*;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{PublicKey, Signature, Spec, WorkingSet};
+
+use crate::events::SequencerSlashed;
+use crate::SequencerRegistry;
+
+/// The denominator of the slashing ratios.
+pub const BASIS_POINTS: u64 = 10_000;
+
+/// The parameters of the slashing of sequencers reported with evidence of misbehavior.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub struct SlashingParams {
+    /// The share of the bond of a misbehaving sequencer which is slashed, in basis points.
+    /// The rest of the bond is returned to the sequencer, which is removed from the registry.
+    pub slash_fraction: u64,
+    /// The share of the slashed tokens rewarded to the reporter of the misbehavior, in basis points.
+    /// The rest of the slashed tokens is burnt.
+    pub reporter_reward: u64,
+}
+
+impl Default for SlashingParams {
+    fn default() -> Self {
+        Self {
+            slash_fraction: BASIS_POINTS,
+            reporter_reward: 0,
+        }
+    }
+}
+
+impl SlashingParams {
+    /// Checks that both ratios are at most [`BASIS_POINTS`].
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        if self.slash_fraction > BASIS_POINTS || self.reporter_reward > BASIS_POINTS {
+            bail!(
+                "The slash fraction and the reporter reward must be at most {} basis points",
+                BASIS_POINTS
+            )
+        }
+        Ok(())
+    }
+
+    /// Splits `bond` into the reporter reward, the burnt amount and the amount returned to the sequencer.
+    pub fn split(&self, bond: u64) -> (u64, u64, u64) {
+        let slashed = (bond as u128 * self.slash_fraction as u128 / BASIS_POINTS as u128) as u64;
+        let reward = (slashed as u128 * self.reporter_reward as u128 / BASIS_POINTS as u128) as u64;
+        (reward, slashed - reward, bond - slashed)
+    }
+}
+
+/// The statement signed by a sequencer for each batch it posts on the DA layer.
+/// Signing two different batches for the same DA height is an equivocation.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, Clone, PartialEq, Eq)]
+pub struct BatchHeader {
+    /// The height of the DA block the batch is posted in.
+    pub da_height: u64,
+    /// The hash of the serialized batch, with the hasher of the rollup.
+    pub batch_hash: [u8; 32],
+}
+
+impl BatchHeader {
+    /// Returns the header of the serialized `batch` posted at `da_height`.
+    pub fn new<C: Spec>(da_height: u64, batch: &[u8]) -> Self {
+        Self {
+            da_height,
+            batch_hash: C::Hasher::digest(batch).into(),
+        }
+    }
+
+    /// Returns the bytes signed by the sequencer.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        self.try_to_vec()
+            .expect("Serializing a batch header to a vector can't fail")
+    }
+}
+
+/// A misbehavior of a sequencer, proven by the evidence of a call message.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// The sequencer signed two different batches for the same DA height.
+    Equivocation,
+    /// The sequencer signed a batch containing a transaction without a valid signature.
+    InvalidTransactionSignature,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
+    /// Checks that the sequencer `da_address` signed two different batches at `da_height`, and slashes it.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn report_equivocation(
+        &self,
+        da_address: &Da::Address,
+        da_height: u64,
+        pub_key: &[u8],
+        first: (&[u8], &[u8]),
+        second: (&[u8], &[u8]),
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        if first.0 == second.0 {
+            bail!("An equivocation needs two different batches");
+        }
+
+        let pub_key = self.sequencer_key(da_address, pub_key, working_set)?;
+        for (batch_hash, signature) in [first, second] {
+            let header = BatchHeader {
+                da_height,
+                batch_hash: batch_hash
+                    .try_into()
+                    .context("A batch hash must be 32 bytes long")?,
+            };
+            verify_header::<C>(&header, &pub_key, signature)?;
+        }
+
+        self.slash(
+            da_address,
+            Misbehavior::Equivocation,
+            context.sender(),
+            working_set,
+        )
+    }
+
+    /// Checks that the sequencer `da_address` signed the serialized `batch`, and that its transaction `tx_index`
+    /// doesn't carry a valid signature. Slashes the sequencer.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn report_invalid_transaction(
+        &self,
+        da_address: &Da::Address,
+        da_height: u64,
+        pub_key: &[u8],
+        signature: &[u8],
+        batch: &[u8],
+        tx_index: u32,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let pub_key = self.sequencer_key(da_address, pub_key, working_set)?;
+        verify_header::<C>(
+            &BatchHeader::new::<C>(da_height, batch),
+            &pub_key,
+            signature,
+        )?;
+
+        let txs =
+            Vec::<Vec<u8>>::try_from_slice(batch).context("The batch can't be deserialized")?;
+        let raw_tx = txs
+            .get(tx_index as usize)
+            .with_context(|| format!("The batch has no transaction {}", tx_index))?;
+        if let Ok(tx) = Transaction::<C>::try_from_slice(raw_tx) {
+            if tx.verify().is_ok() {
+                bail!("Transaction {} of the batch is correctly signed", tx_index);
+            }
+        }
+
+        self.slash(
+            da_address,
+            Misbehavior::InvalidTransactionSignature,
+            context.sender(),
+            working_set,
+        )
+    }
+
+    /// Returns the rollup address and the bond of the sequencer `da_address`, if it is registered
+    /// or waiting for the withdrawal of its bond.
+    fn bonded_sequencer(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<Option<(C::Address, Amount)>> {
+        if let Some(sequencer) = self.allowed_sequencers.get(da_address, working_set) {
+            return Ok(Some((sequencer, self.bond_of(da_address, working_set)?)));
+        }
+        Ok(self
+            .pending_exits
+            .get(da_address, working_set)
+            .map(|exit| (exit.sequencer, exit.bond)))
+    }
+
+    /// Returns the public key `pub_key` if it belongs to the sequencer `da_address`, which must be registered
+    /// or waiting for the withdrawal of its bond.
+    fn sequencer_key(
+        &self,
+        da_address: &Da::Address,
+        pub_key: &[u8],
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<C::PublicKey> {
+        let (rollup_address, _) = self
+            .bonded_sequencer(da_address, working_set)?
+            .with_context(|| format!("Sequencer {} has no bond to slash", da_address))?;
+        let pub_key = C::PublicKey::try_from(pub_key)?;
+        if pub_key.to_address::<C::Address>() != rollup_address {
+            bail!(
+                "The public key doesn't belong to sequencer {}",
+                rollup_address
+            );
+        }
+        Ok(pub_key)
+    }
+
+    /// Removes the sequencer `da_address` from the registry or cancels its pending exit, and splits its bond
+    /// between `reporter`, the burnt tokens and the sequencer according to the [`SlashingParams`].
+    fn slash(
+        &self,
+        da_address: &Da::Address,
+        misbehavior: Misbehavior,
+        reporter: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let (sequencer, bond) = self
+            .bonded_sequencer(da_address, working_set)?
+            .with_context(|| format!("Sequencer {} has no bond to slash", da_address))?;
+        let token_address = self.coins_to_lock.get_or_err(working_set)?.token_address;
+        let params = self.slashing_params.get(working_set).unwrap_or_default();
+        let (reward, burnt, refund) = params.split(bond);
+        self.delete(da_address, working_set);
+        self.remove_pending_exit(da_address, working_set);
+
+        for (amount, to) in [(reward, reporter), (refund, &sequencer)] {
+            if amount > 0 {
+                let coins = sov_bank::Coins {
+                    amount,
+                    token_address: token_address.clone(),
+                };
+                self.bank
+                    .transfer_from(&self.address, to, coins, working_set)?;
+            }
+        }
+        if burnt > 0 {
+            let coins = sov_bank::Coins {
+                amount: burnt,
+                token_address,
+            };
+            self.bank.burn(coins, &self.address, working_set)?;
+        }
+
+        working_set.emit_event(&SequencerSlashed::<C> {
+            sequencer,
+            da_address: da_address.as_ref().to_vec(),
+            misbehavior,
+            reporter: reporter.clone(),
+            reward,
+            burnt,
+            refund,
+        });
+        Ok(())
+    }
+}
+
+fn verify_header<C: sov_modules_api::Context>(
+    header: &BatchHeader,
+    pub_key: &C::PublicKey,
+    signature: &[u8],
+) -> anyhow::Result<()> {
+    let signature = C::Signature::try_from(signature)?;
+    signature
+        .verify(pub_key, &header.signing_bytes())
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "Invalid signature of the batch header at DA height {}: {}",
+                header.da_height,
+                err
+            )
+        })
+}
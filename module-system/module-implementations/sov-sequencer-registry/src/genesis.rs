@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
 
-use crate::SequencerRegistry;
+use crate::{SequencerRegistry, SlashingParams};
 
 /// Genesis configuration for the [`SequencerRegistry`] module.
 ///
@@ -34,6 +34,14 @@ pub struct SequencerConfig<C: sov_modules_api::Context, Da: sov_modules_api::DaS
     /// block, which means the preferred sequencer can guarantee soft
     /// confirmation time for transactions.
     pub is_preferred_sequencer: bool,
+    /// The parameters of the slashing of sequencers reported with evidence of misbehavior.
+    /// By default, the whole bond of a misbehaving sequencer is burnt.
+    #[serde(default)]
+    pub slashing_params: SlashingParams,
+    /// The number of slots between the exit announcement of a sequencer and the withdrawal of its bond.
+    /// The bond can still be slashed during this delay.
+    #[serde(default)]
+    pub exit_delay: u64,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
@@ -42,7 +50,11 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         config: &<Self as sov_modules_api::Module>::Config,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
+        config.slashing_params.validate()?;
         self.coins_to_lock.set(&config.coins_to_lock, working_set);
+        self.slashing_params
+            .set(&config.slashing_params, working_set);
+        self.exit_delay.set(&config.exit_delay, working_set);
         self.register_sequencer(
             &config.seq_da_address,
             &config.seq_rollup_address,
@@ -66,7 +78,7 @@ mod tests {
     use sov_modules_api::default_context::DefaultContext;
     use sov_modules_api::{AddressBech32, Spec};
 
-    use crate::SequencerConfig;
+    use crate::{SequencerConfig, SlashingParams};
 
     #[test]
     fn test_config_serialization() {
@@ -97,6 +109,8 @@ mod tests {
             seq_da_address: seq_da_addreess,
             coins_to_lock: coins,
             is_preferred_sequencer: true,
+            slashing_params: SlashingParams::default(),
+            exit_delay: 0,
         };
 
         let data = r#"
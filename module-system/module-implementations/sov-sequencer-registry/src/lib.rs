@@ -7,14 +7,18 @@
 
 #![deny(missing_docs)]
 mod call;
+pub mod events;
+mod evidence;
 mod genesis;
 mod hooks;
 #[cfg(feature = "native")]
 mod query;
 pub use call::*;
+pub use evidence::*;
 pub use genesis::*;
 #[cfg(feature = "native")]
 pub use query::*;
+use sov_bank::Amount;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
 use sov_state::codec::BcsCodec;
@@ -53,6 +57,47 @@ pub struct SequencerRegistry<C: sov_modules_api::Context, Da: sov_modules_api::D
     /// allowed to exit.
     #[state]
     pub(crate) coins_to_lock: StateValue<sov_bank::Coins<C>>,
+
+    /// The amount of tokens locked by each registered sequencer.
+    #[state]
+    pub(crate) bonds: StateMap<Da::Address, Amount, BcsCodec>,
+
+    /// The parameters of the slashing of sequencers reported with evidence of misbehavior.
+    #[state]
+    pub(crate) slashing_params: StateValue<SlashingParams>,
+
+    /// The number of slots between the exit announcement of a sequencer and the withdrawal of its bond.
+    #[state]
+    pub(crate) exit_delay: StateValue<u64>,
+
+    /// The sequencers which announced their exit, and can still be slashed until they withdraw their bond.
+    #[state]
+    pub(crate) pending_exits: StateMap<Da::Address, PendingExit<C>, BcsCodec>,
+
+    /// The DA addresses of the sequencers with a pending exit, in the order of their announcement.
+    #[state]
+    pub(crate) pending_exit_list: StateValue<Vec<Da::Address>, BcsCodec>,
+}
+
+/// The exit of a sequencer which is waiting for the end of the exit delay.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct PendingExit<C: sov_modules_api::Context> {
+    /// The rollup address of the sequencer, which receives the bond.
+    pub sequencer: C::Address,
+    /// The amount of tokens locked by the sequencer.
+    pub bond: Amount,
+    /// The slot height from which the bond can be withdrawn.
+    pub withdrawable_at: u64,
 }
 
 /// Result of applying a blob, from sequencer's point of view.
@@ -97,6 +142,52 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> sov_modules_api::
                 let da_address = Da::Address::try_from(&da_address)?;
                 self.exit(&da_address, context, working_set)?
             }
+            CallMessage::Withdraw { da_address } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.withdraw(&da_address, context, working_set)?
+            }
+            CallMessage::ReportEquivocation {
+                da_address,
+                da_height,
+                pub_key,
+                first_batch_hash,
+                first_signature,
+                second_batch_hash,
+                second_signature,
+            } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.report_equivocation(
+                    &da_address,
+                    da_height,
+                    &pub_key,
+                    (&first_batch_hash, &first_signature),
+                    (&second_batch_hash, &second_signature),
+                    context,
+                    working_set,
+                )?;
+                CallResponse::default()
+            }
+            CallMessage::ReportInvalidTransaction {
+                da_address,
+                da_height,
+                pub_key,
+                signature,
+                batch,
+                tx_index,
+            } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.report_invalid_transaction(
+                    &da_address,
+                    da_height,
+                    &pub_key,
+                    &signature,
+                    &batch,
+                    tx_index,
+                    context,
+                    working_set,
+                )?;
+                CallResponse::default()
+            }
         })
     }
 }
@@ -120,17 +211,58 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         {
             anyhow::bail!("sequencer {} already registered", rollup_address)
         }
+        if self.pending_exits.get(da_address, working_set).is_some() {
+            anyhow::bail!(
+                "sequencer {} has a pending exit and can't register again before withdrawing its bond",
+                da_address
+            )
+        }
         let locker = &self.address;
         let coins = self.coins_to_lock.get_or_err(working_set)?;
+        let bond = coins.amount;
         self.bank
             .transfer_from(rollup_address, locker, coins, working_set)?;
 
         self.allowed_sequencers
             .set(da_address, rollup_address, working_set);
+        self.bonds.set(da_address, &bond, working_set);
 
         Ok(())
     }
 
+    /// Returns the amount of tokens locked by the registered sequencer `da_address`.
+    pub(crate) fn bond_of(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<Amount> {
+        match self.bonds.get(da_address, working_set) {
+            Some(bond) => Ok(bond),
+            // Sequencers registered before the bonds were tracked locked the configured amount
+            None => Ok(self.coins_to_lock.get_or_err(working_set)?.amount),
+        }
+    }
+
+    /// Returns the pending exit of the sequencer `da_address`, if it announced its exit and didn't
+    /// withdraw its bond yet.
+    pub fn get_pending_exit(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<PendingExit<C>> {
+        self.pending_exits.get(da_address, working_set)
+    }
+
+    /// Returns the DA addresses of the sequencers with a pending exit, in the order of their announcement.
+    pub fn get_pending_exits(&self, working_set: &mut WorkingSet<C>) -> Vec<Da::Address> {
+        self.pending_exit_list.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the slashing parameters.
+    pub fn get_slashing_params(&self, working_set: &mut WorkingSet<C>) -> SlashingParams {
+        self.slashing_params.get(working_set).unwrap_or_default()
+    }
+
     /// Returns the preferred sequencer, or [`None`] it wasn't set.
     ///
     /// Read about [`SequencerConfig::is_preferred_sequencer`] to learn about
@@ -3,7 +3,7 @@ use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
 
-use crate::SequencerRegistry;
+use crate::{PendingExit, SequencerRegistry};
 
 /// The response type to the `getSequencerDddress` RPC method.
 #[cfg_attr(
@@ -16,6 +16,29 @@ pub struct SequencerAddressResponse<C: Context> {
     pub address: Option<C::Address>,
 }
 
+/// The response type to the `getPendingExit` RPC method.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone)
+)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct PendingExitResponse<C: Context> {
+    /// The pending exit of the requested sequencer.
+    pub pending_exit: Option<PendingExit<C>>,
+}
+
+/// The response type to the `getPendingExits` RPC method.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone),
+    serde(bound = "Da::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct PendingExitsResponse<Da: sov_modules_api::DaSpec> {
+    /// The DA addresses of the sequencers with a pending exit.
+    pub da_addresses: Vec<Da::Address>,
+}
+
 #[rpc_gen(client, server, namespace = "sequencer")]
 impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
     /// Returns the rollup address of the sequencer with the given DA address.
@@ -31,4 +54,30 @@ impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
             address: self.allowed_sequencers.get(&da_address, working_set),
         })
     }
+
+    /// Returns the pending exit of the sequencer with the given DA address.
+    ///
+    /// The response only contains data if the sequencer announced its exit and
+    /// didn't withdraw its bond yet.
+    #[rpc_method(name = "getPendingExit")]
+    pub fn pending_exit(
+        &self,
+        da_address: Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<PendingExitResponse<C>> {
+        Ok(PendingExitResponse {
+            pending_exit: self.get_pending_exit(&da_address, working_set),
+        })
+    }
+
+    /// Returns the DA addresses of the sequencers waiting for the withdrawal of their bond.
+    #[rpc_method(name = "getPendingExits")]
+    pub fn pending_exits(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<PendingExitsResponse<Da>> {
+        Ok(PendingExitsResponse {
+            da_addresses: self.get_pending_exits(working_set),
+        })
+    }
 }
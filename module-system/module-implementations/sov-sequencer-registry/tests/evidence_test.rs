@@ -0,0 +1,218 @@
+use borsh::BorshSerialize;
+use helpers::*;
+use sov_mock_da::MockAddress;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, Module, PrivateKey, PublicKey, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_sequencer_registry::{BatchHeader, CallMessage, SlashingParams};
+
+mod helpers;
+
+const REPORTER_KEY: &str = "reporter";
+
+struct SlashingSetup {
+    test_sequencer: TestSequencer,
+    key: DefaultPrivateKey,
+    sequencer_address: <C as Spec>::Address,
+    da_address: MockAddress,
+}
+
+impl SlashingSetup {
+    /// Registers a sequencer whose rollup address is derived from a known key.
+    fn new(working_set: &mut WorkingSet<C>) -> Self {
+        let key = DefaultPrivateKey::generate();
+        let sequencer_address = key.pub_key().to_address::<<C as Spec>::Address>();
+        let da_address = MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS);
+
+        let mut test_sequencer = create_test_sequencer();
+        test_sequencer.bank_config.tokens[0]
+            .address_and_balances
+            .push((sequencer_address, INITIAL_BALANCE));
+        test_sequencer.sequencer_config.slashing_params = SlashingParams {
+            slash_fraction: 5_000,
+            reporter_reward: 2_000,
+        };
+        test_sequencer.genesis(working_set);
+
+        let register_message = CallMessage::Register {
+            da_address: da_address.as_ref().to_vec(),
+        };
+        test_sequencer
+            .registry
+            .call(
+                register_message,
+                &C::new(sequencer_address, sequencer_address, 1),
+                working_set,
+            )
+            .expect("Sequencer registration has failed");
+
+        Self {
+            test_sequencer,
+            key,
+            sequencer_address,
+            da_address,
+        }
+    }
+
+    fn sign(&self, key: &DefaultPrivateKey, header: &BatchHeader) -> Vec<u8> {
+        key.sign(&header.signing_bytes()).try_to_vec().unwrap()
+    }
+
+    fn report(
+        &self,
+        message: CallMessage,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<sov_modules_api::CallResponse, sov_modules_api::Error> {
+        let reporter = generate_address(REPORTER_KEY);
+        self.test_sequencer
+            .registry
+            .call(message, &C::new(reporter, reporter, 2), working_set)
+    }
+
+    fn equivocation(&self, signer: &DefaultPrivateKey, second_batch: &[u8]) -> CallMessage {
+        let first = BatchHeader::new::<C>(10, b"first batch");
+        let second = BatchHeader::new::<C>(10, second_batch);
+        CallMessage::ReportEquivocation {
+            da_address: self.da_address.as_ref().to_vec(),
+            da_height: 10,
+            pub_key: self.key.pub_key().try_to_vec().unwrap(),
+            first_batch_hash: first.batch_hash.to_vec(),
+            first_signature: self.sign(&self.key, &first),
+            second_batch_hash: second.batch_hash.to_vec(),
+            second_signature: self.sign(signer, &second),
+        }
+    }
+
+    fn balance(&mut self, address: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.test_sequencer
+            .query_balance(address, working_set)
+            .unwrap()
+            .amount
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn equivocation_is_slashed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let mut setup = SlashingSetup::new(working_set);
+
+    // Both batches must be signed by the sequencer, and be different
+    let other_key = DefaultPrivateKey::generate();
+    assert!(setup
+        .report(setup.equivocation(&other_key, b"second batch"), working_set)
+        .is_err());
+    assert!(setup
+        .report(setup.equivocation(&setup.key, b"first batch"), working_set)
+        .is_err());
+
+    setup
+        .report(setup.equivocation(&setup.key, b"second batch"), working_set)
+        .expect("Equivocation report has failed");
+
+    // Half of the bond is slashed, and a fifth of the slashed tokens rewards the reporter
+    assert_eq!(
+        20,
+        setup.balance(generate_address(REPORTER_KEY), working_set)
+    );
+    assert_eq!(
+        INITIAL_BALANCE - LOCKED_AMOUNT / 2,
+        setup.balance(setup.sequencer_address, working_set)
+    );
+    assert!(!setup
+        .test_sequencer
+        .registry
+        .is_sender_allowed(&setup.da_address, working_set));
+
+    // The sequencer can't be slashed twice
+    assert!(setup
+        .report(setup.equivocation(&setup.key, b"second batch"), working_set)
+        .is_err());
+}
+
+#[test]
+fn invalid_transaction_is_slashed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let mut setup = SlashingSetup::new(working_set);
+
+    let valid_tx = Transaction::<C>::new_signed_tx(&setup.key, vec![1, 2, 3], 0, 0, 0, 0)
+        .try_to_vec()
+        .unwrap();
+    let mut invalid_tx = valid_tx.clone();
+    *invalid_tx.last_mut().unwrap() ^= 1;
+    let batch = vec![valid_tx, invalid_tx].try_to_vec().unwrap();
+    let header = BatchHeader::new::<C>(10, &batch);
+
+    let report = |tx_index| CallMessage::ReportInvalidTransaction {
+        da_address: setup.da_address.as_ref().to_vec(),
+        da_height: 10,
+        pub_key: setup.key.pub_key().try_to_vec().unwrap(),
+        signature: setup.sign(&setup.key, &header),
+        batch: batch.clone(),
+        tx_index,
+    };
+
+    // The transaction must exist and be badly signed
+    assert!(setup.report(report(0), working_set).is_err());
+    assert!(setup.report(report(2), working_set).is_err());
+
+    setup
+        .report(report(1), working_set)
+        .expect("Invalid transaction report has failed");
+    assert_eq!(
+        20,
+        setup.balance(generate_address(REPORTER_KEY), working_set)
+    );
+    assert!(!setup
+        .test_sequencer
+        .registry
+        .is_sender_allowed(&setup.da_address, working_set));
+}
+
+#[test]
+fn pending_exit_is_slashed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let mut setup = SlashingSetup::new(working_set);
+
+    let exit_message = CallMessage::Exit {
+        da_address: setup.da_address.as_ref().to_vec(),
+    };
+    let sender_context = C::new(setup.sequencer_address, setup.sequencer_address, 1);
+    setup
+        .test_sequencer
+        .registry
+        .call(exit_message, &sender_context, working_set)
+        .expect("Sequencer exit has failed");
+
+    // The exited sequencer can still be slashed during the exit delay
+    setup
+        .report(setup.equivocation(&setup.key, b"second batch"), working_set)
+        .expect("Equivocation report has failed");
+    assert!(setup
+        .test_sequencer
+        .registry
+        .get_pending_exit(&setup.da_address, working_set)
+        .is_none());
+    assert_eq!(
+        INITIAL_BALANCE - LOCKED_AMOUNT / 2,
+        setup.balance(setup.sequencer_address, working_set)
+    );
+
+    let withdraw_message = CallMessage::Withdraw {
+        da_address: setup.da_address.as_ref().to_vec(),
+    };
+    let withdraw_context = C::new(
+        setup.sequencer_address,
+        setup.sequencer_address,
+        1 + EXIT_DELAY,
+    );
+    assert!(setup
+        .test_sequencer
+        .registry
+        .call(withdraw_message, &withdraw_context, working_set)
+        .is_err());
+}
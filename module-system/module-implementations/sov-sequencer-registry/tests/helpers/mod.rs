@@ -3,7 +3,7 @@ use sov_mock_da::{MockAddress, MockDaSpec};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::digest::Digest;
 use sov_modules_api::{Address, Module, Spec, WorkingSet};
-use sov_sequencer_registry::{SequencerConfig, SequencerRegistry};
+use sov_sequencer_registry::{SequencerConfig, SequencerRegistry, SlashingParams};
 
 pub type C = DefaultContext;
 pub type Da = MockDaSpec;
@@ -21,6 +21,7 @@ pub const UNKNOWN_SEQUENCER_DA_ADDRESS: [u8; 32] = [3; 32];
 pub const LOW_FUND_KEY: &str = "zero_funds";
 pub const INITIAL_BALANCE: u64 = 210;
 pub const LOCKED_AMOUNT: u64 = 200;
+pub const EXIT_DELAY: u64 = 10;
 
 pub struct TestSequencer {
     pub bank: sov_bank::Bank<C>,
@@ -79,12 +80,16 @@ pub fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
             (generate_address(LOW_FUND_KEY), 3),
         ],
         authorized_minters: vec![],
+        admins: vec![],
+        max_supply: None,
+        metadata: None,
         salt: 8,
     };
 
     (
         sov_bank::BankConfig {
             tokens: vec![token_config],
+            fee_settlement: Default::default(),
         },
         seq_address,
     )
@@ -102,6 +107,8 @@ pub fn create_sequencer_config(
             token_address,
         },
         is_preferred_sequencer: false,
+        slashing_params: SlashingParams::default(),
+        exit_delay: EXIT_DELAY,
     }
 }
 
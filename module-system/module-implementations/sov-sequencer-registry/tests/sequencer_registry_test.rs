@@ -80,18 +80,67 @@ fn test_registration_lifecycle() {
         .call(exit_message, &sender_context, working_set)
         .expect("Sequencer exit has failed");
 
-    let balance_after_exit = test_sequencer
-        .query_balance(sequencer_address, working_set)
-        .unwrap()
-        .amount
-        .unwrap();
-    assert_eq!(balance_before, balance_after_exit);
-
     let registry_response_after_exit = test_sequencer
         .registry
         .sequencer_address(da_address, working_set)
         .unwrap();
     assert!(registry_response_after_exit.address.is_none());
+
+    // The bond stays locked until the end of the exit delay
+    let pending_exit = test_sequencer
+        .registry
+        .pending_exit(da_address, working_set)
+        .unwrap()
+        .pending_exit
+        .expect("The exit should be pending");
+    assert_eq!(LOCKED_AMOUNT, pending_exit.bond);
+    assert_eq!(1 + EXIT_DELAY, pending_exit.withdrawable_at);
+    assert_eq!(
+        vec![da_address],
+        test_sequencer
+            .registry
+            .pending_exits(working_set)
+            .unwrap()
+            .da_addresses
+    );
+
+    let withdraw_message = CallMessage::Withdraw {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    let early_context = C::new(sequencer_address, reward_address, EXIT_DELAY);
+    assert!(test_sequencer
+        .registry
+        .call(withdraw_message.clone(), &early_context, working_set)
+        .is_err());
+    assert_eq!(
+        balance_after_registration,
+        test_sequencer
+            .query_balance(sequencer_address, working_set)
+            .unwrap()
+            .amount
+            .unwrap()
+    );
+
+    let withdraw_context = C::new(sequencer_address, reward_address, 1 + EXIT_DELAY);
+    test_sequencer
+        .registry
+        .call(withdraw_message.clone(), &withdraw_context, working_set)
+        .expect("Bond withdrawal has failed");
+
+    let balance_after_withdrawal = test_sequencer
+        .query_balance(sequencer_address, working_set)
+        .unwrap()
+        .amount
+        .unwrap();
+    assert_eq!(balance_before, balance_after_withdrawal);
+    assert!(test_sequencer
+        .registry
+        .get_pending_exit(&da_address, working_set)
+        .is_none());
+    assert!(test_sequencer
+        .registry
+        .call(withdraw_message, &withdraw_context, working_set)
+        .is_err());
 }
 
 #[test]
@@ -0,0 +1,344 @@
+use anyhow::{bail, Context as _, Result};
+use sov_bank::{Amount, Coins};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, WorkingSet};
+
+use crate::{InflationSchedule, Staking, Unbonding, Validator};
+
+/// The maximum number of registered validators.
+pub const MAX_VALIDATORS: usize = 100;
+
+/// The maximum number of pending unbondings of a delegator.
+pub const MAX_UNBONDINGS: usize = 16;
+
+/// This enumeration represents the available call messages for interacting with the sov-staking module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: sov_modules_api::Context> {
+    /// Registers the sender as a validator, which can receive delegations.
+    RegisterValidator,
+
+    /// Bonds tokens of the sender to a validator.
+    Delegate {
+        /// The address of the validator.
+        validator: C::Address,
+        /// The amount of tokens to bond.
+        amount: Amount,
+    },
+
+    /// Unbonds tokens of the sender from a validator. The tokens can be withdrawn after the unbonding period.
+    Undelegate {
+        /// The address of the validator.
+        validator: C::Address,
+        /// The amount of tokens to unbond.
+        amount: Amount,
+    },
+
+    /// Transfers the tokens of the sender whose unbonding period is over back to the sender.
+    WithdrawUnbonded,
+
+    /// Replaces the inflation schedule. Only the admin can send this call.
+    UpdateInflation {
+        /// The amount of tokens minted at each slot before the first halving.
+        initial_reward_per_slot: Amount,
+        /// The number of slots between two halvings of the reward, or zero for a constant reward.
+        halving_interval: u64,
+    },
+}
+
+impl<C: sov_modules_api::Context> Staking<C> {
+    /// Registers the sender as a validator.
+    pub fn register_validator(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.insert_validator(context.sender(), working_set)?;
+        working_set.add_event(
+            "register_validator",
+            &format!("validator: {}", context.sender()),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Bonds `amount` tokens of the sender to `validator`, in exchange for shares of its stake.
+    pub fn delegate(
+        &self,
+        validator_address: C::Address,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        if amount == 0 {
+            bail!("A delegation must bond a non-zero amount of tokens")
+        }
+        let mut validator = self.get_validator_or_err(&validator_address, working_set)?;
+        let shares = if validator.shares == 0 {
+            amount as u128
+        } else if validator.stake == 0 {
+            bail!(
+                "The stake of validator {} was entirely slashed",
+                validator_address
+            )
+        } else {
+            amount as u128 * validator.shares / validator.stake as u128
+        };
+        if shares == 0 {
+            bail!("The amount {} is too small to buy a share", amount)
+        }
+
+        self.bank
+            .transfer_from(
+                context.sender(),
+                &self.address,
+                self.coins(amount, working_set)?,
+                working_set,
+            )
+            .context("Failed to bond the delegation")?;
+
+        validator.stake += amount;
+        validator.shares += shares;
+        self.validators
+            .set(&validator_address, &validator, working_set);
+
+        let key = (context.sender().clone(), validator_address.clone());
+        let delegation = self.delegations.get(&key, working_set).unwrap_or_default();
+        self.delegations
+            .set(&key, &(delegation + shares), working_set);
+
+        working_set.add_event(
+            "delegate",
+            &format!(
+                "delegator: {}, validator: {}, amount: {}",
+                context.sender(),
+                validator_address,
+                amount
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Unbonds `amount` tokens of the sender from `validator`. The tokens can be withdrawn once
+    /// the unbonding period is over.
+    pub fn undelegate(
+        &self,
+        validator_address: C::Address,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        if amount == 0 {
+            bail!("An undelegation must unbond a non-zero amount of tokens")
+        }
+        let mut validator = self.get_validator_or_err(&validator_address, working_set)?;
+        let key = (context.sender().clone(), validator_address.clone());
+        let delegation = self.delegations.get(&key, working_set).unwrap_or_default();
+        if validator.shares_to_amount(delegation) < amount {
+            bail!(
+                "{} has less than {} tokens delegated to {}",
+                context.sender(),
+                amount,
+                validator_address
+            )
+        }
+
+        let mut unbondings = self
+            .unbondings
+            .get(context.sender(), working_set)
+            .unwrap_or_default();
+        if unbondings.len() >= MAX_UNBONDINGS {
+            bail!(
+                "{} already has {} pending unbondings",
+                context.sender(),
+                MAX_UNBONDINGS
+            )
+        }
+
+        // The shares are rounded up, so that the remaining delegators aren't diluted
+        let stake = validator.stake as u128;
+        let shares = (amount as u128 * validator.shares + stake - 1) / stake;
+        let shares = shares.min(delegation);
+        validator.stake -= amount;
+        validator.shares -= shares;
+        self.validators
+            .set(&validator_address, &validator, working_set);
+        if delegation == shares {
+            self.delegations.delete(&key, working_set);
+        } else {
+            self.delegations
+                .set(&key, &(delegation - shares), working_set);
+        }
+
+        let release_height = context
+            .slot_height()
+            .saturating_add(self.unbonding_period.get(working_set).unwrap_or_default());
+        unbondings.push(Unbonding {
+            amount,
+            release_height,
+        });
+        self.unbondings
+            .set(context.sender(), &unbondings, working_set);
+
+        working_set.add_event(
+            "undelegate",
+            &format!(
+                "delegator: {}, validator: {}, amount: {}, release_height: {}",
+                context.sender(),
+                validator_address,
+                amount,
+                release_height
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the unbonded tokens of the sender whose release height is reached back to the sender.
+    pub fn withdraw_unbonded(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let unbondings = self
+            .unbondings
+            .get(context.sender(), working_set)
+            .unwrap_or_default();
+        let (released, pending): (Vec<_>, Vec<_>) = unbondings
+            .into_iter()
+            .partition(|unbonding| unbonding.release_height <= context.slot_height());
+        let amount: Amount = released.iter().map(|unbonding| unbonding.amount).sum();
+        if amount == 0 {
+            bail!("{} has no unbonded tokens to withdraw", context.sender())
+        }
+
+        if pending.is_empty() {
+            self.unbondings.delete(context.sender(), working_set);
+        } else {
+            self.unbondings.set(context.sender(), &pending, working_set);
+        }
+        self.bank.transfer_from(
+            &self.address,
+            context.sender(),
+            self.coins(amount, working_set)?,
+            working_set,
+        )?;
+
+        working_set.add_event(
+            "withdraw_unbonded",
+            &format!("delegator: {}, amount: {}", context.sender(), amount),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the inflation schedule. Only the admin can update it.
+    pub fn update_inflation(
+        &self,
+        inflation: InflationSchedule,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let admin = self.admin.get_or_err(working_set)?;
+        if context.sender() != &admin {
+            bail!("Sender {} is not the staking admin", context.sender())
+        }
+        self.inflation.set(&inflation, working_set);
+
+        working_set.add_event(
+            "update_inflation",
+            &format!(
+                "initial_reward_per_slot: {}, halving_interval: {}",
+                inflation.initial_reward_per_slot, inflation.halving_interval
+            ),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the validator registered at `validator`, if any.
+    pub fn get_validator(
+        &self,
+        validator: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Validator> {
+        self.validators.get(validator, working_set)
+    }
+
+    /// Returns the amount of tokens delegated by `delegator` to `validator`, including the compounded rewards.
+    pub fn get_delegated_amount(
+        &self,
+        delegator: &C::Address,
+        validator: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Amount {
+        let shares = self
+            .delegations
+            .get(&(delegator.clone(), validator.clone()), working_set)
+            .unwrap_or_default();
+        self.validators
+            .get(validator, working_set)
+            .map(|validator| validator.shares_to_amount(shares))
+            .unwrap_or_default()
+    }
+
+    /// Returns the pending unbondings of `delegator`.
+    pub fn get_unbondings(
+        &self,
+        delegator: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<Unbonding> {
+        self.unbondings
+            .get(delegator, working_set)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn insert_validator(
+        &self,
+        validator: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if self.validators.get(validator, working_set).is_some() {
+            bail!("Validator {} is already registered", validator)
+        }
+        let mut validator_list = self.validator_list.get(working_set).unwrap_or_default();
+        if validator_list.len() >= MAX_VALIDATORS {
+            bail!("There are already {} validators", MAX_VALIDATORS)
+        }
+
+        validator_list.push(validator.clone());
+        self.validator_list.set(&validator_list, working_set);
+        self.validators
+            .set(validator, &Validator::default(), working_set);
+        Ok(())
+    }
+
+    pub(crate) fn coins(
+        &self,
+        amount: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Coins<C>> {
+        Ok(Coins {
+            amount,
+            token_address: self.token_address.get_or_err(working_set)?,
+        })
+    }
+
+    fn get_validator_or_err(
+        &self,
+        validator: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Validator> {
+        self.validators
+            .get(validator, working_set)
+            .with_context(|| format!("Validator {} is not registered", validator))
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::{InflationSchedule, Staking};
+
+/// Initial configuration for sov-staking module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct StakingConfig<C: sov_modules_api::Context> {
+    /// The address of the staked `sov-bank` token. The staking module must be one of its minters
+    /// to mint the rewards.
+    pub token_address: C::Address,
+    /// The address allowed to update the inflation schedule, e.g. the `sov-governance` module.
+    pub admin: C::Address,
+    /// The number of slots between an undelegation and the withdrawal of its tokens.
+    pub unbonding_period: u64,
+    /// The initial inflation schedule.
+    pub inflation: InflationSchedule,
+    /// The validators registered at genesis.
+    pub validators: Vec<C::Address>,
+}
+
+impl<C: sov_modules_api::Context> Staking<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.token_address.set(&config.token_address, working_set);
+        self.admin.set(&config.admin, working_set);
+        self.unbonding_period
+            .set(&config.unbonding_period, working_set);
+        self.inflation.set(&config.inflation, working_set);
+        for validator in config.validators.iter() {
+            self.insert_validator(validator, working_set)?;
+        }
+        Ok(())
+    }
+}
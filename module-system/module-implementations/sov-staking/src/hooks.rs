@@ -0,0 +1,60 @@
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::Staking;
+
+impl<C: sov_modules_api::Context> Staking<C> {
+    /// Logic executed at the beginning of the slot `slot_height`, which the runtime sets to the visible slot height.
+    /// The reward of the slot is minted to the module, and added to the stake of the validators in proportion
+    /// to their stake, which compounds the rewards of their delegators.
+    pub fn begin_slot_hook(&self, slot_height: u64, working_set: &mut WorkingSet<C>) {
+        let Some(inflation) = self.inflation.get(working_set) else {
+            return;
+        };
+        let reward = inflation.reward_at(slot_height);
+        if reward == 0 {
+            return;
+        }
+
+        let mut validators = Vec::new();
+        for address in self.validator_list.get(working_set).unwrap_or_default() {
+            if let Some(validator) = self.validators.get(&address, working_set) {
+                validators.push((address, validator));
+            }
+        }
+        let total_stake: u128 = validators
+            .iter()
+            .map(|(_, validator)| validator.stake as u128)
+            .sum();
+        if total_stake == 0 {
+            return;
+        }
+
+        let rewards: Vec<Amount> = validators
+            .iter()
+            .map(|(_, validator)| {
+                (reward as u128 * validator.stake as u128 / total_stake) as Amount
+            })
+            .collect();
+        let minted: Amount = rewards.iter().sum();
+        let minting = self.coins(minted, working_set).and_then(|coins| {
+            self.bank
+                .mint(&coins, &self.address, &self.address, working_set)
+        });
+        if let Err(err) = minting {
+            working_set.add_event(
+                "rewards_skipped",
+                &format!("slot_height: {}, error: {}", slot_height, err),
+            );
+            return;
+        }
+
+        for ((address, mut validator), reward) in validators.into_iter().zip(rewards) {
+            if reward > 0 {
+                validator.stake += reward;
+                self.validators.set(&address, &validator, working_set);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use sov_bank::Amount;
+
+/// The rewards minted for the stakers at each slot. The reward per slot halves every `halving_interval` slots,
+/// or stays constant if the interval is zero.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct InflationSchedule {
+    /// The amount of tokens minted at each slot before the first halving.
+    pub initial_reward_per_slot: Amount,
+    /// The number of slots between two halvings of the reward.
+    pub halving_interval: u64,
+}
+
+impl InflationSchedule {
+    /// Returns the amount of tokens minted at the slot `height`.
+    pub fn reward_at(&self, height: u64) -> Amount {
+        if self.halving_interval == 0 {
+            return self.initial_reward_per_slot;
+        }
+        let halvings = height / self.halving_interval;
+        if halvings >= Amount::BITS as u64 {
+            return 0;
+        }
+        self.initial_reward_per_slot >> halvings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_reward() {
+        let schedule = InflationSchedule {
+            initial_reward_per_slot: 100,
+            halving_interval: 0,
+        };
+        assert_eq!(100, schedule.reward_at(0));
+        assert_eq!(100, schedule.reward_at(u64::MAX));
+    }
+
+    #[test]
+    fn halving_reward() {
+        let schedule = InflationSchedule {
+            initial_reward_per_slot: 100,
+            halving_interval: 10,
+        };
+        assert_eq!(100, schedule.reward_at(9));
+        assert_eq!(50, schedule.reward_at(10));
+        assert_eq!(25, schedule.reward_at(25));
+        assert_eq!(0, schedule.reward_at(u64::MAX));
+    }
+}
@@ -0,0 +1,169 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+mod genesis;
+mod hooks;
+mod inflation;
+#[cfg(feature = "native")]
+mod query;
+mod slashing;
+
+pub use call::*;
+pub use genesis::*;
+pub use inflation::*;
+#[cfg(feature = "native")]
+pub use query::*;
+pub use slashing::*;
+use sov_bank::Amount;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The denominator of the slashed fractions.
+pub const BASIS_POINTS: u64 = 10_000;
+
+/// A validator receiving delegations. The stake grows with the rewards and shrinks with the slashes,
+/// and the delegators own shares of it.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct Validator {
+    /// The amount of tokens bonded to the validator, including the compounded rewards.
+    pub stake: Amount,
+    /// The total number of shares owned by the delegators.
+    pub shares: u128,
+}
+
+impl Validator {
+    /// Returns the amount of tokens represented by `shares`.
+    pub fn shares_to_amount(&self, shares: u128) -> Amount {
+        if self.shares == 0 {
+            return 0;
+        }
+        (shares * self.stake as u128 / self.shares) as Amount
+    }
+}
+
+/// Tokens undelegated from a validator, which can be withdrawn once the unbonding period is over.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct Unbonding {
+    /// The amount of undelegated tokens.
+    pub amount: Amount,
+    /// The slot height from which the tokens can be withdrawn.
+    pub release_height: u64,
+}
+
+/// The `sov-staking` module lets the holders of a `sov-bank` token delegate it to validators, such as
+/// sequencers. It provides functionality for:
+/// - Registering validators.
+/// - Delegating tokens to a validator, and undelegating them through an unbonding period.
+/// - Minting rewards at each slot according to an [`InflationSchedule`], see [`Staking::begin_slot_hook`].
+/// - Slashing the stake of a validator, through the [`SlashingHooks`] used by other modules.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Staking<C: sov_modules_api::Context> {
+    /// The address of the sov-staking module, which holds the bonded and unbonding tokens.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address of the staked token. The module must be allowed to mint it.
+    #[state]
+    pub(crate) token_address: StateValue<C::Address>,
+
+    /// The address allowed to update the inflation schedule.
+    #[state]
+    pub(crate) admin: StateValue<C::Address>,
+
+    /// The number of slots between an undelegation and the withdrawal of its tokens.
+    #[state]
+    pub(crate) unbonding_period: StateValue<u64>,
+
+    /// The rewards minted at each slot.
+    #[state]
+    pub(crate) inflation: StateValue<InflationSchedule>,
+
+    /// The registered validators, in registration order.
+    #[state]
+    pub(crate) validator_list: StateValue<Vec<C::Address>>,
+
+    /// The validators, keyed by their address.
+    #[state]
+    pub(crate) validators: StateMap<C::Address, Validator>,
+
+    /// The shares of the delegations, keyed by delegator and validator.
+    #[state]
+    pub(crate) delegations: StateMap<(C::Address, C::Address), u128>,
+
+    /// The pending unbondings of each delegator.
+    #[state]
+    pub(crate) unbondings: StateMap<C::Address, Vec<Unbonding>>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Staking<C> {
+    type Context = C;
+
+    type Config = StakingConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::RegisterValidator => {
+                Ok(self.register_validator(context, working_set)?)
+            }
+
+            call::CallMessage::Delegate { validator, amount } => {
+                Ok(self.delegate(validator, amount, context, working_set)?)
+            }
+
+            call::CallMessage::Undelegate { validator, amount } => {
+                Ok(self.undelegate(validator, amount, context, working_set)?)
+            }
+
+            call::CallMessage::WithdrawUnbonded => {
+                Ok(self.withdraw_unbonded(context, working_set)?)
+            }
+
+            call::CallMessage::UpdateInflation {
+                initial_reward_per_slot,
+                halving_interval,
+            } => {
+                let inflation = InflationSchedule {
+                    initial_reward_per_slot,
+                    halving_interval,
+                };
+                Ok(self.update_inflation(inflation, context, working_set)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Defines rpc queries exposed by the staking module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_bank::Amount;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::WorkingSet;
+
+use crate::{InflationSchedule, Staking, Unbonding, Validator};
+
+/// Structure returned by the `getValidator` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ValidatorResponse {
+    /// The validator, if it is registered.
+    pub validator: Option<Validator>,
+}
+
+/// Structure returned by the `getDelegation` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct DelegationResponse {
+    /// The amount of delegated tokens, including the compounded rewards.
+    pub amount: Amount,
+}
+
+/// Structure returned by the `getUnbondings` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct UnbondingsResponse {
+    /// The pending unbondings, in undelegation order.
+    pub unbondings: Vec<Unbonding>,
+}
+
+#[rpc_gen(client, server, namespace = "staking")]
+impl<C: sov_modules_api::Context> Staking<C> {
+    #[rpc_method(name = "getValidator")]
+    /// Rpc method that returns the stake and the shares of `validator`.
+    pub fn validator(
+        &self,
+        validator: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ValidatorResponse> {
+        Ok(ValidatorResponse {
+            validator: self.get_validator(&validator, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getValidators")]
+    /// Rpc method that returns the addresses of the registered validators.
+    pub fn validator_addresses(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<C::Address>> {
+        Ok(self.validator_list.get(working_set).unwrap_or_default())
+    }
+
+    #[rpc_method(name = "getDelegation")]
+    /// Rpc method that returns the amount of tokens delegated by `delegator` to `validator`.
+    pub fn delegation(
+        &self,
+        delegator: C::Address,
+        validator: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<DelegationResponse> {
+        Ok(DelegationResponse {
+            amount: self.get_delegated_amount(&delegator, &validator, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getUnbondings")]
+    /// Rpc method that returns the pending unbondings of `delegator`.
+    pub fn unbondings_of(
+        &self,
+        delegator: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<UnbondingsResponse> {
+        Ok(UnbondingsResponse {
+            unbondings: self.get_unbondings(&delegator, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getInflation")]
+    /// Rpc method that returns the inflation schedule.
+    pub fn inflation_schedule(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<InflationSchedule>> {
+        Ok(self.inflation.get(working_set))
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::{bail, Result};
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Staking, BASIS_POINTS};
+
+/// Hooks letting other modules punish a misbehaving validator, e.g. the `sov-sequencer-registry` module
+/// when a sequencer submits an invalid blob, or the `sov-attester-incentives` module when an attestation is invalid.
+///
+/// A module consuming the hooks is generic over them, and the runtime plugs in the [`Staking`] module,
+/// or `()` to disable slashing.
+pub trait SlashingHooks<C: Context> {
+    /// Burns `fraction` basis points of the stake bonded to `validator`. The tokens being unbonded aren't slashed.
+    /// Returns the amount of burnt tokens.
+    fn slash(
+        &self,
+        validator: &C::Address,
+        fraction: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Amount>;
+}
+
+/// No slashing.
+impl<C: Context> SlashingHooks<C> for () {
+    fn slash(
+        &self,
+        _validator: &C::Address,
+        _fraction: u64,
+        _working_set: &mut WorkingSet<C>,
+    ) -> Result<Amount> {
+        Ok(0)
+    }
+}
+
+impl<C: Context> SlashingHooks<C> for Staking<C> {
+    fn slash(
+        &self,
+        validator_address: &C::Address,
+        fraction: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Amount> {
+        if fraction > BASIS_POINTS {
+            bail!(
+                "The slashed fraction {} exceeds {} basis points",
+                fraction,
+                BASIS_POINTS
+            )
+        }
+        let Some(mut validator) = self.validators.get(validator_address, working_set) else {
+            bail!("Validator {} is not registered", validator_address)
+        };
+
+        let slashed = (validator.stake as u128 * fraction as u128 / BASIS_POINTS as u128) as Amount;
+        if slashed == 0 {
+            return Ok(0);
+        }
+        self.bank.burn(
+            self.coins(slashed, working_set)?,
+            &self.address,
+            working_set,
+        )?;
+        validator.stake -= slashed;
+        self.validators
+            .set(validator_address, &validator, working_set);
+
+        working_set.add_event(
+            "slash",
+            &format!("validator: {}, amount: {}", validator_address, slashed),
+        );
+        Ok(slashed)
+    }
+}
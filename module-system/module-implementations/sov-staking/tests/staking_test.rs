@@ -0,0 +1,216 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, ModuleInfo, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_staking::{CallMessage, InflationSchedule, SlashingHooks, Staking, StakingConfig};
+
+type C = DefaultContext;
+
+struct TestSetup {
+    bank: Bank<C>,
+    staking: Staking<C>,
+    admin: Address,
+    validator: Address,
+    delegators: [Address; 2],
+    token_address: Address,
+}
+
+impl TestSetup {
+    fn new(working_set: &mut WorkingSet<C>) -> Self {
+        let admin = generate_address::<C>("admin");
+        let validator = generate_address::<C>("validator");
+        let delegators = [
+            generate_address::<C>("delegator_1"),
+            generate_address::<C>("delegator_2"),
+        ];
+        let staking = Staking::<C>::default();
+
+        let token_name = "StakedToken".to_owned();
+        let token_address = get_genesis_token_address::<C>(&token_name, 0);
+        let bank = Bank::<C>::default();
+        let bank_config = BankConfig::<C> {
+            tokens: vec![TokenConfig {
+                token_name,
+                address_and_balances: vec![(delegators[0], 1000), (delegators[1], 1000)],
+                authorized_minters: vec![*staking.address()],
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
+                salt: 0,
+            }],
+            fee_settlement: Default::default(),
+        };
+        bank.genesis(&bank_config, working_set).unwrap();
+
+        let staking_config = StakingConfig::<C> {
+            token_address,
+            admin,
+            unbonding_period: 5,
+            inflation: InflationSchedule {
+                initial_reward_per_slot: 100,
+                halving_interval: 0,
+            },
+            validators: vec![validator],
+        };
+        staking.genesis(&staking_config, working_set).unwrap();
+
+        Self {
+            bank,
+            staking,
+            admin,
+            validator,
+            delegators,
+            token_address,
+        }
+    }
+
+    fn context(&self, sender: Address, height: u64) -> C {
+        C::new(sender, generate_address::<C>("sequencer"), height)
+    }
+
+    fn balance_of(&self, address: Address, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.bank
+            .get_balance_of(address, self.token_address, working_set)
+    }
+
+    fn delegated(&self, delegator: Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.staking
+            .get_delegated_amount(&delegator, &self.validator, working_set)
+    }
+}
+
+#[test]
+fn delegate_earn_rewards_and_undelegate() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let [delegator_1, delegator_2] = setup.delegators;
+
+    // No rewards are minted without any stake
+    setup.staking.begin_slot_hook(1, &mut working_set);
+    assert_eq!(
+        Some(2000),
+        setup
+            .bank
+            .get_total_supply_of(&setup.token_address, &mut working_set)
+    );
+
+    for (delegator, amount) in [(delegator_1, 300), (delegator_2, 100)] {
+        setup
+            .staking
+            .call(
+                CallMessage::Delegate {
+                    validator: setup.validator,
+                    amount,
+                },
+                &setup.context(delegator, 1),
+                &mut working_set,
+            )
+            .expect("Delegate call failed");
+    }
+    assert_eq!(Some(700), setup.balance_of(delegator_1, &mut working_set));
+
+    // The rewards compound into the stake of the validator
+    setup.staking.begin_slot_hook(2, &mut working_set);
+    assert_eq!(375, setup.delegated(delegator_1, &mut working_set));
+    assert_eq!(125, setup.delegated(delegator_2, &mut working_set));
+
+    // Slashing burns a fraction of the stake of the validator
+    let slashed = setup
+        .staking
+        .slash(&setup.validator, 2_000, &mut working_set)
+        .unwrap();
+    assert_eq!(100, slashed);
+    assert_eq!(300, setup.delegated(delegator_1, &mut working_set));
+    assert_eq!(
+        Some(2000),
+        setup
+            .bank
+            .get_total_supply_of(&setup.token_address, &mut working_set)
+    );
+
+    let undelegate = |amount| CallMessage::Undelegate {
+        validator: setup.validator,
+        amount,
+    };
+    assert!(setup
+        .staking
+        .call(
+            undelegate(301),
+            &setup.context(delegator_1, 3),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .staking
+        .call(
+            undelegate(300),
+            &setup.context(delegator_1, 3),
+            &mut working_set,
+        )
+        .expect("Undelegate call failed");
+    assert_eq!(0, setup.delegated(delegator_1, &mut working_set));
+    assert_eq!(100, setup.delegated(delegator_2, &mut working_set));
+
+    // The tokens are released after the unbonding period
+    assert!(setup
+        .staking
+        .call(
+            CallMessage::WithdrawUnbonded,
+            &setup.context(delegator_1, 7),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .staking
+        .call(
+            CallMessage::WithdrawUnbonded,
+            &setup.context(delegator_1, 8),
+            &mut working_set,
+        )
+        .expect("WithdrawUnbonded call failed");
+    assert_eq!(Some(1000), setup.balance_of(delegator_1, &mut working_set));
+    assert!(setup
+        .staking
+        .get_unbondings(&delegator_1, &mut working_set)
+        .is_empty());
+}
+
+#[test]
+fn update_inflation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+
+    let update = CallMessage::UpdateInflation {
+        initial_reward_per_slot: 0,
+        halving_interval: 0,
+    };
+    assert!(setup
+        .staking
+        .call(
+            update.clone(),
+            &setup.context(setup.validator, 1),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .staking
+        .call(update, &setup.context(setup.admin, 1), &mut working_set)
+        .expect("UpdateInflation call failed");
+
+    setup
+        .staking
+        .call(
+            CallMessage::Delegate {
+                validator: setup.validator,
+                amount: 500,
+            },
+            &setup.context(setup.delegators[0], 1),
+            &mut working_set,
+        )
+        .expect("Delegate call failed");
+    setup.staking.begin_slot_hook(2, &mut working_set);
+    assert_eq!(500, setup.delegated(setup.delegators[0], &mut working_set));
+}
@@ -0,0 +1,221 @@
+use anyhow::{bail, Context as _, Result};
+use sov_bank::Coins;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, WorkingSet};
+
+use crate::{Grant, GrantId, Vesting, VestingSchedule};
+
+/// This enumeration represents the available call messages for interacting with the sov-vesting module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: sov_modules_api::Context> {
+    /// Locks the coins of the admin in a grant, released to the beneficiary according to the schedule.
+    CreateGrant {
+        /// The address allowed to claim the vested tokens.
+        beneficiary: C::Address,
+        /// The vested token and the amount of tokens vested at the end of the schedule.
+        coins: Coins<C>,
+        /// The schedule releasing the tokens.
+        schedule: VestingSchedule,
+        /// Whether the admin can revoke the tokens which aren't vested yet.
+        revocable: bool,
+    },
+
+    /// Transfers the vested tokens of a grant which weren't claimed yet to its beneficiary.
+    Claim {
+        /// The identifier of the grant.
+        grant_id: GrantId,
+    },
+
+    /// Returns the tokens of a revocable grant which aren't vested yet to the admin.
+    /// The vested tokens can still be claimed by the beneficiary.
+    Revoke {
+        /// The identifier of the grant.
+        grant_id: GrantId,
+    },
+}
+
+impl<C: sov_modules_api::Context> Vesting<C> {
+    /// Creates a grant, funded with the `coins` of the admin. Returns the identifier of the grant.
+    pub fn create_grant(
+        &self,
+        beneficiary: C::Address,
+        coins: Coins<C>,
+        schedule: VestingSchedule,
+        revocable: bool,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<GrantId> {
+        self.check_admin(context.sender(), working_set)?;
+        schedule.validate()?;
+        if coins.amount == 0 {
+            bail!("A grant must vest a non-zero amount of tokens");
+        }
+
+        self.bank
+            .transfer_from(context.sender(), &self.address, coins.clone(), working_set)
+            .context("Failed to fund the grant")?;
+
+        let grant_id = self.next_grant_id.get(working_set).unwrap_or_default();
+        self.next_grant_id.set(&(grant_id + 1), working_set);
+
+        let grant = Grant {
+            beneficiary: beneficiary.clone(),
+            token_address: coins.token_address,
+            total_amount: coins.amount,
+            claimed: 0,
+            schedule,
+            revocable,
+        };
+        self.grants.set(&grant_id, &grant, working_set);
+
+        let mut beneficiary_grants = self
+            .grants_by_beneficiary
+            .get(&beneficiary, working_set)
+            .unwrap_or_default();
+        beneficiary_grants.push(grant_id);
+        self.grants_by_beneficiary
+            .set(&beneficiary, &beneficiary_grants, working_set);
+
+        working_set.add_event(
+            "create_grant",
+            &format!(
+                "grant_id: {}, beneficiary: {}, amount: {}",
+                grant_id, beneficiary, grant.total_amount
+            ),
+        );
+        Ok(grant_id)
+    }
+
+    /// Transfers the tokens of the grant `grant_id` vested at the current slot height and not claimed yet
+    /// to its beneficiary. Only the beneficiary can claim the tokens.
+    pub fn claim(
+        &self,
+        grant_id: GrantId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut grant = self.get_grant_or_err(grant_id, working_set)?;
+        if context.sender() != &grant.beneficiary {
+            bail!(
+                "Sender {} is not the beneficiary of grant {}",
+                context.sender(),
+                grant_id
+            )
+        }
+
+        let claimable = grant.claimable_at(context.slot_height());
+        if claimable == 0 {
+            bail!("Nothing to claim from grant {}", grant_id)
+        }
+        grant.claimed += claimable;
+        self.grants.set(&grant_id, &grant, working_set);
+
+        let coins = Coins {
+            amount: claimable,
+            token_address: grant.token_address,
+        };
+        self.bank
+            .transfer_from(&self.address, &grant.beneficiary, coins, working_set)?;
+
+        working_set.add_event(
+            "claim",
+            &format!("grant_id: {}, amount: {}", grant_id, claimable),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Revokes the grant `grant_id`: the tokens which aren't vested at the current slot height are
+    /// transferred back to the admin, and the vested tokens remain claimable.
+    pub fn revoke(
+        &self,
+        grant_id: GrantId,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let admin = self.check_admin(context.sender(), working_set)?;
+        let mut grant = self.get_grant_or_err(grant_id, working_set)?;
+        if !grant.revocable {
+            bail!("Grant {} is not revocable", grant_id)
+        }
+
+        let height = context.slot_height();
+        let vested = grant.vested_at(height);
+        let unvested = grant.total_amount - vested;
+
+        // The grant is now fully vested, and can't be revoked again
+        grant.total_amount = vested;
+        grant.schedule = VestingSchedule::cliff(height);
+        grant.revocable = false;
+        self.grants.set(&grant_id, &grant, working_set);
+
+        if unvested > 0 {
+            let coins = Coins {
+                amount: unvested,
+                token_address: grant.token_address,
+            };
+            self.bank
+                .transfer_from(&self.address, &admin, coins, working_set)?;
+        }
+
+        working_set.add_event(
+            "revoke",
+            &format!("grant_id: {}, unvested_amount: {}", grant_id, unvested),
+        );
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the grant `grant_id`, if it exists.
+    pub fn get_grant(
+        &self,
+        grant_id: GrantId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Grant<C>> {
+        self.grants.get(&grant_id, working_set)
+    }
+
+    /// Returns the identifiers of the grants of `beneficiary`.
+    pub fn get_grant_ids_of(
+        &self,
+        beneficiary: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<GrantId> {
+        self.grants_by_beneficiary
+            .get(beneficiary, working_set)
+            .unwrap_or_default()
+    }
+
+    fn get_grant_or_err(
+        &self,
+        grant_id: GrantId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Grant<C>> {
+        self.grants
+            .get(&grant_id, working_set)
+            .with_context(|| format!("Grant {} doesn't exist", grant_id))
+    }
+
+    fn check_admin(
+        &self,
+        sender: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<C::Address> {
+        let admin = self.admin.get_or_err(working_set)?;
+        if sender != &admin {
+            bail!("Sender {} is not the vesting admin", sender)
+        }
+        Ok(admin)
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::Vesting;
+
+/// Initial configuration for sov-vesting module.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct VestingConfig<C: sov_modules_api::Context> {
+    /// The address allowed to create and revoke grants.
+    pub admin: C::Address,
+}
+
+impl<C: sov_modules_api::Context> Vesting<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.admin.set(&config.admin, working_set);
+        self.next_grant_id.set(&0, working_set);
+        Ok(())
+    }
+}
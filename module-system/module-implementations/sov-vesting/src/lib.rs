@@ -0,0 +1,137 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+mod schedule;
+
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+pub use schedule::*;
+use sov_bank::Amount;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The identifier of a grant.
+pub type GrantId = u64;
+
+/// Tokens locked in the vesting module, released to a beneficiary according to a schedule.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C::Address: serde::Serialize",
+        deserialize = "C::Address: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Grant<C: sov_modules_api::Context> {
+    /// The address allowed to claim the vested tokens.
+    pub beneficiary: C::Address,
+    /// The address of the vested token.
+    pub token_address: C::Address,
+    /// The amount of tokens vested at the end of the schedule.
+    pub total_amount: Amount,
+    /// The amount of tokens already claimed by the beneficiary.
+    pub claimed: Amount,
+    /// The schedule releasing the tokens.
+    pub schedule: VestingSchedule,
+    /// Whether the admin can revoke the tokens which aren't vested yet.
+    pub revocable: bool,
+}
+
+impl<C: sov_modules_api::Context> Grant<C> {
+    /// Returns the amount of tokens vested at `height`, including the claimed tokens.
+    pub fn vested_at(&self, height: u64) -> Amount {
+        self.schedule.vested_amount(self.total_amount, height)
+    }
+
+    /// Returns the amount of tokens that the beneficiary can claim at `height`.
+    pub fn claimable_at(&self, height: u64) -> Amount {
+        self.vested_at(height).saturating_sub(self.claimed)
+    }
+}
+
+/// The `sov-vesting` module releases tokens of the `sov-bank` module over time. It provides functionality for:
+/// - Creating grants funded by the admin, with linear and cliff schedules.
+/// - Claiming the vested tokens of a grant.
+/// - Revoking the unvested tokens of revocable grants.
+///
+/// The schedules are expressed in slot heights, as observed by the modules through [`sov_modules_api::Context::slot_height`].
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(ModuleInfo, Clone)]
+pub struct Vesting<C: sov_modules_api::Context> {
+    /// The address of the sov-vesting module, which holds the locked tokens.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The address allowed to create and revoke grants.
+    #[state]
+    pub(crate) admin: StateValue<C::Address>,
+
+    /// The identifier of the next grant.
+    #[state]
+    pub(crate) next_grant_id: StateValue<GrantId>,
+
+    /// The grants, keyed by their identifier.
+    #[state]
+    pub(crate) grants: StateMap<GrantId, Grant<C>>,
+
+    /// The identifiers of the grants of each beneficiary.
+    #[state]
+    pub(crate) grants_by_beneficiary: StateMap<C::Address, Vec<GrantId>>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Vesting<C> {
+    type Context = C;
+
+    type Config = VestingConfig<C>;
+
+    type CallMessage = call::CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::CreateGrant {
+                beneficiary,
+                coins,
+                schedule,
+                revocable,
+            } => {
+                self.create_grant(
+                    beneficiary,
+                    coins,
+                    schedule,
+                    revocable,
+                    context,
+                    working_set,
+                )?;
+                Ok(CallResponse::default())
+            }
+
+            call::CallMessage::Claim { grant_id } => {
+                Ok(self.claim(grant_id, context, working_set)?)
+            }
+
+            call::CallMessage::Revoke { grant_id } => {
+                Ok(self.revoke(grant_id, context, working_set)?)
+            }
+        }
+    }
+}
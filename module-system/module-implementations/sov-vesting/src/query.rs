@@ -0,0 +1,102 @@
+//! Defines rpc queries exposed by the vesting module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_bank::Amount;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::WorkingSet;
+
+use crate::{Grant, GrantId, Vesting};
+
+/// The state of a grant at a given slot height.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct GrantInfo<C: sov_modules_api::Context> {
+    /// The identifier of the grant.
+    pub grant_id: GrantId,
+    /// The grant.
+    pub grant: Grant<C>,
+    /// The amount of tokens vested, including the claimed tokens.
+    pub vested: Amount,
+    /// The amount of tokens which aren't vested yet.
+    pub locked: Amount,
+    /// The amount of tokens that the beneficiary can claim.
+    pub claimable: Amount,
+}
+
+/// Structure returned by the `getGrant` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct GrantResponse<C: sov_modules_api::Context> {
+    /// The state of the grant, if it exists.
+    pub grant: Option<GrantInfo<C>>,
+}
+
+/// Structure returned by the `getGrantsOf` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound(
+    serialize = "C::Address: serde::Serialize",
+    deserialize = "C::Address: serde::Deserialize<'de>"
+))]
+pub struct GrantsResponse<C: sov_modules_api::Context> {
+    /// The state of the grants of the beneficiary, in creation order.
+    pub grants: Vec<GrantInfo<C>>,
+}
+
+#[rpc_gen(client, server, namespace = "vesting")]
+impl<C: sov_modules_api::Context> Vesting<C> {
+    #[rpc_method(name = "getGrant")]
+    /// Rpc method that returns the vested and locked amounts of the grant `grant_id` at the slot `height`.
+    /// The current slot height is returned by the `chainState_visibleSlotHeight` rpc method.
+    pub fn grant(
+        &self,
+        grant_id: GrantId,
+        height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GrantResponse<C>> {
+        Ok(GrantResponse {
+            grant: self.get_grant_info(grant_id, height, working_set),
+        })
+    }
+
+    #[rpc_method(name = "getGrantsOf")]
+    /// Rpc method that returns the vested and locked amounts of all the grants of `beneficiary` at the slot `height`.
+    pub fn grants_of(
+        &self,
+        beneficiary: C::Address,
+        height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GrantsResponse<C>> {
+        let mut grants = Vec::new();
+        for grant_id in self.get_grant_ids_of(&beneficiary, working_set) {
+            if let Some(grant) = self.get_grant_info(grant_id, height, working_set) {
+                grants.push(grant);
+            }
+        }
+        Ok(GrantsResponse { grants })
+    }
+}
+
+impl<C: sov_modules_api::Context> Vesting<C> {
+    fn get_grant_info(
+        &self,
+        grant_id: GrantId,
+        height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<GrantInfo<C>> {
+        self.get_grant(grant_id, working_set).map(|grant| {
+            let vested = grant.vested_at(height);
+            GrantInfo {
+                grant_id,
+                locked: grant.total_amount - vested,
+                claimable: grant.claimable_at(height),
+                vested,
+                grant,
+            }
+        })
+    }
+}
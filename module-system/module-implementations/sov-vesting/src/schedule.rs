@@ -0,0 +1,159 @@
+#[cfg(feature = "native")]
+use core::str::FromStr;
+#[cfg(feature = "native")]
+use std::num::ParseIntError;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sov_bank::Amount;
+#[cfg(feature = "native")]
+use thiserror::Error;
+
+/// A schedule releasing tokens linearly from `start_height` to `end_height`, with nothing released
+/// before `cliff_height`. At the cliff, the tokens vested since the start are released at once.
+///
+/// A cliff schedule, which releases all the tokens at once, has its cliff at the end.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct VestingSchedule {
+    /// The slot height at which the tokens start vesting.
+    pub start_height: u64,
+    /// The slot height before which no token can be claimed.
+    pub cliff_height: u64,
+    /// The slot height at which all the tokens are vested.
+    pub end_height: u64,
+}
+
+impl VestingSchedule {
+    /// Creates a schedule releasing all the tokens at `height`.
+    pub fn cliff(height: u64) -> Self {
+        Self {
+            start_height: height,
+            cliff_height: height,
+            end_height: height,
+        }
+    }
+
+    /// Checks that the cliff is between the start and the end of the schedule.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.start_height > self.cliff_height || self.cliff_height > self.end_height {
+            bail!(
+                "The cliff height {} must be between the start height {} and the end height {}",
+                self.cliff_height,
+                self.start_height,
+                self.end_height
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns the amount of tokens vested at `height`, out of `total_amount`.
+    pub fn vested_amount(&self, total_amount: Amount, height: u64) -> Amount {
+        if height < self.cliff_height {
+            return 0;
+        }
+        if height >= self.end_height {
+            return total_amount;
+        }
+
+        // The cliff isn't the end, so the start is before the end
+        let elapsed = (height - self.start_height) as u128;
+        let duration = (self.end_height - self.start_height) as u128;
+        // Can't overflow: the result is lower than `total_amount`
+        (total_amount as u128 * elapsed / duration) as Amount
+    }
+}
+
+/// The errors that might arise when parsing a `VestingSchedule` struct from a string.
+#[cfg(feature = "native")]
+#[derive(Debug, Error)]
+pub enum VestingScheduleFromStrError {
+    /// The input string doesn't contain exactly three heights.
+    #[error("Three heights must be provided. Make sure that your input is in the format: start_height,cliff_height,end_height. Example: 100,200,1000")]
+    WrongHeightCount,
+    /// A height could not be parsed as a u64.
+    #[error("Could not parse {input} as a valid height: {err}")]
+    InvalidHeight { input: String, err: ParseIntError },
+}
+
+#[cfg(feature = "native")]
+impl FromStr for VestingSchedule {
+    type Err = VestingScheduleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heights = s
+            .split(',')
+            .map(|height| {
+                height
+                    .parse::<u64>()
+                    .map_err(|err| VestingScheduleFromStrError::InvalidHeight {
+                        input: height.into(),
+                        err,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [start_height, cliff_height, end_height] = heights[..] else {
+            return Err(VestingScheduleFromStrError::WrongHeightCount);
+        };
+        Ok(Self {
+            start_height,
+            cliff_height,
+            end_height,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_schedule_with_cliff() {
+        let schedule = VestingSchedule {
+            start_height: 100,
+            cliff_height: 150,
+            end_height: 200,
+        };
+        assert!(schedule.validate().is_ok());
+
+        assert_eq!(0, schedule.vested_amount(1000, 0));
+        assert_eq!(0, schedule.vested_amount(1000, 149));
+        assert_eq!(500, schedule.vested_amount(1000, 150));
+        assert_eq!(990, schedule.vested_amount(1000, 199));
+        assert_eq!(1000, schedule.vested_amount(1000, 200));
+        assert_eq!(1000, schedule.vested_amount(1000, u64::MAX));
+        assert_eq!(u64::MAX / 2, schedule.vested_amount(u64::MAX, 150));
+    }
+
+    #[test]
+    fn cliff_schedule() {
+        let schedule = VestingSchedule::cliff(100);
+        assert!(schedule.validate().is_ok());
+
+        assert_eq!(0, schedule.vested_amount(1000, 99));
+        assert_eq!(1000, schedule.vested_amount(1000, 100));
+    }
+
+    #[test]
+    fn invalid_schedules() {
+        for (start_height, cliff_height, end_height) in [(100, 50, 200), (100, 250, 200)] {
+            let schedule = VestingSchedule {
+                start_height,
+                cliff_height,
+                end_height,
+            };
+            assert!(schedule.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_vesting::{CallMessage, GrantsResponse, Vesting, VestingConfig, VestingSchedule};
+
+type C = DefaultContext;
+
+struct TestSetup {
+    bank: Bank<C>,
+    vesting: Vesting<C>,
+    admin: Address,
+    beneficiary: Address,
+    token_address: Address,
+}
+
+impl TestSetup {
+    fn new(working_set: &mut WorkingSet<C>) -> Self {
+        let admin = generate_address::<C>("admin");
+        let beneficiary = generate_address::<C>("beneficiary");
+        let token_name = "VestedToken".to_owned();
+
+        let bank = Bank::<C>::default();
+        let bank_config = BankConfig::<C> {
+            tokens: vec![TokenConfig {
+                token_name: token_name.clone(),
+                address_and_balances: vec![(admin, 1000)],
+                authorized_minters: vec![],
+                admins: vec![],
+                max_supply: None,
+                metadata: None,
+                salt: 0,
+            }],
+            fee_settlement: Default::default(),
+        };
+        bank.genesis(&bank_config, working_set).unwrap();
+
+        let vesting = Vesting::<C>::default();
+        vesting
+            .genesis(&VestingConfig { admin }, working_set)
+            .unwrap();
+
+        Self {
+            bank,
+            vesting,
+            admin,
+            beneficiary,
+            token_address: get_genesis_token_address::<C>(&token_name, 0),
+        }
+    }
+
+    fn context(&self, sender: Address, height: u64) -> C {
+        C::new(sender, generate_address::<C>("sequencer"), height)
+    }
+
+    fn create_grant(
+        &self,
+        amount: u64,
+        schedule: VestingSchedule,
+        revocable: bool,
+    ) -> CallMessage<C> {
+        CallMessage::CreateGrant {
+            beneficiary: self.beneficiary,
+            coins: Coins {
+                amount,
+                token_address: self.token_address,
+            },
+            schedule,
+            revocable,
+        }
+    }
+
+    fn balance_of(&self, address: Address, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.bank
+            .get_balance_of(address, self.token_address, working_set)
+    }
+}
+
+#[test]
+fn claim_linear_grant() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let schedule = VestingSchedule {
+        start_height: 10,
+        cliff_height: 20,
+        end_height: 50,
+    };
+
+    // Only the admin can create grants
+    assert!(setup
+        .vesting
+        .call(
+            setup.create_grant(400, schedule, false),
+            &setup.context(setup.beneficiary, 1),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .vesting
+        .call(
+            setup.create_grant(400, schedule, false),
+            &setup.context(setup.admin, 1),
+            &mut working_set,
+        )
+        .expect("CreateGrant call failed");
+    assert_eq!(Some(600), setup.balance_of(setup.admin, &mut working_set));
+
+    let claim = CallMessage::Claim { grant_id: 0 };
+    // Nothing is vested before the cliff
+    assert!(setup
+        .vesting
+        .call(
+            claim.clone(),
+            &setup.context(setup.beneficiary, 19),
+            &mut working_set
+        )
+        .is_err());
+
+    // Only the beneficiary can claim
+    assert!(setup
+        .vesting
+        .call(
+            claim.clone(),
+            &setup.context(setup.admin, 20),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .vesting
+        .call(
+            claim.clone(),
+            &setup.context(setup.beneficiary, 20),
+            &mut working_set,
+        )
+        .expect("Claim call failed");
+    assert_eq!(
+        Some(100),
+        setup.balance_of(setup.beneficiary, &mut working_set)
+    );
+
+    let GrantsResponse { grants } = setup
+        .vesting
+        .grants_of(setup.beneficiary, 30, &mut working_set)
+        .unwrap();
+    assert_eq!(1, grants.len());
+    assert_eq!(200, grants[0].vested);
+    assert_eq!(200, grants[0].locked);
+    assert_eq!(100, grants[0].claimable);
+
+    // Everything is vested at the end of the schedule
+    setup
+        .vesting
+        .call(
+            claim.clone(),
+            &setup.context(setup.beneficiary, 60),
+            &mut working_set,
+        )
+        .expect("Claim call failed");
+    assert_eq!(
+        Some(400),
+        setup.balance_of(setup.beneficiary, &mut working_set)
+    );
+    assert!(setup
+        .vesting
+        .call(
+            claim,
+            &setup.context(setup.beneficiary, 70),
+            &mut working_set
+        )
+        .is_err());
+
+    // The grant isn't revocable
+    assert!(setup
+        .vesting
+        .call(
+            CallMessage::Revoke { grant_id: 0 },
+            &setup.context(setup.admin, 70),
+            &mut working_set
+        )
+        .is_err());
+}
+
+#[test]
+fn revoke_grant() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::new(&mut working_set);
+    let schedule = VestingSchedule {
+        start_height: 0,
+        cliff_height: 0,
+        end_height: 100,
+    };
+
+    setup
+        .vesting
+        .call(
+            setup.create_grant(1000, schedule, true),
+            &setup.context(setup.admin, 1),
+            &mut working_set,
+        )
+        .expect("CreateGrant call failed");
+
+    let revoke = CallMessage::Revoke { grant_id: 0 };
+    assert!(setup
+        .vesting
+        .call(
+            revoke.clone(),
+            &setup.context(setup.beneficiary, 25),
+            &mut working_set
+        )
+        .is_err());
+    setup
+        .vesting
+        .call(
+            revoke.clone(),
+            &setup.context(setup.admin, 25),
+            &mut working_set,
+        )
+        .expect("Revoke call failed");
+    assert_eq!(Some(750), setup.balance_of(setup.admin, &mut working_set));
+    assert!(setup
+        .vesting
+        .call(revoke, &setup.context(setup.admin, 30), &mut working_set)
+        .is_err());
+
+    // The tokens vested before the revocation can still be claimed
+    let grant = setup.vesting.get_grant(0, &mut working_set).unwrap();
+    assert_eq!(250, grant.vested_at(u64::MAX));
+    setup
+        .vesting
+        .call(
+            CallMessage::Claim { grant_id: 0 },
+            &setup.context(setup.beneficiary, 100),
+            &mut working_set,
+        )
+        .expect("Claim call failed");
+    assert_eq!(
+        Some(250),
+        setup.balance_of(setup.beneficiary, &mut working_set)
+    );
+}
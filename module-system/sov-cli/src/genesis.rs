@@ -0,0 +1,357 @@
+//! A single JSON genesis file for the standard modules of a rollup, and a builder for it.
+//!
+//! The standard modules are the chain state, the bank, the sequencer registry and the accounts.
+//! Other modules of a runtime keep their own genesis files.
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context as _};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_accounts::AccountConfig;
+use sov_bank::{get_genesis_token_address, BankConfig, Coins, FeeSettlementConfig, TokenConfig};
+use sov_chain_state::ChainStateConfig;
+use sov_modules_api::da::Time;
+use sov_modules_api::{Context, DaSpec};
+use sov_sequencer_registry::SequencerConfig;
+
+/// The genesis configuration of the standard modules of a rollup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    bound = "C: Serialize + DeserializeOwned, Da: Serialize + DeserializeOwned",
+    deny_unknown_fields
+)]
+pub struct GenesisFile<C: Context, Da: DaSpec> {
+    /// The configuration of the chain state, used by the kernel.
+    pub chain_state: ChainStateConfig<C, Da>,
+    /// The tokens and initial balances of the bank.
+    pub bank: BankConfig<C>,
+    /// The sequencer registered at genesis.
+    pub sequencer_registry: SequencerConfig<C, Da>,
+    /// The accounts created at genesis.
+    pub accounts: AccountConfig<C>,
+}
+
+impl<C, Da> GenesisFile<C, Da>
+where
+    C: Context + Serialize + DeserializeOwned,
+    Da: DaSpec + Serialize + DeserializeOwned,
+{
+    /// Reads a genesis file and validates it.
+    pub fn from_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read the genesis file {}", path.display()))?;
+        let genesis: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse the genesis file {}", path.display()))?;
+        genesis
+            .validate()
+            .with_context(|| format!("The genesis file {} is invalid", path.display()))?;
+        Ok(genesis)
+    }
+}
+
+impl<C: Context, Da: DaSpec> GenesisFile<C, Da> {
+    /// Checks that the configurations are consistent, so the rollup can be initialized with them.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.chain_state
+            .validate()
+            .context("Invalid `chain_state` configuration")?;
+        validate_bank(&self.bank)?;
+        validate_sequencer(&self.sequencer_registry, &self.bank)?;
+        validate_accounts(&self.accounts)
+    }
+}
+
+/// Checks that the tokens of the bank have distinct addresses, that their initial balances
+/// are consistent with their supply, and that the fee settlement is valid.
+pub fn validate_bank<C: Context>(bank: &BankConfig<C>) -> anyhow::Result<()> {
+    bank.fee_settlement
+        .validate()
+        .context("Invalid `bank.fee_settlement` configuration")?;
+    let mut token_addresses = HashMap::new();
+    for (i, token) in bank.tokens.iter().enumerate() {
+        ensure!(
+            !token.token_name.is_empty(),
+            "`bank.tokens[{}]` has an empty name",
+            i
+        );
+        let token_address = get_genesis_token_address::<C>(&token.token_name, token.salt);
+        if let Some(other) = token_addresses.insert(token_address, i) {
+            bail!(
+                "`bank.tokens[{}]` and `bank.tokens[{}]` have the same name `{}` and salt {}. Change the salt of one of them",
+                other,
+                i,
+                token.token_name,
+                token.salt
+            );
+        }
+
+        let mut holders = HashSet::new();
+        let mut total_supply = 0u64;
+        for (address, balance) in &token.address_and_balances {
+            ensure!(
+                holders.insert(address),
+                "The address {} has several balances of the token `{}`",
+                address,
+                token.token_name
+            );
+            total_supply = total_supply.checked_add(*balance).with_context(|| {
+                format!(
+                    "The initial balances of the token `{}` overflow its total supply",
+                    token.token_name
+                )
+            })?;
+        }
+        if let Some(max_supply) = token.max_supply {
+            ensure!(
+                total_supply <= max_supply,
+                "The initial balances of the token `{}` add up to {}, which exceeds its maximum supply {}",
+                token.token_name,
+                total_supply,
+                max_supply
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the bond of the sequencer is a token of the bank, and that the sequencer holds
+/// enough of it to be registered.
+pub fn validate_sequencer<C: Context, Da: DaSpec>(
+    sequencer: &SequencerConfig<C, Da>,
+    bank: &BankConfig<C>,
+) -> anyhow::Result<()> {
+    let bond = &sequencer.coins_to_lock;
+    let Some(token) = bank.tokens.iter().find(|token| {
+        get_genesis_token_address::<C>(&token.token_name, token.salt) == bond.token_address
+    }) else {
+        let known_tokens = bank
+            .tokens
+            .iter()
+            .map(|token| {
+                format!(
+                    "`{}` ({})",
+                    token.token_name,
+                    get_genesis_token_address::<C>(&token.token_name, token.salt)
+                )
+            })
+            .collect::<Vec<_>>();
+        bail!(
+            "`sequencer_registry.coins_to_lock.token_address` is {}, which isn't the address of any token of the bank. The tokens of the bank are: {}",
+            bond.token_address,
+            known_tokens.join(", ")
+        );
+    };
+
+    let balance = token
+        .address_and_balances
+        .iter()
+        .find(|(address, _)| address == &sequencer.seq_rollup_address)
+        .map(|(_, balance)| *balance)
+        .unwrap_or_default();
+    ensure!(
+        balance >= bond.amount,
+        "The sequencer {} holds {} of the token `{}` at genesis, but its bond is {}",
+        sequencer.seq_rollup_address,
+        balance,
+        token.token_name,
+        bond.amount
+    );
+    Ok(())
+}
+
+/// Checks that no account is created twice.
+pub fn validate_accounts<C: Context>(accounts: &AccountConfig<C>) -> anyhow::Result<()> {
+    let mut pub_keys = HashSet::new();
+    for pub_key in &accounts.pub_keys {
+        ensure!(
+            pub_keys.insert(pub_key),
+            "The account of the public key {:?} is created twice",
+            pub_key
+        );
+    }
+    Ok(())
+}
+
+/// Builds a validated [`GenesisFile`].
+///
+/// ```
+/// # use sov_cli::genesis::GenesisBuilder;
+/// # use sov_mock_da::{MockAddress, MockDaSpec};
+/// # use sov_modules_api::default_context::DefaultContext;
+/// # use sov_modules_api::Address;
+/// let sequencer = Address::from([1; 32]);
+/// let genesis = GenesisBuilder::<DefaultContext, MockDaSpec>::new(4321)
+///     .with_token("sov-token", vec![(sequencer, 1000)])
+///     .with_sequencer(sequencer, MockAddress::from([0; 32]), "sov-token", 100)
+///     .build()
+///     .unwrap();
+/// assert_eq!(genesis.chain_state.chain_id, 4321);
+/// ```
+pub struct GenesisBuilder<C: Context, Da: DaSpec> {
+    chain_state: ChainStateConfig<C, Da>,
+    tokens: Vec<TokenConfig<C>>,
+    fee_settlement: FeeSettlementConfig<C>,
+    sequencer: Option<SequencerBond<C, Da>>,
+    pub_keys: Vec<C::PublicKey>,
+}
+
+enum SequencerBond<C: Context, Da: DaSpec> {
+    ByTokenName {
+        seq_rollup_address: C::Address,
+        seq_da_address: Da::Address,
+        token_name: String,
+        amount: u64,
+    },
+    Config(SequencerConfig<C, Da>),
+}
+
+impl<C: Context, Da: DaSpec> GenesisBuilder<C, Da> {
+    /// Creates a builder for a rollup with the chain id `chain_id`, starting at slot height `0`.
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_state: ChainStateConfig {
+                initial_slot_height: 0,
+                current_time: Time::default(),
+                chain_id,
+                protocol_version: 0,
+                genesis_da_hash: None,
+                initial_validity_condition: None,
+                transition_retention: None,
+                accumulate_pruned_transitions: false,
+                fee_market: Default::default(),
+                upgrade_authority: None,
+                module_versions: vec![],
+            },
+            tokens: vec![],
+            fee_settlement: Default::default(),
+            sequencer: None,
+            pub_keys: vec![],
+        }
+    }
+
+    /// Replaces the configuration of the chain state. Its chain id takes precedence over the one
+    /// given to [`GenesisBuilder::new`].
+    pub fn with_chain_state(mut self, chain_state: ChainStateConfig<C, Da>) -> Self {
+        self.chain_state = chain_state;
+        self
+    }
+
+    /// Starts the rollup at the slot height `initial_slot_height`.
+    pub fn with_initial_slot_height(mut self, initial_slot_height: u64) -> Self {
+        self.chain_state.initial_slot_height = initial_slot_height;
+        self
+    }
+
+    /// Adds a token with the given initial balances, without minters nor maximum supply.
+    pub fn with_token(
+        self,
+        token_name: impl Into<String>,
+        address_and_balances: Vec<(C::Address, u64)>,
+    ) -> Self {
+        self.with_token_config(TokenConfig {
+            token_name: token_name.into(),
+            address_and_balances,
+            authorized_minters: vec![],
+            admins: vec![],
+            max_supply: None,
+            metadata: None,
+            salt: 0,
+        })
+    }
+
+    /// Adds a token.
+    pub fn with_token_config(mut self, token: TokenConfig<C>) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    /// Shares the consumed gas between the sequencer and the treasury.
+    pub fn with_fee_settlement(mut self, fee_settlement: FeeSettlementConfig<C>) -> Self {
+        self.fee_settlement = fee_settlement;
+        self
+    }
+
+    /// Registers the preferred sequencer, which bonds `amount` of the token named `token_name`.
+    pub fn with_sequencer(
+        mut self,
+        seq_rollup_address: C::Address,
+        seq_da_address: Da::Address,
+        token_name: impl Into<String>,
+        amount: u64,
+    ) -> Self {
+        self.sequencer = Some(SequencerBond::ByTokenName {
+            seq_rollup_address,
+            seq_da_address,
+            token_name: token_name.into(),
+            amount,
+        });
+        self
+    }
+
+    /// Registers a sequencer.
+    pub fn with_sequencer_config(mut self, sequencer: SequencerConfig<C, Da>) -> Self {
+        self.sequencer = Some(SequencerBond::Config(sequencer));
+        self
+    }
+
+    /// Creates an account for `pub_key`.
+    pub fn with_account(mut self, pub_key: C::PublicKey) -> Self {
+        self.pub_keys.push(pub_key);
+        self
+    }
+
+    /// Builds the genesis configuration, and validates it.
+    pub fn build(self) -> anyhow::Result<GenesisFile<C, Da>> {
+        let sequencer_registry = match self.sequencer {
+            None => bail!("No sequencer is registered at genesis"),
+            Some(SequencerBond::Config(sequencer)) => sequencer,
+            Some(SequencerBond::ByTokenName {
+                seq_rollup_address,
+                seq_da_address,
+                token_name,
+                amount,
+            }) => {
+                let token = self
+                    .tokens
+                    .iter()
+                    .find(|token| token.token_name == token_name)
+                    .with_context(|| {
+                        format!(
+                            "The sequencer bonds the token `{}`, which isn't a token of the bank",
+                            token_name
+                        )
+                    })?;
+                SequencerConfig {
+                    seq_rollup_address,
+                    seq_da_address,
+                    coins_to_lock: Coins {
+                        amount,
+                        token_address: get_genesis_token_address::<C>(
+                            &token.token_name,
+                            token.salt,
+                        ),
+                    },
+                    is_preferred_sequencer: true,
+                    slashing_params: Default::default(),
+                    exit_delay: 0,
+                }
+            }
+        };
+
+        let genesis = GenesisFile {
+            chain_state: self.chain_state,
+            bank: BankConfig {
+                tokens: self.tokens,
+                fee_settlement: self.fee_settlement,
+            },
+            sequencer_registry,
+            accounts: AccountConfig {
+                pub_keys: self.pub_keys,
+            },
+        };
+        genesis.validate()?;
+        Ok(genesis)
+    }
+}
@@ -0,0 +1,146 @@
+//! Password-encrypted key files.
+//!
+//! The private key is encrypted with AES-256-GCM, using a key derived from the password with scrypt.
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::wallet_state::PrivateKeyAndAddress;
+
+const SOV_WALLET_PASSWORD_ENV_VAR: &str = "SOV_WALLET_PASSWORD";
+const KEYSTORE_VERSION: u32 = 1;
+const CIPHER: &str = "aes-256-gcm";
+const KDF: &str = "scrypt";
+
+/// The parameters of the scrypt key derivation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    /// The base 2 logarithm of the CPU/memory cost.
+    pub log_n: u8,
+    /// The block size.
+    pub r: u32,
+    /// The parallelization.
+    pub p: u32,
+    /// The random salt.
+    #[serde(with = "hex::serde")]
+    pub salt: Vec<u8>,
+}
+
+/// The encrypted private key and the parameters needed to decrypt it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    /// The cipher of the private key. Only `aes-256-gcm` is supported.
+    pub cipher: String,
+    /// The encrypted key file.
+    #[serde(with = "hex::serde")]
+    pub ciphertext: Vec<u8>,
+    /// The nonce of the cipher.
+    #[serde(with = "hex::serde")]
+    pub nonce: Vec<u8>,
+    /// The key derivation function. Only `scrypt` is supported.
+    pub kdf: String,
+    /// The parameters of the key derivation function.
+    pub kdf_params: ScryptParams,
+}
+
+/// A key file encrypted with a password. The address is stored in clear, so that the key can
+/// be identified without the password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// The version of the keystore format.
+    pub version: u32,
+    /// The address of the key, in its canonical string format.
+    pub address: String,
+    /// The encrypted key.
+    pub crypto: KeystoreCrypto,
+}
+
+impl Keystore {
+    /// Encrypts `key` with `password`.
+    pub fn encrypt<C: sov_modules_api::Context>(
+        key: &PrivateKeyAndAddress<C>,
+        password: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let kdf_params = ScryptParams {
+            log_n: 15,
+            r: 8,
+            p: 1,
+            salt: rand::random::<[u8; 32]>().to_vec(),
+        };
+        let nonce = rand::random::<[u8; 12]>().to_vec();
+        let cipher = Aes256Gcm::new(Key::from_slice(&derive_key(password, &kdf_params)?));
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                serde_json::to_vec(key)?.as_slice(),
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt the key"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address: key.address.to_string(),
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.to_string(),
+                ciphertext,
+                nonce,
+                kdf: KDF.to_string(),
+                kdf_params,
+            },
+        })
+    }
+
+    /// Decrypts the key with `password`.
+    pub fn decrypt<C: sov_modules_api::Context>(
+        &self,
+        password: &str,
+    ) -> Result<PrivateKeyAndAddress<C>, anyhow::Error> {
+        if self.version != KEYSTORE_VERSION {
+            anyhow::bail!("Unsupported keystore version {}", self.version);
+        }
+        if self.crypto.cipher != CIPHER || self.crypto.kdf != KDF {
+            anyhow::bail!(
+                "Unsupported keystore cipher {} or key derivation function {}",
+                self.crypto.cipher,
+                self.crypto.kdf
+            );
+        }
+        if self.crypto.nonce.len() != 12 {
+            anyhow::bail!("Invalid keystore nonce length {}", self.crypto.nonce.len());
+        }
+
+        let cipher = Aes256Gcm::new(Key::from_slice(&derive_key(
+            password,
+            &self.crypto.kdf_params,
+        )?));
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&self.crypto.nonce),
+                self.crypto.ciphertext.as_slice(),
+            )
+            .map_err(|_| anyhow::anyhow!("Wrong password or corrupted keystore"))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+fn derive_key(password: &str, params: &ScryptParams) -> Result<[u8; 32], anyhow::Error> {
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), &params.salt, &scrypt_params, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive the encryption key: {}", e))?;
+    Ok(key)
+}
+
+/// Reads a password from the `SOV_WALLET_PASSWORD` environment variable, or prompts for it on the terminal.
+/// If `confirm` is set, a prompted password must be typed twice.
+pub fn read_password(prompt: &str, confirm: bool) -> Result<String, anyhow::Error> {
+    if let Ok(password) = std::env::var(SOV_WALLET_PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password(prompt)?;
+    if confirm && rpassword::prompt_password("Confirm password: ")? != password {
+        anyhow::bail!("The passwords don't match");
+    }
+    Ok(password)
+}
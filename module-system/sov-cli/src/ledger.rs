@@ -0,0 +1,89 @@
+//! Communication with the Sovereign app of a Ledger device over USB.
+//!
+//! Keys are derived on the device at the BIP-44 path `m/44'/1551'/<account>'/0'/0'`. Messages to sign
+//! are sent in chunks: the first chunk carries the derivation path, and the last one is flagged so that
+//! the device asks for a confirmation before signing.
+use ledger_apdu::APDUCommand;
+use ledger_transport_hid::hidapi::HidApi;
+use ledger_transport_hid::TransportNativeHID;
+
+const CLA: u8 = 0x80;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN: u8 = 0x03;
+const P1_INIT: u8 = 0x00;
+const P1_ADD: u8 = 0x01;
+const P1_LAST: u8 = 0x02;
+const CHUNK_SIZE: usize = 250;
+const SW_OK: u16 = 0x9000;
+const COIN_TYPE: u32 = 1551;
+const HARDENED: u32 = 0x8000_0000;
+
+/// A connected Ledger device
+pub struct Ledger {
+    transport: TransportNativeHID,
+}
+
+impl Ledger {
+    /// Connect to the first Ledger device found
+    pub fn connect() -> Result<Self, anyhow::Error> {
+        let api = HidApi::new()?;
+        let transport = TransportNativeHID::new(&api).map_err(|e| {
+            anyhow::anyhow!(
+                "Unable to connect to a Ledger device. Make sure it is unlocked and the Sovereign app is open: {}",
+                e
+            )
+        })?;
+        Ok(Self { transport })
+    }
+
+    /// Get the serialized public key of the account index `account`
+    pub fn public_key(&self, account: u32) -> Result<Vec<u8>, anyhow::Error> {
+        self.exchange(INS_GET_PUBLIC_KEY, P1_INIT, derivation_path(account))
+    }
+
+    /// Sign `message` with the key of the account index `account`, after the confirmation of the user
+    /// on the device. Returns the serialized signature
+    pub fn sign(&self, account: u32, message: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        self.exchange(INS_SIGN, P1_INIT, derivation_path(account))?;
+
+        let chunks = message.chunks(CHUNK_SIZE).collect::<Vec<_>>();
+        let mut signature = Vec::new();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let p1 = if idx + 1 == chunks.len() {
+                P1_LAST
+            } else {
+                P1_ADD
+            };
+            signature = self.exchange(INS_SIGN, p1, chunk.to_vec())?;
+        }
+        Ok(signature)
+    }
+
+    fn exchange(&self, ins: u8, p1: u8, data: Vec<u8>) -> Result<Vec<u8>, anyhow::Error> {
+        let command = APDUCommand {
+            cla: CLA,
+            ins,
+            p1,
+            p2: 0,
+            data,
+        };
+        let answer = self
+            .transport
+            .exchange(&command)
+            .map_err(|e| anyhow::anyhow!("Ledger communication error: {}", e))?;
+        if answer.retcode() != SW_OK {
+            anyhow::bail!(
+                "The Ledger device rejected the request with status {:#06x}",
+                answer.retcode()
+            );
+        }
+        Ok(answer.data().to_vec())
+    }
+}
+
+fn derivation_path(account: u32) -> Vec<u8> {
+    [44, COIN_TYPE, account, 0, 0]
+        .into_iter()
+        .flat_map(|index| (index | HARDENED).to_le_bytes())
+        .collect()
+}
@@ -6,6 +6,11 @@ use std::path::Path;
 use directories::BaseDirs;
 pub use sov_modules_api::clap;
 
+pub mod genesis;
+pub mod keystore;
+#[cfg(feature = "ledger")]
+pub mod ledger;
+pub mod signer;
 /// Types and functionality storing and loading the persistent state of the wallet
 pub mod wallet_state;
 pub mod workflows;
@@ -0,0 +1,104 @@
+//! Signing of transactions with the keys of the wallet
+use sov_modules_api::transaction::Transaction;
+
+#[cfg(feature = "ledger")]
+use crate::ledger::Ledger;
+use crate::wallet_state::{AddressEntry, KeySource};
+use crate::workflows::keys::load_key;
+
+/// Signs transactions on behalf of an address of the wallet
+pub enum Signer<C: sov_modules_api::Context> {
+    /// A private key loaded from a key file
+    Key(C::PrivateKey),
+    /// A Ledger device holding the key of the account index `account`
+    #[cfg(feature = "ledger")]
+    Ledger {
+        /// The connection to the device
+        device: Ledger,
+        /// The account index of the key on the device
+        account: u32,
+        /// The public key of the account, as registered in the wallet
+        pub_key: C::PublicKey,
+    },
+}
+
+impl<C: sov_modules_api::Context> Signer<C> {
+    /// Load the signer of an address of the wallet. Encrypted key files are decrypted with a password
+    pub fn load(entry: &AddressEntry<C>) -> Result<Self, anyhow::Error> {
+        match entry.key_source {
+            KeySource::File => Ok(Self::Key(load_key::<C>(&entry.location)?)),
+            #[cfg(feature = "ledger")]
+            KeySource::Ledger { account } => Ok(Self::Ledger {
+                device: Ledger::connect()?,
+                account,
+                pub_key: entry.pub_key.clone(),
+            }),
+            #[cfg(not(feature = "ledger"))]
+            KeySource::Ledger { .. } => Err(anyhow::anyhow!(
+                "The key of {} is held by a Ledger device. Rebuild the wallet with the `ledger` feature to use it",
+                entry.address
+            )),
+        }
+    }
+
+    /// Sign a transaction carrying the serialized `runtime_msg`
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_tx(
+        &self,
+        runtime_msg: Vec<u8>,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+    ) -> Result<Transaction<C>, anyhow::Error> {
+        match self {
+            Signer::Key(private_key) => Ok(Transaction::new_signed_tx_in_lane(
+                private_key,
+                runtime_msg,
+                chain_id,
+                gas_tip,
+                gas_limit,
+                nonce_lane,
+                nonce,
+            )),
+            #[cfg(feature = "ledger")]
+            Signer::Ledger {
+                device,
+                account,
+                pub_key,
+            } => {
+                let signing_bytes = Transaction::<C>::signing_bytes(
+                    &runtime_msg,
+                    chain_id,
+                    gas_tip,
+                    gas_limit,
+                    nonce_lane,
+                    nonce,
+                    None,
+                    None,
+                );
+                println!(
+                    "Please confirm the transaction with nonce {} on your Ledger device",
+                    nonce
+                );
+                let signature =
+                    C::Signature::try_from(device.sign(*account, &signing_bytes)?.as_slice())?;
+                let tx = Transaction::new(
+                    pub_key.clone(),
+                    runtime_msg,
+                    signature,
+                    chain_id,
+                    gas_tip,
+                    gas_limit,
+                    nonce_lane,
+                    nonce,
+                    None,
+                    None,
+                );
+                tx.verify()?;
+                Ok(tx)
+            }
+        }
+    }
+}
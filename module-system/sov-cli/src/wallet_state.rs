@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -21,6 +22,12 @@ where
     pub addresses: AddressList<Ctx>,
     /// The addresses in the wallet
     pub rpc_url: Option<String>,
+    /// The named addresses of other accounts
+    #[serde(default)]
+    pub address_book: AddressBook<Ctx>,
+    /// The bech32 prefix of the addresses of the rollup. The default prefix is used if unset.
+    #[serde(default)]
+    pub address_prefix: Option<String>,
 }
 
 impl<Tx, Ctx> Default for WalletState<Tx, Ctx>
@@ -35,6 +42,8 @@ where
                 addresses: Vec::new(),
             },
             rpc_url: None,
+            address_book: AddressBook::default(),
+            address_prefix: None,
         }
     }
 }
@@ -49,8 +58,12 @@ where
         let path = path.as_ref();
         if path.exists() {
             let data = fs::read(path)?;
-            let state = serde_json::from_slice(data.as_slice())?;
-            Ok(state)
+            let state: serde_json::Value = serde_json::from_slice(data.as_slice())?;
+            // The addresses of the wallet are parsed with the prefix of its rollup
+            if let Some(prefix) = state.get("address_prefix").and_then(|p| p.as_str()) {
+                sov_modules_api::set_address_prefix(prefix)?;
+            }
+            Ok(serde_json::from_value(state)?)
         } else {
             Ok(Default::default())
         }
@@ -179,9 +192,42 @@ impl<Ctx: sov_modules_api::Context> AddressList<Ctx> {
             nickname,
             location,
             pub_key: public_key,
+            key_source: KeySource::File,
         };
         self.addresses.push(entry);
     }
+
+    /// Add an address whose key is held by a Ledger device, at the account index `account`
+    pub fn add_ledger(
+        &mut self,
+        address: Ctx::Address,
+        nickname: Option<String>,
+        public_key: Ctx::PublicKey,
+        account: u32,
+    ) {
+        let entry = AddressEntry {
+            address,
+            nickname,
+            location: PathBuf::new(),
+            pub_key: public_key,
+            key_source: KeySource::Ledger { account },
+        };
+        self.addresses.push(entry);
+    }
+}
+
+/// Where the private key of an address is held
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// A key file on disk, either in plaintext or encrypted with a password
+    #[default]
+    File,
+    /// A Ledger device, which signs transactions with the key of the account index `account`
+    Ledger {
+        /// The account index of the key on the device
+        account: u32,
+    },
 }
 
 /// An entry in the address list
@@ -192,11 +238,14 @@ pub struct AddressEntry<Ctx: sov_modules_api::Context> {
     pub address: Ctx::Address,
     /// A user-provided nickname
     pub nickname: Option<String>,
-    /// The location of the private key on disk
+    /// The location of the private key on disk. Empty if the key isn't held in a file
     pub location: PathBuf,
     /// The public key associated with the address
     #[serde(with = "pubkey_hex")]
     pub pub_key: Ctx::PublicKey,
+    /// Where the private key is held
+    #[serde(default)]
+    pub key_source: KeySource,
 }
 
 impl<Ctx: sov_modules_api::Context> AddressEntry<Ctx> {
@@ -237,6 +286,60 @@ impl<C: sov_modules_api::Context> std::fmt::Display for KeyIdentifier<C> {
     }
 }
 
+/// Named addresses of other accounts, like the recipients of transfers
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "Ctx::Address: Serialize + DeserializeOwned")]
+pub struct AddressBook<Ctx: sov_modules_api::Context> {
+    contacts: BTreeMap<String, Ctx::Address>,
+}
+
+impl<Ctx: sov_modules_api::Context> Default for AddressBook<Ctx> {
+    fn default() -> Self {
+        Self {
+            contacts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Ctx: sov_modules_api::Context> AddressBook<Ctx> {
+    /// Add a contact. Fails if the name is already taken
+    pub fn add(&mut self, name: String, address: Ctx::Address) -> Result<(), anyhow::Error> {
+        if self.contacts.contains_key(&name) {
+            anyhow::bail!("Contact {} already exists", name);
+        }
+        self.contacts.insert(name, address);
+        Ok(())
+    }
+
+    /// Remove a contact by name, returning its address
+    pub fn remove(&mut self, name: &str) -> Option<Ctx::Address> {
+        self.contacts.remove(name)
+    }
+
+    /// Get the address of a contact by name
+    pub fn get(&self, name: &str) -> Option<&Ctx::Address> {
+        self.contacts.get(name)
+    }
+
+    /// Resolve a contact name or an address in its canonical string format into an address
+    pub fn resolve(&self, name_or_address: &str) -> Result<Ctx::Address, anyhow::Error> {
+        if let Some(address) = self.get(name_or_address) {
+            return Ok(address.clone());
+        }
+        Ctx::Address::from_str(name_or_address).map_err(|_| {
+            anyhow::anyhow!(
+                "{} is neither a contact nor a valid address",
+                name_or_address
+            )
+        })
+    }
+
+    /// Iterate over the contacts, ordered by name
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Ctx::Address)> {
+        self.contacts.iter()
+    }
+}
+
 mod pubkey_hex {
     use core::fmt;
     use std::marker::PhantomData;
@@ -0,0 +1,57 @@
+//! Address book workflows for the sov CLI wallet
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_modules_api::clap;
+
+use crate::wallet_state::WalletState;
+
+#[derive(clap::Subcommand)]
+/// Name the addresses of other accounts
+pub enum AddressBookWorkflow<C: sov_modules_api::Context> {
+    /// Add a contact to the address book
+    Add {
+        /// The name of the contact
+        name: String,
+        /// The address of the contact
+        address: C::Address,
+    },
+    /// Remove a contact from the address book
+    Remove {
+        /// The name of the contact
+        name: String,
+    },
+    /// List the contacts of the address book
+    List,
+}
+
+impl<C: sov_modules_api::Context> AddressBookWorkflow<C> {
+    /// Run the address book workflow to add, remove or list contacts
+    pub fn run<Tx>(self, wallet_state: &mut WalletState<Tx, C>) -> Result<(), anyhow::Error>
+    where
+        Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+    {
+        match self {
+            AddressBookWorkflow::Add { name, address } => {
+                wallet_state
+                    .address_book
+                    .add(name.clone(), address.clone())?;
+                println!("Added contact {} with address: {}", name, address);
+            }
+            AddressBookWorkflow::Remove { name } => {
+                wallet_state
+                    .address_book
+                    .remove(&name)
+                    .ok_or_else(|| anyhow::anyhow!("Could not find contact {}", name))?;
+                println!("Removed contact {}", name);
+            }
+            AddressBookWorkflow::List => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&wallet_state.address_book)?
+                )
+            }
+        }
+        Ok(())
+    }
+}
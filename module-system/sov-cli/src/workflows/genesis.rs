@@ -0,0 +1,41 @@
+//! Genesis workflows for the sov CLI wallet
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_modules_api::{clap, Context, DaSpec};
+
+use crate::genesis::GenesisFile;
+
+#[derive(clap::Subcommand)]
+/// Check the genesis configuration of a rollup
+pub enum GenesisWorkflow {
+    /// Validate a genesis file of the standard modules
+    Validate {
+        /// The path of the genesis file
+        path: PathBuf,
+    },
+}
+
+impl GenesisWorkflow {
+    /// Run the genesis workflow
+    pub fn run<C, Da>(self) -> Result<(), anyhow::Error>
+    where
+        C: Context + Serialize + DeserializeOwned,
+        Da: DaSpec + Serialize + DeserializeOwned,
+    {
+        match self {
+            GenesisWorkflow::Validate { path } => {
+                let genesis = GenesisFile::<C, Da>::from_path(&path)?;
+                println!(
+                    "The genesis file {} is valid: chain id {}, {} token(s), {} account(s)",
+                    path.display(),
+                    genesis.chain_state.chain_id,
+                    genesis.bank.tokens.len(),
+                    genesis.accounts.pub_keys.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
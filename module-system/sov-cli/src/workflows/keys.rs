@@ -6,7 +6,8 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_modules_api::{clap, PrivateKey, PublicKey, Spec};
 
-use crate::wallet_state::{KeyIdentifier, PrivateKeyAndAddress, WalletState};
+use crate::keystore::{read_password, Keystore};
+use crate::wallet_state::{KeyIdentifier, KeySource, PrivateKeyAndAddress, WalletState};
 
 #[derive(clap::Subcommand)]
 /// View and manage keys associated with this wallet
@@ -16,6 +17,9 @@ pub enum KeyWorkflow<C: sov_modules_api::Context> {
         #[clap(short, long)]
         /// A nickname for this key pair
         nickname: Option<String>,
+        #[clap(long)]
+        /// Encrypt the private key with a password
+        encrypt: bool,
     },
     /// Generate a new key pair if none exist
     GenerateIfMissing {
@@ -23,7 +27,7 @@ pub enum KeyWorkflow<C: sov_modules_api::Context> {
         /// A nickname for this key pair
         nickname: Option<String>,
     },
-    /// Import an existing key pair
+    /// Import an existing key pair, from a plaintext or an encrypted key file
     Import {
         #[clap(short, long)]
         /// A nickname for this key pair
@@ -35,6 +39,34 @@ pub enum KeyWorkflow<C: sov_modules_api::Context> {
         /// The path to the key file
         path: PathBuf,
     },
+    /// Register the key of an account of a Ledger device. Transactions of this address are signed on the device
+    #[cfg(feature = "ledger")]
+    ImportLedger {
+        #[clap(short, long)]
+        /// A nickname for this key pair
+        nickname: Option<String>,
+        #[clap(short, long, default_value_t = 0)]
+        /// The account index of the key on the device
+        account: u32,
+    },
+    /// Export a key of the wallet to a file, encrypted with a new password
+    Export {
+        #[clap(short, long)]
+        /// The path of the exported key file
+        path: PathBuf,
+        #[clap(long)]
+        /// Export the private key in plaintext instead
+        plaintext: bool,
+        /// The identifier of the key to export
+        #[clap(subcommand)]
+        identifier: KeyIdentifier<C>,
+    },
+    /// Encrypt a plaintext key of the wallet with a password
+    Encrypt {
+        /// The identifier of the key to encrypt
+        #[clap(subcommand)]
+        identifier: KeyIdentifier<C>,
+    },
     /// List the keys in this wallet
     List,
     /// Set the active key
@@ -62,8 +94,12 @@ impl<C: sov_modules_api::Context> KeyWorkflow<C> {
         Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
     {
         match self {
-            KeyWorkflow::Generate { nickname } => {
-                generate_and_save_key(nickname, app_dir, wallet_state)?;
+            KeyWorkflow::Generate { nickname, encrypt } => {
+                if encrypt {
+                    generate_and_save_encrypted_key(nickname, app_dir, wallet_state)?;
+                } else {
+                    generate_and_save_key(nickname, app_dir, wallet_state)?;
+                }
             }
             KeyWorkflow::Import {
                 nickname,
@@ -80,6 +116,86 @@ impl<C: sov_modules_api::Context> KeyWorkflow<C> {
                     .addresses
                     .add(address, nickname, public_key, path);
             }
+            #[cfg(feature = "ledger")]
+            KeyWorkflow::ImportLedger { nickname, account } => {
+                let public_key = <C as Spec>::PublicKey::try_from(
+                    crate::ledger::Ledger::connect()?
+                        .public_key(account)?
+                        .as_slice(),
+                )?;
+                let address = public_key.to_address::<C::Address>();
+                println!(
+                    "Imported Ledger account {} with address: {}",
+                    account, address
+                );
+                wallet_state
+                    .addresses
+                    .add_ledger(address, nickname, public_key, account);
+            }
+            KeyWorkflow::Export {
+                path,
+                plaintext,
+                identifier,
+            } => {
+                let entry = wallet_state
+                    .addresses
+                    .get_address(&identifier)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Could not find key with identifier {}", identifier)
+                    })?;
+                if entry.key_source != KeySource::File {
+                    anyhow::bail!(
+                        "The key {} is held by a hardware wallet and can't be exported",
+                        identifier
+                    );
+                }
+                let key_and_address = PrivateKeyAndAddress::<C> {
+                    private_key: load_key::<C>(&entry.location)?,
+                    address: entry.address.clone(),
+                };
+                let data = if plaintext {
+                    serde_json::to_string(&key_and_address)?
+                } else {
+                    let password = read_password("Password of the exported key: ", true)?;
+                    serde_json::to_string_pretty(&Keystore::encrypt(&key_and_address, &password)?)?
+                };
+                std::fs::write(&path, data)?;
+                println!("Exported key {} to {}", identifier, path.display());
+            }
+            KeyWorkflow::Encrypt { identifier } => {
+                let entry = wallet_state
+                    .addresses
+                    .get_address(&identifier)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Could not find key with identifier {}", identifier)
+                    })?;
+                if entry.key_source != KeySource::File {
+                    anyhow::bail!(
+                        "The key {} is held by a hardware wallet and can't be encrypted",
+                        identifier
+                    );
+                }
+                let data = std::fs::read_to_string(&entry.location)?;
+                if serde_json::from_str::<Keystore>(&data).is_ok() {
+                    anyhow::bail!("The key {} is already encrypted", identifier);
+                }
+                let key_and_address: PrivateKeyAndAddress<C> = serde_json::from_str(&data)?;
+                let password = read_password("New password: ", true)?;
+                let key_path = app_dir
+                    .as_ref()
+                    .join(format!("{}.keystore.json", entry.address));
+                save_keystore(&key_and_address, &password, &key_path)?;
+                // Only delete the plaintext key if the wallet owns it
+                if entry.location.starts_with(app_dir.as_ref()) {
+                    std::fs::remove_file(&entry.location)?;
+                }
+                println!(
+                    "Encrypted key {}. Saved to {}",
+                    identifier,
+                    key_path.display()
+                );
+                entry.location = key_path;
+            }
             KeyWorkflow::List => {
                 println!("{}", serde_json::to_string_pretty(&wallet_state.addresses)?)
             }
@@ -111,12 +227,19 @@ impl<C: sov_modules_api::Context> KeyWorkflow<C> {
     }
 }
 
-/// Load a key from the given path
+/// Load a key from the given path. An encrypted key file is decrypted with a password
 pub fn load_key<C: sov_modules_api::Context>(
     path: impl AsRef<Path>,
 ) -> Result<C::PrivateKey, anyhow::Error> {
+    let path = path.as_ref();
     let data = std::fs::read_to_string(path)?;
-    let key_and_address: PrivateKeyAndAddress<C> = serde_json::from_str(&data)?;
+    let key_and_address: PrivateKeyAndAddress<C> =
+        if let Ok(keystore) = serde_json::from_str::<Keystore>(&data) {
+            let password = read_password(&format!("Password of {}: ", path.display()), false)?;
+            keystore.decrypt(&password)?
+        } else {
+            serde_json::from_str(&data)?
+        };
     Ok(key_and_address.private_key)
 }
 
@@ -145,3 +268,39 @@ where
         .add(address, nickname, public_key, key_path);
     Ok(())
 }
+
+/// Generate a new key pair, encrypt it with a password and save it to the wallet
+pub fn generate_and_save_encrypted_key<Tx, C: sov_modules_api::Context>(
+    nickname: Option<String>,
+    app_dir: impl AsRef<Path>,
+    wallet_state: &mut WalletState<Tx, C>,
+) -> Result<(), anyhow::Error>
+where
+    Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+{
+    let password = read_password("New password: ", true)?;
+    let key_and_address = PrivateKeyAndAddress::<C>::generate();
+    let public_key = key_and_address.private_key.pub_key();
+    let address = key_and_address.address.clone();
+    let key_path = app_dir.as_ref().join(format!("{}.keystore.json", address));
+    println!(
+        "Generated key pair with address: {}. Saving encrypted key to {}",
+        address,
+        key_path.display()
+    );
+    save_keystore(&key_and_address, &password, &key_path)?;
+    wallet_state
+        .addresses
+        .add(address, nickname, public_key, key_path);
+    Ok(())
+}
+
+fn save_keystore<C: sov_modules_api::Context>(
+    key_and_address: &PrivateKeyAndAddress<C>,
+    password: &str,
+    path: &Path,
+) -> Result<(), anyhow::Error> {
+    let keystore = Keystore::encrypt(key_and_address, password)?;
+    std::fs::write(path, serde_json::to_string_pretty(&keystore)?)?;
+    Ok(())
+}
@@ -1,4 +1,7 @@
 //! Workflows for the CLI wallet
+pub mod address_book;
+pub mod genesis;
 pub mod keys;
+pub mod offline;
 pub mod rpc;
 pub mod transactions;
@@ -0,0 +1,237 @@
+//! Build, sign and broadcast transactions on separate machines, so that keys never leave an offline host
+
+use core::mem;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use borsh::{BorshDeserialize, BorshSerialize};
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::HttpClientBuilder;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::clap;
+use sov_modules_api::transaction::UnsignedTransaction;
+
+use crate::signer::Signer;
+use crate::wallet_state::{AddressEntry, KeyIdentifier, WalletState};
+
+/// Build, sign and broadcast transactions in separate steps
+#[derive(clap::Subcommand)]
+pub enum TxWorkflow<C: sov_modules_api::Context> {
+    /// Write the current batch of transactions to an unsigned batch file, and clear the batch.
+    /// The file can be signed on an offline machine with `tx sign`
+    Build {
+        /// The path of the unsigned batch file to write
+        path: PathBuf,
+        /// The nonce of the first transaction of the batch. Any other transactions use sequential nonces starting from this value
+        #[clap(long)]
+        nonce: u64,
+        /// The nonce lane of the transactions
+        #[clap(long, default_value_t = 0)]
+        nonce_lane: u64,
+    },
+    /// Sign the transactions of an unsigned batch file, without connecting to the rollup
+    Sign {
+        /// The path of the unsigned batch file
+        input: PathBuf,
+        /// The path of the signed batch file to write
+        output: PathBuf,
+        /// (Optional) The account to sign the transactions with (default: the active account)
+        #[clap(subcommand)]
+        account: Option<KeyIdentifier<C>>,
+    },
+    /// Submit the transactions of a signed batch file to the rollup
+    Broadcast {
+        /// The path of the signed batch file
+        path: PathBuf,
+    },
+}
+
+/// A batch of transactions to sign, with their nonces
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "Tx: Serialize + DeserializeOwned")]
+pub struct UnsignedBatch<Tx: BorshSerialize + BorshDeserialize> {
+    /// The transactions, in the order of their nonces
+    pub txs: Vec<UnsignedPayload<Tx>>,
+}
+
+/// A transaction to sign
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "Tx: Serialize + DeserializeOwned")]
+pub struct UnsignedPayload<Tx: BorshSerialize + BorshDeserialize> {
+    /// The transaction
+    pub tx: UnsignedTransaction<Tx>,
+    /// The nonce lane of the transaction
+    pub nonce_lane: u64,
+    /// The nonce of the transaction in its lane
+    pub nonce: u64,
+}
+
+/// A batch of signed transactions, ready to be broadcast
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedBatch {
+    /// The transactions, in the order of their nonces
+    pub txs: Vec<SignedPayload>,
+}
+
+/// A signed transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedPayload {
+    /// The nonce lane of the transaction
+    pub nonce_lane: u64,
+    /// The nonce of the transaction in its lane
+    pub nonce: u64,
+    /// The borsh serialized transaction, hex encoded
+    #[serde(with = "hex::serde")]
+    pub tx: Vec<u8>,
+}
+
+impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> TxWorkflow<C> {
+    /// Run the offline transaction workflow
+    pub async fn run<Tx>(
+        &self,
+        wallet_state: &mut WalletState<Tx, C>,
+        _app_dir: impl AsRef<Path>,
+    ) -> Result<(), anyhow::Error>
+    where
+        Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+    {
+        match self {
+            TxWorkflow::Build {
+                path,
+                nonce,
+                nonce_lane,
+            } => {
+                let batch = build_batch(wallet_state, *nonce_lane, *nonce);
+                write_json(path, &batch)?;
+                println!(
+                    "Wrote {} unsigned transactions to {}",
+                    batch.txs.len(),
+                    path.display()
+                );
+            }
+            TxWorkflow::Sign {
+                input,
+                output,
+                account,
+            } => {
+                let batch: UnsignedBatch<Tx> = read_json(input)?;
+                let account = resolve_account(wallet_state, account.as_ref())?;
+                let signed = sign_batch(account, &batch)?;
+                write_json(output, &signed)?;
+                println!(
+                    "Signed {} transactions with account {}. Wrote them to {}",
+                    signed.txs.len(),
+                    account.address,
+                    output.display()
+                );
+            }
+            TxWorkflow::Broadcast { path } => {
+                let batch: SignedBatch = read_json(path)?;
+                let rpc_url = wallet_state.rpc_url.as_ref().ok_or(anyhow::format_err!(
+                    "No rpc url set. Use the `rpc set-url` subcommand to set one"
+                ))?;
+                let client = HttpClientBuilder::default().build(rpc_url)?;
+
+                let txs: Vec<Vec<u8>> = batch.txs.into_iter().map(|tx| tx.tx).collect();
+                let response: String = client
+                    .request("sequencer_publishBatch", txs)
+                    .await
+                    .context("Unable to publish batch")?;
+
+                println!(
+                    "Your batch was submitted to the sequencer for publication. Response: {:?}",
+                    response
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Take the current batch of transactions of the wallet, and assign them sequential nonces starting from `nonce`
+pub fn build_batch<Tx, C>(
+    wallet_state: &mut WalletState<Tx, C>,
+    nonce_lane: u64,
+    nonce: u64,
+) -> UnsignedBatch<Tx>
+where
+    Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+    C: sov_modules_api::Context,
+{
+    let txs = mem::take(&mut wallet_state.unsent_transactions)
+        .into_iter()
+        .enumerate()
+        .map(|(offset, tx)| UnsignedPayload {
+            tx,
+            nonce_lane,
+            nonce: nonce + offset as u64,
+        })
+        .collect();
+    UnsignedBatch { txs }
+}
+
+/// Sign the transactions of an unsigned batch with the key of `account`
+pub fn sign_batch<Tx, C>(
+    account: &AddressEntry<C>,
+    batch: &UnsignedBatch<Tx>,
+) -> Result<SignedBatch, anyhow::Error>
+where
+    Tx: BorshSerialize + BorshDeserialize,
+    C: sov_modules_api::Context,
+{
+    let signer = Signer::<C>::load(account)?;
+    let txs = batch
+        .txs
+        .iter()
+        .map(|payload| {
+            let tx = signer.sign_tx(
+                payload.tx.try_to_vec()?,
+                payload.tx.chain_id,
+                payload.tx.gas_tip,
+                payload.tx.gas_limit,
+                payload.nonce_lane,
+                payload.nonce,
+            )?;
+            Ok(SignedPayload {
+                nonce_lane: payload.nonce_lane,
+                nonce: payload.nonce,
+                tx: tx.try_to_vec()?,
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+    Ok(SignedBatch { txs })
+}
+
+fn resolve_account<'wallet, Tx, C>(
+    wallet_state: &'wallet mut WalletState<Tx, C>,
+    account: Option<&KeyIdentifier<C>>,
+) -> Result<&'wallet AddressEntry<C>, anyhow::Error>
+where
+    Tx: BorshSerialize + BorshDeserialize,
+    C: sov_modules_api::Context,
+{
+    match account {
+        Some(id) => wallet_state
+            .addresses
+            .get_address(id)
+            .map(|entry| &*entry)
+            .ok_or_else(|| anyhow::format_err!("No account found matching identifier: {}", id)),
+        None => wallet_state.addresses.default_address().ok_or_else(|| {
+            anyhow::format_err!(
+                "No accounts found. You can generate one with the `keys generate` subcommand"
+            )
+        }),
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, anyhow::Error> {
+    let data = std::fs::read(path)
+        .with_context(|| format!("Unable to read the batch file {}", path.display()))?;
+    serde_json::from_slice(&data).with_context(|| format!("Invalid batch file {}", path.display()))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), anyhow::Error> {
+    std::fs::write(path, serde_json::to_vec_pretty(value)?)
+        .with_context(|| format!("Unable to write the batch file {}", path.display()))
+}
@@ -1,21 +1,21 @@
 //! Query the current state of the rollup and send transactions
 
 use core::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use borsh::{BorshDeserialize, BorshSerialize};
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::HttpClientBuilder;
+use jsonrpsee::rpc_params;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_accounts::AccountsRpcClient;
 use sov_bank::{BalanceResponse, BankRpcClient};
 use sov_modules_api::clap;
-use sov_modules_api::transaction::Transaction;
 
+use crate::signer::Signer;
 use crate::wallet_state::{AddressEntry, KeyIdentifier, WalletState};
-use crate::workflows::keys::load_key;
 const NO_ACCOUNTS_FOUND: &str =
     "No accounts found. You can generate one with the `keys generate` subcommand";
 const BAD_RPC_URL: &str = "Unable to connect to provided rpc. You can change to a different rpc url with the `rpc set-url` subcommand ";
@@ -28,11 +28,19 @@ pub enum RpcWorkflows<C: sov_modules_api::Context> {
         /// A url like http://localhost:8545
         rpc_url: String,
     },
+    /// Set the bech32 prefix of the addresses of the rollup, as in its `chain.address_prefix` configuration
+    SetAddressPrefix {
+        /// A prefix like `sov`, for `sov1...` addresses
+        address_prefix: String,
+    },
     /// Query the rpc server for the nonce of the provided account. If no account is provided, the active account is used
     GetNonce {
         /// (Optional) The account to query the nonce for (default: the active account)
         #[clap(subcommand)]
         account: Option<KeyIdentifier<C>>,
+        /// The nonce lane to query the nonce of
+        #[clap(long, default_value_t = 0)]
+        nonce_lane: u64,
     },
     /// Query the rpc server for the token balance of an account
     GetBalance {
@@ -51,6 +59,19 @@ pub enum RpcWorkflows<C: sov_modules_api::Context> {
         /// (Optional) The nonce to use for the first transaction in the batch (default: the current nonce for the account). Any other transactions will
         /// be signed with sequential nonces starting from this value.
         nonce_override: Option<u64>,
+        /// The nonce lane to sign the transactions in. Transactions in different lanes don't block each other
+        #[clap(long, default_value_t = 0)]
+        nonce_lane: u64,
+    },
+    /// Export the historical transitions of the chain state in the inclusive range `start..=end` to a file.
+    /// The export can be used to bootstrap an auxiliary verifier.
+    ExportTransitions {
+        /// The height of the first transition to export
+        start: u64,
+        /// The height of the last transition to export
+        end: u64,
+        /// The path of the file to write the transitions to
+        path: PathBuf,
     },
 }
 
@@ -63,8 +84,10 @@ impl<C: sov_modules_api::Context> RpcWorkflows<C> {
         Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
     {
         let account_id = match self {
-            RpcWorkflows::SetUrl { .. } => None,
-            RpcWorkflows::GetNonce { account } => account.as_ref(),
+            RpcWorkflows::SetUrl { .. }
+            | RpcWorkflows::SetAddressPrefix { .. }
+            | RpcWorkflows::ExportTransitions { .. } => None,
+            RpcWorkflows::GetNonce { account, .. } => account.as_ref(),
             RpcWorkflows::GetBalance { account, .. } => account.as_ref(),
             RpcWorkflows::SubmitBatch { account, .. } => account.as_ref(),
         };
@@ -102,6 +125,13 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
             println!("Set RPC url to {}", rpc_url);
             return Ok(());
         }
+        if let RpcWorkflows::SetAddressPrefix { address_prefix } = self {
+            sov_modules_api::set_address_prefix(address_prefix)
+                .context("Invalid address prefix")?;
+            wallet_state.address_prefix = Some(address_prefix.clone());
+            println!("Set address prefix to {}", address_prefix);
+            return Ok(());
+        }
 
         // Otherwise, we need to initialize an  RPC and resolve the active account
         let rpc_url = wallet_state
@@ -112,16 +142,40 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
             ))?
             .clone();
         let client = HttpClientBuilder::default().build(rpc_url)?;
+
+        // Exporting transitions doesn't involve any account
+        if let RpcWorkflows::ExportTransitions { start, end, path } = self {
+            let export: serde_json::Value = client
+                .request("chainState_exportTransitions", rpc_params![start, end])
+                .await
+                .context("Unable to export the transitions")?;
+            std::fs::write(path, serde_json::to_vec_pretty(&export)?).with_context(|| {
+                format!("Unable to write the transitions to {}", path.display())
+            })?;
+            println!(
+                "Exported the transitions {}..={} to {}",
+                start,
+                end,
+                path.display()
+            );
+            return Ok(());
+        }
+
         let account = self.resolve_account(wallet_state)?;
 
         // Finally, run the workflow
         match self {
-            RpcWorkflows::SetUrl { .. } => {
+            RpcWorkflows::SetUrl { .. }
+            | RpcWorkflows::SetAddressPrefix { .. }
+            | RpcWorkflows::ExportTransitions { .. } => {
                 unreachable!("This case was handled above")
             }
-            RpcWorkflows::GetNonce { .. } => {
-                let nonce = get_nonce_for_account(&client, account).await?;
-                println!("Nonce for account {} is {}", account.address, nonce);
+            RpcWorkflows::GetNonce { nonce_lane, .. } => {
+                let nonce = get_nonce_for_account(&client, account, *nonce_lane).await?;
+                println!(
+                    "Nonce for account {} in lane {} is {}",
+                    account.address, nonce_lane, nonce
+                );
             }
             RpcWorkflows::GetBalance {
                 account: _,
@@ -142,30 +196,35 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
                     amount.unwrap_or_default()
                 );
             }
-            RpcWorkflows::SubmitBatch { nonce_override, .. } => {
-                let private_key = load_key::<C>(&account.location)?;
+            RpcWorkflows::SubmitBatch {
+                nonce_override,
+                nonce_lane,
+                ..
+            } => {
+                let signer = Signer::<C>::load(account)?;
 
                 let nonce = match nonce_override {
                     Some(nonce) => *nonce,
-                    None => get_nonce_for_account(&client, account).await?,
+                    None => get_nonce_for_account(&client, account, *nonce_lane).await?,
                 };
 
                 let txs = mem::take(&mut wallet_state.unsent_transactions)
                     .into_iter()
                     .enumerate()
                     .map(|(offset, tx)| {
-                        Transaction::<C>::new_signed_tx(
-                            &private_key,
-                            tx.try_to_vec().unwrap(),
-                            tx.chain_id,
-                            tx.gas_tip,
-                            tx.gas_limit,
-                            nonce + offset as u64,
-                        )
-                        .try_to_vec()
-                        .unwrap()
+                        Ok(signer
+                            .sign_tx(
+                                tx.try_to_vec().unwrap(),
+                                tx.chain_id,
+                                tx.gas_tip,
+                                tx.gas_limit,
+                                *nonce_lane,
+                                nonce + offset as u64,
+                            )?
+                            .try_to_vec()
+                            .unwrap())
                     })
-                    .collect::<Vec<_>>();
+                    .collect::<Result<Vec<_>, anyhow::Error>>()?;
 
                 let response: String = client
                     .request("sequencer_publishBatch", txs)
@@ -186,16 +245,11 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
 async fn get_nonce_for_account<C: sov_modules_api::Context + Send + Sync + Serialize>(
     client: &(impl ClientT + Send + Sync),
     account: &AddressEntry<C>,
+    nonce_lane: u64,
 ) -> Result<u64, anyhow::Error> {
-    Ok(match AccountsRpcClient::<C>::get_account(
-        client,
-        account.pub_key.clone(),
-    )
+    AccountsRpcClient::<C>::get_nonce(client, account.pub_key.clone(), nonce_lane)
     .await
     .context(
         "Unable to connect to provided RPC. You can change to a different RPC url with the `rpc set-url` subcommand ",
-    )? {
-        sov_accounts::Response::AccountExists { addr: _, nonce } => nonce,
-        _ => 0,
-    })
+    )
 }
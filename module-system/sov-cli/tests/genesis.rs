@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use sov_cli::genesis::{GenesisBuilder, GenesisFile};
+use sov_mock_da::{MockAddress, MockDaSpec};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::Address;
+
+type Genesis = GenesisFile<DefaultContext, MockDaSpec>;
+
+const GENESIS_DIR: &str = "test-data/genesis";
+
+#[test]
+fn test_genesis_builder_matches_fixture() {
+    let holder =
+        Address::from_str("sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94")
+            .unwrap();
+    let mut genesis = GenesisBuilder::<DefaultContext, MockDaSpec>::new(4321)
+        .with_token("sov-demo-token", vec![(holder, 100000000)])
+        .with_sequencer(holder, MockAddress::from([0; 32]), "sov-demo-token", 50)
+        .build()
+        .unwrap();
+    genesis.bank.tokens[0].authorized_minters = vec![holder];
+
+    let fixture = Genesis::from_path(format!("{GENESIS_DIR}/valid.json")).unwrap();
+    assert_eq!(genesis, fixture);
+
+    // The sequencer must bond a token of the bank
+    let err = GenesisBuilder::<DefaultContext, MockDaSpec>::new(4321)
+        .with_token("sov-demo-token", vec![(holder, 100000000)])
+        .with_sequencer(holder, MockAddress::from([0; 32]), "other-token", 50)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("isn't a token of the bank"));
+}
+
+#[test]
+fn test_invalid_genesis_fixtures() {
+    for (fixture, expected_error) in [
+        ("unknown_bond_token.json", "isn't the address of any token"),
+        ("insufficient_bond.json", "but its bond is 100000001"),
+        (
+            "duplicate_token.json",
+            "have the same name `sov-demo-token`",
+        ),
+        ("zero_transition_retention.json", "Invalid `chain_state`"),
+        ("missing.json", "Failed to read the genesis file"),
+    ] {
+        let err = Genesis::from_path(format!("{GENESIS_DIR}/{fixture}")).unwrap_err();
+        let err = format!("{:#}", err);
+        assert!(
+            err.contains(expected_error),
+            "Unexpected error for {}: {}",
+            fixture,
+            err
+        );
+    }
+}
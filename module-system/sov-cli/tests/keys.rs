@@ -1,6 +1,8 @@
 use demo_stf::runtime::RuntimeCall;
+use sov_cli::keystore::Keystore;
 use sov_cli::wallet_state::{KeyIdentifier, PrivateKeyAndAddress, WalletState};
-use sov_cli::workflows::keys::KeyWorkflow;
+use sov_cli::workflows::address_book::AddressBookWorkflow;
+use sov_cli::workflows::keys::{load_key, KeyWorkflow};
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::{PrivateKey, PublicKey, Spec};
@@ -12,7 +14,10 @@ fn test_key_gen() {
     let app_dir = tempfile::tempdir().unwrap();
     let mut wallet_state =
         WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
-    let workflow = KeyWorkflow::Generate { nickname: None };
+    let workflow = KeyWorkflow::Generate {
+        nickname: None,
+        encrypt: false,
+    };
     workflow.run(&mut wallet_state, app_dir).unwrap();
 
     assert!(wallet_state.addresses.default_address().is_some());
@@ -62,10 +67,12 @@ fn test_activate() {
         WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
     let workflow = KeyWorkflow::Generate {
         nickname: Some("key1".into()),
+        encrypt: false,
     };
     workflow.run(&mut wallet_state, &app_dir).unwrap();
     let workflow = KeyWorkflow::Generate {
         nickname: Some("key2".into()),
+        encrypt: false,
     };
     workflow.run(&mut wallet_state, &app_dir).unwrap();
 
@@ -96,3 +103,110 @@ fn test_activate() {
     let current_active_wallet = wallet_state.addresses.default_address().unwrap();
     assert!(current_active_wallet.is_nicknamed("key1"));
 }
+
+#[test]
+fn test_encrypted_key_export_and_import() {
+    std::env::set_var("SOV_WALLET_PASSWORD", "correct horse battery staple");
+    let app_dir = tempfile::tempdir().unwrap();
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    let workflow = KeyWorkflow::Generate {
+        nickname: Some("encrypted".into()),
+        encrypt: true,
+    };
+    workflow.run(&mut wallet_state, &app_dir).unwrap();
+
+    // The key file doesn't contain the private key in clear
+    let entry = wallet_state.addresses.default_address().unwrap();
+    let address = entry.address;
+    let data = std::fs::read_to_string(&entry.location).unwrap();
+    let keystore: Keystore = serde_json::from_str(&data).unwrap();
+    let private_key = load_key::<DefaultContext>(&entry.location).unwrap();
+    assert!(!data.contains(&private_key.as_hex()));
+    assert_eq!(
+        address,
+        private_key.to_address::<<DefaultContext as Spec>::Address>()
+    );
+    assert!(keystore
+        .decrypt::<DefaultContext>("wrong password")
+        .is_err());
+
+    // Export the key in plaintext, and import it in another wallet
+    let export_path = app_dir.path().join("exported.json");
+    let workflow = KeyWorkflow::Export {
+        path: export_path.clone(),
+        plaintext: true,
+        identifier: KeyIdentifier::ByAddress { address },
+    };
+    workflow.run(&mut wallet_state, &app_dir).unwrap();
+
+    let other_app_dir = tempfile::tempdir().unwrap();
+    let mut other_wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    let workflow = KeyWorkflow::Import {
+        nickname: None,
+        address_override: None,
+        path: export_path,
+    };
+    workflow.run(&mut other_wallet_state, &other_app_dir).unwrap();
+    assert_eq!(
+        address,
+        other_wallet_state
+            .addresses
+            .default_address()
+            .unwrap()
+            .address
+    );
+
+    // Encrypt the imported key
+    let workflow = KeyWorkflow::Encrypt {
+        identifier: KeyIdentifier::ByAddress { address },
+    };
+    workflow.run(&mut other_wallet_state, &other_app_dir).unwrap();
+    let entry = other_wallet_state.addresses.default_address().unwrap();
+    let data = std::fs::read_to_string(&entry.location).unwrap();
+    assert!(serde_json::from_str::<Keystore>(&data).is_ok());
+    assert_eq!(
+        private_key.pub_key(),
+        load_key::<DefaultContext>(&entry.location)
+            .unwrap()
+            .pub_key()
+    );
+}
+
+#[test]
+fn test_address_book() {
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    let address = <DefaultContext as Spec>::PrivateKey::generate()
+        .pub_key()
+        .to_address::<<DefaultContext as Spec>::Address>();
+
+    let workflow = AddressBookWorkflow::Add {
+        name: "alice".to_string(),
+        address,
+    };
+    workflow.run(&mut wallet_state).unwrap();
+    // Names are unique
+    let workflow = AddressBookWorkflow::Add {
+        name: "alice".to_string(),
+        address,
+    };
+    assert!(workflow.run(&mut wallet_state).is_err());
+
+    assert_eq!(Some(&address), wallet_state.address_book.get("alice"));
+    assert_eq!(
+        address,
+        wallet_state
+            .address_book
+            .resolve(&address.to_string())
+            .unwrap()
+    );
+    assert!(wallet_state.address_book.resolve("bob").is_err());
+
+    let workflow = AddressBookWorkflow::Remove {
+        name: "alice".to_string(),
+    };
+    workflow.run(&mut wallet_state).unwrap();
+    assert!(wallet_state.address_book.get("alice").is_none());
+}
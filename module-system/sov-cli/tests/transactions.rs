@@ -1,11 +1,16 @@
 use std::path::{Path, PathBuf};
 
+use borsh::BorshDeserialize;
 use demo_stf::runtime::{Runtime, RuntimeCall, RuntimeSubcommand};
 use sov_cli::wallet_state::WalletState;
+use sov_cli::workflows::keys::KeyWorkflow;
+use sov_cli::workflows::offline::{build_batch, sign_batch, UnsignedBatch};
 use sov_cli::workflows::transactions::{ImportTransaction, TransactionWorkflow};
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::cli::{FileNameArg, JsonStringArg};
 use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::PublicKey;
 
 type Da = MockDaSpec;
 
@@ -63,6 +68,59 @@ fn test_import_transaction_from_file() {
     assert_eq!(wallet_state.unsent_transactions.len(), 1);
 }
 
+#[test]
+fn test_build_and_sign_batch_offline() {
+    let app_dir = tempfile::tempdir().unwrap();
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    KeyWorkflow::Generate {
+        nickname: None,
+        encrypt: false,
+    }
+    .run(&mut wallet_state, &app_dir)
+    .unwrap();
+
+    let test_token_path = make_test_path("requests/create_token.json");
+    for _ in 0..2 {
+        let subcommand = RuntimeSubcommand::<JsonStringArg, DefaultContext, Da>::bank {
+            contents: JsonStringArg {
+                json: std::fs::read_to_string(&test_token_path).unwrap(),
+                chain_id: 0,
+                gas_tip: 0,
+                gas_limit: 0,
+            },
+        };
+        TransactionWorkflow::Import(ImportTransaction::<
+            _,
+            RuntimeSubcommand<JsonStringArg, DefaultContext, Da>,
+        >::FromFile(subcommand))
+        .run::<Runtime<DefaultContext, Da>, _, _, _, _, _>(&mut wallet_state, &app_dir)
+        .unwrap();
+    }
+
+    let batch = build_batch(&mut wallet_state, 1, 5);
+    assert!(wallet_state.unsent_transactions.is_empty());
+
+    // The batch goes through a file to the offline machine
+    let batch: UnsignedBatch<RuntimeCall<DefaultContext, Da>> =
+        serde_json::from_str(&serde_json::to_string(&batch).unwrap()).unwrap();
+    let account = wallet_state.addresses.default_address().unwrap();
+    let signed = sign_batch(account, &batch).unwrap();
+
+    assert_eq!(signed.txs.len(), 2);
+    for (offset, payload) in signed.txs.iter().enumerate() {
+        let tx = Transaction::<DefaultContext>::try_from_slice(&payload.tx).unwrap();
+        tx.verify().expect("The transaction must be signed");
+        assert_eq!(tx.nonce_lane(), 1);
+        assert_eq!(tx.nonce(), 5 + offset as u64);
+        assert_eq!(
+            tx.pub_key()
+                .to_address::<<DefaultContext as sov_modules_api::Spec>::Address>(),
+            account.address
+        );
+    }
+}
+
 fn make_test_path<P: AsRef<Path>>(path: P) -> PathBuf {
     let mut sender_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     sender_path.push("test-data");
@@ -210,6 +210,8 @@ pub(crate) fn mint_token_tx<C: Context>(mint_data: &MintData<C>) -> CallMessage<
         initial_balance: mint_data.initial_balance,
         minter_address: mint_data.minter_address.clone(),
         authorized_minters: mint_data.authorized_minters.clone(),
+        max_supply: None,
+        metadata: None,
     }
 }
 
@@ -306,6 +308,8 @@ impl MessageGenerator for BadSerializationBankCallMessages {
                 initial_balance: 1000,
                 minter_address,
                 authorized_minters: Vec::from([minter_address]),
+                max_supply: None,
+                metadata: None,
             },
             DEFAULT_CHAIN_ID,
             DEFAULT_GAS_TIP,
@@ -384,6 +388,8 @@ impl MessageGenerator for BadSignatureBankCallMessages {
                 initial_balance: 1000,
                 minter_address,
                 authorized_minters: Vec::from([minter_address]),
+                max_supply: None,
+                metadata: None,
             },
             DEFAULT_CHAIN_ID,
             DEFAULT_GAS_TIP,
@@ -421,7 +427,10 @@ impl MessageGenerator for BadSignatureBankCallMessages {
                 chain_id,
                 gas_tip,
                 gas_limit,
+                tx.nonce_lane(),
                 nonce,
+                None,
+                None,
             )
         } else {
             Transaction::<DefaultContext>::new_signed_tx(
@@ -463,6 +472,8 @@ impl MessageGenerator for BadNonceBankCallMessages {
                 initial_balance: 1000,
                 minter_address,
                 authorized_minters: Vec::from([minter_address]),
+                max_supply: None,
+                metadata: None,
             },
             DEFAULT_CHAIN_ID,
             DEFAULT_GAS_TIP,
@@ -1,6 +1,10 @@
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 
-use sov_modules_core::{Context, Prefix, StateCodec, StateKeyCodec, StateValueCodec, WorkingSet};
+use sov_modules_core::{
+    Context, NativeStorage, Prefix, StateCodec, StateKeyCodec, StateKeyDecoder, StateValueCodec,
+    StorageKey, WorkingSet,
+};
 use sov_state::codec::BorshCodec;
 
 use super::traits::StateMapAccessor;
@@ -72,6 +76,105 @@ where
     }
 }
 
+impl<K, V, Codec> StateMap<K, V, Codec>
+where
+    Codec: StateCodec,
+    Codec::KeyCodec: StateKeyDecoder<K>,
+    Codec::ValueCodec: StateValueCodec<V>,
+{
+    /// Returns the entries of the map whose keys are in `range`, sorted by key.
+    ///
+    /// Keys are compared by their encoding rather than by their [`Ord`] implementation. For instance,
+    /// [`BorshCodec`] encodes integers in little-endian, so the keys of a map iterated in numeric order
+    /// must be encoded in big-endian, like `[u8; 8]` arrays.
+    ///
+    /// The changes made through `working_set` are included. Range scans are only available natively,
+    /// see [`WorkingSet::get_range`].
+    pub fn range<C: Context>(
+        &self,
+        range: impl RangeBounds<K>,
+        working_set: &WorkingSet<C>,
+    ) -> Vec<(K, V)>
+    where
+        C::Storage: NativeStorage,
+    {
+        let prefixed_key = |key: &K| StorageKey::new(&self.prefix, key, self.codec.key_codec());
+        let start = match range.start_bound() {
+            Bound::Included(key) => prefixed_key(key).as_ref().clone(),
+            // The smallest byte string greater than the key
+            Bound::Excluded(key) => {
+                let mut start = prefixed_key(key).as_ref().clone();
+                start.push(0);
+                start
+            }
+            Bound::Unbounded => self.prefix.as_aligned_vec().as_ref().clone(),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => {
+                let mut end = prefixed_key(key).as_ref().clone();
+                end.push(0);
+                Some(end)
+            }
+            Bound::Excluded(key) => Some(prefixed_key(key).as_ref().clone()),
+            Bound::Unbounded => prefix_end(self.prefix.as_aligned_vec().as_ref()),
+        };
+        self.decoded_range(start, end, working_set)
+    }
+
+    /// Returns the entries of the map whose encoded keys start with the encoding of `key_prefix`, sorted by key.
+    ///
+    /// With [`BorshCodec`], a tuple is encoded as the concatenation of its fields, so the entries of a map
+    /// keyed by `(owner, id)` which belong to `owner` are returned by `map.prefix_range(&owner, working_set)`.
+    /// See [`StateMap::range`] for how the keys are ordered.
+    pub fn prefix_range<P, C: Context>(
+        &self,
+        key_prefix: &P,
+        working_set: &WorkingSet<C>,
+    ) -> Vec<(K, V)>
+    where
+        Codec::KeyCodec: StateKeyCodec<P>,
+        C::Storage: NativeStorage,
+    {
+        let start = StorageKey::new(&self.prefix, key_prefix, self.codec.key_codec())
+            .as_ref()
+            .clone();
+        let end = prefix_end(&start);
+        self.decoded_range(start, end, working_set)
+    }
+
+    fn decoded_range<C: Context>(
+        &self,
+        start: Vec<u8>,
+        end: Option<Vec<u8>>,
+        working_set: &WorkingSet<C>,
+    ) -> Vec<(K, V)>
+    where
+        C::Storage: NativeStorage,
+    {
+        working_set
+            .get_range(&start, end.as_deref())
+            .into_iter()
+            .map(|(key, value)| {
+                let key = self
+                    .codec
+                    .key_codec()
+                    .decode_key_unwrap(&key.as_ref()[self.prefix.len()..]);
+                let value = self.codec.value_codec().decode_value_unwrap(value.value());
+                (key, value)
+            })
+            .collect()
+    }
+}
+
+/// Returns the smallest byte string greater than every byte string starting with `prefix`,
+/// or `None` if there is none.
+fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last = prefix.iter().rposition(|byte| *byte != u8::MAX)?;
+    let mut end = prefix[..=last].to_vec();
+    end[last] += 1;
+    Some(end)
+}
+
 #[cfg(feature = "arbitrary")]
 impl<'a, K, V, Codec> StateMap<K, V, Codec>
 where
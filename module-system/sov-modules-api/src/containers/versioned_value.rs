@@ -69,6 +69,20 @@ impl<V, Codec> VersionedStateValue<V, Codec> {
         ws.get_value(self.prefix(), &ws.current_version(), &self.codec)
     }
 
+    /// Any version_aware working set can read the contents of a versioned value at a past `version`.
+    /// Returns `None` for versions which are not yet visible to the working set.
+    pub fn get_at(&self, version: u64, ws: &mut impl VersionReader) -> Option<V>
+    where
+        Codec: StateCodec,
+        Codec::ValueCodec: StateValueCodec<V>,
+        Codec::KeyCodec: StateKeyCodec<u64>,
+    {
+        if version > ws.current_version() {
+            return None;
+        }
+        ws.get_value(self.prefix(), &version, &self.codec)
+    }
+
     /// Only the kernel working set can write to versioned values
     pub fn set_current<C: Context>(&self, value: &V, ws: &mut KernelWorkingSet<'_, C>)
     where
@@ -369,4 +383,34 @@ mod tests {
             assert_eq!(value.get_current(&mut versioned_state), Some(17));
         }
     }
+
+    #[test]
+    fn test_kernel_state_value_at_version() {
+        use crate::StateMapAccessor;
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = new_orphan_storage(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new(b"test".to_vec());
+        let value = VersionedStateValue::<u64>::new(prefix);
+        let kernel = MockKernel::<DefaultContext, MockDaSpec>::new(4, 1);
+        {
+            let mut kernel_state = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+            value.set(&2, &100, &mut kernel_state);
+            value.set(&3, &200, &mut kernel_state);
+            value.set(&4, &300, &mut kernel_state);
+        }
+
+        let signer = Address::from([1; 32]);
+        let sequencer = Address::from([2; 32]);
+        let mut versioned_state =
+            working_set.versioned_state(&DefaultContext::new(signer, sequencer, 3));
+        // Past and current versions are visible
+        assert_eq!(value.get_at(2, &mut versioned_state), Some(100));
+        assert_eq!(value.get_at(3, &mut versioned_state), Some(200));
+        // Future versions aren't, even if they are already set
+        assert_eq!(value.get_at(4, &mut versioned_state), None);
+        // Versions which were never set have no value
+        assert_eq!(value.get_at(1, &mut versioned_state), None);
+    }
 }
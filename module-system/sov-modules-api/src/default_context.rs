@@ -12,6 +12,9 @@ use sov_state::{ArrayWitness, DefaultStorageSpec, ZkStorage};
 #[cfg(feature = "native")]
 use crate::default_signature::private_key::DefaultPrivateKey;
 use crate::default_signature::{DefaultPublicKey, DefaultSignature};
+#[cfg(feature = "native")]
+use crate::multi_signature::private_key::MultiPrivateKey;
+use crate::multi_signature::{MultiPublicKey, MultiSignature};
 
 #[cfg(feature = "native")]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -103,6 +106,99 @@ impl Context for ZkDefaultContext {
     }
 }
 
+/// A context whose transactions can be signed with ed25519 keys, secp256k1 keys or passkeys.
+/// See [`crate::multi_signature`].
+#[cfg(feature = "native")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultiSchemeContext {
+    pub sender: Address,
+    pub sequencer: Address,
+    /// The height to report. This is set by the kernel when the context is created
+    visible_height: u64,
+}
+
+#[cfg(feature = "native")]
+impl Spec for MultiSchemeContext {
+    type Address = Address;
+    type Storage = ProverStorage<DefaultStorageSpec, sov_prover_storage_manager::SnapshotManager>;
+    type PrivateKey = MultiPrivateKey;
+    type PublicKey = MultiPublicKey;
+    type Hasher = sha2::Sha256;
+    type Signature = MultiSignature;
+    type Witness = ArrayWitness;
+}
+
+#[cfg(feature = "native")]
+impl Context for MultiSchemeContext {
+    type GasUnit = TupleGasUnit<2>;
+
+    fn sender(&self) -> &Self::Address {
+        &self.sender
+    }
+
+    fn sequencer(&self) -> &Self::Address {
+        &self.sequencer
+    }
+
+    fn new(sender: Self::Address, sequencer: Self::Address, height: u64) -> Self {
+        Self {
+            sender,
+            sequencer,
+            visible_height: height,
+        }
+    }
+
+    fn slot_height(&self) -> u64 {
+        self.visible_height
+    }
+}
+
+/// The zk counterpart of [`MultiSchemeContext`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZkMultiSchemeContext {
+    pub sender: Address,
+    pub sequencer: Address,
+    /// The height to report. This is set by the kernel when the context is created
+    visible_height: u64,
+}
+
+impl Spec for ZkMultiSchemeContext {
+    type Address = Address;
+    type Storage = ZkStorage<DefaultStorageSpec>;
+    #[cfg(feature = "native")]
+    type PrivateKey = MultiPrivateKey;
+    type PublicKey = MultiPublicKey;
+    type Hasher = sha2::Sha256;
+    type Signature = MultiSignature;
+    type Witness = ArrayWitness;
+}
+
+impl Context for ZkMultiSchemeContext {
+    type GasUnit = TupleGasUnit<2>;
+
+    fn sender(&self) -> &Self::Address {
+        &self.sender
+    }
+
+    fn sequencer(&self) -> &Self::Address {
+        &self.sequencer
+    }
+
+    fn new(sender: Self::Address, sequencer: Self::Address, height: u64) -> Self {
+        Self {
+            sender,
+            sequencer,
+            visible_height: height,
+        }
+    }
+
+    fn slot_height(&self) -> u64 {
+        self.visible_height
+    }
+}
+
 impl PublicKey for DefaultPublicKey {
     fn to_address<A: RollupAddress>(&self) -> A {
         let pub_key_hash = {
@@ -0,0 +1,139 @@
+//! Commitments to the events emitted in a slot.
+//!
+//! The events of all the transactions of a slot, in execution order, are the leaves of a binary Merkle tree.
+//! The root of the tree is recorded in state by the kernel, so that the inclusion of an event can be proven
+//! against the state root with an [`EventInclusionProof`].
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::digest::typenum::U32;
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::stf::Event;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// The root of the events tree of a slot without any event.
+pub const EMPTY_EVENTS_ROOT: [u8; 32] = [0; 32];
+
+/// A proof that an event is a leaf of an events tree.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventInclusionProof {
+    /// The position of the event among the events of the slot
+    pub index: u64,
+    /// The number of events emitted in the slot
+    pub leaf_count: u64,
+    /// The siblings on the path from the leaf to the root, from the bottom up.
+    /// The last node of a level without a sibling is promoted to the next level.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl EventInclusionProof {
+    /// Checks that `event` is the leaf at `self.index` of the events tree with the given `root`.
+    pub fn verify<H: Digest<OutputSize = U32>>(&self, event: &Event, root: &[u8; 32]) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+
+        let mut siblings = self.siblings.iter();
+        let mut node = leaf_hash::<H>(event);
+        let mut index = self.index;
+        let mut width = self.leaf_count;
+        while width > 1 {
+            if index % 2 == 1 {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                node = node_hash::<H>(sibling, &node);
+            } else if index + 1 < width {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                node = node_hash::<H>(&node, sibling);
+            }
+            index /= 2;
+            width = (width + 1) / 2;
+        }
+
+        siblings.next().is_none() && &node == root
+    }
+}
+
+/// Returns the root of the events tree over `events`.
+pub fn events_root<'a, H: Digest<OutputSize = U32>>(
+    events: impl IntoIterator<Item = &'a Event>,
+) -> [u8; 32] {
+    let levels = tree_levels::<H>(events);
+    levels
+        .last()
+        .and_then(|level| level.first())
+        .copied()
+        .unwrap_or(EMPTY_EVENTS_ROOT)
+}
+
+/// Returns a proof that the event at `index` is included in the events tree over `events`,
+/// or `None` if there is no such event.
+pub fn events_inclusion_proof<H: Digest<OutputSize = U32>>(
+    events: &[Event],
+    index: usize,
+) -> Option<EventInclusionProof> {
+    if index >= events.len() {
+        return None;
+    }
+
+    let levels = tree_levels::<H>(events);
+    let mut siblings = Vec::new();
+    let mut position = index;
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(position ^ 1) {
+            siblings.push(*sibling);
+        }
+        position /= 2;
+    }
+
+    Some(EventInclusionProof {
+        index: index as u64,
+        leaf_count: events.len() as u64,
+        siblings,
+    })
+}
+
+/// Returns all the levels of the tree, from the leaves up to the root.
+fn tree_levels<'a, H: Digest<OutputSize = U32>>(
+    events: impl IntoIterator<Item = &'a Event>,
+) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![events.into_iter().map(leaf_hash::<H>).collect::<Vec<_>>()];
+    while levels.last().map_or(false, |level| level.len() > 1) {
+        let next = levels
+            .last()
+            .expect("The levels are never empty")
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash::<H>(left, right),
+                [single] => *single,
+                _ => unreachable!("Chunks have one or two nodes"),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn leaf_hash<H: Digest<OutputSize = U32>>(event: &Event) -> [u8; 32] {
+    let mut hasher = H::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(
+        event
+            .try_to_vec()
+            .expect("Serializing an event to a vector can't fail"),
+    );
+    hasher.finalize().into()
+}
+
+fn node_hash<H: Digest<OutputSize = U32>>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = H::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
@@ -5,7 +5,9 @@ pub mod cli;
 mod containers;
 pub mod default_context;
 pub mod default_signature;
+pub mod events;
 pub mod hooks;
+pub mod multi_signature;
 mod pub_key_hex;
 
 #[cfg(feature = "macros")]
@@ -13,10 +15,13 @@ mod reexport_macros;
 #[cfg(feature = "macros")]
 pub use reexport_macros::*;
 
+#[cfg(feature = "native")]
+pub mod schema;
 mod serde_pub_key;
 #[cfg(test)]
 mod tests;
 pub mod transaction;
+pub mod tx_middleware;
 #[cfg(feature = "native")]
 pub mod utils;
 
@@ -32,14 +37,16 @@ pub use clap;
 #[cfg(feature = "native")]
 pub use sov_modules_core::PrivateKey;
 pub use sov_modules_core::{
-    archival_state, runtime, AccessoryWorkingSet, Address, AddressBech32, CallResponse, Context,
-    DispatchCall, EncodeCall, GasUnit, Genesis, KernelModule, KernelWorkingSet, Module,
-    ModuleCallJsonSchema, ModuleError, ModuleError as Error, ModuleInfo, ModulePrefix, PublicKey,
-    Signature, Spec, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet, WorkingSet,
+    address_prefix, archival_state, runtime, set_address_prefix, AccessoryWorkingSet, Address,
+    AddressBech32, CallResponse, Context, DispatchCall, EncodeCall, GasUnit, Genesis, KernelModule,
+    KernelWorkingSet, Migration, Module, ModuleCallJsonSchema, ModuleError, ModuleError as Error,
+    ModuleEvent, ModuleInfo, ModulePrefix, PublicKey, Savepoint, Signature, Spec,
+    StateAccessGasCosts, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet, WorkingSet,
+    MAX_MODULE_CALL_DEPTH,
 };
 pub use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 pub use sov_rollup_interface::services::da::SlotData;
-pub use sov_rollup_interface::stf::Event;
+pub use sov_rollup_interface::stf::{Event, EventAttribute};
 pub use sov_rollup_interface::zk::{
     StateTransition, ValidityCondition, ValidityConditionChecker, Zkvm,
 };
@@ -0,0 +1,437 @@
+//! Public keys and signatures of several signature schemes, selected per transaction.
+//!
+//! A [`MultiPublicKey`] and a [`MultiSignature`] start with the tag of their scheme: `0` for ed25519, `1` for
+//! secp256k1 and `2` for WebAuthn. A signature only verifies against a public key of the same scheme. This lets
+//! the users of a rollup sign transactions with the keys they already hold: ed25519 keys of the default
+//! signature scheme, the secp256k1 keys of Ethereum wallets (which sign with `personal_sign`) or passkeys.
+#[cfg(feature = "native")]
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_modules_core::{PublicKey, SigVerificationError, Signature};
+use sov_rollup_interface::RollupAddress;
+
+use crate::default_signature::{DefaultPublicKey, DefaultSignature};
+
+pub mod secp256k1;
+pub mod webauthn;
+
+pub use secp256k1::{Secp256k1PublicKey, Secp256k1Signature};
+pub use webauthn::{WebAuthnPublicKey, WebAuthnSignature};
+
+pub(crate) const ED25519_TAG: u8 = 0;
+pub(crate) const SECP256K1_TAG: u8 = 1;
+pub(crate) const WEBAUTHN_TAG: u8 = 2;
+
+#[cfg(feature = "native")]
+pub mod private_key {
+    use sov_modules_core::PrivateKey;
+
+    pub use super::secp256k1::private_key::Secp256k1PrivateKey;
+    use super::{MultiPublicKey, MultiSignature};
+    use crate::default_signature::private_key::DefaultPrivateKey;
+
+    /// A private key of one of the schemes which can sign natively. Passkeys sign in their authenticator.
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub enum MultiPrivateKey {
+        /// An ed25519 private key.
+        Ed25519(DefaultPrivateKey),
+        /// A secp256k1 private key.
+        Secp256k1(Secp256k1PrivateKey),
+    }
+
+    impl From<DefaultPrivateKey> for MultiPrivateKey {
+        fn from(key: DefaultPrivateKey) -> Self {
+            Self::Ed25519(key)
+        }
+    }
+
+    impl From<Secp256k1PrivateKey> for MultiPrivateKey {
+        fn from(key: Secp256k1PrivateKey) -> Self {
+            Self::Secp256k1(key)
+        }
+    }
+
+    /// Expects the tag of the scheme, followed by the private key.
+    impl TryFrom<&[u8]> for MultiPrivateKey {
+        type Error = anyhow::Error;
+
+        fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+            match value.split_first() {
+                Some((&super::ED25519_TAG, key)) => {
+                    Ok(Self::Ed25519(DefaultPrivateKey::try_from(key)?))
+                }
+                Some((&super::SECP256K1_TAG, key)) => {
+                    Ok(Self::Secp256k1(Secp256k1PrivateKey::try_from(key)?))
+                }
+                _ => anyhow::bail!("Unknown or missing signature scheme tag"),
+            }
+        }
+    }
+
+    impl PrivateKey for MultiPrivateKey {
+        type PublicKey = MultiPublicKey;
+
+        type Signature = MultiSignature;
+
+        /// Generates an ed25519 key.
+        fn generate() -> Self {
+            Self::Ed25519(DefaultPrivateKey::generate())
+        }
+
+        fn pub_key(&self) -> Self::PublicKey {
+            match self {
+                Self::Ed25519(key) => MultiPublicKey::Ed25519(key.pub_key()),
+                Self::Secp256k1(key) => MultiPublicKey::Secp256k1(key.pub_key()),
+            }
+        }
+
+        fn sign(&self, msg: &[u8]) -> Self::Signature {
+            match self {
+                Self::Ed25519(key) => MultiSignature::Ed25519(key.sign(msg)),
+                Self::Secp256k1(key) => MultiSignature::Secp256k1(key.sign(msg)),
+            }
+        }
+    }
+}
+
+/// A public key of one of the supported signature schemes.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    Clone,
+    Debug,
+)]
+pub enum MultiPublicKey {
+    /// An ed25519 public key, with the same address as in the default signature scheme.
+    Ed25519(DefaultPublicKey),
+    /// A secp256k1 public key.
+    Secp256k1(Secp256k1PublicKey),
+    /// The P-256 public key of a passkey.
+    WebAuthn(WebAuthnPublicKey),
+}
+
+/// Expects the tag of the scheme, followed by the public key.
+impl TryFrom<&[u8]> for MultiPublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::try_from_slice(value)?)
+    }
+}
+
+impl PublicKey for MultiPublicKey {
+    fn to_address<A: RollupAddress>(&self) -> A {
+        match self {
+            Self::Ed25519(pub_key) => pub_key.to_address(),
+            Self::Secp256k1(pub_key) => pub_key.to_address(),
+            Self::WebAuthn(pub_key) => pub_key.to_address(),
+        }
+    }
+}
+
+/// A signature of one of the supported signature schemes.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Eq, Debug, Clone)]
+pub enum MultiSignature {
+    /// An ed25519 signature of the message.
+    Ed25519(DefaultSignature),
+    /// A secp256k1 signature of the EIP-191 hash of the message.
+    Secp256k1(Secp256k1Signature),
+    /// A WebAuthn assertion over the message.
+    WebAuthn(WebAuthnSignature),
+}
+
+/// Expects the tag of the scheme, followed by the signature.
+impl TryFrom<&[u8]> for MultiSignature {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::try_from_slice(value)?)
+    }
+}
+
+impl Signature for MultiSignature {
+    type PublicKey = MultiPublicKey;
+
+    fn verify(&self, pub_key: &Self::PublicKey, msg: &[u8]) -> Result<(), SigVerificationError> {
+        match (self, pub_key) {
+            (Self::Ed25519(signature), MultiPublicKey::Ed25519(pub_key)) => {
+                signature.verify(pub_key, msg)
+            }
+            (Self::Secp256k1(signature), MultiPublicKey::Secp256k1(pub_key)) => {
+                signature.verify(pub_key, msg)
+            }
+            (Self::WebAuthn(signature), MultiPublicKey::WebAuthn(pub_key)) => {
+                signature.verify(pub_key, msg)
+            }
+            _ => Err(SigVerificationError::BadSignature(
+                "The signature scheme doesn't match the scheme of the public key".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl FromStr for MultiPublicKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
+
+#[cfg(feature = "native")]
+impl FromStr for MultiSignature {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::SigningKey;
+    use rand::rngs::OsRng;
+    use sha2::Digest;
+    use sov_modules_core::PrivateKey;
+
+    use super::private_key::{MultiPrivateKey, Secp256k1PrivateKey};
+    use super::webauthn::{base64url, WEBAUTHN_ORIGIN, WEBAUTHN_RP_ID};
+    use super::*;
+    use crate::default_signature::private_key::DefaultPrivateKey;
+    use crate::Address;
+
+    const MSG: &[u8] = b"runtime message";
+    const USER_PRESENT_AND_VERIFIED: u8 = 0x05;
+
+    fn authenticator_data(rp_id: &str, flags: u8) -> Vec<u8> {
+        let mut authenticator_data = sha2::Sha256::digest(rp_id).to_vec();
+        authenticator_data.extend_from_slice(&[flags, 0, 0, 0, 0]);
+        authenticator_data
+    }
+
+    fn client_data_json(tx_type: &str, msg: &[u8], origin: &str) -> Vec<u8> {
+        format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"{}","crossOrigin":false}}"#,
+            tx_type,
+            base64url(&sha2::Sha256::digest(msg)),
+            origin
+        )
+        .into_bytes()
+    }
+
+    /// Signs an assertion with a low S, as wallets do.
+    fn sign_assertion(
+        key: &SigningKey,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> WebAuthnSignature {
+        let mut signed = authenticator_data.clone();
+        signed.extend_from_slice(&sha2::Sha256::digest(&client_data_json));
+        let signature: p256::ecdsa::Signature = key.sign(&signed);
+        let signature = signature.normalize_s().unwrap_or(signature);
+        WebAuthnSignature {
+            authenticator_data,
+            client_data_json,
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    fn webauthn_signature(
+        key: &SigningKey,
+        msg: &[u8],
+        tx_type: &str,
+        flags: u8,
+    ) -> WebAuthnSignature {
+        sign_assertion(
+            key,
+            authenticator_data(WEBAUTHN_RP_ID, flags),
+            client_data_json(tx_type, msg, WEBAUTHN_ORIGIN),
+        )
+    }
+
+    fn webauthn_key() -> (SigningKey, MultiPublicKey) {
+        let key = SigningKey::random(&mut OsRng);
+        let pub_key = MultiPublicKey::WebAuthn(
+            WebAuthnPublicKey::try_from(key.verifying_key().to_encoded_point(true).as_bytes())
+                .unwrap(),
+        );
+        (key, pub_key)
+    }
+
+    #[test]
+    fn test_native_schemes() {
+        for key in [
+            MultiPrivateKey::from(DefaultPrivateKey::generate()),
+            MultiPrivateKey::from(Secp256k1PrivateKey::generate()),
+        ] {
+            let pub_key = key.pub_key();
+            let signature = key.sign(MSG);
+            signature.verify(&pub_key, MSG).unwrap();
+            signature.verify(&pub_key, b"other message").unwrap_err();
+
+            // The keys and signatures round trip through their tagged encodings
+            let serialized = pub_key.try_to_vec().unwrap();
+            assert_eq!(MultiPublicKey::try_from(&serialized[..]).unwrap(), pub_key);
+            let serialized = signature.try_to_vec().unwrap();
+            assert_eq!(
+                MultiSignature::try_from(&serialized[..]).unwrap(),
+                signature
+            );
+            let json = serde_json::to_string(&key).unwrap();
+            let deserialized: MultiPrivateKey = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.pub_key(), pub_key);
+        }
+    }
+
+    #[test]
+    fn test_ed25519_address_is_unchanged() {
+        let key = DefaultPrivateKey::generate();
+        let address: Address = MultiPrivateKey::from(key.clone()).to_address();
+        assert_eq!(address, key.default_address());
+    }
+
+    #[test]
+    fn test_schemes_dont_mix() {
+        let ed25519 = MultiPrivateKey::from(DefaultPrivateKey::generate());
+        let secp256k1 = MultiPrivateKey::from(Secp256k1PrivateKey::generate());
+        ed25519
+            .sign(MSG)
+            .verify(&secp256k1.pub_key(), MSG)
+            .unwrap_err();
+        secp256k1
+            .sign(MSG)
+            .verify(&ed25519.pub_key(), MSG)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_secp256k1_accepts_recovery_id() {
+        let key = Secp256k1PrivateKey::generate();
+        let mut bytes = key.sign(MSG).msg_sig.to_bytes().to_vec();
+        bytes.push(27);
+        let signature = Secp256k1Signature::try_from(&bytes[..]).unwrap();
+        signature.verify(&key.pub_key(), MSG).unwrap();
+    }
+
+    #[test]
+    fn test_webauthn() {
+        let (key, pub_key) = webauthn_key();
+
+        MultiSignature::WebAuthn(webauthn_signature(
+            &key,
+            MSG,
+            "webauthn.get",
+            USER_PRESENT_AND_VERIFIED,
+        ))
+        .verify(&pub_key, MSG)
+        .unwrap();
+        MultiSignature::WebAuthn(webauthn_signature(
+            &key,
+            MSG,
+            "webauthn.get",
+            USER_PRESENT_AND_VERIFIED,
+        ))
+        .verify(&pub_key, b"other message")
+        .expect_err("The challenge doesn't match");
+        MultiSignature::WebAuthn(webauthn_signature(
+            &key,
+            MSG,
+            "webauthn.create",
+            USER_PRESENT_AND_VERIFIED,
+        ))
+        .verify(&pub_key, MSG)
+        .expect_err("A credential creation isn't an assertion");
+
+        MultiSignature::WebAuthn(webauthn_signature(&key, MSG, "webauthn.get", 0))
+            .verify(&pub_key, MSG)
+            .expect_err("The user must be present");
+    }
+
+    #[test]
+    fn test_webauthn_rejects_other_relying_parties() {
+        let (key, pub_key) = webauthn_key();
+
+        MultiSignature::WebAuthn(sign_assertion(
+            &key,
+            authenticator_data("attacker.example", USER_PRESENT_AND_VERIFIED),
+            client_data_json("webauthn.get", MSG, WEBAUTHN_ORIGIN),
+        ))
+        .verify(&pub_key, MSG)
+        .expect_err("The relying party id doesn't match");
+        MultiSignature::WebAuthn(sign_assertion(
+            &key,
+            authenticator_data(WEBAUTHN_RP_ID, USER_PRESENT_AND_VERIFIED),
+            client_data_json("webauthn.get", MSG, "https://attacker.example"),
+        ))
+        .verify(&pub_key, MSG)
+        .expect_err("The origin doesn't match");
+    }
+
+    #[test]
+    fn test_webauthn_parses_client_data() {
+        let (key, pub_key) = webauthn_key();
+        let challenge = base64url(&sha2::Sha256::digest(MSG));
+
+        // The expected members are present, but the client data isn't a valid assertion.
+        for client_data_json in [
+            format!(
+                r#""type":"webauthn.get","challenge":"{}","origin":"{}""#,
+                challenge, WEBAUTHN_ORIGIN
+            ),
+            format!(
+                r#"{{"type":"webauthn.create","type":"webauthn.get","challenge":"{}","origin":"{}"}}"#,
+                challenge, WEBAUTHN_ORIGIN
+            ),
+            format!(
+                r#"{{"type":"webauthn.create","challenge":"{}","origin":"{}","other":{{"type":"webauthn.get"}}}}"#,
+                challenge, WEBAUTHN_ORIGIN
+            ),
+        ] {
+            MultiSignature::WebAuthn(sign_assertion(
+                &key,
+                authenticator_data(WEBAUTHN_RP_ID, USER_PRESENT_AND_VERIFIED),
+                client_data_json.into_bytes(),
+            ))
+            .verify(&pub_key, MSG)
+            .expect_err("The client data isn't a valid assertion");
+        }
+    }
+
+    #[test]
+    fn test_webauthn_rejects_high_s() {
+        let (key, pub_key) = webauthn_key();
+        let signature = webauthn_signature(&key, MSG, "webauthn.get", USER_PRESENT_AND_VERIFIED);
+
+        let (r, s) = p256::ecdsa::Signature::from_slice(&signature.signature)
+            .unwrap()
+            .split_scalars();
+        let high_s = p256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+        MultiSignature::WebAuthn(WebAuthnSignature {
+            signature: high_s.to_bytes().to_vec(),
+            ..signature.clone()
+        })
+        .verify(&pub_key, MSG)
+        .expect_err("Only the low S form is accepted");
+        MultiSignature::WebAuthn(signature)
+            .verify(&pub_key, MSG)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(base64url(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64url(b"fooba"), "Zm9vYmE");
+        assert_eq!(base64url(&[0xfb, 0xff]), "-_8");
+    }
+}
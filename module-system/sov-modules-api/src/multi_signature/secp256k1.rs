@@ -0,0 +1,278 @@
+//! ECDSA signatures over secp256k1, as produced by the `personal_sign` method of Ethereum wallets.
+
+use std::hash::Hash;
+#[cfg(feature = "native")]
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{Signature as K256Signature, VerifyingKey};
+use sha2::Digest;
+use sov_modules_core::{PublicKey, SigVerificationError, Signature};
+use sov_rollup_interface::RollupAddress;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The length of a compressed secp256k1 public key.
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+
+/// The length of a secp256k1 signature, without its recovery id.
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+
+/// Returns the hash signed by Ethereum wallets for `msg`, as defined by EIP-191.
+pub fn eip191_hash(msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(b"\x19Ethereum Signed Message:\n");
+    hasher.update(msg.len().to_string().as_bytes());
+    hasher.update(msg);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+#[cfg(feature = "native")]
+pub mod private_key {
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use k256::ecdsa::SigningKey;
+    use rand::rngs::OsRng;
+    use sov_modules_core::PrivateKey;
+
+    use super::{eip191_hash, Secp256k1PublicKey, Secp256k1Signature};
+
+    /// A secp256k1 private key, such as the key of an Ethereum account.
+    #[derive(Clone)]
+    pub struct Secp256k1PrivateKey {
+        key: SigningKey,
+    }
+
+    impl core::fmt::Debug for Secp256k1PrivateKey {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Secp256k1PrivateKey")
+                .field("public_key", self.key.verifying_key())
+                .field("private_key", &"***REDACTED***")
+                .finish()
+        }
+    }
+
+    impl TryFrom<&[u8]> for Secp256k1PrivateKey {
+        type Error = anyhow::Error;
+
+        fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+            Ok(Self {
+                key: SigningKey::from_slice(value).map_err(anyhow::Error::msg)?,
+            })
+        }
+    }
+
+    impl serde::Serialize for Secp256k1PrivateKey {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.as_hex())
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Secp256k1PrivateKey {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let hex: String = serde::Deserialize::deserialize(deserializer)?;
+            Self::from_hex(&hex).map_err(serde::de::Error::custom)
+        }
+    }
+
+    impl PrivateKey for Secp256k1PrivateKey {
+        type PublicKey = Secp256k1PublicKey;
+
+        type Signature = Secp256k1Signature;
+
+        fn generate() -> Self {
+            Self {
+                key: SigningKey::random(&mut OsRng),
+            }
+        }
+
+        fn pub_key(&self) -> Self::PublicKey {
+            Secp256k1PublicKey {
+                pub_key: *self.key.verifying_key(),
+            }
+        }
+
+        fn sign(&self, msg: &[u8]) -> Self::Signature {
+            Secp256k1Signature {
+                msg_sig: self
+                    .key
+                    .sign_prehash(&eip191_hash(msg))
+                    .expect("Signing a 32 bytes hash can't fail"),
+            }
+        }
+    }
+
+    impl Secp256k1PrivateKey {
+        pub fn as_hex(&self) -> String {
+            hex::encode(self.key.to_bytes())
+        }
+
+        pub fn from_hex(hex: &str) -> anyhow::Result<Self> {
+            let bytes = hex::decode(hex)?;
+            Self::try_from(&bytes[..])
+        }
+    }
+}
+
+/// A secp256k1 public key, such as the key of an Ethereum account.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Secp256k1PublicKey {
+    #[cfg_attr(
+        feature = "native",
+        schemars(with = "&[u8]", length(equal = "SECP256K1_PUBLIC_KEY_LENGTH"))
+    )]
+    pub(crate) pub_key: VerifyingKey,
+}
+
+impl Secp256k1PublicKey {
+    /// Returns the compressed SEC1 encoding of the key.
+    pub fn to_bytes(&self) -> [u8; SECP256K1_PUBLIC_KEY_LENGTH] {
+        let mut bytes = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.pub_key.to_encoded_point(true).as_bytes());
+        bytes
+    }
+}
+
+impl Hash for Secp256k1PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state);
+    }
+}
+
+impl BorshDeserialize for Secp256k1PublicKey {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buffer = [0; SECP256K1_PUBLIC_KEY_LENGTH];
+        reader.read_exact(&mut buffer)?;
+        Self::try_from(&buffer[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl BorshSerialize for Secp256k1PublicKey {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// Accepts compressed and uncompressed SEC1 encodings.
+impl TryFrom<&[u8]> for Secp256k1PublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pub_key: VerifyingKey::from_sec1_bytes(value).map_err(anyhow::Error::msg)?,
+        })
+    }
+}
+
+impl serde::Serialize for Secp256k1PublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Secp256k1PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex: String = serde::Deserialize::deserialize(deserializer)?;
+        let bytes = hex::decode(hex).map_err(serde::de::Error::custom)?;
+        Self::try_from(&bytes[..]).map_err(serde::de::Error::custom)
+    }
+}
+
+impl PublicKey for Secp256k1PublicKey {
+    fn to_address<A: RollupAddress>(&self) -> A {
+        let pub_key_hash: [u8; 32] = sha2::Sha256::new()
+            .chain_update([super::SECP256K1_TAG])
+            .chain_update(self.to_bytes())
+            .finalize()
+            .into();
+        A::from(pub_key_hash)
+    }
+}
+
+/// A secp256k1 signature of the EIP-191 hash of a message.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Secp256k1Signature {
+    #[cfg_attr(
+        feature = "native",
+        schemars(with = "&[u8]", length(equal = "SECP256K1_SIGNATURE_LENGTH"))
+    )]
+    pub msg_sig: K256Signature,
+}
+
+impl BorshDeserialize for Secp256k1Signature {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buffer = [0; SECP256K1_SIGNATURE_LENGTH];
+        reader.read_exact(&mut buffer)?;
+        Self::try_from(&buffer[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl BorshSerialize for Secp256k1Signature {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.msg_sig.to_bytes())
+    }
+}
+
+/// Accepts the `r || s` encoding of a signature, optionally followed by the recovery id added by Ethereum wallets.
+impl TryFrom<&[u8]> for Secp256k1Signature {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let value = match value.len() {
+            SECP256K1_SIGNATURE_LENGTH => value,
+            len if len == SECP256K1_SIGNATURE_LENGTH + 1 => &value[..SECP256K1_SIGNATURE_LENGTH],
+            len => anyhow::bail!("Unexpected secp256k1 signature length {}", len),
+        };
+        Ok(Self {
+            msg_sig: K256Signature::from_slice(value).map_err(anyhow::Error::msg)?,
+        })
+    }
+}
+
+impl serde::Serialize for Secp256k1Signature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.msg_sig.to_bytes()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Secp256k1Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex: String = serde::Deserialize::deserialize(deserializer)?;
+        let bytes = hex::decode(hex).map_err(serde::de::Error::custom)?;
+        Self::try_from(&bytes[..]).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Signature for Secp256k1Signature {
+    type PublicKey = Secp256k1PublicKey;
+
+    fn verify(&self, pub_key: &Self::PublicKey, msg: &[u8]) -> Result<(), SigVerificationError> {
+        pub_key
+            .pub_key
+            .verify_prehash(&eip191_hash(msg), &self.msg_sig)
+            .map_err(|e| SigVerificationError::BadSignature(e.to_string()))
+    }
+}
+
+#[cfg(feature = "native")]
+impl FromStr for Secp256k1PublicKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
+
+#[cfg(feature = "native")]
+impl FromStr for Secp256k1Signature {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
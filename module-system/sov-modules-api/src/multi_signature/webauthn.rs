@@ -0,0 +1,232 @@
+//! Passkey signatures, produced by WebAuthn authenticators with P-256 keys.
+
+use std::hash::Hash;
+#[cfg(feature = "native")]
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature as P256Signature, VerifyingKey};
+use sha2::Digest;
+use sov_modules_core::{PublicKey, SigVerificationError, Signature};
+use sov_modules_macros::config_constant;
+use sov_rollup_interface::RollupAddress;
+
+/// The length of a compressed P-256 public key.
+pub const WEBAUTHN_PUBLIC_KEY_LENGTH: usize = 33;
+
+/// The relying party id of the passkeys signing the transactions of the rollup, i.e. the domain of its wallet.
+#[config_constant]
+pub const WEBAUTHN_RP_ID: &str;
+
+/// The origin of the wallet requesting the assertions, as written in the client data by the browser.
+#[config_constant]
+pub const WEBAUTHN_ORIGIN: &str;
+
+/// The offset of the flags in the authenticator data, after the hash of the relying party id.
+const AUTHENTICATOR_FLAGS_OFFSET: usize = 32;
+
+/// The flag set by the authenticator when the user was present.
+const USER_PRESENT: u8 = 0x01;
+
+/// The public key of a passkey.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct WebAuthnPublicKey {
+    #[cfg_attr(
+        feature = "native",
+        schemars(with = "&[u8]", length(equal = "WEBAUTHN_PUBLIC_KEY_LENGTH"))
+    )]
+    pub(crate) pub_key: VerifyingKey,
+}
+
+impl WebAuthnPublicKey {
+    /// Returns the compressed SEC1 encoding of the key.
+    pub fn to_bytes(&self) -> [u8; WEBAUTHN_PUBLIC_KEY_LENGTH] {
+        let mut bytes = [0u8; WEBAUTHN_PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.pub_key.to_encoded_point(true).as_bytes());
+        bytes
+    }
+}
+
+impl Hash for WebAuthnPublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state);
+    }
+}
+
+impl BorshDeserialize for WebAuthnPublicKey {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buffer = [0; WEBAUTHN_PUBLIC_KEY_LENGTH];
+        reader.read_exact(&mut buffer)?;
+        Self::try_from(&buffer[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl BorshSerialize for WebAuthnPublicKey {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// Accepts compressed and uncompressed SEC1 encodings.
+impl TryFrom<&[u8]> for WebAuthnPublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pub_key: VerifyingKey::from_sec1_bytes(value).map_err(anyhow::Error::msg)?,
+        })
+    }
+}
+
+impl serde::Serialize for WebAuthnPublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WebAuthnPublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex: String = serde::Deserialize::deserialize(deserializer)?;
+        let bytes = hex::decode(hex).map_err(serde::de::Error::custom)?;
+        Self::try_from(&bytes[..]).map_err(serde::de::Error::custom)
+    }
+}
+
+impl PublicKey for WebAuthnPublicKey {
+    fn to_address<A: RollupAddress>(&self) -> A {
+        let pub_key_hash: [u8; 32] = sha2::Sha256::new()
+            .chain_update([super::WEBAUTHN_TAG])
+            .chain_update(self.to_bytes())
+            .finalize()
+            .into();
+        A::from(pub_key_hash)
+    }
+}
+
+/// A WebAuthn assertion over a message, for the relying party [`WEBAUTHN_RP_ID`] and the origin [`WEBAUTHN_ORIGIN`].
+/// The challenge of the assertion is the base64url encoding (without padding) of the SHA-256 hash of the message.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Debug,
+    Clone,
+)]
+pub struct WebAuthnSignature {
+    /// The authenticator data returned by the authenticator.
+    #[serde(with = "hex::serde")]
+    pub authenticator_data: Vec<u8>,
+    /// The client data JSON, as serialized by the browser.
+    #[serde(with = "hex::serde")]
+    pub client_data_json: Vec<u8>,
+    /// The `r || s` encoding of the ECDSA signature, with a low `s`. Authenticators return a DER encoding with
+    /// either `s`, which wallets convert and normalize, so that a signature can't be malleated.
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+}
+
+/// The members of the client data checked by the rollup.
+#[derive(serde::Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+    origin: String,
+}
+
+impl TryFrom<&[u8]> for WebAuthnSignature {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::try_from_slice(value)?)
+    }
+}
+
+impl Signature for WebAuthnSignature {
+    type PublicKey = WebAuthnPublicKey;
+
+    fn verify(&self, pub_key: &Self::PublicKey, msg: &[u8]) -> Result<(), SigVerificationError> {
+        let bad_signature = |reason: &str| SigVerificationError::BadSignature(reason.to_string());
+
+        let rp_id_hash = self
+            .authenticator_data
+            .get(..AUTHENTICATOR_FLAGS_OFFSET)
+            .ok_or_else(|| bad_signature("The authenticator data is too short"))?;
+        if rp_id_hash != sha2::Sha256::digest(WEBAUTHN_RP_ID).as_slice() {
+            return Err(bad_signature("The assertion is for another relying party"));
+        }
+        let flags = self
+            .authenticator_data
+            .get(AUTHENTICATOR_FLAGS_OFFSET)
+            .ok_or_else(|| bad_signature("The authenticator data is too short"))?;
+        if flags & USER_PRESENT == 0 {
+            return Err(bad_signature("The user wasn't present"));
+        }
+
+        let client_data: ClientData = serde_json::from_slice(&self.client_data_json)
+            .map_err(|e| SigVerificationError::BadSignature(e.to_string()))?;
+        if client_data.ty != "webauthn.get" {
+            return Err(bad_signature("The client data isn't an assertion"));
+        }
+        if client_data.challenge != base64url(&sha2::Sha256::digest(msg)) {
+            return Err(bad_signature("The challenge doesn't match the message"));
+        }
+        if client_data.origin != WEBAUTHN_ORIGIN {
+            return Err(bad_signature(
+                "The assertion was requested by another origin",
+            ));
+        }
+
+        let signature = P256Signature::from_slice(&self.signature)
+            .map_err(|e| SigVerificationError::BadSignature(e.to_string()))?;
+        if signature.normalize_s().is_some() {
+            return Err(bad_signature("The signature isn't normalized to a low S"));
+        }
+        let mut signed = self.authenticator_data.clone();
+        signed.extend_from_slice(&sha2::Sha256::digest(&self.client_data_json));
+        pub_key
+            .pub_key
+            .verify(&signed, &signature)
+            .map_err(|e| SigVerificationError::BadSignature(e.to_string()))
+    }
+}
+
+/// Encodes `bytes` in base64url, without padding, as in the challenge of the client data.
+pub fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | ((*byte as u32) << (16 - 8 * i))
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+#[cfg(feature = "native")]
+impl FromStr for WebAuthnPublicKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
+
+#[cfg(feature = "native")]
+impl FromStr for WebAuthnSignature {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(&hex::decode(s)?[..])
+    }
+}
@@ -54,6 +54,34 @@ pub use sov_modules_macros::MessageCodec;
 /// ```
 #[cfg(feature = "macros")]
 pub use sov_modules_macros::ModuleCallJsonSchema;
+/// Derives the [`ModuleEvent`](trait.ModuleEvent.html) trait for the underlying `struct`.
+///
+/// The event is named after the `struct` and starts at version `0`, unless specified otherwise
+/// with the `#[event(name = "...", version = N)]` attribute. Fields with the `#[indexed]` attribute
+/// are exposed as indexed attributes of the event.
+///
+/// ## Example
+///
+/// ```
+/// use sov_modules_api::{EventAttribute, ModuleEvent};
+///
+/// #[derive(borsh::BorshSerialize, ModuleEvent)]
+/// #[event(name = "transfer", version = 1)]
+/// struct Transfer {
+///     #[indexed]
+///     from: [u8; 32],
+///     amount: u64,
+/// }
+///
+/// let event = Transfer { from: [1; 32], amount: 100 };
+/// assert_eq!(Transfer::NAME, "transfer");
+/// assert_eq!(
+///     event.indexed_attributes(),
+///     vec![EventAttribute::new("from", vec![1; 32])]
+/// );
+/// ```
+#[cfg(feature = "macros")]
+pub use sov_modules_macros::ModuleEvent;
 /// Derives the [`ModuleInfo`] trait for the underlying `struct`.
 ///
 /// The underlying type must respect the following conditions, or compilation
@@ -130,6 +158,31 @@ pub mod macros {
     /// ```
     #[cfg(feature = "native")]
     pub use sov_modules_macros::CliWallet;
+    /// Implements the [`RuntimeCallSchema`](crate::schema::RuntimeCallSchema) trait for the annotated runtime.
+    /// Every module of the runtime must implement [`ModuleCallJsonSchema`](crate::ModuleCallJsonSchema).
+    ///
+    /// To exclude a module from the schema, use the `#[schema_skip]` attribute. The indexes of the
+    /// other modules are unchanged.
+    ///
+    /// ## Examples
+    /// ```
+    /// use sov_modules_api::{Context, DispatchCall, MessageCodec};
+    /// use sov_modules_api::default_context::DefaultContext;
+    /// use sov_modules_api::macros::RuntimeCallSchema;
+    /// use sov_modules_api::schema::RuntimeSchema;
+    ///
+    /// #[derive(DispatchCall, MessageCodec, RuntimeCallSchema)]
+    /// #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+    /// pub struct Runtime<C: Context> {
+    ///     pub bank: sov_bank::Bank<C>,
+    ///     // ...
+    /// }
+    ///
+    /// let schema = RuntimeSchema::of::<Runtime<DefaultContext>>().unwrap();
+    /// assert_eq!(schema.modules[0].module, "bank");
+    /// ```
+    #[cfg(feature = "native")]
+    pub use sov_modules_macros::RuntimeCallSchema;
     /// Implement [`CliWalletArg`](crate::CliWalletArg) for the annotated struct or enum. Unions are not supported.
     ///
     /// Under the hood, this macro generates a new struct or enum which derives the [`clap::Parser`] trait, and then implements the
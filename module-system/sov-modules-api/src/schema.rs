@@ -0,0 +1,545 @@
+//! Machine-readable schemas of the call messages of a runtime.
+//!
+//! The schema of a runtime describes, for every module, the JSON Schema of its
+//! [`Module::CallMessage`](crate::Module::CallMessage) and its borsh layout, along with the layout of the
+//! runtime call and of the transaction envelope. Wallets, indexers and SDKs in other languages can encode
+//! transactions from the schema alone, without depending on the Rust crates of the rollup.
+//!
+//! The borsh layouts are derived from the JSON Schemas, whose properties are kept in declaration order.
+//! Maps are described with string keys, as in JSON, and enums mixing unit and data variants are rejected,
+//! because their variant order can't be recovered from the JSON Schema.
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context as _};
+use schemars::schema::{
+    InstanceType, ObjectValidation, RootSchema, Schema, SchemaObject, SingleOrVec,
+};
+use serde::{Deserialize, Serialize};
+use sov_modules_core::{DispatchCall, ModuleCallJsonSchema, Spec};
+use sov_rollup_interface::digest::Digest;
+
+/// The borsh encoding of a type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BorshLayout {
+    /// A primitive type: `bool`, an integer like `u64` encoded in little endian, `f32`, `f64`, or
+    /// `string`, encoded as its `u32` length followed by its UTF-8 bytes.
+    Primitive {
+        /// The name of the primitive type.
+        name: String,
+    },
+    /// The unit type, encoded as nothing.
+    Unit,
+    /// An optional value, encoded as a `0` byte or a `1` byte followed by the value.
+    Option {
+        /// The layout of the value.
+        item: Box<BorshLayout>,
+    },
+    /// A sequence of values, encoded as its `u32` length followed by the values.
+    Vec {
+        /// The layout of the values.
+        item: Box<BorshLayout>,
+    },
+    /// A fixed-size sequence of values, encoded as the values.
+    Array {
+        /// The layout of the values.
+        item: Box<BorshLayout>,
+        /// The number of values.
+        len: u32,
+    },
+    /// A tuple, encoded as its items in order.
+    Tuple {
+        /// The layouts of the items.
+        items: Vec<BorshLayout>,
+    },
+    /// A struct, encoded as its fields in order.
+    Struct {
+        /// The fields, in order.
+        fields: Vec<BorshField>,
+    },
+    /// An enum, encoded as the `u8` index of the variant followed by its content.
+    Enum {
+        /// The variants.
+        variants: Vec<BorshVariant>,
+    },
+    /// A map, encoded as its `u32` length followed by its entries ordered by key.
+    Map {
+        /// The layout of the keys.
+        key: Box<BorshLayout>,
+        /// The layout of the values.
+        value: Box<BorshLayout>,
+    },
+    /// A type defined in the definitions of the schema.
+    Ref {
+        /// The name of the definition.
+        name: String,
+    },
+}
+
+/// A field of a [`BorshLayout::Struct`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BorshField {
+    /// The name of the field.
+    pub name: String,
+    /// The layout of the field.
+    pub layout: BorshLayout,
+}
+
+/// A variant of a [`BorshLayout::Enum`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BorshVariant {
+    /// The index encoding the variant.
+    pub index: u8,
+    /// The name of the variant.
+    pub name: String,
+    /// The layout of the content of the variant, [`BorshLayout::Unit`] for unit variants.
+    pub layout: BorshLayout,
+}
+
+/// The borsh layout of a type, and of the types it refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BorshLayoutSchema {
+    /// The layout of the type.
+    pub root: BorshLayout,
+    /// The layouts of the types referred to by [`BorshLayout::Ref`].
+    pub definitions: BTreeMap<String, BorshLayout>,
+}
+
+impl BorshLayoutSchema {
+    /// Derives the borsh layout of the type described by the JSON Schema `schema`.
+    /// The names of the definitions are prefixed with `namespace`, so that the layouts
+    /// of different schemas can be merged.
+    pub fn from_json_schema(schema: &RootSchema, namespace: &str) -> anyhow::Result<Self> {
+        let converter = Converter { namespace };
+        let root = converter.convert_object(&schema.schema)?;
+        let definitions = schema
+            .definitions
+            .iter()
+            .map(|(name, definition)| {
+                let layout = converter
+                    .convert(definition)
+                    .with_context(|| format!("Invalid definition {}", name))?;
+                Ok((converter.qualified(name), layout))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { root, definitions })
+    }
+
+    /// Checks that `bytes` is exactly the borsh encoding of a value of the type.
+    pub fn validate(&self, bytes: &[u8]) -> anyhow::Result<()> {
+        let mut remaining = bytes;
+        self.consume(&self.root, &mut remaining)?;
+        if !remaining.is_empty() {
+            bail!("{} unexpected trailing bytes", remaining.len());
+        }
+        Ok(())
+    }
+
+    fn consume(&self, layout: &BorshLayout, bytes: &mut &[u8]) -> anyhow::Result<()> {
+        match layout {
+            BorshLayout::Primitive { name } => match name.as_str() {
+                "bool" => {
+                    if take(bytes, 1)?[0] > 1 {
+                        bail!("Invalid boolean");
+                    }
+                }
+                "string" => {
+                    let len = take_len(bytes)?;
+                    std::str::from_utf8(take(bytes, len)?).context("Invalid UTF-8 string")?;
+                }
+                other => {
+                    take(bytes, primitive_size(other)?)?;
+                }
+            },
+            BorshLayout::Unit => {}
+            BorshLayout::Option { item } => match take(bytes, 1)?[0] {
+                0 => {}
+                1 => self.consume(item, bytes)?,
+                tag => bail!("Invalid option tag {}", tag),
+            },
+            BorshLayout::Vec { item } => {
+                for _ in 0..take_len(bytes)? {
+                    self.consume(item, bytes)?;
+                }
+            }
+            BorshLayout::Array { item, len } => {
+                for _ in 0..*len {
+                    self.consume(item, bytes)?;
+                }
+            }
+            BorshLayout::Tuple { items } => {
+                for item in items {
+                    self.consume(item, bytes)?;
+                }
+            }
+            BorshLayout::Struct { fields } => {
+                for field in fields {
+                    self.consume(&field.layout, bytes)
+                        .with_context(|| format!("Invalid field {}", field.name))?;
+                }
+            }
+            BorshLayout::Enum { variants } => {
+                let index = take(bytes, 1)?[0];
+                let variant = variants
+                    .iter()
+                    .find(|variant| variant.index == index)
+                    .with_context(|| format!("Unknown variant index {}", index))?;
+                self.consume(&variant.layout, bytes)
+                    .with_context(|| format!("Invalid variant {}", variant.name))?;
+            }
+            BorshLayout::Map { key, value } => {
+                for _ in 0..take_len(bytes)? {
+                    self.consume(key, bytes)?;
+                    self.consume(value, bytes)?;
+                }
+            }
+            BorshLayout::Ref { name } => {
+                let definition = self
+                    .definitions
+                    .get(name)
+                    .with_context(|| format!("Missing definition {}", name))?;
+                self.consume(definition, bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    if bytes.len() < len {
+        bail!("Unexpected end of input");
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+fn take_len(bytes: &mut &[u8]) -> anyhow::Result<usize> {
+    let len = u32::from_le_bytes(take(bytes, 4)?.try_into().expect("4 bytes were taken"));
+    Ok(len as usize)
+}
+
+fn primitive_size(name: &str) -> anyhow::Result<usize> {
+    Ok(match name {
+        "u8" | "i8" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" | "f32" => 4,
+        "u64" | "i64" | "f64" => 8,
+        "u128" | "i128" => 16,
+        _ => bail!("Unknown primitive type {}", name),
+    })
+}
+
+fn primitive(name: &str) -> BorshLayout {
+    BorshLayout::Primitive {
+        name: name.to_string(),
+    }
+}
+
+struct Converter<'a> {
+    namespace: &'a str,
+}
+
+impl Converter<'_> {
+    fn qualified(&self, name: &str) -> String {
+        format!("{}::{}", self.namespace, name)
+    }
+
+    fn convert(&self, schema: &Schema) -> anyhow::Result<BorshLayout> {
+        match schema {
+            Schema::Object(object) => self.convert_object(object),
+            Schema::Bool(_) => bail!("Unconstrained schemas have no borsh layout"),
+        }
+    }
+
+    fn convert_object(&self, object: &SchemaObject) -> anyhow::Result<BorshLayout> {
+        if let Some(reference) = &object.reference {
+            let name = reference
+                .strip_prefix("#/definitions/")
+                .with_context(|| format!("Unsupported reference {}", reference))?;
+            return Ok(BorshLayout::Ref {
+                name: self.qualified(name),
+            });
+        }
+
+        if let Some(subschemas) = &object.subschemas {
+            // A described reference is wrapped in `allOf`, and an optional one in `anyOf` with `null`
+            if let Some([schema]) = subschemas.all_of.as_deref() {
+                return self.convert(schema);
+            }
+            if let Some([schema, Schema::Object(null)]) = subschemas.any_of.as_deref() {
+                if null.instance_type == Some(InstanceType::Null.into()) {
+                    return Ok(BorshLayout::Option {
+                        item: Box::new(self.convert(schema)?),
+                    });
+                }
+            }
+            if let Some(variants) = &subschemas.one_of {
+                return self.convert_enum(variants);
+            }
+            bail!("Unsupported combination of schemas");
+        }
+
+        if let Some(values) = &object.enum_values {
+            let variants = values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    Ok(BorshVariant {
+                        index: u8::try_from(index).context("Too many enum variants")?,
+                        name: value
+                            .as_str()
+                            .context("Only string enum values are supported")?
+                            .to_string(),
+                        layout: BorshLayout::Unit,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?;
+            return Ok(BorshLayout::Enum { variants });
+        }
+
+        match &object.instance_type {
+            Some(SingleOrVec::Single(instance_type)) => {
+                self.convert_instance(**instance_type, object)
+            }
+            Some(SingleOrVec::Vec(instance_types)) => match instance_types.as_slice() {
+                [instance_type, InstanceType::Null] | [InstanceType::Null, instance_type] => {
+                    Ok(BorshLayout::Option {
+                        item: Box::new(self.convert_instance(*instance_type, object)?),
+                    })
+                }
+                _ => bail!("Unsupported union of types {:?}", instance_types),
+            },
+            None => bail!("Schemas without a type have no borsh layout"),
+        }
+    }
+
+    fn convert_instance(
+        &self,
+        instance_type: InstanceType,
+        object: &SchemaObject,
+    ) -> anyhow::Result<BorshLayout> {
+        let format = object.format.as_deref();
+        Ok(match instance_type {
+            InstanceType::Null => BorshLayout::Unit,
+            InstanceType::Boolean => primitive("bool"),
+            InstanceType::String => primitive("string"),
+            InstanceType::Integer => primitive(match format {
+                Some("uint8") => "u8",
+                Some("uint16") => "u16",
+                Some("uint32") => "u32",
+                Some("uint64") | Some("uint") => "u64",
+                Some("uint128") => "u128",
+                Some("int8") => "i8",
+                Some("int16") => "i16",
+                Some("int32") => "i32",
+                Some("int64") | Some("int") => "i64",
+                Some("int128") => "i128",
+                _ => bail!("Unsupported integer format {:?}", format),
+            }),
+            InstanceType::Number => primitive(match format {
+                Some("float") => "f32",
+                Some("double") => "f64",
+                _ => bail!("Unsupported number format {:?}", format),
+            }),
+            InstanceType::Array => {
+                let array = object.array.as_ref().context("Array without items")?;
+                match &array.items {
+                    Some(SingleOrVec::Single(item)) => {
+                        let item = Box::new(self.convert(item)?);
+                        match (array.min_items, array.max_items) {
+                            (Some(min), Some(max)) if min == max => {
+                                BorshLayout::Array { item, len: min }
+                            }
+                            _ => BorshLayout::Vec { item },
+                        }
+                    }
+                    Some(SingleOrVec::Vec(items)) => BorshLayout::Tuple {
+                        items: items
+                            .iter()
+                            .map(|item| self.convert(item))
+                            .collect::<anyhow::Result<_>>()?,
+                    },
+                    None => bail!("Array without items"),
+                }
+            }
+            InstanceType::Object => match object.object.as_deref() {
+                Some(ObjectValidation {
+                    properties,
+                    additional_properties: Some(value),
+                    ..
+                }) if properties.is_empty() => BorshLayout::Map {
+                    key: Box::new(primitive("string")),
+                    value: Box::new(self.convert(value)?),
+                },
+                Some(ObjectValidation { properties, .. }) => BorshLayout::Struct {
+                    fields: properties
+                        .iter()
+                        .map(|(name, schema)| {
+                            Ok(BorshField {
+                                name: name.clone(),
+                                layout: self
+                                    .convert(schema)
+                                    .with_context(|| format!("Invalid field {}", name))?,
+                            })
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                },
+                None => BorshLayout::Struct { fields: Vec::new() },
+            },
+        })
+    }
+
+    /// Converts the variants of an externally tagged enum, each being an object with a single property.
+    fn convert_enum(&self, variants: &[Schema]) -> anyhow::Result<BorshLayout> {
+        let variants = variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let Schema::Object(variant) = variant else {
+                    bail!("Unconstrained enum variants have no borsh layout");
+                };
+                if variant.enum_values.is_some() {
+                    bail!("Enums mixing unit and data variants have no borsh layout");
+                }
+                let (name, content) = match variant.object.as_deref() {
+                    Some(ObjectValidation { properties, .. }) if properties.len() == 1 => {
+                        properties.iter().next().expect("There is one property")
+                    }
+                    _ => bail!("Only externally tagged enums have a borsh layout"),
+                };
+                Ok(BorshVariant {
+                    index: u8::try_from(index).context("Too many enum variants")?,
+                    name: name.clone(),
+                    layout: self
+                        .convert(content)
+                        .with_context(|| format!("Invalid variant {}", name))?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(BorshLayout::Enum { variants })
+    }
+}
+
+/// The schema of the call message of a module of a runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleCallSchema {
+    /// The name of the module in the runtime.
+    pub module: String,
+    /// The index of the module in the runtime call, which prefixes its encoded call messages.
+    pub index: u8,
+    /// The JSON Schema of the call message.
+    pub json_schema: serde_json::Value,
+    /// The borsh layout of the call message. Its definitions are prefixed with the name of the module.
+    pub borsh: BorshLayoutSchema,
+}
+
+impl ModuleCallSchema {
+    /// Builds the schema of the call message of the module `M`, the field `module` of the runtime
+    /// at the position `index`.
+    pub fn new<M: ModuleCallJsonSchema>(module: &str, index: u8) -> anyhow::Result<Self> {
+        let json_schema = M::json_schema();
+        let root: RootSchema = serde_json::from_str(&json_schema)?;
+        let borsh = BorshLayoutSchema::from_json_schema(&root, module).with_context(|| {
+            format!("The call message of module {} has no borsh layout", module)
+        })?;
+        Ok(Self {
+            module: module.to_string(),
+            index,
+            json_schema: serde_json::from_str(&json_schema)?,
+            borsh,
+        })
+    }
+}
+
+/// A runtime exporting the schema of the call messages of its modules.
+/// You can derive it with `#[derive(RuntimeCallSchema)]`, which requires every module of the runtime
+/// to implement [`ModuleCallJsonSchema`], unless it is marked with `#[schema_skip]`.
+pub trait RuntimeCallSchema: DispatchCall {
+    /// Returns the schemas of the call messages of the modules, in the order of the runtime call.
+    fn module_call_schemas() -> anyhow::Result<Vec<ModuleCallSchema>>;
+}
+
+/// The schema of the transactions accepted by a runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSchema {
+    /// The version of the schema: the hex-encoded hash of the borsh layouts of the runtime call and
+    /// of the transaction. It changes whenever the encoding of a transaction changes.
+    pub version: String,
+    /// The schemas of the call messages of the modules.
+    pub modules: Vec<ModuleCallSchema>,
+    /// The borsh layout of the runtime call, which is the message signed in a transaction.
+    pub call: BorshLayoutSchema,
+    /// The borsh layout of a signed [`Transaction`](crate::transaction::Transaction).
+    pub transaction: BorshLayoutSchema,
+}
+
+impl RuntimeSchema {
+    /// Builds the schema of the runtime `RT`.
+    pub fn of<RT: RuntimeCallSchema>() -> anyhow::Result<Self> {
+        let modules = RT::module_call_schemas()?;
+
+        let mut definitions = BTreeMap::new();
+        let mut variants = Vec::new();
+        for module in &modules {
+            definitions.extend(module.borsh.definitions.clone());
+            variants.push(BorshVariant {
+                index: module.index,
+                name: module.module.clone(),
+                layout: module.borsh.root.clone(),
+            });
+        }
+        let call = BorshLayoutSchema {
+            root: BorshLayout::Enum { variants },
+            definitions,
+        };
+        let transaction = transaction_layout::<RT::Context>()?;
+
+        let mut hasher = <RT::Context as Spec>::Hasher::new();
+        hasher.update(serde_json::to_vec(&call)?);
+        hasher.update(serde_json::to_vec(&transaction)?);
+        Ok(Self {
+            version: hex::encode(hasher.finalize()),
+            modules,
+            call,
+            transaction,
+        })
+    }
+}
+
+fn transaction_layout<C: sov_modules_core::Context>() -> anyhow::Result<BorshLayoutSchema> {
+    let signature =
+        BorshLayoutSchema::from_json_schema(&schemars::schema_for!(C::Signature), "signature")?;
+    let pub_key =
+        BorshLayoutSchema::from_json_schema(&schemars::schema_for!(C::PublicKey), "pub_key")?;
+
+    let mut fields = vec![
+        BorshField {
+            name: "signature".to_string(),
+            layout: signature.root,
+        },
+        BorshField {
+            name: "pub_key".to_string(),
+            layout: pub_key.root,
+        },
+        BorshField {
+            name: "runtime_msg".to_string(),
+            layout: BorshLayout::Vec {
+                item: Box::new(primitive("u8")),
+            },
+        },
+    ];
+    for name in ["chain_id", "gas_tip", "gas_limit", "nonce_lane", "nonce"] {
+        fields.push(BorshField {
+            name: name.to_string(),
+            layout: primitive("u64"),
+        });
+    }
+
+    let mut definitions = signature.definitions;
+    definitions.extend(pub_key.definitions);
+    Ok(BorshLayoutSchema {
+        root: BorshLayout::Struct { fields },
+        definitions,
+    })
+}
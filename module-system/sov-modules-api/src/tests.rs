@@ -180,3 +180,136 @@ fn test_default_signature_roundtrip() {
     sig.verify(&key.pub_key(), msg)
         .expect("Roundtrip verification failed");
 }
+
+#[test]
+fn test_events_inclusion_proofs() {
+    use crate::events::{events_inclusion_proof, events_root, EMPTY_EVENTS_ROOT};
+    use crate::Event;
+
+    type H = <DefaultContext as crate::Spec>::Hasher;
+
+    assert_eq!(events_root::<H>(&[]), EMPTY_EVENTS_ROOT);
+    assert!(events_inclusion_proof::<H>(&[], 0).is_none());
+
+    for count in 1..=7 {
+        let events: Vec<Event> = (0..count)
+            .map(|i| Event::new("event", &i.to_string()))
+            .collect();
+        let root = events_root::<H>(&events);
+        for (index, event) in events.iter().enumerate() {
+            let proof = events_inclusion_proof::<H>(&events, index).unwrap();
+            assert!(proof.verify::<H>(event, &root));
+
+            let other = &events[(index + 1) % events.len()];
+            if count > 1 {
+                assert!(!proof.verify::<H>(other, &root));
+            }
+        }
+        assert!(events_inclusion_proof::<H>(&events, events.len()).is_none());
+    }
+}
+
+/// A module counting its calls, which forwards them to the next module of a chain.
+struct Relay {
+    address: Address,
+    next: Option<Box<Relay>>,
+    calls: crate::StateValue<u32>,
+    last_sender: crate::StateValue<Address>,
+}
+
+impl Relay {
+    fn chain(length: u8) -> Relay {
+        let relay = |i: u8, next| Relay {
+            address: Address::from([i; 32]),
+            next,
+            calls: crate::StateValue::new(sov_modules_core::Prefix::new(vec![i, 0])),
+            last_sender: crate::StateValue::new(sov_modules_core::Prefix::new(vec![i, 1])),
+        };
+        (1..length).rev().fold(relay(length, None), |next, i| {
+            relay(i, Some(Box::new(next)))
+        })
+    }
+
+    fn next(&self) -> &Relay {
+        self.next.as_ref().unwrap()
+    }
+}
+
+impl ModuleInfo for Relay {
+    type Context = DefaultContext;
+
+    fn address(&self) -> &<Self::Context as crate::Spec>::Address {
+        &self.address
+    }
+
+    fn prefix(&self) -> crate::ModulePrefix {
+        crate::ModulePrefix::new_module(module_path!(), "Relay")
+    }
+
+    fn dependencies(&self) -> Vec<&<Self::Context as crate::Spec>::Address> {
+        self.next.iter().map(|next| &next.address).collect()
+    }
+}
+
+impl crate::Module for Relay {
+    type Context = DefaultContext;
+    type Config = ();
+    /// Whether the last module of the chain calls itself.
+    type CallMessage = bool;
+    type Event = ();
+
+    fn call(
+        &self,
+        reenter: bool,
+        context: &DefaultContext,
+        working_set: &mut crate::WorkingSet<DefaultContext>,
+    ) -> Result<crate::CallResponse, crate::Error> {
+        use crate::prelude::StateValueAccessor;
+        use crate::Context;
+
+        let calls = self.calls.get(working_set).unwrap_or_default();
+        self.calls.set(&(calls + 1), working_set);
+        self.last_sender.set(context.sender(), working_set);
+        match &self.next {
+            Some(next) => working_set.call_module(self, next.as_ref(), reenter, context),
+            None if reenter => working_set.call_module(self, self, reenter, context),
+            None => Ok(crate::CallResponse::default()),
+        }
+    }
+}
+
+#[test]
+fn test_module_calls() {
+    use crate::prelude::StateValueAccessor;
+    use crate::{Context, Module, WorkingSet, MAX_MODULE_CALL_DEPTH};
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = sov_prover_storage_manager::new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage);
+    let sender = Address::from([0; 32]);
+    let context = DefaultContext::new(sender, sender, 1);
+
+    // The called modules see their caller as the sender
+    let relays = Relay::chain(3);
+    relays.call(false, &context, &mut working_set).unwrap();
+    let last = relays.next().next();
+    assert_eq!(last.calls.get(&mut working_set), Some(1));
+    assert_eq!(
+        last.last_sender.get(&mut working_set),
+        Some(relays.next().address)
+    );
+
+    // A reentrant call fails, and the changes of the failed calls are rolled back
+    assert!(relays.call(true, &context, &mut working_set).is_err());
+    assert_eq!(last.calls.get(&mut working_set), Some(1));
+    assert_eq!(relays.next().calls.get(&mut working_set), Some(1));
+
+    // The first module of the chain isn't called by another module
+    let depth = MAX_MODULE_CALL_DEPTH as u8;
+    assert!(Relay::chain(depth + 1)
+        .call(false, &context, &mut working_set)
+        .is_ok());
+    assert!(Relay::chain(depth + 2)
+        .call(false, &context, &mut working_set)
+        .is_err());
+}
@@ -8,7 +8,7 @@ use sov_modules_macros::config_constant;
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 
-const EXTEND_MESSAGE_LEN: usize = 4 * core::mem::size_of::<u64>();
+const EXTEND_MESSAGE_LEN: usize = 5 * core::mem::size_of::<u64>();
 
 /// A Transaction object that is compatible with the module-system/sov-default-stf.
 #[derive(
@@ -21,7 +21,33 @@ pub struct Transaction<C: Context> {
     chain_id: u64,
     gas_tip: u64,
     gas_limit: u64,
+    nonce_lane: u64,
     nonce: u64,
+    access_list: Option<AccessList>,
+    valid_until_slot: Option<u64>,
+}
+
+/// The keys of the provable state accessed by a transaction, declared by its sender. The STF can execute
+/// consecutive transactions with non-conflicting access lists in parallel.
+///
+/// The keys are the raw bytes of [`StorageKey`](sov_modules_core::StorageKey)s. A transaction accessing
+/// a key missing from its access list is still executed correctly, but sequentially.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Default,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct AccessList {
+    /// The keys only read by the transaction.
+    pub reads: Vec<Vec<u8>>,
+    /// The keys written or deleted by the transaction, which it may also read.
+    pub writes: Vec<Vec<u8>>,
 }
 
 /// An unsent transaction with the required data to be submitted to the DA layer
@@ -58,6 +84,12 @@ impl<C: Context> Transaction<C> {
         self.nonce
     }
 
+    /// The nonce lane of the transaction. Each lane of an account has its own sequence of nonces,
+    /// so transactions sent in different lanes don't block each other.
+    pub const fn nonce_lane(&self) -> u64 {
+        self.nonce_lane
+    }
+
     pub const fn chain_id(&self) -> u64 {
         self.chain_id
     }
@@ -70,6 +102,17 @@ impl<C: Context> Transaction<C> {
         self.gas_limit
     }
 
+    /// The state the transaction declares it accesses, if any.
+    pub fn access_list(&self) -> Option<&AccessList> {
+        self.access_list.as_ref()
+    }
+
+    /// The last slot height at which the transaction can be executed, if any. An expired transaction
+    /// is rejected, so it can't be replayed long after it was signed.
+    pub const fn valid_until_slot(&self) -> Option<u64> {
+        self.valid_until_slot
+    }
+
     pub fn gas_fixed_cost(&self) -> C::GasUnit {
         #[config_constant]
         const GAS_TX_FIXED_COST: &[u64];
@@ -89,20 +132,54 @@ impl<C: Context> Transaction<C> {
     /// Check whether the transaction has been signed correctly.
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     pub fn verify(&self) -> anyhow::Result<()> {
-        let mut serialized_tx = Vec::with_capacity(self.runtime_msg().len() + EXTEND_MESSAGE_LEN);
-
-        serialized_tx.extend_from_slice(self.runtime_msg());
-        serialized_tx.extend_from_slice(&self.chain_id().to_le_bytes());
-        serialized_tx.extend_from_slice(&self.gas_tip().to_le_bytes());
-        serialized_tx.extend_from_slice(&self.gas_limit().to_le_bytes());
-        serialized_tx.extend_from_slice(&self.nonce().to_le_bytes());
+        let serialized_tx = Self::signing_bytes(
+            self.runtime_msg(),
+            self.chain_id(),
+            self.gas_tip(),
+            self.gas_limit(),
+            self.nonce_lane(),
+            self.nonce(),
+            self.access_list(),
+            self.valid_until_slot(),
+        );
 
         self.signature().verify(&self.pub_key, &serialized_tx)?;
 
         Ok(())
     }
 
+    /// Returns the bytes signed by the sender of a transaction with the given fields.
+    /// External signers, like hardware wallets, sign these bytes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn signing_bytes(
+        runtime_msg: &[u8],
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+        access_list: Option<&AccessList>,
+        valid_until_slot: Option<u64>,
+    ) -> Vec<u8> {
+        let mut serialized_tx = Vec::with_capacity(runtime_msg.len() + EXTEND_MESSAGE_LEN);
+
+        serialized_tx.extend_from_slice(runtime_msg);
+        serialized_tx.extend_from_slice(&chain_id.to_le_bytes());
+        serialized_tx.extend_from_slice(&gas_tip.to_le_bytes());
+        serialized_tx.extend_from_slice(&gas_limit.to_le_bytes());
+        serialized_tx.extend_from_slice(&nonce_lane.to_le_bytes());
+        serialized_tx.extend_from_slice(&nonce.to_le_bytes());
+        access_list
+            .serialize(&mut serialized_tx)
+            .expect("Serialization to vec is infallible");
+        valid_until_slot
+            .serialize(&mut serialized_tx)
+            .expect("Serialization to vec is infallible");
+        serialized_tx
+    }
+
     /// New transaction.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pub_key: C::PublicKey,
         message: Vec<u8>,
@@ -110,7 +187,10 @@ impl<C: Context> Transaction<C> {
         chain_id: u64,
         gas_tip: u64,
         gas_limit: u64,
+        nonce_lane: u64,
         nonce: u64,
+        access_list: Option<AccessList>,
+        valid_until_slot: Option<u64>,
     ) -> Self {
         Self {
             signature,
@@ -119,24 +199,108 @@ impl<C: Context> Transaction<C> {
             chain_id,
             gas_tip,
             gas_limit,
+            nonce_lane,
             nonce,
+            access_list,
+            valid_until_slot,
         }
     }
 }
 
 #[cfg(feature = "native")]
 impl<C: Context> Transaction<C> {
-    /// New signed transaction.
+    /// New signed transaction, in the default nonce lane `0`.
     pub fn new_signed_tx(
+        priv_key: &C::PrivateKey,
+        message: Vec<u8>,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce: u64,
+    ) -> Self {
+        Self::new_signed_tx_in_lane(priv_key, message, chain_id, gas_tip, gas_limit, 0, nonce)
+    }
+
+    /// New signed transaction, in the nonce lane `nonce_lane`.
+    pub fn new_signed_tx_in_lane(
+        priv_key: &C::PrivateKey,
+        message: Vec<u8>,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+    ) -> Self {
+        Self::new_signed_tx_with_access_list(
+            priv_key, message, chain_id, gas_tip, gas_limit, nonce_lane, nonce, None,
+        )
+    }
+
+    /// New signed transaction, in the nonce lane `nonce_lane`, declaring the state it accesses.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_signed_tx_with_access_list(
+        priv_key: &C::PrivateKey,
+        message: Vec<u8>,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+        access_list: Option<AccessList>,
+    ) -> Self {
+        Self::new_signed_tx_with_options(
+            priv_key,
+            message,
+            chain_id,
+            gas_tip,
+            gas_limit,
+            nonce_lane,
+            nonce,
+            access_list,
+            None,
+        )
+    }
+
+    /// New signed transaction, in the nonce lane `nonce_lane`, which expires after the slot height `valid_until_slot`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_signed_tx_with_expiry(
+        priv_key: &C::PrivateKey,
+        message: Vec<u8>,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce_lane: u64,
+        nonce: u64,
+        valid_until_slot: u64,
+    ) -> Self {
+        Self::new_signed_tx_with_options(
+            priv_key,
+            message,
+            chain_id,
+            gas_tip,
+            gas_limit,
+            nonce_lane,
+            nonce,
+            None,
+            Some(valid_until_slot),
+        )
+    }
+
+    /// New signed transaction, with all the optional fields of the envelope.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_signed_tx_with_options(
         priv_key: &C::PrivateKey,
         mut message: Vec<u8>,
         chain_id: u64,
         gas_tip: u64,
         gas_limit: u64,
+        nonce_lane: u64,
         nonce: u64,
+        access_list: Option<AccessList>,
+        valid_until_slot: Option<u64>,
     ) -> Self {
         // Since we own the message already, try to add the serialized nonce in-place.
-        // This lets us avoid a copy if the message vec has at least 8 bytes of extra capacity.
+        // This lets us avoid a copy if the message vec has at least 40 bytes of extra capacity.
         let len = message.len();
 
         // resizes once to avoid potential multiple realloc
@@ -145,7 +309,14 @@ impl<C: Context> Transaction<C> {
         message[len..len + 8].copy_from_slice(&chain_id.to_le_bytes());
         message[len + 8..len + 16].copy_from_slice(&gas_tip.to_le_bytes());
         message[len + 16..len + 24].copy_from_slice(&gas_limit.to_le_bytes());
-        message[len + 24..len + 32].copy_from_slice(&nonce.to_le_bytes());
+        message[len + 24..len + 32].copy_from_slice(&nonce_lane.to_le_bytes());
+        message[len + 32..len + 40].copy_from_slice(&nonce.to_le_bytes());
+        access_list
+            .serialize(&mut message)
+            .expect("Serialization to vec is infallible");
+        valid_until_slot
+            .serialize(&mut message)
+            .expect("Serialization to vec is infallible");
 
         let pub_key = priv_key.pub_key();
         let signature = priv_key.sign(&message);
@@ -160,7 +331,10 @@ impl<C: Context> Transaction<C> {
             chain_id,
             gas_tip,
             gas_limit,
+            nonce_lane,
             nonce,
+            access_list,
+            valid_until_slot,
         }
     }
 }
@@ -0,0 +1,399 @@
+//! An ordered pipeline of transaction middlewares, which implements the [`TxHooks`](crate::hooks::TxHooks)
+//! of a runtime.
+//!
+//! Each middleware enforces one policy: authentication and nonces, fee charging, allow and deny lists,
+//! rate limits... A runtime lists its middlewares in a [`TxMiddlewares`] pipeline, which runs them in order
+//! before and after dispatching each transaction. Adding a policy to a runtime is adding a middleware to its
+//! pipeline.
+
+use anyhow::{bail, ensure};
+use sov_modules_core::{Context, Prefix, PublicKey, WorkingSet};
+
+use crate::transaction::Transaction;
+use crate::{StateMap, StateMapAccessor};
+
+/// The transaction going through a [`TxMiddlewares`] pipeline, as resolved by the middlewares run so far.
+pub struct TxMiddlewareState<C: Context> {
+    /// The public key of the sequencer of the batch.
+    pub sequencer_pub_key: C::PublicKey,
+    /// The slot height at which the transaction is executed.
+    pub height: u64,
+    /// The address of the sender. Derived from the public key of the transaction, unless a middleware
+    /// authenticates the transaction for another account.
+    pub sender: C::Address,
+    /// The address of the sequencer. Derived from its public key, unless a middleware resolves another one.
+    pub sequencer: C::Address,
+    /// The address of the module called by the transaction, if the runtime resolved it with [`TxMiddlewares::calling`].
+    pub module: Option<C::Address>,
+}
+
+/// A step of the transaction hooks of a runtime.
+pub trait TxMiddleware<C: Context> {
+    /// Runs before the transaction is dispatched, after the previous middlewares of the pipeline.
+    /// If this returns an error, the transaction is rejected and the next middlewares are skipped.
+    fn pre_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        state: &mut TxMiddlewareState<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()>;
+
+    /// Runs after the transaction is dispatched, after the previous middlewares of the pipeline.
+    /// If this returns an error, the rollup panics.
+    fn post_dispatch(
+        &self,
+        _tx: &Transaction<C>,
+        _ctx: &C,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// An ordered list of [`TxMiddleware`]s.
+///
+/// ```ignore
+/// fn pre_dispatch_tx_hook(&self, tx, working_set, arg) -> anyhow::Result<C> {
+///     TxMiddlewares::new()
+///         .with(&self.accounts)
+///         .with(&self.bank)
+///         .pre_dispatch(tx, &arg.sequencer, arg.height, working_set)
+/// }
+/// ```
+///
+/// Middlewares which depend on the module called by the transaction, such as the caps of a [`RateLimit`],
+/// need the runtime to resolve it from the message of the transaction:
+///
+/// ```ignore
+/// let module = Self::decode_call(tx.runtime_msg())
+///     .ok()
+///     .map(|msg| self.module_address(&msg).clone());
+/// TxMiddlewares::new()
+///     .with(&self.accounts)
+///     .with(&self.rate_limit)
+///     .calling(module)
+///     .pre_dispatch(tx, &arg.sequencer, arg.height, working_set)
+/// ```
+pub struct TxMiddlewares<'a, C: Context> {
+    middlewares: Vec<&'a dyn TxMiddleware<C>>,
+    module: Option<C::Address>,
+}
+
+impl<'a, C: Context> Default for TxMiddlewares<'a, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, C: Context> TxMiddlewares<'a, C> {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Self {
+            middlewares: Vec::new(),
+            module: None,
+        }
+    }
+
+    /// Appends `middleware` to the pipeline.
+    pub fn with(mut self, middleware: &'a dyn TxMiddleware<C>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Sets the module called by the transactions going through the pipeline, as resolved by the runtime.
+    pub fn calling(mut self, module: Option<C::Address>) -> Self {
+        self.module = module;
+        self
+    }
+
+    /// Runs the pre-dispatch step of the middlewares in order, and returns the context of the transaction.
+    pub fn pre_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        sequencer_pub_key: &C::PublicKey,
+        height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<C> {
+        let mut state = TxMiddlewareState {
+            sequencer_pub_key: sequencer_pub_key.clone(),
+            height,
+            sender: tx.pub_key().to_address(),
+            sequencer: sequencer_pub_key.to_address(),
+            module: self.module.clone(),
+        };
+        for middleware in &self.middlewares {
+            middleware.pre_dispatch(tx, &mut state, working_set)?;
+        }
+        Ok(C::new(state.sender, state.sequencer, state.height))
+    }
+
+    /// Runs the post-dispatch step of the middlewares in order.
+    pub fn post_dispatch(
+        &self,
+        tx: &Transaction<C>,
+        ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        for middleware in &self.middlewares {
+            middleware.post_dispatch(tx, ctx, working_set)?;
+        }
+        Ok(())
+    }
+}
+
+/// Rejects the transactions of senders missing from an allow list, or present in a deny list.
+/// Must run after the middleware authenticating the sender.
+pub enum SenderFilter<C: Context> {
+    /// Only these senders can send transactions.
+    AllowList(Vec<C::Address>),
+    /// These senders can't send transactions.
+    DenyList(Vec<C::Address>),
+}
+
+impl<C: Context> TxMiddleware<C> for SenderFilter<C> {
+    fn pre_dispatch(
+        &self,
+        _tx: &Transaction<C>,
+        state: &mut TxMiddlewareState<C>,
+        _working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        match self {
+            SenderFilter::AllowList(allowed) if !allowed.contains(&state.sender) => {
+                bail!("Sender {} isn't allowed to send transactions", state.sender)
+            }
+            SenderFilter::DenyList(denied) if denied.contains(&state.sender) => {
+                bail!(
+                    "Sender {} is denied from sending transactions",
+                    state.sender
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The counters of a [`RateLimit`].
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+enum RateLimitKey<C: Context> {
+    /// The transactions sent by an account.
+    Sender(C::Address),
+    /// The calls to a module, from any sender.
+    Module(C::Address),
+}
+
+/// Limits the number of transactions of each sender, and the number of calls to each module, per window of slots.
+/// This protects rollups with low or zero fees from spam.
+///
+/// Must run after the middleware authenticating the sender, and preferably last, as a transaction rejected by a
+/// later middleware still counts against the limits. The caps on modules require the runtime to resolve the
+/// module called by each transaction with [`TxMiddlewares::calling`]; transactions whose module isn't resolved
+/// only count against the quota of their sender.
+pub struct RateLimit<C: Context> {
+    max_txs_per_window: u64,
+    window: u64,
+    sender_quotas: Vec<(C::Address, u64)>,
+    module_caps: Vec<(C::Address, u64)>,
+    /// The window in which each counter was last incremented, and its value in this window.
+    counters: StateMap<RateLimitKey<C>, (u64, u64)>,
+}
+
+impl<C: Context> RateLimit<C> {
+    /// Creates a rate limit allowing `max_txs_per_slot` transactions per sender and per slot, which
+    /// stores its counters under `prefix`. The prefix must not be used by any module of the runtime.
+    pub fn new(prefix: Prefix, max_txs_per_slot: u64) -> Self {
+        Self {
+            max_txs_per_window: max_txs_per_slot,
+            window: 1,
+            sender_quotas: Vec::new(),
+            module_caps: Vec::new(),
+            counters: StateMap::new(prefix),
+        }
+    }
+
+    /// Counts the transactions over consecutive windows of `slots` slots instead of single slots. The limits then
+    /// apply per window: the window of a transaction executed at slot height `h` is `h / slots`.
+    pub fn with_window(mut self, slots: u64) -> Self {
+        self.window = slots.max(1);
+        self
+    }
+
+    /// Allows `max_txs` transactions per window to `sender`, instead of the default quota. For example, a relayer
+    /// can send more transactions than other accounts, and a quota of zero bans an account.
+    pub fn with_sender_quota(mut self, sender: C::Address, max_txs: u64) -> Self {
+        self.sender_quotas.retain(|(address, _)| address != &sender);
+        self.sender_quotas.push((sender, max_txs));
+        self
+    }
+
+    /// Caps the number of calls to `module` per window, from all the senders together.
+    pub fn with_module_cap(mut self, module: C::Address, max_calls: u64) -> Self {
+        self.module_caps.retain(|(address, _)| address != &module);
+        self.module_caps.push((module, max_calls));
+        self
+    }
+
+    /// Returns the value of `counter` in `window`.
+    fn count(
+        &self,
+        counter: &RateLimitKey<C>,
+        window: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        match self.counters.get(counter, working_set) {
+            Some((counter_window, count)) if counter_window == window => count,
+            _ => 0,
+        }
+    }
+}
+
+impl<C: Context> TxMiddleware<C> for RateLimit<C> {
+    fn pre_dispatch(
+        &self,
+        _tx: &Transaction<C>,
+        state: &mut TxMiddlewareState<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let window = state.height / self.window;
+
+        let sender_quota = self
+            .sender_quotas
+            .iter()
+            .find(|(sender, _)| sender == &state.sender)
+            .map_or(self.max_txs_per_window, |(_, quota)| *quota);
+        let sender = RateLimitKey::Sender(state.sender.clone());
+        let sent = self.count(&sender, window, working_set);
+        ensure!(
+            sent < sender_quota,
+            "Sender {} exceeded the limit of {} transactions per {} slots",
+            state.sender,
+            sender_quota,
+            self.window
+        );
+
+        if let Some(module) = &state.module {
+            if let Some((_, cap)) = self.module_caps.iter().find(|(capped, _)| capped == module) {
+                let counter = RateLimitKey::Module(module.clone());
+                let calls = self.count(&counter, window, working_set);
+                ensure!(
+                    calls < *cap,
+                    "Module {} exceeded the limit of {} calls per {} slots",
+                    module,
+                    cap,
+                    self.window
+                );
+                self.counters
+                    .set(&counter, &(window, calls + 1), working_set);
+            }
+        }
+        self.counters.set(&sender, &(window, sent + 1), working_set);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use sov_modules_core::{PrivateKey, Spec};
+    use sov_prover_storage_manager::new_orphan_storage;
+
+    use super::*;
+    use crate::default_context::DefaultContext;
+    use crate::default_signature::private_key::DefaultPrivateKey;
+
+    type C = DefaultContext;
+
+    #[test]
+    fn test_tx_middlewares() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let working_set = &mut WorkingSet::<C>::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+        let sequencer = DefaultPrivateKey::generate().pub_key();
+        let allowed_key = DefaultPrivateKey::generate();
+        let denied_key = DefaultPrivateKey::generate();
+        let allowed: <C as Spec>::Address = allowed_key.pub_key().to_address();
+        let filter = SenderFilter::<C>::AllowList(vec![allowed]);
+        let rate_limit = RateLimit::<C>::new(Prefix::new(b"rate_limit".to_vec()), 2);
+        let middlewares = TxMiddlewares::new().with(&filter).with(&rate_limit);
+
+        let tx = |key: &DefaultPrivateKey| Transaction::<C>::new_signed_tx(key, vec![], 0, 0, 0, 0);
+        let ctx = middlewares
+            .pre_dispatch(&tx(&allowed_key), &sequencer, 1, working_set)
+            .expect("The sender is allowed");
+        assert_eq!(ctx.sender(), &allowed);
+        assert_eq!(ctx.slot_height(), 1);
+        middlewares
+            .post_dispatch(&tx(&allowed_key), &ctx, working_set)
+            .unwrap();
+
+        middlewares
+            .pre_dispatch(&tx(&denied_key), &sequencer, 1, working_set)
+            .expect_err("The sender isn't in the allow list");
+
+        middlewares
+            .pre_dispatch(&tx(&allowed_key), &sequencer, 1, working_set)
+            .expect("The second transaction of the slot is within the limit");
+        middlewares
+            .pre_dispatch(&tx(&allowed_key), &sequencer, 1, working_set)
+            .expect_err("The third transaction of the slot exceeds the limit");
+        middlewares
+            .pre_dispatch(&tx(&allowed_key), &sequencer, 2, working_set)
+            .expect("The limit is reset at the next slot");
+
+        let deny_list = SenderFilter::<C>::DenyList(vec![allowed]);
+        TxMiddlewares::new()
+            .with(&deny_list)
+            .pre_dispatch(&tx(&allowed_key), &sequencer, 2, working_set)
+            .expect_err("The sender is in the deny list");
+    }
+
+    #[test]
+    fn test_rate_limit_quotas() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let working_set = &mut WorkingSet::<C>::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+        let sequencer = DefaultPrivateKey::generate().pub_key();
+        let relayer_key = DefaultPrivateKey::generate();
+        let user_key = DefaultPrivateKey::generate();
+        let relayer: <C as Spec>::Address = relayer_key.pub_key().to_address();
+        let capped_module: <C as Spec>::Address =
+            DefaultPrivateKey::generate().pub_key().to_address();
+        let other_module: <C as Spec>::Address =
+            DefaultPrivateKey::generate().pub_key().to_address();
+        let rate_limit = RateLimit::<C>::new(Prefix::new(b"rate_limit".to_vec()), 1)
+            .with_window(10)
+            .with_sender_quota(relayer, 3)
+            .with_module_cap(capped_module, 2);
+
+        let tx = |key: &DefaultPrivateKey| Transaction::<C>::new_signed_tx(key, vec![], 0, 0, 0, 0);
+        let pre_dispatch = |key: &DefaultPrivateKey,
+                            module: &<C as Spec>::Address,
+                            height: u64,
+                            working_set: &mut WorkingSet<C>| {
+            TxMiddlewares::new()
+                .with(&rate_limit)
+                .calling(Some(*module))
+                .pre_dispatch(&tx(key), &sequencer, height, working_set)
+        };
+
+        // The quota of a sender applies over the whole window
+        pre_dispatch(&user_key, &other_module, 10, working_set)
+            .expect("The first transaction of the user is within its quota");
+        pre_dispatch(&user_key, &other_module, 19, working_set)
+            .expect_err("The user already sent a transaction in this window");
+        pre_dispatch(&user_key, &other_module, 20, working_set)
+            .expect("The quota is reset at the next window");
+
+        // The relayer has a larger quota, but its calls to the capped module are limited
+        pre_dispatch(&relayer_key, &capped_module, 20, working_set)
+            .expect("The first call to the module is within the cap");
+        pre_dispatch(&user_key, &capped_module, 21, working_set)
+            .expect_err("The user already sent a transaction in this window");
+        pre_dispatch(&relayer_key, &capped_module, 22, working_set)
+            .expect("The second call to the module is within the cap");
+        pre_dispatch(&relayer_key, &capped_module, 23, working_set)
+            .expect_err("The module reached its cap");
+        pre_dispatch(&relayer_key, &other_module, 24, working_set)
+            .expect("A rejected call doesn't count against the quota of the relayer");
+        pre_dispatch(&relayer_key, &other_module, 25, working_set)
+            .expect_err("The relayer reached its quota");
+    }
+}
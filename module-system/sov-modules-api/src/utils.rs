@@ -1,5 +1,5 @@
 use jsonrpsee::types::ErrorObjectOwned;
-use sov_modules_core::{Context, Spec};
+use sov_modules_core::{Context, Spec, WorkingSet};
 use sov_rollup_interface::digest::Digest;
 
 pub fn generate_address<C: Context>(key: &str) -> <C as Spec>::Address {
@@ -14,3 +14,25 @@ pub fn to_jsonrpsee_error_object(err: impl ToString, message: &str) -> ErrorObje
         Some(err.to_string()),
     )
 }
+
+/// The code of the RPC errors returned when the requested data was pruned from the node.
+pub const PRUNED_ERROR_CODE: i32 = -32010;
+
+/// Converts an error caused by requesting pruned data to an RPC error with the [`PRUNED_ERROR_CODE`] code.
+pub fn to_pruned_error_object(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(PRUNED_ERROR_CODE, "PRUNED", Some(err.to_string()))
+}
+
+/// Makes `working_set` read the state at `version` instead of the latest state, if a version is provided.
+/// Used by the rpc methods which can query historical state.
+pub fn set_rpc_archival_version<C: Context>(
+    version: Option<u64>,
+    working_set: &mut WorkingSet<C>,
+) -> Result<(), ErrorObjectOwned> {
+    if let Some(version) = version {
+        working_set
+            .set_archival_version(version)
+            .map_err(to_pruned_error_object)?;
+    }
+    Ok(())
+}
@@ -406,3 +406,78 @@ fn test_state_vec_diff_type() {
         assert_eq!(len, 2);
     }
 }
+
+#[test]
+fn test_nested_savepoints() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+    let state_map = create_state_map(1, 11, &mut working_set);
+    working_set.add_event("before", "savepoint");
+
+    // The changes of a committed savepoint are reverted with the enclosing one
+    let mut outer = working_set.savepoint();
+    state_map.set(&1, &12, &mut *outer);
+    let mut inner = outer.savepoint();
+    state_map.set(&2, &22, &mut *inner);
+    inner.add_event("inner", "savepoint");
+    inner.commit();
+    assert_eq!(state_map.get(&2, &mut *outer), Some(22));
+    outer.rollback();
+    assert_eq!(state_map.get(&1, &mut working_set), Some(11));
+    assert_eq!(state_map.get(&2, &mut working_set), None);
+    assert_eq!(working_set.events().len(), 1);
+
+    // Rolling back a nested savepoint keeps the changes of the enclosing one
+    let mut outer = working_set.savepoint();
+    state_map.set(&1, &13, &mut *outer);
+    {
+        let mut inner = outer.savepoint();
+        state_map.delete(&1, &mut *inner);
+        inner.add_event("inner", "savepoint");
+        // Dropped without being committed
+    }
+    assert_eq!(state_map.get(&1, &mut *outer), Some(13));
+    outer.commit();
+    assert_eq!(state_map.get(&1, &mut working_set), Some(13));
+    assert_eq!(working_set.events().len(), 1);
+
+    // The changes made in savepoints are committed with the working set
+    let mut working_set = working_set.checkpoint().to_revertable();
+    assert_eq!(state_map.get(&1, &mut working_set), Some(13));
+}
+
+#[test]
+fn test_state_map_range() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let state_map: StateMap<(u8, u8), u32> = StateMap::new(Prefix::new(vec![0]));
+    let other_map: StateMap<(u8, u8), u32> = StateMap::new(Prefix::new(vec![1]));
+
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+    for key in [(1, 1), (1, 2), (2, 1), (3, 1)] {
+        state_map.set(&key, &u32::from(key.0 * 10 + key.1), &mut working_set);
+    }
+    other_map.set(&(1, 1), &0, &mut working_set);
+    let mut working_set = Operation::Finalize
+        .execute(working_set, storage.clone())
+        .to_revertable();
+
+    // The writes which aren't committed yet are merged with the storage
+    state_map.set(&(1, 3), &13, &mut working_set);
+    state_map.delete(&(1, 2), &mut working_set);
+    state_map.set(&(2, 1), &210, &mut working_set);
+
+    assert_eq!(
+        state_map.prefix_range(&1u8, &working_set),
+        vec![((1, 1), 11), ((1, 3), 13)]
+    );
+    assert_eq!(
+        state_map.range((1, 2)..(3, 1), &working_set),
+        vec![((1, 3), 13), ((2, 1), 210)]
+    );
+    assert_eq!(
+        state_map.range(.., &working_set),
+        vec![((1, 1), 11), ((1, 3), 13), ((2, 1), 210), ((3, 1), 31)]
+    );
+}
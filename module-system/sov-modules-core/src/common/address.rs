@@ -136,6 +136,36 @@ impl<'de> serde::Deserialize<'de> for Address {
 impl BasicAddress for Address {}
 impl RollupAddress for Address {}
 
+/// The "Human-Readable Part" of the addresses, unless another one is set with [`set_address_prefix`].
+pub const DEFAULT_ADDRESS_PREFIX: &str = "sov";
+
+#[cfg(feature = "std")]
+static ADDRESS_PREFIX: std::sync::RwLock<Option<String>> = std::sync::RwLock::new(None);
+
+/// Returns the "Human-Readable Part" used to display and parse the addresses of the rollup.
+pub fn address_prefix() -> String {
+    #[cfg(feature = "std")]
+    {
+        let prefix = ADDRESS_PREFIX.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(prefix) = prefix.as_ref() {
+            return prefix.clone();
+        }
+    }
+    DEFAULT_ADDRESS_PREFIX.to_string()
+}
+
+/// Sets the "Human-Readable Part" of the addresses of the rollup, usually from the configuration of the chain.
+/// It must be set before any address is displayed or parsed, as addresses with another prefix are rejected.
+#[cfg(feature = "std")]
+pub fn set_address_prefix(prefix: &str) -> Result<(), Bech32ParseError> {
+    vec_to_bech32m(&[], prefix)?;
+    if prefix.to_lowercase() != prefix {
+        return Err(Bech32ParseError::WrongHPR(prefix.to_string()));
+    }
+    *ADDRESS_PREFIX.write().unwrap_or_else(|e| e.into_inner()) = Some(prefix.to_string());
+    Ok(())
+}
+
 /// Converts bytes into a bech32m address, using the provided "Human-Readable Part".
 pub fn vec_to_bech32m(vec: &[u8], hrp: &str) -> Result<String, Error> {
     let data = vec.to_base32();
@@ -150,9 +180,33 @@ pub fn bech32m_to_decoded_vec(bech32_addr: &str) -> Result<(String, Vec<u8>), Er
     Ok((hrp, vec))
 }
 
-const HRP: &str = "sov";
-
 impl AddressBech32 {
+    fn encode(addr: &[u8], hrp: &str) -> Result<Self, bech32::Error> {
+        if addr.len() != 32 {
+            return Err(bech32::Error::InvalidLength);
+        }
+        let value = vec_to_bech32m(addr, hrp)?;
+        Ok(AddressBech32 { value })
+    }
+
+    /// Parses a bech32m address of 32 bytes, checking its checksum and that its "Human-Readable Part" is `hrp`.
+    fn parse(s: &str, hrp: &str) -> Result<Self, Bech32ParseError> {
+        let (decoded_hrp, data, variant) = bech32::decode(s)?;
+        if variant != bech32::Variant::Bech32m {
+            return Err(bech32::Error::InvalidChecksum.into());
+        }
+        if decoded_hrp != hrp {
+            return Err(Bech32ParseError::WrongHPR(decoded_hrp));
+        }
+        if Vec::<u8>::from_base32(&data)?.len() != 32 {
+            return Err(bech32::Error::InvalidLength.into());
+        }
+
+        Ok(AddressBech32 {
+            value: s.to_lowercase(),
+        })
+    }
+
     pub(crate) fn to_byte_array(&self) -> [u8; 32] {
         let (_, data) = bech32m_to_decoded_vec(&self.value).unwrap();
 
@@ -171,25 +225,19 @@ impl TryFrom<&[u8]> for AddressBech32 {
     type Error = bech32::Error;
 
     fn try_from(addr: &[u8]) -> Result<Self, bech32::Error> {
-        if addr.len() != 32 {
-            return Err(bech32::Error::InvalidLength);
-        }
-        let string = vec_to_bech32m(addr, HRP)?;
-        Ok(AddressBech32 { value: string })
+        AddressBech32::encode(addr, &address_prefix())
     }
 }
 
 impl From<&Address> for AddressBech32 {
     fn from(addr: &Address) -> Self {
-        let string = vec_to_bech32m(&addr.addr, HRP).unwrap();
-        AddressBech32 { value: string }
+        AddressBech32::encode(&addr.addr, &address_prefix()).unwrap()
     }
 }
 
 impl From<Address> for AddressBech32 {
     fn from(addr: Address) -> Self {
-        let string = vec_to_bech32m(&addr.addr, HRP).unwrap();
-        AddressBech32 { value: string }
+        AddressBech32::from(&addr)
     }
 }
 
@@ -205,15 +253,7 @@ impl FromStr for AddressBech32 {
     type Err = Bech32ParseError;
 
     fn from_str(s: &str) -> Result<Self, Bech32ParseError> {
-        let (hrp, _) = bech32m_to_decoded_vec(s)?;
-
-        if HRP != hrp {
-            return Err(Bech32ParseError::WrongHPR(hrp));
-        }
-
-        Ok(AddressBech32 {
-            value: s.to_string(),
-        })
+        AddressBech32::parse(s, &address_prefix())
     }
 }
 
@@ -233,4 +273,46 @@ mod test {
             "sov1pv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9stup8tx"
         );
     }
+
+    #[test]
+    fn test_address_prefix() {
+        let address = Address::from([11; 32]);
+        let encoded = AddressBech32::encode(&address.addr, "rol").unwrap();
+        assert_eq!(
+            encoded.to_string(),
+            "rol1pv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9s0uj09t"
+        );
+        let parsed = AddressBech32::parse(&encoded.to_string().to_uppercase(), "rol").unwrap();
+        assert_eq!(Address::from(parsed), address);
+
+        assert!(matches!(
+            AddressBech32::parse(&encoded.to_string(), DEFAULT_ADDRESS_PREFIX),
+            Err(Bech32ParseError::WrongHPR(hrp)) if hrp == "rol"
+        ));
+        assert!(set_address_prefix("").is_err());
+        assert!(set_address_prefix("Sov").is_err());
+    }
+
+    #[test]
+    fn test_address_parsing_errors() {
+        let valid = AddressBech32::from(Address::from([11; 32])).to_string();
+
+        // A single mistyped character breaks the checksum
+        let mut mistyped = valid.into_bytes();
+        mistyped[10] = if mistyped[10] == b'q' { b'p' } else { b'q' };
+        assert!(matches!(
+            AddressBech32::parse(core::str::from_utf8(&mistyped).unwrap(), "sov"),
+            Err(Bech32ParseError::Bech32(bech32::Error::InvalidChecksum))
+        ));
+
+        // Bech32 addresses, and addresses which aren't 32 bytes long, are rejected
+        let bech32 =
+            bech32::encode("sov", [11u8; 32].to_base32(), bech32::Variant::Bech32).unwrap();
+        assert!(AddressBech32::parse(&bech32, "sov").is_err());
+        let short = vec_to_bech32m(&[11; 20], "sov").unwrap();
+        assert!(matches!(
+            AddressBech32::parse(&short, "sov"),
+            Err(Bech32ParseError::Bech32(bech32::Error::InvalidLength))
+        ));
+    }
 }
@@ -66,7 +66,35 @@ impl<const N: usize> GasUnit for TupleGasUnit<N> {
     }
 }
 
+/// The gas charged by a [`GasMeter`] for every access to the provable state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateAccessGasCosts<GU>
+where
+    GU: GasUnit,
+{
+    /// The gas charged for every read
+    pub read: GU,
+    /// The gas charged for every write
+    pub write: GU,
+    /// The gas charged for every deletion
+    pub delete: GU,
+}
+
+impl<GU> Default for StateAccessGasCosts<GU>
+where
+    GU: GasUnit,
+{
+    fn default() -> Self {
+        Self {
+            read: GU::ZEROED,
+            write: GU::ZEROED,
+            delete: GU::ZEROED,
+        }
+    }
+}
+
 /// A gas meter.
+#[derive(Debug, Clone)]
 pub struct GasMeter<GU>
 where
     GU: GasUnit,
@@ -74,6 +102,8 @@ where
     remaining_funds: u64,
     gas_price: GU,
     gas_used: GU,
+    state_access_costs: StateAccessGasCosts<GU>,
+    exhausted: bool,
 }
 
 impl<GU> Default for GasMeter<GU>
@@ -85,6 +115,8 @@ where
             remaining_funds: 0,
             gas_price: GU::ZEROED,
             gas_used: GU::ZEROED,
+            state_access_costs: StateAccessGasCosts::default(),
+            exhausted: false,
         }
     }
 }
@@ -99,9 +131,17 @@ where
             remaining_funds,
             gas_price,
             gas_used: GU::ZEROED,
+            state_access_costs: StateAccessGasCosts::default(),
+            exhausted: false,
         }
     }
 
+    /// Sets the gas charged for every access to the provable state.
+    pub fn with_state_access_costs(mut self, state_access_costs: StateAccessGasCosts<GU>) -> Self {
+        self.state_access_costs = state_access_costs;
+        self
+    }
+
     /// Returns the remaining gas funds.
     pub const fn remaining_funds(&self) -> u64 {
         self.remaining_funds
@@ -117,17 +157,78 @@ where
         &self.gas_price
     }
 
+    /// Returns the gas charged for every access to the provable state.
+    pub const fn state_access_costs(&self) -> &StateAccessGasCosts<GU> {
+        &self.state_access_costs
+    }
+
+    /// Returns true if a charge failed since the meter was created, because the remaining funds
+    /// couldn't cover it.
+    pub const fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
     /// Deducts the provided gas unit from the remaining funds, computing the scalar value of the
     /// funds from the price of the instance.
     pub fn charge_gas(&mut self, gas: &GU) -> Result<()> {
         self.gas_used.combine(gas);
 
         let gas = gas.value(&self.gas_price);
-        self.remaining_funds = self
-            .remaining_funds
-            .checked_sub(gas)
-            .ok_or_else(|| anyhow::anyhow!("Not enough gas"))?;
+        self.remaining_funds = match self.remaining_funds.checked_sub(gas) {
+            Some(remaining_funds) => remaining_funds,
+            None => {
+                self.exhausted = true;
+                anyhow::bail!("Not enough gas");
+            }
+        };
 
         Ok(())
     }
+
+    /// Charges the cost of a state read. State accesses can't fail, so running out of funds only
+    /// marks the meter as exhausted, see [`GasMeter::is_exhausted`].
+    pub fn charge_read(&mut self) {
+        let gas = self.state_access_costs.read.clone();
+        let _ = self.charge_gas(&gas);
+    }
+
+    /// Charges the cost of a state write. See [`GasMeter::charge_read`].
+    pub fn charge_write(&mut self) {
+        let gas = self.state_access_costs.write.clone();
+        let _ = self.charge_gas(&gas);
+    }
+
+    /// Charges the cost of a state deletion. See [`GasMeter::charge_read`].
+    pub fn charge_delete(&mut self) {
+        let gas = self.state_access_costs.delete.clone();
+        let _ = self.charge_gas(&gas);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_accesses_exhaust_the_meter() {
+        let mut meter = GasMeter::new(10, [1, 1]).with_state_access_costs(StateAccessGasCosts {
+            read: [1, 2],
+            write: [3, 2],
+            delete: [0, 1],
+        });
+
+        meter.charge_read();
+        meter.charge_delete();
+        assert_eq!(meter.remaining_funds(), 6);
+        assert!(!meter.is_exhausted());
+
+        meter.charge_write();
+        assert_eq!(meter.remaining_funds(), 1);
+        assert!(!meter.is_exhausted());
+
+        meter.charge_read();
+        assert_eq!(meter.remaining_funds(), 1);
+        assert!(meter.is_exhausted());
+        assert_eq!(meter.gas_used(), &[5, 7]);
+    }
 }
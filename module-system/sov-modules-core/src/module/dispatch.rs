@@ -6,6 +6,9 @@ use crate::common::ModuleError;
 use crate::module::{CallResponse, Context, Spec};
 use crate::storage::WorkingSet;
 
+/// The maximum number of nested calls between modules made with [`WorkingSet::call_module`].
+pub const MAX_MODULE_CALL_DEPTH: usize = 8;
+
 /// A trait that needs to be implemented for any call message.
 pub trait DispatchCall: Send + Sync {
     /// The context of the call
@@ -0,0 +1,28 @@
+use alloc::vec::Vec;
+
+use borsh::BorshSerialize;
+use sov_rollup_interface::stf::{Event, EventAttribute};
+
+/// A typed event emitted by a module. You can derive it with `#[derive(ModuleEvent)]`.
+///
+/// The event is stored in the transaction receipt under its [`ModuleEvent::NAME`], with its borsh-encoded value,
+/// its [`ModuleEvent::VERSION`] and its indexed attributes, so that indexers can filter events by attribute and
+/// decode events emitted by older versions of the module.
+pub trait ModuleEvent: BorshSerialize {
+    /// The key of the event, shared by all the versions of its layout.
+    const NAME: &'static str;
+
+    /// The version of the layout of the event. Must be bumped whenever the fields of the event change.
+    const VERSION: u8;
+
+    /// Returns the names and borsh-encoded values of the fields designated as indexed.
+    fn indexed_attributes(&self) -> Vec<EventAttribute>;
+
+    /// Converts the typed event into the event stored in receipts.
+    fn to_event(&self) -> Event {
+        let value = self
+            .try_to_vec()
+            .expect("Serializing an event to a vector can't fail");
+        Event::new_typed(Self::NAME, Self::VERSION, value, self.indexed_attributes())
+    }
+}
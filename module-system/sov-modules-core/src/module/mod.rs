@@ -10,9 +10,11 @@ use crate::common::{ModuleError, ModulePrefix};
 use crate::storage::WorkingSet;
 
 mod dispatch;
+mod event;
 mod spec;
 
 pub use dispatch::*;
+pub use event::*;
 pub use spec::*;
 
 /// Response type for the `Module::call` method.
@@ -91,6 +93,23 @@ pub trait ModuleInfo {
     fn dependencies(&self) -> Vec<&<Self::Context as Spec>::Address>;
 }
 
+/// A module with a versioned storage layout, which can transform its state from one version to the next.
+///
+/// The version of the state of each module is recorded outside of the module, and migrations
+/// only run when the runtime applies them at the activation slot of a protocol upgrade.
+/// Until then, the code of the module must keep reading the state in its previous layout.
+pub trait Migration: ModuleInfo {
+    /// The version of the storage layout used by the code of the module. Modules start at version `0`.
+    const VERSION: u64;
+
+    /// Transforms the state of the module from version `from_version` to version `from_version + 1`.
+    fn migrate(
+        &self,
+        from_version: u64,
+        working_set: &mut WorkingSet<Self::Context>,
+    ) -> Result<(), ModuleError>;
+}
+
 /// A trait that specifies how a runtime should encode the data for each module
 pub trait EncodeCall<M: Module> {
     /// The encoding function
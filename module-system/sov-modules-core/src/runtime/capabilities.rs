@@ -33,6 +33,15 @@ pub trait Kernel<C: Context, Da: DaSpec>: BlobSelector<Da, Context = C> + Defaul
     fn true_height(&self, working_set: &mut WorkingSet<C>) -> u64;
     /// Return the height at which transactions currently *appear* to be executing.
     fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64;
+    /// Return the identifier of the rollup. Transactions signed for any other chain are rejected.
+    fn chain_id(&self, working_set: &mut WorkingSet<C>) -> u64;
+    /// Return the base fee per unit of gas charged in the current slot.
+    fn base_fee_per_gas(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit;
+    /// Record gas consumed in the current slot, so that the kernel can adjust the base fee of the next slot.
+    fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>);
+    /// Record the root of the tree over the events emitted by the transactions of the current slot,
+    /// so that the inclusion of an event can be proven against the state root.
+    fn record_events_root(&self, events_root: [u8; 32], working_set: &mut WorkingSet<C>);
 }
 
 /// Hooks allowing the kernel to get access to the DA layer state
@@ -114,7 +123,7 @@ pub mod mocks {
     use sov_rollup_interface::da::DaSpec;
 
     use super::{BlobRefOrOwned, BlobSelector, Kernel};
-    use crate::{Context, WorkingSet};
+    use crate::{Context, GasUnit, WorkingSet};
 
     /// A mock kernel for use in tests
     #[derive(Debug, Clone)]
@@ -123,6 +132,8 @@ pub mod mocks {
         pub true_height: u64,
         /// The height at which transactions appear to be executing
         pub visible_height: u64,
+        /// The identifier of the rollup
+        pub chain_id: u64,
         phantom: core::marker::PhantomData<(C, Da)>,
     }
 
@@ -131,6 +142,7 @@ pub mod mocks {
             Self {
                 true_height: 0,
                 visible_height: 0,
+                chain_id: 0,
                 phantom: Default::default(),
             }
         }
@@ -142,6 +154,7 @@ pub mod mocks {
             Self {
                 true_height,
                 visible_height,
+                chain_id: 0,
                 phantom: core::marker::PhantomData,
             }
         }
@@ -154,6 +167,15 @@ pub mod mocks {
         fn visible_height(&self, _ws: &mut WorkingSet<C>) -> u64 {
             self.visible_height
         }
+        fn chain_id(&self, _ws: &mut WorkingSet<C>) -> u64 {
+            self.chain_id
+        }
+        fn base_fee_per_gas(&self, _ws: &mut WorkingSet<C>) -> C::GasUnit {
+            C::GasUnit::ZEROED
+        }
+        fn record_gas_used(&self, _gas_used: &C::GasUnit, _ws: &mut WorkingSet<C>) {}
+
+        fn record_events_root(&self, _events_root: [u8; 32], _ws: &mut WorkingSet<C>) {}
 
         type GenesisConfig = ();
 
@@ -225,7 +225,7 @@ pub enum ValueExists {
 /// CacheLog keeps track of the original and current values of each key accessed.
 /// By tracking original values, we can detect and eliminate write patterns where a key is
 /// changed temporarily and then reset to its original value
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct CacheLog {
     log: HashMap<CacheKey, Access>,
 }
@@ -252,6 +252,15 @@ impl CacheLog {
             .collect()
     }
 
+    /// Returns the key/value pairs written to the cache, without consuming it.
+    pub fn writes(&self) -> impl Iterator<Item = (&CacheKey, &Option<CacheValue>)> {
+        self.log.iter().filter_map(|(k, v)| match v {
+            Access::Read(_) => None,
+            Access::ReadThenWrite { modified, .. } => Some((k, modified)),
+            Access::Write(write) => Some((k, write)),
+        })
+    }
+
     /// Returns a value corresponding to the key.
     pub fn get_value(&self, key: &CacheKey) -> ValueExists {
         match self.log.get(key) {
@@ -361,7 +370,7 @@ impl CacheLog {
 /// Caches reads and writes for a (key, value) pair. On the first read the value is fetched
 /// from an external source represented by the `ValueReader` trait. On following reads,
 /// the cache checks if the value we read was inserted before.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct StorageInternalCache {
     /// Transaction cache.
     pub tx_cache: CacheLog,
@@ -441,7 +450,7 @@ impl StorageInternalCache {
         self.tx_cache.merge_writes_left(rhs.tx_cache)
     }
 
-    fn add_read(&mut self, key: CacheKey, value: Option<CacheValue>) {
+    pub(crate) fn add_read(&mut self, key: CacheKey, value: Option<CacheValue>) {
         self.tx_cache
             .add_read(key.clone(), value.clone())
             // It is ok to panic here, we must guarantee that the cache is consistent.
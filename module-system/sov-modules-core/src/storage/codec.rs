@@ -43,7 +43,8 @@ pub trait StateValueCodec<V> {
 /// access.
 ///
 /// Note that, unlike [`StateValueCodec`], this trait does not provide
-/// deserialization logic as it's not needed nor supported.
+/// deserialization logic. Codecs which can deserialize keys, as needed to
+/// iterate over the keys of a map, implement [`StateKeyDecoder`].
 pub trait StateKeyCodec<K> {
     /// Serializes a key into a bytes vector.
     ///
@@ -60,6 +61,32 @@ pub trait StateKeyCodec<K> {
     fn encode_key(&self, key: &K) -> Vec<u8>;
 }
 
+/// A [`StateKeyCodec`] which can also deserialize the keys it serialized.
+pub trait StateKeyDecoder<K>: StateKeyCodec<K> {
+    /// Error type that can arise during deserialization.
+    type Error: fmt::Debug;
+
+    /// Tries to deserialize a key from a bytes slice, and returns a
+    /// [`Result`] with either the deserialized key or an error.
+    fn try_decode_key(&self, bytes: &[u8]) -> Result<K, Self::Error>;
+
+    /// Deserializes a key from a bytes slice.
+    ///
+    /// # Panics
+    /// Panics if the call to [`StateKeyDecoder::try_decode_key`] fails.
+    fn decode_key_unwrap(&self, bytes: &[u8]) -> K {
+        self.try_decode_key(bytes)
+            .map_err(|err| {
+                format!(
+                    "Failed to decode key 0x{}, error: {:?}",
+                    hex::encode(bytes),
+                    err
+                )
+            })
+            .unwrap()
+    }
+}
+
 /// A trait for types that can serialize keys and values, as well
 /// as deserializing values for storage access.
 ///
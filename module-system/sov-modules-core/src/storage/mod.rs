@@ -35,6 +35,14 @@ impl From<CacheKey> for StorageKey {
     }
 }
 
+impl From<Vec<u8>> for StorageKey {
+    fn from(key: Vec<u8>) -> Self {
+        Self {
+            key: RefCount::new(key),
+        }
+    }
+}
+
 impl StorageKey {
     /// Returns a new [`RefCount`] reference to the bytes of this key.
     pub fn key(&self) -> RefCount<Vec<u8>> {
@@ -227,6 +235,12 @@ pub trait Storage: Clone {
         None
     }
 
+    /// Returns the oldest version of the state which can be read with [`Storage::get`].
+    /// Older versions were pruned. Storages that never prune keep the default of `0`.
+    fn oldest_retained_version(&self) -> Version {
+        0
+    }
+
     /// Calculates new state root but does not commit any changes to the database.
     fn compute_state_update(
         &self,
@@ -304,6 +318,31 @@ pub trait NativeStorage: Storage {
     /// get the value.
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof>;
 
+    /// Returns the value corresponding to the key or None if key is absent, and a proof of it
+    /// against the root hash at the requested version. Fails if the version was pruned or
+    /// isn't committed yet.
+    fn get_with_proof_at(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> Result<StorageProof<Self::Proof>, anyhow::Error>;
+
+    /// Returns the latest committed version of the tree.
+    fn latest_version(&self) -> Version;
+
     /// Get the root hash of the tree at the requested version
     fn get_root_hash(&self, version: Version) -> Result<Self::Root, anyhow::Error>;
+
+    /// Returns the entries whose keys are in the range `[start, end)` at the requested version,
+    /// or at the latest version if `None`, sorted by key. Keys are compared as byte strings, and
+    /// the range is unbounded above if `end` is `None`.
+    ///
+    /// The result isn't recorded in a witness: the state commitment hashes the keys, so the
+    /// completeness of a range can't be proven to the zkVM.
+    fn get_range(
+        &self,
+        start: &[u8],
+        end: Option<&[u8]>,
+        version: Option<Version>,
+    ) -> Vec<(StorageKey, StorageValue)>;
 }
@@ -1,18 +1,24 @@
 //! Runtime state machine definitions.
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
 use core::{fmt, mem};
 
 pub use kernel_state::{KernelWorkingSet, VersionedWorkingSet};
-use sov_rollup_interface::maybestd::collections::HashMap;
+use sov_rollup_interface::maybestd::collections::{HashMap, HashSet};
+use sov_rollup_interface::maybestd::RefCount;
 use sov_rollup_interface::stf::Event;
 
 use crate::archival_state::{ArchivalAccessoryWorkingSet, ArchivalJmtWorkingSet};
-use crate::common::{GasMeter, Prefix};
-use crate::module::{Context, Spec};
+use crate::common::{GasMeter, ModuleError, Prefix, StateAccessGasCosts};
+use crate::module::{
+    CallResponse, Context, Module, ModuleEvent, ModuleInfo, Spec, MAX_MODULE_CALL_DEPTH,
+};
 use crate::storage::{
     CacheKey, CacheValue, EncodeKeyLike, NativeStorage, OrderedReadsAndWrites, StateCodec,
     StateValueCodec, Storage, StorageInternalCache, StorageKey, StorageProof, StorageValue,
+    ValueExists,
 };
 use crate::Version;
 
@@ -154,6 +160,25 @@ pub struct Delta<S: Storage> {
     inner: S,
     witness: S::Witness,
     cache: StorageInternalCache,
+    /// The keys accessed through this delta, only recorded for forks.
+    accesses: Option<StateAccesses>,
+    /// The values read by the parent of a fork, which the fork reads through to.
+    fork_base: Option<ForkBase>,
+}
+
+/// The values of the provable state shared by the forks of a [`StateCheckpoint`], read through
+/// the checkpoint with [`StateCheckpoint::read_fork_base`].
+#[derive(Debug, Default, Clone)]
+pub struct ForkBase(RefCount<HashMap<CacheKey, Option<CacheValue>>>);
+
+/// The keys of the provable state accessed through a fork of a [`StateCheckpoint`],
+/// see [`StateCheckpoint::fork`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StateAccesses {
+    /// The keys read.
+    pub reads: HashSet<CacheKey>,
+    /// The keys written or deleted.
+    pub writes: HashSet<CacheKey>,
 }
 
 impl<S: Storage> Delta<S> {
@@ -169,6 +194,18 @@ impl<S: Storage> Delta<S> {
                 None => Default::default(),
                 Some(v) => StorageInternalCache::new_with_version(v),
             },
+            accesses: None,
+            fork_base: None,
+        }
+    }
+
+    fn fork(&self, base: &ForkBase) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            witness: Default::default(),
+            cache: Default::default(),
+            accesses: Some(Default::default()),
+            fork_base: Some(base.clone()),
         }
     }
 
@@ -188,14 +225,30 @@ impl<S: Storage> fmt::Debug for Delta<S> {
 
 impl<S: Storage> StateReaderAndWriter for Delta<S> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.reads.insert(key.to_cache_key());
+        }
+        if let Some(ForkBase(base)) = &self.fork_base {
+            if let (ValueExists::No, Some(value)) =
+                (self.cache.try_get(key), base.get(&key.to_cache_key()))
+            {
+                self.cache.add_read(key.to_cache_key(), value.clone());
+            }
+        }
         self.cache.get_or_fetch(key, &self.inner, &self.witness)
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.writes.insert(key.to_cache_key());
+        }
         self.cache.set(key, value)
     }
 
     fn delete(&mut self, key: &StorageKey) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.writes.insert(key.to_cache_key());
+        }
         self.cache.delete(key)
     }
 }
@@ -304,6 +357,8 @@ impl<C: Context> StateCheckpoint<C> {
             delta: RevertableWriter::new(self.delta, None),
             accessory_delta: RevertableWriter::new(self.accessory_delta, None),
             events: Default::default(),
+            savepoint_event_counts: Default::default(),
+            module_call_stack: Default::default(),
             gas_meter: self.gas_meter,
             archival_working_set: None,
             archival_accessory_working_set: None,
@@ -324,6 +379,54 @@ impl<C: Context> StateCheckpoint<C> {
         self.delta.freeze()
     }
 
+    /// Reads the values of `keys` through this [`StateCheckpoint`], so that they are part of its witness,
+    /// and returns them as the base of forks, see [`StateCheckpoint::fork`].
+    pub fn read_fork_base(&mut self, keys: impl IntoIterator<Item = StorageKey>) -> ForkBase {
+        let values = keys
+            .into_iter()
+            .map(|key| {
+                let value = self.delta.get(&key).map(StorageValue::into_cache_value);
+                (key.into_cache_key(), value)
+            })
+            .collect();
+        ForkBase(RefCount::new(values))
+    }
+
+    /// Forks this [`StateCheckpoint`]. The fork starts with an empty cache, reads the keys of `base` from it
+    /// and the other keys from the storage, and records the keys of the provable state accessed through it.
+    /// The fork doesn't see the changes made through this checkpoint to keys missing from `base`.
+    ///
+    /// Forks accessing disjoint keys of `base` can be used from different threads, then merged back in order with
+    /// [`StateCheckpoint::merge_fork`]. The merged changes are the same as if the forks had been used one
+    /// after the other, but the witness of the forks is dropped: the keys accessed by the forks must be read
+    /// through this checkpoint beforehand, with [`StateCheckpoint::read_fork_base`].
+    pub fn fork(&self, base: &ForkBase) -> Self {
+        Self {
+            delta: self.delta.fork(base),
+            accessory_delta: AccessoryDelta::new(self.accessory_delta.storage.clone(), None),
+            gas_meter: GasMeter::default(),
+        }
+    }
+
+    /// Returns the keys of the provable state accessed through this checkpoint, if it is a fork.
+    pub fn fork_accesses(&self) -> Option<&StateAccesses> {
+        self.delta.accesses.as_ref()
+    }
+
+    /// Applies the writes made through `fork` to this checkpoint.
+    pub fn merge_fork(&mut self, fork: Self) {
+        let accesses = fork.delta.accesses.unwrap_or_default();
+        for key in accesses.writes {
+            if let ValueExists::Yes(value) = fork.delta.cache.tx_cache.get_value(&key) {
+                self.delta.cache.tx_cache.add_write(key, value);
+            }
+        }
+        self.accessory_delta
+            .writes
+            .cache
+            .extend(fork.accessory_delta.writes.cache);
+    }
+
     /// Extracts ordered reads and writes of accessory state from this
     /// [`StateCheckpoint`].
     ///
@@ -343,6 +446,11 @@ pub struct WorkingSet<C: Context> {
     delta: RevertableWriter<Delta<C::Storage>>,
     accessory_delta: RevertableWriter<AccessoryDelta<C::Storage>>,
     events: Vec<Event>,
+    /// The number of events emitted before each open savepoint.
+    savepoint_event_counts: Vec<usize>,
+    /// The addresses of the modules executing a call made with [`WorkingSet::call_module`],
+    /// starting with the module which made the outermost call.
+    module_call_stack: Vec<C::Address>,
     gas_meter: GasMeter<C::GasUnit>,
     archival_working_set: Option<ArchivalJmtWorkingSet<C>>,
     archival_accessory_working_set: Option<ArchivalAccessoryWorkingSet<C>>,
@@ -375,10 +483,19 @@ impl<C: Context> WorkingSet<C> {
         ArchivalAccessoryWorkingSet::new(&self.accessory_delta.inner.storage, version)
     }
 
-    /// Sets archival version for a working set
-    pub fn set_archival_version(&mut self, version: Version) {
+    /// Sets archival version for a working set.
+    /// Returns an error if the state at `version` was pruned from the storage.
+    pub fn set_archival_version(&mut self, version: Version) -> anyhow::Result<()> {
+        let oldest_retained_version = self.delta.inner.inner.oldest_retained_version();
+        anyhow::ensure!(
+            version >= oldest_retained_version,
+            "The state at version {} was pruned, the oldest retained version is {}",
+            version,
+            oldest_retained_version
+        );
         self.archival_working_set = Some(self.archival_state(version));
         self.archival_accessory_working_set = Some(self.archival_accessory_state(version));
+        Ok(())
     }
 
     /// Unset archival version
@@ -439,11 +556,124 @@ impl<C: Context> WorkingSet<C> {
         }
     }
 
-    /// Adds an event to the working set.
+    /// Opens a savepoint, returning a guard through which the working set keeps being used.
+    ///
+    /// The state changes and events made through the guard are kept with [`Savepoint::commit`],
+    /// and reverted with [`Savepoint::rollback`] or when the guard is dropped, without affecting the
+    /// changes made before the savepoint. Savepoints can be nested, and the changes of a committed
+    /// savepoint are still reverted with the enclosing one. The gas charged through the guard is never refunded.
+    ///
+    /// ```ignore
+    /// let mut savepoint = working_set.savepoint();
+    /// match other_module.call(msg, context, &mut savepoint) {
+    ///     Ok(_) => savepoint.commit(),
+    ///     Err(_) => savepoint.rollback(),
+    /// }
+    /// ```
+    pub fn savepoint(&mut self) -> Savepoint<C> {
+        self.delta.begin_savepoint();
+        self.accessory_delta.begin_savepoint();
+        self.savepoint_event_counts.push(self.events.len());
+        Savepoint {
+            ws: self,
+            finished: false,
+        }
+    }
+
+    /// Calls the module `callee` with `message` on behalf of the module `caller`, which is the sender of
+    /// the [`Context`] seen by `callee`. The sequencer and the slot height of `context` are kept.
+    ///
+    /// This is how a module composes with the call messages of other modules. The changes made by a
+    /// failed call are rolled back. A module can't be called while it's already executing, directly
+    /// or through other modules, and calls can only be nested [`MAX_MODULE_CALL_DEPTH`] deep.
+    ///
+    /// ```ignore
+    /// fn call(&self, msg: CallMessage, context: &C, working_set: &mut WorkingSet<C>) -> Result<CallResponse, Error> {
+    ///     let transfer = sov_bank::CallMessage::Transfer { to, coins };
+    ///     working_set.call_module(self, &self.bank, transfer, context)?;
+    ///     // ...
+    /// }
+    /// ```
+    pub fn call_module<M>(
+        &mut self,
+        caller: &impl ModuleInfo<Context = C>,
+        callee: &M,
+        message: M::CallMessage,
+        context: &C,
+    ) -> Result<CallResponse, ModuleError>
+    where
+        M: Module<Context = C> + ModuleInfo<Context = C>,
+    {
+        let is_outermost_call = self.module_call_stack.is_empty();
+        if is_outermost_call {
+            self.module_call_stack.push(caller.address().clone());
+        }
+        let result = self.call_nested_module(caller, callee, message, context);
+        if is_outermost_call {
+            self.module_call_stack.pop();
+        }
+        result
+    }
+
+    fn call_nested_module<M>(
+        &mut self,
+        caller: &impl ModuleInfo<Context = C>,
+        callee: &M,
+        message: M::CallMessage,
+        context: &C,
+    ) -> Result<CallResponse, ModuleError>
+    where
+        M: Module<Context = C> + ModuleInfo<Context = C>,
+    {
+        let executing = self
+            .module_call_stack
+            .last()
+            .expect("The caller is on the call stack");
+        if executing != caller.address() {
+            return Err(anyhow::anyhow!(
+                "Module {} can't make a call while module {} is executing",
+                caller.address(),
+                executing
+            )
+            .into());
+        }
+        if self.module_call_stack.contains(callee.address()) {
+            return Err(anyhow::anyhow!("Reentrant call to module {}", callee.address()).into());
+        }
+        if self.module_call_stack.len() > MAX_MODULE_CALL_DEPTH {
+            return Err(anyhow::anyhow!(
+                "Module calls can't be nested more than {} deep",
+                MAX_MODULE_CALL_DEPTH
+            )
+            .into());
+        }
+
+        let callee_context = C::new(
+            caller.address().clone(),
+            context.sequencer().clone(),
+            context.slot_height(),
+        );
+        self.module_call_stack.push(callee.address().clone());
+        let mut savepoint = self.savepoint();
+        let result = callee.call(message, &callee_context, &mut savepoint);
+        match result {
+            Ok(_) => savepoint.commit(),
+            Err(_) => savepoint.rollback(),
+        }
+        self.module_call_stack.pop();
+        result
+    }
+
+    /// Adds an ad-hoc string event to the working set.
     pub fn add_event(&mut self, key: &str, value: &str) {
         self.events.push(Event::new(key, value));
     }
 
+    /// Adds a typed event to the working set.
+    pub fn emit_event<E: ModuleEvent>(&mut self, event: &E) {
+        self.events.push(event.to_event());
+    }
+
     /// Extracts all events from this working set.
     pub fn take_events(&mut self) -> Vec<Event> {
         mem::take(&mut self.events)
@@ -461,8 +691,29 @@ impl<C: Context> WorkingSet<C> {
     }
 
     /// Overrides the current gas settings with the provided values.
+    /// The state access costs are kept, see [`WorkingSet::set_state_access_gas_costs`].
     pub fn set_gas(&mut self, funds: u64, gas_price: C::GasUnit) {
-        self.gas_meter = GasMeter::new(funds, gas_price);
+        let state_access_costs = self.gas_meter.state_access_costs().clone();
+        self.gas_meter =
+            GasMeter::new(funds, gas_price).with_state_access_costs(state_access_costs);
+    }
+
+    /// Replaces the gas meter of this working set, e.g. to carry the gas meter of a transaction
+    /// across working sets, and returns the previous one.
+    pub fn replace_gas_meter(&mut self, gas_meter: GasMeter<C::GasUnit>) -> GasMeter<C::GasUnit> {
+        mem::replace(&mut self.gas_meter, gas_meter)
+    }
+
+    /// Sets the gas charged for every read, write and deletion of the provable state made through this working set.
+    /// Accesses to the accessory state aren't charged, since they only happen natively.
+    pub fn set_state_access_gas_costs(&mut self, costs: StateAccessGasCosts<C::GasUnit>) {
+        self.gas_meter = mem::take(&mut self.gas_meter).with_state_access_costs(costs);
+    }
+
+    /// Returns true if a gas charge failed since the last call to [`WorkingSet::set_gas`].
+    /// Since state accesses can't fail, this is how running out of gas while accessing the state is detected.
+    pub const fn is_out_of_gas(&self) -> bool {
+        self.gas_meter.is_exhausted()
     }
 
     /// Attempts to charge the provided gas unit from the gas meter, using the internal price to
@@ -492,10 +743,27 @@ impl<C: Context> WorkingSet<C> {
         // First inner is `RevertableWriter` and second inner is actually a `Storage` instance
         self.delta.inner.inner.get_with_proof(key)
     }
+
+    /// Returns the entries of the provable state whose keys are in the range `[start, end)`, sorted by key.
+    /// Keys are compared as byte strings, and the range is unbounded above if `end` is `None`.
+    ///
+    /// The changes made through this working set are merged with the entries of the storage. Since the
+    /// completeness of a range can't be proven to the zkVM, range scans are only meant for native code
+    /// like module RPC, and aren't charged any gas.
+    pub fn get_range(&self, start: &[u8], end: Option<&[u8]>) -> Vec<(StorageKey, StorageValue)>
+    where
+        C::Storage: NativeStorage,
+    {
+        match &self.archival_working_set {
+            None => self.delta.get_range(start, end),
+            Some(archival_working_set) => archival_working_set.get_range(start, end),
+        }
+    }
 }
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        self.gas_meter.charge_read();
         match &mut self.archival_working_set {
             None => self.delta.get(key),
             Some(ref mut archival_working_set) => archival_working_set.get(key),
@@ -503,6 +771,7 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
+        self.gas_meter.charge_write();
         match &mut self.archival_working_set {
             None => self.delta.set(key, value),
             Some(ref mut archival_working_set) => archival_working_set.set(key, value),
@@ -510,6 +779,7 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 
     fn delete(&mut self, key: &StorageKey) {
+        self.gas_meter.charge_delete();
         match &mut self.archival_working_set {
             None => self.delta.delete(key),
             Some(ref mut archival_working_set) => archival_working_set.delete(key),
@@ -517,6 +787,63 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 }
 
+/// A savepoint of a [`WorkingSet`], created with [`WorkingSet::savepoint`].
+///
+/// Dereferences to the [`WorkingSet`], and rolls back its changes when dropped without being committed.
+pub struct Savepoint<'a, C: Context> {
+    ws: &'a mut WorkingSet<C>,
+    finished: bool,
+}
+
+impl<'a, C: Context> Savepoint<'a, C> {
+    /// Keeps the changes made since the savepoint was opened.
+    pub fn commit(mut self) {
+        self.ws.delta.commit_savepoint();
+        self.ws.accessory_delta.commit_savepoint();
+        self.ws.savepoint_event_counts.pop();
+        self.finished = true;
+    }
+
+    /// Reverts the state changes and the events made since the savepoint was opened.
+    pub fn rollback(mut self) {
+        self.revert_changes();
+    }
+
+    fn revert_changes(&mut self) {
+        self.ws.delta.rollback_savepoint();
+        self.ws.accessory_delta.rollback_savepoint();
+        let event_count = self
+            .ws
+            .savepoint_event_counts
+            .pop()
+            .expect("The savepoint was opened");
+        self.ws.events.truncate(event_count);
+        self.finished = true;
+    }
+}
+
+impl<'a, C: Context> Deref for Savepoint<'a, C> {
+    type Target = WorkingSet<C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ws
+    }
+}
+
+impl<'a, C: Context> DerefMut for Savepoint<'a, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ws
+    }
+}
+
+impl<'a, C: Context> Drop for Savepoint<'a, C> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.revert_changes();
+        }
+    }
+}
+
 /// A wrapper over [`WorkingSet`] that only allows access to the accessory
 /// state (non-JMT state).
 pub struct AccessoryWorkingSet<'a, C: Context> {
@@ -588,6 +915,20 @@ pub mod archival_state {
         }
     }
 
+    impl<C: Context> ArchivalJmtWorkingSet<C>
+    where
+        C::Storage: NativeStorage,
+    {
+        /// Returns the entries in the range `[start, end)` at the archival version, see [`WorkingSet::get_range`].
+        pub(super) fn get_range(
+            &self,
+            start: &[u8],
+            end: Option<&[u8]>,
+        ) -> Vec<(StorageKey, StorageValue)> {
+            self.delta.get_range(start, end)
+        }
+    }
+
     impl<C: Context> StateReaderAndWriter for ArchivalJmtWorkingSet<C> {
         fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
             self.delta.get(key)
@@ -724,10 +1065,15 @@ pub mod kernel_state {
     }
 }
 
+/// The previous entries of the `writes` of a [`RevertableWriter`] overwritten since a savepoint.
+/// `None` means the key wasn't written before the savepoint.
+type WritesJournal = HashMap<CacheKey, Option<Option<CacheValue>>>;
+
 struct RevertableWriter<T> {
     inner: T,
     writes: HashMap<CacheKey, Option<CacheValue>>,
     version: Option<u64>,
+    savepoints: Vec<WritesJournal>,
 }
 
 impl<T: fmt::Debug> fmt::Debug for RevertableWriter<T> {
@@ -747,6 +1093,45 @@ where
             inner,
             writes: Default::default(),
             version,
+            savepoints: Vec::new(),
+        }
+    }
+
+    fn begin_savepoint(&mut self) {
+        self.savepoints.push(Default::default());
+    }
+
+    /// Keeps the writes made since the last savepoint, which are then reverted with the enclosing savepoint, if any.
+    fn commit_savepoint(&mut self) {
+        let journal = self
+            .savepoints
+            .pop()
+            .expect("There is no savepoint to commit");
+        if let Some(parent) = self.savepoints.last_mut() {
+            for (key, previous) in journal {
+                parent.entry(key).or_insert(previous);
+            }
+        }
+    }
+
+    /// Reverts the writes made since the last savepoint.
+    fn rollback_savepoint(&mut self) {
+        let journal = self
+            .savepoints
+            .pop()
+            .expect("There is no savepoint to roll back");
+        for (key, previous) in journal {
+            match previous {
+                Some(previous) => self.writes.insert(key, previous),
+                None => self.writes.remove(&key),
+            };
+        }
+    }
+
+    fn write(&mut self, key: CacheKey, value: Option<CacheValue>) {
+        let previous = self.writes.insert(key.clone(), value);
+        if let Some(journal) = self.savepoints.last_mut() {
+            journal.entry(key).or_insert(previous);
         }
     }
 
@@ -767,6 +1152,46 @@ where
     }
 }
 
+impl<S: NativeStorage> RevertableWriter<Delta<S>> {
+    /// Returns the entries in the range `[start, end)`, with the writes of the delta and of this
+    /// writer applied on top of the storage.
+    fn get_range(&self, start: &[u8], end: Option<&[u8]>) -> Vec<(StorageKey, StorageValue)> {
+        let mut entries: BTreeMap<Vec<u8>, Option<StorageValue>> = self
+            .inner
+            .inner
+            .get_range(start, end, self.version)
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().clone(), Some(value)))
+            .collect();
+
+        // The cache keys of a versioned writer are prefixed by the version
+        let version_prefix = self.version.map(u64::to_be_bytes);
+        let writes = self.inner.cache.tx_cache.writes().chain(self.writes.iter());
+        for (key, value) in writes {
+            let key = match &version_prefix {
+                None => Some(key.key.as_slice()),
+                Some(version_prefix) => key.key.strip_prefix(&version_prefix[..]),
+            };
+            match key {
+                Some(key) if key >= start && end.map_or(true, |end| key < end) => {
+                    entries.insert(key.to_vec(), value.clone().map(Into::into));
+                }
+                _ => {}
+            }
+        }
+
+        entries
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let key = CacheKey {
+                    key: RefCount::new(key),
+                };
+                value.map(|value| (key.into(), value))
+            })
+            .collect()
+    }
+}
+
 impl<T: StateReaderAndWriter> StateReaderAndWriter for RevertableWriter<T> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
         if let Some(value) = self.writes.get(&key.to_cache_key_version(self.version)) {
@@ -777,14 +1202,13 @@ impl<T: StateReaderAndWriter> StateReaderAndWriter for RevertableWriter<T> {
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
-        self.writes.insert(
+        self.write(
             key.to_cache_key_version(self.version),
             Some(value.into_cache_value()),
         );
     }
 
     fn delete(&mut self, key: &StorageKey) {
-        self.writes
-            .insert(key.to_cache_key_version(self.version), None);
+        self.write(key.to_cache_key_version(self.version), None);
     }
 }
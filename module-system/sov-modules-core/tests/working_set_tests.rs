@@ -1,8 +1,11 @@
+use std::collections::HashSet;
+
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_core::capabilities::mocks::MockKernel;
 use sov_modules_core::{
-    Address, Context, KernelWorkingSet, StateReaderAndWriter, StorageKey, StorageValue, WorkingSet,
+    Address, Context, KernelWorkingSet, StateAccessGasCosts, StateReaderAndWriter, StorageKey,
+    StorageValue, WorkingSet,
 };
 use sov_prover_storage_manager::new_orphan_storage;
 use sov_state::codec::BcsCodec;
@@ -59,3 +62,78 @@ fn test_kernel_workingset_get() {
 
     assert_eq!(Some(storage_value), working_set.get(&storage_key));
 }
+
+#[test]
+fn test_workingset_charges_state_accesses() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let codec = BcsCodec {};
+    let storage = new_orphan_storage(tempdir.path()).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let storage_key = StorageKey::new(&prefix, &vec![4, 5, 6], &codec);
+    let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage);
+    working_set.set_state_access_gas_costs(StateAccessGasCosts {
+        read: [1, 0],
+        write: [2, 0],
+        delete: [3, 0],
+    });
+    working_set.set_gas(6, [1, 1]);
+
+    working_set.set(&storage_key, storage_value.clone());
+    assert_eq!(Some(storage_value), working_set.get(&storage_key));
+    working_set.delete(&storage_key);
+    assert_eq!(working_set.gas_remaining_funds(), 0);
+    assert_eq!(working_set.gas_used(), &[6, 0]);
+    assert!(!working_set.is_out_of_gas());
+
+    // Accesses beyond the gas limit still happen, but exhaust the gas meter
+    assert_eq!(None, working_set.get(&storage_key));
+    assert!(working_set.is_out_of_gas());
+}
+
+#[test]
+fn test_checkpoint_forks_read_base_and_merge_in_order() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let codec = BcsCodec {};
+    let storage = new_orphan_storage(tempdir.path()).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let key_a = StorageKey::new(&prefix, &vec![4], &codec);
+    let key_b = StorageKey::new(&prefix, &vec![5], &codec);
+    let value_1 = StorageValue::new(&vec![1], &codec);
+    let value_2 = StorageValue::new(&vec![2], &codec);
+    let value_3 = StorageValue::new(&vec![3], &codec);
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage);
+    working_set.set(&key_a, value_1.clone());
+    let mut checkpoint = working_set.checkpoint();
+    let base = checkpoint.read_fork_base([key_a.clone(), key_b.clone()]);
+
+    // The forks see the changes made through the checkpoint to the keys of the base
+    let mut fork_1 = checkpoint.fork(&base).to_revertable();
+    assert_eq!(Some(value_1), fork_1.get(&key_a));
+    fork_1.set(&key_a, value_2.clone());
+    let fork_1 = fork_1.checkpoint();
+
+    let mut fork_2 = checkpoint.fork(&base).to_revertable();
+    assert_eq!(None, fork_2.get(&key_b));
+    fork_2.set(&key_b, value_2.clone());
+    fork_2.set(&key_b, value_3.clone());
+    let fork_2 = fork_2.checkpoint();
+
+    let accesses = fork_1.fork_accesses().unwrap();
+    assert_eq!(accesses.reads, HashSet::from([key_a.to_cache_key()]));
+    assert_eq!(accesses.writes, HashSet::from([key_a.to_cache_key()]));
+    let accesses = fork_2.fork_accesses().unwrap();
+    assert_eq!(accesses.reads, HashSet::from([key_b.to_cache_key()]));
+    assert_eq!(accesses.writes, HashSet::from([key_b.to_cache_key()]));
+    assert!(checkpoint.fork_accesses().is_none());
+
+    checkpoint.merge_fork(fork_1);
+    checkpoint.merge_fork(fork_2);
+    let mut working_set = checkpoint.to_revertable();
+    assert_eq!(Some(value_2), working_set.get(&key_a));
+    assert_eq!(Some(value_3), working_set.get(&key_b));
+}
@@ -18,11 +18,14 @@ mod event;
 mod make_constants;
 mod manifest;
 mod module_call_json_schema;
+mod module_event;
 mod module_info;
 mod new_types;
 mod offchain;
 #[cfg(feature = "native")]
 mod rpc;
+#[cfg(feature = "native")]
+mod runtime_call_schema;
 
 #[cfg(feature = "native")]
 use cli_parser::{derive_cli_wallet_arg, CliParserMacro};
@@ -39,9 +42,11 @@ use offchain::offchain_generator;
 use proc_macro::TokenStream;
 #[cfg(feature = "native")]
 use rpc::ExposeRpcMacro;
+#[cfg(feature = "native")]
+use runtime_call_schema::RuntimeCallSchemaMacro;
 use syn::{parse_macro_input, DeriveInput, ItemFn};
 
-#[proc_macro_derive(ModuleInfo, attributes(state, module, address, gas))]
+#[proc_macro_derive(ModuleInfo, attributes(state, module, address, gas, phantom))]
 pub fn module_info(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
 
@@ -50,7 +55,7 @@ pub fn module_info(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(
     KernelModuleInfo,
-    attributes(state, module, kernel_module, address, gas)
+    attributes(state, module, kernel_module, address, gas, phantom)
 )]
 pub fn kernel_module_info(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
@@ -90,6 +95,12 @@ pub fn event(input: TokenStream) -> TokenStream {
     handle_macro_error(event_macro.derive_event_enum(input))
 }
 
+#[proc_macro_derive(ModuleEvent, attributes(event, indexed))]
+pub fn module_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+    handle_macro_error(module_event::derive_module_event(input))
+}
+
 #[proc_macro_derive(ModuleCallJsonSchema)]
 pub fn module_call_json_schema(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
@@ -226,6 +237,14 @@ pub fn cli_parser(input: TokenStream) -> TokenStream {
     let cli_parser = CliParserMacro::new("Cmd");
     handle_macro_error(cli_parser.cli_macro(input))
 }
+#[cfg(feature = "native")]
+#[proc_macro_derive(RuntimeCallSchema, attributes(schema_skip))]
+pub fn runtime_call_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+    let schema_macro = RuntimeCallSchemaMacro::new("RuntimeCallSchema");
+    handle_macro_error(schema_macro.derive_runtime_call_schema(input))
+}
+
 #[cfg(feature = "native")]
 #[proc_macro_derive(CliWalletArg)]
 pub fn custom_enum_clap(input: TokenStream) -> TokenStream {
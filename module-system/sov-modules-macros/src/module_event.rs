@@ -0,0 +1,111 @@
+use proc_macro2::{Ident, TokenStream};
+use syn::{DataStruct, DeriveInput, Lit, Meta, NestedMeta};
+
+const EVENT_ATTR: &str = "event";
+const INDEXED_ATTR: &str = "indexed";
+
+/// The name and the version of an event, parsed from the `#[event(name = "...", version = N)]` attribute.
+struct EventAttributes {
+    name: String,
+    version: u8,
+}
+
+impl EventAttributes {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = Self {
+            name: input.ident.to_string(),
+            version: 0,
+        };
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(EVENT_ATTR))
+        {
+            let syntax_err = syn::Error::new_spanned(
+                attr,
+                "Invalid syntax for the `#[event]` attribute, expected `#[event(name = \"...\", version = N)]`.",
+            );
+            let Meta::List(list) = attr.parse_meta()? else {
+                return Err(syntax_err);
+            };
+
+            for nested in list.nested.iter() {
+                let NestedMeta::Meta(Meta::NameValue(name_value)) = nested else {
+                    return Err(syntax_err);
+                };
+                match (
+                    name_value.path.get_ident().map(Ident::to_string).as_deref(),
+                    &name_value.lit,
+                ) {
+                    (Some("name"), Lit::Str(name)) => attributes.name = name.value(),
+                    (Some("version"), Lit::Int(version)) => {
+                        attributes.version = version.base10_parse()?
+                    }
+                    _ => return Err(syntax_err),
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+fn data_to_struct(data: &syn::Data) -> syn::Result<&DataStruct> {
+    match data {
+        syn::Data::Struct(data_struct) => Ok(data_struct),
+        syn::Data::Enum(en) => Err(syn::Error::new_spanned(
+            en.enum_token,
+            "The `ModuleEvent` macro supports structs only.",
+        )),
+        syn::Data::Union(un) => Err(syn::Error::new_spanned(
+            un.union_token,
+            "The `ModuleEvent` macro supports structs only.",
+        )),
+    }
+}
+
+pub(crate) fn derive_module_event(input: DeriveInput) -> syn::Result<proc_macro::TokenStream> {
+    let EventAttributes { name, version } = EventAttributes::parse(&input)?;
+    let data_struct = data_to_struct(&input.data)?;
+
+    let mut indexed_attributes = Vec::new();
+    for field in data_struct.fields.iter() {
+        if !field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident(INDEXED_ATTR))
+        {
+            continue;
+        }
+        let Some(ident) = &field.ident else {
+            return Err(syn::Error::new_spanned(
+                field,
+                "Only the named fields of an event can be indexed.",
+            ));
+        };
+        let attribute_name = ident.to_string();
+        indexed_attributes.push(quote::quote! {
+            ::sov_modules_api::EventAttribute::new(
+                #attribute_name,
+                ::borsh::BorshSerialize::try_to_vec(&self.#ident)
+                    .expect("Serializing an event attribute to a vector can't fail"),
+            )
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let tokens: TokenStream = quote::quote! {
+        impl #impl_generics ::sov_modules_api::ModuleEvent for #ident #type_generics #where_clause {
+            const NAME: &'static str = #name;
+            const VERSION: u8 = #version;
+
+            fn indexed_attributes(&self) -> ::std::vec::Vec<::sov_modules_api::EventAttribute> {
+                ::std::vec![#(#indexed_attributes),*]
+            }
+        }
+    };
+
+    Ok(tokens.into())
+}
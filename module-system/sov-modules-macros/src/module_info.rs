@@ -110,6 +110,10 @@ fn impl_module_info(
                 impl_self_init.push(make_init_gas_config(ident, field)?);
                 impl_self_body.push(&field.ident);
             }
+            ModuleFieldAttribute::Phantom => {
+                impl_self_init.push(make_init_phantom(field)?);
+                impl_self_body.push(&field.ident);
+            }
         };
     }
 
@@ -257,6 +261,15 @@ fn make_init_gas_config(
     Manifest::read_constants(parent)?.parse_gas_config(ty, field_ident)
 }
 
+fn make_init_phantom(field: &ModuleField) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let field_ident = &field.ident;
+    let ty = &field.ty;
+
+    Ok(quote::quote! {
+        let #field_ident = <#ty as ::std::default::Default>::default();
+    })
+}
+
 fn make_module_prefix_fn(struct_ident: &Ident) -> proc_macro2::TokenStream {
     let body = make_module_prefix_fn_body(struct_ident);
     quote::quote! {
@@ -345,9 +358,14 @@ pub mod parsing {
     pub enum ModuleFieldAttribute {
         Module,
         KernelModule,
-        State { codec_builder: Option<syn::Path> },
+        State {
+            codec_builder: Option<syn::Path>,
+        },
         Address,
         Gas,
+        /// A field which isn't stored in the state, such as a `PhantomData` carrying a generic parameter.
+        /// It is initialized with its default value.
+        Phantom,
     }
 
     impl ModuleFieldAttribute {
@@ -394,6 +412,16 @@ pub mod parsing {
                         ))
                     }
                 }
+                "phantom" => {
+                    if attr.tokens.is_empty() {
+                        Ok(Self::Phantom)
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            attr,
+                            "The `#[phantom]` attribute does not accept any arguments.",
+                        ))
+                    }
+                }
                 _ => unreachable!("attribute names were validated already; this is a bug"),
             }
         }
@@ -518,9 +546,9 @@ pub mod parsing {
         let mut attr = None;
         for a in field.attrs.iter() {
             match a.path.segments[0].ident.to_string().as_str() {
-                "state" | "module" | "address" | "gas" | "kernel_module" => {
+                "state" | "module" | "address" | "gas" | "kernel_module" | "phantom" => {
                     if attr.is_some() {
-                        return Err(syn::Error::new_spanned(ident, "Only one attribute out of `#[kernel_module]`, `#[module]`, `#[state]`, `#[address]`, and #[gas] is allowed per field."));
+                        return Err(syn::Error::new_spanned(ident, "Only one attribute out of `#[kernel_module]`, `#[module]`, `#[state]`, `#[address]`, #[gas], and #[phantom] is allowed per field."));
                     } else {
                         attr = Some(a);
                     }
@@ -534,7 +562,7 @@ pub mod parsing {
         } else {
             Err(syn::Error::new_spanned(
                 ident,
-                format!("The field `{}` is missing an attribute: add `#[kernel_module]`, `#[module]`, `#[state]`, `#[address]`, or #[gas].", ident),
+                format!("The field `{}` is missing an attribute: add `#[kernel_module]`, `#[module]`, `#[state]`, `#[address]`, #[gas], or #[phantom].", ident),
             ))
         }
     }
@@ -0,0 +1,59 @@
+use syn::DeriveInput;
+
+use crate::common::StructFieldExtractor;
+
+pub(crate) struct RuntimeCallSchemaMacro {
+    field_extractor: StructFieldExtractor,
+}
+
+impl RuntimeCallSchemaMacro {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            field_extractor: StructFieldExtractor::new(name),
+        }
+    }
+
+    pub(crate) fn derive_runtime_call_schema(
+        &self,
+        input: DeriveInput,
+    ) -> Result<proc_macro::TokenStream, syn::Error> {
+        let DeriveInput {
+            ident,
+            generics,
+            data,
+            ..
+        } = input;
+        let fields = self.field_extractor.get_fields_from_struct(&data)?;
+        let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+        // The index of a module is its position among all the fields, skipped or not,
+        // as it is the index of its variant in the runtime call.
+        let mut module_schemas = Vec::new();
+        for (index, field) in fields.iter().enumerate() {
+            if field
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("schema_skip"))
+            {
+                continue;
+            }
+            let index = u8::try_from(index).map_err(|_| {
+                syn::Error::new_spanned(&field.ident, "A runtime can have at most 256 modules")
+            })?;
+            let name = field.ident.to_string();
+            let ty = &field.ty;
+            module_schemas.push(quote::quote! {
+                ::sov_modules_api::schema::ModuleCallSchema::new::<#ty>(#name, #index)?
+            });
+        }
+
+        Ok(quote::quote! {
+            impl #impl_generics ::sov_modules_api::schema::RuntimeCallSchema for #ident #type_generics #where_clause {
+                fn module_call_schemas() -> ::anyhow::Result<::std::vec::Vec<::sov_modules_api::schema::ModuleCallSchema>> {
+                    Ok(::std::vec![#(#module_schemas),*])
+                }
+            }
+        }
+        .into())
+    }
+}
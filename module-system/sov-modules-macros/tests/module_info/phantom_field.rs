@@ -0,0 +1,19 @@
+use std::marker::PhantomData;
+
+use sov_modules_api::{Context, ModuleInfo, StateMap};
+
+#[derive(ModuleInfo)]
+struct TestStruct<C: Context, T> {
+    #[address]
+    pub address: C::Address,
+
+    #[state]
+    pub test_state: StateMap<u32, u32>,
+
+    #[phantom]
+    pub phantom: PhantomData<T>,
+}
+
+fn main() {
+    let _ = TestStruct::<sov_modules_api::default_context::ZkDefaultContext, u32>::default();
+}
@@ -2,12 +2,15 @@
 #![doc = include_str!("../README.md")]
 
 mod runtime_rpc;
+mod snapshot;
 mod wallet;
 use std::net::SocketAddr;
 
 use async_trait::async_trait;
 pub use runtime_rpc::*;
+pub use snapshot::*;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::DEFAULT_PRUNING_INTERVAL;
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{Context, DaSpec, Spec};
 use sov_modules_stf_blueprint::{GenesisParams, Runtime as RuntimeTrait, StfBlueprint};
@@ -17,7 +20,8 @@ use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{
-    InitVariant, ProverService, RollupConfig, RollupProverConfig, StateTransitionRunner,
+    subscriptions_rpc_module, InitVariant, ProverService, RollupConfig, RollupProverConfig,
+    StateTransitionRunner,
 };
 use tokio::sync::oneshot;
 pub use wallet::*;
@@ -120,9 +124,16 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         rollup_config: &RollupConfig<Self::DaConfig>,
     ) -> Result<Self::StorageManager, anyhow::Error>;
 
-    /// Creates instance of a LedgerDB.
+    /// Creates instance of a LedgerDB, pruned in the background according to the pruning mode of the config.
     fn create_ledger_db(&self, rollup_config: &RollupConfig<Self::DaConfig>) -> LedgerDB {
-        LedgerDB::with_path(&rollup_config.storage.path).expect("Ledger DB failed to open")
+        let mut ledger_db =
+            LedgerDB::with_path(&rollup_config.storage.path).expect("Ledger DB failed to open");
+        if let Some(retained_slots) = rollup_config.storage.pruning.retained_versions() {
+            ledger_db
+                .start_pruning(retained_slots, DEFAULT_PRUNING_INTERVAL)
+                .expect("Ledger DB pruning task failed to start");
+        }
+        ledger_db
     }
 
     /// Creates a new rollup.
@@ -139,6 +150,10 @@ pub trait RollupBlueprint: Sized + Send + Sync {
     where
         <Self::NativeContext as Spec>::Storage: NativeStorage,
     {
+        // Addresses are parsed from the genesis files and displayed by the RPC methods with the prefix of the chain.
+        sov_modules_api::set_address_prefix(&rollup_config.chain.address_prefix)
+            .map_err(|e| anyhow::anyhow!("Invalid address prefix: {}", e))?;
+
         let da_service = self.create_da_service(&rollup_config).await;
         // TODO: Double check what kind of storage needed here.
         // Maybe whole "prev_root" can be initialized inside runner
@@ -220,7 +235,9 @@ impl<S: RollupBlueprint> Rollup<S> {
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) -> Result<(), anyhow::Error> {
         let mut runner = self.runner;
-        runner.start_rpc_server(self.rpc_methods, channel).await;
+        let mut rpc_methods = self.rpc_methods;
+        rpc_methods.merge(subscriptions_rpc_module(runner.notifications())?)?;
+        runner.start_rpc_server(rpc_methods, channel).await;
         runner.run_in_process().await?;
         Ok(())
     }
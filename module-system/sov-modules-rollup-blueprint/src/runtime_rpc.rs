@@ -1,12 +1,18 @@
 use anyhow::Context as _;
 use sov_db::ledger_db::LedgerDB;
+use sov_modules_api::runtime::capabilities::KernelSlotHooks;
 use sov_modules_api::{Context, Spec};
+use sov_modules_stf_blueprint::schema::get_schema_rpc;
+use sov_modules_stf_blueprint::simulation::get_simulation_rpc;
+use sov_modules_stf_blueprint::state_proof::get_state_proof_rpc;
 use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, SequencerOutcome, TxEffect};
 use sov_rollup_interface::services::da::DaService;
-use sov_sequencer::batch_builder::FiFoStrictBatchBuilder;
+use sov_sequencer::batch_builder::{BatchPolicy, PriorityBatchBuilder};
+use sov_sequencer::mempool::MempoolConfig;
+use sov_state::storage::NativeStorage;
 
 /// Register rollup's default rpc methods.
-pub fn register_rpc<RT, C, Da>(
+pub fn register_rpc<RT, K, C, Da>(
     storage: &<C as Spec>::Storage,
     ledger_db: &LedgerDB,
     da_service: &Da,
@@ -14,7 +20,9 @@ pub fn register_rpc<RT, C, Da>(
 ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error>
 where
     RT: RuntimeTrait<C, <Da as DaService>::Spec> + Send + Sync + 'static,
+    K: KernelSlotHooks<C, <Da as DaService>::Spec> + Send + Sync + 'static,
     C: Context,
+    <C as Spec>::Storage: NativeStorage,
     Da: DaService + Clone,
 {
     // runtime rpc.
@@ -29,11 +37,24 @@ where
         >(ledger_db.clone())?)?;
     }
 
+    // simulation rpc.
+    {
+        rpc_methods.merge(get_simulation_rpc::<C, Da::Spec, RT, K>(
+            storage.clone(),
+            sequencer.clone(),
+        ))?;
+    }
+
+    // state proof rpc.
+    {
+        rpc_methods.merge(get_state_proof_rpc(storage.clone()))?;
+    }
+
     // sequencer rpc.
     {
-        let batch_builder = FiFoStrictBatchBuilder::new(
-            1024 * 100,
-            u32::MAX as usize,
+        let batch_builder = PriorityBatchBuilder::new(
+            BatchPolicy::default(),
+            MempoolConfig::default(),
             RT::default(),
             storage.clone(),
             sequencer,
@@ -45,5 +66,15 @@ where
             .context("Failed to merge Txs RPC modules")?;
     }
 
+    // schema rpc.
+    {
+        let method_names = rpc_methods
+            .method_names()
+            .chain(["rollup_schema"])
+            .map(String::from)
+            .collect();
+        rpc_methods.merge(get_schema_rpc::<C, Da::Spec, RT>(method_names))?;
+    }
+
     Ok(rpc_methods)
 }
@@ -0,0 +1,107 @@
+use std::hash::Hash;
+use std::path::Path;
+
+use anyhow::Context as _;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_modules_api::DaSpec;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_state::config::Config as StorageConfig;
+use sov_state::snapshot::StateSnapshot;
+use sov_state::MerkleProofSpec;
+
+/// A snapshot of a rollup node, holding the state at a finalized slot.
+/// A new node can import it, and then process the DA blocks following the slot instead of
+/// replaying the chain from genesis.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct RollupSnapshot {
+    /// The number of the slot the state was taken at.
+    pub slot_number: u64,
+    /// The hash of the DA block of the slot.
+    pub slot_hash: [u8; 32],
+    /// The state after processing the slot.
+    pub state: StateSnapshot,
+}
+
+impl RollupSnapshot {
+    /// Writes the snapshot to the file at `path`.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let bytes = self.try_to_vec()?;
+        std::fs::write(path, bytes)
+            .with_context(|| format!("Failed to write snapshot to {}", path.display()))
+    }
+
+    /// Reads the snapshot from the file at `path`.
+    pub fn read_from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read snapshot from {}", path.display()))?;
+        Self::try_from_slice(&bytes)
+            .with_context(|| format!("Invalid snapshot file {}", path.display()))
+    }
+}
+
+/// Takes a snapshot of the state at the latest slot of the node storing its data according to `storage_config`.
+/// The node must not be running.
+pub fn export_snapshot<Da: DaSpec, S: MerkleProofSpec>(
+    storage_config: StorageConfig,
+) -> anyhow::Result<RollupSnapshot>
+where
+    Da::SlotHash: Hash,
+{
+    let ledger_db = LedgerDB::with_path(&storage_config.path)?;
+    let (slot_number, slot) = ledger_db
+        .get_head_slot()?
+        .context("Cannot take a snapshot of a node which has not processed any slot")?;
+
+    let mut storage_manager = ProverStorageManager::<Da, S>::new(storage_config)?;
+    let state = storage_manager.export_snapshot(slot_number.0)?;
+
+    Ok(RollupSnapshot {
+        slot_number: slot_number.0,
+        slot_hash: slot.hash,
+        state,
+    })
+}
+
+/// Imports `snapshot` into the empty storage described by `storage_config`. The node then resumes
+/// from the slot after the snapshot.
+///
+/// The root of the imported state is recomputed from its key-value pairs, and must match the root of
+/// the snapshot, and `trusted_root` if any. Since the snapshot file is not authenticated, `trusted_root` should be
+/// obtained from a trusted source, like an operator of the rollup or a proof settled on the DA layer.
+pub fn import_snapshot<Da: DaSpec, S: MerkleProofSpec>(
+    storage_config: StorageConfig,
+    snapshot: RollupSnapshot,
+    trusted_root: Option<[u8; 32]>,
+) -> anyhow::Result<()>
+where
+    Da::SlotHash: Hash,
+{
+    if let Some(trusted_root) = trusted_root {
+        if trusted_root != snapshot.state.root {
+            anyhow::bail!(
+                "The snapshot has root 0x{}, but the trusted root is 0x{}",
+                hex::encode(snapshot.state.root),
+                hex::encode(trusted_root)
+            );
+        }
+    }
+    if snapshot.state.version != snapshot.slot_number {
+        anyhow::bail!(
+            "The state of the snapshot is at version {}, but the snapshot was taken at slot {}",
+            snapshot.state.version,
+            snapshot.slot_number
+        );
+    }
+
+    let ledger_db = LedgerDB::with_path(&storage_config.path)?;
+    if ledger_db.get_head_slot()?.is_some() {
+        anyhow::bail!("Cannot import a snapshot into a node which has already processed slots");
+    }
+
+    let mut storage_manager = ProverStorageManager::<Da, S>::new(storage_config)?;
+    storage_manager.import_snapshot(snapshot.state)?;
+    ledger_db.import_snapshot_head(snapshot.slot_number, snapshot.slot_hash)
+}
@@ -3,7 +3,10 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_cli::wallet_state::WalletState;
+use sov_cli::workflows::address_book::AddressBookWorkflow;
+use sov_cli::workflows::genesis::GenesisWorkflow;
 use sov_cli::workflows::keys::KeyWorkflow;
+use sov_cli::workflows::offline::TxWorkflow;
 use sov_cli::workflows::rpc::RpcWorkflows;
 use sov_cli::workflows::transactions::TransactionWorkflow;
 use sov_cli::{clap, wallet_dir};
@@ -22,6 +25,12 @@ enum Workflows<File: clap::Subcommand, Json: clap::Subcommand, C: Context> {
     Keys(KeyWorkflow<C>),
     #[clap(subcommand)]
     Rpc(RpcWorkflows<C>),
+    #[clap(subcommand)]
+    Tx(TxWorkflow<C>),
+    #[clap(subcommand)]
+    AddressBook(AddressBookWorkflow<C>),
+    #[clap(subcommand)]
+    Genesis(GenesisWorkflow),
 }
 
 #[derive(clap::Parser)]
@@ -92,6 +101,13 @@ where
             Workflows::Rpc(inner) => {
                 inner.run(&mut wallet_state, app_dir).await?;
             }
+            Workflows::Tx(inner) => {
+                inner.run(&mut wallet_state, app_dir).await?;
+            }
+            Workflows::AddressBook(inner) => inner.run(&mut wallet_state)?,
+            Workflows::Genesis(inner) => inner
+                .run::<<Self as RollupBlueprint>::NativeContext, <Self as RollupBlueprint>::DaSpec>(
+                )?,
         }
 
         wallet_state.save(wallet_state_path)
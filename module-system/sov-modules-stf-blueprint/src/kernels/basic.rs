@@ -6,7 +6,7 @@ use sov_chain_state::ChainState;
 use sov_modules_api::runtime::capabilities::{
     BlobRefOrOwned, BlobSelector, Kernel, KernelSlotHooks,
 };
-use sov_modules_api::{Context, DaSpec, KernelModule, WorkingSet};
+use sov_modules_api::{BlobReaderTrait, Context, DaSpec, KernelModule, WorkingSet};
 use sov_state::Storage;
 
 /// The simplest imaginable kernel. It does not do any batching or reordering of blobs.
@@ -43,7 +43,21 @@ impl<C: Context, Da: DaSpec> Kernel<C, Da> for BasicKernel<C, Da> {
         self.chain_state.true_slot_height(working_set)
     }
     fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64 {
-        self.chain_state.true_slot_height(working_set)
+        self.chain_state.visible_slot_height(working_set)
+    }
+    fn chain_id(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.chain_state.get_chain_id(working_set)
+    }
+    fn base_fee_per_gas(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        self.chain_state.base_fee_per_gas(working_set)
+    }
+    fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>) {
+        self.chain_state.record_gas_used(gas_used, working_set)
+    }
+
+    fn record_events_root(&self, events_root: [u8; 32], working_set: &mut WorkingSet<C>) {
+        self.chain_state
+            .record_events_root(events_root, working_set)
     }
 
     type GenesisConfig = BasicKernelGenesisConfig<C, Da>;
@@ -66,13 +80,21 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BasicKernel<C, Da> {
     fn get_blobs_for_this_slot<'a, 'k, I>(
         &self,
         current_blobs: I,
-        _working_set: &mut sov_modules_api::KernelWorkingSet<'k, Self::Context>,
+        working_set: &mut sov_modules_api::KernelWorkingSet<'k, Self::Context>,
     ) -> anyhow::Result<Vec<BlobRefOrOwned<'a, Da::BlobTransaction>>>
     where
         I: IntoIterator<Item = &'a mut Da::BlobTransaction>,
     {
-        self.blob_storage
-            .get_blobs_for_this_slot(current_blobs, _working_set)
+        let selected_blobs = self
+            .blob_storage
+            .get_blobs_for_this_slot(current_blobs, working_set)?;
+
+        self.chain_state.record_slot_sequencers(
+            selected_blobs.iter().map(|blob| blob.as_ref().sender()),
+            working_set,
+        );
+
+        Ok(selected_blobs)
     }
 }
 
@@ -86,7 +108,14 @@ impl<C: Context, Da: DaSpec> KernelSlotHooks<C, Da> for BasicKernel<C, Da> {
     ) {
         let mut ws = sov_modules_api::KernelWorkingSet::from_kernel(self, working_set);
         self.chain_state
-            .begin_slot_hook(slot_header, validity_condition, pre_state_root, &mut ws);
+            .begin_slot_hook(slot_header, validity_condition, pre_state_root, &mut ws)
+            .expect("The chain state must be initialized at genesis");
+
+        // Blobs are executed as soon as they appear on DA, so the visible height never lags.
+        let true_height = self.chain_state.true_slot_height(ws.inner);
+        self.chain_state
+            .update_visible_slot_height(true_height, &mut ws)
+            .expect("The visible slot height can always catch up with the true slot height");
     }
 
     fn end_slot_hook(&self, working_set: &mut sov_modules_api::WorkingSet<Self::Context>) {
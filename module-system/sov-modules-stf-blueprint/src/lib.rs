@@ -3,15 +3,23 @@
 
 mod batch;
 pub mod kernels;
+mod scheduler;
+#[cfg(feature = "native")]
+pub mod schema;
+#[cfg(feature = "native")]
+pub mod simulation;
+#[cfg(feature = "native")]
+pub mod state_proof;
 mod stf_blueprint;
 mod tx_verifier;
 
 pub use batch::Batch;
+use sov_modules_api::events::events_root;
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{
     BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, KernelWorkingSet, Spec,
-    StateCheckpoint, Zkvm,
+    StateAccessGasCosts, StateCheckpoint, WorkingSet, Zkvm,
 };
 pub use sov_rollup_interface::stf::BatchReceipt;
 use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
@@ -64,6 +72,56 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     fn genesis_config(
         genesis_paths: &Self::GenesisPaths,
     ) -> Result<Self::GenesisConfig, anyhow::Error>;
+
+    #[cfg(feature = "native")]
+    /// The schema of the call messages and of the transactions of the runtime. Runtimes deriving
+    /// [`RuntimeCallSchema`](sov_modules_api::schema::RuntimeCallSchema) can return
+    /// `RuntimeSchema::of::<Self>()`.
+    fn schema() -> Result<sov_modules_api::schema::RuntimeSchema, anyhow::Error> {
+        anyhow::bail!("The runtime doesn't export its schema")
+    }
+
+    /// The gas charged for every access to the provable state made by the call of a transaction, on top of the
+    /// gas charged by the modules themselves. The hooks run for free. State accesses are free by default.
+    fn state_access_gas_costs() -> StateAccessGasCosts<C::GasUnit> {
+        StateAccessGasCosts::default()
+    }
+
+    /// The limits on the resources used by the batches and the transactions of the rollup.
+    fn resource_limits() -> ResourceLimits {
+        ResourceLimits::default()
+    }
+
+    /// Returns true if the call messages of `module` must not be dispatched, e.g. because the module was paused
+    /// after a bug was found in it. The transactions calling a paused module are reverted.
+    fn is_module_paused(&self, _module: &C::Address, _working_set: &mut WorkingSet<C>) -> bool {
+        false
+    }
+}
+
+/// The limits on the resources used by a batch, which protect the prover from batches too expensive to process.
+/// The sequencer of a batch exceeding the limits on the blob, the transactions or the call messages is slashed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The maximum size of a blob, in bytes.
+    pub max_blob_size: usize,
+    /// The maximum number of transactions in a batch.
+    pub max_txs_per_batch: usize,
+    /// The maximum size of the runtime message of a transaction, in bytes.
+    pub max_call_message_size: usize,
+    /// The maximum number of events emitted by a transaction. Transactions emitting more events are reverted.
+    pub max_events_per_tx: usize,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_blob_size: 4 * 1024 * 1024,
+            max_txs_per_batch: 10_000,
+            max_call_message_size: 128 * 1024,
+            max_events_per_tx: 1024,
+        }
+    }
 }
 
 /// The receipts of all the transactions in a batch.
@@ -109,6 +167,12 @@ pub enum SlashingReason {
     StatelessVerificationFailed,
     /// This status indicates problem with transaction deserialization.
     InvalidTransactionEncoding,
+    /// The blob is larger than [`ResourceLimits::max_blob_size`].
+    BlobTooLarge,
+    /// The batch has more transactions than [`ResourceLimits::max_txs_per_batch`].
+    TooManyTransactions,
+    /// The runtime message of a transaction is larger than [`ResourceLimits::max_call_message_size`].
+    CallMessageTooLarge,
 }
 
 impl<C, RT, Vm, Da, K> StfBlueprint<C, Da, Vm, RT, K>
@@ -155,9 +219,10 @@ where
         <<C as Spec>::Storage as Storage>::Witness,
         C::Storage,
     ) {
-        // Run end end_slot_hook
+        // Run the end_slot_hook of the runtime, then the one of the kernel, mirroring the order of the begin_slot_hooks
         let mut working_set = checkpoint.to_revertable();
         self.runtime.end_slot_hook(&mut working_set);
+        self.kernel.end_slot_hook(&mut working_set);
         // Save checkpoint
         let mut checkpoint = working_set.checkpoint();
 
@@ -303,6 +368,16 @@ where
             batch_receipts.push(batch_receipt);
         }
 
+        // Commit to the events of all the transactions of the slot, in execution order
+        let events = batch_receipts
+            .iter()
+            .flat_map(|batch_receipt| batch_receipt.tx_receipts.iter())
+            .flat_map(|tx_receipt| tx_receipt.events.iter());
+        let mut working_set = checkpoint.to_revertable();
+        self.kernel
+            .record_events_root(events_root::<C::Hasher>(events), &mut working_set);
+        let checkpoint = working_set.checkpoint();
+
         let (state_root, witness, storage) = self.end_slot(pre_state, checkpoint);
         SlotResult {
             state_root,
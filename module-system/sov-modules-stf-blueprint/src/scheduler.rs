@@ -0,0 +1,84 @@
+//! Scheduling of the transactions of a batch into waves of transactions which can be executed in parallel.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use sov_modules_api::transaction::AccessList;
+#[cfg(feature = "native")]
+use sov_modules_core::{CacheKey, StateAccesses};
+
+/// Splits a batch into consecutive waves of transactions with non-conflicting access lists.
+///
+/// Two transactions conflict if one of them writes a key declared by the other. A transaction without an
+/// access list is alone in its wave. The access lists are only declarations: the transactions of a wave can
+/// only be executed in parallel once the keys they accessed, hooks included, are checked against them with
+/// `accesses_declared`.
+pub(crate) fn schedule<'a>(
+    access_lists: impl IntoIterator<Item = Option<&'a AccessList>>,
+) -> Vec<Range<usize>> {
+    let mut waves = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+    let mut reads: HashSet<&[u8]> = HashSet::new();
+    let mut writes: HashSet<&[u8]> = HashSet::new();
+
+    for (index, access_list) in access_lists.into_iter().enumerate() {
+        len = index + 1;
+        let Some(access_list) = access_list else {
+            if start < index {
+                waves.push(start..index);
+            }
+            waves.push(index..index + 1);
+            start = index + 1;
+            reads.clear();
+            writes.clear();
+            continue;
+        };
+
+        let conflicts = access_list
+            .writes
+            .iter()
+            .any(|key| reads.contains(key.as_slice()) || writes.contains(key.as_slice()))
+            || access_list
+                .reads
+                .iter()
+                .any(|key| writes.contains(key.as_slice()));
+        if conflicts {
+            waves.push(start..index);
+            start = index;
+            reads.clear();
+            writes.clear();
+        }
+
+        reads.extend(access_list.reads.iter().map(Vec::as_slice));
+        writes.extend(access_list.writes.iter().map(Vec::as_slice));
+    }
+
+    if start < len {
+        waves.push(start..len);
+    }
+    waves
+}
+
+/// Returns true if the keys accessed through a fork were all declared in the access list of its transaction.
+#[cfg(feature = "native")]
+pub(crate) fn accesses_declared(
+    accesses: Option<&StateAccesses>,
+    access_list: Option<&AccessList>,
+) -> bool {
+    let (Some(accesses), Some(access_list)) = (accesses, access_list) else {
+        return false;
+    };
+    let declared = |keys: &[Vec<u8>], key: &CacheKey| {
+        keys.iter()
+            .any(|declared| declared.as_slice() == key.key.as_slice())
+    };
+    accesses
+        .writes
+        .iter()
+        .all(|key| declared(&access_list.writes, key))
+        && accesses
+            .reads
+            .iter()
+            .all(|key| declared(&access_list.reads, key) || declared(&access_list.writes, key))
+}
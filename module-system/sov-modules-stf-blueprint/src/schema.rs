@@ -0,0 +1,40 @@
+//! Export of the schema of the runtime over RPC.
+
+use jsonrpsee::RpcModule;
+use sov_modules_api::schema::RuntimeSchema;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{Context, DaSpec};
+
+use crate::Runtime;
+
+const SCHEMA_RPC_ERROR: &str = "SCHEMA_RPC_ERROR";
+
+/// The schema served by the `rollup_schema` RPC method.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RollupSchema {
+    /// The schema of the call messages and of the transactions of the runtime.
+    pub runtime: RuntimeSchema,
+    /// The names of the RPC methods served by the node, including the queries of the modules.
+    pub rpc_methods: Vec<String>,
+}
+
+/// Creates an RPC module with the `rollup_schema` method, which returns the [`RollupSchema`] of the
+/// runtime `RT`, listing `rpc_methods` as the methods served by the node.
+pub fn get_schema_rpc<C, Da, RT>(rpc_methods: Vec<String>) -> RpcModule<Vec<String>>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+{
+    let mut rpc = RpcModule::new(rpc_methods);
+    rpc.register_method("rollup_schema", |_, rpc_methods| {
+        RT::schema()
+            .map(|runtime| RollupSchema {
+                runtime,
+                rpc_methods: rpc_methods.clone(),
+            })
+            .map_err(|e| to_jsonrpsee_error_object(e, SCHEMA_RPC_ERROR))
+    })
+    .expect("Failed to register schema RPC methods");
+    rpc
+}
@@ -0,0 +1,264 @@
+//! Dry-run execution of transactions against the current state, without committing anything.
+
+use std::marker::PhantomData;
+
+use anyhow::Context as _;
+use borsh::BorshDeserialize;
+use jsonrpsee::RpcModule;
+use sov_modules_api::runtime::capabilities::KernelSlotHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{Context, DaSpec, DispatchCall, GasUnit, StateCheckpoint};
+use sov_modules_core::WorkingSet;
+use sov_rollup_interface::stf::Event;
+
+use crate::{Runtime, RuntimeTxHook, TxEffect};
+
+const SIMULATION_RPC_ERROR: &str = "SIMULATION_RPC_ERROR";
+
+/// A transaction to simulate.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(bound = "", rename_all = "snake_case")]
+pub enum SimulatedTx<C: Context> {
+    /// A signed and serialized transaction, which goes through the same checks as in a batch:
+    /// signature, chain id and the stateful checks of the runtime, like the nonce.
+    Signed {
+        /// The serialized transaction.
+        body: Vec<u8>,
+    },
+    /// A serialized runtime message, executed on behalf of `sender` without any signature,
+    /// chain id or nonce check. Useful to preview a transaction before signing it.
+    Unsigned {
+        /// The sender of the message.
+        sender: C::Address,
+        /// The serialized runtime message.
+        runtime_msg: Vec<u8>,
+        /// The gas limit of the execution.
+        gas_limit: u64,
+    },
+}
+
+/// A write to the provable state made by a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StateDiff {
+    /// The storage key.
+    pub key: Vec<u8>,
+    /// The new value, or `None` if the key was deleted.
+    pub value: Option<Vec<u8>>,
+}
+
+/// The outcome of a simulated transaction.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SimulationResult {
+    /// Whether the transaction would succeed or be reverted.
+    pub effect: TxEffect,
+    /// The reason of the revert, if the transaction would be reverted.
+    pub revert_reason: Option<String>,
+    /// The gas used by the transaction, per dimension.
+    pub gas_used: Vec<u64>,
+    /// The events emitted by the transaction. A reverted transaction emits no events.
+    pub events: Vec<Event>,
+    /// The writes to the provable state, in order. The writes of a reverted transaction are discarded,
+    /// except for the ones of the runtime hooks, like the increment of the nonce.
+    pub state_diff: Vec<StateDiff>,
+}
+
+/// Executes transactions against a snapshot of the latest committed state, the same way as the
+/// [`StfBlueprint`](crate::StfBlueprint) does in a batch, and discards the changes.
+pub struct Simulator<C: Context, Da: DaSpec, RT: Runtime<C, Da>, K: KernelSlotHooks<C, Da>> {
+    runtime: RT,
+    kernel: K,
+    storage: C::Storage,
+    sequencer: C::Address,
+    phantom_da: PhantomData<Da>,
+}
+
+impl<C, Da, RT, K> Simulator<C, Da, RT, K>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+    K: KernelSlotHooks<C, Da>,
+{
+    /// Creates a simulator reading the state from `storage`. Unsigned transactions are executed
+    /// with `sequencer` as the sequencer.
+    pub fn new(storage: C::Storage, sequencer: C::Address) -> Self {
+        Self {
+            runtime: RT::default(),
+            kernel: K::default(),
+            storage,
+            sequencer,
+            phantom_da: PhantomData,
+        }
+    }
+
+    /// Simulates the transaction `tx`. Returns an error if the transaction can't be included in a batch
+    /// at all, because it can't be deserialized, isn't correctly signed or its message can't be decoded.
+    pub fn simulate(&self, tx: SimulatedTx<C>) -> anyhow::Result<SimulationResult> {
+        let mut working_set = StateCheckpoint::<C>::new(self.storage.clone()).to_revertable();
+        let gas_price = self.kernel.base_fee_per_gas(&mut working_set);
+        let visible_height = self.kernel.visible_height(&mut working_set);
+        let chain_id = self.kernel.chain_id(&mut working_set);
+        // The kernel reads aren't part of the transaction
+        let mut working_set = working_set.revert().to_revertable();
+
+        match tx {
+            SimulatedTx::Signed { body } => {
+                let tx = Transaction::<C>::try_from_slice(&body)
+                    .context("Failed to deserialize transaction")?;
+                tx.verify().context("Failed to verify transaction")?;
+                let msg = RT::decode_call(tx.runtime_msg())
+                    .map_err(anyhow::Error::new)
+                    .context("Failed to decode message in transaction")?;
+
+                working_set.set_gas(tx.gas_limit(), gas_price);
+                if tx.chain_id() != chain_id {
+                    let reason = format!(
+                        "Transaction was signed for chain id {} but the rollup chain id is {}",
+                        tx.chain_id(),
+                        chain_id
+                    );
+                    return Ok(reverted(working_set, reason));
+                }
+                if let Some(valid_until_slot) = tx.valid_until_slot() {
+                    if visible_height > valid_until_slot {
+                        let reason = format!(
+                            "Transaction expired at slot {} but the current slot is {}",
+                            valid_until_slot, visible_height
+                        );
+                        return Ok(reverted(working_set, reason));
+                    }
+                }
+
+                let hook = RuntimeTxHook {
+                    height: visible_height,
+                    sequencer: tx.pub_key().clone(),
+                };
+                let ctx = match self
+                    .runtime
+                    .pre_dispatch_tx_hook(&tx, &mut working_set, &hook)
+                {
+                    Ok(ctx) => ctx,
+                    Err(e) => {
+                        let reason = format!("Stateful verification error: {}", e);
+                        return Ok(reverted(working_set, reason));
+                    }
+                };
+
+                self.execute(msg, &ctx, Some(&tx), working_set)
+            }
+            SimulatedTx::Unsigned {
+                sender,
+                runtime_msg,
+                gas_limit,
+            } => {
+                let msg = RT::decode_call(&runtime_msg)
+                    .map_err(anyhow::Error::new)
+                    .context("Failed to decode runtime message")?;
+                working_set.set_gas(gas_limit, gas_price);
+                let ctx = C::new(sender, self.sequencer.clone(), visible_height);
+                self.execute(msg, &ctx, None, working_set)
+            }
+        }
+    }
+
+    /// Dispatches `msg` on top of the changes of the pre-dispatch hook, which are kept if the call is reverted,
+    /// and runs the post-dispatch hook of the signed transaction `tx`.
+    fn execute(
+        &self,
+        msg: RT::Decodable,
+        ctx: &C,
+        tx: Option<&Transaction<C>>,
+        working_set: WorkingSet<C>,
+    ) -> anyhow::Result<SimulationResult> {
+        let mut working_set = working_set.checkpoint().to_revertable();
+        // As in the `StfBlueprint`, only the call pays for its state accesses.
+        working_set.set_state_access_gas_costs(RT::state_access_gas_costs());
+        let tx_result = self
+            .runtime
+            .dispatch_call(msg, &mut working_set, ctx)
+            .map_err(|sov_modules_api::Error::ModuleError(err)| err)
+            .and_then(|_| {
+                // State accesses can't fail, so a transaction running out of gas while accessing
+                // the state is only caught here.
+                if working_set.is_out_of_gas() {
+                    Err(anyhow::anyhow!("Transaction ran out of gas"))
+                } else {
+                    Ok(())
+                }
+            });
+        working_set.set_state_access_gas_costs(Default::default());
+
+        let gas_used = working_set.gas_used().to_dimensions();
+        let events = working_set.take_events();
+        let (mut working_set, effect, revert_reason, mut events) = match tx_result {
+            Ok(()) => (working_set, TxEffect::Successful, None, events),
+            Err(err) => (
+                working_set.revert().to_revertable(),
+                TxEffect::Reverted,
+                Some(err.to_string()),
+                Vec::new(),
+            ),
+        };
+
+        if let Some(tx) = tx {
+            self.runtime
+                .post_dispatch_tx_hook(tx, ctx, &mut working_set)
+                .context("Error in post_dispatch_tx_hook")?;
+            events.extend(working_set.take_events());
+        }
+
+        Ok(SimulationResult {
+            effect,
+            revert_reason,
+            gas_used,
+            events,
+            state_diff: state_diff(working_set),
+        })
+    }
+}
+
+/// Returns the result of a transaction rejected before its dispatch, keeping the changes made so far.
+fn reverted<C: Context>(mut working_set: WorkingSet<C>, reason: String) -> SimulationResult {
+    SimulationResult {
+        effect: TxEffect::Reverted,
+        revert_reason: Some(reason),
+        gas_used: working_set.gas_used().to_dimensions(),
+        events: working_set.take_events(),
+        state_diff: state_diff(working_set),
+    }
+}
+
+fn state_diff<C: Context>(working_set: WorkingSet<C>) -> Vec<StateDiff> {
+    let (log, _) = working_set.checkpoint().freeze();
+    log.ordered_writes
+        .into_iter()
+        .map(|(key, value)| StateDiff {
+            key: key.key.to_vec(),
+            value: value.map(|value| value.value.to_vec()),
+        })
+        .collect()
+}
+
+/// Creates an RPC module with the `rollup_simulate` method, which simulates a [`SimulatedTx`]
+/// and returns a [`SimulationResult`].
+pub fn get_simulation_rpc<C, Da, RT, K>(
+    storage: C::Storage,
+    sequencer: C::Address,
+) -> RpcModule<Simulator<C, Da, RT, K>>
+where
+    C: Context,
+    Da: DaSpec + Send + Sync,
+    RT: Runtime<C, Da> + Send + Sync + 'static,
+    K: KernelSlotHooks<C, Da> + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(Simulator::new(storage, sequencer));
+    rpc.register_method("rollup_simulate", |params, simulator| {
+        let tx: SimulatedTx<C> = params.one()?;
+        simulator
+            .simulate(tx)
+            .map_err(|e| to_jsonrpsee_error_object(e, SIMULATION_RPC_ERROR))
+    })
+    .expect("Failed to register simulation RPC methods");
+    rpc
+}
@@ -0,0 +1,77 @@
+//! Merkle proofs of the keys of the rollup state, against the state roots retained by the node.
+
+use anyhow::{bail, ensure};
+use borsh::BorshSerialize;
+use jsonrpsee::RpcModule;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_core::{NativeStorage, StorageKey, Version};
+use sov_rollup_interface::rpc::{utils, StateProofResponse};
+
+const STATE_PROOF_RPC_ERROR: &str = "STATE_PROOF_RPC_ERROR";
+
+/// A request for the proof of a key of the rollup state, served by the `rollup_getProof` RPC method.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StateProofRequest {
+    /// The hex encoded storage key, e.g. as returned by a storage key query of a module.
+    #[serde(with = "utils::rpc_hex")]
+    pub key: Vec<u8>,
+    /// The hex encoded state root against which the key is proven, or the latest state root if `None`.
+    #[serde(default, with = "utils::rpc_hex_option")]
+    pub state_root: Option<[u8; 32]>,
+}
+
+/// Returns the value of the requested key, or its absence, with a merkle proof against the requested
+/// state root. Fails if the state root isn't the root of one of the versions retained by `storage`.
+pub fn get_state_proof<S: NativeStorage>(
+    storage: &S,
+    request: StateProofRequest,
+) -> anyhow::Result<StateProofResponse> {
+    ensure!(!storage.is_empty(), "The state is empty");
+    let version = match request.state_root {
+        Some(state_root) => find_version_of_root(storage, &state_root)?,
+        None => storage.latest_version(),
+    };
+
+    let state_root = storage.get_root_hash(version)?;
+    let proof = storage.get_with_proof_at(StorageKey::from(request.key), version)?;
+    Ok(StateProofResponse {
+        state_root: state_root.into(),
+        version,
+        key: proof.key.key().to_vec(),
+        value: proof.value.map(|value| value.value().to_vec()),
+        proof: proof.proof.try_to_vec()?,
+    })
+}
+
+// The roots are only indexed by version, so the retained versions are scanned from the latest one,
+// which is the most likely to be requested.
+fn find_version_of_root<S: NativeStorage>(
+    storage: &S,
+    state_root: &[u8; 32],
+) -> anyhow::Result<Version> {
+    for version in (storage.oldest_retained_version()..=storage.latest_version()).rev() {
+        if storage.get_root_hash(version)?.as_ref() == state_root {
+            return Ok(version);
+        }
+    }
+    bail!(
+        "The state root 0x{} is unknown or was pruned",
+        hex::encode(state_root)
+    )
+}
+
+/// Creates an RPC module with the `rollup_getProof` method, which returns the [`StateProofResponse`]
+/// of a [`StateProofRequest`].
+pub fn get_state_proof_rpc<S>(storage: S) -> RpcModule<S>
+where
+    S: NativeStorage + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(storage);
+    rpc.register_method("rollup_getProof", |params, storage| {
+        let request: StateProofRequest = params.one()?;
+        get_state_proof(storage, request)
+            .map_err(|e| to_jsonrpsee_error_object(e, STATE_PROOF_RPC_ERROR))
+    })
+    .expect("Failed to register state proof RPC methods");
+    rpc
+}
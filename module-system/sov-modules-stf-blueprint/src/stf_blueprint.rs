@@ -5,10 +5,13 @@ use sov_modules_api::runtime::capabilities::KernelSlotHooks;
 use sov_modules_api::{
     BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, GasUnit, StateCheckpoint,
 };
-use sov_modules_core::WorkingSet;
+use sov_modules_core::{ForkBase, GasMeter, StorageKey, WorkingSet};
 use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use tracing::{debug, error};
 
+#[cfg(feature = "native")]
+use crate::scheduler::accesses_declared;
+use crate::scheduler::schedule;
 use crate::tx_verifier::{verify_txs_stateless, TransactionAndRawHash};
 use crate::{Batch, Runtime, RuntimeTxHook, SequencerOutcome, SlashingReason, TxEffect};
 
@@ -30,6 +33,9 @@ pub struct StfBlueprint<C: Context, Da: DaSpec, Vm, RT: Runtime<C, Da>, K: Kerne
     /// The runtime includes all the modules that the rollup supports.
     pub(crate) runtime: RT,
     pub(crate) kernel: K,
+    /// The maximum number of threads dispatching the calls of a wave of transactions.
+    #[cfg(feature = "native")]
+    max_threads: usize,
     phantom_context: PhantomData<C>,
     phantom_vm: PhantomData<Vm>,
     phantom_da: PhantomData<Da>,
@@ -46,6 +52,63 @@ pub(crate) enum ApplyBatchError<A: BasicAddress> {
     },
 }
 
+/// The parameters of the execution of the transactions of a batch.
+struct TxEnv<C: Context> {
+    chain_id: u64,
+    visible_height: u64,
+    gas_price: C::GasUnit,
+    max_events_per_tx: usize,
+}
+
+/// The result of applying a transaction, before the gas it used is recorded by the kernel.
+struct TxOutcome<C: Context> {
+    receipt: TransactionReceipt<TxEffect>,
+    reward: u64,
+    /// The gas used by the transaction, if it was dispatched.
+    gas_used: Option<C::GasUnit>,
+}
+
+impl<C: Context> TxOutcome<C> {
+    /// The outcome of a transaction rejected before being dispatched.
+    fn rejected(tx_hash: [u8; 32], batch_workspace: &mut WorkingSet<C>) -> Self {
+        let gas_used = batch_workspace.gas_used().to_dimensions();
+        Self {
+            receipt: TransactionReceipt {
+                tx_hash,
+                body_to_save: None,
+                events: batch_workspace.take_events(),
+                receipt: TxEffect::Reverted,
+                gas_used,
+            },
+            reward: 0,
+            gas_used: None,
+        }
+    }
+}
+
+/// A transaction which passed its pre-dispatch hook, waiting for its call to be dispatched.
+struct PreparedTx<C: Context> {
+    ctx: C,
+    /// Whether the module called by the transaction is paused.
+    paused: bool,
+    /// The gas meter of the transaction, after its pre-dispatch hook.
+    gas_meter: GasMeter<C::GasUnit>,
+}
+
+/// A transaction whose call was dispatched, waiting for its post-dispatch hook.
+struct DispatchedTx<C: Context> {
+    receipt: TransactionReceipt<TxEffect>,
+    reward: u64,
+    gas_used: C::GasUnit,
+    /// The gas meter of the transaction, after its call.
+    gas_meter: GasMeter<C::GasUnit>,
+}
+
+/// Takes the gas meter of a transaction out of `batch_workspace`.
+fn take_gas_meter<C: Context>(batch_workspace: &mut WorkingSet<C>) -> GasMeter<C::GasUnit> {
+    batch_workspace.replace_gas_meter(GasMeter::default())
+}
+
 impl<A: BasicAddress> From<ApplyBatchError<A>> for BatchReceipt<SequencerOutcome<A>, TxEffect> {
     fn from(value: ApplyBatchError<A>) -> Self {
         match value {
@@ -94,12 +157,22 @@ where
         Self {
             runtime: RT::default(),
             kernel: K::default(),
+            #[cfg(feature = "native")]
+            max_threads: std::thread::available_parallelism().map_or(1, usize::from),
             phantom_context: PhantomData,
             phantom_vm: PhantomData,
             phantom_da: PhantomData,
         }
     }
 
+    /// Sets the maximum number of threads dispatching the calls of a wave of transactions. With a single
+    /// thread, the calls are dispatched sequentially, like in the zkVM.
+    #[cfg(feature = "native")]
+    pub fn with_max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = max_threads;
+        self
+    }
+
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     pub(crate) fn apply_blob(
         &self,
@@ -174,8 +247,8 @@ where
             "Error in preprocessing batch, there should be same number of txs and messages"
         );
 
-        // TODO fetch gas price from chain state
-        let gas_elastic_price = [0, 0];
+        let gas_price = self.kernel.base_fee_per_gas(&mut batch_workspace);
+        let visible_height = self.kernel.visible_height(&mut batch_workspace);
         let mut sequencer_reward = 0u64;
 
         let mut tx_receipts = Vec::with_capacity(txs.len());
@@ -183,7 +256,8 @@ where
         let mut batch_workspace = self.apply_txs(
             txs,
             messages,
-            &gas_elastic_price,
+            &gas_price,
+            visible_height,
             &mut tx_receipts,
             batch_workspace,
             &mut sequencer_reward,
@@ -222,120 +296,411 @@ where
         ),
         SlashingReason,
     > {
+        let limits = RT::resource_limits();
+        if blob_data.total_len() > limits.max_blob_size {
+            error!(
+                "The blob of {} bytes exceeds the limit of {} bytes",
+                blob_data.total_len(),
+                limits.max_blob_size
+            );
+            return Err(SlashingReason::BlobTooLarge);
+        }
+
         let batch = self.deserialize_batch(blob_data)?;
         debug!("Deserialized batch with {} txs", batch.txs.len());
+        if batch.txs.len() > limits.max_txs_per_batch {
+            error!(
+                "The batch of {} txs exceeds the limit of {} txs",
+                batch.txs.len(),
+                limits.max_txs_per_batch
+            );
+            return Err(SlashingReason::TooManyTransactions);
+        }
 
         // Run the stateless verification, since it is stateless we don't commit.
         let txs = self.verify_txs_stateless(batch)?;
 
-        let messages = self.decode_txs(&txs)?;
+        let messages = self.decode_txs(&txs, limits.max_call_message_size)?;
 
         Ok((txs, messages))
     }
 
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
+    #[allow(clippy::too_many_arguments)]
     fn apply_txs(
         &self,
         txs: Vec<TransactionAndRawHash<C>>,
         messages: Vec<<RT as DispatchCall>::Decodable>,
-        gas_elastic_price: &[u64],
+        gas_price: &C::GasUnit,
+        visible_height: u64,
         tx_receipts: &mut Vec<TransactionReceipt<TxEffect>>,
         mut batch_workspace: WorkingSet<C>,
         sequencer_reward: &mut u64,
     ) -> WorkingSet<C> {
-        // Dispatching transactions
-        for (TransactionAndRawHash { tx, raw_tx_hash }, msg) in
-            txs.into_iter().zip(messages.into_iter())
-        {
-            // Update the working set gas meter with the available funds
-            let gas_price = C::GasUnit::from_arbitrary_dimensions(gas_elastic_price);
-            let gas_limit = tx.gas_limit();
-            let gas_tip = tx.gas_tip();
-            batch_workspace.set_gas(gas_limit, gas_price);
-
-            // Pre dispatch hook
-            // TODO set the sequencer pubkey
-            let hook = RuntimeTxHook {
-                height: 1,
-                sequencer: tx.pub_key().clone(),
-            };
-            let ctx = match self
-                .runtime
-                .pre_dispatch_tx_hook(&tx, &mut batch_workspace, &hook)
-            {
-                Ok(verified_tx) => verified_tx,
-                Err(e) => {
-                    // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
-                    // For example nonce for the relevant account is incremented.
-                    error!("Stateful verification error - the sequencer included an invalid transaction: {}", e);
-                    let gas_used = batch_workspace.gas_used().to_dimensions();
-                    let receipt = TransactionReceipt {
-                        tx_hash: raw_tx_hash,
-                        body_to_save: None,
-                        events: batch_workspace.take_events(),
-                        receipt: TxEffect::Reverted,
-                        gas_used,
-                    };
-
-                    tx_receipts.push(receipt);
-                    continue;
+        let env = TxEnv {
+            chain_id: self.kernel.chain_id(&mut batch_workspace),
+            visible_height,
+            gas_price: gas_price.clone(),
+            max_events_per_tx: RT::resource_limits().max_events_per_tx,
+        };
+
+        // Transactions with non-conflicting access lists are dispatched in waves
+        let waves = schedule(txs.iter().map(|tx| tx.tx.access_list()));
+        let mut txs = txs.into_iter().zip(messages);
+        let mut batch_checkpoint = batch_workspace.checkpoint();
+        for wave in waves {
+            let wave = txs.by_ref().take(wave.len()).collect();
+            let (checkpoint, outcomes) = self.apply_wave(&env, wave, batch_checkpoint);
+
+            // The gas consumed by the slot drives the base fee of the next one.
+            let mut batch_workspace = checkpoint.to_revertable();
+            for outcome in outcomes {
+                *sequencer_reward = sequencer_reward.saturating_add(outcome.reward);
+                if let Some(gas_used) = &outcome.gas_used {
+                    self.kernel.record_gas_used(gas_used, &mut batch_workspace);
                 }
-            };
+                tx_receipts.push(outcome.receipt);
+            }
+            batch_checkpoint = batch_workspace.checkpoint();
+        }
+
+        batch_checkpoint.to_revertable()
+    }
+
+    /// Applies a wave of transactions with non-conflicting access lists. Each transaction runs its pre-dispatch
+    /// hook, its call and its post-dispatch hook one after the other, so the hooks of a transaction see the changes
+    /// made by the previous transactions of the wave, like the nonce of their sender. Natively, the transactions are
+    /// applied in parallel if they only access the keys they declared, including the keys accessed by the hooks.
+    ///
+    /// The values of the keys declared by the transactions are read before the wave, both natively and in the zkVM,
+    /// so that the witness doesn't depend on the order in which the transactions are applied.
+    fn apply_wave(
+        &self,
+        env: &TxEnv<C>,
+        wave: Vec<(TransactionAndRawHash<C>, <RT as DispatchCall>::Decodable)>,
+        mut checkpoint: StateCheckpoint<C>,
+    ) -> (StateCheckpoint<C>, Vec<TxOutcome<C>>) {
+        let (txs, messages): (Vec<_>, Vec<_>) = wave.into_iter().unzip();
+        if txs.len() == 1 {
+            return self.apply_sequentially(env, &txs, messages, checkpoint);
+        }
+
+        let declared_keys: Vec<StorageKey> = txs
+            .iter()
+            .flat_map(|tx| {
+                let access_list = tx
+                    .tx
+                    .access_list()
+                    .expect("Waves of several txs have access lists");
+                access_list.reads.iter().chain(&access_list.writes)
+            })
+            .map(|key| StorageKey::from(key.clone()))
+            .collect();
+        let base = checkpoint.read_fork_base(declared_keys);
+
+        #[cfg(feature = "native")]
+        if self.max_threads > 1 {
+            return self.apply_in_parallel(env, &txs, messages, checkpoint, &base);
+        }
+        let _ = base;
+        self.apply_sequentially(env, &txs, messages, checkpoint)
+    }
+
+    fn apply_sequentially(
+        &self,
+        env: &TxEnv<C>,
+        txs: &[TransactionAndRawHash<C>],
+        messages: Vec<<RT as DispatchCall>::Decodable>,
+        checkpoint: StateCheckpoint<C>,
+    ) -> (StateCheckpoint<C>, Vec<TxOutcome<C>>) {
+        let mut batch_workspace = checkpoint.to_revertable();
+        let mut outcomes = Vec::with_capacity(txs.len());
+        for (tx, msg) in txs.iter().zip(messages) {
+            let (next_workspace, outcome) =
+                Self::apply_tx(&self.runtime, env, tx, msg, batch_workspace);
+            batch_workspace = next_workspace;
+            outcomes.push(outcome);
+        }
+        (batch_workspace.checkpoint(), outcomes)
+    }
 
-            // Commit changes after pre_dispatch_tx_hook
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
+    /// Applies each transaction on its own fork of the checkpoint, in parallel threads. If a transaction accessed a
+    /// key missing from its access list, the forks are dropped and the transactions are applied sequentially instead.
+    #[cfg(feature = "native")]
+    fn apply_in_parallel(
+        &self,
+        env: &TxEnv<C>,
+        txs: &[TransactionAndRawHash<C>],
+        messages: Vec<<RT as DispatchCall>::Decodable>,
+        mut checkpoint: StateCheckpoint<C>,
+        base: &ForkBase,
+    ) -> (StateCheckpoint<C>, Vec<TxOutcome<C>>) {
+        let threads = self.max_threads.min(txs.len()).max(1);
+        let chunk_size = (txs.len() + threads - 1) / threads;
+
+        let mut jobs: Vec<_> = txs
+            .iter()
+            .zip(messages)
+            .map(|(tx, msg)| (tx, msg, checkpoint.fork(base)))
+            .collect();
+        let runtime = &self.runtime;
+        let results: Vec<(StateCheckpoint<C>, TxOutcome<C>)> = std::thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(threads);
+            while !jobs.is_empty() {
+                let chunk: Vec<_> = jobs.drain(..chunk_size.min(jobs.len())).collect();
+                handles.push(scope.spawn(move || {
+                    chunk
+                        .into_iter()
+                        .map(|(tx, msg, fork)| {
+                            let (fork, outcome) =
+                                Self::apply_tx(runtime, env, tx, msg, fork.to_revertable());
+                            (fork.checkpoint(), outcome)
+                        })
+                        .collect::<Vec<_>>()
+                }));
+            }
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Transaction thread panicked"))
+                .collect()
+        });
+
+        let declared = results
+            .iter()
+            .zip(txs)
+            .all(|((fork, _), tx)| accesses_declared(fork.fork_accesses(), tx.tx.access_list()));
+        if !declared {
+            debug!("A transaction accessed undeclared state, applying its wave sequentially");
+            let messages = txs
+                .iter()
+                .map(|tx| {
+                    RT::decode_call(tx.tx.runtime_msg())
+                        .expect("The message was decoded during the stateless verification")
+                })
+                .collect();
+            return self.apply_sequentially(env, txs, messages, checkpoint);
+        }
 
-            let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
+        let mut outcomes = Vec::with_capacity(results.len());
+        for (fork, outcome) in results {
+            checkpoint.merge_fork(fork);
+            outcomes.push(outcome);
+        }
+        (checkpoint, outcomes)
+    }
 
-            let remaining_gas = batch_workspace.gas_remaining_funds();
-            let gas_reward = gas_limit
-                .saturating_add(gas_tip)
-                .saturating_sub(remaining_gas);
+    /// Applies a single transaction. The changes made by the pre-dispatch hook are kept even if the transaction
+    /// is reverted.
+    fn apply_tx(
+        runtime: &RT,
+        env: &TxEnv<C>,
+        tx: &TransactionAndRawHash<C>,
+        msg: <RT as DispatchCall>::Decodable,
+        batch_workspace: WorkingSet<C>,
+    ) -> (WorkingSet<C>, TxOutcome<C>) {
+        let (batch_workspace, prepared) =
+            Self::pre_dispatch(runtime, env, tx, &msg, batch_workspace);
+        let prepared = match prepared {
+            Ok(prepared) => prepared,
+            Err(outcome) => return (batch_workspace, outcome),
+        };
+        let (batch_workspace, dispatched) =
+            Self::dispatch(runtime, env, tx, &prepared, msg, batch_workspace);
+        Self::post_dispatch(runtime, tx, prepared, dispatched, batch_workspace)
+    }
 
-            *sequencer_reward = sequencer_reward.saturating_add(gas_reward);
-            debug!(
-                "Tx {} sequencer reward: {}",
+    /// Runs the checks and the pre-dispatch hook of a transaction. The changes made by the hook are kept even if it
+    /// rejects the transaction.
+    fn pre_dispatch(
+        runtime: &RT,
+        env: &TxEnv<C>,
+        tx: &TransactionAndRawHash<C>,
+        msg: &<RT as DispatchCall>::Decodable,
+        mut batch_workspace: WorkingSet<C>,
+    ) -> (WorkingSet<C>, Result<PreparedTx<C>, TxOutcome<C>>) {
+        let raw_tx_hash = tx.raw_tx_hash;
+        // Update the working set gas meter with the available funds
+        batch_workspace.set_gas(tx.tx.gas_limit(), env.gas_price.clone());
+
+        // Transactions are signed over the chain id, so a transaction signed for another chain
+        // can't be replayed on this one.
+        if tx.tx.chain_id() != env.chain_id {
+            error!(
+                "Tx 0x{} was signed for chain id {} but the rollup chain id is {}",
                 hex::encode(raw_tx_hash),
-                gas_reward
+                tx.tx.chain_id(),
+                env.chain_id
             );
+            let outcome = TxOutcome::rejected(raw_tx_hash, &mut batch_workspace);
+            return (batch_workspace, Err(outcome));
+        }
 
-            let events = batch_workspace.take_events();
-            let tx_effect = match tx_result {
-                Ok(_) => TxEffect::Successful,
-                Err(e) => {
-                    error!(
-                        "Tx 0x{} was reverted error: {}",
-                        hex::encode(raw_tx_hash),
-                        e
-                    );
-                    // The transaction causing invalid state transition is reverted
-                    // but we don't slash and we continue processing remaining transactions.
-                    batch_workspace = batch_workspace.revert().to_revertable();
-                    TxEffect::Reverted
-                }
-            };
-            debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
+        // A transaction with an expiry can't be executed once the rollup moves past it, so a stale
+        // transaction can't be replayed long after it was signed.
+        if let Some(valid_until_slot) = tx.tx.valid_until_slot() {
+            if env.visible_height > valid_until_slot {
+                error!(
+                    "Tx 0x{} expired at slot {} but the current slot is {}",
+                    hex::encode(raw_tx_hash),
+                    valid_until_slot,
+                    env.visible_height
+                );
+                let outcome = TxOutcome::rejected(raw_tx_hash, &mut batch_workspace);
+                return (batch_workspace, Err(outcome));
+            }
+        }
 
-            let gas_used = batch_workspace.gas_used().to_dimensions();
-            let receipt = TransactionReceipt {
-                tx_hash: raw_tx_hash,
-                body_to_save: None,
-                events,
-                receipt: tx_effect,
-                gas_used,
-            };
+        // Pre dispatch hook
+        // TODO set the sequencer pubkey
+        let hook = RuntimeTxHook {
+            height: env.visible_height,
+            sequencer: tx.tx.pub_key().clone(),
+        };
+        let ctx = match runtime.pre_dispatch_tx_hook(&tx.tx, &mut batch_workspace, &hook) {
+            Ok(verified_tx) => verified_tx,
+            Err(e) => {
+                // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
+                // For example nonce for the relevant account is incremented.
+                error!("Stateful verification error - the sequencer included an invalid transaction: {}", e);
+                let outcome = TxOutcome::rejected(raw_tx_hash, &mut batch_workspace);
+                return (batch_workspace, Err(outcome));
+            }
+        };
 
-            tx_receipts.push(receipt);
-            // We commit after events have been extracted into receipt.
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
+        // Commit changes after pre_dispatch_tx_hook
+        let mut batch_workspace = batch_workspace.checkpoint().to_revertable();
 
-            // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            self.runtime
-                .post_dispatch_tx_hook(&tx, &ctx, &mut batch_workspace)
-                .expect("inconsistent state: error in post_dispatch_tx_hook");
-        }
+        let paused = runtime.is_module_paused(runtime.module_address(msg), &mut batch_workspace);
+        let gas_meter = take_gas_meter(&mut batch_workspace);
+        let prepared = PreparedTx {
+            ctx,
+            paused,
+            gas_meter,
+        };
+        (batch_workspace, Ok(prepared))
+    }
 
-        batch_workspace
+    /// Dispatches the call of a prepared transaction. The changes made by the call are reverted if it fails.
+    fn dispatch(
+        runtime: &RT,
+        env: &TxEnv<C>,
+        tx: &TransactionAndRawHash<C>,
+        prepared: &PreparedTx<C>,
+        msg: <RT as DispatchCall>::Decodable,
+        mut batch_workspace: WorkingSet<C>,
+    ) -> (WorkingSet<C>, DispatchedTx<C>) {
+        let raw_tx_hash = tx.raw_tx_hash;
+        batch_workspace.replace_gas_meter(prepared.gas_meter.clone());
+        // Only the call pays for its state accesses, so the reward computed right after it covers all of them.
+        batch_workspace.set_state_access_gas_costs(RT::state_access_gas_costs());
+
+        let tx_result = if prepared.paused {
+            Err(anyhow::anyhow!("Module {} is paused", runtime.module_address(&msg)).into())
+        } else {
+            runtime.dispatch_call(msg, &mut batch_workspace, &prepared.ctx)
+        };
+        let tx_result = tx_result.and_then(|response| {
+            // State accesses can't fail, so a transaction running out of gas while accessing
+            // the state is only caught here.
+            if batch_workspace.is_out_of_gas() {
+                Err(anyhow::anyhow!("Transaction ran out of gas").into())
+            } else {
+                Ok(response)
+            }
+        });
+        batch_workspace.set_state_access_gas_costs(Default::default());
+
+        let remaining_gas = batch_workspace.gas_remaining_funds();
+        let reward = tx
+            .tx
+            .gas_limit()
+            .saturating_add(tx.tx.gas_tip())
+            .saturating_sub(remaining_gas);
+        debug!(
+            "Tx {} sequencer reward: {}",
+            hex::encode(raw_tx_hash),
+            reward
+        );
+
+        let events = batch_workspace.take_events();
+        // The events are kept until the end of the slot, so a transaction emitting too many of them is reverted
+        // without them.
+        let (tx_result, events) = if events.len() > env.max_events_per_tx {
+            let error = anyhow::anyhow!(
+                "Transaction emitted {} events, more than the limit of {}",
+                events.len(),
+                env.max_events_per_tx
+            );
+            (Err(error.into()), Vec::new())
+        } else {
+            (tx_result, events)
+        };
+        let tx_effect = match tx_result {
+            Ok(_) => TxEffect::Successful,
+            Err(e) => {
+                error!(
+                    "Tx 0x{} was reverted error: {}",
+                    hex::encode(raw_tx_hash),
+                    e
+                );
+                // The transaction causing invalid state transition is reverted
+                // but we don't slash and we continue processing remaining transactions.
+                batch_workspace = batch_workspace.revert().to_revertable();
+                TxEffect::Reverted
+            }
+        };
+        debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
+
+        let receipt = TransactionReceipt {
+            tx_hash: raw_tx_hash,
+            body_to_save: None,
+            events,
+            receipt: tx_effect,
+            gas_used: batch_workspace.gas_used().to_dimensions(),
+        };
+        // We commit after events have been extracted into receipt.
+        let mut batch_workspace = batch_workspace.checkpoint().to_revertable();
+        let gas_used = batch_workspace.gas_used().clone();
+        let gas_meter = take_gas_meter(&mut batch_workspace);
+
+        let dispatched = DispatchedTx {
+            receipt,
+            reward,
+            gas_used,
+            gas_meter,
+        };
+        (batch_workspace, dispatched)
+    }
+
+    /// Runs the post-dispatch hook of a dispatched transaction, even if its call was reverted.
+    fn post_dispatch(
+        runtime: &RT,
+        tx: &TransactionAndRawHash<C>,
+        prepared: PreparedTx<C>,
+        dispatched: DispatchedTx<C>,
+        mut batch_workspace: WorkingSet<C>,
+    ) -> (WorkingSet<C>, TxOutcome<C>) {
+        let DispatchedTx {
+            mut receipt,
+            reward,
+            gas_used,
+            gas_meter,
+        } = dispatched;
+        batch_workspace.replace_gas_meter(gas_meter);
+
+        // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
+        runtime
+            .post_dispatch_tx_hook(&tx.tx, &prepared.ctx, &mut batch_workspace)
+            .expect("inconsistent state: error in post_dispatch_tx_hook");
+        // The fee settlement runs even if the transaction was reverted, and its events are part of the receipt.
+        receipt.events.extend(batch_workspace.take_events());
+
+        let outcome = TxOutcome {
+            receipt,
+            reward,
+            gas_used: Some(gas_used),
+        };
+        (batch_workspace, outcome)
     }
 
     // Attempt to deserialize batch, error results in sequencer slashing.
@@ -376,14 +741,24 @@ where
     }
 
     // Checks that runtime message can be decoded from transaction.
-    // If a single message cannot be decoded, sequencer is slashed
+    // If a single message is too large or cannot be decoded, sequencer is slashed
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     fn decode_txs(
         &self,
         txs: &[TransactionAndRawHash<C>],
+        max_call_message_size: usize,
     ) -> Result<Vec<<RT as DispatchCall>::Decodable>, SlashingReason> {
         let mut decoded_messages = Vec::with_capacity(txs.len());
         for TransactionAndRawHash { tx, raw_tx_hash } in txs {
+            if tx.runtime_msg().len() > max_call_message_size {
+                error!(
+                    "Tx 0x{} message of {} bytes exceeds the limit of {} bytes",
+                    hex::encode(raw_tx_hash),
+                    tx.runtime_msg().len(),
+                    max_call_message_size
+                );
+                return Err(SlashingReason::CallMessageTooLarge);
+            }
             match RT::decode_call(tx.runtime_msg()) {
                 Ok(msg) => decoded_messages.push(msg),
                 Err(e) => {
@@ -1,7 +1,7 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use sov_modules_core::EncodeKeyLike;
 
-use super::{StateCodec, StateKeyCodec};
+use super::{StateCodec, StateKeyCodec, StateKeyDecoder};
 use crate::codec::StateValueCodec;
 
 /// A [`StateCodec`] that uses [`borsh`] for all keys and values.
@@ -17,6 +17,17 @@ where
     }
 }
 
+impl<K> StateKeyDecoder<K> for BorshCodec
+where
+    K: BorshSerialize + BorshDeserialize,
+{
+    type Error = std::io::Error;
+
+    fn try_decode_key(&self, bytes: &[u8]) -> Result<K, Self::Error> {
+        K::try_from_slice(bytes)
+    }
+}
+
 impl<V> StateValueCodec<V> for BorshCodec
 where
     V: BorshSerialize + BorshDeserialize,
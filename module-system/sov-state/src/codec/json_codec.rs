@@ -1,6 +1,6 @@
 use serde_json;
 
-use super::{StateCodec, StateKeyCodec};
+use super::{StateCodec, StateKeyCodec, StateKeyDecoder};
 use crate::codec::StateValueCodec;
 
 /// A [`StateCodec`] that uses [`serde_json`] for all keys and values.
@@ -16,6 +16,17 @@ where
     }
 }
 
+impl<K> StateKeyDecoder<K> for JsonCodec
+where
+    K: serde::Serialize + for<'a> serde::Deserialize<'a>,
+{
+    type Error = serde_json::Error;
+
+    fn try_decode_key(&self, bytes: &[u8]) -> Result<K, Self::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
 impl<V> StateValueCodec<V> for JsonCodec
 where
     V: serde::Serialize + for<'a> serde::Deserialize<'a>,
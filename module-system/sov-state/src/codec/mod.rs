@@ -1,6 +1,6 @@
 //! Serialization and deserialization -related logic.
 
-use sov_modules_core::{StateCodec, StateKeyCodec, StateValueCodec};
+use sov_modules_core::{StateCodec, StateKeyCodec, StateKeyDecoder, StateValueCodec};
 
 mod bcs_codec;
 mod borsh_codec;
@@ -1,6 +1,6 @@
 //! This module defines a codec which delegates to one codec for keys and one codec for values.
 
-use super::{StateCodec, StateKeyCodec, StateValueCodec};
+use super::{StateCodec, StateKeyCodec, StateKeyDecoder, StateValueCodec};
 
 /// A [`StateValueCodec`] that uses one pre-existing codec for keys and a different one values.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
@@ -20,6 +20,17 @@ where
     }
 }
 
+impl<K, KC, VC> StateKeyDecoder<K> for SplitCodec<KC, VC>
+where
+    KC: StateKeyDecoder<K>,
+{
+    type Error = KC::Error;
+
+    fn try_decode_key(&self, bytes: &[u8]) -> Result<K, Self::Error> {
+        self.key_codec.try_decode_key(bytes)
+    }
+}
+
 impl<V, KC, VC> StateValueCodec<V> for SplitCodec<KC, VC>
 where
     VC: StateValueCodec<V>,
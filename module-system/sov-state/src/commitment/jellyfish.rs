@@ -0,0 +1,418 @@
+use std::sync::Arc;
+
+#[cfg(feature = "native")]
+use jmt::storage::{Node, NodeBatch, NodeKey, StaleNodeIndexBatch, TreeWriter};
+#[cfg(feature = "native")]
+use jmt::JellyfishMerkleTree;
+use jmt::KeyHash;
+#[cfg(feature = "native")]
+use sov_db::schema::QueryManager;
+#[cfg(feature = "native")]
+use sov_db::state_db::StateDB;
+use sov_modules_core::{
+    CacheKey, CacheValue, OrderedReadsAndWrites, StorageKey, StorageValue, Witness,
+};
+use sov_rollup_interface::digest::Digest;
+
+#[cfg(feature = "native")]
+use super::NativeStateCommitment;
+use super::StateCommitment;
+
+/// The [`StateCommitment`] backed by a [Jellyfish Merkle Tree](https://github.com/penumbra-zone/jmt),
+/// a sparse merkle tree of radix 16 keyed by the hashes of the storage keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Jmt;
+
+/// The nodes and key preimages written to the [`StateDB`] by an update of the [`Jmt`].
+#[cfg(feature = "native")]
+pub struct JmtUpdate {
+    pub(crate) node_batch: NodeBatch,
+    pub(crate) stale_node_indices: StaleNodeIndexBatch,
+    pub(crate) key_preimages: Vec<(KeyHash, CacheKey)>,
+}
+
+/// Returns the reads of `ordered_reads` sorted by key hash, which is the order of their proofs in the witness.
+/// Proofs of keys close in the tree are then adjacent, and share most of their siblings.
+fn sorted_reads<H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+    ordered_reads: &[(CacheKey, Option<CacheValue>)],
+) -> Vec<(KeyHash, Option<&CacheValue>)> {
+    let mut reads = ordered_reads
+        .iter()
+        .map(|(key, value)| (KeyHash::with::<H>(key.key.as_ref()), value.as_ref()))
+        .collect::<Vec<_>>();
+    reads.sort_by_key(|(key_hash, _)| key_hash.0);
+    reads
+}
+
+impl<H: Digest<OutputSize = sha2::digest::typenum::U32>> StateCommitment<H> for Jmt {
+    type Root = jmt::RootHash;
+    type Proof = jmt::proof::SparseMerkleProof<H>;
+
+    fn verify_reads<W: Witness>(
+        prev_root: &Self::Root,
+        state_accesses: &OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<()> {
+        // For each value that's been read from the tree, verify the provided smt proof
+        for (key_hash, read_value) in sorted_reads::<H>(&state_accesses.ordered_reads) {
+            // TODO: Switch to the batch read API once it becomes available
+            let proof: jmt::proof::SparseMerkleProof<H> = witness.get_hint();
+
+            match read_value {
+                Some(val) => proof.verify_existence(
+                    jmt::RootHash(prev_root.0),
+                    key_hash,
+                    val.value.as_ref(),
+                )?,
+                None => proof.verify_nonexistence(jmt::RootHash(prev_root.0), key_hash)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_update<W: Witness>(
+        prev_root: &Self::Root,
+        state_accesses: OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<Self::Root> {
+        // Compute the jmt update from the write batch
+        let batch = state_accesses
+            .ordered_writes
+            .into_iter()
+            .map(|(key, value)| {
+                let key_hash = KeyHash::with::<H>(key.key.as_ref());
+                (
+                    key_hash,
+                    value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let update_proof: jmt::proof::UpdateMerkleProof<H> = witness.get_hint();
+        let new_root: [u8; 32] = witness.get_hint();
+        update_proof.verify_update(jmt::RootHash(prev_root.0), jmt::RootHash(new_root), batch)?;
+
+        Ok(jmt::RootHash(new_root))
+    }
+
+    fn verify_proof(
+        root: &Self::Root,
+        key: &StorageKey,
+        value: Option<&StorageValue>,
+        proof: Self::Proof,
+    ) -> anyhow::Result<()> {
+        let key_hash = KeyHash::with::<H>(key.as_ref());
+        proof.verify(jmt::RootHash(root.0), key_hash, value.map(|v| v.value()))
+    }
+}
+
+#[cfg(feature = "native")]
+impl<H: Digest<OutputSize = sha2::digest::typenum::U32>> NativeStateCommitment<H> for Jmt {
+    type Update = JmtUpdate;
+
+    fn get_root<Q: QueryManager>(db: &StateDB<Q>, version: u64) -> anyhow::Result<Self::Root> {
+        JellyfishMerkleTree::<_, H>::new(db).get_root_hash(version)
+    }
+
+    fn get_with_proof<Q: QueryManager>(
+        db: &StateDB<Q>,
+        key: &StorageKey,
+        version: u64,
+    ) -> anyhow::Result<(Option<StorageValue>, Self::Proof)> {
+        let (value, proof) = JellyfishMerkleTree::<_, H>::new(db)
+            .get_with_proof(KeyHash::with::<H>(key.as_ref()), version)?;
+        Ok((value.map(StorageValue::from), proof))
+    }
+
+    fn compute_update<Q: QueryManager, W: Witness>(
+        db: &StateDB<Q>,
+        state_accesses: OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<(Self::Root, Self::Update)> {
+        let latest_version = db.get_next_version() - 1;
+        let jmt = JellyfishMerkleTree::<_, H>::new(db);
+
+        // Handle empty jmt
+        // TODO: Fix this before introducing snapshots!
+        if jmt.get_root_hash_option(latest_version)?.is_none() {
+            assert_eq!(latest_version, 0);
+            let empty_batch = Vec::default().into_iter();
+            let (_, tree_update) = jmt
+                .put_value_set(empty_batch, latest_version)
+                .expect("JMT update must succeed");
+
+            db.write_node_batch(&tree_update.node_batch)
+                .expect("db write must succeed");
+        }
+        let prev_root = jmt
+            .get_root_hash(latest_version)
+            .expect("Previous root hash was just populated");
+        witness.add_hint(&prev_root);
+
+        // For each value that's been read from the tree, read it from the logged JMT to populate hints
+        for (key_hash, read_value) in sorted_reads::<H>(&state_accesses.ordered_reads) {
+            // TODO: Switch to the batch read API once it becomes available
+            let (result, proof) = jmt.get_with_proof(key_hash, latest_version)?;
+            if result.as_ref() != read_value.map(|f| f.value.as_ref()) {
+                anyhow::bail!("Bug! Incorrect value read from jmt");
+            }
+            witness.add_hint(proof);
+        }
+
+        let mut key_preimages = Vec::with_capacity(state_accesses.ordered_writes.len());
+
+        // Compute the jmt update from the write batch
+        let batch = state_accesses
+            .ordered_writes
+            .into_iter()
+            .map(|(key, value)| {
+                let key_hash = KeyHash::with::<H>(key.key.as_ref());
+                key_preimages.push((key_hash, key));
+                (
+                    key_hash,
+                    value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
+                )
+            });
+
+        let next_version = db.get_next_version();
+
+        let (new_root, update_proof, tree_update) = jmt
+            .put_value_set_with_proof(batch, next_version)
+            .expect("JMT update must succeed");
+
+        witness.add_hint(update_proof);
+        witness.add_hint(new_root.0);
+
+        let update = JmtUpdate {
+            node_batch: tree_update.node_batch,
+            stale_node_indices: tree_update.stale_node_index_batch,
+            key_preimages,
+        };
+
+        Ok((new_root, update))
+    }
+
+    fn compute_import<Q: QueryManager>(
+        db: &StateDB<Q>,
+        version: u64,
+        entries: Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> anyhow::Result<(Self::Root, Self::Update)> {
+        // The tree is built on top of an empty root at the previous version
+        if let Some(prev_version) = version.checked_sub(1) {
+            let mut empty_root = NodeBatch::default();
+            empty_root.extend(
+                vec![(NodeKey::new_empty_path(prev_version), Node::Null)],
+                vec![],
+            );
+            db.write_node_batch(&empty_root)?;
+        }
+
+        let mut key_preimages = Vec::with_capacity(entries.len());
+        let batch = entries
+            .into_iter()
+            .map(|(key, value)| {
+                let key_hash = KeyHash::with::<H>(&key);
+                key_preimages.push((key_hash, CacheKey { key: Arc::new(key) }));
+                (key_hash, Some(value))
+            })
+            .collect::<Vec<_>>();
+
+        let (root, tree_update) =
+            JellyfishMerkleTree::<_, H>::new(db).put_value_set(batch, version)?;
+
+        let update = JmtUpdate {
+            node_batch: tree_update.node_batch,
+            stale_node_indices: tree_update.stale_node_index_batch,
+            key_preimages,
+        };
+
+        Ok((root, update))
+    }
+
+    fn commit_update<Q: QueryManager>(
+        db: &StateDB<Q>,
+        update: &Self::Update,
+    ) -> anyhow::Result<()> {
+        db.put_preimages(
+            update
+                .key_preimages
+                .iter()
+                .map(|(key_hash, key)| (*key_hash, key.key.as_ref())),
+        )?;
+        db.put_stale_node_indices(&update.stale_node_indices)?;
+        // Write the nodes last, since we base our view of what has been touched on them.
+        db.write_node_batch(&update.node_batch)
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use std::sync::RwLock;
+
+    use borsh::BorshSerialize;
+    use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, ReadOnlyLock};
+
+    use super::*;
+    use crate::ArrayWitness;
+
+    type H = sha2::Sha256;
+
+    type Writes = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
+    fn new_db() -> StateDB<NoopQueryManager> {
+        let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let db_snapshot = DbSnapshot::<NoopQueryManager>::new(0, manager);
+        StateDB::with_db_snapshot(db_snapshot).unwrap()
+    }
+
+    fn cache_key(key: &[u8]) -> CacheKey {
+        CacheKey {
+            key: Arc::new(key.to_vec()),
+        }
+    }
+
+    fn cache_value(value: &[u8]) -> CacheValue {
+        CacheValue {
+            value: Arc::new(value.to_vec()),
+        }
+    }
+
+    /// Applies `writes` at the next version of `db` through the [`Jmt`] commitment.
+    fn commitment_update(
+        db: &StateDB<NoopQueryManager>,
+        ordered_reads: Vec<(CacheKey, Option<CacheValue>)>,
+        writes: &Writes,
+        witness: &ArrayWitness,
+    ) -> jmt::RootHash {
+        let state_accesses = OrderedReadsAndWrites {
+            ordered_reads,
+            ordered_writes: writes
+                .iter()
+                .map(|(key, value)| (cache_key(key), value.as_deref().map(cache_value)))
+                .collect(),
+        };
+        let (root, update) =
+            <Jmt as NativeStateCommitment<H>>::compute_update(db, state_accesses, witness).unwrap();
+        <Jmt as NativeStateCommitment<H>>::commit_update(db, &update).unwrap();
+        db.inc_next_version();
+        root
+    }
+
+    /// Applies `writes` at the next version of `db` with the [`JellyfishMerkleTree`] directly.
+    fn reference_update(db: &StateDB<NoopQueryManager>, writes: &Writes) -> jmt::RootHash {
+        let jmt = JellyfishMerkleTree::<_, H>::new(db);
+        let version = db.get_next_version();
+        if jmt.get_root_hash_option(version - 1).unwrap().is_none() {
+            let (_, tree_update) = jmt
+                .put_value_set(Vec::<(KeyHash, Option<Vec<u8>>)>::new(), version - 1)
+                .unwrap();
+            db.write_node_batch(&tree_update.node_batch).unwrap();
+        }
+        let batch = writes
+            .iter()
+            .map(|(key, value)| (KeyHash::with::<H>(key), value.clone()));
+        let (root, tree_update) = jmt.put_value_set(batch, version).unwrap();
+        db.write_node_batch(&tree_update.node_batch).unwrap();
+        db.inc_next_version();
+        root
+    }
+
+    fn slots() -> Vec<Writes> {
+        let key = |i: u8| format!("key{i}").into_bytes();
+        vec![
+            // Inserts
+            (0..20).map(|i| (key(i), Some(vec![i; 40]))).collect(),
+            // Overwrites, deletions and new keys
+            (0..10)
+                .map(|i| (key(i), (i % 2 == 0).then(|| vec![i + 100; 10])))
+                .chain((20..25).map(|i| (key(i), Some(vec![i; 8]))))
+                .collect(),
+            // Reinsertion of a deleted key
+            vec![(key(1), Some(vec![1; 5])), (key(30), Some(vec![30; 5]))],
+        ]
+    }
+
+    #[test]
+    fn test_roots_and_proofs_match_jmt() {
+        let db = new_db();
+        let reference_db = new_db();
+
+        let mut keys: Vec<Vec<u8>> = vec![b"never written".to_vec()];
+        for writes in slots() {
+            let root = commitment_update(&db, vec![], &writes, &ArrayWitness::default());
+            let reference_root = reference_update(&reference_db, &writes);
+            assert_eq!(root, reference_root);
+
+            let version = db.get_next_version() - 1;
+            assert_eq!(reference_db.get_next_version() - 1, version);
+            assert_eq!(
+                <Jmt as NativeStateCommitment<H>>::get_root(&db, version).unwrap(),
+                reference_root
+            );
+
+            keys.extend(writes.into_iter().map(|(key, _)| key));
+            for key in &keys {
+                let storage_key = StorageKey::from(cache_key(key));
+                let (value, proof) =
+                    <Jmt as NativeStateCommitment<H>>::get_with_proof(&db, &storage_key, version)
+                        .unwrap();
+                let (reference_value, reference_proof) =
+                    JellyfishMerkleTree::<_, H>::new(&reference_db)
+                        .get_with_proof(KeyHash::with::<H>(key), version)
+                        .unwrap();
+
+                assert_eq!(
+                    value.as_ref().map(|v| v.value()),
+                    reference_value.as_deref()
+                );
+                assert_eq!(
+                    proof.try_to_vec().unwrap(),
+                    reference_proof.try_to_vec().unwrap()
+                );
+                <Jmt as StateCommitment<H>>::verify_proof(
+                    &root,
+                    &storage_key,
+                    value.as_ref(),
+                    proof,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_witness_verifies_to_jmt_root() {
+        let db = new_db();
+        let reference_db = new_db();
+        let mut slots = slots().into_iter();
+        let first_slot = slots.next().unwrap();
+        commitment_update(&db, vec![], &first_slot, &ArrayWitness::default());
+        reference_update(&reference_db, &first_slot);
+
+        // Reads of existing and missing keys, followed by the writes of the next slot
+        let reads = vec![
+            (cache_key(b"key3"), Some(cache_value(&[3; 40]))),
+            (cache_key(b"key40"), None),
+        ];
+        let writes = slots.next().unwrap();
+        let witness = ArrayWitness::default();
+        let root = commitment_update(&db, reads.clone(), &writes, &witness);
+        let reference_root = reference_update(&reference_db, &writes);
+        assert_eq!(root, reference_root);
+
+        let prev_root: jmt::RootHash = witness.get_hint();
+        let state_accesses = OrderedReadsAndWrites {
+            ordered_reads: reads,
+            ordered_writes: writes
+                .iter()
+                .map(|(key, value)| (cache_key(key), value.as_deref().map(cache_value)))
+                .collect(),
+        };
+        <Jmt as StateCommitment<H>>::verify_reads(&prev_root, &state_accesses, &witness).unwrap();
+        let verified_root =
+            <Jmt as StateCommitment<H>>::verify_update(&prev_root, state_accesses, &witness)
+                .unwrap();
+        assert_eq!(verified_root, reference_root);
+    }
+}
@@ -0,0 +1,114 @@
+//! The authenticated data structures committing to the state.
+//!
+//! Modules never interact with the state commitment directly: [`ProverStorage`](crate::ProverStorage)
+//! and [`ZkStorage`](crate::ZkStorage) delegate to the [`StateCommitment`] selected by their
+//! [`MerkleProofSpec`](crate::MerkleProofSpec), so a different authenticated data structure
+//! can be swapped in by changing the spec of the rollup.
+
+mod jellyfish;
+
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use jellyfish::Jmt;
+#[cfg(feature = "native")]
+pub use jellyfish::JmtUpdate;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+#[cfg(feature = "native")]
+use sov_db::schema::QueryManager;
+#[cfg(feature = "native")]
+use sov_db::state_db::StateDB;
+use sov_modules_core::{OrderedReadsAndWrites, StorageKey, StorageValue, Witness};
+use sov_rollup_interface::digest::Digest;
+
+/// An authenticated data structure committing to the state, as seen by the verifier.
+///
+/// Implementations consume the hints recorded by [`NativeStateCommitment::compute_update`],
+/// in the same order.
+pub trait StateCommitment<H: Digest<OutputSize = sha2::digest::typenum::U32>> {
+    /// A cryptographic commitment to the whole state.
+    type Root: Serialize
+        + DeserializeOwned
+        + fmt::Debug
+        + Clone
+        + BorshSerialize
+        + BorshDeserialize
+        + Eq
+        + Send
+        + Sync
+        + AsRef<[u8]>
+        + Into<[u8; 32]>;
+
+    /// A proof that a key has a particular value, or is absent, under a [`Self::Root`].
+    type Proof: Serialize
+        + DeserializeOwned
+        + fmt::Debug
+        + Clone
+        + BorshSerialize
+        + BorshDeserialize;
+
+    /// Verifies every read of `state_accesses` against `prev_root`, using the proofs provided by `witness`.
+    fn verify_reads<W: Witness>(
+        prev_root: &Self::Root,
+        state_accesses: &OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<()>;
+
+    /// Verifies that applying the writes of `state_accesses` to `prev_root` produces the root
+    /// provided by `witness`, and returns it.
+    fn verify_update<W: Witness>(
+        prev_root: &Self::Root,
+        state_accesses: OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<Self::Root>;
+
+    /// Verifies that `proof` opens `root` to `value` at `key`.
+    fn verify_proof(
+        root: &Self::Root,
+        key: &StorageKey,
+        value: Option<&StorageValue>,
+        proof: Self::Proof,
+    ) -> anyhow::Result<()>;
+}
+
+/// The native side of a [`StateCommitment`], which maintains the data structure in the [`StateDB`]
+/// and records the hints needed by the verifier.
+#[cfg(feature = "native")]
+pub trait NativeStateCommitment<H: Digest<OutputSize = sha2::digest::typenum::U32>>:
+    StateCommitment<H>
+{
+    /// The changes to the data structure computed from a set of writes, not yet written to the database.
+    type Update;
+
+    /// Returns the root of the state at `version`.
+    fn get_root<Q: QueryManager>(db: &StateDB<Q>, version: u64) -> anyhow::Result<Self::Root>;
+
+    /// Returns the value of `key` at `version`, with a proof of it.
+    fn get_with_proof<Q: QueryManager>(
+        db: &StateDB<Q>,
+        key: &StorageKey,
+        version: u64,
+    ) -> anyhow::Result<(Option<StorageValue>, Self::Proof)>;
+
+    /// Computes the update of the data structure applying the writes of `state_accesses` on top of the
+    /// latest version of `db`. Records the previous root into `witness`, followed by the hints verified
+    /// by [`StateCommitment::verify_reads`] and [`StateCommitment::verify_update`].
+    fn compute_update<Q: QueryManager, W: Witness>(
+        db: &StateDB<Q>,
+        state_accesses: OrderedReadsAndWrites,
+        witness: &W,
+    ) -> anyhow::Result<(Self::Root, Self::Update)>;
+
+    /// Computes the data structure holding exactly the key-value pairs of `entries` at `version`, on top of
+    /// an empty `db`. Used to import a snapshot of the state without replaying the history which produced it.
+    fn compute_import<Q: QueryManager>(
+        db: &StateDB<Q>,
+        version: u64,
+        entries: Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> anyhow::Result<(Self::Root, Self::Update)>;
+
+    /// Writes `update` to `db`. Doesn't increment the version of `db`.
+    fn commit_update<Q: QueryManager>(db: &StateDB<Q>, update: &Self::Update)
+        -> anyhow::Result<()>;
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sov_modules_core::Witness;
+
+use crate::ArrayWitness;
+
+/// The size of the nodes deduplicated across hints, which is the output size of the hashers
+/// allowed by [`MerkleProofSpec`](crate::MerkleProofSpec).
+const NODE_SIZE: usize = 32;
+
+/// The flag set in the header of an encoded witness whose nodes are deduplicated.
+const DEDUPLICATED_FLAG: u8 = 1;
+/// The flag set in the header of an encoded witness whose hints are compressed.
+const COMPRESSED_FLAG: u8 = 2;
+
+/// A reference from a 32-byte word of a hint to an entry of the node table.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct NodeRef {
+    /// The position of the word, counted from the end of the hint.
+    word: u32,
+    /// The index of the node in the node table.
+    node: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct EncodedHint {
+    /// The bytes of the hint which aren't replaced by a node reference, in order.
+    literal: Vec<u8>,
+    refs: Vec<NodeRef>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct EncodedHints {
+    nodes: Vec<[u8; NODE_SIZE]>,
+    hints: Vec<EncodedHint>,
+}
+
+/// Returns the 32-byte words of `hint` aligned on its end, which is where the sibling hashes of
+/// merkle proofs are serialized, with their positions counted from the end.
+fn words(hint: &[u8]) -> impl Iterator<Item = (u32, [u8; NODE_SIZE])> + '_ {
+    hint.rchunks_exact(NODE_SIZE).enumerate().map(|(i, word)| {
+        (
+            i as u32,
+            word.try_into().expect("Chunks are exactly NODE_SIZE long"),
+        )
+    })
+}
+
+/// Builds [`CompactWitness`]es, selecting how their hints are encoded when serialized.
+///
+/// # Example
+///
+/// ```
+/// use sov_state::{CompactWitness, Witness, WitnessBuilder};
+///
+/// let witness = WitnessBuilder::new().compress(true).build();
+/// witness.add_hint([7u8; 32]);
+/// witness.add_hint([7u8; 32]);
+///
+/// let serialized = serde_json::to_vec(&witness).unwrap();
+/// let witness: CompactWitness = serde_json::from_slice(&serialized).unwrap();
+/// assert_eq!(witness.get_hint::<[u8; 32]>(), [7u8; 32]);
+/// assert_eq!(witness.get_hint::<[u8; 32]>(), [7u8; 32]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WitnessBuilder {
+    deduplicate_nodes: bool,
+    compress: bool,
+}
+
+impl Default for WitnessBuilder {
+    fn default() -> Self {
+        Self {
+            deduplicate_nodes: true,
+            compress: false,
+        }
+    }
+}
+
+impl WitnessBuilder {
+    /// Creates a new [`WitnessBuilder`] deduplicating nodes, without compression.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the 32-byte nodes repeated across hints, like the sibling hashes shared by the merkle
+    /// proofs of nearby keys, are serialized once.
+    pub fn deduplicate_nodes(mut self, deduplicate_nodes: bool) -> Self {
+        self.deduplicate_nodes = deduplicate_nodes;
+        self
+    }
+
+    /// Sets whether the hints are compressed with snappy once serialized.
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Creates an empty [`CompactWitness`] with the encoding of the builder.
+    pub fn build(self) -> CompactWitness {
+        CompactWitness {
+            hints: ArrayWitness::default(),
+            encoding: self,
+        }
+    }
+
+    fn encode(&self, hints: &[Vec<u8>]) -> Vec<u8> {
+        // Only the nodes appearing more than once go in the node table, the others stay inline
+        let mut occurrences = HashMap::<[u8; NODE_SIZE], u32>::new();
+        if self.deduplicate_nodes {
+            for (_, word) in hints.iter().flat_map(|hint| words(hint)) {
+                *occurrences.entry(word).or_default() += 1;
+            }
+        }
+
+        let mut node_indices = HashMap::<[u8; NODE_SIZE], u32>::new();
+        let mut encoded = EncodedHints {
+            nodes: Vec::new(),
+            hints: Vec::with_capacity(hints.len()),
+        };
+        for hint in hints {
+            let mut refs = Vec::new();
+            for (position, word) in words(hint) {
+                if occurrences.get(&word).copied().unwrap_or_default() < 2 {
+                    continue;
+                }
+                let node = *node_indices.entry(word).or_insert_with(|| {
+                    encoded.nodes.push(word);
+                    encoded.nodes.len() as u32 - 1
+                });
+                refs.push(NodeRef {
+                    word: position,
+                    node,
+                });
+            }
+
+            let literal = if refs.is_empty() {
+                hint.clone()
+            } else {
+                let mut replaced_words = vec![false; hint.len() / NODE_SIZE];
+                for node_ref in &refs {
+                    replaced_words[node_ref.word as usize] = true;
+                }
+                let prefix_len = hint.len() % NODE_SIZE;
+                let mut literal = hint[..prefix_len].to_vec();
+                for (word, replaced) in hint[prefix_len..]
+                    .chunks_exact(NODE_SIZE)
+                    .zip(replaced_words.iter().rev())
+                {
+                    if !replaced {
+                        literal.extend_from_slice(word);
+                    }
+                }
+                literal
+            };
+            encoded.hints.push(EncodedHint { literal, refs });
+        }
+
+        let encoded = encoded
+            .try_to_vec()
+            .expect("Serializing the hints to a vector can't fail");
+        let mut flags = 0;
+        if self.deduplicate_nodes {
+            flags |= DEDUPLICATED_FLAG;
+        }
+        if self.compress {
+            flags |= COMPRESSED_FLAG;
+        }
+        let mut output = vec![flags];
+        if self.compress {
+            output.extend(
+                snap::raw::Encoder::new()
+                    .compress_vec(&encoded)
+                    .expect("Compressing the hints can't fail"),
+            );
+        } else {
+            output.extend(encoded);
+        }
+        output
+    }
+
+    fn decode(bytes: &[u8]) -> anyhow::Result<(Self, Vec<Vec<u8>>)> {
+        let (flags, encoded) = bytes
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("The encoded witness is empty"))?;
+        let compress = flags & COMPRESSED_FLAG != 0;
+        let decompressed;
+        let encoded = if compress {
+            decompressed = snap::raw::Decoder::new().decompress_vec(encoded)?;
+            &decompressed[..]
+        } else {
+            encoded
+        };
+        let EncodedHints { nodes, hints } = EncodedHints::try_from_slice(encoded)?;
+
+        let hints = hints
+            .into_iter()
+            .map(|EncodedHint { literal, refs }| -> anyhow::Result<Vec<u8>> {
+                let len = literal.len() + refs.len() * NODE_SIZE;
+                let mut hint = vec![0; len];
+                let mut replaced = vec![false; len];
+                for NodeRef { word, node } in refs {
+                    let node = nodes
+                        .get(node as usize)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid node reference {node}"))?;
+                    let end = len
+                        .checked_sub(word as usize * NODE_SIZE)
+                        .filter(|end| *end >= NODE_SIZE)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid word position {word}"))?;
+                    hint[end - NODE_SIZE..end].copy_from_slice(node);
+                    replaced[end - NODE_SIZE..end].fill(true);
+                }
+                let mut literal = literal.into_iter();
+                for (byte, replaced) in hint.iter_mut().zip(replaced) {
+                    if !replaced {
+                        *byte = literal
+                            .next()
+                            .ok_or_else(|| anyhow::anyhow!("The literal bytes are truncated"))?;
+                    }
+                }
+                Ok(hint)
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok((
+            Self {
+                deduplicate_nodes: flags & DEDUPLICATED_FLAG != 0,
+                compress,
+            },
+            hints,
+        ))
+    }
+}
+
+/// A [`Witness`] which reduces the size of its serialized hints, and so the size of the input of the guest.
+///
+/// It behaves like an [`ArrayWitness`], but is serialized as the encoding selected by the
+/// [`WitnessBuilder`] it was created with. [`CompactWitness::default`] deduplicates nodes without
+/// compressing the hints.
+#[derive(Debug)]
+pub struct CompactWitness {
+    hints: ArrayWitness,
+    encoding: WitnessBuilder,
+}
+
+impl Default for CompactWitness {
+    fn default() -> Self {
+        WitnessBuilder::default().build()
+    }
+}
+
+impl Witness for CompactWitness {
+    fn add_hint<T: BorshSerialize>(&self, hint: T) {
+        self.hints.add_hint(hint)
+    }
+
+    fn get_hint<T: BorshDeserialize>(&self) -> T {
+        self.hints.get_hint()
+    }
+
+    fn merge(&self, rhs: &Self) {
+        self.hints.merge(&rhs.hints)
+    }
+}
+
+impl Serialize for CompactWitness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hints = self.hints.hints.lock().unwrap();
+        self.encoding.encode(&hints).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactWitness {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        let (encoding, hints) = WitnessBuilder::decode(&bytes).map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            hints: ArrayWitness::from_hints(hints),
+            encoding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::any;
+
+    use super::*;
+
+    fn round_trip(builder: WitnessBuilder, hints: &[Vec<u8>]) -> Vec<u8> {
+        let encoded = builder.encode(hints);
+        let (decoded_builder, decoded_hints) = WitnessBuilder::decode(&encoded).unwrap();
+        assert_eq!(decoded_builder.deduplicate_nodes, builder.deduplicate_nodes);
+        assert_eq!(decoded_builder.compress, builder.compress);
+        assert_eq!(decoded_hints, hints);
+        encoded
+    }
+
+    #[test]
+    fn test_shared_nodes_are_serialized_once() {
+        // Two proofs with a different leaf and the same 8 siblings
+        let siblings: Vec<[u8; 32]> = (0..8).map(|i| [i; 32]).collect();
+        let hints: Vec<Vec<u8>> = (0..2u8)
+            .map(|leaf| {
+                let mut hint = vec![leaf; 5];
+                for sibling in &siblings {
+                    hint.extend_from_slice(sibling);
+                }
+                hint
+            })
+            .collect();
+
+        let plain = round_trip(WitnessBuilder::new().deduplicate_nodes(false), &hints);
+        let deduplicated = round_trip(WitnessBuilder::new(), &hints);
+        assert!(deduplicated.len() + 7 * NODE_SIZE < plain.len());
+        round_trip(WitnessBuilder::new().compress(true), &hints);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_encoding_round_trip(
+            hints in vec((vec(any::<u8>(), 0..40), vec(0..4u8, 0..8)), 0..20),
+            deduplicate_nodes in any::<bool>(),
+            compress in any::<bool>(),
+        ) {
+            // Hints made of a random prefix followed by words taken from a small set, so that words repeat
+            let hints: Vec<Vec<u8>> = hints
+                .into_iter()
+                .map(|(mut hint, words)| {
+                    for word in words {
+                        hint.extend_from_slice(&[word; NODE_SIZE]);
+                    }
+                    hint
+                })
+                .collect();
+            let builder = WitnessBuilder::new()
+                .deduplicate_nodes(deduplicate_nodes)
+                .compress(compress);
+            round_trip(builder, &hints);
+        }
+    }
+}
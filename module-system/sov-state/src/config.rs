@@ -8,4 +8,35 @@ use std::path::PathBuf;
 pub struct Config {
     /// Path to folder where storage files will be stored.
     pub path: PathBuf,
+    /// Which historical versions of the state, and slots of the ledger, are kept. Everything is kept by default.
+    #[serde(default)]
+    pub pruning: PruningMode,
+}
+
+/// How much history a node keeps. Since a version of the state is committed for every slot,
+/// the same number of versions of the state and of slots of the ledger are retained.
+///
+/// In TOML: `pruning = "archive"`, `pruning = { keep_recent = 1000 }` or `pruning = "minimal"`.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningMode {
+    /// Every version of the state, and every slot with its batches, transactions and events, is kept.
+    #[default]
+    Archive,
+    /// Only the latest `n` finalized versions of the state, and the latest `n` slots, are kept.
+    KeepRecent(u64),
+    /// Only the latest finalized version of the state, and the latest slot, are kept.
+    Minimal,
+}
+
+impl PruningMode {
+    /// The number of latest versions of the state and slots of the ledger which are kept,
+    /// or `None` if nothing is pruned.
+    pub fn retained_versions(&self) -> Option<u64> {
+        match self {
+            PruningMode::Archive => None,
+            PruningMode::KeepRecent(versions) => Some((*versions).max(1)),
+            PruningMode::Minimal => Some(1),
+        }
+    }
 }
@@ -3,10 +3,14 @@
 #![deny(missing_docs)]
 
 pub mod codec;
+pub mod commitment;
 
 #[cfg(feature = "native")]
 mod prover_storage;
+#[cfg(feature = "native")]
+pub mod snapshot;
 
+mod compact_witness;
 mod witness;
 mod zk_storage;
 
@@ -22,17 +26,35 @@ pub use sov_modules_core::{
 };
 use sov_rollup_interface::digest::Digest;
 
+#[cfg(feature = "native")]
+use crate::commitment::NativeStateCommitment;
+use crate::commitment::{Jmt, StateCommitment};
+pub use crate::compact_witness::{CompactWitness, WitnessBuilder};
 pub use crate::witness::ArrayWitness;
 
-/// A trait specifying the hash function and format of the witness used in
-/// merkle proofs for storage access
+/// A trait specifying the hash function, the authenticated data structure and format of the witness
+/// used in merkle proofs for storage access
 pub trait MerkleProofSpec {
     /// The structure that accumulates the witness data
     type Witness: Witness + Send + Sync;
     /// The hash function used to compute the merkle root
     type Hasher: Digest<OutputSize = sha2::digest::typenum::U32>;
+    /// The authenticated data structure committing to the state
+    #[cfg(feature = "native")]
+    type Commitment: NativeStateCommitment<Self::Hasher>;
+    /// The authenticated data structure committing to the state
+    #[cfg(not(feature = "native"))]
+    type Commitment: StateCommitment<Self::Hasher>;
 }
 
+/// The root of the state committed by the [`MerkleProofSpec`] `S`.
+pub type StateRoot<S> =
+    <<S as MerkleProofSpec>::Commitment as StateCommitment<<S as MerkleProofSpec>::Hasher>>::Root;
+
+/// The proofs of the state committed by the [`MerkleProofSpec`] `S`.
+pub type StateProof<S> =
+    <<S as MerkleProofSpec>::Commitment as StateCommitment<<S as MerkleProofSpec>::Hasher>>::Proof;
+
 use sha2::Sha256;
 
 /// The default [`MerkleProofSpec`] implementation.
@@ -45,4 +67,6 @@ impl MerkleProofSpec for DefaultStorageSpec {
     type Witness = ArrayWitness;
 
     type Hasher = Sha256;
+
+    type Commitment = Jmt;
 }
@@ -1,8 +1,7 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use jmt::storage::{NodeBatch, TreeWriter};
-use jmt::{JellyfishMerkleTree, KeyHash, Version};
+use jmt::Version;
 use sov_db::native_db::NativeDB;
 use sov_db::schema::{QueryManager, ReadOnlyDbSnapshot};
 use sov_db::state_db::StateDB;
@@ -11,8 +10,10 @@ use sov_modules_core::{
     StorageValue, Witness,
 };
 
+use crate::commitment::NativeStateCommitment;
 use crate::config::Config;
-use crate::MerkleProofSpec;
+use crate::snapshot::StateSnapshot;
+use crate::{MerkleProofSpec, StateProof, StateRoot};
 
 /// A [`Storage`] implementation to be used by the prover in a native execution
 /// environment (outside of the zkVM).
@@ -54,6 +55,53 @@ impl<S: MerkleProofSpec, Q> ProverStorage<S, Q> {
 }
 
 impl<S: MerkleProofSpec, Q: QueryManager> ProverStorage<S, Q> {
+    /// Takes a snapshot of the state at `version`.
+    pub fn export_snapshot(&self, version: Version) -> anyhow::Result<StateSnapshot> {
+        let oldest_retained_version = self.oldest_retained_version();
+        if version < oldest_retained_version {
+            anyhow::bail!(
+                "Version {} of the state was pruned, the oldest retained version is {}",
+                version,
+                oldest_retained_version
+            );
+        }
+
+        let root = self.get_root_hash(version)?;
+        let entries = self.db.get_values_in_range(version, &[], None)?;
+        Ok(StateSnapshot {
+            version,
+            root: root.into(),
+            entries,
+        })
+    }
+
+    /// Writes the key-value pairs of `snapshot` into this empty storage, at the version of the snapshot.
+    /// Fails if the root computed from the key-value pairs doesn't match the root of the snapshot.
+    /// The versions before the snapshot can't be read.
+    pub fn import_snapshot(&self, snapshot: StateSnapshot) -> anyhow::Result<()> {
+        if !self.is_empty() {
+            anyhow::bail!("Cannot import a snapshot into a non-empty storage");
+        }
+
+        let StateSnapshot {
+            version,
+            root: expected_root,
+            entries,
+        } = snapshot;
+        let (root, update) = S::Commitment::compute_import(&self.db, version, entries)?;
+        let root: [u8; 32] = root.into();
+        if root != expected_root {
+            anyhow::bail!(
+                "The state of the snapshot has root 0x{}, but the snapshot claims 0x{}",
+                hex::encode(root),
+                hex::encode(expected_root)
+            );
+        }
+
+        S::Commitment::commit_update(&self.db, &update)?;
+        self.db.put_oldest_retained_version(version)
+    }
+
     fn read_value(&self, key: &StorageKey, version: Option<Version>) -> Option<StorageValue> {
         let version_to_use = version.unwrap_or_else(|| self.db.get_next_version());
         match self
@@ -67,17 +115,12 @@ impl<S: MerkleProofSpec, Q: QueryManager> ProverStorage<S, Q> {
     }
 }
 
-pub struct ProverStateUpdate {
-    pub(crate) node_batch: NodeBatch,
-    pub key_preimages: Vec<(KeyHash, CacheKey)>,
-}
-
 impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
     type Witness = S::Witness;
     type RuntimeConfig = Config;
-    type Proof = jmt::proof::SparseMerkleProof<S::Hasher>;
-    type Root = jmt::RootHash;
-    type StateUpdate = ProverStateUpdate;
+    type Proof = StateProof<S>;
+    type Root = StateRoot<S>;
+    type StateUpdate = <S::Commitment as NativeStateCommitment<S::Hasher>>::Update;
 
     fn get(
         &self,
@@ -99,86 +142,22 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
             .map(Into::into)
     }
 
+    fn oldest_retained_version(&self) -> Version {
+        self.db
+            .get_oldest_retained_version()
+            .expect("Unable to read the oldest retained version from db")
+    }
+
     fn compute_state_update(
         &self,
         state_accesses: OrderedReadsAndWrites,
         witness: &Self::Witness,
     ) -> Result<(Self::Root, Self::StateUpdate), anyhow::Error> {
-        let latest_version = self.db.get_next_version() - 1;
-        let jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&self.db);
-
-        // Handle empty jmt
-        // TODO: Fix this before introducing snapshots!
-        if jmt.get_root_hash_option(latest_version)?.is_none() {
-            assert_eq!(latest_version, 0);
-            let empty_batch = Vec::default().into_iter();
-            let (_, tree_update) = jmt
-                .put_value_set(empty_batch, latest_version)
-                .expect("JMT update must succeed");
-
-            self.db
-                .write_node_batch(&tree_update.node_batch)
-                .expect("db write must succeed");
-        }
-        let prev_root = jmt
-            .get_root_hash(latest_version)
-            .expect("Previous root hash was just populated");
-        witness.add_hint(prev_root.0);
-
-        // For each value that's been read from the tree, read it from the logged JMT to populate hints
-        for (key, read_value) in state_accesses.ordered_reads {
-            let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            // TODO: Switch to the batch read API once it becomes available
-            let (result, proof) = jmt.get_with_proof(key_hash, latest_version)?;
-            if result.as_ref() != read_value.as_ref().map(|f| f.value.as_ref()) {
-                anyhow::bail!("Bug! Incorrect value read from jmt");
-            }
-            witness.add_hint(proof);
-        }
-
-        let mut key_preimages = Vec::with_capacity(state_accesses.ordered_writes.len());
-
-        // Compute the jmt update from the write batch
-        let batch = state_accesses
-            .ordered_writes
-            .into_iter()
-            .map(|(key, value)| {
-                let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-                key_preimages.push((key_hash, key));
-                (
-                    key_hash,
-                    value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
-                )
-            });
-
-        let next_version = self.db.get_next_version();
-
-        let (new_root, update_proof, tree_update) = jmt
-            .put_value_set_with_proof(batch, next_version)
-            .expect("JMT update must succeed");
-
-        witness.add_hint(update_proof);
-        witness.add_hint(new_root.0);
-
-        let state_update = ProverStateUpdate {
-            node_batch: tree_update.node_batch,
-            key_preimages,
-        };
-
-        Ok((new_root, state_update))
+        S::Commitment::compute_update(&self.db, state_accesses, witness)
     }
 
     fn commit(&self, state_update: &Self::StateUpdate, accessory_writes: &OrderedReadsAndWrites) {
         let latest_version = self.db.get_next_version() - 1;
-        self.db
-            .put_preimages(
-                state_update
-                    .key_preimages
-                    .iter()
-                    .map(|(key_hash, key)| (*key_hash, key.key.as_ref())),
-            )
-            .expect("Preimage put must succeed");
-
         self.native_db
             .set_values(
                 accessory_writes
@@ -192,9 +171,7 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
         // Write the state values last, since we base our view of what has been touched
         // on state. If the node crashes between the `native_db` update and this update,
         // then the whole `commit` will be re-run later so no data can be lost.
-        self.db
-            .write_node_batch(&state_update.node_batch)
-            .expect("db write must succeed");
+        S::Commitment::commit_update(&self.db, state_update).expect("db write must succeed");
 
         // Finally, update our in-memory view of the current item numbers
         self.db.inc_next_version();
@@ -205,9 +182,8 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
         state_proof: StorageProof<Self::Proof>,
     ) -> Result<(StorageKey, Option<StorageValue>), anyhow::Error> {
         let StorageProof { key, value, proof } = state_proof;
-        let key_hash = KeyHash::with::<S::Hasher>(key.as_ref());
 
-        proof.verify(state_root, key_hash, value.as_ref().map(|v| v.value()))?;
+        S::Commitment::verify_proof(&state_root, &key, value.as_ref(), proof)?;
         Ok((key, value))
     }
 
@@ -219,23 +195,52 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
 
 impl<S: MerkleProofSpec, Q: QueryManager> NativeStorage for ProverStorage<S, Q> {
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof> {
-        let merkle = JellyfishMerkleTree::<StateDB<Q>, S::Hasher>::new(&self.db);
-        let (val_opt, proof) = merkle
-            .get_with_proof(
-                KeyHash::with::<S::Hasher>(key.as_ref()),
-                self.db.get_next_version() - 1,
-            )
-            .unwrap();
-        StorageProof {
-            key,
-            value: val_opt.map(StorageValue::from),
-            proof,
-        }
+        let (value, proof) =
+            S::Commitment::get_with_proof(&self.db, &key, self.db.get_next_version() - 1).unwrap();
+        StorageProof { key, value, proof }
+    }
+
+    fn get_with_proof_at(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> anyhow::Result<StorageProof<Self::Proof>> {
+        anyhow::ensure!(
+            version >= self.oldest_retained_version() && version <= self.latest_version(),
+            "Version {} is not retained: the retained versions are {}..={}",
+            version,
+            self.oldest_retained_version(),
+            self.latest_version()
+        );
+        let (value, proof) = S::Commitment::get_with_proof(&self.db, &key, version)?;
+        Ok(StorageProof { key, value, proof })
+    }
+
+    fn latest_version(&self) -> Version {
+        self.db.get_next_version() - 1
+    }
+
+    fn get_root_hash(&self, version: Version) -> anyhow::Result<Self::Root> {
+        S::Commitment::get_root(&self.db, version)
     }
 
-    fn get_root_hash(&self, version: Version) -> anyhow::Result<jmt::RootHash> {
-        let temp_merkle: JellyfishMerkleTree<'_, StateDB<Q>, S::Hasher> =
-            JellyfishMerkleTree::new(&self.db);
-        temp_merkle.get_root_hash(version)
+    fn get_range(
+        &self,
+        start: &[u8],
+        end: Option<&[u8]>,
+        version: Option<Version>,
+    ) -> Vec<(StorageKey, StorageValue)> {
+        let version_to_use = version.unwrap_or_else(|| self.db.get_next_version());
+        match self.db.get_values_in_range(version_to_use, start, end) {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = CacheKey { key: Arc::new(key) };
+                    (key.into(), value.into())
+                })
+                .collect(),
+            // It is ok to panic here, we assume the db is available and consistent.
+            Err(e) => panic!("Unable to read values from db: {e}"),
+        }
     }
 }
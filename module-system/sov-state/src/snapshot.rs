@@ -0,0 +1,16 @@
+//! Snapshots of the state, which let a new node start from a recent version of the state instead of
+//! replaying the whole history of the rollup.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::Version;
+
+/// All the key-value pairs of the state at a given version, with the root committing to them.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StateSnapshot {
+    /// The version of the state
+    pub version: Version,
+    /// The root of the state at `version`
+    pub root: [u8; 32],
+    /// The key-value pairs of the state, sorted by key
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
@@ -23,7 +23,21 @@ use sov_modules_core::Witness;
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct ArrayWitness {
     next_idx: AtomicUsize,
-    hints: Mutex<Vec<Vec<u8>>>,
+    pub(crate) hints: Mutex<Vec<Vec<u8>>>,
+}
+
+impl ArrayWitness {
+    pub(crate) fn from_hints(hints: Vec<Vec<u8>>) -> Self {
+        Self {
+            next_idx: AtomicUsize::new(0),
+            hints: Mutex::new(hints),
+        }
+    }
+
+    /// Returns the total size of the hints of the witness, in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.hints.lock().unwrap().iter().map(Vec::len).sum()
+    }
 }
 
 impl Witness for ArrayWitness {
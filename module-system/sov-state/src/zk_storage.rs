@@ -1,14 +1,12 @@
 use std::marker::PhantomData;
-use std::sync::Arc;
 
-use jmt::KeyHash;
 use sov_modules_core::{
     OrderedReadsAndWrites, Storage, StorageKey, StorageProof, StorageValue, Witness,
 };
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 
-use crate::MerkleProofSpec;
+use crate::{MerkleProofSpec, StateProof, StateRoot};
 
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 extern crate risc0_zkvm;
@@ -37,67 +35,28 @@ impl<S: MerkleProofSpec> ZkStorage<S> {
 }
 
 #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
-fn jmt_verify_existence<S: MerkleProofSpec>(
-    prev_state_root: [u8; 32],
+fn verify_state_reads<S: MerkleProofSpec>(
+    prev_state_root: &StateRoot<S>,
     state_accesses: &OrderedReadsAndWrites,
     witness: &S::Witness,
 ) -> Result<(), anyhow::Error> {
-    // For each value that's been read from the tree, verify the provided smt proof
-    for (key, read_value) in &state_accesses.ordered_reads {
-        let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-        // TODO: Switch to the batch read API once it becomes available
-        let proof: jmt::proof::SparseMerkleProof<S::Hasher> = witness.get_hint();
-
-        match read_value {
-            Some(val) => proof.verify_existence(
-                jmt::RootHash(prev_state_root),
-                key_hash,
-                val.value.as_ref(),
-            )?,
-            None => proof.verify_nonexistence(jmt::RootHash(prev_state_root), key_hash)?,
-        }
-    }
-
-    Ok(())
+    S::Commitment::verify_reads(prev_state_root, state_accesses, witness)
 }
 
 #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
-fn jmt_verify_update<S: MerkleProofSpec>(
-    prev_state_root: [u8; 32],
+fn verify_state_update<S: MerkleProofSpec>(
+    prev_state_root: &StateRoot<S>,
     state_accesses: OrderedReadsAndWrites,
     witness: &S::Witness,
-) -> [u8; 32] {
-    // Compute the jmt update from the write batch
-    let batch = state_accesses
-        .ordered_writes
-        .into_iter()
-        .map(|(key, value)| {
-            let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            (
-                key_hash,
-                value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
-            )
-        })
-        .collect::<Vec<_>>();
-
-    let update_proof: jmt::proof::UpdateMerkleProof<S::Hasher> = witness.get_hint();
-    let new_root: [u8; 32] = witness.get_hint();
-    update_proof
-        .verify_update(
-            jmt::RootHash(prev_state_root),
-            jmt::RootHash(new_root),
-            batch,
-        )
-        .expect("Updates must be valid");
-
-    new_root
+) -> Result<StateRoot<S>, anyhow::Error> {
+    S::Commitment::verify_update(prev_state_root, state_accesses, witness)
 }
 
 impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
     type Witness = S::Witness;
     type RuntimeConfig = ();
-    type Proof = jmt::proof::SparseMerkleProof<S::Hasher>;
-    type Root = jmt::RootHash;
+    type Proof = StateProof<S>;
+    type Root = StateRoot<S>;
     type StateUpdate = ();
 
     fn get(
@@ -116,12 +75,12 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
     ) -> Result<(Self::Root, Self::StateUpdate), anyhow::Error> {
         let prev_state_root = witness.get_hint();
 
-        // For each value that's been read from the tree, verify the provided smt proof
-        jmt_verify_existence::<S>(prev_state_root, &state_accesses, witness)?;
+        // For each value that's been read from the tree, verify the provided proof
+        verify_state_reads::<S>(&prev_state_root, &state_accesses, witness)?;
 
-        let new_root = jmt_verify_update::<S>(prev_state_root, state_accesses, witness);
+        let new_root = verify_state_update::<S>(&prev_state_root, state_accesses, witness)?;
 
-        Ok((jmt::RootHash(new_root), ()))
+        Ok((new_root, ()))
     }
 
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
@@ -132,9 +91,8 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
         state_proof: StorageProof<Self::Proof>,
     ) -> Result<(StorageKey, Option<StorageValue>), anyhow::Error> {
         let StorageProof { key, value, proof } = state_proof;
-        let key_hash = KeyHash::with::<S::Hasher>(key.as_ref());
 
-        proof.verify(state_root, key_hash, value.as_ref().map(|v| v.value()))?;
+        S::Commitment::verify_proof(&state_root, &key, value.as_ref(), proof)?;
         Ok((key, value))
     }
 
@@ -0,0 +1,8 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+mod receipts;
+mod runner;
+
+pub use receipts::SlotReceipt;
+pub use runner::{TestGenesisParams, TestRunner, TestStf};
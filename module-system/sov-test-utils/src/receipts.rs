@@ -0,0 +1,100 @@
+use borsh::BorshDeserialize;
+use sov_mock_da::MockAddress;
+use sov_modules_api::{Event, ModuleEvent};
+use sov_modules_stf_blueprint::{BatchReceipt, SequencerOutcome, TxEffect};
+use sov_rollup_interface::stf::TransactionReceipt;
+
+/// The receipts of a slot applied by a [`TestRunner`](crate::TestRunner).
+#[derive(Debug, Clone)]
+pub struct SlotReceipt {
+    /// The height of the slot.
+    pub height: u64,
+    /// The receipts of the batches of the slot.
+    pub batch_receipts: Vec<BatchReceipt<SequencerOutcome<MockAddress>, TxEffect>>,
+}
+
+impl SlotReceipt {
+    /// The receipts of the transactions of all the batches of the slot, in order.
+    pub fn tx_receipts(&self) -> impl Iterator<Item = &TransactionReceipt<TxEffect>> {
+        self.batch_receipts
+            .iter()
+            .flat_map(|batch| batch.tx_receipts.iter())
+    }
+
+    /// The outcome of every transaction of the slot, in order.
+    pub fn tx_effects(&self) -> Vec<TxEffect> {
+        self.tx_receipts().map(|receipt| receipt.receipt).collect()
+    }
+
+    /// The events emitted by all the transactions of the slot, in order.
+    pub fn events(&self) -> impl Iterator<Item = &Event> {
+        self.tx_receipts().flat_map(|receipt| receipt.events.iter())
+    }
+
+    /// Decodes the typed events `E` emitted by the transactions of the slot.
+    pub fn events_of<E: ModuleEvent + BorshDeserialize>(&self) -> Vec<E> {
+        self.events()
+            .filter(|event| {
+                event.key().inner() == E::NAME.as_bytes() && event.version() == E::VERSION
+            })
+            .map(|event| {
+                E::try_from_slice(event.value().inner())
+                    .unwrap_or_else(|e| panic!("Failed to decode the event {}: {}", E::NAME, e))
+            })
+            .collect()
+    }
+
+    /// Panics unless every transaction of the slot was executed successfully.
+    pub fn assert_all_txs_succeeded(&self) -> &Self {
+        let effects = self.tx_effects();
+        if let Some(index) = effects
+            .iter()
+            .position(|effect| *effect != TxEffect::Successful)
+        {
+            panic!(
+                "Transaction {} of slot {} was reverted. The outcomes of the slot are {:?}",
+                index, self.height, effects
+            );
+        }
+        self
+    }
+
+    /// Panics unless the transaction at `index` in the slot was reverted.
+    pub fn assert_tx_reverted(&self, index: usize) -> &Self {
+        let effects = self.tx_effects();
+        assert_eq!(
+            effects.get(index),
+            Some(&TxEffect::Reverted),
+            "Transaction {} of slot {} wasn't reverted. The outcomes of the slot are {:?}",
+            index,
+            self.height,
+            effects
+        );
+        self
+    }
+
+    /// Panics unless a transaction of the slot emitted an event with the key `key`.
+    pub fn assert_event_emitted(&self, key: &str) -> &Self {
+        assert!(
+            self.events()
+                .any(|event| event.key().inner() == key.as_bytes()),
+            "No event {} was emitted in slot {}",
+            key,
+            self.height
+        );
+        self
+    }
+
+    /// Panics unless the sequencer was rewarded for every batch of the slot.
+    pub fn assert_sequencer_rewarded(&self) -> &Self {
+        for batch in &self.batch_receipts {
+            assert!(
+                matches!(batch.inner, SequencerOutcome::Rewarded(_)),
+                "The sequencer wasn't rewarded for a batch of slot {}: {:?}",
+                self.height,
+                batch.inner
+            );
+        }
+        self
+    }
+}
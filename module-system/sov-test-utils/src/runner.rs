@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use borsh::BorshSerialize;
+use sov_mock_da::{
+    MockAddress, MockBlob, MockBlock, MockDaSpec, MockValidityCond, MOCK_SEQUENCER_DA_ADDRESS,
+};
+use sov_mock_zkvm::MockZkvm;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{EncodeCall, Module, PrivateKey, PublicKey, Spec, WorkingSet};
+use sov_modules_stf_blueprint::kernels::basic::{BasicKernel, BasicKernelGenesisConfig};
+use sov_modules_stf_blueprint::{Batch, GenesisParams, RawTx, Runtime, StfBlueprint};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::{DefaultStorageSpec, Storage};
+
+use crate::SlotReceipt;
+
+type C = DefaultContext;
+
+/// The state transition function run by a [`TestRunner`].
+pub type TestStf<RT> =
+    StfBlueprint<C, MockDaSpec, MockZkvm<MockValidityCond>, RT, BasicKernel<C, MockDaSpec>>;
+
+/// The genesis parameters of the runtime `RT` and of the kernel of a [`TestRunner`].
+pub type TestGenesisParams<RT> = GenesisParams<
+    <RT as Runtime<C, MockDaSpec>>::GenesisConfig,
+    BasicKernelGenesisConfig<C, MockDaSpec>,
+>;
+
+/// Runs the runtime `RT` on simulated DA slots, backed by a temporary storage.
+///
+/// Transactions are queued with [`TestRunner::submit_call`] or [`TestRunner::submit_tx`], and
+/// executed as a single batch of the sequencer by the next call to [`TestRunner::advance_slot`].
+pub struct TestRunner<RT: Runtime<C, MockDaSpec>> {
+    stf: TestStf<RT>,
+    storage_manager: ProverStorageManager<MockDaSpec, DefaultStorageSpec>,
+    state_root: <<C as Spec>::Storage as Storage>::Root,
+    last_block: MockBlock,
+    pending_txs: Vec<RawTx>,
+    nonces: HashMap<<C as Spec>::Address, u64>,
+    chain_id: u64,
+    gas_tip: u64,
+    gas_limit: u64,
+    sequencer_da_address: MockAddress,
+    // Declared last, so that the storage is closed before its directory is removed.
+    _storage_dir: tempfile::TempDir,
+}
+
+impl<RT: Runtime<C, MockDaSpec>> TestRunner<RT> {
+    /// Initializes the runtime and the kernel with `genesis`, at slot height `0`.
+    pub fn new(genesis: TestGenesisParams<RT>) -> Self {
+        let storage_dir = tempfile::tempdir().expect("Failed to create the storage directory");
+        let mut storage_manager = ProverStorageManager::new(sov_state::config::Config {
+            path: storage_dir.path().to_path_buf(),
+            pruning: Default::default(),
+        })
+        .expect("Failed to create the storage manager");
+
+        let chain_id = genesis.kernel.chain_state.chain_id;
+        let stf = TestStf::<RT>::new();
+        let genesis_block = MockBlock::default();
+        let storage = storage_manager
+            .create_storage_on(genesis_block.header())
+            .expect("Failed to create the genesis storage");
+        let (state_root, change_set) = stf.init_chain(storage, genesis);
+        storage_manager
+            .save_change_set(genesis_block.header(), change_set)
+            .expect("Failed to save the genesis state");
+
+        Self {
+            stf,
+            storage_manager,
+            state_root,
+            last_block: genesis_block,
+            pending_txs: vec![],
+            nonces: HashMap::new(),
+            chain_id,
+            gas_tip: 0,
+            gas_limit: 0,
+            sequencer_da_address: MockAddress::from(MOCK_SEQUENCER_DA_ADDRESS),
+            _storage_dir: storage_dir,
+        }
+    }
+
+    /// Sets the gas tip and the gas limit of the transactions signed by [`TestRunner::submit_call`].
+    pub fn with_gas(mut self, gas_tip: u64, gas_limit: u64) -> Self {
+        self.gas_tip = gas_tip;
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the DA address of the sequencer submitting the batches. It must be registered at genesis.
+    pub fn with_sequencer_da_address(mut self, sequencer_da_address: MockAddress) -> Self {
+        self.sequencer_da_address = sequencer_da_address;
+        self
+    }
+
+    /// The height of the last applied slot.
+    pub fn height(&self) -> u64 {
+        self.last_block.header.height
+    }
+
+    /// The state root after the last applied slot.
+    pub fn state_root(&self) -> &<<C as Spec>::Storage as Storage>::Root {
+        &self.state_root
+    }
+
+    /// Signs a transaction calling the module `M` with `msg`, and queues it for the next slot.
+    /// The nonce of `sender` is tracked by the runner, starting from `0`.
+    pub fn submit_call<M: Module>(
+        &mut self,
+        sender: &<C as Spec>::PrivateKey,
+        msg: M::CallMessage,
+    ) -> &mut Self
+    where
+        RT: EncodeCall<M>,
+    {
+        let nonce = self
+            .nonces
+            .entry(sender.pub_key().to_address::<<C as Spec>::Address>())
+            .or_default();
+        let tx = Transaction::<C>::new_signed_tx(
+            sender,
+            <RT as EncodeCall<M>>::encode_call(msg),
+            self.chain_id,
+            self.gas_tip,
+            self.gas_limit,
+            *nonce,
+        );
+        *nonce += 1;
+        self.submit_tx(tx)
+    }
+
+    /// Queues an already signed transaction for the next slot.
+    pub fn submit_tx(&mut self, tx: Transaction<C>) -> &mut Self {
+        self.pending_txs.push(RawTx {
+            data: tx.try_to_vec().expect("Serialization to vec is infallible"),
+        });
+        self
+    }
+
+    /// Applies the next slot, with a batch of the queued transactions if there are any.
+    pub fn advance_slot(&mut self) -> SlotReceipt {
+        let block = self.last_block.next_mock();
+        let mut blobs = Vec::new();
+        if !self.pending_txs.is_empty() {
+            let batch = Batch {
+                txs: std::mem::take(&mut self.pending_txs),
+            };
+            blobs.push(MockBlob::new(
+                batch
+                    .try_to_vec()
+                    .expect("Serialization to vec is infallible"),
+                self.sequencer_da_address,
+                block.header.hash.into(),
+            ));
+        }
+
+        let storage = self
+            .storage_manager
+            .create_storage_on(block.header())
+            .expect("Failed to create the storage of the slot");
+        let result = self.stf.apply_slot(
+            &self.state_root,
+            storage,
+            Default::default(),
+            &block.header,
+            &block.validity_cond,
+            &mut blobs,
+        );
+        self.storage_manager
+            .save_change_set(block.header(), result.change_set)
+            .expect("Failed to save the state of the slot");
+        self.state_root = result.state_root;
+        self.last_block = block;
+
+        SlotReceipt {
+            height: self.height(),
+            batch_receipts: result.batch_receipts,
+        }
+    }
+
+    /// Applies `count` slots, the first one including the queued transactions.
+    pub fn advance_slots(&mut self, count: usize) -> Vec<SlotReceipt> {
+        (0..count).map(|_| self.advance_slot()).collect()
+    }
+
+    /// Reads the state after the last applied slot. The changes made by `query` are discarded.
+    pub fn query<T>(&mut self, query: impl FnOnce(&RT, &mut WorkingSet<C>) -> T) -> T {
+        let storage = self
+            .storage_manager
+            .create_storage_on(self.last_block.next_mock().header())
+            .expect("Failed to open the storage of the last slot");
+        let mut working_set = WorkingSet::new(storage);
+        query(&RT::default(), &mut working_set)
+    }
+}
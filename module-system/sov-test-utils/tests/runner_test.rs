@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use demo_stf::genesis_config::{get_genesis_config, GenesisPaths};
+use demo_stf::runtime::Runtime;
+use sov_bank::{get_genesis_token_address, Bank, Coins};
+use sov_cli::wallet_state::PrivateKeyAndAddress;
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{PrivateKey, Spec};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisConfig;
+use sov_modules_stf_blueprint::{GenesisParams, TxEffect};
+use sov_stf_runner::read_json_file;
+use sov_test_utils::{TestGenesisParams, TestRunner};
+use sov_value_setter::ValueSetter;
+
+type C = DefaultContext;
+type RT = Runtime<C, MockDaSpec>;
+
+const GENESIS_DIR: &str = "../../examples/test-data/genesis/integration-tests";
+
+fn genesis_params() -> TestGenesisParams<RT> {
+    let dir = Path::new(GENESIS_DIR);
+    GenesisParams {
+        runtime: get_genesis_config::<C, MockDaSpec>(&GenesisPaths::from_dir(dir)).unwrap(),
+        kernel: BasicKernelGenesisConfig {
+            chain_state: read_json_file(dir.join("chain_state.json")).unwrap(),
+        },
+    }
+}
+
+fn admin_key() -> <C as Spec>::PrivateKey {
+    let data =
+        std::fs::read_to_string("../../examples/test-data/keys/token_deployer_private_key.json")
+            .unwrap();
+    serde_json::from_str::<PrivateKeyAndAddress<C>>(&data)
+        .unwrap()
+        .private_key
+}
+
+#[test]
+fn test_value_setter_end_to_end() {
+    let mut runner = TestRunner::<RT>::new(genesis_params());
+    let admin = admin_key();
+    let stranger = <C as Spec>::PrivateKey::generate();
+
+    runner
+        .submit_call::<ValueSetter<C>>(&admin, sov_value_setter::CallMessage::SetValue(99))
+        .submit_call::<ValueSetter<C>>(&admin, sov_value_setter::CallMessage::SetValue(33));
+    runner
+        .advance_slot()
+        .assert_all_txs_succeeded()
+        .assert_sequencer_rewarded()
+        .assert_event_emitted("set");
+    assert_eq!(runner.height(), 1);
+    let value = runner
+        .query(|runtime, working_set| runtime.value_setter.query_value(working_set).unwrap().value);
+    assert_eq!(value, Some(33));
+
+    // Only the admin can set the value
+    runner.submit_call::<ValueSetter<C>>(&stranger, sov_value_setter::CallMessage::SetValue(1));
+    runner.advance_slot().assert_tx_reverted(0);
+    let value = runner
+        .query(|runtime, working_set| runtime.value_setter.query_value(working_set).unwrap().value);
+    assert_eq!(value, Some(33));
+}
+
+#[test]
+fn test_bank_transfers_across_slots() {
+    let mut runner = TestRunner::<RT>::new(genesis_params());
+    let admin = admin_key();
+    let receiver = generate_address::<C>("receiver");
+    let token_address = get_genesis_token_address::<C>("sov-demo-token", 0);
+
+    // Empty slots are applied too
+    let receipts = runner.advance_slots(3);
+    assert!(receipts
+        .iter()
+        .all(|receipt| receipt.batch_receipts.is_empty()));
+    assert_eq!(runner.height(), 3);
+
+    for amount in [100, 200] {
+        runner.submit_call::<Bank<C>>(
+            &admin,
+            sov_bank::CallMessage::Transfer {
+                to: receiver,
+                coins: Coins {
+                    amount,
+                    token_address,
+                },
+            },
+        );
+        let receipt = runner.advance_slot();
+        assert_eq!(receipt.tx_effects(), vec![TxEffect::Successful]);
+    }
+
+    let receiver_balance = runner.query(|runtime, working_set| {
+        runtime
+            .bank
+            .get_balance_of(receiver, token_address, working_set)
+    });
+    assert_eq!(receiver_balance, Some(300));
+}
@@ -4,6 +4,7 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
 #[cfg(feature = "native")]
 use crate::stf::Event;
@@ -190,7 +191,102 @@ pub enum ItemOrHash<T> {
     Full(T),
 }
 
+/// The status of the job proving the state transition of a slot.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    borsh::BorshSerialize,
+    borsh::BorshDeserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofJobStatus {
+    /// The job waits for a prover worker, either for the first time or to be retried.
+    Pending,
+    /// A prover worker is generating the proof.
+    Running,
+    /// The proof generation failed too many times. The job can be requeued.
+    Failed,
+    /// The proof was generated and sent to the DA.
+    Complete,
+}
+
+/// The body of a response to a JSON-RPC request for the proof job of a slot.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ProofJobResponse {
+    /// The DA height of the slot.
+    pub height: u64,
+    /// The status of the job.
+    pub status: ProofJobStatus,
+    /// The number of times the proof generation was started since the job was last queued.
+    pub attempts: u32,
+    /// The error of the last failed attempt, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// The body of a response to a JSON-RPC request for the execution and proving checkpoints of the node.
+/// Execution runs ahead of proving, so the difference between the two heights is the proving lag.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct CheckpointsResponse {
+    /// The DA height of the last slot executed by the node, if any.
+    pub last_executed_height: Option<u64>,
+    /// The DA height of the last slot whose proof was generated and sent to the DA, if any.
+    pub last_proven_height: Option<u64>,
+}
+
+/// The body of a response to a JSON-RPC request for the proofs posted to the DA by other parties,
+/// which were verified by the node.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct VerifiedProofResponse {
+    /// The DA height at which the proof was posted.
+    pub height: u64,
+    /// The hex encoded serialized proof.
+    #[serde(with = "utils::rpc_hex")]
+    pub proof: Vec<u8>,
+    /// The hex encoded public output of the proof.
+    #[serde(with = "utils::rpc_hex")]
+    pub public_output: Vec<u8>,
+}
+
+/// The body of a response to a JSON-RPC request for the merkle proof of a key of the rollup state.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct StateProofResponse {
+    /// The hex encoded state root against which the proof is made.
+    #[serde(with = "utils::rpc_hex")]
+    pub state_root: [u8; 32],
+    /// The version of the state, i.e. the number of the slot after which the rollup has this state root.
+    pub version: u64,
+    /// The hex encoded storage key.
+    #[serde(with = "utils::rpc_hex")]
+    pub key: Vec<u8>,
+    /// The hex encoded value of the key, or `None` if the key is absent from the state.
+    #[serde(with = "utils::rpc_hex_option")]
+    pub value: Option<Vec<u8>>,
+    /// The hex encoded, borsh serialized proof of the value, or of the absence of the key.
+    #[serde(with = "utils::rpc_hex")]
+    pub proof: Vec<u8>,
+}
+
+/// The error returned by a [`LedgerRpcProvider`] when the requested item was pruned from the ledger.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} {number} was pruned, the oldest retained {kind} is {oldest_retained}")]
+pub struct PrunedError {
+    /// The kind of the requested item: `slot`, `batch`, `transaction` or `event`.
+    pub kind: &'static str,
+    /// The number of the requested item.
+    pub number: u64,
+    /// The number of the oldest item of this kind which can still be read.
+    pub oldest_retained: u64,
+}
+
 /// A LedgerRpcProvider provides a way to query the ledger for information about slots, batches, transactions, and events.
+/// Queries by number for items which were pruned fail with a [`PrunedError`].
 #[cfg(feature = "native")]
 pub trait LedgerRpcProvider {
     /// Get the latest slot in the ledger.
@@ -301,6 +397,27 @@ pub trait LedgerRpcProvider {
         query_mode: QueryMode,
     ) -> Result<Vec<Option<TxResponse<T>>>, anyhow::Error>;
 
+    /// Get the proof jobs of the slots from DA height `start`, by increasing height, up to `limit` jobs.
+    fn get_proof_jobs(
+        &self,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<ProofJobResponse>, anyhow::Error>;
+
+    /// Queue the failed or running proof job of the slot at DA height `height` again, resetting its attempts.
+    fn requeue_proof_job(&self, height: u64) -> Result<ProofJobResponse, anyhow::Error>;
+
+    /// Get the DA heights of the last executed slot and of the last proven slot.
+    fn get_checkpoints(&self) -> Result<CheckpointsResponse, anyhow::Error>;
+
+    /// Get the proofs posted to the DA by other parties which were verified by the node,
+    /// from DA height `start`, by increasing height, up to `limit` proofs.
+    fn get_verified_proofs(
+        &self,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<VerifiedProofResponse>, anyhow::Error>;
+
     /// Get a notification each time a slot is processed
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 }
@@ -378,6 +495,42 @@ pub mod utils {
             deserializer.deserialize_str(HexStrVisitor(PhantomData))
         }
     }
+
+    /// Serialization and deserialization logic for optional `0x`-prefixed hex strings,
+    /// where `None` is `null`.
+    pub mod rpc_hex_option {
+        use core::fmt;
+
+        use hex::FromHex;
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use crate::maybestd::string::String;
+
+        /// Serializes `data` as a hex string with [`super::rpc_hex::serialize`], or as `null`.
+        pub fn serialize<S, T>(data: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: AsRef<[u8]>,
+        {
+            match data {
+                Some(data) => super::rpc_hex::serialize(data.as_ref(), serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserializes a hex string into raw bytes, or `null` into `None`.
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: FromHex,
+            <T as FromHex>::Error: fmt::Display,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|data| FromHex::from_hex(data.trim_start_matches("0x")).map_err(Error::custom))
+                .transpose()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -405,6 +558,33 @@ mod rpc_hex_tests {
         assert_eq!(deserialized, test_data)
     }
 
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct OptionTestStruct {
+        #[serde(with = "super::utils::rpc_hex_option")]
+        data: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_option_roundtrip() {
+        for test_data in [
+            OptionTestStruct {
+                data: Some(vec![0x01, 0x02]),
+            },
+            OptionTestStruct { data: None },
+        ] {
+            let serialized = serde_json::to_string(&test_data).unwrap();
+            let deserialized: OptionTestStruct = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, test_data)
+        }
+        assert_eq!(
+            r#"{"data":"0x0102"}"#,
+            serde_json::to_string(&OptionTestStruct {
+                data: Some(vec![0x01, 0x02])
+            })
+            .unwrap()
+        );
+    }
+
     #[test]
     fn test_accepts_hex_without_0x_prefix() {
         let test_data = TestStruct {
@@ -1,5 +1,8 @@
 //! This module defines the trait that is used to build batches of transactions.
 
+use serde::{Deserialize, Serialize};
+
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
 
 /// BlockBuilder trait is responsible for managing mempool and building batches.
@@ -11,4 +14,42 @@ pub trait BatchBuilder {
     /// Builds a new batch out of transactions in mempool.
     /// Logic of which transactions and how many of them is included in batch is up to implementation.
     fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>>;
+
+    /// Builds a new batch out of transactions in mempool, of at most `max_size_bytes` bytes.
+    /// Used by the sequencer to fit the batch to the fees of the DA layer.
+    /// Implementations which can't bound the size of their batches build the same batch as [`BatchBuilder::get_next_blob`].
+    fn get_next_blob_with_max_size(
+        &mut self,
+        max_size_bytes: usize,
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        let _ = max_size_bytes;
+        self.get_next_blob()
+    }
+
+    /// Returns the transactions waiting in the mempool, in the order they would be considered for the next batch.
+    /// Implementations which can't describe their mempool return an empty list.
+    fn mempool_txs(&self) -> Vec<MempoolTx> {
+        Vec::new()
+    }
+}
+
+/// A transaction waiting in the mempool of a [`BatchBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolTx {
+    /// The hash of the raw transaction.
+    pub hash: [u8; 32],
+    /// The address of the sender of the transaction.
+    pub sender: String,
+    /// The nonce lane of the transaction.
+    pub nonce_lane: u64,
+    /// The nonce of the transaction in its lane.
+    pub nonce: u64,
+    /// The gas tip offered to the sequencer, which sets the priority of the transaction.
+    pub gas_tip: u64,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The size of the raw transaction, in bytes.
+    pub size: usize,
+    /// The number of seconds since the transaction was accepted into the mempool.
+    pub age_secs: u64,
 }
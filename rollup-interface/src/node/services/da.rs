@@ -1,7 +1,7 @@
 //! The da module defines traits used by the full node to interact with the DA layer.
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::da::BlockHeaderTrait;
 #[cfg(feature = "native")]
@@ -55,7 +55,9 @@ pub trait DaService: Send + Sync + 'static {
 
     /// Subscribe to finalized headers as they are finalized.
     /// Expect only to receive headers which were finalized after subscription
-    /// Optimized version of `get_last_finalized_block_header`.
+    /// Optimized version of `get_last_finalized_block_header`: the runner reacts to
+    /// the streamed headers, and only falls back to `get_last_finalized_block_header`
+    /// when the stream fails or is closed.
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error>;
 
     /// Fetch the head block of the most popular fork.
@@ -118,6 +120,23 @@ pub trait DaService: Send + Sync + 'static {
 
     /// Fetches all aggregated ZK proofs at a specified block height.
     async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error>;
+
+    /// Estimates the fee to post a blob of `blob_size` bytes with [`DaService::send_transaction`],
+    /// at the current prices of the DA layer.
+    async fn estimate_fee(&self, blob_size: usize) -> Result<Fee, Self::Error>;
+}
+
+/// The fee to post a blob on a DA layer, in the smallest unit of its token,
+/// e.g. utia on Celestia or satoshis on Bitcoin.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Fee(pub u128);
+
+impl core::fmt::Display for Fee {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 /// `SlotData` is the subset of a DA layer block which is stored in the rollup's database.
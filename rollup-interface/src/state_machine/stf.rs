@@ -166,12 +166,20 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
         I: IntoIterator<Item = &'a mut Da::BlobTransaction>;
 }
 
-/// A key-value pair representing a change to the rollup state
+/// A key-value pair representing a change to the rollup state.
+///
+/// Typed events carry the version of the layout of their value, and designate some of their fields
+/// as indexed attributes, so that indexers can filter events without decoding their value.
+/// Ad-hoc string events are at version `0` and have no indexed attributes.
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
 pub struct Event {
     key: EventKey,
     value: EventValue,
+    #[serde(default)]
+    version: u8,
+    #[serde(default)]
+    indexed_attributes: Vec<EventAttribute>,
 }
 
 impl Event {
@@ -180,6 +188,23 @@ impl Event {
         Self {
             key: EventKey(key.as_bytes().to_vec()),
             value: EventValue(value.as_bytes().to_vec()),
+            version: 0,
+            indexed_attributes: Vec::new(),
+        }
+    }
+
+    /// Create a new typed event with the given key, layout version, serialized value and indexed attributes
+    pub fn new_typed(
+        key: &str,
+        version: u8,
+        value: Vec<u8>,
+        indexed_attributes: Vec<EventAttribute>,
+    ) -> Self {
+        Self {
+            key: EventKey(key.as_bytes().to_vec()),
+            value: EventValue(value),
+            version,
+            indexed_attributes,
         }
     }
 
@@ -192,6 +217,44 @@ impl Event {
     pub fn value(&self) -> &EventValue {
         &self.value
     }
+
+    /// Get the version of the layout of the event value
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Get the indexed attributes of the event
+    pub fn indexed_attributes(&self) -> &[EventAttribute] {
+        &self.indexed_attributes
+    }
+}
+
+/// A field of a typed event designated as indexed, with its serialized value.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct EventAttribute {
+    name: EventKey,
+    value: EventValue,
+}
+
+impl EventAttribute {
+    /// Create a new attribute with the given name and serialized value
+    pub fn new(name: &str, value: Vec<u8>) -> Self {
+        Self {
+            name: EventKey(name.as_bytes().to_vec()),
+            value: EventValue(value),
+        }
+    }
+
+    /// Get the attribute name
+    pub fn name(&self) -> &EventKey {
+        &self.name
+    }
+
+    /// Get the attribute value
+    pub fn value(&self) -> &EventValue {
+        &self.value
+    }
 }
 
 /// The key of an event. This is a wrapper around a `Vec<u8>`.
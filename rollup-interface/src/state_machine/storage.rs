@@ -32,4 +32,14 @@ pub trait HierarchicalStorageManager<Da: DaSpec> {
 
     /// Finalizes snapshot on given block header
     fn finalize(&mut self, block_header: &Da::BlockHeader) -> anyhow::Result<()>;
+
+    /// Returns the latest version of the finalized storage. It is recorded before finalizing a block,
+    /// so that the finalization can be reverted with [`Self::rollback_finalized`]
+    /// if the node is killed before the block is committed to the ledger.
+    fn finalized_version(&self) -> anyhow::Result<u64>;
+
+    /// Reverts the finalized storage to `version`, returned by [`Self::finalized_version`],
+    /// by deleting the data of the blocks finalized after it.
+    /// Must be called before any block is being processed.
+    fn rollback_finalized(&mut self, version: u64) -> anyhow::Result<()>;
 }
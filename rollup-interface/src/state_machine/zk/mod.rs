@@ -17,7 +17,8 @@ use serde::{Deserialize, Serialize};
 use crate::da::DaSpec;
 
 /// The ZK proof generated by the [`ZkvmHost::run`] method.
-#[derive(Clone)]
+/// Proofs are posted to the DA layer in their borsh encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum Proof {
     /// Only public input was generated.
     PublicInput(Vec<u8>),
@@ -78,6 +79,13 @@ pub trait Zkvm: Send + Sync {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<StateTransition<Da, Root>, Self::Error>;
+
+    /// Same as [`verify_and_extract_output`](Zkvm::verify_and_extract_output), for the proofs of the
+    /// aggregation program, whose output is an aggregated state transition.
+    fn verify_and_extract_aggregated_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Da, Root>, Self::Error>;
 }
 
 /// A trait which is accessible from within a zkVM program.
@@ -88,6 +96,57 @@ pub trait ZkvmGuest: Zkvm + Send + Sync {
     fn commit<T: Serialize>(&self, item: &T);
 }
 
+/// A zkVM host which can provide proofs to its guest, to be verified inside the zkVM by a [`RecursiveZkvmGuest`].
+pub trait RecursiveZkvmHost: ZkvmHost {
+    /// Give the guest a proof to verify with [`RecursiveZkvmGuest::verify_previous_proof`].
+    /// Fails if the proof can't be verified recursively, e.g. if it only contains public input.
+    fn add_proof_hint(&mut self, proof: &Proof) -> Result<(), anyhow::Error>;
+}
+
+/// A zkVM guest which can verify a previous proof inside the zkVM ("verify-in-circuit"). The proof of the
+/// guest execution is then only valid if the verified proof is, which allows rolling proofs, where the proof
+/// of each slot attests to the validity of the proof of the previous one.
+pub trait RecursiveZkvmGuest: ZkvmGuest {
+    /// Verifies the next proof provided by the host with [`RecursiveZkvmHost::add_proof_hint`] against
+    /// `code_commitment`, and returns its public output.
+    fn verify_previous_proof(
+        &self,
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Same as [`verify_previous_proof`](RecursiveZkvmGuest::verify_previous_proof), except that instead
+    /// of returning the output as a serialized array, it returns a state transition structure.
+    fn verify_previous_transition<Da: DaSpec, Root: Serialize + DeserializeOwned>(
+        &self,
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<StateTransition<Da, Root>, Self::Error>;
+}
+
+/// Verifies, inside the zkVM, the proof of the state transition preceding the one which starts from
+/// `initial_state_root`, and checks that the previous transition ends at this root. This is the first step
+/// of a slot proven as part of rolling proofs. Returns the previous state transition.
+pub fn verify_rolling_proof<G, Da, Root>(
+    guest: &G,
+    code_commitment: &G::CodeCommitment,
+    initial_state_root: &Root,
+) -> Result<StateTransition<Da, Root>, anyhow::Error>
+where
+    G: RecursiveZkvmGuest,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let previous = guest
+        .verify_previous_transition::<Da, Root>(code_commitment)
+        .map_err(|e| anyhow::anyhow!("Failed to verify the previous proof: {:?}", e))?;
+    anyhow::ensure!(
+        &previous.final_state_root == initial_state_root,
+        "The previous state transition ends at {:?}, not at {:?}",
+        previous.final_state_root,
+        initial_state_root
+    );
+    Ok(previous)
+}
+
 /// This trait is implemented on the struct/enum which expresses the validity condition
 pub trait ValidityCondition:
     Serialize
@@ -129,6 +188,84 @@ pub struct StateTransition<Da: DaSpec, Root> {
     pub validity_condition: Da::ValidityCondition,
 }
 
+/// The public output of a proof aggregating the proofs of consecutive slots. It makes a claim that
+/// the state of the rollup has transitioned from `initial_state_root` to `final_state_root` over the slots
+/// from `initial_slot_hash` to `final_slot_hash`, if and only if the combined `validity_condition` is satisfied.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct AggregatedStateTransition<Da: DaSpec, Root> {
+    /// The state of the rollup before the first slot
+    pub initial_state_root: Root,
+    /// The state of the rollup after the last slot
+    pub final_state_root: Root,
+    /// The slot hash of the first slot
+    pub initial_slot_hash: Da::SlotHash,
+    /// The slot hash of the last slot
+    pub final_slot_hash: Da::SlotHash,
+    /// The number of aggregated slots
+    pub slot_count: u64,
+    /// The validity conditions of all the slots, combined with [`ValidityCondition::combine`]
+    pub validity_condition: Da::ValidityCondition,
+}
+
+impl<Da: DaSpec, Root: PartialEq + Debug> AggregatedStateTransition<Da, Root> {
+    /// Aggregates the state transitions of consecutive slots, in order. Fails if there are no transitions,
+    /// if a transition doesn't start from the final state root of the previous one, or if the validity
+    /// conditions can't be combined.
+    pub fn aggregate<H: Digest>(
+        transitions: impl IntoIterator<Item = StateTransition<Da, Root>>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut transitions = transitions.into_iter();
+        let first = transitions
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("At least one state transition must be aggregated"))?;
+
+        let mut aggregated = Self {
+            initial_state_root: first.initial_state_root,
+            final_state_root: first.final_state_root,
+            initial_slot_hash: first.slot_hash.clone(),
+            final_slot_hash: first.slot_hash,
+            slot_count: 1,
+            validity_condition: first.validity_condition,
+        };
+        for transition in transitions {
+            anyhow::ensure!(
+                transition.initial_state_root == aggregated.final_state_root,
+                "The transition of slot {:?} starts from state root {:?}, but the previous one ends at {:?}",
+                transition.slot_hash,
+                transition.initial_state_root,
+                aggregated.final_state_root
+            );
+            aggregated.validity_condition = aggregated
+                .validity_condition
+                .combine::<H>(transition.validity_condition)
+                .map_err(Into::<anyhow::Error>::into)?;
+            aggregated.final_state_root = transition.final_state_root;
+            aggregated.final_slot_hash = transition.slot_hash;
+            aggregated.slot_count += 1;
+        }
+        Ok(aggregated)
+    }
+
+    /// Verifies the serialized proofs of consecutive slots against the `code_commitment` of the rollup,
+    /// and aggregates their state transitions. This is the logic of the aggregation program, which
+    /// commits the returned value as the public output of the recursive proof.
+    pub fn verify_and_aggregate<Vm: Zkvm, H: Digest>(
+        serialized_proofs: &[Vec<u8>],
+        code_commitment: &Vm::CodeCommitment,
+    ) -> Result<Self, anyhow::Error>
+    where
+        Root: Serialize + DeserializeOwned,
+    {
+        let mut transitions = Vec::with_capacity(serialized_proofs.len());
+        for proof in serialized_proofs {
+            let transition = Vm::verify_and_extract_output::<Da, Root>(proof, code_commitment)
+                .map_err(|e| anyhow::anyhow!("Failed to verify a slot proof: {:?}", e))?;
+            transitions.push(transition);
+        }
+        Self::aggregate::<H>(transitions)
+    }
+}
+
 /// This trait expresses that a type can check a validity condition.
 pub trait ValidityConditionChecker<Condition: ValidityCondition>:
     BorshDeserialize + BorshSerialize + Debug
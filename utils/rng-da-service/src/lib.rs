@@ -13,7 +13,7 @@ use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{Address, AddressBech32, EncodeCall, PrivateKey, PublicKey, Spec};
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, DaVerifier, Time};
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, Fee, SlotData};
 
 const DEFAULT_CHAIN_ID: u64 = 0;
 const DEFAULT_GAS_TIP: u64 = 0;
@@ -164,6 +164,10 @@ impl DaService for RngDaService {
     async fn get_aggregated_proofs_at(&self, _height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
         unimplemented!()
     }
+
+    async fn estimate_fee(&self, _blob_size: usize) -> Result<Fee, Self::Error> {
+        Ok(Fee(0))
+    }
 }
 
 pub struct RngDaVerifier;
@@ -232,6 +236,8 @@ pub fn generate_create_token_payload(start_nonce: u64) -> Vec<u8> {
             initial_balance: 100000000,
             minter_address,
             authorized_minters: vec![minter_address],
+            max_supply: None,
+            metadata: None,
         };
     let enc_msg =
         <Runtime<DefaultContext, RngDaSpec> as EncodeCall<Bank<DefaultContext>>>::encode_call(msg);